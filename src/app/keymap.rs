@@ -0,0 +1,266 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use crate::error::{NimbusError, Result};
+
+/// A key, or group of keys, and what it does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub keys: &'static str,
+    pub description: &'static str,
+}
+
+/// The bindings that apply in one view or popup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeySection {
+    pub title: &'static str,
+    pub bindings: &'static [KeyBinding],
+}
+
+const fn bind(keys: &'static str, description: &'static str) -> KeyBinding {
+    KeyBinding { keys, description }
+}
+
+/// Every key binding, grouped by where it applies. The help popup is built
+/// from this, so it must be kept in step with the key handling in `main`.
+pub const KEYMAP: &[KeySection] = &[
+    KeySection {
+        title: "Everywhere",
+        bindings: &[
+            bind("?", "Show or hide this help"),
+            bind("m", "Show or hide the notifications panel"),
+            bind("q", "Quit"),
+            bind("ESC", "Go back, or close the open popup"),
+        ],
+    },
+    KeySection {
+        title: "Dashboard and resource list",
+        bindings: &[
+            bind("Tab / Shift+Tab", "Next or previous provider tab"),
+            bind("1-4", "Jump to AWS, GCP, Azure or All Clouds"),
+            bind("d", "Switch between the dashboard and the resource list"),
+            bind("r", "Refresh resources"),
+            bind("/", "Filter resources"),
+            bind("n", "Create a new instance"),
+            bind("x", "Switch context"),
+            bind("R", "Switch the region of the tab's provider"),
+            bind("P", "Switch AWS account or profile"),
+            bind("c", "Clear the cache for this tab"),
+            bind("C", "Show cache statistics"),
+        ],
+    },
+    KeySection {
+        title: "Dashboard",
+        bindings: &[
+            bind("p", "Choose the cost period"),
+            bind("u", "Service quotas"),
+            bind("w", "Idle and wasted resources"),
+            bind("t", "Tag compliance"),
+            bind("y", "Inventory changes in the last 24 hours"),
+            bind("h", "Action history"),
+        ],
+    },
+    KeySection {
+        title: "Resource list",
+        bindings: &[
+            bind("↑↓", "Select a resource"),
+            bind("PgUp / PgDn", "Move a page up or down"),
+            bind("Home / End", "Jump to the first or last resource"),
+            bind("Enter", "Open details, run a bulk action, or collapse a group"),
+            bind("Space", "Mark or unmark the selected resource"),
+            bind("a", "Mark every filtered resource, or clear the marks"),
+            bind("g", "Cycle the grouping"),
+            bind("v", "Choose and reorder columns"),
+            bind("o", "Open the resource in the cloud console"),
+        ],
+    },
+    KeySection {
+        title: "Resource details",
+        bindings: &[
+            bind("←→", "Switch between the Overview, Metrics, Logs and Raw tabs"),
+            bind("Tab / Shift+Tab", "Move focus between the actions, details and tags"),
+            bind("↑↓ / PgUp PgDn", "Select an action, or scroll the focused pane or tab"),
+            bind("Enter", "Run the selected action"),
+            bind("r", "Reload health, metrics or logs"),
+            bind("o", "Open the resource in the cloud console"),
+        ],
+    },
+    KeySection {
+        title: "Logs tab",
+        bindings: &[
+            bind("↑↓", "Scroll a line"),
+            bind("PgUp / PgDn", "Scroll a page"),
+            bind("f", "Follow new log lines"),
+            bind("r", "Reload"),
+        ],
+    },
+    KeySection {
+        title: "DNS records",
+        bindings: &[bind("←→ / PgUp PgDn", "Previous or next page"), bind("r", "Reload")],
+    },
+    KeySection {
+        title: "Popups",
+        bindings: &[
+            bind("↑↓", "Select an option"),
+            bind("Enter", "Choose the option, or confirm"),
+            bind("Space", "Show or hide a column"),
+            bind("[ ]", "Move a column"),
+            bind("p", "Prune expired cache entries"),
+            bind("Tab", "Toggle the snapshot while typing a name to confirm"),
+        ],
+    },
+];
+
+/// Keys the vim preset adds or moves, shown above the rest of the keymap.
+pub const VIM_KEYMAP: KeySection = KeySection {
+    title: "Vim preset",
+    bindings: &[
+        bind("j / k", "Move down or up"),
+        bind("gg / G", "Jump to the top or bottom"),
+        bind("Ctrl+d / Ctrl+u", "Move half a page down or up"),
+        bind("/", "Search the resource list by name or ID"),
+        bind("n / N", "Next or previous search match"),
+        bind("F", "Filter resources (instead of /)"),
+        bind("z", "Cycle the grouping (instead of g)"),
+        bind("i", "Create a new instance (instead of n)"),
+    ],
+};
+
+/// Sections shown in the help popup for a preset.
+pub fn sections(preset: KeymapPreset) -> Vec<&'static KeySection> {
+    let vim = match preset {
+        KeymapPreset::Vim => Some(&VIM_KEYMAP),
+        KeymapPreset::Default => None,
+    };
+    vim.into_iter().chain(KEYMAP).collect()
+}
+
+/// Number of lines the help popup shows: a title, the bindings and a blank
+/// line per section.
+pub fn help_line_count(preset: KeymapPreset) -> usize {
+    sections(preset).iter().map(|section| section.bindings.len() + 2).sum()
+}
+
+/// Sets of key bindings that can be chosen with `ui.keymap`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeymapPreset {
+    #[default]
+    Default,
+    /// j/k, gg/G, Ctrl+d/u and search with `/`, `n` and `N`
+    Vim,
+}
+
+impl KeymapPreset {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "" | "default" => Ok(KeymapPreset::Default),
+            "vim" => Ok(KeymapPreset::Vim),
+            _ => Err(NimbusError::ConfigError(format!(
+                "Unknown keymap '{}'; expected 'default' or 'vim'",
+                name
+            ))),
+        }
+    }
+}
+
+/// What a key press means once the preset has been applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyCommand {
+    /// Handled as this key of the default bindings
+    Key(KeyCode),
+    /// PageUp or PageDown, moving half as far
+    HalfPage(KeyCode),
+    Search,
+    SearchNext,
+    SearchPrevious,
+}
+
+/// Turns key presses into commands for a preset, remembering a pending `g`
+/// so that `gg` can be recognised.
+#[derive(Debug, Clone, Default)]
+pub struct KeyTranslator {
+    preset: KeymapPreset,
+    pending_g: bool,
+}
+
+impl KeyTranslator {
+    pub fn new(preset: KeymapPreset) -> Self {
+        Self { preset, pending_g: false }
+    }
+
+    /// Returns `None` while waiting for the second key of `gg`.
+    pub fn translate(&mut self, code: KeyCode, modifiers: KeyModifiers) -> Option<KeyCommand> {
+        if self.preset == KeymapPreset::Default {
+            return Some(KeyCommand::Key(code));
+        }
+        if std::mem::take(&mut self.pending_g) && code == KeyCode::Char('g') {
+            return Some(KeyCommand::Key(KeyCode::Home));
+        }
+
+        let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+        let command = match code {
+            KeyCode::Char('d') if ctrl => KeyCommand::HalfPage(KeyCode::PageDown),
+            KeyCode::Char('u') if ctrl => KeyCommand::HalfPage(KeyCode::PageUp),
+            KeyCode::Char('g') => {
+                self.pending_g = true;
+                return None;
+            }
+            KeyCode::Char('j') => KeyCommand::Key(KeyCode::Down),
+            KeyCode::Char('k') => KeyCommand::Key(KeyCode::Up),
+            KeyCode::Char('G') => KeyCommand::Key(KeyCode::End),
+            KeyCode::Char('/') => KeyCommand::Search,
+            KeyCode::Char('n') => KeyCommand::SearchNext,
+            KeyCode::Char('N') => KeyCommand::SearchPrevious,
+            // The default keys these replace
+            KeyCode::Char('F') => KeyCommand::Key(KeyCode::Char('/')),
+            KeyCode::Char('z') => KeyCommand::Key(KeyCode::Char('g')),
+            KeyCode::Char('i') => KeyCommand::Key(KeyCode::Char('n')),
+            code => KeyCommand::Key(code),
+        };
+        Some(command)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keymap_has_no_duplicate_keys() {
+        for section in sections(KeymapPreset::Vim) {
+            assert!(!section.bindings.is_empty(), "{} has no bindings", section.title);
+            for (i, binding) in section.bindings.iter().enumerate() {
+                assert!(
+                    section.bindings[i + 1..].iter().all(|other| other.keys != binding.keys),
+                    "{} is bound twice in {}",
+                    binding.keys,
+                    section.title
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_vim_translation() {
+        let none = KeyModifiers::NONE;
+        let mut default = KeyTranslator::new(KeymapPreset::Default);
+        assert_eq!(default.translate(KeyCode::Char('j'), none), Some(KeyCommand::Key(KeyCode::Char('j'))));
+
+        let mut vim = KeyTranslator::new(KeymapPreset::parse("Vim").unwrap());
+        assert_eq!(vim.translate(KeyCode::Char('j'), none), Some(KeyCommand::Key(KeyCode::Down)));
+        assert_eq!(vim.translate(KeyCode::Char('g'), none), None);
+        assert_eq!(vim.translate(KeyCode::Char('g'), none), Some(KeyCommand::Key(KeyCode::Home)));
+        assert_eq!(
+            vim.translate(KeyCode::Char('d'), KeyModifiers::CONTROL),
+            Some(KeyCommand::HalfPage(KeyCode::PageDown))
+        );
+        assert_eq!(vim.translate(KeyCode::Char('N'), none), Some(KeyCommand::SearchPrevious));
+        assert_eq!(vim.translate(KeyCode::Char('z'), none), Some(KeyCommand::Key(KeyCode::Char('g'))));
+
+        // A lone g is dropped rather than delaying the next key
+        assert_eq!(vim.translate(KeyCode::Char('g'), none), None);
+        assert_eq!(vim.translate(KeyCode::Char('k'), none), Some(KeyCommand::Key(KeyCode::Up)));
+
+        assert!(KeymapPreset::parse("emacs").is_err());
+        assert_eq!(help_line_count(KeymapPreset::Vim), help_line_count(KeymapPreset::Default) + 10);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod keymap;
 pub mod state;
 
-pub use state::{AppState, InputMode, TabIndex, ViewMode};
\ No newline at end of file
+pub use state::{AccountChoice, AccountPicker, AppState, CreateStep, CreateWizard, DetailPane, DetailTab, GroupBy, ImageJob, InputMode, ListColumn, ListRow, ListingStatus, OperationJob, RefreshProgress, RegionPicker, TabIndex, TagEditor, ViewMode};
\ No newline at end of file
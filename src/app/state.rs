@@ -1,5 +1,15 @@
-use crate::core::{CloudProvider, CloudResource};
+use crate::cache::CacheStats;
+use crate::config::AwsConfig;
+use crate::core::{
+    parse_tag_list, AccountIdentity, Action, ActionRecord, ActionRequest, Budget, CloudProvider, CloudResource,
+    CommitmentCoverage, CostAlert, CostAlertPolicy, CostAnomaly, CostFeature, CostForecast, CostPeriod, CostPoint, CostSnapshot, CreateOptions,
+    CreateSpec, DnsRecord, InventoryChange, ListingEvent, LogEntry, MetricSeries, ModifyOption, OperationHandle, OperationStatus,
+    ProviderCapabilities, Quota, ResourceHealth, ResourceType, TagChanges, TagPolicy, TargetGroupHealth, WasteFinding,
+};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use tokio::sync::mpsc::{self, error::TryRecvError};
+use tokio::sync::oneshot;
 use tokio::sync::RwLock;
 use chrono::{DateTime, Utc};
 
@@ -73,34 +83,916 @@ pub enum ViewMode {
     Dashboard,
     ResourceList,
     ResourceDetail,
+    Records,
+    Quotas,
+    Waste,
+    Compliance,
+    Changes,
+    History,
+}
+
+/// Maximum number of log lines kept in memory by the log viewer.
+pub const MAX_LOG_LINES: usize = 2000;
+
+/// Maximum number of messages kept in the notifications panel.
+pub const MAX_NOTIFICATIONS: usize = 500;
+
+/// Number of DNS records shown per page in the records view.
+pub const RECORDS_PAGE_SIZE: usize = 20;
+
+/// How long fetched cost history is reused before fetching it again.
+pub const COST_HISTORY_TTL_HOURS: i64 = 6;
+
+/// Days of cost snapshots charted on the dashboard.
+pub const COST_TREND_DAYS: i64 = 30;
+
+/// Sub-views available within the resource detail view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetailTab {
+    Overview,
+    Metrics,
+    Logs,
+    /// Every field of the resource as JSON
+    Raw,
+}
+
+impl DetailTab {
+    pub fn all() -> Vec<DetailTab> {
+        vec![DetailTab::Overview, DetailTab::Metrics, DetailTab::Logs, DetailTab::Raw]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DetailTab::Overview => "Overview",
+            DetailTab::Metrics => "Metrics",
+            DetailTab::Logs => "Logs",
+            DetailTab::Raw => "Raw",
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        match self {
+            DetailTab::Overview => 0,
+            DetailTab::Metrics => 1,
+            DetailTab::Logs => 2,
+            DetailTab::Raw => 3,
+        }
+    }
+
+    pub fn next(&self) -> DetailTab {
+        let all = Self::all();
+        all[(self.index() + 1) % all.len()]
+    }
+}
+
+/// The pane of the detail view that Up and Down act on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetailPane {
+    Actions,
+    Metadata,
+    Tags,
+}
+
+impl DetailPane {
+    pub fn next(&self) -> DetailPane {
+        match self {
+            DetailPane::Actions => DetailPane::Metadata,
+            DetailPane::Metadata => DetailPane::Tags,
+            DetailPane::Tags => DetailPane::Actions,
+        }
+    }
+
+    pub fn prev(&self) -> DetailPane {
+        match self {
+            DetailPane::Actions => DetailPane::Tags,
+            DetailPane::Metadata => DetailPane::Actions,
+            DetailPane::Tags => DetailPane::Metadata,
+        }
+    }
+}
+
+/// What the resource list is grouped by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroupBy {
+    Type,
+    Region,
+    Account,
+    /// The value of this tag key
+    Tag(String),
+}
+
+impl GroupBy {
+    pub fn label(&self) -> String {
+        match self {
+            GroupBy::Type => "type".to_string(),
+            GroupBy::Region => "region".to_string(),
+            GroupBy::Account => "account".to_string(),
+            GroupBy::Tag(key) => format!("tag '{}'", key),
+        }
+    }
+}
+
+/// A line of the grouped resource list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListRow {
+    /// A section header with the number of resources in the group and
+    /// their combined monthly cost
+    Group { name: String, count: usize, cost: f64, collapsed: bool },
+    /// Index into the resources
+    Resource(usize),
+}
+
+/// A column of the resource list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ListColumn {
+    Type,
+    Name,
+    Id,
+    State,
+    Health,
+    Region,
+    Provider,
+    Account,
+    Tags,
+    Cost,
+}
+
+impl ListColumn {
+    pub fn all() -> Vec<ListColumn> {
+        vec![
+            ListColumn::Type,
+            ListColumn::Name,
+            ListColumn::Id,
+            ListColumn::State,
+            ListColumn::Health,
+            ListColumn::Region,
+            ListColumn::Provider,
+            ListColumn::Account,
+            ListColumn::Tags,
+            ListColumn::Cost,
+        ]
+    }
+
+    /// Columns shown when the config does not list any.
+    pub fn defaults() -> Vec<ListColumn> {
+        Self::all()
+            .into_iter()
+            .filter(|column| !matches!(column, ListColumn::Provider | ListColumn::Tags))
+            .collect()
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ListColumn::Type => "Type",
+            ListColumn::Name => "Name",
+            ListColumn::Id => "ID",
+            ListColumn::State => "State",
+            ListColumn::Health => "Health",
+            ListColumn::Region => "Region",
+            ListColumn::Provider => "Provider",
+            ListColumn::Account => "Account",
+            ListColumn::Tags => "Tags",
+            ListColumn::Cost => "Cost/Month",
+        }
+    }
+
+    /// Parses a column name from the config, such as `"id"` or `"cost"`.
+    pub fn parse(key: &str) -> Option<ListColumn> {
+        match key.trim().to_lowercase().as_str() {
+            "type" => Some(ListColumn::Type),
+            "name" => Some(ListColumn::Name),
+            "id" => Some(ListColumn::Id),
+            "state" => Some(ListColumn::State),
+            "health" => Some(ListColumn::Health),
+            "region" => Some(ListColumn::Region),
+            "provider" => Some(ListColumn::Provider),
+            "account" => Some(ListColumn::Account),
+            "tags" => Some(ListColumn::Tags),
+            "cost" | "cost/month" => Some(ListColumn::Cost),
+            _ => None,
+        }
+    }
+
+    /// Parses the `columns` setting. An empty list means the defaults.
+    pub fn parse_list(keys: &[String]) -> crate::error::Result<Vec<ListColumn>> {
+        if keys.is_empty() {
+            return Ok(Self::defaults());
+        }
+
+        let mut columns = Vec::new();
+        for key in keys {
+            let column = Self::parse(key)
+                .ok_or_else(|| crate::error::NimbusError::ConfigError(format!("Unknown column '{}' in columns", key)))?;
+            if !columns.contains(&column) {
+                columns.push(column);
+            }
+        }
+        Ok(columns)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InputMode {
     Normal,
     Filter,
+    /// Collecting text input for an action (e.g., an image name)
+    Prompt,
+    /// Typing a search that moves the selection without filtering
+    Search,
+}
+
+/// Pending tag edits for the selected resource.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagEditor {
+    original: HashMap<String, String>,
+    /// Edited tags, sorted by key for display
+    pub tags: Vec<(String, String)>,
+    pub selected: usize,
+}
+
+impl TagEditor {
+    pub fn new(original: HashMap<String, String>) -> Self {
+        let mut tags: Vec<(String, String)> = original
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        tags.sort();
+
+        Self {
+            original,
+            tags,
+            selected: 0,
+        }
+    }
+
+    /// Adds a tag or replaces the value of an existing key.
+    pub fn set(&mut self, key: String, value: String) {
+        match self.tags.iter().position(|(k, _)| *k == key) {
+            Some(idx) => {
+                self.tags[idx].1 = value;
+                self.selected = idx;
+            }
+            None => {
+                self.tags.push((key.clone(), value));
+                self.tags.sort();
+                self.selected = self.tags.iter().position(|(k, _)| *k == key).unwrap_or(0);
+            }
+        }
+    }
+
+    pub fn remove_selected(&mut self) {
+        if self.selected < self.tags.len() {
+            self.tags.remove(self.selected);
+            if self.selected >= self.tags.len() && self.selected > 0 {
+                self.selected -= 1;
+            }
+        }
+    }
+
+    pub fn next(&mut self) {
+        if !self.tags.is_empty() {
+            self.selected = (self.selected + 1) % self.tags.len();
+        }
+    }
+
+    pub fn prev(&mut self) {
+        if !self.tags.is_empty() {
+            self.selected = if self.selected == 0 {
+                self.tags.len() - 1
+            } else {
+                self.selected - 1
+            };
+        }
+    }
+
+    /// Returns the changes needed to apply the edits.
+    pub fn changes(&self) -> TagChanges {
+        let desired: HashMap<String, String> = self.tags.iter().cloned().collect();
+        TagChanges::diff(&self.original, &desired)
+    }
+}
+
+/// Steps of the resource creation wizard, in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreateStep {
+    Image,
+    InstanceType,
+    KeyPair,
+    Name,
+    Tags,
+    Review,
+}
+
+impl CreateStep {
+    pub fn title(&self) -> &'static str {
+        match self {
+            CreateStep::Image => "Choose an image",
+            CreateStep::InstanceType => "Choose an instance type",
+            CreateStep::KeyPair => "Choose a key pair",
+            CreateStep::Name => "Name the instance",
+            CreateStep::Tags => "Add tags",
+            CreateStep::Review => "Review and launch",
+        }
+    }
+
+    /// Returns true for steps that take typed input through the prompt.
+    pub fn is_text(&self) -> bool {
+        matches!(self, CreateStep::Name | CreateStep::Tags)
+    }
+
+    fn next(self) -> Self {
+        match self {
+            CreateStep::Image => CreateStep::InstanceType,
+            CreateStep::InstanceType => CreateStep::KeyPair,
+            CreateStep::KeyPair => CreateStep::Name,
+            CreateStep::Name => CreateStep::Tags,
+            CreateStep::Tags | CreateStep::Review => CreateStep::Review,
+        }
+    }
+
+    fn prev(self) -> Option<Self> {
+        match self {
+            CreateStep::Image => None,
+            CreateStep::InstanceType => Some(CreateStep::Image),
+            CreateStep::KeyPair => Some(CreateStep::InstanceType),
+            CreateStep::Name => Some(CreateStep::KeyPair),
+            CreateStep::Tags => Some(CreateStep::Name),
+            CreateStep::Review => Some(CreateStep::Tags),
+        }
+    }
+}
+
+/// Choices made so far in the resource creation wizard.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateWizard {
+    /// Provider the resource is launched with
+    pub provider_idx: usize,
+    pub options: CreateOptions,
+    pub step: CreateStep,
+    /// Highlighted entry in the current step's list
+    pub selected: usize,
+    image: usize,
+    instance_type: usize,
+    key_pair: usize,
+    pub name: String,
+    pub tags: HashMap<String, String>,
+}
+
+impl CreateWizard {
+    pub fn new(provider_idx: usize, mut options: CreateOptions) -> Self {
+        options.key_pairs.insert(0, ModifyOption::new("", "None (no SSH access)"));
+        Self {
+            provider_idx,
+            options,
+            step: CreateStep::Image,
+            selected: 0,
+            image: 0,
+            instance_type: 0,
+            key_pair: 0,
+            name: String::new(),
+            tags: HashMap::new(),
+        }
+    }
+
+    /// Returns the list shown for the current step; text steps and the
+    /// review have none.
+    pub fn choices(&self) -> &[ModifyOption] {
+        match self.step {
+            CreateStep::Image => &self.options.images,
+            CreateStep::InstanceType => &self.options.instance_types,
+            CreateStep::KeyPair => &self.options.key_pairs,
+            _ => &[],
+        }
+    }
+
+    pub fn next(&mut self) {
+        let len = self.choices().len();
+        if len > 0 {
+            self.selected = (self.selected + 1) % len;
+        }
+    }
+
+    pub fn prev(&mut self) {
+        let len = self.choices().len();
+        if len > 0 {
+            self.selected = if self.selected == 0 { len - 1 } else { self.selected - 1 };
+        }
+    }
+
+    /// Records the highlighted choice and moves to the next step.
+    pub fn choose(&mut self) {
+        match self.step {
+            CreateStep::Image => self.image = self.selected,
+            CreateStep::InstanceType => self.instance_type = self.selected,
+            CreateStep::KeyPair => self.key_pair = self.selected,
+            _ => {}
+        }
+        self.advance();
+    }
+
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.trim().to_string();
+        self.advance();
+    }
+
+    pub fn set_tags(&mut self, input: &str) {
+        self.tags = parse_tag_list(input);
+        self.advance();
+    }
+
+    /// Goes back one step, highlighting the earlier choice. Returns false
+    /// on the first step.
+    pub fn back(&mut self) -> bool {
+        match self.step.prev() {
+            Some(step) => {
+                self.step = step;
+                self.selected = self.chosen_index();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Tags as typed in the tags prompt, for editing them again.
+    pub fn tags_input(&self) -> String {
+        let mut tags: Vec<String> = self.tags.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        tags.sort();
+        tags.join(", ")
+    }
+
+    pub fn spec(&self) -> CreateSpec {
+        let value = |options: &[ModifyOption], idx: usize| {
+            options.get(idx).map(|o| o.value.clone()).unwrap_or_default()
+        };
+
+        let mut spec = CreateSpec::new(
+            ResourceType::Compute,
+            value(&self.options.images, self.image),
+            value(&self.options.instance_types, self.instance_type),
+        )
+        .with_name(self.name.clone());
+        spec.tags = self.tags.clone();
+
+        let key_pair = value(&self.options.key_pairs, self.key_pair);
+        if !key_pair.is_empty() {
+            spec = spec.with_key_pair(key_pair);
+        }
+        spec
+    }
+
+    /// Estimated monthly cost of the chosen instance type.
+    pub fn monthly_cost(&self) -> Option<f64> {
+        self.options.instance_types.get(self.instance_type)?.monthly_cost
+    }
+
+    /// Describes the choices for the review step, one per line.
+    pub fn review_lines(&self) -> Vec<String> {
+        let label = |options: &[ModifyOption], idx: usize| {
+            options.get(idx).map(|o| o.label.clone()).unwrap_or_default()
+        };
+
+        let mut lines = vec![
+            format!("Image: {}", label(&self.options.images, self.image)),
+            format!("Instance type: {}", label(&self.options.instance_types, self.instance_type)),
+            format!("Key pair: {}", label(&self.options.key_pairs, self.key_pair)),
+        ];
+        if !self.name.is_empty() {
+            lines.push(format!("Name: {}", self.name));
+        }
+        if !self.tags.is_empty() {
+            lines.push(format!("Tags: {}", self.tags_input()));
+        }
+        lines.push(match self.monthly_cost() {
+            Some(cost) => format!("Estimated cost: ${:.2}/month", cost),
+            None => "Estimated cost: unavailable".to_string(),
+        });
+        lines
+    }
+
+    fn advance(&mut self) {
+        self.step = self.step.next();
+        self.selected = self.chosen_index();
+    }
+
+    fn chosen_index(&self) -> usize {
+        match self.step {
+            CreateStep::Image => self.image,
+            CreateStep::InstanceType => self.instance_type,
+            CreateStep::KeyPair => self.key_pair,
+            _ => 0,
+        }
+    }
+}
+
+/// The regions one provider can switch to, with the highlighted one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionPicker {
+    /// Provider whose region is being changed
+    pub provider_idx: usize,
+    pub provider_name: String,
+    pub regions: Vec<String>,
+    pub current: String,
+    pub selected: usize,
+}
+
+impl RegionPicker {
+    /// Creates a picker that starts on the provider's current region.
+    pub fn new(provider_idx: usize, provider_name: impl Into<String>, regions: Vec<String>, current: &str) -> Self {
+        let selected = regions.iter().position(|region| region == current).unwrap_or(0);
+        Self {
+            provider_idx,
+            provider_name: provider_name.into(),
+            regions,
+            current: current.to_string(),
+            selected,
+        }
+    }
+
+    pub fn next(&mut self) {
+        if !self.regions.is_empty() {
+            self.selected = (self.selected + 1) % self.regions.len();
+        }
+    }
+
+    pub fn prev(&mut self) {
+        if !self.regions.is_empty() {
+            self.selected = if self.selected == 0 { self.regions.len() - 1 } else { self.selected - 1 };
+        }
+    }
+
+    /// Returns the highlighted region, unless it is the current one.
+    pub fn chosen(&self) -> Option<&str> {
+        self.regions
+            .get(self.selected)
+            .map(String::as_str)
+            .filter(|region| *region != self.current)
+    }
+}
+
+/// An AWS identity the account switcher can move a provider to.
+#[derive(Debug, Clone)]
+pub struct AccountChoice {
+    pub label: String,
+    pub config: AwsConfig,
+    /// Read from `~/.aws/credentials` rather than the config file
+    pub detected: bool,
+}
+
+/// The AWS identities one provider can switch to, with the highlighted one.
+#[derive(Debug, Clone)]
+pub struct AccountPicker {
+    /// Provider whose identity is being changed
+    pub provider_idx: usize,
+    /// Account the provider runs in now
+    pub current: String,
+    pub choices: Vec<AccountChoice>,
+    pub selected: usize,
+}
+
+impl AccountPicker {
+    /// Lists the configured accounts, then every detected profile that none
+    /// of them uses. Detected profiles keep the provider's current region.
+    pub fn new(
+        provider_idx: usize,
+        current: &str,
+        region: &str,
+        configured: &[AwsConfig],
+        profiles: Vec<String>,
+    ) -> Self {
+        let mut choices: Vec<AccountChoice> = configured
+            .iter()
+            .map(|config| AccountChoice {
+                label: config.display_name().to_string(),
+                config: config.clone(),
+                detected: false,
+            })
+            .collect();
+
+        for profile in profiles {
+            let configured = configured
+                .iter()
+                .any(|config| config.profile.as_deref().unwrap_or("default") == profile);
+            if !configured {
+                choices.push(AccountChoice {
+                    label: profile.clone(),
+                    config: AwsConfig {
+                        profile: Some(profile),
+                        region: region.to_string(),
+                        ..AwsConfig::default()
+                    },
+                    detected: true,
+                });
+            }
+        }
+
+        let selected = choices.iter().position(|choice| choice.label == current).unwrap_or(0);
+        Self {
+            provider_idx,
+            current: current.to_string(),
+            choices,
+            selected,
+        }
+    }
+
+    pub fn next(&mut self) {
+        if !self.choices.is_empty() {
+            self.selected = (self.selected + 1) % self.choices.len();
+        }
+    }
+
+    pub fn prev(&mut self) {
+        if !self.choices.is_empty() {
+            self.selected = if self.selected == 0 { self.choices.len() - 1 } else { self.selected - 1 };
+        }
+    }
+
+    /// Returns the highlighted identity, unless it is the current one.
+    pub fn chosen(&self) -> Option<&AccountChoice> {
+        self.choices
+            .get(self.selected)
+            .filter(|choice| choice.label != self.current)
+    }
+}
+
+/// A provider authenticating as another identity in the background.
+struct AccountSwitch {
+    provider_idx: usize,
+    label: String,
+    receiver: oneshot::Receiver<crate::error::Result<Box<dyn CloudProvider>>>,
+}
+
+/// A message shown in the status bar, kept for the notifications panel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub at: DateTime<Utc>,
+    pub message: String,
+    pub is_error: bool,
+}
+
+/// A machine image being created in the background.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageJob {
+    pub image_id: String,
+    pub name: String,
+    /// Index into `providers` of the provider creating the image
+    pub provider_idx: usize,
+    pub state: String,
+}
+
+/// An action that was accepted by the provider and is still taking effect.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationJob {
+    pub handle: OperationHandle,
+    pub resource_name: String,
+    /// Index into `providers` of the provider running the action
+    pub provider_idx: usize,
+    pub status: OperationStatus,
+}
+
+/// Progress reported by the tasks listing each provider's resources.
+enum RefreshEvent {
+    /// A listing by the provider at the given index started or finished
+    Listing(usize, ListingEvent),
+    /// The provider at the given index finished listing, with the error if
+    /// it failed
+    Finished(usize, Option<String>),
+}
+
+/// How far one service's listing in a refresh has got.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListingStatus {
+    Listing,
+    Done,
+    Failed,
+}
+
+/// One provider's progress through the refresh in progress.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefreshProgress {
+    pub provider: String,
+    /// Services the provider has started listing, in the order they started
+    pub listings: Vec<(&'static str, ListingStatus)>,
+    /// `None` while the provider is still listing
+    pub outcome: Option<ListingStatus>,
+}
+
+impl RefreshProgress {
+    pub fn new(provider: impl Into<String>) -> Self {
+        Self {
+            provider: provider.into(),
+            listings: Vec::new(),
+            outcome: None,
+        }
+    }
+
+    /// Records a listing event for one of the provider's services.
+    fn update(&mut self, service: &'static str, status: ListingStatus) {
+        match self.listings.iter_mut().find(|(name, _)| *name == service) {
+            Some(listing) => listing.1 = status,
+            None => self.listings.push((service, status)),
+        }
+    }
+
+    /// Marks the provider finished. Listings it never reported the end of
+    /// take on the provider's outcome.
+    fn finish(&mut self, failed: bool) {
+        let outcome = if failed { ListingStatus::Failed } else { ListingStatus::Done };
+        for listing in &mut self.listings {
+            if listing.1 == ListingStatus::Listing {
+                listing.1 = outcome;
+            }
+        }
+        self.outcome = Some(outcome);
+    }
 }
 
 pub struct AppState {
     pub providers: Vec<Arc<RwLock<Box<dyn CloudProvider>>>>,
     pub active_tab: TabIndex,
     pub resources: Arc<RwLock<Vec<Box<dyn CloudResource>>>>,
+    /// Index into `providers` for each entry in `resources`.
+    pub resource_sources: Vec<usize>,
     pub filtered_resources: Vec<usize>,
     pub selected_index: usize,
+    /// Grouping of the resource list, if any. While set, `selected_index`
+    /// points into `list_rows` rather than `filtered_resources`.
+    pub group_by: Option<GroupBy>,
+    /// Names of the groups whose resources are hidden.
+    pub collapsed_groups: HashSet<String>,
+    pub list_rows: Vec<ListRow>,
     pub filter_text: String,
+    /// IDs the cache search index matched for `filter_text`, which also
+    /// covers tag keys and values.
+    search_hits: Option<HashSet<String>>,
+    /// Cache search still running, with the filter text it was started for.
+    pending_search: Option<(String, oneshot::Receiver<crate::error::Result<Vec<String>>>)>,
+    /// Text searched for with the vim preset's `/`
+    pub search_text: String,
+    /// Row selected when the search was started
+    search_origin: usize,
+    pub keymap: crate::app::keymap::KeymapPreset,
     pub view_mode: ViewMode,
     pub input_mode: InputMode,
     pub loading: bool,
     pub last_refresh: Option<DateTime<Utc>>,
+    /// Batches from the refresh in progress, or `None` when idle.
+    refresh_events: Option<mpsc::UnboundedReceiver<RefreshEvent>>,
+    /// Providers still listing in the current refresh.
+    pub refresh_pending: usize,
+    /// Per-provider progress of the current refresh, in provider order, or
+    /// empty when idle.
+    pub refresh_progress: Vec<RefreshProgress>,
+    /// Resource types each provider failed to list in the last refresh, in
+    /// provider order. The cache keeps what it had of them.
+    pub refresh_failures: Vec<HashSet<ResourceType>>,
+    /// True while the list holds resources loaded from the cache rather
+    /// than listed from the providers.
+    pub showing_cached: bool,
+    /// True when running from the cache without contacting any provider.
+    pub offline: bool,
+    /// Names of the configured contexts, empty when there are none.
+    pub contexts: Vec<String>,
+    /// Context whose accounts are loaded.
+    pub active_context: Option<String>,
+    pub show_context_picker: bool,
+    pub selected_context: usize,
+    refresh_error: Option<String>,
+    /// Resource to re-select once it reappears during a refresh.
+    refresh_selection: Option<String>,
     pub error_message: Option<String>,
     pub success_message: Option<String>,
     pub should_quit: bool,
     pub selected_action: usize,
     pub show_confirmation: bool,
     pub confirmation_message: String,
-    pub last_action: Option<String>,
-    pub last_action_time: Option<DateTime<Utc>>,
+    /// Text one of which must be typed before the confirmation is accepted;
+    /// empty when Enter alone confirms.
+    pub confirm_answers: Vec<String>,
+    pub confirm_input: String,
+    /// Actions run this session, oldest first.
+    pub action_log: Vec<ActionRecord>,
+    /// How many entries at the end of `action_log` are not yet persisted.
+    unsaved_actions: usize,
+    /// Audit log entries shown in the history view, newest first.
+    pub action_history: Vec<ActionRecord>,
     pub cache_enabled: bool, // CHANGES: Added cache awareness
+    pub detail_tab: DetailTab,
+    /// Pane of the overview tab that has focus.
+    pub detail_pane: DetailPane,
+    /// Rows scrolled past at the top of the metadata pane.
+    pub metadata_scroll: usize,
+    /// Tags scrolled past at the top of the tag pane.
+    pub tags_scroll: usize,
+    /// Lines scrolled past at the top of the Raw tab.
+    pub raw_scroll: usize,
+    pub metrics: Vec<MetricSeries>,
+    /// ID of the resource that `metrics` belongs to.
+    pub metrics_resource_id: Option<String>,
+    /// True when `metrics` were read from the cache rather than the provider.
+    pub metrics_cached: bool,
+    pub target_health: Vec<TargetGroupHealth>,
+    /// ID of the load balancer that `target_health` belongs to.
+    pub target_health_resource_id: Option<String>,
+    /// Provider health check results by resource ID.
+    pub resource_health: HashMap<String, ResourceHealth>,
+    pub logs: Vec<LogEntry>,
+    /// When true the log viewer polls for new events and sticks to the end.
+    pub logs_follow: bool,
+    /// Number of lines scrolled up from the newest log line.
+    pub logs_scroll: usize,
+    pub dns_records: Vec<DnsRecord>,
+    /// Zero-based page of `dns_records` shown in the records view.
+    pub records_page: usize,
+    pub modify_options: Vec<ModifyOption>,
+    pub show_modify_picker: bool,
+    pub selected_modify_option: usize,
+    /// Modification awaiting confirmation, applied instead of the selected action.
+    pub pending_modification: Option<ModifyOption>,
+    /// Parameterized action awaiting confirmation, run instead of the selected action.
+    pub pending_request: Option<ActionRequest>,
+    /// IDs of resources marked in the list for a bulk action.
+    pub marked_resources: HashSet<String>,
+    pub bulk_actions: Vec<Action>,
+    pub show_bulk_picker: bool,
+    pub selected_bulk_action: usize,
+    /// Every resource list column in display order, and whether it is shown.
+    pub columns: Vec<(ListColumn, bool)>,
+    pub show_column_picker: bool,
+    pub selected_column: usize,
+    /// Action awaiting confirmation that runs on every marked resource.
+    pub pending_bulk_action: Option<Action>,
+    /// Whether terminations must be preceded by a snapshot.
+    pub require_snapshot: bool,
+    /// Snapshot choice for the pending termination, or `None` if not offered.
+    pub snapshot_choice: Option<bool>,
+    /// Whether confirming should remove termination protection first.
+    pub pending_unprotect: bool,
+    /// Dependencies that would make the pending termination fail. The
+    /// confirmation cannot be accepted while any are listed.
+    pub deletion_blockers: Vec<String>,
+    pub prompt_title: String,
+    pub prompt_input: String,
+    /// Action that receives the prompt input when submitted.
+    pub prompt_action: Option<Action>,
+    pub image_jobs: Vec<ImageJob>,
+    pub operations: Vec<OperationJob>,
+    pub tag_editor: Option<TagEditor>,
+    pub create_wizard: Option<CreateWizard>,
+    pub region_picker: Option<RegionPicker>,
+    pub account_picker: Option<AccountPicker>,
+    account_switch: Option<AccountSwitch>,
+    /// Authenticated identity of each provider that reports one.
+    pub identities: Vec<AccountIdentity>,
+    /// Account name of each provider, by index into `providers`.
+    pub account_names: Vec<String>,
+    /// What each provider supports, in the same order as `providers`.
+    pub capabilities: Vec<ProviderCapabilities>,
+    /// Daily spend across all providers, oldest first.
+    pub cost_history: Vec<CostPoint>,
+    pub cost_history_loaded_at: Option<DateTime<Utc>>,
+    /// Daily snapshots of the estimated monthly cost, oldest first.
+    pub cost_snapshots: Vec<CostSnapshot>,
+    /// Period the daily spend covers, chosen in the period picker.
+    pub cost_period: CostPeriod,
+    pub show_period_picker: bool,
+    /// Highlighted entry: a preset, or the custom range after the presets
+    pub selected_period: usize,
+    /// Custom range being typed, while the picker is in text entry
+    pub period_input: Option<String>,
+    /// Cost-allocation tag keys to break spend down by.
+    pub cost_tag_keys: Vec<String>,
+    /// This month's spend per tag key, as (value, cost) sorted by cost.
+    pub cost_by_tag: Vec<(String, Vec<(String, f64)>)>,
+    /// Reservation and savings plan coverage per provider, by provider name.
+    pub commitments: Vec<(String, CommitmentCoverage)>,
+    pub budgets: Vec<Budget>,
+    /// Projected end-of-month spend across providers that forecast costs.
+    pub cost_forecast: Option<CostForecast>,
+    /// Active cost anomalies across providers, largest impact first.
+    pub anomalies: Vec<CostAnomaly>,
+    /// Service quotas per provider, by provider name, most used first.
+    pub quotas: Vec<(String, Quota)>,
+    /// Idle resources per provider, by provider name, costliest first.
+    pub waste: Vec<(String, WasteFinding)>,
+    /// Tags every resource is expected to carry.
+    pub tag_policy: TagPolicy,
+    /// Cost limits from the `[alerts]` config.
+    pub cost_alert_policy: CostAlertPolicy,
+    /// Limits currently crossed, kept until a refresh clears them.
+    pub cost_alerts: Vec<CostAlert>,
+    /// Cache statistics, while the cache popup is open.
+    pub cache_stats: Option<CacheStats>,
+    pub show_help: bool,
+    /// Lines scrolled past at the top of the help popup.
+    pub help_scroll: usize,
+    /// Every message and action outcome of the session, oldest first.
+    pub notifications: Vec<Notification>,
+    pub show_notifications: bool,
+    /// Notifications scrolled past, counting from the newest.
+    pub notifications_scroll: usize,
+    /// Inventory changes between the refresh recorded at the given time
+    /// and the latest one, or `None` until two refreshes are recorded.
+    pub inventory_changes: Option<(DateTime<Utc>, Vec<InventoryChange>)>,
 }
 
 impl AppState {
@@ -109,125 +1001,1216 @@ impl AppState {
             providers: Vec::new(),
             active_tab: TabIndex::AWS,
             resources: Arc::new(RwLock::new(Vec::new())),
+            resource_sources: Vec::new(),
             filtered_resources: Vec::new(),
             selected_index: 0,
+            group_by: None,
+            collapsed_groups: HashSet::new(),
+            list_rows: Vec::new(),
             filter_text: String::new(),
+            search_hits: None,
+            pending_search: None,
+            search_text: String::new(),
+            search_origin: 0,
+            keymap: crate::app::keymap::KeymapPreset::Default,
             view_mode: ViewMode::Dashboard,
             input_mode: InputMode::Normal,
             loading: false,
             last_refresh: None,
+            refresh_events: None,
+            refresh_pending: 0,
+            refresh_progress: Vec::new(),
+            refresh_failures: Vec::new(),
+            showing_cached: false,
+            offline: false,
+            contexts: Vec::new(),
+            active_context: None,
+            show_context_picker: false,
+            selected_context: 0,
+            refresh_error: None,
+            refresh_selection: None,
             error_message: None,
             success_message: None,
             should_quit: false,
             selected_action: 0,
             show_confirmation: false,
             confirmation_message: String::new(),
-            last_action: None,
-            last_action_time: None,
+            confirm_answers: Vec::new(),
+            confirm_input: String::new(),
+            action_log: Vec::new(),
+            unsaved_actions: 0,
+            action_history: Vec::new(),
             cache_enabled: false, // CHANGES: Initialize cache_enabled
+            detail_tab: DetailTab::Overview,
+            detail_pane: DetailPane::Actions,
+            metadata_scroll: 0,
+            tags_scroll: 0,
+            raw_scroll: 0,
+            metrics: Vec::new(),
+            metrics_resource_id: None,
+            metrics_cached: false,
+            target_health: Vec::new(),
+            target_health_resource_id: None,
+            resource_health: HashMap::new(),
+            logs: Vec::new(),
+            logs_follow: true,
+            logs_scroll: 0,
+            dns_records: Vec::new(),
+            records_page: 0,
+            modify_options: Vec::new(),
+            show_modify_picker: false,
+            selected_modify_option: 0,
+            pending_modification: None,
+            pending_request: None,
+            marked_resources: HashSet::new(),
+            bulk_actions: Vec::new(),
+            show_bulk_picker: false,
+            selected_bulk_action: 0,
+            columns: column_layout(&ListColumn::defaults()),
+            show_column_picker: false,
+            selected_column: 0,
+            pending_bulk_action: None,
+            require_snapshot: false,
+            snapshot_choice: None,
+            pending_unprotect: false,
+            deletion_blockers: Vec::new(),
+            prompt_title: String::new(),
+            prompt_input: String::new(),
+            prompt_action: None,
+            image_jobs: Vec::new(),
+            operations: Vec::new(),
+            tag_editor: None,
+            create_wizard: None,
+            region_picker: None,
+            account_picker: None,
+            account_switch: None,
+            identities: Vec::new(),
+            account_names: Vec::new(),
+            capabilities: Vec::new(),
+            cost_history: Vec::new(),
+            cost_history_loaded_at: None,
+            cost_snapshots: Vec::new(),
+            cost_period: CostPeriod::Last30Days,
+            show_period_picker: false,
+            selected_period: 0,
+            period_input: None,
+            cost_tag_keys: Vec::new(),
+            cost_by_tag: Vec::new(),
+            commitments: Vec::new(),
+            budgets: Vec::new(),
+            cost_forecast: None,
+            anomalies: Vec::new(),
+            quotas: Vec::new(),
+            waste: Vec::new(),
+            tag_policy: TagPolicy::default(),
+            cost_alert_policy: CostAlertPolicy::default(),
+            cost_alerts: Vec::new(),
+            cache_stats: None,
+            show_help: false,
+            help_scroll: 0,
+            notifications: Vec::new(),
+            show_notifications: false,
+            notifications_scroll: 0,
+            inventory_changes: None,
+        }
+    }
+
+    pub fn with_cost_tags(mut self, tag_keys: Vec<String>) -> Self {
+        self.cost_tag_keys = tag_keys;
+        self
+    }
+
+    pub fn with_tag_policy(mut self, policy: TagPolicy) -> Self {
+        self.tag_policy = policy;
+        self
+    }
+
+    pub fn with_cost_alert_policy(mut self, policy: CostAlertPolicy) -> Self {
+        self.cost_alert_policy = policy;
+        self
+    }
+
+    /// Checks the listed resources and the forecast against the cost limits.
+    pub async fn update_cost_alerts(&mut self) {
+        if self.cost_alert_policy.is_empty() {
+            return;
+        }
+        let resources = self.resources.read().await;
+        self.cost_alerts = self.cost_alert_policy.evaluate(&resources, self.cost_forecast);
+    }
+
+    pub fn with_providers(mut self, providers: Vec<Arc<RwLock<Box<dyn CloudProvider>>>>) -> Self {
+        self.providers = providers;
+        self
+    }
+
+    /// Collects the identity and account name of each provider for display.
+    pub async fn load_identities(&mut self) {
+        let mut identities = Vec::new();
+        let mut account_names = Vec::new();
+        for provider in &self.providers {
+            let provider = provider.read().await;
+            if let Some(identity) = provider.identity() {
+                identities.push(identity);
+            }
+            account_names.push(provider.account_name().to_string());
+        }
+        self.identities = identities;
+        self.account_names = account_names;
+    }
+
+    /// Returns the name of the account a resource was listed from.
+    pub fn account_for_resource(&self, resource_idx: usize) -> Option<&str> {
+        self.resource_sources
+            .get(resource_idx)
+            .and_then(|&provider_idx| self.account_names.get(provider_idx))
+            .map(String::as_str)
+    }
+
+    /// Collects what each provider supports, so views and actions it cannot
+    /// serve are left out.
+    pub async fn load_capabilities(&mut self) {
+        let mut capabilities = Vec::new();
+        for provider in &self.providers {
+            capabilities.push(provider.read().await.capabilities());
+        }
+        self.capabilities = capabilities;
+    }
+
+    /// Returns the capabilities of a provider by index. Providers that have
+    /// not been asked yet are assumed to support everything.
+    pub fn provider_capabilities(&self, provider_idx: usize) -> ProviderCapabilities {
+        self.capabilities.get(provider_idx).cloned().unwrap_or_default()
+    }
+
+    /// Returns the capabilities of the provider a resource was listed from.
+    pub fn capabilities_for_resource(&self, resource_idx: usize) -> ProviderCapabilities {
+        match self.resource_sources.get(resource_idx) {
+            Some(&provider_idx) => self.provider_capabilities(provider_idx),
+            None => ProviderCapabilities::default(),
+        }
+    }
+
+    /// Returns the actions to offer for a resource: those it supports that
+    /// its provider can carry out.
+    pub fn actions_for(&self, resource_idx: usize, resource: &dyn CloudResource) -> Vec<Action> {
+        self.capabilities_for_resource(resource_idx)
+            .filter_actions(resource.supported_actions())
+    }
+
+    /// Returns true if any provider reports the given cost data.
+    pub fn supports_cost(&self, feature: CostFeature) -> bool {
+        (0..self.providers.len()).any(|idx| self.provider_capabilities(idx).supports_cost(feature))
+    }
+
+    /// Returns true if any provider can carry out the action.
+    pub fn any_provider_supports(&self, action: Action) -> bool {
+        (0..self.providers.len()).any(|idx| self.provider_capabilities(idx).supports_action(action))
+    }
+
+    /// Returns the detail tabs that apply to the selected resource; the
+    /// metrics tab is left out when its provider has no metrics.
+    pub fn detail_tabs(&self) -> Vec<DetailTab> {
+        let (metrics, logs) = match self.get_selected_resource_index() {
+            Some(resource_idx) => {
+                let capabilities = self.capabilities_for_resource(resource_idx);
+                (capabilities.metrics, capabilities.actions.contains(&Action::ViewLogs))
+            }
+            None => (true, true),
+        };
+        DetailTab::all()
+            .into_iter()
+            .filter(|tab| match tab {
+                DetailTab::Metrics => metrics,
+                DetailTab::Logs => logs,
+                _ => true,
+            })
+            .collect()
+    }
+
+    /// Summarizes the authenticated accounts for the header. A single account
+    /// also shows the principal ARN.
+    pub fn identity_summary(&self) -> Option<String> {
+        match self.identities.as_slice() {
+            [] => None,
+            [identity] => Some(format!("{} · {}", identity.account_label(), identity.arn)),
+            identities => Some(
+                identities
+                    .iter()
+                    .map(|i| i.account_label())
+                    .collect::<Vec<_>>()
+                    .join(" | "),
+            ),
+        }
+    }
+
+    // CHANGES: Added method to enable cache awareness
+    pub fn with_cache_enabled(mut self, enabled: bool) -> Self {
+        self.cache_enabled = enabled;
+        self
+    }
+
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Shows `visible` in the resource list in that order, and offers the
+    /// other columns hidden after them.
+    pub fn with_columns(mut self, visible: &[ListColumn]) -> Self {
+        self.columns = column_layout(visible);
+        self
+    }
+
+    pub fn with_keymap(mut self, keymap: crate::app::keymap::KeymapPreset) -> Self {
+        self.keymap = keymap;
+        self
+    }
+
+    pub fn with_contexts(mut self, contexts: Vec<String>, active: Option<String>) -> Self {
+        self.contexts = contexts;
+        self.active_context = active;
+        self
+    }
+
+    pub fn with_snapshot_before_terminate(mut self, required: bool) -> Self {
+        self.require_snapshot = required;
+        self
+    }
+
+    // CHANGES: Added method to check if using cached data
+    pub fn is_using_cache(&self) -> bool {
+        self.cache_enabled && self.last_refresh.is_some()
+    }
+
+    // CHANGES: Added method to get cache age display string
+    pub fn cache_age_display(&self) -> Option<String> {
+        if !self.cache_enabled {
+            return None;
+        }
+
+        self.last_refresh.map(|refresh_time| {
+            let age = Utc::now().signed_duration_since(refresh_time);
+            
+            if age.num_minutes() < 1 {
+                "just now".to_string()
+            } else if age.num_minutes() < 60 {
+                format!("{}m ago", age.num_minutes())
+            } else if age.num_hours() < 24 {
+                format!("{}h ago", age.num_hours())
+            } else {
+                format!("{}d ago", age.num_days())
+            }
+        })
+    }
+
+    pub fn next_tab(&mut self) {
+        self.active_tab = self.active_tab.next();
+    }
+
+    pub fn prev_tab(&mut self) {
+        self.active_tab = self.active_tab.prev();
+    }
+
+    pub fn set_tab(&mut self, tab: TabIndex) {
+        self.active_tab = tab;
+    }
+
+    pub fn toggle_view_mode(&mut self) {
+        self.view_mode = match self.view_mode {
+            ViewMode::Dashboard => ViewMode::ResourceList,
+            ViewMode::ResourceList => ViewMode::Dashboard,
+            ViewMode::ResourceDetail => ViewMode::ResourceList,
+            ViewMode::Records => ViewMode::ResourceDetail,
+            ViewMode::Quotas
+            | ViewMode::Waste
+            | ViewMode::Compliance
+            | ViewMode::Changes
+            | ViewMode::History => ViewMode::Dashboard,
+        };
+    }
+
+    pub fn enter_detail_view(&mut self) {
+        if self.get_selected_resource_index().is_some() {
+            self.view_mode = ViewMode::ResourceDetail;
+            self.selected_action = 0;
+            self.reset_detail_panes();
+        }
+    }
+
+    pub fn exit_detail_view(&mut self) {
+        self.view_mode = ViewMode::ResourceList;
+        self.selected_action = 0;
+        self.show_confirmation = false;
+        self.set_detail_tab(DetailTab::Overview);
+    }
+
+    fn reset_detail_panes(&mut self) {
+        self.detail_pane = DetailPane::Actions;
+        self.metadata_scroll = 0;
+        self.tags_scroll = 0;
+        self.raw_scroll = 0;
+    }
+
+    /// Switches the detail view to `tab`, scrolled to the top. Logs are
+    /// dropped when leaving the Logs tab.
+    pub fn set_detail_tab(&mut self, tab: DetailTab) {
+        if self.detail_tab == DetailTab::Logs && tab != DetailTab::Logs {
+            self.logs.clear();
+        }
+        self.detail_tab = tab;
+        self.reset_detail_panes();
+    }
+
+    pub fn next_detail_pane(&mut self) {
+        self.detail_pane = self.detail_pane.next();
+    }
+
+    pub fn prev_detail_pane(&mut self) {
+        self.detail_pane = self.detail_pane.prev();
+    }
+
+    /// Number of rows the focused pane holds for `resource`: the basic
+    /// fields, health, and the resource's own details, or its tags.
+    pub fn detail_pane_len(&self, resource: &dyn CloudResource) -> usize {
+        match self.detail_pane {
+            DetailPane::Metadata => 4 + self.health_of(resource).is_some() as usize + resource.details().len(),
+            DetailPane::Tags => resource.tags().len(),
+            DetailPane::Actions => 0,
+        }
+    }
+
+    /// Scrolls the focused pane up by `lines`.
+    pub fn scroll_detail_pane_up(&mut self, lines: usize) {
+        match self.detail_pane {
+            DetailPane::Metadata => self.metadata_scroll = self.metadata_scroll.saturating_sub(lines),
+            DetailPane::Tags => self.tags_scroll = self.tags_scroll.saturating_sub(lines),
+            DetailPane::Actions => {}
+        }
+    }
+
+    /// Scrolls the focused pane down by `lines`, stopping at its last row
+    /// given it holds `len` rows.
+    pub fn scroll_detail_pane_down(&mut self, lines: usize, len: usize) {
+        let last = len.saturating_sub(1);
+        match self.detail_pane {
+            DetailPane::Metadata => self.metadata_scroll = (self.metadata_scroll + lines).min(last),
+            DetailPane::Tags => self.tags_scroll = (self.tags_scroll + lines).min(last),
+            DetailPane::Actions => {}
+        }
+    }
+
+    pub fn next_detail_tab(&mut self) {
+        let tabs = self.detail_tabs();
+        let tab = match tabs.iter().position(|tab| *tab == self.detail_tab) {
+            Some(idx) => tabs[(idx + 1) % tabs.len()],
+            None => DetailTab::Overview,
+        };
+        self.set_detail_tab(tab);
+    }
+
+    pub fn prev_detail_tab(&mut self) {
+        let tabs = self.detail_tabs();
+        let tab = match tabs.iter().position(|tab| *tab == self.detail_tab) {
+            Some(idx) => tabs[(idx + tabs.len() - 1) % tabs.len()],
+            None => DetailTab::Overview,
+        };
+        self.set_detail_tab(tab);
+    }
+
+    /// Returns true if metrics need to be fetched for the given resource.
+    pub fn metrics_stale_for(&self, resource_id: &str) -> bool {
+        self.metrics_resource_id.as_deref() != Some(resource_id)
+    }
+
+    /// Returns true if target health needs to be fetched for the given resource.
+    pub fn target_health_stale_for(&self, resource_id: &str) -> bool {
+        self.target_health_resource_id.as_deref() != Some(resource_id)
+    }
+
+    pub fn set_target_health(&mut self, resource_id: String, health: Vec<TargetGroupHealth>) {
+        self.target_health = health;
+        self.target_health_resource_id = Some(resource_id);
+    }
+
+    /// Returns the worse of the provider's health check and what the
+    /// resource reports about itself.
+    pub fn health_of(&self, resource: &dyn CloudResource) -> Option<ResourceHealth> {
+        match (self.resource_health.get(resource.id()).cloned(), resource.health()) {
+            (Some(checked), Some(own)) => Some(checked.worst(own)),
+            (checked, own) => checked.or(own),
+        }
+    }
+
+    /// Returns true if the cost history should be fetched again. Billing
+    /// data updates a few times a day, so a few hours' staleness is fine.
+    pub fn cost_history_stale(&self) -> bool {
+        match self.cost_history_loaded_at {
+            Some(loaded_at) => Utc::now() - loaded_at > chrono::Duration::hours(COST_HISTORY_TTL_HOURS),
+            None => true,
+        }
+    }
+
+    pub fn set_cost_history(&mut self, history: Vec<CostPoint>) {
+        self.cost_history = history;
+        self.cost_history_loaded_at = Some(Utc::now());
+    }
+
+    /// Opens the period picker with the current period highlighted.
+    pub fn open_period_picker(&mut self) {
+        let presets = CostPeriod::presets();
+        self.selected_period = presets
+            .iter()
+            .position(|p| *p == self.cost_period)
+            .unwrap_or(presets.len());
+        self.period_input = None;
+        self.show_period_picker = true;
+    }
+
+    pub fn close_period_picker(&mut self) {
+        self.show_period_picker = false;
+        self.period_input = None;
+    }
+
+    pub fn show_cache_stats(&mut self, stats: CacheStats) {
+        self.cache_stats = Some(stats);
+    }
+
+    pub fn close_cache_stats(&mut self) {
+        self.cache_stats = None;
+    }
+
+    pub fn open_help(&mut self) {
+        self.show_help = true;
+        self.help_scroll = 0;
+    }
+
+    pub fn close_help(&mut self) {
+        self.show_help = false;
+    }
+
+    pub fn scroll_help_up(&mut self, lines: usize) {
+        self.help_scroll = self.help_scroll.saturating_sub(lines);
+    }
+
+    pub fn scroll_help_down(&mut self, lines: usize) {
+        let last = crate::app::keymap::help_line_count(self.keymap).saturating_sub(1);
+        self.help_scroll = (self.help_scroll + lines).min(last);
+    }
+
+    pub fn toggle_notifications(&mut self) {
+        self.show_notifications = !self.show_notifications;
+        self.notifications_scroll = 0;
+    }
+
+    pub fn scroll_notifications_up(&mut self, lines: usize) {
+        self.notifications_scroll = self.notifications_scroll.saturating_sub(lines);
+    }
+
+    pub fn scroll_notifications_down(&mut self, lines: usize) {
+        let last = self.notifications.len().saturating_sub(1);
+        self.notifications_scroll = (self.notifications_scroll + lines).min(last);
+    }
+
+    /// Adds a message to the notifications panel. A repeat of the newest
+    /// message, such as an action outcome that is also shown in the status
+    /// bar, is kept once.
+    fn notify(&mut self, message: &str, is_error: bool) {
+        let repeated = self
+            .notifications
+            .last()
+            .is_some_and(|last| last.message == message && last.is_error == is_error);
+        if repeated {
+            return;
+        }
+
+        self.notifications.push(Notification {
+            at: Utc::now(),
+            message: message.to_string(),
+            is_error,
+        });
+        if self.notifications.len() > MAX_NOTIFICATIONS {
+            self.notifications.remove(0);
+        }
+    }
+
+    pub fn next_period(&mut self) {
+        // The presets plus the custom range entry
+        self.selected_period = (self.selected_period + 1) % (CostPeriod::presets().len() + 1);
+    }
+
+    pub fn prev_period(&mut self) {
+        let len = CostPeriod::presets().len() + 1;
+        self.selected_period = (self.selected_period + len - 1) % len;
+    }
+
+    /// Returns the highlighted preset, or `None` for the custom range entry.
+    pub fn highlighted_period(&self) -> Option<CostPeriod> {
+        CostPeriod::presets().get(self.selected_period).copied()
+    }
+
+    /// Switches the daily spend to a new period; the history reloads on
+    /// the next refresh.
+    pub fn set_cost_period(&mut self, period: CostPeriod) {
+        self.close_period_picker();
+        if period != self.cost_period {
+            self.cost_period = period;
+            self.cost_history_loaded_at = None;
+        }
+    }
+
+    /// Stores spend for a tag key, summing values reported by several providers.
+    pub fn set_cost_by_tag(&mut self, tag_key: &str, costs: Vec<HashMap<String, f64>>) {
+        let mut totals: HashMap<String, f64> = HashMap::new();
+        for provider_costs in costs {
+            for (value, cost) in provider_costs {
+                *totals.entry(value).or_insert(0.0) += cost;
+            }
+        }
+
+        let mut values: Vec<(String, f64)> = totals.into_iter().collect();
+        values.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        match self.cost_by_tag.iter_mut().find(|(key, _)| key == tag_key) {
+            Some(entry) => entry.1 = values,
+            None => self.cost_by_tag.push((tag_key.to_string(), values)),
+        }
+    }
+
+    pub fn set_metrics(&mut self, resource_id: String, metrics: Vec<MetricSeries>) {
+        self.metrics = metrics;
+        self.metrics_resource_id = Some(resource_id);
+        self.metrics_cached = false;
+    }
+
+    /// Shows metric samples stored in the cache when the provider could not
+    /// be reached.
+    pub fn set_cached_metrics(&mut self, resource_id: String, metrics: Vec<MetricSeries>) {
+        self.set_metrics(resource_id, metrics);
+        self.metrics_cached = true;
+    }
+
+    /// Switches to the Logs tab, following new lines.
+    pub fn open_logs_tab(&mut self) {
+        self.set_detail_tab(DetailTab::Logs);
+        self.logs.clear();
+        self.logs_follow = true;
+        self.logs_scroll = 0;
+    }
+
+    pub fn scroll_raw_up(&mut self, lines: usize) {
+        self.raw_scroll = self.raw_scroll.saturating_sub(lines);
+    }
+
+    /// Scrolls the Raw tab down by `lines`, stopping at its last line given
+    /// it holds `len` lines.
+    pub fn scroll_raw_down(&mut self, lines: usize, len: usize) {
+        self.raw_scroll = (self.raw_scroll + lines).min(len.saturating_sub(1));
+    }
+
+    /// Appends newly fetched log lines, dropping the oldest beyond `MAX_LOG_LINES`.
+    ///
+    /// When not following, the scroll offset is shifted so the visible lines
+    /// stay in place as new ones arrive.
+    pub fn append_logs(&mut self, entries: Vec<LogEntry>) {
+        if !self.logs_follow {
+            self.logs_scroll += entries.len();
+        }
+        self.logs.extend(entries);
+        if self.logs.len() > MAX_LOG_LINES {
+            let excess = self.logs.len() - MAX_LOG_LINES;
+            self.logs.drain(..excess);
+        }
+        self.logs_scroll = self.logs_scroll.min(self.logs.len().saturating_sub(1));
+    }
+
+    /// Timestamp of the newest log line, used as the starting point for tailing.
+    pub fn latest_log_time(&self) -> Option<DateTime<Utc>> {
+        self.logs.last().map(|entry| entry.timestamp)
+    }
+
+    pub fn toggle_log_follow(&mut self) {
+        self.logs_follow = !self.logs_follow;
+        if self.logs_follow {
+            self.logs_scroll = 0;
+        }
+    }
+
+    pub fn scroll_logs_up(&mut self, lines: usize) {
+        self.logs_follow = false;
+        self.logs_scroll = (self.logs_scroll + lines).min(self.logs.len().saturating_sub(1));
+    }
+
+    pub fn scroll_logs_down(&mut self, lines: usize) {
+        self.logs_scroll = self.logs_scroll.saturating_sub(lines);
+    }
+
+    pub fn enter_records_view(&mut self) {
+        self.view_mode = ViewMode::Records;
+        self.dns_records.clear();
+        self.records_page = 0;
+    }
+
+    pub fn exit_records_view(&mut self) {
+        self.view_mode = ViewMode::ResourceDetail;
+        self.dns_records.clear();
+    }
+
+    pub fn set_dns_records(&mut self, records: Vec<DnsRecord>) {
+        self.dns_records = records;
+        self.records_page = self.records_page.min(self.records_page_count() - 1);
+    }
+
+    pub fn records_page_count(&self) -> usize {
+        self.dns_records.len().div_ceil(RECORDS_PAGE_SIZE).max(1)
+    }
+
+    /// Records on the current page of the records view.
+    pub fn current_records_page(&self) -> &[DnsRecord] {
+        let start = (self.records_page * RECORDS_PAGE_SIZE).min(self.dns_records.len());
+        let end = (start + RECORDS_PAGE_SIZE).min(self.dns_records.len());
+        &self.dns_records[start..end]
+    }
+
+    pub fn next_records_page(&mut self) {
+        if self.records_page + 1 < self.records_page_count() {
+            self.records_page += 1;
+        }
+    }
+
+    pub fn prev_records_page(&mut self) {
+        self.records_page = self.records_page.saturating_sub(1);
+    }
+
+    pub fn enter_quotas_view(&mut self) {
+        self.view_mode = ViewMode::Quotas;
+        self.quotas.clear();
+    }
+
+    pub fn exit_quotas_view(&mut self) {
+        self.view_mode = ViewMode::Dashboard;
+    }
+
+    pub fn set_quotas(&mut self, mut quotas: Vec<(String, Quota)>) {
+        quotas.sort_by(|a, b| b.1.used_ratio().total_cmp(&a.1.used_ratio()));
+        self.quotas = quotas;
+    }
+
+    /// Number of quotas at or above the warning threshold.
+    pub fn quotas_near_limit(&self) -> usize {
+        self.quotas.iter().filter(|(_, q)| q.is_near_limit()).count()
+    }
+
+    pub fn enter_waste_view(&mut self) {
+        self.view_mode = ViewMode::Waste;
+        self.waste.clear();
+    }
+
+    pub fn exit_waste_view(&mut self) {
+        self.view_mode = ViewMode::Dashboard;
+    }
+
+    pub fn set_waste(&mut self, mut waste: Vec<(String, WasteFinding)>) {
+        waste.sort_by(|a, b| {
+            b.1.monthly_cost
+                .unwrap_or(0.0)
+                .total_cmp(&a.1.monthly_cost.unwrap_or(0.0))
+        });
+        self.waste = waste;
+    }
+
+    /// Findings across providers, without their provider names.
+    pub fn waste_findings(&self) -> Vec<WasteFinding> {
+        self.waste.iter().map(|(_, finding)| finding.clone()).collect()
+    }
+
+    pub fn enter_compliance_view(&mut self) {
+        self.view_mode = ViewMode::Compliance;
+    }
+
+    pub fn exit_compliance_view(&mut self) {
+        self.view_mode = ViewMode::Dashboard;
+    }
+
+    pub fn enter_changes_view(&mut self) {
+        self.view_mode = ViewMode::Changes;
+        self.inventory_changes = None;
+    }
+
+    pub fn exit_changes_view(&mut self) {
+        self.view_mode = ViewMode::Dashboard;
+    }
+
+    /// Returns the tags the policy requires that a resource lacks.
+    pub fn missing_tags(&self, resource: &dyn CloudResource) -> Vec<String> {
+        self.tag_policy.missing_tags(resource.tags())
+    }
+
+    pub fn next_action(&mut self, max_actions: usize) {
+        if max_actions > 0 {
+            self.selected_action = (self.selected_action + 1) % max_actions;
+        }
+    }
+
+    pub fn prev_action(&mut self, max_actions: usize) {
+        if max_actions > 0 {
+            if self.selected_action == 0 {
+                self.selected_action = max_actions - 1;
+            } else {
+                self.selected_action -= 1;
+            }
+        }
+    }
+
+    pub fn show_action_confirmation(&mut self, message: String) {
+        self.confirmation_message = message;
+        self.show_confirmation = true;
+    }
+
+    pub fn cancel_confirmation(&mut self) {
+        self.show_confirmation = false;
+        self.confirmation_message.clear();
+        self.confirm_answers.clear();
+        self.confirm_input.clear();
+        self.pending_modification = None;
+        self.pending_request = None;
+        self.pending_bulk_action = None;
+        self.snapshot_choice = None;
+        self.pending_unprotect = false;
+        self.deletion_blockers.clear();
+    }
+
+    /// Lists dependencies that block the pending termination in the dialog.
+    pub fn set_deletion_blockers(&mut self, blockers: Vec<String>) {
+        self.deletion_blockers = blockers;
+    }
+
+    /// Returns true if the pending confirmation can be accepted.
+    pub fn can_confirm(&self) -> bool {
+        self.show_confirmation && self.deletion_blockers.is_empty() && self.confirm_input_matches()
+    }
+
+    /// Requires the pending confirmation to be typed out as one of `answers`.
+    pub fn require_typed_confirmation(&mut self, answers: Vec<String>) {
+        self.confirm_answers = answers.into_iter().filter(|answer| !answer.is_empty()).collect();
+        self.confirm_input.clear();
+    }
+
+    /// Requires a termination to be typed out before it is confirmed. Takes
+    /// the name and ID of each target. One resource is confirmed with its
+    /// name or the last four characters of its ID, several with
+    /// `termination_phrase`.
+    pub fn require_termination_confirmation(&mut self, targets: &[(&str, &str)]) {
+        let answers = match targets {
+            [(resource_name, resource_id)] => {
+                let chars: Vec<char> = resource_id.chars().collect();
+                let id_suffix: String = chars[chars.len().saturating_sub(4)..].iter().collect();
+                vec![resource_name.to_string(), id_suffix]
+            }
+            _ => vec![Self::termination_phrase(targets.len())],
+        };
+        self.require_typed_confirmation(answers);
+    }
+
+    /// What to type to terminate several resources at once, e.g. "terminate 3".
+    pub fn termination_phrase(count: usize) -> String {
+        format!("terminate {}", count)
+    }
+
+    pub fn needs_typed_confirmation(&self) -> bool {
+        !self.confirm_answers.is_empty()
+    }
+
+    pub fn confirm_input_matches(&self) -> bool {
+        self.confirm_answers.is_empty() || self.confirm_answers.iter().any(|answer| *answer == self.confirm_input.trim())
+    }
+
+    pub fn push_confirm_char(&mut self, c: char) {
+        self.confirm_input.push(c);
+    }
+
+    pub fn pop_confirm_char(&mut self) {
+        self.confirm_input.pop();
+    }
+
+    /// Asks to confirm an action with parameters, which are listed in the dialog.
+    pub fn confirm_request(&mut self, message: String, request: ActionRequest) {
+        self.show_action_confirmation(message);
+        self.pending_request = Some(request);
+    }
+
+    /// Asks to remove termination protection and retry the termination,
+    /// keeping the snapshot choice made for the first attempt.
+    pub fn confirm_unprotect(&mut self, resource_name: &str, snapshot_choice: Option<bool>) {
+        self.show_action_confirmation(format!(
+            "'{}' has termination protection enabled.\n\nDisable protection and terminate it?\n\nPress Enter to confirm or ESC to cancel.",
+            resource_name
+        ));
+        self.pending_unprotect = true;
+        self.snapshot_choice = snapshot_choice;
+    }
+
+    /// Offers a snapshot in the pending confirmation, pre-selected when required.
+    pub fn offer_snapshot(&mut self) {
+        self.snapshot_choice = Some(self.require_snapshot);
+    }
+
+    pub fn toggle_snapshot_choice(&mut self) {
+        if let Some(choice) = self.snapshot_choice {
+            if !self.require_snapshot {
+                self.snapshot_choice = Some(!choice);
+            }
+        }
+    }
+
+    pub fn wants_snapshot(&self) -> bool {
+        self.snapshot_choice == Some(true)
+    }
+
+    pub fn open_modify_picker(&mut self, options: Vec<ModifyOption>) {
+        self.modify_options = options;
+        self.selected_modify_option = 0;
+        self.show_modify_picker = true;
+    }
+
+    pub fn close_modify_picker(&mut self) {
+        self.show_modify_picker = false;
+        self.modify_options.clear();
+        self.selected_modify_option = 0;
+    }
+
+    pub fn next_modify_option(&mut self) {
+        if !self.modify_options.is_empty() {
+            self.selected_modify_option = (self.selected_modify_option + 1) % self.modify_options.len();
+        }
+    }
+
+    pub fn prev_modify_option(&mut self) {
+        if !self.modify_options.is_empty() {
+            if self.selected_modify_option == 0 {
+                self.selected_modify_option = self.modify_options.len() - 1;
+            } else {
+                self.selected_modify_option -= 1;
+            }
         }
     }
 
-    pub fn with_providers(mut self, providers: Vec<Arc<RwLock<Box<dyn CloudProvider>>>>) -> Self {
-        self.providers = providers;
-        self
+    /// Moves the highlighted option into `pending_modification` and asks for confirmation.
+    pub fn confirm_modify_option(&mut self, message: String) {
+        self.pending_modification = self.modify_options.get(self.selected_modify_option).cloned();
+        self.close_modify_picker();
+        if self.pending_modification.is_some() {
+            self.show_action_confirmation(message);
+        }
     }
 
-    // CHANGES: Added method to enable cache awareness
-    pub fn with_cache_enabled(mut self, enabled: bool) -> Self {
-        self.cache_enabled = enabled;
-        self
+    /// Marks a resource for a bulk action, or unmarks it if already marked.
+    pub fn toggle_mark(&mut self, resource_id: &str) {
+        if !self.marked_resources.remove(resource_id) {
+            self.marked_resources.insert(resource_id.to_string());
+        }
     }
 
-    // CHANGES: Added method to check if using cached data
-    pub fn is_using_cache(&self) -> bool {
-        self.cache_enabled && self.last_refresh.is_some()
+    /// Marks every resource the filter shows, or clears the marks if they
+    /// are all marked already.
+    pub async fn toggle_mark_all(&mut self) {
+        let resources = self.resources.read().await;
+        let ids: Vec<String> = self
+            .filtered_resources
+            .iter()
+            .filter_map(|&idx| resources.get(idx).map(|r| r.id().to_string()))
+            .collect();
+        drop(resources);
+
+        if !ids.is_empty() && ids.iter().all(|id| self.marked_resources.contains(id)) {
+            self.marked_resources.clear();
+        } else {
+            self.marked_resources.extend(ids);
+        }
     }
 
-    // CHANGES: Added method to get cache age display string
-    pub fn cache_age_display(&self) -> Option<String> {
-        if !self.cache_enabled {
-            return None;
+    pub fn is_marked(&self, resource_id: &str) -> bool {
+        self.marked_resources.contains(resource_id)
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.marked_resources.clear();
+    }
+
+    pub fn open_bulk_picker(&mut self, actions: Vec<Action>) {
+        self.bulk_actions = actions;
+        self.selected_bulk_action = 0;
+        self.show_bulk_picker = true;
+    }
+
+    pub fn close_bulk_picker(&mut self) {
+        self.show_bulk_picker = false;
+        self.bulk_actions.clear();
+        self.selected_bulk_action = 0;
+    }
+
+    pub fn next_bulk_action(&mut self) {
+        if !self.bulk_actions.is_empty() {
+            self.selected_bulk_action = (self.selected_bulk_action + 1) % self.bulk_actions.len();
         }
+    }
 
-        self.last_refresh.map(|refresh_time| {
-            let age = Utc::now().signed_duration_since(refresh_time);
-            
-            if age.num_minutes() < 1 {
-                "just now".to_string()
-            } else if age.num_minutes() < 60 {
-                format!("{}m ago", age.num_minutes())
-            } else if age.num_hours() < 24 {
-                format!("{}h ago", age.num_hours())
+    pub fn prev_bulk_action(&mut self) {
+        if !self.bulk_actions.is_empty() {
+            if self.selected_bulk_action == 0 {
+                self.selected_bulk_action = self.bulk_actions.len() - 1;
             } else {
-                format!("{}d ago", age.num_days())
+                self.selected_bulk_action -= 1;
             }
-        })
+        }
     }
 
-    pub fn next_tab(&mut self) {
-        self.active_tab = self.active_tab.next();
+    pub fn visible_columns(&self) -> Vec<ListColumn> {
+        self.columns.iter().filter(|(_, shown)| *shown).map(|(column, _)| *column).collect()
     }
 
-    pub fn prev_tab(&mut self) {
-        self.active_tab = self.active_tab.prev();
+    pub fn open_column_picker(&mut self) {
+        self.selected_column = 0;
+        self.show_column_picker = true;
     }
 
-    pub fn set_tab(&mut self, tab: TabIndex) {
-        self.active_tab = tab;
+    pub fn close_column_picker(&mut self) {
+        self.show_column_picker = false;
     }
 
-    pub fn toggle_view_mode(&mut self) {
-        self.view_mode = match self.view_mode {
-            ViewMode::Dashboard => ViewMode::ResourceList,
-            ViewMode::ResourceList => ViewMode::Dashboard,
-            ViewMode::ResourceDetail => ViewMode::ResourceList,
+    pub fn next_column(&mut self) {
+        self.selected_column = (self.selected_column + 1) % self.columns.len();
+    }
+
+    pub fn prev_column(&mut self) {
+        if self.selected_column == 0 {
+            self.selected_column = self.columns.len() - 1;
+        } else {
+            self.selected_column -= 1;
+        }
+    }
+
+    /// Shows or hides the highlighted column. The last shown column stays.
+    pub fn toggle_column(&mut self) {
+        let shown = self.columns.iter().filter(|(_, shown)| *shown).count();
+        if let Some((_, visible)) = self.columns.get_mut(self.selected_column) {
+            if !*visible || shown > 1 {
+                *visible = !*visible;
+            }
+        }
+    }
+
+    /// Moves the highlighted column one place left in the list, or right
+    /// when `later` is set, keeping it highlighted.
+    pub fn move_column(&mut self, later: bool) {
+        let from = self.selected_column;
+        let to = if later {
+            from + 1
+        } else {
+            match from.checked_sub(1) {
+                Some(to) => to,
+                None => return,
+            }
         };
+        if to < self.columns.len() {
+            self.columns.swap(from, to);
+            self.selected_column = to;
+        }
     }
 
-    pub fn enter_detail_view(&mut self) {
-        if !self.filtered_resources.is_empty() {
-            self.view_mode = ViewMode::ResourceDetail;
-            self.selected_action = 0;
+    pub fn highlighted_bulk_action(&self) -> Option<Action> {
+        self.bulk_actions.get(self.selected_bulk_action).copied()
+    }
+
+    /// Moves the highlighted action into `pending_bulk_action` and asks for confirmation.
+    pub fn confirm_bulk_action(&mut self, message: String) {
+        self.pending_bulk_action = self.highlighted_bulk_action();
+        self.close_bulk_picker();
+        if self.pending_bulk_action.is_some() {
+            self.show_action_confirmation(message);
         }
     }
 
-    pub fn exit_detail_view(&mut self) {
-        self.view_mode = ViewMode::ResourceList;
-        self.selected_action = 0;
-        self.show_confirmation = false;
+    /// Opens the context picker on the active context. Needs at least two
+    /// contexts to choose between.
+    pub fn open_context_picker(&mut self) {
+        if self.contexts.len() < 2 {
+            return;
+        }
+        self.selected_context = self
+            .active_context
+            .as_ref()
+            .and_then(|active| self.contexts.iter().position(|c| c == active))
+            .unwrap_or(0);
+        self.show_context_picker = true;
     }
 
-    pub fn next_action(&mut self, max_actions: usize) {
-        if max_actions > 0 {
-            self.selected_action = (self.selected_action + 1) % max_actions;
+    pub fn close_context_picker(&mut self) {
+        self.show_context_picker = false;
+    }
+
+    pub fn next_context(&mut self) {
+        if !self.contexts.is_empty() {
+            self.selected_context = (self.selected_context + 1) % self.contexts.len();
         }
     }
 
-    pub fn prev_action(&mut self, max_actions: usize) {
-        if max_actions > 0 {
-            if self.selected_action == 0 {
-                self.selected_action = max_actions - 1;
+    pub fn prev_context(&mut self) {
+        if !self.contexts.is_empty() {
+            if self.selected_context == 0 {
+                self.selected_context = self.contexts.len() - 1;
             } else {
-                self.selected_action -= 1;
+                self.selected_context -= 1;
             }
         }
     }
 
-    pub fn show_action_confirmation(&mut self, message: String) {
-        self.confirmation_message = message;
-        self.show_confirmation = true;
+    pub fn highlighted_context(&self) -> Option<&str> {
+        self.contexts.get(self.selected_context).map(String::as_str)
     }
 
-    pub fn cancel_confirmation(&mut self) {
-        self.show_confirmation = false;
-        self.confirmation_message.clear();
+    /// Replaces the providers with another context's, dropping everything
+    /// that was loaded from the old ones.
+    pub async fn switch_context(
+        &mut self,
+        name: impl Into<String>,
+        providers: Vec<Arc<RwLock<Box<dyn CloudProvider>>>>,
+    ) {
+        self.refresh_events = None;
+        self.refresh_pending = 0;
+        self.refresh_progress.clear();
+        self.refresh_failures.clear();
+        self.refresh_error = None;
+        self.refresh_selection = None;
+        self.account_switch = None;
+        self.clear_resources().await;
+        self.selected_index = 0;
+        self.last_refresh = None;
+        self.marked_resources.clear();
+        self.operations.clear();
+        self.image_jobs.clear();
+        self.resource_health.clear();
+        self.cost_history.clear();
+        self.cost_history_loaded_at = None;
+        self.cost_by_tag.clear();
+        self.commitments.clear();
+        self.budgets.clear();
+        self.cost_forecast = None;
+        self.anomalies.clear();
+        self.quotas.clear();
+        self.waste.clear();
+        self.cost_alerts.clear();
+        self.inventory_changes = None;
+
+        self.providers = providers;
+        self.active_context = Some(name.into());
+        self.load_identities().await;
+        self.load_capabilities().await;
     }
 
     pub fn get_selected_resource_index(&self) -> Option<usize> {
+        if self.group_by.is_some() {
+            return match self.list_rows.get(self.selected_index) {
+                Some(ListRow::Resource(idx)) => Some(*idx),
+                _ => None,
+            };
+        }
         self.filtered_resources.get(self.selected_index).copied()
     }
 
+    /// Number of lines in the resource list, counting group headers.
+    pub fn row_count(&self) -> usize {
+        if self.group_by.is_some() {
+            self.list_rows.len()
+        } else {
+            self.filtered_resources.len()
+        }
+    }
+
+    /// Switches to the next grouping: none, type, region, account, then
+    /// each cost-allocation tag.
+    pub fn cycle_grouping(&mut self) {
+        let mut options = vec![None, Some(GroupBy::Type), Some(GroupBy::Region), Some(GroupBy::Account)];
+        options.extend(self.cost_tag_keys.iter().map(|key| Some(GroupBy::Tag(key.clone()))));
+
+        let current = options.iter().position(|option| *option == self.group_by).unwrap_or(0);
+        self.group_by = options[(current + 1) % options.len()].clone();
+        self.collapsed_groups.clear();
+        self.selected_index = 0;
+        self.apply_filter();
+    }
+
+    /// The group header under the selection, if one is selected.
+    pub fn selected_group(&self) -> Option<&str> {
+        match self.list_rows.get(self.selected_index) {
+            Some(ListRow::Group { name, .. }) if self.group_by.is_some() => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Collapses the selected group, or expands it if it is collapsed.
+    /// Returns false if no group header is selected.
+    pub fn toggle_group(&mut self) -> bool {
+        let name = match self.selected_group() {
+            Some(name) => name.to_string(),
+            None => return false,
+        };
+        if !self.collapsed_groups.remove(&name) {
+            self.collapsed_groups.insert(name.clone());
+        }
+        self.apply_filter();
+        if let Some(position) = self
+            .list_rows
+            .iter()
+            .position(|row| matches!(row, ListRow::Group { name: group, .. } if *group == name))
+        {
+            self.selected_index = position;
+        }
+        true
+    }
+
+    /// Lays out the filtered resources under a header per group, groups
+    /// sorted by name, leaving out the resources of collapsed groups.
+    fn group_rows(&self, group_by: &GroupBy, resources: &[Box<dyn CloudResource>]) -> Vec<ListRow> {
+        let mut groups: std::collections::BTreeMap<String, Vec<usize>> = std::collections::BTreeMap::new();
+        for &idx in &self.filtered_resources {
+            let resource = match resources.get(idx) {
+                Some(resource) => resource,
+                None => continue,
+            };
+            let name = match group_by {
+                GroupBy::Type => resource.resource_type().as_str().to_string(),
+                GroupBy::Region => resource.region().to_string(),
+                GroupBy::Account => self.account_for_resource(idx).unwrap_or("-").to_string(),
+                GroupBy::Tag(key) => resource.tags().get(key).cloned().unwrap_or_else(|| "(untagged)".to_string()),
+            };
+            groups.entry(name).or_default().push(idx);
+        }
+
+        let mut rows = Vec::new();
+        for (name, members) in groups {
+            let collapsed = self.collapsed_groups.contains(&name);
+            rows.push(ListRow::Group {
+                count: members.len(),
+                cost: members.iter().filter_map(|&idx| resources[idx].cost_per_month()).sum(),
+                collapsed,
+                name,
+            });
+            if !collapsed {
+                rows.extend(members.into_iter().map(ListRow::Resource));
+            }
+        }
+        rows
+    }
+
+    /// Returns the provider (account) that a resource was listed from.
+    pub fn provider_for_resource(&self, resource_idx: usize) -> Option<Arc<RwLock<Box<dyn CloudProvider>>>> {
+        self.resource_sources
+            .get(resource_idx)
+            .and_then(|&provider_idx| self.providers.get(provider_idx))
+            .cloned()
+    }
+
     pub fn quit(&mut self) {
         self.should_quit = true;
     }
@@ -243,6 +2226,7 @@ impl AppState {
     }
 
     pub fn set_error(&mut self, error: String) {
+        self.notify(&error, true);
         self.error_message = Some(error);
         self.success_message = None;
         self.loading = false;
@@ -253,6 +2237,7 @@ impl AppState {
     }
 
     pub fn set_success(&mut self, message: String) {
+        self.notify(&message, false);
         self.success_message = Some(message);
         self.error_message = None;
         self.loading = false;
@@ -267,9 +2252,31 @@ impl AppState {
         self.success_message = None;
     }
 
-    pub fn record_action(&mut self, action_description: String) {
-        self.last_action = Some(action_description);
-        self.last_action_time = Some(Utc::now());
+    pub fn record_action(&mut self, record: ActionRecord) {
+        self.notify(&record.message, record.is_failure());
+        self.action_log.push(record);
+        self.unsaved_actions += 1;
+    }
+
+    /// The most recent action of this session.
+    pub fn last_action(&self) -> Option<&ActionRecord> {
+        self.action_log.last()
+    }
+
+    /// Returns the actions recorded since the last call, to be persisted.
+    pub fn take_unsaved_actions(&mut self) -> Vec<ActionRecord> {
+        let start = self.action_log.len() - self.unsaved_actions;
+        self.unsaved_actions = 0;
+        self.action_log[start..].to_vec()
+    }
+
+    pub fn enter_history_view(&mut self) {
+        self.view_mode = ViewMode::History;
+        self.action_history.clear();
+    }
+
+    pub fn exit_history_view(&mut self) {
+        self.view_mode = ViewMode::Dashboard;
     }
 
     pub fn enter_filter_mode(&mut self) {
@@ -284,6 +2291,163 @@ impl AppState {
         self.input_mode == InputMode::Filter
     }
 
+    pub fn enter_search_mode(&mut self) {
+        self.input_mode = InputMode::Search;
+        self.search_text.clear();
+        self.search_origin = self.selected_index;
+    }
+
+    /// Leaves the search, going back to where it started unless `keep` is set.
+    pub fn exit_search_mode(&mut self, keep: bool) {
+        self.input_mode = InputMode::Normal;
+        if !keep {
+            self.search_text.clear();
+            self.selected_index = self.search_origin;
+        }
+    }
+
+    pub fn is_searching(&self) -> bool {
+        self.input_mode == InputMode::Search
+    }
+
+    pub async fn push_search_char(&mut self, c: char) {
+        self.search_text.push(c);
+        self.search_from_origin().await;
+    }
+
+    pub async fn pop_search_char(&mut self) {
+        self.search_text.pop();
+        self.search_from_origin().await;
+    }
+
+    /// Selects the first match at or after the row the search started on.
+    async fn search_from_origin(&mut self) {
+        self.selected_index = self.search_origin;
+        if let Some(row) = self.find_search_match(self.search_origin, true).await {
+            self.selected_index = row;
+        }
+    }
+
+    /// Moves to the next or previous row matching the search, wrapping
+    /// around. Returns false if nothing matches.
+    pub async fn search_next(&mut self, forward: bool) -> bool {
+        let rows = self.row_count();
+        if rows == 0 {
+            return false;
+        }
+        let start = if forward {
+            (self.selected_index + 1) % rows
+        } else {
+            (self.selected_index + rows - 1) % rows
+        };
+        match self.find_search_match(start, forward).await {
+            Some(row) => {
+                self.selected_index = row;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the first row from `start` whose resource name or ID contains
+    /// the search text, looking in the given direction.
+    async fn find_search_match(&self, start: usize, forward: bool) -> Option<usize> {
+        let needle = self.search_text.to_lowercase();
+        let rows = self.row_count();
+        if needle.is_empty() || rows == 0 {
+            return None;
+        }
+
+        let resources = self.resources.read().await;
+        (0..rows)
+            .map(|offset| {
+                if forward {
+                    (start + offset) % rows
+                } else {
+                    (start + rows - offset) % rows
+                }
+            })
+            .find(|row| {
+                let resource_idx = if self.group_by.is_some() {
+                    match self.list_rows.get(*row) {
+                        Some(ListRow::Resource(idx)) => Some(*idx),
+                        _ => None,
+                    }
+                } else {
+                    self.filtered_resources.get(*row).copied()
+                };
+                resource_idx.and_then(|idx| resources.get(idx)).is_some_and(|resource| {
+                    resource.name().to_lowercase().contains(&needle) || resource.id().to_lowercase().contains(&needle)
+                })
+            })
+    }
+
+    /// Opens a text prompt whose input is passed to `action` on submit.
+    pub fn open_prompt(&mut self, action: Action, title: String, default_input: String) {
+        self.input_mode = InputMode::Prompt;
+        self.prompt_action = Some(action);
+        self.prompt_title = title;
+        self.prompt_input = default_input;
+    }
+
+    /// Closes the prompt, returning the action and entered text.
+    pub fn take_prompt(&mut self) -> Option<(Action, String)> {
+        self.input_mode = InputMode::Normal;
+        self.prompt_title.clear();
+        let input = std::mem::take(&mut self.prompt_input);
+        self.prompt_action.take().map(|action| (action, input))
+    }
+
+    pub fn is_prompting(&self) -> bool {
+        self.input_mode == InputMode::Prompt
+    }
+
+    /// Whether keys go into a text field: an input mode, a confirmation
+    /// or the custom cost range.
+    pub fn is_typing(&self) -> bool {
+        self.input_mode != InputMode::Normal || self.show_confirmation || self.period_input.is_some()
+    }
+
+    pub fn track_image(&mut self, job: ImageJob) {
+        self.image_jobs.push(job);
+    }
+
+    /// Updates the state of a tracked image, removing it once it has
+    /// finished. Returns the job if it finished.
+    pub fn update_image_state(&mut self, image_id: &str, state: String) -> Option<ImageJob> {
+        let idx = self.image_jobs.iter().position(|job| job.image_id == image_id)?;
+        self.image_jobs[idx].state = state;
+        if self.image_jobs[idx].state == "pending" {
+            None
+        } else {
+            Some(self.image_jobs.remove(idx))
+        }
+    }
+
+    /// Starts following an action until its resource reaches the target
+    /// state. A newer action on the same resource replaces the older one.
+    pub fn track_operation(&mut self, job: OperationJob) {
+        self.operations.retain(|existing| existing.handle.resource_id != job.handle.resource_id);
+        self.operations.push(job);
+    }
+
+    /// Updates the status of a tracked action, removing it once it has
+    /// finished. Returns the job if it finished.
+    pub fn update_operation(&mut self, resource_id: &str, status: OperationStatus) -> Option<OperationJob> {
+        let idx = self.operations.iter().position(|job| job.handle.resource_id == resource_id)?;
+        self.operations[idx].status = status;
+        if self.operations[idx].status.is_finished() {
+            Some(self.operations.remove(idx))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the action still taking effect on a resource, if any.
+    pub fn operation_for(&self, resource_id: &str) -> Option<&OperationJob> {
+        self.operations.iter().find(|job| job.handle.resource_id == resource_id)
+    }
+
     pub fn push_filter_char(&mut self, c: char) {
         self.filter_text.push(c);
         self.apply_filter();
@@ -296,42 +2460,352 @@ impl AppState {
 
     pub fn clear_filter(&mut self) {
         self.filter_text.clear();
+        self.search_hits = None;
+        self.pending_search = None;
+        self.apply_filter();
+    }
+
+    /// Sets the resources the cache search index matched for the current
+    /// filter and re-applies it. They are shown alongside direct matches.
+    pub fn set_search_hits(&mut self, hits: Option<HashSet<String>>) {
+        self.pending_search = None;
+        self.search_hits = hits;
         self.apply_filter();
     }
 
+    /// Waits for the cache search for `text` in the background, replacing
+    /// any search still running for earlier filter text.
+    pub fn start_search(&mut self, text: String, receiver: oneshot::Receiver<crate::error::Result<Vec<String>>>) {
+        self.pending_search = Some((text, receiver));
+    }
+
+    /// Applies the cache search hits once the query has finished, unless the
+    /// filter has changed since. Returns `None` while still waiting.
+    pub fn poll_search(&mut self) -> Option<crate::error::Result<()>> {
+        let result = match self.pending_search.as_mut()?.1.try_recv() {
+            Ok(result) => result,
+            Err(oneshot::error::TryRecvError::Empty) => return None,
+            Err(oneshot::error::TryRecvError::Closed) => {
+                Err(crate::error::NimbusError::CacheError("Cache search stopped before it finished".to_string()))
+            }
+        };
+        let (text, _) = self.pending_search.take()?;
+        if text != self.filter_text {
+            return Some(Ok(()));
+        }
+
+        match result {
+            Ok(ids) => {
+                self.set_search_hits(Some(ids.into_iter().collect()));
+                Some(Ok(()))
+            }
+            Err(e) => {
+                self.set_search_hits(None);
+                Some(Err(e))
+            }
+        }
+    }
+
+    /// Lists every provider's resources and waits until all have finished.
     pub async fn refresh_resources(&mut self) -> crate::error::Result<()> {
-        self.start_loading();
+        self.start_refresh().await;
+
+        while let Some(receiver) = self.refresh_events.as_mut() {
+            let event = match receiver.recv().await {
+                Some(event) => event,
+                None => return self.finish_refresh(),
+            };
+            if let Some(result) = self.handle_refresh_event(event).await {
+                return result;
+            }
+        }
 
-        let mut all_resources = Vec::new();
-        let mut had_error = None;
+        Ok(())
+    }
 
+    /// Fills the list with resources loaded from the cache, each shown
+    /// under the account it was cached from. They stay until the next
+    /// refresh lists live resources.
+    pub async fn load_cached_resources(&mut self, resources: Vec<(String, Box<dyn CloudResource>)>) {
+        let mut accounts = Vec::with_capacity(self.providers.len());
         for provider in &self.providers {
             let provider = provider.read().await;
-            match provider.list_all_resources().await {
-                Ok(resources) => {
-                    all_resources.extend(resources);
+            accounts.push((provider.provider_type(), provider.account_name().to_string(), provider.capabilities()));
+        }
+
+        let mut batches: Vec<Vec<Box<dyn CloudResource>>> = accounts.iter().map(|_| Vec::new()).collect();
+        for (account, resource) in resources {
+            // Resource types an account no longer lists are left out, as are
+            // accounts no longer configured. Rows cached before accounts were
+            // recorded go to the first account of their cloud.
+            let owner = accounts.iter().position(|(t, name, caps)| {
+                *t == resource.provider()
+                    && (account.is_empty() || *name == account)
+                    && caps.supports_type(resource.resource_type())
+            });
+            if let Some(idx) = owner {
+                batches[idx].push(resource);
+            }
+        }
+
+        self.clear_resources().await;
+        for (provider_idx, batch) in batches.into_iter().enumerate() {
+            self.add_resource_batch(provider_idx, batch).await;
+        }
+        self.showing_cached = true;
+    }
+
+    /// Starts listing every provider's resources in the background.
+    ///
+    /// The current list is cleared and refilled as each provider sends
+    /// batches; call `poll_refresh` to take them in. Cached resources stay
+    /// on screen until the first batch arrives.
+    pub async fn start_refresh(&mut self) {
+        self.start_loading();
+        self.refresh_selection = self.selected_resource_id().await;
+
+        let mut progress = Vec::with_capacity(self.providers.len());
+        for provider in &self.providers {
+            progress.push(RefreshProgress::new(provider.read().await.name()));
+        }
+
+        let (events, receiver) = mpsc::unbounded_channel();
+        for (provider_idx, provider) in self.providers.iter().enumerate() {
+            let provider = Arc::clone(provider);
+            let events = events.clone();
+            tokio::spawn(async move {
+                let provider = provider.read().await;
+                let (sender, mut listings) = mpsc::unbounded_channel();
+                let forward = async {
+                    while let Some(event) = listings.recv().await {
+                        let _ = events.send(RefreshEvent::Listing(provider_idx, event));
+                    }
+                };
+
+                let (result, ()) = tokio::join!(provider.stream_all_resources(sender), forward);
+                let error = result.err().map(|e| format!("{}: {}", provider.name(), e));
+                let _ = events.send(RefreshEvent::Finished(provider_idx, error));
+            });
+        }
+
+        if !self.showing_cached {
+            self.clear_resources().await;
+        }
+        self.refresh_pending = self.providers.len();
+        self.refresh_progress = progress;
+        self.refresh_failures = vec![HashSet::new(); self.providers.len()];
+        self.refresh_error = None;
+        self.refresh_events = Some(receiver);
+
+        if self.refresh_pending == 0 {
+            let _ = self.finish_refresh();
+        }
+    }
+
+    pub fn is_refreshing(&self) -> bool {
+        self.refresh_events.is_some()
+    }
+
+    /// Waits in the background for a provider authenticated as `label` to
+    /// replace the one at `provider_idx`.
+    pub fn start_account_switch(
+        &mut self,
+        provider_idx: usize,
+        label: impl Into<String>,
+        receiver: oneshot::Receiver<crate::error::Result<Box<dyn CloudProvider>>>,
+    ) {
+        self.account_switch = Some(AccountSwitch {
+            provider_idx,
+            label: label.into(),
+            receiver,
+        });
+    }
+
+    pub fn is_switching_account(&self) -> bool {
+        self.account_switch.is_some()
+    }
+
+    /// Swaps in the provider once it has authenticated. Returns the new
+    /// identity's label and the outcome, or `None` while still waiting.
+    pub async fn poll_account_switch(&mut self) -> Option<(String, crate::error::Result<()>)> {
+        let result = match self.account_switch.as_mut()?.receiver.try_recv() {
+            Ok(result) => result,
+            Err(oneshot::error::TryRecvError::Empty) => return None,
+            Err(oneshot::error::TryRecvError::Closed) => Err(crate::error::NimbusError::auth(
+                "AWS",
+                "Authentication stopped before it finished",
+            )),
+        };
+        let switch = self.account_switch.take()?;
+
+        let outcome = match result {
+            Ok(provider) => {
+                self.replace_provider(switch.provider_idx, provider).await;
+                Ok(())
+            }
+            Err(e) => Err(e),
+        };
+        Some((switch.label, outcome))
+    }
+
+    /// Puts a newly authenticated provider in place of another, forgetting
+    /// the work that was tracked against the old one.
+    async fn replace_provider(&mut self, provider_idx: usize, provider: Box<dyn CloudProvider>) {
+        if provider_idx >= self.providers.len() {
+            return;
+        }
+        self.providers[provider_idx] = Arc::new(RwLock::new(provider));
+        self.operations.retain(|job| job.provider_idx != provider_idx);
+        self.image_jobs.retain(|job| job.provider_idx != provider_idx);
+        self.marked_resources.clear();
+        self.cost_history_loaded_at = None;
+        self.load_identities().await;
+        self.load_capabilities().await;
+    }
+
+    /// Takes in the batches that arrived since the last call. Returns the
+    /// outcome once every provider has finished, or `None` while listing.
+    pub async fn poll_refresh(&mut self) -> Option<crate::error::Result<()>> {
+        loop {
+            let event = match self.refresh_events.as_mut()?.try_recv() {
+                Ok(event) => event,
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Disconnected) => return Some(self.finish_refresh()),
+            };
+            if let Some(result) = self.handle_refresh_event(event).await {
+                return Some(result);
+            }
+        }
+    }
+
+    async fn handle_refresh_event(&mut self, event: RefreshEvent) -> Option<crate::error::Result<()>> {
+        match event {
+            RefreshEvent::Listing(provider_idx, event) => {
+                let (service, status) = match event {
+                    ListingEvent::Started(service) => (service, ListingStatus::Listing),
+                    ListingEvent::Listed(service, batch) => {
+                        if !batch.is_empty() {
+                            self.add_resource_batch(provider_idx, batch).await;
+                        }
+                        (service, ListingStatus::Done)
+                    }
+                    ListingEvent::Failed(service, resource_type, _) => {
+                        if let Some(failed) = self.refresh_failures.get_mut(provider_idx) {
+                            failed.insert(resource_type);
+                        }
+                        (service, ListingStatus::Failed)
+                    }
+                };
+                if let Some(progress) = self.refresh_progress.get_mut(provider_idx) {
+                    progress.update(service, status);
                 }
-                Err(e) => {
-                    had_error = Some(format!("Failed to load resources: {}", e));
-                    break;
+                None
+            }
+            RefreshEvent::Finished(provider_idx, error) => {
+                if let Some(progress) = self.refresh_progress.get_mut(provider_idx) {
+                    progress.finish(error.is_some());
+                }
+                if self.refresh_error.is_none() {
+                    self.refresh_error = error;
                 }
+                self.refresh_pending = self.refresh_pending.saturating_sub(1);
+                if self.refresh_pending == 0 {
+                    // Providers listed nothing, so the cached resources are gone too
+                    if self.showing_cached && self.refresh_error.is_none() {
+                        self.clear_resources().await;
+                    }
+                    Some(self.finish_refresh())
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Adds a batch after the resources of earlier providers, so the list
+    /// stays in provider order whichever provider responds first.
+    async fn add_resource_batch(&mut self, provider_idx: usize, batch: Vec<Box<dyn CloudResource>>) {
+        if self.showing_cached {
+            self.clear_resources().await;
+        }
+
+        let position = self
+            .resource_sources
+            .iter()
+            .position(|&idx| idx > provider_idx)
+            .unwrap_or(self.resource_sources.len());
+        let count = batch.len();
+
+        self.resources.write().await.splice(position..position, batch);
+        self.resource_sources
+            .splice(position..position, std::iter::repeat(provider_idx).take(count));
+
+        // Show what has arrived instead of the loading screen
+        self.loading = false;
+        self.apply_filter();
+
+        if let Some(resource_id) = self.refresh_selection.clone() {
+            if self.select_resource_id(&resource_id).await {
+                self.refresh_selection = None;
             }
         }
+    }
+
+    async fn clear_resources(&mut self) {
+        self.resources.write().await.clear();
+        self.resource_sources.clear();
+        self.filtered_resources.clear();
+        self.list_rows.clear();
+        self.showing_cached = false;
+    }
+
+    fn finish_refresh(&mut self) -> crate::error::Result<()> {
+        self.refresh_events = None;
+        self.refresh_pending = 0;
+        self.refresh_progress.clear();
+        self.refresh_selection = None;
+        self.stop_loading();
 
-        if let Some(error) = had_error {
-            self.set_error(error.clone());
-            return Err(crate::error::NimbusError::Other(error));
+        match self.refresh_error.take() {
+            Some(e) => {
+                let error = format!("Failed to load resources: {}", e);
+                self.set_error(error.clone());
+                Err(crate::error::NimbusError::Other(error))
+            }
+            None => {
+                self.last_refresh = Some(chrono::Utc::now());
+                Ok(())
+            }
         }
+    }
 
-        let mut resources = self.resources.write().await;
-        *resources = all_resources;
-        drop(resources);
+    async fn selected_resource_id(&self) -> Option<String> {
+        let resource_idx = self.get_selected_resource_index()?;
+        let resources = self.resources.read().await;
+        resources.get(resource_idx).map(|resource| resource.id().to_string())
+    }
 
-        self.apply_filter();
-        self.last_refresh = Some(chrono::Utc::now());
-        self.stop_loading();
+    /// Moves the selection to the resource with the given ID if it is shown.
+    async fn select_resource_id(&mut self, resource_id: &str) -> bool {
+        let position = {
+            let resources = self.resources.read().await;
+            let matches = |idx: usize| resources.get(idx).map(|resource| resource.id() == resource_id).unwrap_or(false);
+            if self.group_by.is_some() {
+                self.list_rows
+                    .iter()
+                    .position(|row| matches!(row, ListRow::Resource(idx) if matches(*idx)))
+            } else {
+                self.filtered_resources.iter().position(|&idx| matches(idx))
+            }
+        };
 
-        Ok(())
+        match position {
+            Some(position) => {
+                self.selected_index = position;
+                true
+            }
+            None => false,
+        }
     }
 
     pub fn apply_filter(&mut self) {
@@ -350,12 +2824,19 @@ impl AppState {
                     resources
                         .iter()
                         .enumerate()
-                        .filter(|(_, resource)| {
+                        .filter(|(idx, resource)| {
                             resource.name().to_lowercase().contains(&filter_lower)
                                 || resource.id().to_lowercase().contains(&filter_lower)
                                 || resource.resource_type().as_str().to_lowercase().contains(&filter_lower)
                                 || resource.state().as_str().to_lowercase().contains(&filter_lower)
                                 || resource.region().to_lowercase().contains(&filter_lower)
+                                || self
+                                    .account_for_resource(*idx)
+                                    .is_some_and(|account| account.to_lowercase().contains(&filter_lower))
+                                || self
+                                    .search_hits
+                                    .as_ref()
+                                    .map_or(false, |hits| hits.contains(resource.id()))
                         })
                         .map(|(idx, _)| idx)
                         .collect()
@@ -363,27 +2844,58 @@ impl AppState {
             });
         }
 
-        if self.selected_index >= self.filtered_resources.len() && !self.filtered_resources.is_empty() {
-            self.selected_index = self.filtered_resources.len() - 1;
+        self.list_rows = match self.group_by {
+            Some(ref group_by) => tokio::task::block_in_place(|| {
+                tokio::runtime::Handle::current().block_on(async {
+                    let resources = self.resources.read().await;
+                    self.group_rows(group_by, &resources)
+                })
+            }),
+            None => Vec::new(),
+        };
+
+        let rows = self.row_count();
+        if self.selected_index >= rows && rows > 0 {
+            self.selected_index = rows - 1;
         }
     }
 
     pub fn next_resource(&mut self) {
-        if !self.filtered_resources.is_empty() {
-            self.selected_index = (self.selected_index + 1) % self.filtered_resources.len();
+        let rows = self.row_count();
+        if rows > 0 {
+            self.selected_index = (self.selected_index + 1) % rows;
         }
     }
 
     pub fn prev_resource(&mut self) {
-        if !self.filtered_resources.is_empty() {
+        let rows = self.row_count();
+        if rows > 0 {
             if self.selected_index == 0 {
-                self.selected_index = self.filtered_resources.len() - 1;
+                self.selected_index = rows - 1;
             } else {
                 self.selected_index -= 1;
             }
         }
     }
 
+    /// Moves the selection down by `rows`, stopping at the last row.
+    pub fn page_down(&mut self, rows: usize) {
+        self.selected_index = (self.selected_index + rows).min(self.row_count().saturating_sub(1));
+    }
+
+    /// Moves the selection up by `rows`, stopping at the first row.
+    pub fn page_up(&mut self, rows: usize) {
+        self.selected_index = self.selected_index.saturating_sub(rows);
+    }
+
+    pub fn select_first_resource(&mut self) {
+        self.selected_index = 0;
+    }
+
+    pub fn select_last_resource(&mut self) {
+        self.selected_index = self.row_count().saturating_sub(1);
+    }
+
     pub fn resource_count(&self) -> usize {
         self.filtered_resources.len()
     }
@@ -397,6 +2909,16 @@ impl AppState {
     }
 }
 
+/// Lists `visible` first as shown, then every other column hidden.
+fn column_layout(visible: &[ListColumn]) -> Vec<(ListColumn, bool)> {
+    let hidden = ListColumn::all().into_iter().filter(|column| !visible.contains(column));
+    visible
+        .iter()
+        .map(|column| (*column, true))
+        .chain(hidden.map(|column| (column, false)))
+        .collect()
+}
+
 impl Default for AppState {
     fn default() -> Self {
         Self::new()
@@ -410,22 +2932,118 @@ impl Clone for AppState {
             providers: self.providers.clone(),
             active_tab: self.active_tab,
             resources: Arc::clone(&self.resources),
+            resource_sources: self.resource_sources.clone(),
             filtered_resources: self.filtered_resources.clone(),
             selected_index: self.selected_index,
+            group_by: self.group_by.clone(),
+            collapsed_groups: self.collapsed_groups.clone(),
+            list_rows: self.list_rows.clone(),
             filter_text: self.filter_text.clone(),
+            search_hits: self.search_hits.clone(),
+            pending_search: None,
+            search_text: self.search_text.clone(),
+            search_origin: self.search_origin,
+            keymap: self.keymap,
             view_mode: self.view_mode,
             input_mode: self.input_mode,
             loading: self.loading,
             last_refresh: self.last_refresh,
+            // The receiver belongs to the original; a clone sees a finished refresh
+            refresh_events: None,
+            refresh_pending: 0,
+            refresh_progress: Vec::new(),
+            refresh_failures: self.refresh_failures.clone(),
+            showing_cached: self.showing_cached,
+            offline: self.offline,
+            contexts: self.contexts.clone(),
+            active_context: self.active_context.clone(),
+            show_context_picker: self.show_context_picker,
+            selected_context: self.selected_context,
+            refresh_error: None,
+            refresh_selection: None,
             error_message: self.error_message.clone(),
             success_message: self.success_message.clone(),
             should_quit: self.should_quit,
             selected_action: self.selected_action,
             show_confirmation: self.show_confirmation,
             confirmation_message: self.confirmation_message.clone(),
-            last_action: self.last_action.clone(),
-            last_action_time: self.last_action_time,
+            confirm_answers: self.confirm_answers.clone(),
+            confirm_input: self.confirm_input.clone(),
+            action_log: self.action_log.clone(),
+            unsaved_actions: self.unsaved_actions,
+            action_history: self.action_history.clone(),
             cache_enabled: self.cache_enabled,
+            detail_tab: self.detail_tab,
+            detail_pane: self.detail_pane,
+            metadata_scroll: self.metadata_scroll,
+            tags_scroll: self.tags_scroll,
+            raw_scroll: self.raw_scroll,
+            metrics: self.metrics.clone(),
+            metrics_resource_id: self.metrics_resource_id.clone(),
+            metrics_cached: self.metrics_cached,
+            target_health: self.target_health.clone(),
+            target_health_resource_id: self.target_health_resource_id.clone(),
+            resource_health: self.resource_health.clone(),
+            logs: self.logs.clone(),
+            logs_follow: self.logs_follow,
+            logs_scroll: self.logs_scroll,
+            dns_records: self.dns_records.clone(),
+            records_page: self.records_page,
+            modify_options: self.modify_options.clone(),
+            show_modify_picker: self.show_modify_picker,
+            selected_modify_option: self.selected_modify_option,
+            pending_modification: self.pending_modification.clone(),
+            pending_request: self.pending_request.clone(),
+            marked_resources: self.marked_resources.clone(),
+            bulk_actions: self.bulk_actions.clone(),
+            show_bulk_picker: self.show_bulk_picker,
+            selected_bulk_action: self.selected_bulk_action,
+            columns: self.columns.clone(),
+            show_column_picker: self.show_column_picker,
+            selected_column: self.selected_column,
+            pending_bulk_action: self.pending_bulk_action,
+            require_snapshot: self.require_snapshot,
+            snapshot_choice: self.snapshot_choice,
+            pending_unprotect: self.pending_unprotect,
+            deletion_blockers: self.deletion_blockers.clone(),
+            prompt_title: self.prompt_title.clone(),
+            prompt_input: self.prompt_input.clone(),
+            prompt_action: self.prompt_action,
+            image_jobs: self.image_jobs.clone(),
+            operations: self.operations.clone(),
+            tag_editor: self.tag_editor.clone(),
+            create_wizard: self.create_wizard.clone(),
+            region_picker: self.region_picker.clone(),
+            account_picker: self.account_picker.clone(),
+            account_switch: None,
+            identities: self.identities.clone(),
+            account_names: self.account_names.clone(),
+            capabilities: self.capabilities.clone(),
+            cost_history: self.cost_history.clone(),
+            cost_history_loaded_at: self.cost_history_loaded_at,
+            cost_snapshots: self.cost_snapshots.clone(),
+            cost_period: self.cost_period,
+            show_period_picker: self.show_period_picker,
+            selected_period: self.selected_period,
+            period_input: self.period_input.clone(),
+            cost_tag_keys: self.cost_tag_keys.clone(),
+            cost_by_tag: self.cost_by_tag.clone(),
+            commitments: self.commitments.clone(),
+            budgets: self.budgets.clone(),
+            cost_forecast: self.cost_forecast,
+            anomalies: self.anomalies.clone(),
+            quotas: self.quotas.clone(),
+            waste: self.waste.clone(),
+            tag_policy: self.tag_policy.clone(),
+            cost_alert_policy: self.cost_alert_policy.clone(),
+            cost_alerts: self.cost_alerts.clone(),
+            cache_stats: self.cache_stats.clone(),
+            show_help: self.show_help,
+            help_scroll: self.help_scroll,
+            notifications: self.notifications.clone(),
+            show_notifications: self.show_notifications,
+            notifications_scroll: self.notifications_scroll,
+            inventory_changes: self.inventory_changes.clone(),
         }
     }
 }
@@ -481,6 +3099,525 @@ mod tests {
         assert_eq!(age.unwrap(), "just now");
     }
 
+    #[test]
+    fn test_provider_for_resource_without_sources() {
+        let state = AppState::new();
+        assert!(state.provider_for_resource(0).is_none());
+    }
+
+    #[test]
+    fn test_detail_tab_cycle() {
+        let mut state = AppState::new();
+        assert_eq!(state.detail_tab, DetailTab::Overview);
+
+        state.next_detail_tab();
+        assert_eq!(state.detail_tab, DetailTab::Metrics);
+
+        state.next_detail_tab();
+        state.next_detail_tab();
+        assert_eq!(state.detail_tab, DetailTab::Raw);
+
+        state.next_detail_tab();
+        assert_eq!(state.detail_tab, DetailTab::Overview);
+
+        state.prev_detail_tab();
+        assert_eq!(state.detail_tab, DetailTab::Raw);
+
+        state.next_detail_tab();
+        state.exit_detail_view();
+        assert_eq!(state.detail_tab, DetailTab::Overview);
+    }
+
+    #[test]
+    fn test_capabilities_hide_metrics_and_cost() {
+        let mut state = AppState::new();
+        state.resource_sources = vec![0];
+        state.filtered_resources = vec![0];
+        state.capabilities = vec![ProviderCapabilities::none()];
+
+        assert_eq!(state.detail_tabs(), vec![DetailTab::Overview, DetailTab::Raw]);
+        state.next_detail_tab();
+        assert_eq!(state.detail_tab, DetailTab::Raw);
+
+        state.capabilities = vec![ProviderCapabilities::default()];
+        state.next_detail_tab();
+        state.next_detail_tab();
+        assert_eq!(state.detail_tab, DetailTab::Metrics);
+
+        // Without providers there is nothing to report
+        assert!(!state.supports_cost(CostFeature::History));
+        assert!(!state.any_provider_supports(Action::Create));
+    }
+
+    #[test]
+    fn test_confirm_unprotect() {
+        let mut state = AppState::new();
+        state.confirm_unprotect("web", Some(true));
+        assert!(state.show_confirmation);
+        assert!(state.pending_unprotect);
+        assert!(state.wants_snapshot());
+
+        state.cancel_confirmation();
+        assert!(!state.pending_unprotect);
+        assert!(!state.wants_snapshot());
+    }
+
+    #[test]
+    fn test_period_picker() {
+        let mut state = AppState::new();
+        state.set_cost_history(Vec::new());
+        state.open_period_picker();
+        assert_eq!(state.highlighted_period(), Some(CostPeriod::Last30Days));
+
+        state.next_period();
+        assert_eq!(state.highlighted_period(), None);
+        state.next_period();
+        assert_eq!(state.highlighted_period(), Some(CostPeriod::ThisWeek));
+        state.prev_period();
+        state.prev_period();
+        assert_eq!(state.highlighted_period(), Some(CostPeriod::Last30Days));
+
+        let custom = CostPeriod::parse_range("2024-01-01 to 2024-01-31").unwrap();
+        state.set_cost_period(custom);
+        assert!(!state.show_period_picker);
+        assert_eq!(state.cost_period, custom);
+        assert!(state.cost_history_stale());
+
+        state.open_period_picker();
+        assert_eq!(state.highlighted_period(), None);
+    }
+
+    #[test]
+    fn test_cost_history_stale() {
+        let mut state = AppState::new();
+        assert!(state.cost_history_stale());
+
+        state.set_cost_history(Vec::new());
+        assert!(!state.cost_history_stale());
+
+        state.cost_history_loaded_at = Some(Utc::now() - chrono::Duration::hours(COST_HISTORY_TTL_HOURS + 1));
+        assert!(state.cost_history_stale());
+    }
+
+    #[test]
+    fn test_set_cost_by_tag() {
+        let mut state = AppState::new();
+        let account_a: HashMap<String, f64> = [("payments".to_string(), 10.0), ("search".to_string(), 5.0)].into();
+        let account_b: HashMap<String, f64> = [("search".to_string(), 20.0)].into();
+
+        state.set_cost_by_tag("team", vec![account_a, account_b]);
+        assert_eq!(
+            state.cost_by_tag,
+            vec![("team".to_string(), vec![("search".to_string(), 25.0), ("payments".to_string(), 10.0)])]
+        );
+
+        state.set_cost_by_tag("team", Vec::new());
+        assert_eq!(state.cost_by_tag, vec![("team".to_string(), Vec::new())]);
+    }
+
+    #[test]
+    fn test_metrics_stale_for() {
+        let mut state = AppState::new();
+        assert!(state.metrics_stale_for("i-123"));
+
+        state.set_metrics("i-123".to_string(), Vec::new());
+        assert!(!state.metrics_stale_for("i-123"));
+        assert!(state.metrics_stale_for("i-456"));
+    }
+
+    #[test]
+    fn test_append_logs_keeps_position_when_not_following() {
+        let mut state = AppState::new();
+        state.open_logs_tab();
+        assert_eq!(state.detail_tab, DetailTab::Logs);
+
+        let now = Utc::now();
+        state.append_logs((0..5).map(|i| LogEntry::new(now, format!("line {}", i))).collect());
+        assert_eq!(state.logs_scroll, 0);
+
+        state.scroll_logs_up(2);
+        assert!(!state.logs_follow);
+        state.append_logs(vec![LogEntry::new(now, "line 5")]);
+        assert_eq!(state.logs_scroll, 3);
+
+        state.toggle_log_follow();
+        assert!(state.logs_follow);
+        assert_eq!(state.logs_scroll, 0);
+
+        state.next_detail_tab();
+        assert_eq!(state.detail_tab, DetailTab::Raw);
+        assert!(state.logs.is_empty());
+    }
+
+    #[test]
+    fn test_append_logs_trims_to_max() {
+        let mut state = AppState::new();
+        let now = Utc::now();
+        state.append_logs((0..MAX_LOG_LINES + 10).map(|i| LogEntry::new(now, i.to_string())).collect());
+
+        assert_eq!(state.logs.len(), MAX_LOG_LINES);
+        assert_eq!(state.logs[0].message, "10");
+    }
+
+    #[test]
+    fn test_records_paging() {
+        let mut state = AppState::new();
+        state.enter_records_view();
+        assert_eq!(state.view_mode, ViewMode::Records);
+        assert_eq!(state.records_page_count(), 1);
+        assert!(state.current_records_page().is_empty());
+
+        state.set_dns_records(
+            (0..RECORDS_PAGE_SIZE + 5)
+                .map(|i| DnsRecord {
+                    name: format!("host{}.example.com.", i),
+                    record_type: "A".to_string(),
+                    ttl: Some(300),
+                    values: vec!["192.0.2.1".to_string()],
+                })
+                .collect(),
+        );
+        assert_eq!(state.records_page_count(), 2);
+        assert_eq!(state.current_records_page().len(), RECORDS_PAGE_SIZE);
+
+        state.next_records_page();
+        state.next_records_page();
+        assert_eq!(state.records_page, 1);
+        assert_eq!(state.current_records_page().len(), 5);
+
+        state.prev_records_page();
+        assert_eq!(state.records_page, 0);
+
+        state.exit_records_view();
+        assert_eq!(state.view_mode, ViewMode::ResourceDetail);
+        assert!(state.dns_records.is_empty());
+    }
+
+    #[test]
+    fn test_quotas_sorted_by_usage() {
+        let mut state = AppState::new();
+        state.enter_quotas_view();
+        assert_eq!(state.view_mode, ViewMode::Quotas);
+
+        state.set_quotas(vec![
+            ("AWS".to_string(), Quota::new("VPC", "VPCs per Region", 1.0, 5.0)),
+            ("AWS".to_string(), Quota::new("EC2", "Elastic IPs", 5.0, 5.0)),
+            ("AWS".to_string(), Quota::new("EC2", "On-Demand Standard vCPUs", 20.0, 32.0)),
+        ]);
+        let names: Vec<&str> = state.quotas.iter().map(|(_, q)| q.name.as_str()).collect();
+        assert_eq!(names, vec!["Elastic IPs", "On-Demand Standard vCPUs", "VPCs per Region"]);
+        assert_eq!(state.quotas_near_limit(), 1);
+
+        state.exit_quotas_view();
+        assert_eq!(state.view_mode, ViewMode::Dashboard);
+    }
+
+    #[test]
+    fn test_waste_sorted_by_cost() {
+        use crate::core::WasteKind;
+
+        let mut state = AppState::new();
+        state.enter_waste_view();
+        assert_eq!(state.view_mode, ViewMode::Waste);
+
+        state.set_waste(vec![
+            (
+                "AWS".to_string(),
+                WasteFinding::new(WasteKind::EmptyVersionedBucket, "logs", "logs", "us-east-1"),
+            ),
+            (
+                "AWS".to_string(),
+                WasteFinding::new(WasteKind::UnattachedVolume, "vol-1", "data", "us-east-1").with_monthly_cost(8.0),
+            ),
+        ]);
+        assert_eq!(state.waste[0].1.resource_id, "vol-1");
+        assert_eq!(state.waste_findings().len(), 2);
+
+        state.toggle_view_mode();
+        assert_eq!(state.view_mode, ViewMode::Dashboard);
+    }
+
+    #[test]
+    fn test_modify_picker_flow() {
+        let mut state = AppState::new();
+        state.open_modify_picker(vec![
+            ModifyOption::new("t3.small", "t3.small"),
+            ModifyOption::new("t3.large", "t3.large"),
+        ]);
+        assert!(state.show_modify_picker);
+
+        state.prev_modify_option();
+        assert_eq!(state.selected_modify_option, 1);
+
+        state.confirm_modify_option("Resize?".to_string());
+        assert!(!state.show_modify_picker);
+        assert!(state.show_confirmation);
+        assert_eq!(state.pending_modification.as_ref().map(|o| o.value.as_str()), Some("t3.large"));
+
+        state.cancel_confirmation();
+        assert!(state.pending_modification.is_none());
+    }
+
+    #[test]
+    fn test_bulk_action_flow() {
+        let mut state = AppState::new();
+        state.toggle_mark("i-1");
+        state.toggle_mark("i-2");
+        state.toggle_mark("i-3");
+        state.toggle_mark("i-2");
+        assert!(state.is_marked("i-1"));
+        assert!(!state.is_marked("i-2"));
+        assert_eq!(state.marked_resources.len(), 2);
+
+        state.open_bulk_picker(vec![Action::Stop, Action::Terminate]);
+        state.next_bulk_action();
+        assert_eq!(state.highlighted_bulk_action(), Some(Action::Terminate));
+
+        state.confirm_bulk_action("Terminate 2 resources?".to_string());
+        assert!(!state.show_bulk_picker);
+        assert!(state.show_confirmation);
+        assert_eq!(state.pending_bulk_action, Some(Action::Terminate));
+
+        state.cancel_confirmation();
+        assert!(state.pending_bulk_action.is_none());
+        assert_eq!(state.marked_resources.len(), 2);
+
+        state.clear_marks();
+        assert!(state.marked_resources.is_empty());
+    }
+
+    #[test]
+    fn test_confirm_request() {
+        let mut state = AppState::new();
+        state.confirm_request(
+            "Scale 'checkout'?".to_string(),
+            ActionRequest::new(Action::Scale).with_desired_capacity(0),
+        );
+        assert!(state.show_confirmation);
+        assert_eq!(
+            state.pending_request.as_ref().and_then(|r| r.params.desired_capacity),
+            Some(0)
+        );
+
+        state.cancel_confirmation();
+        assert!(state.pending_request.is_none());
+    }
+
+    #[test]
+    fn test_deletion_blockers() {
+        let mut state = AppState::new();
+        state.show_action_confirmation("Terminate 'logs'?".to_string());
+        assert!(state.can_confirm());
+
+        state.set_deletion_blockers(vec!["The bucket still holds objects. Empty it first.".to_string()]);
+        assert!(!state.can_confirm());
+
+        state.cancel_confirmation();
+        assert!(state.deletion_blockers.is_empty());
+        assert!(!state.can_confirm());
+    }
+
+    #[test]
+    fn test_snapshot_choice() {
+        let mut state = AppState::new();
+        assert!(!state.wants_snapshot());
+
+        state.offer_snapshot();
+        assert_eq!(state.snapshot_choice, Some(false));
+        state.toggle_snapshot_choice();
+        assert!(state.wants_snapshot());
+
+        state.cancel_confirmation();
+        assert_eq!(state.snapshot_choice, None);
+
+        let mut state = AppState::new().with_snapshot_before_terminate(true);
+        state.offer_snapshot();
+        state.toggle_snapshot_choice();
+        assert!(state.wants_snapshot());
+    }
+
+    #[test]
+    fn test_prompt_roundtrip() {
+        let mut state = AppState::new();
+        state.open_prompt(Action::CreateImage, "Image name".to_string(), "web".to_string());
+        assert!(state.is_prompting());
+
+        state.prompt_input.push_str("-1");
+        assert_eq!(state.take_prompt(), Some((Action::CreateImage, "web-1".to_string())));
+        assert!(!state.is_prompting());
+        assert_eq!(state.take_prompt(), None);
+    }
+
+    #[test]
+    fn test_is_typing() {
+        let mut state = AppState::new();
+        assert!(!state.is_typing());
+
+        state.open_prompt(Action::CreateImage, "Image name".to_string(), String::new());
+        assert!(state.is_typing());
+        state.take_prompt();
+
+        // The custom cost range is typed without an input mode
+        state.period_input = Some(String::new());
+        assert!(state.is_typing());
+    }
+
+    #[test]
+    fn test_operation_tracking() {
+        use crate::core::{ResourceState, ResourceType};
+
+        let mut state = AppState::new();
+        let job = |action: Action| OperationJob {
+            handle: OperationHandle::new("i-123", ResourceType::Compute, &action.into()),
+            resource_name: "web".to_string(),
+            provider_idx: 0,
+            status: OperationStatus::InProgress(ResourceState::Running),
+        };
+
+        state.track_operation(job(Action::Stop));
+        state.track_operation(job(Action::Start));
+        assert_eq!(state.operations.len(), 1);
+        assert_eq!(state.operation_for("i-123").map(|job| job.handle.action), Some(Action::Start));
+
+        assert!(state
+            .update_operation("i-123", OperationStatus::InProgress(ResourceState::Starting))
+            .is_none());
+        let done = state.update_operation("i-123", OperationStatus::Completed);
+        assert_eq!(done.map(|job| job.status), Some(OperationStatus::Completed));
+        assert!(state.operation_for("i-123").is_none());
+    }
+
+    #[test]
+    fn test_image_job_tracking() {
+        let mut state = AppState::new();
+        state.track_image(ImageJob {
+            image_id: "ami-123".to_string(),
+            name: "web".to_string(),
+            provider_idx: 0,
+            state: "pending".to_string(),
+        });
+
+        assert!(state.update_image_state("ami-123", "pending".to_string()).is_none());
+        assert_eq!(state.image_jobs.len(), 1);
+
+        let done = state.update_image_state("ami-123", "available".to_string());
+        assert_eq!(done.map(|job| job.state), Some("available".to_string()));
+        assert!(state.image_jobs.is_empty());
+        assert!(state.update_image_state("ami-unknown", "available".to_string()).is_none());
+    }
+
+    #[test]
+    fn test_tag_editor_changes() {
+        let mut original = HashMap::new();
+        original.insert("Env".to_string(), "dev".to_string());
+        original.insert("Name".to_string(), "web".to_string());
+
+        let mut editor = TagEditor::new(original);
+        assert_eq!(editor.tags[0].0, "Env");
+
+        editor.set("Env".to_string(), "prod".to_string());
+        editor.set("Team".to_string(), "core".to_string());
+        assert_eq!(editor.selected, 2);
+
+        editor.prev();
+        editor.remove_selected();
+
+        let changes = editor.changes();
+        assert_eq!(changes.remove, vec!["Name".to_string()]);
+        assert_eq!(changes.set.get("Env").map(String::as_str), Some("prod"));
+        assert_eq!(changes.set.get("Team").map(String::as_str), Some("core"));
+    }
+
+    #[test]
+    fn test_region_picker() {
+        let regions = vec!["us-east-1".to_string(), "us-west-2".to_string(), "eu-west-1".to_string()];
+        let mut picker = RegionPicker::new(0, "AWS", regions, "us-west-2");
+        assert_eq!(picker.selected, 1);
+        assert_eq!(picker.chosen(), None);
+
+        picker.next();
+        picker.next();
+        assert_eq!(picker.chosen(), Some("us-east-1"));
+        picker.prev();
+        assert_eq!(picker.chosen(), Some("eu-west-1"));
+
+        let unknown = RegionPicker::new(0, "AWS", vec!["us-east-1".to_string()], "local");
+        assert_eq!(unknown.chosen(), Some("us-east-1"));
+    }
+
+    #[test]
+    fn test_account_picker() {
+        let configured = vec![
+            AwsConfig {
+                label: Some("prod".to_string()),
+                profile: Some("production".to_string()),
+                ..AwsConfig::default()
+            },
+            AwsConfig::default(),
+        ];
+        let profiles = vec!["default".to_string(), "production".to_string(), "sandbox".to_string()];
+        let mut picker = AccountPicker::new(0, "default", "eu-west-1", &configured, profiles);
+
+        let labels: Vec<&str> = picker.choices.iter().map(|choice| choice.label.as_str()).collect();
+        assert_eq!(labels, vec!["prod", "default", "sandbox"]);
+        assert_eq!(picker.selected, 1);
+        assert!(picker.chosen().is_none());
+
+        picker.next();
+        let sandbox = picker.chosen().unwrap();
+        assert!(sandbox.detected);
+        assert_eq!(sandbox.config.profile.as_deref(), Some("sandbox"));
+        assert_eq!(sandbox.config.region, "eu-west-1");
+
+        picker.next();
+        assert_eq!(picker.chosen().unwrap().label, "prod");
+    }
+
+    #[test]
+    fn test_create_wizard_flow() {
+        let options = CreateOptions {
+            images: vec![ModifyOption::new("ami-1", "al2023 (ami-1)"), ModifyOption::new("ami-2", "ubuntu (ami-2)")],
+            instance_types: vec![
+                ModifyOption::new("t3.micro", "t3.micro").with_monthly_cost(Some(7.59)),
+                ModifyOption::new("t3.small", "t3.small").with_monthly_cost(Some(15.18)),
+            ],
+            key_pairs: vec![ModifyOption::new("ops", "ops")],
+        };
+        let mut wizard = CreateWizard::new(0, options);
+        assert_eq!(wizard.choices().len(), 2);
+
+        wizard.next();
+        wizard.choose();
+        assert_eq!(wizard.step, CreateStep::InstanceType);
+        wizard.prev();
+        wizard.choose();
+
+        // The key pair list starts with the option to launch without one
+        assert_eq!(wizard.choices().len(), 2);
+        wizard.choose();
+        assert!(wizard.step.is_text());
+
+        wizard.set_name(" web-1 ");
+        wizard.set_tags("team=platform");
+        assert_eq!(wizard.step, CreateStep::Review);
+
+        let spec = wizard.spec();
+        assert_eq!(spec.image, "ami-2");
+        assert_eq!(spec.instance_type, "t3.small");
+        assert_eq!(spec.key_pair, None);
+        assert_eq!(spec.name, "web-1");
+        assert_eq!(wizard.monthly_cost(), Some(15.18));
+        assert_eq!(wizard.review_lines().last().unwrap(), "Estimated cost: $15.18/month");
+
+        assert!(wizard.back());
+        assert_eq!(wizard.tags_input(), "team=platform");
+        assert!(wizard.back() && wizard.back());
+        assert_eq!(wizard.step, CreateStep::KeyPair);
+        assert!(wizard.back() && wizard.back());
+        assert_eq!(wizard.selected, 1);
+        assert!(!wizard.back());
+    }
+
     #[test]
     fn test_is_using_cache() {
         let mut state = AppState::new().with_cache_enabled(true);
@@ -489,4 +3626,384 @@ mod tests {
         state.last_refresh = Some(Utc::now());
         assert!(state.is_using_cache());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_identity_summary() {
+        let mut state = AppState::new();
+        assert_eq!(state.identity_summary(), None);
+
+        state.identities = vec![AccountIdentity::new("111111111111", "arn:aws:iam::111111111111:user/alice")
+            .with_alias(Some("prod".to_string()))];
+        assert_eq!(
+            state.identity_summary().as_deref(),
+            Some("prod (111111111111) · arn:aws:iam::111111111111:user/alice")
+        );
+
+        state.identities.push(AccountIdentity::new("222222222222", "arn:aws:iam::222222222222:user/alice"));
+        assert_eq!(state.identity_summary().as_deref(), Some("prod (111111111111) | 222222222222"));
+    }
+
+    struct TaggedResource {
+        name: String,
+        region: String,
+        cost: Option<f64>,
+        tags: HashMap<String, String>,
+    }
+
+    impl CloudResource for TaggedResource {
+        fn id(&self) -> &str {
+            &self.name
+        }
+
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn resource_type(&self) -> ResourceType {
+            ResourceType::Compute
+        }
+
+        fn provider(&self) -> crate::core::Provider {
+            crate::core::Provider::AWS
+        }
+
+        fn region(&self) -> &str {
+            &self.region
+        }
+
+        fn state(&self) -> crate::core::ResourceState {
+            crate::core::ResourceState::Running
+        }
+
+        fn cost_per_month(&self) -> Option<f64> {
+            self.cost
+        }
+
+        fn tags(&self) -> &HashMap<String, String> {
+            &self.tags
+        }
+
+        fn created_at(&self) -> Option<DateTime<Utc>> {
+            None
+        }
+
+        fn supported_actions(&self) -> Vec<Action> {
+            Vec::new()
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    #[test]
+    fn test_group_rows() {
+        let resource = |name: &str, region: &str, cost: f64, team: Option<&str>| -> Box<dyn CloudResource> {
+            Box::new(TaggedResource {
+                name: name.to_string(),
+                region: region.to_string(),
+                cost: Some(cost),
+                tags: team.map(|team| ("team".to_string(), team.to_string())).into_iter().collect(),
+            })
+        };
+        let resources = vec![
+            resource("web-1", "us-west-2", 10.0, Some("web")),
+            resource("web-2", "us-east-1", 20.0, Some("web")),
+            resource("batch", "us-west-2", 5.0, None),
+        ];
+        let mut state = AppState::new();
+        state.filtered_resources = vec![0, 1, 2];
+
+        let rows = state.group_rows(&GroupBy::Region, &resources);
+        assert_eq!(
+            rows,
+            vec![
+                ListRow::Group { name: "us-east-1".to_string(), count: 1, cost: 20.0, collapsed: false },
+                ListRow::Resource(1),
+                ListRow::Group { name: "us-west-2".to_string(), count: 2, cost: 15.0, collapsed: false },
+                ListRow::Resource(0),
+                ListRow::Resource(2),
+            ]
+        );
+
+        state.collapsed_groups.insert("web".to_string());
+        let rows = state.group_rows(&GroupBy::Tag("team".to_string()), &resources);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0], ListRow::Group { name: "(untagged)".to_string(), count: 1, cost: 5.0, collapsed: false });
+        assert_eq!(rows[2], ListRow::Group { name: "web".to_string(), count: 2, cost: 30.0, collapsed: true });
+
+        state.group_by = Some(GroupBy::Region);
+        state.list_rows = state.group_rows(&GroupBy::Region, &resources);
+        assert_eq!(state.selected_group(), Some("us-east-1"));
+        assert_eq!(state.get_selected_resource_index(), None);
+        state.next_resource();
+        assert_eq!(state.get_selected_resource_index(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_toggle_mark_all() {
+        let mut state = AppState::new();
+        *state.resources.write().await = ["web-1", "web-2", "batch"]
+            .iter()
+            .map(|name| -> Box<dyn CloudResource> {
+                Box::new(TaggedResource {
+                    name: name.to_string(),
+                    region: "us-east-1".to_string(),
+                    cost: None,
+                    tags: HashMap::new(),
+                })
+            })
+            .collect();
+        state.filtered_resources = vec![0, 1];
+
+        state.toggle_mark("web-1");
+        state.toggle_mark_all().await;
+        assert!(state.is_marked("web-1") && state.is_marked("web-2"));
+        assert!(!state.is_marked("batch"));
+
+        state.toggle_mark_all().await;
+        assert!(state.marked_resources.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search() {
+        let mut state = AppState::new();
+        *state.resources.write().await = ["web-1", "batch", "web-2"]
+            .iter()
+            .map(|name| -> Box<dyn CloudResource> {
+                Box::new(TaggedResource {
+                    name: name.to_string(),
+                    region: "us-east-1".to_string(),
+                    cost: None,
+                    tags: HashMap::new(),
+                })
+            })
+            .collect();
+        state.filtered_resources = vec![0, 1, 2];
+        state.selected_index = 1;
+
+        state.enter_search_mode();
+        state.push_search_char('W').await;
+        assert_eq!(state.selected_index, 2);
+        state.exit_search_mode(true);
+
+        assert!(state.search_next(true).await);
+        assert_eq!(state.selected_index, 0);
+        assert!(state.search_next(false).await);
+        assert_eq!(state.selected_index, 2);
+
+        state.enter_search_mode();
+        state.push_search_char('x').await;
+        assert_eq!(state.selected_index, 2);
+        state.exit_search_mode(false);
+        assert!(state.search_text.is_empty());
+        assert!(!state.search_next(true).await);
+    }
+
+    // Applying hits re-runs the filter, which blocks in place on the runtime
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_poll_search() {
+        let mut state = AppState::new();
+        state.filter_text = "w".to_string();
+        let (sender, receiver) = oneshot::channel();
+        state.start_search("w".to_string(), receiver);
+        assert!(state.poll_search().is_none());
+
+        sender.send(Ok(vec!["i-1".to_string()])).unwrap();
+        assert!(matches!(state.poll_search(), Some(Ok(()))));
+        assert!(state.search_hits.as_ref().is_some_and(|hits| hits.contains("i-1")));
+        assert!(state.poll_search().is_none());
+
+        // Hits for text that has since been edited are dropped
+        let (sender, receiver) = oneshot::channel();
+        state.start_search("w".to_string(), receiver);
+        state.filter_text.push('e');
+        sender.send(Ok(vec!["i-2".to_string()])).unwrap();
+        assert!(matches!(state.poll_search(), Some(Ok(()))));
+        assert!(state.search_hits.as_ref().is_some_and(|hits| !hits.contains("i-2")));
+    }
+
+    #[test]
+    fn test_detail_pane_scroll() {
+        let mut state = AppState::new();
+        state.scroll_detail_pane_down(5, 10);
+        assert_eq!((state.metadata_scroll, state.tags_scroll), (0, 0));
+
+        state.prev_detail_pane();
+        assert_eq!(state.detail_pane, DetailPane::Tags);
+        state.scroll_detail_pane_down(5, 10);
+        state.scroll_detail_pane_down(5, 10);
+        assert_eq!(state.tags_scroll, 9);
+        state.scroll_detail_pane_up(3);
+        assert_eq!(state.tags_scroll, 6);
+
+        state.prev_detail_pane();
+        state.scroll_detail_pane_down(1, 10);
+        assert_eq!((state.detail_pane, state.metadata_scroll), (DetailPane::Metadata, 1));
+
+        state.filtered_resources = vec![0];
+        state.enter_detail_view();
+        assert_eq!(state.detail_pane, DetailPane::Actions);
+        assert_eq!((state.metadata_scroll, state.tags_scroll), (0, 0));
+    }
+
+    #[test]
+    fn test_paging() {
+        let mut state = AppState::new();
+        state.filtered_resources = (0..50).collect();
+
+        state.page_down(20);
+        state.page_down(20);
+        assert_eq!(state.selected_index, 40);
+        state.page_down(20);
+        assert_eq!(state.selected_index, 49);
+        state.page_up(30);
+        assert_eq!(state.selected_index, 19);
+        state.select_first_resource();
+        assert_eq!(state.selected_index, 0);
+        state.select_last_resource();
+        assert_eq!(state.get_selected_resource_index(), Some(49));
+    }
+
+    #[test]
+    fn test_column_picker() {
+        let columns = ListColumn::parse_list(&["name".to_string(), "Tags".to_string(), "cost".to_string()]).unwrap();
+        let mut state = AppState::new().with_columns(&columns);
+        assert_eq!(state.visible_columns(), vec![ListColumn::Name, ListColumn::Tags, ListColumn::Cost]);
+        assert_eq!(state.columns.len(), ListColumn::all().len());
+
+        state.open_column_picker();
+        state.next_column();
+        state.move_column(false);
+        assert_eq!(state.visible_columns(), vec![ListColumn::Tags, ListColumn::Name, ListColumn::Cost]);
+        state.toggle_column();
+        assert_eq!(state.visible_columns(), vec![ListColumn::Name, ListColumn::Cost]);
+        state.prev_column();
+        assert_eq!(state.columns[state.selected_column].0, ListColumn::Account);
+
+        state.columns.iter_mut().for_each(|(_, shown)| *shown = false);
+        state.columns[0].1 = true;
+        state.selected_column = 0;
+        state.toggle_column();
+        assert_eq!(state.visible_columns().len(), 1);
+
+        assert!(ListColumn::parse_list(&["owner".to_string()]).is_err());
+        assert_eq!(ListColumn::parse_list(&[]).unwrap(), ListColumn::defaults());
+    }
+
+    #[test]
+    fn test_context_picker() {
+        let mut state = AppState::new().with_contexts(vec!["dev".to_string()], Some("dev".to_string()));
+        state.open_context_picker();
+        assert!(!state.show_context_picker);
+
+        state.contexts = vec!["dev".to_string(), "prod".to_string(), "staging".to_string()];
+        state.active_context = Some("prod".to_string());
+        state.open_context_picker();
+        assert!(state.show_context_picker);
+        assert_eq!(state.highlighted_context(), Some("prod"));
+
+        state.next_context();
+        state.next_context();
+        assert_eq!(state.highlighted_context(), Some("dev"));
+        state.prev_context();
+        assert_eq!(state.highlighted_context(), Some("staging"));
+    }
+
+    #[test]
+    fn test_typed_termination_confirmation() {
+        let mut state = AppState::new();
+        state.show_action_confirmation("Terminate 'web-1'?".to_string());
+        state.require_termination_confirmation(&[("web-1", "i-0abc1234")]);
+        assert!(state.needs_typed_confirmation());
+        assert!(!state.can_confirm());
+
+        for c in "web-".chars() {
+            state.push_confirm_char(c);
+        }
+        assert!(!state.can_confirm());
+        state.push_confirm_char('1');
+        assert!(state.can_confirm());
+
+        state.confirm_input = "1234".to_string();
+        assert!(state.can_confirm());
+        state.pop_confirm_char();
+        assert!(!state.can_confirm());
+
+        state.cancel_confirmation();
+        assert!(!state.needs_typed_confirmation());
+        state.show_action_confirmation("Stop 'web-1'?".to_string());
+        assert!(state.can_confirm());
+
+        state.show_action_confirmation("Terminate these 2 resources?".to_string());
+        state.require_termination_confirmation(&[("web-1", "i-0abc1234"), ("web-2", "i-0def5678")]);
+        state.confirm_input = "2".to_string();
+        assert!(!state.can_confirm());
+        state.confirm_input = "web-1".to_string();
+        assert!(!state.can_confirm());
+        state.confirm_input = "terminate 2".to_string();
+        assert!(state.can_confirm());
+    }
+
+    #[test]
+    fn test_notifications() {
+        let mut state = AppState::new();
+        state.record_action(ActionRecord::failed("Stop", "Access denied"));
+        state.set_error("Access denied".to_string());
+        state.set_success("Resources refreshed successfully".to_string());
+        state.clear_success();
+
+        let messages: Vec<(&str, bool)> = state
+            .notifications
+            .iter()
+            .map(|n| (n.message.as_str(), n.is_error))
+            .collect();
+        assert_eq!(messages, vec![("Access denied", true), ("Resources refreshed successfully", false)]);
+
+        state.toggle_notifications();
+        state.scroll_notifications_down(5);
+        assert_eq!(state.notifications_scroll, 1);
+
+        for i in 0..MAX_NOTIFICATIONS {
+            state.set_success(format!("Message {}", i));
+        }
+        assert_eq!(state.notifications.len(), MAX_NOTIFICATIONS);
+        assert_eq!(state.notifications[0].message, "Message 0");
+    }
+
+    #[test]
+    fn test_take_unsaved_actions() {
+        let mut state = AppState::new();
+        state.record_action(ActionRecord::succeeded("Stop", "Stop requested for 'web'"));
+        state.record_action(ActionRecord::failed("Start", "Access denied"));
+
+        assert_eq!(state.take_unsaved_actions().len(), 2);
+        assert!(state.take_unsaved_actions().is_empty());
+
+        state.record_action(ActionRecord::succeeded("Refresh", "Resources refreshed successfully"));
+        let unsaved = state.take_unsaved_actions();
+        assert_eq!(unsaved.len(), 1);
+        assert_eq!(unsaved[0].action, "Refresh");
+        assert_eq!(state.last_action().map(|r| r.action.as_str()), Some("Refresh"));
+        assert_eq!(state.action_log.len(), 3);
+    }
+
+    #[test]
+    fn test_refresh_progress() {
+        let mut progress = RefreshProgress::new("AWS (prod)");
+        progress.update("EC2", ListingStatus::Listing);
+        progress.update("RDS", ListingStatus::Listing);
+        progress.update("S3", ListingStatus::Listing);
+        progress.update("EC2", ListingStatus::Done);
+        progress.update("S3", ListingStatus::Failed);
+        assert_eq!(
+            progress.listings,
+            vec![("EC2", ListingStatus::Done), ("RDS", ListingStatus::Listing), ("S3", ListingStatus::Failed)]
+        );
+        assert!(progress.outcome.is_none());
+
+        progress.finish(false);
+        assert_eq!(progress.listings[1], ("RDS", ListingStatus::Done));
+        assert_eq!(progress.outcome, Some(ListingStatus::Done));
+    }
+}
@@ -1,3 +1,5 @@
 pub mod store;
+pub mod view;
 
-pub use store::{CachedResource, CacheStore};
\ No newline at end of file
+pub use store::{AccountListing, CachePartition, CacheStats, CachedResource, CacheStore, SyncSummary};
+pub use view::CachedResourceView;
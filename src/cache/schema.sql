@@ -1,12 +1,19 @@
 -- Nimbus Cache Database Schema
 
+-- Lets INSERT OR REPLACE fire the delete trigger that keeps the search index
+-- in step with the resources table
+PRAGMA recursive_triggers = ON;
+
 CREATE TABLE IF NOT EXISTS resources (
     id TEXT PRIMARY KEY,
     provider TEXT NOT NULL,
     resource_type TEXT NOT NULL,
     region TEXT NOT NULL,
     data TEXT NOT NULL,
-    cached_at INTEGER NOT NULL
+    cached_at INTEGER NOT NULL,
+    -- Name of the account the resource was listed from, empty for rows
+    -- cached before accounts were recorded
+    account TEXT NOT NULL DEFAULT ''
 );
 
 CREATE INDEX IF NOT EXISTS idx_provider ON resources(provider);
@@ -14,6 +21,118 @@ CREATE INDEX IF NOT EXISTS idx_type ON resources(resource_type);
 CREATE INDEX IF NOT EXISTS idx_region ON resources(region);
 CREATE INDEX IF NOT EXISTS idx_cached_at ON resources(cached_at);
 
+-- Full-text index over resource names, IDs and tags, sharing rowids with the
+-- resources table. IDs such as i-0abc or ARNs stay single tokens.
+CREATE VIRTUAL TABLE IF NOT EXISTS resources_fts USING fts5(
+    id,
+    name,
+    resource_type,
+    region,
+    state,
+    tags,
+    tokenize = "unicode61 tokenchars '-_.:/'"
+);
+
+CREATE TRIGGER IF NOT EXISTS resources_fts_insert AFTER INSERT ON resources BEGIN
+    INSERT INTO resources_fts (rowid, id, name, resource_type, region, state, tags) VALUES (
+        new.rowid,
+        new.id,
+        json_extract(new.data, '$.name'),
+        new.resource_type,
+        new.region,
+        json_extract(new.data, '$.state'),
+        (SELECT group_concat(key || ' ' || value, ' ') FROM json_each(new.data, '$.tags'))
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS resources_fts_delete AFTER DELETE ON resources BEGIN
+    DELETE FROM resources_fts WHERE rowid = old.rowid;
+END;
+
+CREATE TRIGGER IF NOT EXISTS resources_fts_update AFTER UPDATE OF data ON resources BEGIN
+    DELETE FROM resources_fts WHERE rowid = old.rowid;
+    INSERT INTO resources_fts (rowid, id, name, resource_type, region, state, tags) VALUES (
+        new.rowid,
+        new.id,
+        json_extract(new.data, '$.name'),
+        new.resource_type,
+        new.region,
+        json_extract(new.data, '$.state'),
+        (SELECT group_concat(key || ' ' || value, ' ') FROM json_each(new.data, '$.tags'))
+    );
+END;
+
+-- Last successful sync of each (provider, region, resource type) slice of the
+-- inventory, so slices expire and refresh independently of each other
+CREATE TABLE IF NOT EXISTS partitions (
+    provider TEXT NOT NULL,
+    region TEXT NOT NULL,
+    resource_type TEXT NOT NULL,
+    synced_at INTEGER NOT NULL,
+    ttl_seconds INTEGER NOT NULL,
+    PRIMARY KEY (provider, region, resource_type)
+);
+
+-- One estimated-spend snapshot per day, the latest refresh of the day winning
+CREATE TABLE IF NOT EXISTS cost_history (
+    day TEXT PRIMARY KEY,
+    recorded_at INTEGER NOT NULL,
+    total REAL NOT NULL,
+    by_service TEXT NOT NULL
+);
+
+-- One row per recorded refresh; inventory rows belong to a generation
+CREATE TABLE IF NOT EXISTS inventory_generations (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    recorded_at INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS inventory (
+    generation INTEGER NOT NULL,
+    id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    resource_type TEXT NOT NULL,
+    provider TEXT NOT NULL,
+    region TEXT NOT NULL,
+    state TEXT NOT NULL,
+    PRIMARY KEY (generation, id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_generation_recorded_at ON inventory_generations(recorded_at);
+
+-- Audit log of actions run from the TUI
+CREATE TABLE IF NOT EXISTS action_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    recorded_at INTEGER NOT NULL,
+    action TEXT NOT NULL,
+    account TEXT,
+    resource_id TEXT,
+    resource_name TEXT,
+    outcome TEXT NOT NULL,
+    message TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_action_log_recorded_at ON action_log(recorded_at);
+
+-- Metric samples fetched for the detail view, so charts survive restarts
+CREATE TABLE IF NOT EXISTS metrics (
+    resource_id TEXT NOT NULL,
+    label TEXT NOT NULL,
+    unit TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    timestamp INTEGER NOT NULL,
+    value REAL NOT NULL,
+    PRIMARY KEY (resource_id, label, timestamp)
+);
+
+CREATE INDEX IF NOT EXISTS idx_metrics_timestamp ON metrics(timestamp);
+
+CREATE TABLE IF NOT EXISTS prices (
+    key TEXT PRIMARY KEY,
+    price_per_hour REAL NOT NULL,
+    cached_at INTEGER NOT NULL
+);
+
 CREATE TABLE IF NOT EXISTS metadata (
     key TEXT PRIMARY KEY,
     value TEXT NOT NULL,
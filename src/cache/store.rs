@@ -1,22 +1,138 @@
-use crate::core::{CloudResource, Provider, ResourceType};
+use crate::cache::view::CachedResourceView;
+use crate::core::{
+    diff_inventories, ActionOutcome, ActionRecord, CloudResource, CostSnapshot, InventoryChange, InventoryEntry,
+    MetricSeries, Provider, ResourceState, ResourceType,
+};
 use crate::error::{NimbusError, Result};
 use chrono::{DateTime, Duration, Utc};
 use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedResource {
     pub id: String,
     pub provider: Provider,
+    /// Account the resource was listed from, empty if it was cached before
+    /// accounts were recorded
+    pub account: String,
     pub resource_type: ResourceType,
     pub data: String,
     pub cached_at: DateTime<Utc>,
 }
 
+impl CachedResource {
+    /// Rebuilds the resource from its cached data for display.
+    pub fn to_view(&self) -> Result<CachedResourceView> {
+        let resource: SerializableResource = serde_json::from_str(&self.data)
+            .map_err(|e| NimbusError::CacheError(format!("Failed to deserialize resource {}: {}", self.id, e)))?;
+        Ok(resource.into_view(self.cached_at))
+    }
+}
+
+/// The slice of cached resources sharing a provider, region and type. Each
+/// partition has its own sync time and TTL, so a refresh can re-fetch only
+/// the partitions that have gone stale.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CachePartition {
+    pub provider: Provider,
+    pub region: String,
+    pub resource_type: ResourceType,
+}
+
+impl CachePartition {
+    pub fn new(provider: Provider, region: impl Into<String>, resource_type: ResourceType) -> Self {
+        Self {
+            provider,
+            region: region.into(),
+            resource_type,
+        }
+    }
+
+    pub fn of(resource: &dyn CloudResource) -> Self {
+        Self::new(resource.provider(), resource.region(), resource.resource_type())
+    }
+}
+
+/// The resources one account listed in a refresh.
+pub struct AccountListing {
+    pub provider: Provider,
+    pub account: String,
+    pub resources: Vec<Box<dyn CloudResource>>,
+    /// Resource types whose listing failed, so what is cached of them is
+    /// kept rather than treated as gone
+    pub failed_types: HashSet<ResourceType>,
+}
+
+impl AccountListing {
+    pub fn new(provider: Provider, account: impl Into<String>, resources: Vec<Box<dyn CloudResource>>) -> Self {
+        Self {
+            provider,
+            account: account.into(),
+            resources,
+            failed_types: HashSet::new(),
+        }
+    }
+
+    pub fn with_failed_types(mut self, failed_types: HashSet<ResourceType>) -> Self {
+        self.failed_types = failed_types;
+        self
+    }
+}
+
+/// What an incremental sync changed in the cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncSummary {
+    pub added: usize,
+    pub removed: usize,
+    /// Resources whose cached fields, such as state or tags, changed
+    pub changed: usize,
+}
+
+impl SyncSummary {
+    pub fn is_empty(&self) -> bool {
+        self.added == 0 && self.removed == 0 && self.changed == 0
+    }
+}
+
+impl fmt::Display for SyncSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} added, {} removed, {} changed", self.added, self.removed, self.changed)
+    }
+}
+
+/// How long refresh inventories are kept for comparison.
+const INVENTORY_RETENTION_DAYS: i64 = 8;
+
+/// How long metric samples are kept.
+const METRICS_RETENTION_DAYS: i64 = 14;
+
+/// Size and age of what the cache holds.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CacheStats {
+    pub total: usize,
+    /// Row count per provider and resource type, largest first
+    pub by_type: Vec<(String, String, usize)>,
+    /// Database size on disk in bytes
+    pub size_bytes: u64,
+    pub oldest: Option<DateTime<Utc>>,
+    pub newest: Option<DateTime<Utc>>,
+    /// Entries older than the cache's maximum age
+    pub expired: usize,
+}
+
+/// SQLite-backed cache. The connection sits behind a mutex so the store can
+/// be shared with blocking tasks; see [`CacheStore::run_blocking`].
 pub struct CacheStore {
-    conn: Connection,
+    conn: Mutex<Connection>,
     max_age: Duration,
+    /// Per-type time to stay fresh, overriding `max_age`
+    ttls: HashMap<ResourceType, Duration>,
+    /// Whether resources past their retention are hidden and pruned
+    expire: bool,
 }
 
 impl CacheStore {
@@ -27,29 +143,153 @@ impl CacheStore {
 
         let conn = Connection::open(db_path)?;
         let store = Self {
-            conn,
+            conn: Mutex::new(conn),
             max_age: Duration::hours(max_age_hours as i64),
+            ttls: HashMap::new(),
+            expire: true,
         };
 
         store.initialize_schema()?;
         Ok(store)
     }
 
+    /// Sets how long each resource type stays fresh. Types without an entry
+    /// use the store's maximum age.
+    pub fn with_ttls(mut self, ttls: HashMap<ResourceType, Duration>) -> Self {
+        self.ttls = ttls;
+        self
+    }
+
+    /// Keeps resources readable past their retention when `expire` is false,
+    /// and stops `prune_expired` from deleting them. Offline mode runs this
+    /// way, since stale data is all it has.
+    pub fn with_expiry(mut self, expire: bool) -> Self {
+        self.expire = expire;
+        self
+    }
+
+    /// How long resources of a type stay fresh before a refetch.
+    pub fn ttl_for(&self, resource_type: ResourceType) -> Duration {
+        self.ttls.get(&resource_type).copied().unwrap_or(self.max_age)
+    }
+
+    /// How long resources of a type are kept: their TTL, but never less
+    /// than the store's maximum age.
+    fn retention_for(&self, resource_type: ResourceType) -> Duration {
+        self.ttl_for(resource_type).max(self.max_age)
+    }
+
+    fn longest_retention(&self) -> Duration {
+        ResourceType::all()
+            .into_iter()
+            .map(|t| self.retention_for(t))
+            .max()
+            .unwrap_or(self.max_age)
+    }
+
+    fn is_retained(&self, cached: &CachedResource) -> bool {
+        !self.expire || cached.cached_at > Utc::now() - self.retention_for(cached.resource_type)
+    }
+
+    /// Oldest `cached_at` timestamp that reads should return.
+    fn read_cutoff(&self) -> i64 {
+        if self.expire {
+            (Utc::now() - self.longest_retention()).timestamp()
+        } else {
+            i64::MIN
+        }
+    }
+
+    /// Runs `f` on tokio's blocking pool so large reads and writes do not
+    /// stall the async task driving the UI.
+    pub async fn run_blocking<T, F>(self: &Arc<Self>, f: F) -> Result<T>
+    where
+        F: FnOnce(&CacheStore) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let store = Arc::clone(self);
+        tokio::task::spawn_blocking(move || f(&store))
+            .await
+            .map_err(|e| NimbusError::CacheError(format!("Cache task failed: {}", e)))?
+    }
+
+    fn conn(&self) -> Result<MutexGuard<'_, Connection>> {
+        self.conn
+            .lock()
+            .map_err(|_| NimbusError::CacheError("Cache connection lock poisoned".to_string()))
+    }
+
     pub fn initialize_schema(&self) -> Result<()> {
         let schema = include_str!("schema.sql");
-        self.conn.execute_batch(schema)?;
+        self.conn()?.execute_batch(schema)?;
+        self.add_account_column_if_needed()?;
+        self.rebuild_search_index_if_needed()?;
         Ok(())
     }
 
-    pub fn cache_resource(&self, resource: &dyn CloudResource) -> Result<()> {
+    /// Adds the account column to caches created before it existed. Their
+    /// rows keep an empty account until the next sync rewrites them.
+    fn add_account_column_if_needed(&self) -> Result<()> {
+        let conn = self.conn()?;
+        let columns = conn
+            .prepare("PRAGMA table_info(resources)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        if !columns.iter().any(|column| column == "account") {
+            conn.execute_batch("ALTER TABLE resources ADD COLUMN account TEXT NOT NULL DEFAULT ''")?;
+        }
+        Ok(())
+    }
+
+    /// Indexes resources cached before the search index existed.
+    fn rebuild_search_index_if_needed(&self) -> Result<()> {
+        let conn = self.conn()?;
+        let cached: usize = conn.query_row("SELECT COUNT(*) FROM resources", [], |row| row.get(0))?;
+        let indexed: usize = conn.query_row("SELECT COUNT(*) FROM resources_fts", [], |row| row.get(0))?;
+        if cached == indexed {
+            return Ok(());
+        }
+
+        conn.execute_batch(
+            "DELETE FROM resources_fts;
+             INSERT INTO resources_fts (rowid, id, name, resource_type, region, state, tags)
+             SELECT rowid, id, json_extract(data, '$.name'), resource_type, region, json_extract(data, '$.state'),
+                    (SELECT group_concat(key || ' ' || value, ' ') FROM json_each(resources.data, '$.tags'))
+             FROM resources;",
+        )?;
+        Ok(())
+    }
+
+    /// Searches cached resources by name, ID, type, region, state, and tag
+    /// keys and values. Every word of the query must match the start of a
+    /// word in the resource. Returns matching resource IDs, best match first.
+    pub fn search_resources(&self, query: &str, limit: usize) -> Result<Vec<String>> {
+        let match_expr = match search_expression(query) {
+            Some(expr) => expr,
+            None => return Ok(Vec::new()),
+        };
+
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id FROM resources_fts WHERE resources_fts MATCH ?1 ORDER BY rank LIMIT ?2",
+        )?;
+
+        let ids = stmt
+            .query_map(params![match_expr, limit as i64], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<String>, _>>()?;
+
+        Ok(ids)
+    }
+
+    pub fn cache_resource(&self, account: &str, resource: &dyn CloudResource) -> Result<()> {
         let serialized_data = serde_json::to_string(&SerializableResource::from_resource(resource))
             .map_err(|e| NimbusError::CacheError(format!("Failed to serialize resource: {}", e)))?;
 
         let cached_at = Utc::now().timestamp();
 
-        self.conn.execute(
-            "INSERT OR REPLACE INTO resources (id, provider, resource_type, region, data, cached_at) 
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        self.conn()?.execute(
+            "INSERT OR REPLACE INTO resources (id, provider, resource_type, region, data, cached_at, account) 
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
             params![
                 resource.id(),
                 resource.provider().as_str(),
@@ -57,24 +297,31 @@ impl CacheStore {
                 resource.region(),
                 serialized_data,
                 cached_at,
+                account,
             ],
         )?;
 
         Ok(())
     }
 
-    pub fn cache_resources(&self, resources: &[Box<dyn CloudResource>]) -> Result<()> {
-        let tx = self.conn.unchecked_transaction()?;
+    /// Writes resources listed from `account` to the cache and marks the
+    /// partitions they belong to as synced with the default TTL.
+    pub fn cache_resources(&self, account: &str, resources: &[Box<dyn CloudResource>]) -> Result<()> {
+        let conn = self.conn()?;
+        let tx = conn.unchecked_transaction()?;
+        let mut partitions = HashSet::new();
 
         for resource in resources {
+            partitions.insert(CachePartition::of(resource.as_ref()));
+
             let serialized_data = serde_json::to_string(&SerializableResource::from_resource(resource.as_ref()))
                 .map_err(|e| NimbusError::CacheError(format!("Failed to serialize resource: {}", e)))?;
 
             let cached_at = Utc::now().timestamp();
 
             tx.execute(
-                "INSERT OR REPLACE INTO resources (id, provider, resource_type, region, data, cached_at) 
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                "INSERT OR REPLACE INTO resources (id, provider, resource_type, region, data, cached_at, account) 
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
                 params![
                     resource.id(),
                     resource.provider().as_str(),
@@ -82,19 +329,215 @@ impl CacheStore {
                     resource.region(),
                     serialized_data,
                     cached_at,
+                    account,
                 ],
             )?;
         }
 
+        let synced_at = Utc::now().timestamp();
+        for partition in &partitions {
+            record_partition_sync(&tx, partition, synced_at, self.ttl_for(partition.resource_type))?;
+        }
+
         tx.commit()?;
         Ok(())
     }
 
+    /// Brings the cache in line with a fresh listing of some accounts: new
+    /// and changed resources are written, unchanged ones only have their
+    /// cache time renewed, and cached resources of those accounts that are
+    /// no longer listed are deleted. Entries of other accounts, and of the
+    /// resource types an account failed to list, are untouched.
+    pub fn sync_resources(&self, listings: &[AccountListing]) -> Result<SyncSummary> {
+        let conn = self.conn()?;
+        let tx = conn.unchecked_transaction()?;
+
+        let mut cached: HashMap<String, String> = HashMap::new();
+        {
+            let mut stmt =
+                tx.prepare("SELECT id, data, resource_type FROM resources WHERE provider = ?1 AND account = ?2")?;
+            for listing in listings {
+                let rows = stmt.query_map(params![listing.provider.as_str(), listing.account], |row| {
+                    Ok((row.get(0)?, row.get(1)?, row.get::<_, String>(2)?))
+                })?;
+                for row in rows {
+                    let (id, data, type_str) = row?;
+                    if !listing.failed_types.contains(&parse_resource_type(&type_str)) {
+                        cached.insert(id, data);
+                    }
+                }
+            }
+        }
+
+        let mut summary = SyncSummary::default();
+        let mut partitions = HashSet::new();
+        let cached_at = Utc::now().timestamp();
+
+        for listing in listings {
+            let account = listing.account.as_str();
+            for resource in &listing.resources {
+                partitions.insert(CachePartition::of(resource.as_ref()));
+                let serialized_data = serde_json::to_string(&SerializableResource::from_resource(resource.as_ref()))
+                    .map_err(|e| NimbusError::CacheError(format!("Failed to serialize resource: {}", e)))?;
+
+                match cached.remove(resource.id()) {
+                    None => summary.added += 1,
+                    Some(previous) if previous != serialized_data => summary.changed += 1,
+                    Some(_) => {
+                        tx.execute(
+                            "UPDATE resources SET cached_at = ?2 WHERE id = ?1",
+                            params![resource.id(), cached_at],
+                        )?;
+                        continue;
+                    }
+                }
+
+                tx.execute(
+                    "INSERT OR REPLACE INTO resources (id, provider, resource_type, region, data, cached_at, account) 
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    params![
+                        resource.id(),
+                        resource.provider().as_str(),
+                        resource.resource_type().as_str(),
+                        resource.region(),
+                        serialized_data,
+                        cached_at,
+                        account,
+                    ],
+                )?;
+            }
+        }
+
+        // Whatever is left was cached but not listed this time
+        for id in cached.keys() {
+            tx.execute("DELETE FROM resources WHERE id = ?1", params![id])?;
+        }
+        summary.removed = cached.len();
+
+        for partition in &partitions {
+            record_partition_sync(&tx, partition, cached_at, self.ttl_for(partition.resource_type))?;
+        }
+
+        tx.commit()?;
+        Ok(summary)
+    }
+
+    /// Returns an inventory entry for each resource the cache holds. Taken
+    /// after a sync, it counts what an account or service failed to list as
+    /// still there rather than terminated.
+    pub fn cached_inventory(&self) -> Result<Vec<InventoryEntry>> {
+        self.get_all_cached_resources()?
+            .iter()
+            .map(|cached| cached.to_view().map(|view| InventoryEntry::from_resource(&view)))
+            .collect()
+    }
+
+    /// Replaces what `account` has cached in one partition with
+    /// `resources`, dropping entries that are no longer listed, and marks it
+    /// synced. Resources outside the partition are ignored.
+    pub fn replace_partition(
+        &self,
+        partition: &CachePartition,
+        account: &str,
+        resources: &[Box<dyn CloudResource>],
+        ttl: Duration,
+    ) -> Result<()> {
+        let conn = self.conn()?;
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM resources WHERE provider = ?1 AND region = ?2 AND resource_type = ?3 AND account = ?4",
+            params![partition.provider.as_str(), partition.region, partition.resource_type.as_str(), account],
+        )?;
+
+        let cached_at = Utc::now().timestamp();
+        for resource in resources.iter().filter(|r| CachePartition::of(r.as_ref()) == *partition) {
+            let serialized_data = serde_json::to_string(&SerializableResource::from_resource(resource.as_ref()))
+                .map_err(|e| NimbusError::CacheError(format!("Failed to serialize resource: {}", e)))?;
+
+            tx.execute(
+                "INSERT OR REPLACE INTO resources (id, provider, resource_type, region, data, cached_at, account) 
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    resource.id(),
+                    resource.provider().as_str(),
+                    resource.resource_type().as_str(),
+                    resource.region(),
+                    serialized_data,
+                    cached_at,
+                    account,
+                ],
+            )?;
+        }
+
+        record_partition_sync(&tx, partition, cached_at, ttl)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Marks a partition as synced now, e.g. after listing it came back
+    /// empty, and sets how long it stays fresh.
+    pub fn mark_partition_synced(&self, partition: &CachePartition, ttl: Duration) -> Result<()> {
+        record_partition_sync(&*self.conn()?, partition, Utc::now().timestamp(), ttl)
+    }
+
+    pub fn get_partition_sync_time(&self, partition: &CachePartition) -> Result<Option<DateTime<Utc>>> {
+        Ok(self
+            .partition_sync(partition)?
+            .and_then(|(synced_at, _)| DateTime::from_timestamp(synced_at, 0)))
+    }
+
+    /// Returns true if the partition has never been synced or its own TTL
+    /// has passed since the last sync.
+    pub fn is_partition_stale(&self, partition: &CachePartition) -> Result<bool> {
+        Ok(match self.partition_sync(partition)? {
+            Some((synced_at, ttl_seconds)) => Utc::now().timestamp() - synced_at > ttl_seconds,
+            None => true,
+        })
+    }
+
+    /// Returns the known partitions of a provider whose TTL has passed.
+    pub fn get_stale_partitions(&self, provider: Provider) -> Result<Vec<CachePartition>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT region, resource_type FROM partitions 
+             WHERE provider = ?1 AND ?2 - synced_at > ttl_seconds
+             ORDER BY region, resource_type",
+        )?;
+
+        let partitions = stmt
+            .query_map(params![provider.as_str(), Utc::now().timestamp()], |row| {
+                let region: String = row.get(0)?;
+                let type_str: String = row.get(1)?;
+                Ok(CachePartition::new(provider, region, parse_resource_type(&type_str)))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(partitions)
+    }
+
+    fn partition_sync(&self, partition: &CachePartition) -> Result<Option<(i64, i64)>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT synced_at, ttl_seconds FROM partitions 
+             WHERE provider = ?1 AND region = ?2 AND resource_type = ?3",
+        )?;
+
+        let sync = stmt
+            .query_row(
+                params![partition.provider.as_str(), partition.region, partition.resource_type.as_str()],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .ok();
+
+        Ok(sync)
+    }
+
     pub fn get_cached_resources(&self, provider: Provider) -> Result<Vec<CachedResource>> {
-        let cutoff_time = (Utc::now() - self.max_age).timestamp();
+        let cutoff_time = self.read_cutoff();
 
-        let mut stmt = self.conn.prepare(
-            "SELECT id, provider, resource_type, data, cached_at 
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, provider, resource_type, data, cached_at, account 
              FROM resources 
              WHERE provider = ?1 AND cached_at > ?2
              ORDER BY cached_at DESC",
@@ -109,6 +552,7 @@ impl CacheStore {
                 Ok(CachedResource {
                     id: row.get(0)?,
                     provider: parse_provider(&provider_str),
+                    account: row.get(5)?,
                     resource_type: parse_resource_type(&type_str),
                     data: row.get(3)?,
                     cached_at: DateTime::from_timestamp(cached_at_timestamp, 0)
@@ -117,14 +561,15 @@ impl CacheStore {
             })?
             .collect::<std::result::Result<Vec<_>, _>>()?;
 
-        Ok(resources)
+        Ok(resources.into_iter().filter(|r| self.is_retained(r)).collect())
     }
 
     pub fn get_all_cached_resources(&self) -> Result<Vec<CachedResource>> {
-        let cutoff_time = (Utc::now() - self.max_age).timestamp();
+        let cutoff_time = self.read_cutoff();
 
-        let mut stmt = self.conn.prepare(
-            "SELECT id, provider, resource_type, data, cached_at 
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, provider, resource_type, data, cached_at, account 
              FROM resources 
              WHERE cached_at > ?1
              ORDER BY cached_at DESC",
@@ -139,6 +584,7 @@ impl CacheStore {
                 Ok(CachedResource {
                     id: row.get(0)?,
                     provider: parse_provider(&provider_str),
+                    account: row.get(5)?,
                     resource_type: parse_resource_type(&type_str),
                     data: row.get(3)?,
                     cached_at: DateTime::from_timestamp(cached_at_timestamp, 0)
@@ -147,11 +593,12 @@ impl CacheStore {
             })?
             .collect::<std::result::Result<Vec<_>, _>>()?;
 
-        Ok(resources)
+        Ok(resources.into_iter().filter(|r| self.is_retained(r)).collect())
     }
 
     pub fn get_last_sync_time(&self, provider: Provider) -> Result<Option<DateTime<Utc>>> {
-        let mut stmt = self.conn.prepare(
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
             "SELECT MAX(cached_at) FROM resources WHERE provider = ?1",
         )?;
 
@@ -162,21 +609,71 @@ impl CacheStore {
 
     pub fn get_cache_count(&self) -> Result<usize> {
         let count: usize = self
-            .conn
+            .conn()?
             .query_row("SELECT COUNT(*) FROM resources", [], |row| row.get(0))?;
         Ok(count)
     }
 
+    pub fn stats(&self) -> Result<CacheStats> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT provider, resource_type, COUNT(*) FROM resources
+             GROUP BY provider, resource_type
+             ORDER BY COUNT(*) DESC, provider, resource_type",
+        )?;
+        let by_type = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<std::result::Result<Vec<(String, String, usize)>, _>>()?;
+
+        let (oldest, newest): (Option<i64>, Option<i64>) = conn.query_row(
+            "SELECT MIN(cached_at), MAX(cached_at) FROM resources",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+
+        let mut expired = 0;
+        for resource_type in ResourceType::all() {
+            let cutoff_time = (Utc::now() - self.retention_for(resource_type)).timestamp();
+            let count: usize = conn.query_row(
+                "SELECT COUNT(*) FROM resources WHERE resource_type = ?1 AND cached_at < ?2",
+                params![resource_type.as_str(), cutoff_time],
+                |row| row.get(0),
+            )?;
+            expired += count;
+        }
+
+        let size_bytes: i64 = conn.query_row(
+            "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+            [],
+            |row| row.get(0),
+        )?;
+
+        Ok(CacheStats {
+            total: by_type.iter().map(|(_, _, count)| count).sum(),
+            by_type,
+            size_bytes: size_bytes.max(0) as u64,
+            oldest: oldest.and_then(|ts| DateTime::from_timestamp(ts, 0)),
+            newest: newest.and_then(|ts| DateTime::from_timestamp(ts, 0)),
+            expired,
+        })
+    }
+
     pub fn clear_cache(&self, provider: Option<Provider>) -> Result<()> {
+        let conn = self.conn()?;
         match provider {
             Some(p) => {
-                self.conn.execute(
+                conn.execute(
                     "DELETE FROM resources WHERE provider = ?1",
                     params![p.as_str()],
                 )?;
+                conn.execute(
+                    "DELETE FROM partitions WHERE provider = ?1",
+                    params![p.as_str()],
+                )?;
             }
             None => {
-                self.conn.execute("DELETE FROM resources", [])?;
+                conn.execute("DELETE FROM resources", [])?;
+                conn.execute("DELETE FROM partitions", [])?;
             }
         }
         Ok(())
@@ -185,7 +682,7 @@ impl CacheStore {
     pub fn prune_old_entries(&self, max_age: Duration) -> Result<usize> {
         let cutoff_time = (Utc::now() - max_age).timestamp();
 
-        let deleted = self.conn.execute(
+        let deleted = self.conn()?.execute(
             "DELETE FROM resources WHERE cached_at < ?1",
             params![cutoff_time],
         )?;
@@ -193,7 +690,343 @@ impl CacheStore {
         Ok(deleted)
     }
 
+    /// Removes entries kept longer than their type's retention, unless
+    /// expiry is turned off.
+    pub fn prune_expired(&self) -> Result<usize> {
+        if !self.expire {
+            return Ok(0);
+        }
+
+        let conn = self.conn()?;
+        let tx = conn.unchecked_transaction()?;
+        let mut deleted = 0;
+        for resource_type in ResourceType::all() {
+            let cutoff_time = (Utc::now() - self.retention_for(resource_type)).timestamp();
+            deleted += tx.execute(
+                "DELETE FROM resources WHERE resource_type = ?1 AND cached_at < ?2",
+                params![resource_type.as_str(), cutoff_time],
+            )?;
+        }
+        tx.commit()?;
+        Ok(deleted)
+    }
+
+    /// Rebuilds the database file to give back the space of deleted rows.
+    pub fn vacuum(&self) -> Result<()> {
+        self.conn()?.execute_batch("VACUUM")?;
+        Ok(())
+    }
+
+    pub fn cache_price(&self, key: &str, price_per_hour: f64) -> Result<()> {
+        self.conn()?.execute(
+            "INSERT OR REPLACE INTO prices (key, price_per_hour, cached_at) VALUES (?1, ?2, ?3)",
+            params![key, price_per_hour, Utc::now().timestamp()],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_cached_price(&self, key: &str, max_age: Duration) -> Result<Option<f64>> {
+        let cutoff_time = (Utc::now() - max_age).timestamp();
+
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT price_per_hour FROM prices WHERE key = ?1 AND cached_at > ?2",
+        )?;
+
+        let price: Option<f64> = stmt
+            .query_row(params![key, cutoff_time], |row| row.get(0))
+            .ok();
+
+        Ok(price)
+    }
+
+    /// Stores a cost snapshot as the one for its day, replacing an earlier
+    /// snapshot from the same day.
+    pub fn record_cost_snapshot(&self, snapshot: &CostSnapshot) -> Result<()> {
+        let by_service = serde_json::to_string(&snapshot.by_service)
+            .map_err(|e| NimbusError::CacheError(format!("Failed to serialize cost snapshot: {}", e)))?;
+
+        self.conn()?.execute(
+            "INSERT OR REPLACE INTO cost_history (day, recorded_at, total, by_service) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                snapshot.recorded_at.format("%Y-%m-%d").to_string(),
+                snapshot.recorded_at.timestamp(),
+                snapshot.total,
+                by_service,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Returns the daily cost snapshots of the last `days` days, oldest first.
+    pub fn get_cost_snapshots(&self, days: i64) -> Result<Vec<CostSnapshot>> {
+        let cutoff_time = (Utc::now() - Duration::days(days)).timestamp();
+
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT recorded_at, total, by_service FROM cost_history 
+             WHERE recorded_at > ?1
+             ORDER BY recorded_at ASC",
+        )?;
+
+        let snapshots = stmt
+            .query_map(params![cutoff_time], |row| {
+                let recorded_at: i64 = row.get(0)?;
+                let by_service: String = row.get(2)?;
+
+                Ok(CostSnapshot {
+                    recorded_at: DateTime::from_timestamp(recorded_at, 0).unwrap_or_else(Utc::now),
+                    total: row.get(1)?,
+                    by_service: serde_json::from_str(&by_service).unwrap_or_default(),
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(snapshots)
+    }
+
+    /// Stores what a refresh listed as a new inventory generation and drops
+    /// generations past the retention period. Returns the generation ID.
+    pub fn record_inventory(&self, entries: &[InventoryEntry]) -> Result<i64> {
+        let conn = self.conn()?;
+        let tx = conn.unchecked_transaction()?;
+        let now = Utc::now();
+
+        tx.execute("INSERT INTO inventory_generations (recorded_at) VALUES (?1)", params![now.timestamp()])?;
+        let generation = tx.last_insert_rowid();
+
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR REPLACE INTO inventory (generation, id, name, resource_type, provider, region, state)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?;
+            for entry in entries {
+                stmt.execute(params![
+                    generation,
+                    entry.id,
+                    entry.name,
+                    entry.resource_type.as_str(),
+                    entry.provider.as_str(),
+                    entry.region,
+                    entry.state.as_str(),
+                ])?;
+            }
+        }
+
+        let cutoff_time = (now - Duration::days(INVENTORY_RETENTION_DAYS)).timestamp();
+        tx.execute(
+            "DELETE FROM inventory WHERE generation IN
+             (SELECT id FROM inventory_generations WHERE recorded_at < ?1)",
+            params![cutoff_time],
+        )?;
+        tx.execute("DELETE FROM inventory_generations WHERE recorded_at < ?1", params![cutoff_time])?;
+
+        tx.commit()?;
+        Ok(generation)
+    }
+
+    /// Returns the resources recorded in an inventory generation.
+    pub fn get_inventory(&self, generation: i64) -> Result<Vec<InventoryEntry>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, resource_type, provider, region, state FROM inventory WHERE generation = ?1",
+        )?;
+
+        let entries = stmt
+            .query_map(params![generation], |row| {
+                let type_str: String = row.get(2)?;
+                let provider_str: String = row.get(3)?;
+                let state_str: String = row.get(5)?;
+
+                Ok(InventoryEntry {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    resource_type: parse_resource_type(&type_str),
+                    provider: parse_provider(&provider_str),
+                    region: row.get(4)?,
+                    state: parse_resource_state(&state_str),
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(entries)
+    }
+
+    /// Compares the latest inventory with the last one recorded at or
+    /// before `since`, or the oldest kept if none is that old. Returns when
+    /// the older inventory was recorded and the changes, or `None` until
+    /// two inventories exist.
+    pub fn inventory_changes_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Option<(DateTime<Utc>, Vec<InventoryChange>)>> {
+        let (latest, baseline) = {
+            let conn = self.conn()?;
+            let latest: Option<i64> =
+                conn.query_row("SELECT MAX(id) FROM inventory_generations", [], |row| row.get(0))?;
+            let baseline: Option<(i64, i64)> = conn
+                .query_row(
+                    "SELECT id, recorded_at FROM inventory_generations
+                     ORDER BY recorded_at <= ?1 DESC,
+                              CASE WHEN recorded_at <= ?1 THEN -recorded_at ELSE recorded_at END
+                     LIMIT 1",
+                    params![since.timestamp()],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .ok();
+            (latest, baseline)
+        };
+
+        let (latest, (baseline, recorded_at)) = match (latest, baseline) {
+            (Some(latest), Some(baseline)) if latest != baseline.0 => (latest, baseline),
+            _ => return Ok(None),
+        };
+
+        let changes = diff_inventories(&self.get_inventory(baseline)?, &self.get_inventory(latest)?);
+        Ok(Some((DateTime::from_timestamp(recorded_at, 0).unwrap_or_else(Utc::now), changes)))
+    }
+
+    /// Stores the samples of a resource's metric series, replacing any
+    /// already stored for the same timestamps, and drops samples past the
+    /// retention period.
+    pub fn record_metrics(&self, resource_id: &str, series: &[MetricSeries]) -> Result<()> {
+        let conn = self.conn()?;
+        let tx = conn.unchecked_transaction()?;
+
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR REPLACE INTO metrics (resource_id, label, unit, position, timestamp, value)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?;
+            for (position, metric) in series.iter().enumerate() {
+                for point in &metric.datapoints {
+                    stmt.execute(params![
+                        resource_id,
+                        metric.label,
+                        metric.unit,
+                        position as i64,
+                        point.timestamp.timestamp(),
+                        point.value,
+                    ])?;
+                }
+            }
+        }
+
+        let cutoff_time = (Utc::now() - Duration::days(METRICS_RETENTION_DAYS)).timestamp();
+        tx.execute("DELETE FROM metrics WHERE timestamp < ?1", params![cutoff_time])?;
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Returns the stored samples of a resource's metrics taken since
+    /// `since`, one series per metric in the order they were fetched.
+    pub fn get_metrics(&self, resource_id: &str, since: DateTime<Utc>) -> Result<Vec<MetricSeries>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT label, unit, timestamp, value FROM metrics
+             WHERE resource_id = ?1 AND timestamp >= ?2
+             ORDER BY position, label, timestamp",
+        )?;
+
+        let rows = stmt
+            .query_map(params![resource_id, since.timestamp()], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, f64>(3)?,
+                ))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let mut series: Vec<MetricSeries> = Vec::new();
+        for (label, unit, timestamp, value) in rows {
+            let timestamp = match DateTime::from_timestamp(timestamp, 0) {
+                Some(timestamp) => timestamp,
+                None => continue,
+            };
+            match series.last_mut() {
+                Some(metric) if metric.label == label => metric.push(timestamp, value),
+                _ => {
+                    let mut metric = MetricSeries::new(label, unit);
+                    metric.push(timestamp, value);
+                    series.push(metric);
+                }
+            }
+        }
+
+        Ok(series)
+    }
+
+    /// Appends entries to the action audit log.
+    pub fn record_actions(&self, records: &[ActionRecord]) -> Result<()> {
+        let conn = self.conn()?;
+        let tx = conn.unchecked_transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO action_log (recorded_at, action, account, resource_id, resource_name, outcome, message)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?;
+            for record in records {
+                stmt.execute(params![
+                    record.recorded_at.timestamp(),
+                    record.action,
+                    record.account,
+                    record.resource_id,
+                    record.resource_name,
+                    record.outcome.as_str(),
+                    record.message,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Returns up to `limit` audit log entries, newest first.
+    pub fn get_action_log(&self, limit: usize) -> Result<Vec<ActionRecord>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT recorded_at, action, account, resource_id, resource_name, outcome, message
+             FROM action_log
+             ORDER BY recorded_at DESC, id DESC
+             LIMIT ?1",
+        )?;
+
+        let records = stmt
+            .query_map(params![limit as i64], |row| {
+                let recorded_at: i64 = row.get(0)?;
+                let outcome: String = row.get(5)?;
+
+                Ok(ActionRecord {
+                    recorded_at: DateTime::from_timestamp(recorded_at, 0).unwrap_or_else(Utc::now),
+                    action: row.get(1)?,
+                    account: row.get(2)?,
+                    resource_id: row.get(3)?,
+                    resource_name: row.get(4)?,
+                    outcome: ActionOutcome::parse(&outcome),
+                    message: row.get(6)?,
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        Ok(records)
+    }
+
+    /// Returns true if any of the provider's partitions is stale. Caches
+    /// written before partitions were tracked fall back to the age of the
+    /// newest entry.
     pub fn is_cache_stale(&self, provider: Provider) -> Result<bool> {
+        let tracked: usize = self.conn()?.query_row(
+            "SELECT COUNT(*) FROM partitions WHERE provider = ?1",
+            params![provider.as_str()],
+            |row| row.get(0),
+        )?;
+        if tracked > 0 {
+            return Ok(!self.get_stale_partitions(provider)?.is_empty());
+        }
+
         match self.get_last_sync_time(provider)? {
             Some(last_sync) => {
                 let age = Utc::now().signed_duration_since(last_sync);
@@ -231,6 +1064,58 @@ impl SerializableResource {
             created_at: resource.created_at(),
         }
     }
+
+    fn into_view(self, cached_at: DateTime<Utc>) -> CachedResourceView {
+        CachedResourceView {
+            resource_type: parse_resource_type(&self.resource_type),
+            provider: parse_provider(&self.provider),
+            state: parse_resource_state(&self.state),
+            id: self.id,
+            name: self.name,
+            region: self.region,
+            cost_per_month: self.cost_per_month,
+            tags: self.tags,
+            created_at: self.created_at,
+            cached_at,
+        }
+    }
+}
+
+/// Turns free text into an FTS5 query that prefix-matches every word.
+/// Words are quoted so characters such as `-` or `:` are not read as
+/// query syntax.
+fn search_expression(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "")))
+        .filter(|term| term != "\"\"*")
+        .collect();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+fn record_partition_sync(
+    conn: &Connection,
+    partition: &CachePartition,
+    synced_at: i64,
+    ttl: Duration,
+) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO partitions (provider, region, resource_type, synced_at, ttl_seconds) 
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            partition.provider.as_str(),
+            partition.region,
+            partition.resource_type.as_str(),
+            synced_at,
+            ttl.num_seconds(),
+        ],
+    )?;
+    Ok(())
 }
 
 fn parse_provider(s: &str) -> Provider {
@@ -238,6 +1123,7 @@ fn parse_provider(s: &str) -> Provider {
         "AWS" => Provider::AWS,
         "GCP" => Provider::GCP,
         "Azure" => Provider::Azure,
+        "Kubernetes" => Provider::Kubernetes,
         _ => Provider::AWS,
     }
 }
@@ -258,11 +1144,108 @@ fn parse_resource_type(s: &str) -> ResourceType {
     }
 }
 
+fn parse_resource_state(s: &str) -> ResourceState {
+    match s {
+        "Running" => ResourceState::Running,
+        "Degraded" => ResourceState::Degraded,
+        "Maintenance" => ResourceState::Maintenance,
+        "Stopped" => ResourceState::Stopped,
+        "Terminated" => ResourceState::Terminated,
+        "Pending" => ResourceState::Pending,
+        "Stopping" => ResourceState::Stopping,
+        "Starting" => ResourceState::Starting,
+        "Deleting" => ResourceState::Deleting,
+        "Error" => ResourceState::Error,
+        _ => ResourceState::Unknown,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::{Action, ChangeKind};
     use tempfile::TempDir;
 
+    struct TestResource {
+        id: String,
+        resource_type: ResourceType,
+        state: ResourceState,
+        tags: std::collections::HashMap<String, String>,
+    }
+
+    impl CloudResource for TestResource {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn name(&self) -> &str {
+            &self.id
+        }
+
+        fn resource_type(&self) -> ResourceType {
+            self.resource_type
+        }
+
+        fn provider(&self) -> Provider {
+            Provider::AWS
+        }
+
+        fn region(&self) -> &str {
+            "us-east-1"
+        }
+
+        fn state(&self) -> ResourceState {
+            self.state
+        }
+
+        fn cost_per_month(&self) -> Option<f64> {
+            None
+        }
+
+        fn tags(&self) -> &std::collections::HashMap<String, String> {
+            &self.tags
+        }
+
+        fn created_at(&self) -> Option<DateTime<Utc>> {
+            None
+        }
+
+        fn supported_actions(&self) -> Vec<Action> {
+            Vec::new()
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    fn resource(id: &str, state: ResourceState) -> Box<dyn CloudResource> {
+        tagged_resource(id, state, &[])
+    }
+
+    fn tagged_resource(id: &str, state: ResourceState, tags: &[(&str, &str)]) -> Box<dyn CloudResource> {
+        Box::new(TestResource {
+            id: id.to_string(),
+            resource_type: ResourceType::Compute,
+            state,
+            tags: tags.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        })
+    }
+
+    fn database(id: &str) -> Box<dyn CloudResource> {
+        Box::new(TestResource {
+            id: id.to_string(),
+            resource_type: ResourceType::Database,
+            state: ResourceState::Running,
+            tags: HashMap::new(),
+        })
+    }
+
+    /// A refresh of the default AWS account that listed `resources`.
+    fn listed(resources: Vec<Box<dyn CloudResource>>) -> [AccountListing; 1] {
+        [AccountListing::new(Provider::AWS, "default", resources)]
+    }
+
     #[test]
     fn test_cache_store_creation() {
         let temp_dir = TempDir::new().unwrap();
@@ -275,6 +1258,32 @@ mod tests {
         assert_eq!(count, 0);
     }
 
+    #[test]
+    fn test_account_column_added_to_old_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        Connection::open(&db_path)
+            .unwrap()
+            .execute_batch(&format!(
+                "CREATE TABLE resources (
+                    id TEXT PRIMARY KEY,
+                    provider TEXT NOT NULL,
+                    resource_type TEXT NOT NULL,
+                    region TEXT NOT NULL,
+                    data TEXT NOT NULL,
+                    cached_at INTEGER NOT NULL
+                );
+                INSERT INTO resources VALUES ('i-1', 'AWS', 'Compute', 'us-east-1', '{{}}', {});",
+                Utc::now().timestamp()
+            ))
+            .unwrap();
+
+        let store = CacheStore::new(&db_path, 24).unwrap();
+        let cached = store.get_all_cached_resources().unwrap();
+        assert_eq!(cached.len(), 1);
+        assert_eq!(cached[0].account, "");
+    }
+
     #[test]
     fn test_cache_clear() {
         let temp_dir = TempDir::new().unwrap();
@@ -286,6 +1295,368 @@ mod tests {
         assert_eq!(count, 0);
     }
 
+    #[test]
+    fn test_price_cache_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let store = CacheStore::new(&db_path, 24).unwrap();
+
+        assert_eq!(store.get_cached_price("ec2:us-east-1:t3.micro:Linux", Duration::days(7)).unwrap(), None);
+
+        store.cache_price("ec2:us-east-1:t3.micro:Linux", 0.0104).unwrap();
+        assert_eq!(
+            store.get_cached_price("ec2:us-east-1:t3.micro:Linux", Duration::days(7)).unwrap(),
+            Some(0.0104)
+        );
+    }
+
+    #[test]
+    fn test_partition_staleness() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let store = CacheStore::new(&db_path, 24).unwrap();
+
+        let compute = CachePartition::new(Provider::AWS, "us-east-1", ResourceType::Compute);
+        let dns = CachePartition::new(Provider::AWS, "us-east-1", ResourceType::DNS);
+        assert!(store.is_partition_stale(&compute).unwrap());
+        assert_eq!(store.get_partition_sync_time(&compute).unwrap(), None);
+
+        store.mark_partition_synced(&compute, Duration::hours(1)).unwrap();
+        assert!(!store.is_partition_stale(&compute).unwrap());
+
+        let two_hours_ago = (Utc::now() - Duration::hours(2)).timestamp();
+        record_partition_sync(&store.conn().unwrap(), &compute, two_hours_ago, Duration::hours(1)).unwrap();
+        record_partition_sync(&store.conn().unwrap(), &dns, two_hours_ago, Duration::days(7)).unwrap();
+        assert!(store.is_partition_stale(&compute).unwrap());
+        assert!(!store.is_partition_stale(&dns).unwrap());
+        assert_eq!(store.get_stale_partitions(Provider::AWS).unwrap(), vec![compute.clone()]);
+        assert!(store.get_stale_partitions(Provider::GCP).unwrap().is_empty());
+        assert!(store.is_cache_stale(Provider::AWS).unwrap());
+
+        store.clear_cache(Some(Provider::AWS)).unwrap();
+        assert!(store.get_partition_sync_time(&dns).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_resource_ttls() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let store = CacheStore::new(&db_path, 1)
+            .unwrap()
+            .with_ttls(HashMap::from([(ResourceType::Compute, Duration::hours(48))]));
+        assert_eq!(store.ttl_for(ResourceType::Compute), Duration::hours(48));
+        assert_eq!(store.ttl_for(ResourceType::DNS), Duration::hours(1));
+
+        let partition = CachePartition::of(resource("i-1", ResourceState::Running).as_ref());
+        store.sync_resources(&listed(vec![resource("i-1", ResourceState::Running)])).unwrap();
+        assert!(!store.is_partition_stale(&partition).unwrap());
+
+        // Older than max_age but within the type's TTL, so still fresh and kept
+        let three_hours_ago = (Utc::now() - Duration::hours(3)).timestamp();
+        store
+            .conn()
+            .unwrap()
+            .execute("UPDATE partitions SET synced_at = ?1", params![three_hours_ago])
+            .unwrap();
+        store
+            .conn()
+            .unwrap()
+            .execute("UPDATE resources SET cached_at = ?1", params![three_hours_ago])
+            .unwrap();
+        assert!(!store.is_cache_stale(Provider::AWS).unwrap());
+        assert_eq!(store.prune_expired().unwrap(), 0);
+        assert_eq!(store.get_all_cached_resources().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_without_expiry() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let store = CacheStore::new(&db_path, 1).unwrap();
+        store.sync_resources(&listed(vec![resource("i-1", ResourceState::Running)])).unwrap();
+
+        let last_week = (Utc::now() - Duration::days(7)).timestamp();
+        store
+            .conn()
+            .unwrap()
+            .execute("UPDATE resources SET cached_at = ?1", params![last_week])
+            .unwrap();
+        assert!(store.get_all_cached_resources().unwrap().is_empty());
+
+        let store = store.with_expiry(false);
+        assert_eq!(store.get_all_cached_resources().unwrap().len(), 1);
+        assert_eq!(store.prune_expired().unwrap(), 0);
+        assert_eq!(store.stats().unwrap().expired, 1);
+    }
+
+    #[test]
+    fn test_inventory_changes_since() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let store = CacheStore::new(&db_path, 24).unwrap();
+
+        let first = vec![resource("i-1", ResourceState::Running), resource("i-2", ResourceState::Running)];
+        let entries: Vec<InventoryEntry> = first.iter().map(|r| InventoryEntry::from_resource(r.as_ref())).collect();
+        let older = store.record_inventory(&entries).unwrap();
+        assert!(store.inventory_changes_since(Utc::now()).unwrap().is_none());
+
+        // Backdate the first refresh to yesterday
+        let yesterday = Utc::now() - Duration::days(1);
+        store
+            .conn()
+            .unwrap()
+            .execute(
+                "UPDATE inventory_generations SET recorded_at = ?1 WHERE id = ?2",
+                params![yesterday.timestamp(), older],
+            )
+            .unwrap();
+
+        let second = vec![resource("i-1", ResourceState::Stopped), resource("i-3", ResourceState::Running)];
+        let entries: Vec<InventoryEntry> = second.iter().map(|r| InventoryEntry::from_resource(r.as_ref())).collect();
+        store.record_inventory(&entries).unwrap();
+        assert_eq!(store.get_inventory(older).unwrap().len(), 2);
+
+        let (recorded_at, changes) = store.inventory_changes_since(Utc::now() - Duration::hours(12)).unwrap().unwrap();
+        assert_eq!(recorded_at.timestamp(), yesterday.timestamp());
+        let summary: Vec<(ChangeKind, &str)> = changes.iter().map(|c| (c.kind, c.entry.id.as_str())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ChangeKind::Created, "i-3"),
+                (ChangeKind::Terminated, "i-2"),
+                (ChangeKind::StateChanged, "i-1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_action_log() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let store = CacheStore::new(&db_path, 24).unwrap();
+
+        let records = vec![
+            ActionRecord::succeeded("Stop", "Stop requested for 'web'")
+                .with_account("production")
+                .with_resource("i-1", "web"),
+            ActionRecord::failed("Terminate", "Access denied").with_resource("i-2", "db"),
+            ActionRecord::succeeded("Refresh", "Resources refreshed successfully"),
+        ];
+        store.record_actions(&records).unwrap();
+
+        let log = store.get_action_log(2).unwrap();
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0].action, "Refresh");
+        assert!(log[0].resource_id.is_none());
+        assert_eq!(log[1].outcome, ActionOutcome::Failed);
+        assert_eq!(log[1].resource_name.as_deref(), Some("db"));
+        assert_eq!(store.get_action_log(10).unwrap()[2].account.as_deref(), Some("production"));
+    }
+
+    #[test]
+    fn test_metrics() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let store = CacheStore::new(&db_path, 24).unwrap();
+
+        let now = DateTime::from_timestamp(Utc::now().timestamp(), 0).unwrap();
+        let mut network = MetricSeries::new("Network In", "Bytes");
+        network.push(now - Duration::hours(2), 2048.0);
+        let mut cpu = MetricSeries::new("CPU Utilization", "Percent");
+        cpu.push(now - Duration::hours(2), 10.0);
+        cpu.push(now - Duration::hours(1), 20.0);
+        store.record_metrics("i-1", &[network.clone(), cpu]).unwrap();
+
+        // A later fetch overlaps the earlier one and extends it
+        let mut cpu = MetricSeries::new("CPU Utilization", "Percent");
+        cpu.push(now - Duration::hours(1), 25.0);
+        cpu.push(now, 30.0);
+        store.record_metrics("i-1", &[network, cpu]).unwrap();
+
+        let series = store.get_metrics("i-1", now - Duration::hours(3)).unwrap();
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].label, "Network In");
+        assert_eq!(series[1].unit, "Percent");
+        let values: Vec<f64> = series[1].datapoints.iter().map(|p| p.value).collect();
+        assert_eq!(values, vec![10.0, 25.0, 30.0]);
+
+        let recent = store.get_metrics("i-1", now - Duration::minutes(90)).unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].datapoints.len(), 2);
+        assert!(store.get_metrics("i-2", now - Duration::hours(3)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sync_resources() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let store = CacheStore::new(&db_path, 24).unwrap();
+
+        let first = vec![
+            resource("i-1", ResourceState::Running),
+            resource("i-2", ResourceState::Running),
+            resource("i-3", ResourceState::Stopped),
+        ];
+        let summary = store.sync_resources(&listed(first)).unwrap();
+        assert_eq!(summary, SyncSummary { added: 3, removed: 0, changed: 0 });
+
+        let second = vec![
+            resource("i-1", ResourceState::Running),
+            resource("i-2", ResourceState::Stopped),
+            resource("i-4", ResourceState::Pending),
+        ];
+        let summary = store.sync_resources(&listed(second)).unwrap();
+        assert_eq!(summary, SyncSummary { added: 1, removed: 1, changed: 1 });
+        assert_eq!(summary.to_string(), "1 added, 1 removed, 1 changed");
+        assert_eq!(store.get_cache_count().unwrap(), 3);
+
+        // Resources of providers and accounts that were not listed stay cached
+        let summary = store
+            .sync_resources(&[
+                AccountListing::new(Provider::GCP, "default", Vec::new()),
+                AccountListing::new(Provider::AWS, "staging", Vec::new()),
+            ])
+            .unwrap();
+        assert!(summary.is_empty());
+        assert_eq!(store.get_cache_count().unwrap(), 3);
+        let cached = store.get_all_cached_resources().unwrap();
+        assert!(cached.iter().all(|resource| resource.account == "default"));
+    }
+
+    #[test]
+    fn test_sync_keeps_types_that_failed_to_list() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let store = CacheStore::new(&db_path, 24).unwrap();
+
+        let first = vec![resource("i-1", ResourceState::Running), database("db-1")];
+        store.sync_resources(&listed(first)).unwrap();
+
+        // RDS failed to list, so its database stays while the gone instance is removed
+        let listing = AccountListing::new(Provider::AWS, "default", Vec::new())
+            .with_failed_types(HashSet::from([ResourceType::Database]));
+        let summary = store.sync_resources(&[listing]).unwrap();
+        assert_eq!(summary, SyncSummary { added: 0, removed: 1, changed: 0 });
+
+        let cached = store.get_all_cached_resources().unwrap();
+        assert_eq!(cached.len(), 1);
+        assert_eq!(cached[0].id, "db-1");
+
+        let inventory = store.cached_inventory().unwrap();
+        assert_eq!(inventory.len(), 1);
+        assert_eq!(inventory[0].resource_type, ResourceType::Database);
+    }
+
+    #[test]
+    fn test_cost_snapshots_one_per_day() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let store = CacheStore::new(&db_path, 24).unwrap();
+
+        let snapshot = |days_ago: i64, total: f64| CostSnapshot {
+            recorded_at: Utc::now() - Duration::days(days_ago),
+            total,
+            by_service: [("Compute".to_string(), total)].into_iter().collect(),
+        };
+
+        store.record_cost_snapshot(&snapshot(40, 50.0)).unwrap();
+        store.record_cost_snapshot(&snapshot(2, 100.0)).unwrap();
+        store.record_cost_snapshot(&snapshot(0, 110.0)).unwrap();
+        store.record_cost_snapshot(&snapshot(0, 120.0)).unwrap();
+
+        let snapshots = store.get_cost_snapshots(30).unwrap();
+        let totals: Vec<f64> = snapshots.iter().map(|s| s.total).collect();
+        assert_eq!(totals, vec![100.0, 120.0]);
+        assert_eq!(snapshots[1].by_service.get("Compute"), Some(&120.0));
+    }
+
+    #[test]
+    fn test_search_expression() {
+        assert_eq!(search_expression("  "), None);
+        assert_eq!(search_expression("web prod"), Some("\"web\"* \"prod\"*".to_string()));
+        assert_eq!(search_expression("i-0ab\""), Some("\"i-0ab\"*".to_string()));
+    }
+
+    #[test]
+    fn test_search_resources() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let store = CacheStore::new(&db_path, 24).unwrap();
+
+        let resources = vec![
+            tagged_resource("i-0abc123", ResourceState::Running, &[("team", "payments")]),
+            tagged_resource("i-0def456", ResourceState::Stopped, &[("team", "search")]),
+        ];
+        store.sync_resources(&listed(resources)).unwrap();
+
+        assert_eq!(store.search_resources("payments", 10).unwrap(), vec!["i-0abc123".to_string()]);
+        assert_eq!(store.search_resources("i-0de", 10).unwrap(), vec!["i-0def456".to_string()]);
+        assert_eq!(store.search_resources("team", 10).unwrap().len(), 2);
+
+        // Replaced and deleted rows leave the index too
+        let resources = vec![tagged_resource("i-0abc123", ResourceState::Running, &[("team", "billing")])];
+        store.sync_resources(&listed(resources)).unwrap();
+        assert!(store.search_resources("payments", 10).unwrap().is_empty());
+        assert!(store.search_resources("i-0def456", 10).unwrap().is_empty());
+        assert_eq!(store.search_resources("billing", 10).unwrap(), vec!["i-0abc123".to_string()]);
+    }
+
+    #[test]
+    fn test_stats() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let store = CacheStore::new(&db_path, 24).unwrap();
+
+        let stats = store.stats().unwrap();
+        assert_eq!(stats.total, 0);
+        assert!(stats.oldest.is_none());
+
+        let resources = vec![
+            resource("i-1", ResourceState::Running),
+            resource("i-2", ResourceState::Stopped),
+        ];
+        store.sync_resources(&listed(resources)).unwrap();
+        store
+            .conn()
+            .unwrap()
+            .execute("UPDATE resources SET cached_at = cached_at - 90000 WHERE id = 'i-1'", [])
+            .unwrap();
+
+        let stats = store.stats().unwrap();
+        assert_eq!(stats.total, 2);
+        assert_eq!(stats.by_type.len(), 1);
+        assert_eq!(stats.by_type[0].2, 2);
+        assert_eq!(stats.expired, 1);
+        assert!(stats.oldest < stats.newest);
+        assert!(stats.size_bytes > 0);
+
+        assert_eq!(store.prune_expired().unwrap(), 1);
+        store.vacuum().unwrap();
+        assert_eq!(store.stats().unwrap().expired, 0);
+        assert_eq!(store.search_resources("i-2", 10).unwrap(), vec!["i-2".to_string()]);
+    }
+
+    #[test]
+    fn test_cached_resource_view() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let store = CacheStore::new(&db_path, 24).unwrap();
+
+        let resources = vec![tagged_resource("i-1", ResourceState::Stopped, &[("env", "prod")])];
+        store.sync_resources(&listed(resources)).unwrap();
+
+        let cached = store.get_all_cached_resources().unwrap();
+        assert_eq!(cached[0].account, "default");
+        let view = cached[0].to_view().unwrap();
+        assert_eq!(view.id(), "i-1");
+        assert_eq!(view.name(), "i-1");
+        assert_eq!(view.provider(), Provider::AWS);
+        assert_eq!(view.resource_type(), ResourceType::Compute);
+        assert_eq!(view.state(), ResourceState::Stopped);
+        assert_eq!(view.tags().get("env").map(String::as_str), Some("prod"));
+        assert!(view.supported_actions().is_empty());
+    }
+
     #[test]
     fn test_prune_old_entries() {
         let temp_dir = TempDir::new().unwrap();
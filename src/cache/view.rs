@@ -0,0 +1,97 @@
+use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// A resource rebuilt from its cached row. It carries what the cache stores,
+/// so it can be listed and inspected offline but offers no actions.
+#[derive(Debug, Clone)]
+pub struct CachedResourceView {
+    pub(super) id: String,
+    pub(super) name: String,
+    pub(super) resource_type: ResourceType,
+    pub(super) provider: Provider,
+    pub(super) region: String,
+    pub(super) state: ResourceState,
+    pub(super) cost_per_month: Option<f64>,
+    pub(super) tags: HashMap<String, String>,
+    pub(super) created_at: Option<DateTime<Utc>>,
+    pub(super) cached_at: DateTime<Utc>,
+}
+
+impl CachedResourceView {
+    /// Copies what the cache stores about a resource, so it can be handed
+    /// to a blocking task.
+    pub fn snapshot(resource: &dyn CloudResource) -> Self {
+        Self {
+            id: resource.id().to_string(),
+            name: resource.name().to_string(),
+            resource_type: resource.resource_type(),
+            provider: resource.provider(),
+            region: resource.region().to_string(),
+            state: resource.state(),
+            cost_per_month: resource.cost_per_month(),
+            tags: resource.tags().clone(),
+            created_at: resource.created_at(),
+            cached_at: Utc::now(),
+        }
+    }
+
+    /// When the row was last written by a refresh.
+    pub fn cached_at(&self) -> DateTime<Utc> {
+        self.cached_at
+    }
+}
+
+impl CloudResource for CachedResourceView {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        self.resource_type
+    }
+
+    fn provider(&self) -> Provider {
+        self.provider
+    }
+
+    fn region(&self) -> &str {
+        &self.region
+    }
+
+    fn state(&self) -> ResourceState {
+        self.state
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        self.cost_per_month
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.tags
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        // State may be out of date, so nothing is offered until a refresh
+        Vec::new()
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        vec![(
+            "Cached At".to_string(),
+            self.cached_at.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        )]
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
@@ -0,0 +1,122 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+
+use crate::config::NimbusConfig;
+
+/// Command-line arguments. Settings given here override both the config
+/// file and the environment.
+#[derive(Debug, Parser)]
+#[command(name = "nimbus", version, about = "A Terminal User Interface for managing multi-cloud resources")]
+pub struct Cli {
+    /// Config file to load instead of the default one
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Context to start in
+    #[arg(long, global = true, value_name = "NAME", env = "NIMBUS_CONTEXT")]
+    pub context: Option<String>,
+
+    /// AWS profile for the first AWS account
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// AWS region for the first AWS account
+    #[arg(long, global = true, value_name = "REGION")]
+    pub region: Option<String>,
+
+    /// AWS endpoint URL for the first AWS account, e.g. LocalStack
+    #[arg(long, global = true, value_name = "URL")]
+    pub endpoint_url: Option<String>,
+
+    /// Theme preset: dark, light or high-contrast
+    #[arg(long, global = true, value_name = "PRESET")]
+    pub theme: Option<String>,
+
+    /// Run without the cache
+    #[arg(long, global = true, conflicts_with = "offline")]
+    pub no_cache: bool,
+
+    /// Run from the cache without contacting any provider
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum Command {
+    /// Print the action audit log, oldest first
+    History {
+        /// Print JSON instead of CSV
+        #[arg(long)]
+        json: bool,
+    },
+    /// Check the config, the cache, and access to every account
+    Doctor,
+}
+
+impl Cli {
+    /// Applies the settings given on the command line to `config`.
+    pub fn apply(&self, config: &mut NimbusConfig) {
+        if let Some(ref profile) = self.profile {
+            config.providers.primary_aws_mut().profile = Some(profile.clone());
+        }
+
+        if let Some(ref region) = self.region {
+            config.providers.primary_aws_mut().region = region.clone();
+        }
+
+        if let Some(ref endpoint_url) = self.endpoint_url {
+            config.providers.primary_aws_mut().endpoint_url = Some(endpoint_url.clone());
+        }
+
+        if let Some(ref theme) = self.theme {
+            config.theme.preset = theme.clone();
+        }
+
+        if self.no_cache {
+            config.cache.enabled = false;
+        }
+
+        if self.offline {
+            config.cache.offline = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let cli = Cli::try_parse_from([
+            "nimbus",
+            "--profile",
+            "prod",
+            "--region=eu-west-1",
+            "--no-cache",
+            "history",
+            "--json",
+        ])
+        .unwrap();
+        assert_eq!(cli.command, Some(Command::History { json: true }));
+
+        let mut config: NimbusConfig = toml::from_str(
+            r#"
+            [providers.aws]
+            profile = "dev"
+            region = "us-east-1"
+            "#,
+        )
+        .unwrap();
+        cli.apply(&mut config);
+        assert_eq!(config.providers.aws[0].profile.as_deref(), Some("prod"));
+        assert_eq!(config.providers.aws[0].region, "eu-west-1");
+        assert!(!config.cache.enabled);
+
+        assert!(Cli::try_parse_from(["nimbus", "--no-cache", "--offline"]).is_err());
+    }
+}
@@ -1,79 +1,255 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
+use crate::core::ResourceType;
 use crate::error::{NimbusError, Result};
 
 pub mod aws_profile;
 pub use aws_profile::AwsProfileDetector;
 
+/// Config shared by every user of the machine, overridden by their own.
+pub const SYSTEM_CONFIG_PATH: &str = "/etc/nimbus/config.toml";
+
+/// Config in the working directory, overriding the user's.
+pub const PROJECT_CONFIG_FILE: &str = ".nimbus.toml";
+
+/// Context holding the `[providers]` accounts when other contexts exist.
+pub const DEFAULT_CONTEXT: &str = "default";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NimbusConfig {
     #[serde(default)]
     pub providers: ProviderConfigs,
+    /// Named sets of accounts to switch between, such as `[[contexts.prod.aws]]`.
+    /// When any are set, `providers` holds the active context's accounts.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contexts: BTreeMap<String, ProviderConfigs>,
+    /// Context to start in. Defaults to the first by name.
+    #[serde(default)]
+    pub context: Option<String>,
     #[serde(default)]
     pub ui: UiConfig,
     #[serde(default)]
+    pub theme: ThemeConfig,
+    #[serde(default)]
+    pub alerts: AlertsConfig,
+    #[serde(default)]
     pub cache: CacheConfig,
     #[serde(default)]
     pub refresh: RefreshConfig,
+    #[serde(default)]
+    pub retry: RetryConfig,
 }
 
 impl NimbusConfig {
     pub fn load() -> Result<Self> {
-        if let Some(config_path) = Self::config_file_path() {
-            if config_path.exists() {
-                return Self::from_file(&config_path);
-            }
+        Self::load_from(None)
+    }
+
+    /// Loads the given config file, which must exist. Without one, merges
+    /// the config files in `config_files`, or loads the environment if there
+    /// are none.
+    pub fn load_from(path: Option<&Path>) -> Result<Self> {
+        if let Some(path) = path {
+            return Self::from_file(path);
         }
-        
-        let from_env = Self::from_env()?;
-        Ok(from_env)
+
+        let files = Self::config_files();
+        if files.is_empty() {
+            return Self::from_env();
+        }
+        Self::from_files(&files)
+    }
+
+    /// The config files to load when none is named: `$NIMBUS_CONFIG` alone
+    /// if set, otherwise whichever of the system, user, and project files
+    /// exist, in that order.
+    pub fn config_files() -> Vec<PathBuf> {
+        if let Some(path) = config_override() {
+            return vec![path];
+        }
+
+        let mut layers = vec![PathBuf::from(SYSTEM_CONFIG_PATH)];
+        layers.extend(Self::config_file_path());
+        layers.push(PathBuf::from(PROJECT_CONFIG_FILE));
+        layers.into_iter().filter(|path| path.is_file()).collect()
     }
 
     pub fn from_file(path: &Path) -> Result<Self> {
-        let contents = std::fs::read_to_string(path)
-            .map_err(|e| NimbusError::ConfigRead(path.to_path_buf(), e))?;
-        
-        toml::from_str(&contents).map_err(NimbusError::ConfigParse)
+        Self::from_files(&[path.to_path_buf()])
+    }
+
+    /// Loads config files in order, each overriding the settings the ones
+    /// before it set. Tables are merged key by key; any other value,
+    /// including an array of accounts, replaces the earlier one.
+    pub fn from_files(paths: &[PathBuf]) -> Result<Self> {
+        let mut merged = toml::Value::Table(toml::Table::new());
+        for path in paths {
+            let contents = std::fs::read_to_string(path)
+                .map_err(|e| NimbusError::ConfigRead(path.clone(), e))?;
+            let layer: toml::Value = toml::from_str(&contents).map_err(NimbusError::ConfigParse)?;
+            merge_values(&mut merged, layer);
+        }
+
+        Self::from_value(merged, |name| std::env::var(name).ok())
+    }
+
+    /// Deserializes parsed config, expanding `${VAR}` in string values with
+    /// `lookup`.
+    fn from_value(mut value: toml::Value, lookup: impl Fn(&str) -> Option<String> + Copy) -> Result<Self> {
+        expand_env_values(&mut value, lookup)?;
+        value.try_into().map_err(NimbusError::ConfigParse)
     }
 
     pub fn from_env() -> Result<Self> {
         let mut config = Self::default();
-        
-        if let Ok(profile) = std::env::var("NIMBUS_AWS_PROFILE") {
-            config.providers.aws.get_or_insert_with(AwsConfig::default).profile = Some(profile);
+        config.apply_env();
+        Ok(config)
+    }
+
+    /// Overrides settings with the `NIMBUS_*` environment variables that
+    /// are set.
+    pub fn apply_env(&mut self) {
+        self.apply_env_from(|name| std::env::var(name).ok())
+    }
+
+    /// Overrides settings with the variables `lookup` finds. Provider
+    /// variables apply to the first account of that provider, adding one
+    /// if none is configured.
+    fn apply_env_from(&mut self, lookup: impl Fn(&str) -> Option<String>) {
+        if let Some(profile) = lookup("NIMBUS_AWS_PROFILE") {
+            self.providers.primary_aws_mut().profile = Some(profile);
         }
         
-        if let Ok(region) = std::env::var("NIMBUS_AWS_REGION") {
-            config.providers.aws.get_or_insert_with(AwsConfig::default).region = region;
+        if let Some(region) = lookup("NIMBUS_AWS_REGION") {
+            self.providers.primary_aws_mut().region = region;
         }
         
-        if let Ok(enabled) = std::env::var("NIMBUS_CACHE_ENABLED") {
-            config.cache.enabled = enabled.parse().unwrap_or(true);
+        if let Some(endpoint_url) = lookup("NIMBUS_AWS_ENDPOINT_URL") {
+            self.providers.primary_aws_mut().endpoint_url = Some(endpoint_url);
+        }
+
+        if let Some(project_id) = lookup("NIMBUS_GCP_PROJECT") {
+            self.providers.primary_gcp_mut().project_id = project_id;
+        }
+
+        if let Some(region) = lookup("NIMBUS_GCP_REGION") {
+            self.providers.primary_gcp_mut().region = region;
+        }
+
+        if let Some(credentials_file) = lookup("NIMBUS_GCP_CREDENTIALS_FILE") {
+            self.providers.primary_gcp_mut().credentials_file = Some(credentials_file);
+        }
+
+        if let Some(table) = lookup("NIMBUS_GCP_BILLING_EXPORT_TABLE") {
+            self.providers.primary_gcp_mut().billing_export_table = Some(table);
+        }
+
+        if let Some(subscription_id) = lookup("NIMBUS_AZURE_SUBSCRIPTION_ID") {
+            self.providers.primary_azure_mut().subscription_id = subscription_id;
+        }
+
+        if let Some(region) = lookup("NIMBUS_AZURE_REGION") {
+            self.providers.primary_azure_mut().region = region;
+        }
+
+        if let Some(tenant_id) = lookup("NIMBUS_AZURE_TENANT_ID") {
+            self.providers.primary_azure_mut().tenant_id = Some(tenant_id);
+        }
+
+        if let Some(client_id) = lookup("NIMBUS_AZURE_CLIENT_ID") {
+            self.providers.primary_azure_mut().client_id = Some(client_id);
+        }
+
+        if let Some(client_secret) = lookup("NIMBUS_AZURE_CLIENT_SECRET") {
+            self.providers.primary_azure_mut().client_secret = Some(client_secret);
         }
         
-        Ok(config)
+        if let Some(enabled) = lookup("NIMBUS_CACHE_ENABLED") {
+            self.cache.enabled = enabled.parse().unwrap_or(true);
+        }
+
+        if let Some(offline) = lookup("NIMBUS_OFFLINE") {
+            self.cache.offline = offline.parse().unwrap_or(false);
+        }
+
+        if let Some(preset) = lookup("NIMBUS_THEME") {
+            self.theme.preset = preset;
+        }
+    }
+
+    /// Makes the named context, or the configured or first one if `None`,
+    /// the active one. Accounts under `[providers]` become a context named
+    /// "default" once any other context is defined.
+    pub fn select_context(&mut self, name: Option<&str>) -> Result<()> {
+        if self.contexts.is_empty() {
+            return match name {
+                Some(name) => Err(NimbusError::ConfigError(format!(
+                    "Context '{}' was requested, but no contexts are configured",
+                    name
+                ))),
+                None => Ok(()),
+            };
+        }
+
+        if !self.providers.is_empty() && !self.contexts.contains_key(DEFAULT_CONTEXT) {
+            self.contexts.insert(DEFAULT_CONTEXT.to_string(), std::mem::take(&mut self.providers));
+        }
+
+        let name = name
+            .map(str::to_string)
+            .or_else(|| self.context.clone())
+            .or_else(|| self.contexts.keys().next().cloned())
+            .unwrap_or_default();
+        self.providers = self
+            .contexts
+            .get(&name)
+            .cloned()
+            .ok_or_else(|| NimbusError::ConfigError(format!("Unknown context '{}'", name)))?;
+        self.context = Some(name);
+        Ok(())
     }
 
     pub fn merge(mut self, other: Self) -> Self {
-        if other.providers.aws.is_some() {
+        if !other.providers.aws.is_empty() {
             self.providers.aws = other.providers.aws;
         }
-        if other.providers.gcp.is_some() {
+        if !other.providers.gcp.is_empty() {
             self.providers.gcp = other.providers.gcp;
         }
-        if other.providers.azure.is_some() {
+        if !other.providers.azure.is_empty() {
             self.providers.azure = other.providers.azure;
         }
+        if !other.providers.kubernetes.is_empty() {
+            self.providers.kubernetes = other.providers.kubernetes;
+        }
         
         self
     }
 
     pub fn validate(&self) -> Result<()> {
-        if self.providers.aws.is_none() 
-            && self.providers.gcp.is_none() 
-            && self.providers.azure.is_none() {
+        self.providers.validate()?;
+        for (name, providers) in &self.contexts {
+            providers
+                .validate()
+                .map_err(|e| NimbusError::ConfigError(format!("In context '{}': {}", name, e)))?;
+        }
+
+        let limits = [
+            ("monthly_budget", self.alerts.monthly_budget),
+            ("resource_cost_ceiling", self.alerts.resource_cost_ceiling),
+        ];
+        for (name, limit) in limits {
+            if limit.is_some_and(|limit| !limit.is_finite() || limit <= 0.0) {
+                return Err(NimbusError::ConfigError(format!("alerts.{} must be a positive amount", name)));
+            }
+        }
+
+        self.cache.resource_ttls()?;
+
+        if self.cache.offline && !self.cache.enabled {
             return Err(NimbusError::ConfigError(
-                "At least one cloud provider must be configured".to_string()
+                "Offline mode runs from the cache, which is disabled".to_string()
             ));
         }
         
@@ -92,93 +268,524 @@ impl NimbusConfig {
         Ok(())
     }
 
+    /// `$NIMBUS_CONFIG` if set, otherwise `config.toml` in `config_dir`.
     pub fn config_file_path() -> Option<PathBuf> {
-        dirs::home_dir().map(|home| home.join(".nimbus").join("config.toml"))
+        config_override().or_else(|| Self::config_dir().map(|dir| dir.join("config.toml")))
     }
 
+    /// `$XDG_CONFIG_HOME/nimbus`, defaulting to `~/.config/nimbus`.
     pub fn config_dir() -> Option<PathBuf> {
+        xdg_dir(std::env::var_os("XDG_CONFIG_HOME"), dirs::home_dir(), ".config")
+    }
+
+    /// `$XDG_CACHE_HOME/nimbus`, defaulting to `~/.cache/nimbus`. Holds the
+    /// cache database and the log.
+    pub fn cache_dir() -> Option<PathBuf> {
+        xdg_dir(std::env::var_os("XDG_CACHE_HOME"), dirs::home_dir(), ".cache")
+    }
+
+    /// Where earlier versions kept everything.
+    pub fn legacy_dir() -> Option<PathBuf> {
         dirs::home_dir().map(|home| home.join(".nimbus"))
     }
+
+    /// Moves files left in `~/.nimbus` to the config and cache directories,
+    /// returning the paths they were moved to. Files that already exist in
+    /// the new location are left alone.
+    pub fn migrate_legacy_dir() -> Result<Vec<PathBuf>> {
+        match (Self::legacy_dir(), Self::config_dir(), Self::cache_dir()) {
+            (Some(legacy), Some(config_dir), Some(cache_dir)) if legacy.is_dir() => {
+                migrate_layout(&legacy, &config_dir, &cache_dir)
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+}
+
+/// Merges `layer` into `base`: tables key by key, anything else replaced.
+fn merge_values(base: &mut toml::Value, layer: toml::Value) {
+    match (base, layer) {
+        (toml::Value::Table(base), toml::Value::Table(layer)) => {
+            for (key, value) in layer {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_values(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, layer) => *base = layer,
+    }
+}
+
+fn expand_env_values(value: &mut toml::Value, lookup: impl Fn(&str) -> Option<String> + Copy) -> Result<()> {
+    match value {
+        toml::Value::String(s) => *s = expand_env(s, lookup)?,
+        toml::Value::Array(values) => {
+            for value in values {
+                expand_env_values(value, lookup)?;
+            }
+        }
+        toml::Value::Table(table) => {
+            for value in table.values_mut() {
+                expand_env_values(value, lookup)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Replaces `${VAR}` with the variable's value, or with `default` for
+/// `${VAR:-default}` when it is unset or empty. `$${` is a literal `${`.
+fn expand_env(value: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String> {
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        if let Some(escaped) = after.strip_prefix("${") {
+            expanded.push_str("${");
+            rest = escaped;
+            continue;
+        }
+        let reference = match after.strip_prefix('{') {
+            Some(reference) => reference,
+            None => {
+                expanded.push('$');
+                rest = after;
+                continue;
+            }
+        };
+
+        let end = reference.find('}').ok_or_else(|| {
+            NimbusError::ConfigError(format!("Unclosed '${{' in config value '{}'", value))
+        })?;
+        let (name, default) = match reference[..end].split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (&reference[..end], None),
+        };
+
+        match (lookup(name).filter(|v| !v.is_empty()), default) {
+            (Some(resolved), _) => expanded.push_str(&resolved),
+            (None, Some(default)) => expanded.push_str(default),
+            (None, None) => {
+                return Err(NimbusError::ConfigError(format!(
+                    "Environment variable '{}' used in the config is not set",
+                    name
+                )))
+            }
+        }
+        rest = &reference[end + 1..];
+    }
+
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+fn config_override() -> Option<PathBuf> {
+    std::env::var_os("NIMBUS_CONFIG")
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Resolves an XDG base directory: the variable if it holds an absolute
+/// path (relative ones are ignored, as the spec requires), otherwise the
+/// fallback under home.
+fn xdg_dir(var: Option<std::ffi::OsString>, home: Option<PathBuf>, fallback: &str) -> Option<PathBuf> {
+    var.map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| home.map(|home| home.join(fallback)))
+        .map(|base| base.join("nimbus"))
+}
+
+fn migrate_layout(legacy: &Path, config_dir: &Path, cache_dir: &Path) -> Result<Vec<PathBuf>> {
+    let moves = [
+        ("config.toml", config_dir),
+        ("cache.db", cache_dir),
+        ("cache.db-wal", cache_dir),
+        ("cache.db-shm", cache_dir),
+        ("nimbus.log", cache_dir),
+    ];
+
+    let mut moved = Vec::new();
+    for (file, dir) in moves {
+        let from = legacy.join(file);
+        let to = dir.join(file);
+        if !from.is_file() || to.exists() {
+            continue;
+        }
+        std::fs::create_dir_all(dir)?;
+        // rename fails across filesystems, so fall back to copying
+        if std::fs::rename(&from, &to).is_err() {
+            std::fs::copy(&from, &to)?;
+            std::fs::remove_file(&from)?;
+        }
+        moved.push(to);
+    }
+
+    // Only remove the old directory once nothing is left in it
+    if std::fs::read_dir(legacy)?.next().is_none() {
+        std::fs::remove_dir(legacy)?;
+    }
+    Ok(moved)
 }
 
 impl Default for NimbusConfig {
     fn default() -> Self {
         Self {
             providers: ProviderConfigs::default(),
+            contexts: BTreeMap::new(),
+            context: None,
             ui: UiConfig::default(),
+            theme: ThemeConfig::default(),
+            alerts: AlertsConfig::default(),
             cache: CacheConfig::default(),
             refresh: RefreshConfig::default(),
+            retry: RetryConfig::default(),
         }
     }
 }
 
+/// Accounts to connect to. Each provider accepts a single table, such as
+/// `[providers.aws]`, or an array of tables, such as `[[providers.aws]]`,
+/// one per account.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ProviderConfigs {
-    pub aws: Option<AwsConfig>,
-    pub gcp: Option<GcpConfig>,
-    pub azure: Option<AzureConfig>,
+    #[serde(default, deserialize_with = "one_or_many", skip_serializing_if = "Vec::is_empty")]
+    pub aws: Vec<AwsConfig>,
+    /// GCP projects
+    #[serde(default, deserialize_with = "one_or_many", skip_serializing_if = "Vec::is_empty")]
+    pub gcp: Vec<GcpConfig>,
+    /// Azure subscriptions
+    #[serde(default, deserialize_with = "one_or_many", skip_serializing_if = "Vec::is_empty")]
+    pub azure: Vec<AzureConfig>,
+    /// Kubernetes clusters
+    #[serde(default, deserialize_with = "one_or_many", skip_serializing_if = "Vec::is_empty")]
+    pub kubernetes: Vec<KubernetesConfig>,
+}
+
+impl ProviderConfigs {
+    pub fn is_empty(&self) -> bool {
+        self.aws.is_empty() && self.gcp.is_empty() && self.azure.is_empty() && self.kubernetes.is_empty()
+    }
+
+    /// Checks the accounts' settings.
+    pub fn validate(&self) -> Result<()> {
+        if self.is_empty() {
+            return Err(NimbusError::ConfigError(
+                "At least one cloud provider must be configured".to_string()
+            ));
+        }
+
+        let resource_type_lists = self.aws.iter().map(|c| &c.resource_types)
+            .chain(self.gcp.iter().map(|c| &c.resource_types))
+            .chain(self.azure.iter().map(|c| &c.resource_types))
+            .chain(self.kubernetes.iter().map(|c| &c.resource_types));
+        for resource_types in resource_type_lists {
+            parse_resource_types(resource_types)?;
+        }
+
+        for account in &self.aws {
+            if account.role_arn.is_none()
+                && (account.external_id.is_some() || account.source_profile.is_some()) {
+                return Err(NimbusError::ConfigError(
+                    format!("AWS account '{}' sets external_id or source_profile without role_arn", account.display_name())
+                ));
+            }
+        }
+
+        for account in &self.aws {
+            if let Some(ref endpoint_url) = account.endpoint_url {
+                if !endpoint_url.starts_with("http://") && !endpoint_url.starts_with("https://") {
+                    return Err(NimbusError::ConfigError(
+                        format!("AWS account '{}' endpoint_url '{}' must start with http:// or https://", account.display_name(), endpoint_url)
+                    ));
+                }
+            }
+        }
+
+        for table in self.gcp.iter().filter_map(|gcp| gcp.billing_export_table.as_ref()) {
+            let valid_chars = table
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "_-.".contains(c));
+            if table.split('.').count() != 3 || !valid_chars {
+                return Err(NimbusError::ConfigError(
+                    format!("GCP billing_export_table '{}' must be of the form project.dataset.table", table)
+                ));
+            }
+        }
+
+        for azure in &self.azure {
+            let secret_fields = [&azure.tenant_id, &azure.client_id, &azure.client_secret];
+            let set = secret_fields.iter().filter(|field| field.is_some()).count();
+            if set != 0 && set != secret_fields.len() {
+                return Err(NimbusError::ConfigError(
+                    "Azure client secret authentication needs tenant_id, client_id and client_secret".to_string()
+                ));
+            }
+        }
+
+        check_account_labels("AWS", self.aws.iter().map(|a| a.label.as_deref()).collect())?;
+        check_account_labels("GCP", self.gcp.iter().map(|a| a.label.as_deref()).collect())?;
+        check_account_labels("Azure", self.azure.iter().map(|a| a.label.as_deref()).collect())?;
+        check_account_labels("Kubernetes", self.kubernetes.iter().map(|a| a.label.as_deref()).collect())?;
+        Ok(())
+    }
+
+    /// Returns the first configured AWS account, creating a default one if
+    /// none exist. Used by single-account overrides such as environment variables.
+    pub fn primary_aws_mut(&mut self) -> &mut AwsConfig {
+        if self.aws.is_empty() {
+            self.aws.push(AwsConfig::default());
+        }
+        &mut self.aws[0]
+    }
+
+    /// Returns the first configured GCP project, creating a default one if
+    /// none exist.
+    pub fn primary_gcp_mut(&mut self) -> &mut GcpConfig {
+        if self.gcp.is_empty() {
+            self.gcp.push(GcpConfig::default());
+        }
+        &mut self.gcp[0]
+    }
+
+    /// Returns the first configured Azure subscription, creating a default
+    /// one if none exist.
+    pub fn primary_azure_mut(&mut self) -> &mut AzureConfig {
+        if self.azure.is_empty() {
+            self.azure.push(AzureConfig::default());
+        }
+        &mut self.azure[0]
+    }
+}
+
+/// Requires a label on every account of a provider configured more than
+/// once, and no label used twice.
+fn check_account_labels(provider: &str, labels: Vec<Option<&str>>) -> Result<()> {
+    if labels.len() < 2 {
+        return Ok(());
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    for label in labels {
+        let label = label.ok_or_else(|| {
+            NimbusError::ConfigError(format!(
+                "Each {} account needs a label when more than one is configured",
+                provider
+            ))
+        })?;
+        if !seen.insert(label) {
+            return Err(NimbusError::ConfigError(
+                format!("Duplicate {} account label '{}'", provider, label)
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn one_or_many<'de, D, T>(deserializer: D) -> std::result::Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        One(T),
+        Many(Vec<T>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => vec![value],
+        OneOrMany::Many(values) => values,
+    })
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AwsConfig {
+    /// Display label distinguishing this account from others.
+    #[serde(default, alias = "name")]
+    pub label: Option<String>,
     pub profile: Option<String>,
     pub region: String,
     pub access_key_id: Option<String>,
     pub secret_access_key: Option<String>,
+    /// IAM role to assume, for reaching other accounts in an organization.
+    #[serde(default)]
+    pub role_arn: Option<String>,
+    /// External ID required by the role's trust policy, if any.
+    #[serde(default)]
+    pub external_id: Option<String>,
+    /// Profile whose credentials are used to assume `role_arn`. Defaults to
+    /// `profile`, then the standard credential chain.
+    #[serde(default)]
+    pub source_profile: Option<String>,
+    /// Fetch bucket size metrics from CloudWatch while listing S3.
+    #[serde(default = "default_true")]
+    pub s3_metrics: bool,
+    /// Endpoint to send every AWS API call to instead of the real service,
+    /// e.g. `http://localhost:4566` for LocalStack or a MinIO server.
+    #[serde(default)]
+    pub endpoint_url: Option<String>,
+    /// Resource types to list, e.g. `["compute", "database"]`. Empty lists
+    /// every type the provider supports.
+    #[serde(default)]
+    pub resource_types: Vec<String>,
 }
 
 impl Default for AwsConfig {
     fn default() -> Self {
         Self {
+            label: None,
             profile: None,
             region: "us-east-1".to_string(),
             access_key_id: None,
             secret_access_key: None,
+            role_arn: None,
+            external_id: None,
+            source_profile: None,
+            s3_metrics: true,
+            endpoint_url: None,
+            resource_types: Vec::new(),
         }
     }
 }
 
+fn default_true() -> bool {
+    true
+}
+
+impl AwsConfig {
+    /// Returns a short name for this account: its label, else its profile.
+    pub fn display_name(&self) -> &str {
+        self.label
+            .as_deref()
+            .or(self.profile.as_deref())
+            .unwrap_or("default")
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GcpConfig {
+    /// Display label distinguishing this project from others.
+    #[serde(default, alias = "name")]
+    pub label: Option<String>,
     pub project_id: String,
     pub credentials_file: Option<String>,
     pub region: String,
+    /// BigQuery table receiving the Cloud Billing export, as
+    /// `project.dataset.table`. Spend is only available when this is set.
+    #[serde(default)]
+    pub billing_export_table: Option<String>,
+    /// Resource types to list, e.g. `["compute", "database"]`. Empty lists
+    /// every type the provider supports.
+    #[serde(default)]
+    pub resource_types: Vec<String>,
 }
 
 impl Default for GcpConfig {
     fn default() -> Self {
         Self {
+            label: None,
             project_id: String::new(),
             credentials_file: None,
             region: "us-central1".to_string(),
+            billing_export_table: None,
+            resource_types: Vec::new(),
         }
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AzureConfig {
+    /// Display label distinguishing this subscription from others.
+    #[serde(default, alias = "name")]
+    pub label: Option<String>,
     pub subscription_id: String,
     pub tenant_id: Option<String>,
     pub client_id: Option<String>,
     pub client_secret: Option<String>,
+    #[serde(default = "default_azure_region")]
+    pub region: String,
+    /// Resource types to list, e.g. `["compute", "database"]`. Empty lists
+    /// every type the provider supports.
+    #[serde(default)]
+    pub resource_types: Vec<String>,
 }
 
 impl Default for AzureConfig {
     fn default() -> Self {
         Self {
+            label: None,
             subscription_id: String::new(),
             tenant_id: None,
             client_id: None,
             client_secret: None,
+            region: default_azure_region(),
+            resource_types: Vec::new(),
         }
     }
 }
 
+fn default_azure_region() -> String {
+    "eastus".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct KubernetesConfig {
+    /// Display label distinguishing this cluster from others.
+    #[serde(default, alias = "name")]
+    pub label: Option<String>,
+    /// Kubeconfig file to read. Defaults to `$KUBECONFIG`, then `~/.kube/config`.
+    #[serde(default)]
+    pub kubeconfig: Option<String>,
+    /// Context to connect with. Defaults to the kubeconfig's current context.
+    #[serde(default)]
+    pub context: Option<String>,
+    /// Namespace to list workloads from. Defaults to all namespaces.
+    #[serde(default)]
+    pub namespace: Option<String>,
+    /// Resource types to list, e.g. `["compute", "database"]`. Empty lists
+    /// every type the provider supports.
+    #[serde(default)]
+    pub resource_types: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UiConfig {
     pub default_tab: String,
     pub auto_refresh: bool,
     pub confirm_destructive_actions: bool,
+    /// Always snapshot resources that support it before terminating them.
+    #[serde(default)]
+    pub snapshot_before_terminate: bool,
+    /// Cost-allocation tag keys the dashboard groups spend by.
+    #[serde(default = "default_cost_tags")]
+    pub cost_tags: Vec<String>,
+    /// Tag keys every resource must carry; resources missing one are
+    /// flagged in the list and the compliance report.
+    #[serde(default)]
+    pub required_tags: Vec<String>,
+    /// Resource list columns in display order, e.g. `["name", "state",
+    /// "account", "cost"]`. Empty shows the default columns.
+    #[serde(default)]
+    pub columns: Vec<String>,
+    /// Key binding preset: `default` or `vim`.
+    #[serde(default = "default_keymap")]
+    pub keymap: String,
+}
+
+fn default_keymap() -> String {
+    "default".to_string()
+}
+
+fn default_cost_tags() -> Vec<String> {
+    vec!["team".to_string(), "project".to_string()]
 }
 
 impl Default for UiConfig {
@@ -187,6 +794,76 @@ impl Default for UiConfig {
             default_tab: "aws".to_string(),
             auto_refresh: true,
             confirm_destructive_actions: true,
+            snapshot_before_terminate: false,
+            cost_tags: default_cost_tags(),
+            required_tags: Vec::new(),
+            columns: Vec::new(),
+            keymap: default_keymap(),
+        }
+    }
+}
+
+/// Cost limits that raise warnings in the status bar and on the dashboard
+/// when crossed. Amounts are monthly, in USD.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AlertsConfig {
+    /// Warns when the estimated monthly cost, or the month-end forecast,
+    /// is over this.
+    #[serde(default)]
+    pub monthly_budget: Option<f64>,
+    /// Warns about every resource costing more than this per month.
+    #[serde(default)]
+    pub resource_cost_ceiling: Option<f64>,
+}
+
+/// Colors for the interface. Each color is a name ("cyan", "lightred"), a
+/// hex value ("#5f87af"), or a 256-color index ("208"), and replaces the
+/// preset's color of the same name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeConfig {
+    /// "dark", "light", or "high-contrast"
+    #[serde(default = "default_theme_preset")]
+    pub preset: String,
+    #[serde(default)]
+    pub accent: Option<String>,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub muted: Option<String>,
+    #[serde(default)]
+    pub surface: Option<String>,
+    #[serde(default)]
+    pub background: Option<String>,
+    #[serde(default)]
+    pub status_bar: Option<String>,
+    #[serde(default)]
+    pub key: Option<String>,
+    #[serde(default)]
+    pub success: Option<String>,
+    #[serde(default)]
+    pub warning: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+fn default_theme_preset() -> String {
+    "dark".to_string()
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        Self {
+            preset: default_theme_preset(),
+            accent: None,
+            text: None,
+            muted: None,
+            surface: None,
+            background: None,
+            status_bar: None,
+            key: None,
+            success: None,
+            warning: None,
+            error: None,
         }
     }
 }
@@ -196,6 +873,59 @@ pub struct CacheConfig {
     pub enabled: bool,
     pub max_age_hours: u64,
     pub db_path: Option<String>,
+    /// Prune expired entries and compact the database at launch
+    #[serde(default = "default_true")]
+    pub auto_prune: bool,
+    /// Hours between prunes while running (0 to prune only at launch)
+    #[serde(default = "default_prune_interval_hours")]
+    pub prune_interval_hours: u64,
+    /// How long each resource type stays fresh, e.g. `compute = "1h"`,
+    /// `dns = "7d"`. Types not listed use `max_age_hours`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub ttl: HashMap<String, String>,
+    /// Run from the cache alone: skip provider authentication and disable
+    /// anything that would change resources
+    #[serde(default)]
+    pub offline: bool,
+}
+
+fn default_prune_interval_hours() -> u64 {
+    6
+}
+
+/// Parses a `resource_types` list such as `["compute", "load_balancer"]`.
+pub fn parse_resource_types(values: &[String]) -> Result<Vec<ResourceType>> {
+    values
+        .iter()
+        .map(|value| {
+            parse_resource_type_key(value)
+                .ok_or_else(|| NimbusError::ConfigError(format!("Unknown resource type '{}' in resource_types", value)))
+        })
+        .collect()
+}
+
+/// Matches a config key such as `load_balancer` to a resource type.
+fn parse_resource_type_key(key: &str) -> Option<ResourceType> {
+    let normalize = |s: &str| s.to_lowercase().replace([' ', '_', '-'], "");
+    let key = normalize(key);
+    ResourceType::all().into_iter().find(|t| normalize(t.as_str()) == key)
+}
+
+/// Parses a duration such as `90s`, `30m`, `1h` or `7d`.
+fn parse_ttl(value: &str) -> Option<chrono::Duration> {
+    let value = value.trim();
+    let unit = value.chars().last()?;
+    let amount: i64 = value[..value.len() - unit.len_utf8()].trim().parse().ok()?;
+    if amount <= 0 {
+        return None;
+    }
+    match unit {
+        's' => Some(chrono::Duration::seconds(amount)),
+        'm' => Some(chrono::Duration::minutes(amount)),
+        'h' => Some(chrono::Duration::hours(amount)),
+        'd' => Some(chrono::Duration::days(amount)),
+        _ => None,
+    }
 }
 
 impl Default for CacheConfig {
@@ -204,6 +934,10 @@ impl Default for CacheConfig {
             enabled: true,
             max_age_hours: 24,
             db_path: None,
+            auto_prune: true,
+            prune_interval_hours: default_prune_interval_hours(),
+            ttl: HashMap::new(),
+            offline: false,
         }
     }
 }
@@ -213,11 +947,38 @@ impl CacheConfig {
         if let Some(ref path) = self.db_path {
             PathBuf::from(path)
         } else {
-            NimbusConfig::config_dir()
+            NimbusConfig::cache_dir()
                 .unwrap_or_else(|| PathBuf::from("."))
                 .join("cache.db")
         }
     }
+
+    /// Parses the per-type TTLs.
+    pub fn resource_ttls(&self) -> Result<HashMap<ResourceType, chrono::Duration>> {
+        let mut ttls = HashMap::new();
+        for (key, value) in &self.ttl {
+            let resource_type = parse_resource_type_key(key).ok_or_else(|| {
+                NimbusError::ConfigError(format!("Unknown resource type '{}' in cache.ttl", key))
+            })?;
+            let ttl = parse_ttl(value).ok_or_else(|| {
+                NimbusError::ConfigError(format!(
+                    "cache.ttl.{} '{}' must be a number followed by s, m, h or d, e.g. 30m",
+                    key, value
+                ))
+            })?;
+            ttls.insert(resource_type, ttl);
+        }
+        Ok(ttls)
+    }
+
+    /// How often to prune while running, or `None` when pruning is off or
+    /// only happens at launch.
+    pub fn prune_interval(&self) -> Option<std::time::Duration> {
+        if !self.auto_prune || self.prune_interval_hours == 0 {
+            return None;
+        }
+        Some(std::time::Duration::from_secs(self.prune_interval_hours * 3600))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -233,4 +994,279 @@ impl Default for RefreshConfig {
             auto_refresh_on_focus: true,
         }
     }
-}
\ No newline at end of file
+}
+
+/// Retry behaviour for cloud API calls that fail with throttling or
+/// other transient errors. Backoff is exponential with jitter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryConfig {
+    /// Total attempts per request, including the first one.
+    pub max_attempts: u32,
+    /// Base delay before the first retry, in milliseconds.
+    pub initial_backoff_ms: u64,
+    /// Upper bound on any single backoff delay, in seconds.
+    pub max_backoff_secs: u64,
+    /// Also rate-limit requests client-side once throttling is observed.
+    pub adaptive: bool,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff_ms: 500,
+            max_backoff_secs: 20,
+            adaptive: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_ttls() {
+        assert_eq!(parse_ttl("1h"), Some(chrono::Duration::hours(1)));
+        assert_eq!(parse_ttl(" 7d"), Some(chrono::Duration::days(7)));
+        assert_eq!(parse_ttl("30"), None);
+        assert_eq!(parse_ttl("0m"), None);
+        assert_eq!(parse_resource_type_key("load_balancer"), Some(ResourceType::LoadBalancer));
+        assert_eq!(parse_resource_type_key("DNS"), Some(ResourceType::DNS));
+
+        let mut cache = CacheConfig::default();
+        cache.ttl.insert("compute".to_string(), "1h".to_string());
+        cache.ttl.insert("dns".to_string(), "7d".to_string());
+        let ttls = cache.resource_ttls().unwrap();
+        assert_eq!(ttls.get(&ResourceType::Compute), Some(&chrono::Duration::hours(1)));
+        assert_eq!(ttls.get(&ResourceType::DNS), Some(&chrono::Duration::days(7)));
+
+        cache.ttl.insert("widgets".to_string(), "1h".to_string());
+        assert!(cache.resource_ttls().is_err());
+    }
+
+    #[test]
+    fn test_multiple_accounts() {
+        let config: NimbusConfig = toml::from_str(
+            r#"
+            [[providers.gcp]]
+            name = "prod"
+            project_id = "shop-prod"
+
+            [[providers.gcp]]
+            name = "staging"
+            project_id = "shop-staging"
+
+            [providers.kubernetes]
+            context = "kind-dev"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.providers.gcp.len(), 2);
+        assert_eq!(config.providers.gcp[1].label.as_deref(), Some("staging"));
+        assert_eq!(config.providers.kubernetes.len(), 1);
+        assert!(config.providers.azure.is_empty());
+        assert!(config.validate().is_ok());
+
+        let mut duplicate = config.clone();
+        duplicate.providers.gcp[1].label = Some("prod".to_string());
+        assert!(duplicate.validate().is_err());
+
+        let mut unlabeled = config;
+        unlabeled.providers.gcp[0].label = None;
+        assert!(unlabeled.validate().is_err());
+    }
+
+    #[test]
+    fn test_select_context() {
+        let mut config: NimbusConfig = toml::from_str(
+            r#"
+            context = "staging"
+
+            [providers.kubernetes]
+            context = "kind-dev"
+
+            [[contexts.prod.aws]]
+            label = "prod"
+            region = "us-east-1"
+
+            [contexts.staging.gcp]
+            project_id = "shop-staging"
+            region = "europe-west1"
+            "#,
+        )
+        .unwrap();
+
+        config.select_context(None).unwrap();
+        assert_eq!(config.context.as_deref(), Some("staging"));
+        assert_eq!(config.providers.gcp[0].project_id, "shop-staging");
+        assert_eq!(config.context_names(), vec!["default", "prod", "staging"]);
+        assert!(config.validate().is_ok());
+
+        config.select_context(Some("default")).unwrap();
+        assert_eq!(config.providers.kubernetes.len(), 1);
+        assert!(config.select_context(Some("qa")).is_err());
+        assert!(NimbusConfig::default().select_context(Some("prod")).is_err());
+    }
+
+    #[test]
+    fn test_resource_types() {
+        let types = vec!["compute".to_string(), "Load Balancer".to_string()];
+        assert_eq!(
+            parse_resource_types(&types).unwrap(),
+            vec![ResourceType::Compute, ResourceType::LoadBalancer]
+        );
+
+        let mut config = NimbusConfig::default();
+        config.providers.kubernetes.push(KubernetesConfig {
+            resource_types: vec!["pods".to_string()],
+            ..KubernetesConfig::default()
+        });
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_expand_env() {
+        let lookup = |name: &str| match name {
+            "AWS_PROFILE" => Some("ci".to_string()),
+            "EMPTY" => Some(String::new()),
+            _ => None,
+        };
+
+        assert_eq!(expand_env("${AWS_PROFILE}", lookup).unwrap(), "ci");
+        assert_eq!(expand_env("team-${AWS_PROFILE}-a", lookup).unwrap(), "team-ci-a");
+        assert_eq!(expand_env("${REGION:-eu-west-1}", lookup).unwrap(), "eu-west-1");
+        assert_eq!(expand_env("${EMPTY:-fallback}", lookup).unwrap(), "fallback");
+        assert_eq!(expand_env("cost $5 and $${literal}", lookup).unwrap(), "cost $5 and ${literal}");
+        assert!(expand_env("${MISSING}", lookup).is_err());
+        assert!(expand_env("${AWS_PROFILE", lookup).is_err());
+
+        let value = toml::from_str(
+            r#"
+            [providers.aws]
+            profile = "${AWS_PROFILE}"
+            region = "${REGION:-us-west-2}"
+            "#,
+        )
+        .unwrap();
+        let config = NimbusConfig::from_value(value, lookup).unwrap();
+        assert_eq!(config.providers.aws[0].profile.as_deref(), Some("ci"));
+        assert_eq!(config.providers.aws[0].region, "us-west-2");
+    }
+
+    #[test]
+    fn test_from_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let system = temp_dir.path().join("system.toml");
+        let project = temp_dir.path().join("project.toml");
+        std::fs::write(
+            &system,
+            r#"
+            [providers.aws]
+            profile = "shared"
+            region = "us-east-1"
+
+            [alerts]
+            monthly_budget = 1000.0
+            "#,
+        )
+        .unwrap();
+        std::fs::write(
+            &project,
+            r#"
+            [providers.aws]
+            region = "eu-west-1"
+
+            [[providers.gcp]]
+            project_id = "shop-dev"
+            region = "europe-west1"
+            "#,
+        )
+        .unwrap();
+
+        let config = NimbusConfig::from_files(&[system, project]).unwrap();
+        assert_eq!(config.providers.aws[0].profile.as_deref(), Some("shared"));
+        assert_eq!(config.providers.aws[0].region, "eu-west-1");
+        assert_eq!(config.providers.gcp.len(), 1);
+        assert_eq!(config.alerts.monthly_budget, Some(1000.0));
+
+        let mut base = toml::Value::from(vec!["a"]);
+        merge_values(&mut base, toml::Value::from(vec!["b"]));
+        assert_eq!(base, toml::Value::from(vec!["b"]));
+    }
+
+    #[test]
+    fn test_apply_env() {
+        let mut config: NimbusConfig = toml::from_str(
+            r#"
+            [providers.gcp]
+            project_id = "shop-prod"
+            region = "us-central1"
+            billing_export_table = "billing.export.gcp_billing"
+            "#,
+        )
+        .unwrap();
+        config.apply_env_from(|name| match name {
+            "NIMBUS_GCP_REGION" => Some("europe-west1".to_string()),
+            "NIMBUS_AZURE_SUBSCRIPTION_ID" => Some("sub-123".to_string()),
+            "NIMBUS_AZURE_TENANT_ID" => Some("tenant".to_string()),
+            _ => None,
+        });
+
+        let gcp = &config.providers.gcp[0];
+        assert_eq!(gcp.project_id, "shop-prod");
+        assert_eq!(gcp.region, "europe-west1");
+        assert_eq!(gcp.billing_export_table.as_deref(), Some("billing.export.gcp_billing"));
+
+        let azure = &config.providers.azure[0];
+        assert_eq!(azure.subscription_id, "sub-123");
+        assert_eq!(azure.region, "eastus");
+        assert_eq!(azure.tenant_id.as_deref(), Some("tenant"));
+        assert!(config.providers.aws.is_empty());
+
+        // Tenant without client ID and secret is incomplete
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_xdg_dir() {
+        let home = Some(PathBuf::from("/home/me"));
+        assert_eq!(
+            xdg_dir(None, home.clone(), ".config"),
+            Some(PathBuf::from("/home/me/.config/nimbus"))
+        );
+        assert_eq!(
+            xdg_dir(Some("/xdg/cache".into()), home.clone(), ".cache"),
+            Some(PathBuf::from("/xdg/cache/nimbus"))
+        );
+        assert_eq!(
+            xdg_dir(Some("relative".into()), home, ".cache"),
+            Some(PathBuf::from("/home/me/.cache/nimbus"))
+        );
+        assert_eq!(xdg_dir(None, None, ".config"), None);
+    }
+
+    #[test]
+    fn test_migrate_layout() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let legacy = temp_dir.path().join(".nimbus");
+        let config_dir = temp_dir.path().join("config");
+        let cache_dir = temp_dir.path().join("cache");
+        std::fs::create_dir_all(&legacy).unwrap();
+        std::fs::write(legacy.join("config.toml"), "[ui]").unwrap();
+        std::fs::write(legacy.join("cache.db"), "db").unwrap();
+
+        let moved = migrate_layout(&legacy, &config_dir, &cache_dir).unwrap();
+        assert_eq!(moved, vec![config_dir.join("config.toml"), cache_dir.join("cache.db")]);
+        assert_eq!(std::fs::read_to_string(config_dir.join("config.toml")).unwrap(), "[ui]");
+        assert!(!legacy.exists());
+
+        // A file already in the new location wins over the legacy one
+        std::fs::create_dir_all(&legacy).unwrap();
+        std::fs::write(legacy.join("config.toml"), "[cache]").unwrap();
+        assert!(migrate_layout(&legacy, &config_dir, &cache_dir).unwrap().is_empty());
+        assert!(legacy.join("config.toml").exists());
+    }
+}
@@ -11,6 +11,8 @@ pub enum Action {
     Start,
     /// Stop a running resource without terminating it
     Stop,
+    /// Stop a running resource, preserving its memory contents for resume
+    Hibernate,
     /// Restart a resource (stop then start)
     Restart,
     /// Permanently delete/terminate a resource
@@ -19,8 +21,22 @@ pub enum Action {
     ViewDetails,
     /// View logs for the resource (if applicable)
     ViewLogs,
+    /// Browse the records of a DNS zone
+    ViewRecords,
     /// Modify resource configuration
     Modify,
+    /// Open an interactive shell session on the resource
+    Connect,
+    /// Take a point-in-time backup of the resource
+    Snapshot,
+    /// Create a machine image (e.g., AMI) from the resource
+    CreateImage,
+    /// Add, change, or remove resource tags
+    Tag,
+    /// Change the number of instances or replicas
+    Scale,
+    /// Launch a new resource from the creation wizard
+    Create,
 }
 
 impl Action {
@@ -29,11 +45,19 @@ impl Action {
         match self {
             Action::Start => "Start",
             Action::Stop => "Stop",
+            Action::Hibernate => "Hibernate",
             Action::Restart => "Restart",
             Action::Terminate => "Terminate",
             Action::ViewDetails => "View Details",
             Action::ViewLogs => "View Logs",
+            Action::ViewRecords => "View Records",
             Action::Modify => "Modify",
+            Action::Connect => "Connect",
+            Action::Snapshot => "Snapshot",
+            Action::CreateImage => "Create Image",
+            Action::Tag => "Edit Tags",
+            Action::Scale => "Scale",
+            Action::Create => "Create",
         }
     }
 
@@ -46,13 +70,29 @@ impl Action {
     pub fn is_mutating(&self) -> bool {
         matches!(
             self,
-            Action::Start | Action::Stop | Action::Restart | Action::Terminate | Action::Modify
+            Action::Start
+                | Action::Stop
+                | Action::Hibernate
+                | Action::Restart
+                | Action::Terminate
+                | Action::Modify
+                | Action::Scale
+                | Action::Create
         )
     }
 
     /// Returns true if this action is read-only (viewing information).
     pub fn is_readonly(&self) -> bool {
-        matches!(self, Action::ViewDetails | Action::ViewLogs)
+        matches!(self, Action::ViewDetails | Action::ViewLogs | Action::ViewRecords)
+    }
+
+    /// Returns true if this action can be run on several resources at once.
+    /// Actions that need per-resource input, such as a size, cannot.
+    pub fn supports_bulk(&self) -> bool {
+        matches!(
+            self,
+            Action::Start | Action::Stop | Action::Hibernate | Action::Restart | Action::Terminate
+        )
     }
 
     /// Returns all available actions.
@@ -60,15 +100,126 @@ impl Action {
         vec![
             Action::Start,
             Action::Stop,
+            Action::Hibernate,
             Action::Restart,
             Action::Terminate,
             Action::ViewDetails,
             Action::ViewLogs,
+            Action::ViewRecords,
             Action::Modify,
+            Action::Connect,
+            Action::Snapshot,
+            Action::CreateImage,
+            Action::Tag,
+            Action::Scale,
+            Action::Create,
         ]
     }
 }
 
+/// Values an action needs beyond the resource it targets.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActionParams {
+    /// Size to change to, e.g. an instance type for `Action::Modify`
+    pub instance_type: Option<String>,
+    /// Number of instances or replicas for `Action::Scale`
+    pub desired_capacity: Option<u32>,
+    /// Name for the snapshot taken by `Action::Snapshot`
+    pub snapshot_name: Option<String>,
+}
+
+impl ActionParams {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// An action together with its parameters, as passed to
+/// `CloudProvider::execute_action`. Actions without parameters convert
+/// from `Action` directly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionRequest {
+    pub action: Action,
+    pub params: ActionParams,
+}
+
+impl ActionRequest {
+    pub fn new(action: Action) -> Self {
+        Self {
+            action,
+            params: ActionParams::default(),
+        }
+    }
+
+    pub fn with_instance_type<S: Into<String>>(mut self, instance_type: S) -> Self {
+        self.params.instance_type = Some(instance_type.into());
+        self
+    }
+
+    pub fn with_desired_capacity(mut self, desired_capacity: u32) -> Self {
+        self.params.desired_capacity = Some(desired_capacity);
+        self
+    }
+
+    pub fn with_snapshot_name<S: Into<String>>(mut self, snapshot_name: S) -> Self {
+        self.params.snapshot_name = Some(snapshot_name.into());
+        self
+    }
+
+    /// Describes the parameters for confirmation dialogs, one per line.
+    pub fn describe_params(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(ref instance_type) = self.params.instance_type {
+            lines.push(format!("Size: {}", instance_type));
+        }
+        if let Some(desired_capacity) = self.params.desired_capacity {
+            lines.push(format!("Desired capacity: {}", desired_capacity));
+        }
+        if let Some(ref snapshot_name) = self.params.snapshot_name {
+            lines.push(format!("Snapshot name: {}", snapshot_name));
+        }
+        lines
+    }
+}
+
+impl From<Action> for ActionRequest {
+    fn from(action: Action) -> Self {
+        Self::new(action)
+    }
+}
+
+/// A configuration change offered by `Action::Modify`, such as a new
+/// instance size. Also used for the choices of `Action::Create`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModifyOption {
+    /// Provider-specific value passed back when applying the change
+    pub value: String,
+    /// Human-readable description shown in the picker
+    pub label: String,
+    /// Estimated monthly cost after the change, if known
+    pub monthly_cost: Option<f64>,
+}
+
+impl ModifyOption {
+    pub fn new<S1: Into<String>, S2: Into<String>>(value: S1, label: S2) -> Self {
+        Self {
+            value: value.into(),
+            label: label.into(),
+            monthly_cost: None,
+        }
+    }
+
+    pub fn with_monthly_cost(mut self, cost: Option<f64>) -> Self {
+        self.monthly_cost = cost;
+        self
+    }
+
+    /// Returns the monthly cost difference relative to the current cost.
+    pub fn cost_delta(&self, current_cost: Option<f64>) -> Option<f64> {
+        Some(self.monthly_cost? - current_cost?)
+    }
+}
+
 impl fmt::Display for Action {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.as_str())
@@ -99,36 +250,76 @@ mod tests {
         assert!(!Action::Stop.is_destructive());
         assert!(!Action::Restart.is_destructive());
         assert!(!Action::ViewDetails.is_destructive());
+        assert!(!Action::Snapshot.is_destructive());
     }
 
     #[test]
     fn test_action_is_mutating() {
         assert!(Action::Start.is_mutating());
         assert!(Action::Stop.is_mutating());
+        assert!(Action::Hibernate.is_mutating());
         assert!(Action::Restart.is_mutating());
         assert!(Action::Terminate.is_mutating());
         assert!(Action::Modify.is_mutating());
+        assert!(Action::Scale.is_mutating());
+        assert!(Action::Create.is_mutating());
         assert!(!Action::ViewDetails.is_mutating());
         assert!(!Action::ViewLogs.is_mutating());
+        assert!(!Action::Connect.is_mutating());
     }
 
     #[test]
     fn test_action_is_readonly() {
         assert!(Action::ViewDetails.is_readonly());
         assert!(Action::ViewLogs.is_readonly());
+        assert!(Action::ViewRecords.is_readonly());
         assert!(!Action::Start.is_readonly());
         assert!(!Action::Stop.is_readonly());
         assert!(!Action::Terminate.is_readonly());
     }
 
+    #[test]
+    fn test_action_supports_bulk() {
+        assert!(Action::Stop.supports_bulk());
+        assert!(Action::Terminate.supports_bulk());
+        assert!(!Action::Modify.supports_bulk());
+        assert!(!Action::Scale.supports_bulk());
+        assert!(!Action::ViewDetails.supports_bulk());
+    }
+
     #[test]
     fn test_action_all() {
         let all = Action::all();
-        assert_eq!(all.len(), 7);
+        assert_eq!(all.len(), 15);
         assert!(all.contains(&Action::Start));
         assert!(all.contains(&Action::Terminate));
     }
 
+    #[test]
+    fn test_modify_option_cost_delta() {
+        let option = ModifyOption::new("t3.large", "t3.large (2 vCPU, 8 GiB)")
+            .with_monthly_cost(Some(60.0));
+
+        assert_eq!(option.cost_delta(Some(15.0)), Some(45.0));
+        assert_eq!(option.cost_delta(None), None);
+        assert_eq!(ModifyOption::new("t3.nano", "t3.nano").cost_delta(Some(15.0)), None);
+    }
+
+    #[test]
+    fn test_action_request_params() {
+        let request = ActionRequest::from(Action::Stop);
+        assert_eq!(request.action, Action::Stop);
+        assert!(request.params.is_empty());
+        assert!(request.describe_params().is_empty());
+
+        let request = ActionRequest::new(Action::Scale).with_desired_capacity(3);
+        assert!(!request.params.is_empty());
+        assert_eq!(request.describe_params(), vec!["Desired capacity: 3".to_string()]);
+
+        let request = ActionRequest::new(Action::Snapshot).with_snapshot_name("orders-before-migration");
+        assert_eq!(request.params.snapshot_name.as_deref(), Some("orders-before-migration"));
+    }
+
     #[test]
     fn test_action_equality() {
         assert_eq!(Action::Start, Action::Start);
@@ -0,0 +1,195 @@
+use crate::core::cost::CostForecast;
+use crate::core::resource::CloudResource;
+
+/// Cost limits to warn about, from the `[alerts]` config section.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CostAlertPolicy {
+    /// Most the whole estate should cost per month, in USD
+    pub monthly_budget: Option<f64>,
+    /// Most any one resource should cost per month, in USD
+    pub resource_ceiling: Option<f64>,
+}
+
+/// A cost limit that has been crossed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CostAlert {
+    /// Monthly spend is over the budget. `forecast` is set when only the
+    /// month-end forecast is over, not the current estimate.
+    BudgetExceeded { spend: f64, budget: f64, forecast: bool },
+    /// A resource costs more per month than the ceiling
+    ResourceOverCeiling { id: String, name: String, cost: f64, ceiling: f64 },
+}
+
+impl CostAlert {
+    pub fn message(&self) -> String {
+        match self {
+            CostAlert::BudgetExceeded { spend, budget, forecast: false } => {
+                format!("Estimated monthly cost ${:.2} is over the ${:.2} budget", spend, budget)
+            }
+            CostAlert::BudgetExceeded { spend, budget, forecast: true } => {
+                format!("Month-end forecast ${:.2} is over the ${:.2} budget", spend, budget)
+            }
+            CostAlert::ResourceOverCeiling { name, cost, ceiling, .. } => {
+                format!("{} costs ${:.2}/mo, over the ${:.2} ceiling", name, cost, ceiling)
+            }
+        }
+    }
+}
+
+impl CostAlertPolicy {
+    pub fn new(monthly_budget: Option<f64>, resource_ceiling: Option<f64>) -> Self {
+        Self {
+            monthly_budget,
+            resource_ceiling,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.monthly_budget.is_none() && self.resource_ceiling.is_none()
+    }
+
+    /// Checks the resources' estimated monthly costs, and the month-end
+    /// forecast if there is one, against the limits. The budget alert comes
+    /// first, then resources over the ceiling, costliest first.
+    pub fn evaluate(&self, resources: &[Box<dyn CloudResource>], forecast: Option<CostForecast>) -> Vec<CostAlert> {
+        let mut alerts = Vec::new();
+
+        if let Some(budget) = self.monthly_budget {
+            let estimate: f64 = resources.iter().filter_map(|r| r.cost_per_month()).sum();
+            if estimate > budget {
+                alerts.push(CostAlert::BudgetExceeded {
+                    spend: estimate,
+                    budget,
+                    forecast: false,
+                });
+            } else if let Some(forecast) = forecast.filter(|f| f.projected_total > budget) {
+                alerts.push(CostAlert::BudgetExceeded {
+                    spend: forecast.projected_total,
+                    budget,
+                    forecast: true,
+                });
+            }
+        }
+
+        if let Some(ceiling) = self.resource_ceiling {
+            let mut over: Vec<CostAlert> = resources
+                .iter()
+                .filter_map(|resource| {
+                    let cost = resource.cost_per_month().filter(|cost| *cost > ceiling)?;
+                    Some(CostAlert::ResourceOverCeiling {
+                        id: resource.id().to_string(),
+                        name: resource.name().to_string(),
+                        cost,
+                        ceiling,
+                    })
+                })
+                .collect();
+            over.sort_by(|a, b| alert_cost(b).partial_cmp(&alert_cost(a)).unwrap_or(std::cmp::Ordering::Equal));
+            alerts.extend(over);
+        }
+
+        alerts
+    }
+}
+
+fn alert_cost(alert: &CostAlert) -> f64 {
+    match alert {
+        CostAlert::BudgetExceeded { spend, .. } => *spend,
+        CostAlert::ResourceOverCeiling { cost, .. } => *cost,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::{DateTime, Utc};
+
+    use super::*;
+    use crate::core::{Action, Provider, ResourceState, ResourceType};
+
+    struct PricedResource {
+        name: String,
+        cost: Option<f64>,
+        tags: HashMap<String, String>,
+    }
+
+    impl CloudResource for PricedResource {
+        fn id(&self) -> &str {
+            &self.name
+        }
+
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn resource_type(&self) -> ResourceType {
+            ResourceType::Compute
+        }
+
+        fn provider(&self) -> Provider {
+            Provider::AWS
+        }
+
+        fn region(&self) -> &str {
+            "us-east-1"
+        }
+
+        fn state(&self) -> ResourceState {
+            ResourceState::Running
+        }
+
+        fn cost_per_month(&self) -> Option<f64> {
+            self.cost
+        }
+
+        fn tags(&self) -> &HashMap<String, String> {
+            &self.tags
+        }
+
+        fn created_at(&self) -> Option<DateTime<Utc>> {
+            None
+        }
+
+        fn supported_actions(&self) -> Vec<Action> {
+            Vec::new()
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    fn resource(name: &str, cost: Option<f64>) -> Box<dyn CloudResource> {
+        Box::new(PricedResource {
+            name: name.to_string(),
+            cost,
+            tags: HashMap::new(),
+        })
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let resources = vec![resource("web", Some(120.0)), resource("db", Some(400.0)), resource("dns", None)];
+
+        let policy = CostAlertPolicy::new(Some(500.0), Some(100.0));
+        let alerts = policy.evaluate(&resources, None);
+        assert_eq!(alerts.len(), 3);
+        assert_eq!(
+            alerts[0],
+            CostAlert::BudgetExceeded {
+                spend: 520.0,
+                budget: 500.0,
+                forecast: false
+            }
+        );
+        assert_eq!(alerts[1].message(), "db costs $400.00/mo, over the $100.00 ceiling");
+
+        let policy = CostAlertPolicy::new(Some(600.0), None);
+        assert!(policy.evaluate(&resources, None).is_empty());
+        let alerts = policy.evaluate(&resources, Some(CostForecast::new(650.0, 600.0, 700.0)));
+        assert_eq!(alerts[0].message(), "Month-end forecast $650.00 is over the $600.00 budget");
+
+        assert!(CostAlertPolicy::default().evaluate(&resources, None).is_empty());
+    }
+}
@@ -0,0 +1,123 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActionOutcome {
+    Succeeded,
+    Failed,
+}
+
+impl ActionOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActionOutcome::Succeeded => "Succeeded",
+            ActionOutcome::Failed => "Failed",
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "Failed" => ActionOutcome::Failed,
+            _ => ActionOutcome::Succeeded,
+        }
+    }
+}
+
+/// One entry of the action audit log: something the user ran and how it
+/// went.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionRecord {
+    pub recorded_at: DateTime<Utc>,
+    /// The action, e.g. "Stop" or "Refresh"
+    pub action: String,
+    /// Name of the provider account the action ran in
+    pub account: Option<String>,
+    pub resource_id: Option<String>,
+    pub resource_name: Option<String>,
+    pub outcome: ActionOutcome,
+    /// What was shown to the user, or the error
+    pub message: String,
+}
+
+impl ActionRecord {
+    pub fn succeeded(action: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(action, ActionOutcome::Succeeded, message)
+    }
+
+    pub fn failed(action: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(action, ActionOutcome::Failed, message)
+    }
+
+    fn new(action: impl Into<String>, outcome: ActionOutcome, message: impl Into<String>) -> Self {
+        Self {
+            recorded_at: Utc::now(),
+            action: action.into(),
+            account: None,
+            resource_id: None,
+            resource_name: None,
+            outcome,
+            message: message.into(),
+        }
+    }
+
+    pub fn with_account(mut self, account: impl Into<String>) -> Self {
+        self.account = Some(account.into());
+        self
+    }
+
+    pub fn with_resource(mut self, resource_id: impl Into<String>, resource_name: impl Into<String>) -> Self {
+        self.resource_id = Some(resource_id.into());
+        self.resource_name = Some(resource_name.into());
+        self
+    }
+
+    pub fn is_failure(&self) -> bool {
+        self.outcome == ActionOutcome::Failed
+    }
+
+    /// Column names matching `to_csv_row`.
+    pub const CSV_HEADER: &'static str = "recorded_at,action,account,resource_id,resource_name,outcome,message";
+
+    /// Formats the record as one CSV line, quoting fields as needed.
+    pub fn to_csv_row(&self) -> String {
+        let recorded_at = self.recorded_at.to_rfc3339();
+        let fields = [
+            recorded_at.as_str(),
+            self.action.as_str(),
+            self.account.as_deref().unwrap_or(""),
+            self.resource_id.as_deref().unwrap_or(""),
+            self.resource_name.as_deref().unwrap_or(""),
+            self.outcome.as_str(),
+            self.message.as_str(),
+        ];
+        fields.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(",")
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_csv_row() {
+        let mut record = ActionRecord::failed("Stop", "Access denied, \"ec2:StopInstances\"")
+            .with_account("production")
+            .with_resource("i-123", "web");
+        record.recorded_at = DateTime::from_timestamp(0, 0).unwrap();
+
+        assert!(record.is_failure());
+        assert_eq!(
+            record.to_csv_row(),
+            "1970-01-01T00:00:00+00:00,Stop,production,i-123,web,Failed,\"Access denied, \"\"ec2:StopInstances\"\"\""
+        );
+        assert_eq!(ActionRecord::CSV_HEADER.split(',').count(), 7);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::core::action::Action;
+use crate::core::resource::ResourceType;
+
+/// Optional cost data a provider can report beyond its total and breakdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CostFeature {
+    /// Daily or monthly spend over a period
+    History,
+    /// Spend grouped by tag or label values
+    ByTag,
+    Budgets,
+    Anomalies,
+    /// Reservation and savings plan coverage
+    Commitments,
+    /// Projected end-of-month spend
+    Forecast,
+}
+
+impl CostFeature {
+    pub fn all() -> Vec<CostFeature> {
+        vec![
+            CostFeature::History,
+            CostFeature::ByTag,
+            CostFeature::Budgets,
+            CostFeature::Anomalies,
+            CostFeature::Commitments,
+            CostFeature::Forecast,
+        ]
+    }
+}
+
+/// What a provider supports, so the UI can leave out views and actions that
+/// would only ever come back empty or unsupported.
+///
+/// The default claims everything, which keeps the UI unchanged for
+/// providers that do not describe themselves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderCapabilities {
+    /// Resource types the provider lists
+    pub resource_types: Vec<ResourceType>,
+    /// Actions the provider can carry out on at least one resource type
+    pub actions: Vec<Action>,
+    pub cost_features: Vec<CostFeature>,
+    /// Whether `get_metrics` returns data
+    pub metrics: bool,
+}
+
+impl ProviderCapabilities {
+    /// Capabilities of a provider that supports nothing optional.
+    pub fn none() -> Self {
+        Self {
+            resource_types: Vec::new(),
+            actions: vec![Action::ViewDetails],
+            cost_features: Vec::new(),
+            metrics: false,
+        }
+    }
+
+    pub fn with_resource_types(mut self, resource_types: &[ResourceType]) -> Self {
+        self.resource_types = resource_types.to_vec();
+        self
+    }
+
+    /// Keeps only the resource types in `enabled`, for accounts configured
+    /// to list fewer types. An empty list keeps them all.
+    pub fn restrict_resource_types(mut self, enabled: &[ResourceType]) -> Self {
+        if !enabled.is_empty() {
+            self.resource_types.retain(|t| enabled.contains(t));
+        }
+        self
+    }
+
+    pub fn with_actions(mut self, actions: &[Action]) -> Self {
+        self.actions = actions.to_vec();
+        if !self.actions.contains(&Action::ViewDetails) {
+            self.actions.push(Action::ViewDetails);
+        }
+        self
+    }
+
+    pub fn with_cost_features(mut self, cost_features: &[CostFeature]) -> Self {
+        self.cost_features = cost_features.to_vec();
+        self
+    }
+
+    pub fn with_metrics(mut self, metrics: bool) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    pub fn supports_type(&self, resource_type: ResourceType) -> bool {
+        self.resource_types.contains(&resource_type)
+    }
+
+    pub fn supports_action(&self, action: Action) -> bool {
+        self.actions.contains(&action)
+    }
+
+    pub fn supports_cost(&self, feature: CostFeature) -> bool {
+        self.cost_features.contains(&feature)
+    }
+
+    /// Drops the actions a resource offers that its provider cannot carry out.
+    pub fn filter_actions(&self, actions: Vec<Action>) -> Vec<Action> {
+        actions.into_iter().filter(|a| self.supports_action(*a)).collect()
+    }
+}
+
+impl Default for ProviderCapabilities {
+    fn default() -> Self {
+        Self {
+            resource_types: ResourceType::all(),
+            actions: Action::all(),
+            cost_features: CostFeature::all(),
+            metrics: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_supports_everything() {
+        let caps = ProviderCapabilities::default();
+        assert!(caps.metrics);
+        assert!(caps.supports_type(ResourceType::Queue));
+        assert!(caps.supports_cost(CostFeature::Forecast));
+        assert_eq!(caps.filter_actions(Action::all()), Action::all());
+    }
+
+    #[test]
+    fn test_filter_actions() {
+        let caps = ProviderCapabilities::none()
+            .with_resource_types(&[ResourceType::Compute])
+            .with_actions(&[Action::Start, Action::Stop]);
+
+        assert!(caps.supports_action(Action::ViewDetails));
+        assert!(!caps.supports_type(ResourceType::Database));
+        assert!(!caps.supports_cost(CostFeature::History));
+        assert_eq!(
+            caps.filter_actions(vec![Action::Start, Action::Tag, Action::ViewDetails]),
+            vec![Action::Start, Action::ViewDetails]
+        );
+    }
+
+    #[test]
+    fn test_restrict_resource_types() {
+        let caps = ProviderCapabilities::none().with_resource_types(&[ResourceType::Compute, ResourceType::Storage]);
+
+        let restricted = caps.clone().restrict_resource_types(&[ResourceType::Storage, ResourceType::Queue]);
+        assert_eq!(restricted.resource_types, vec![ResourceType::Storage]);
+        assert_eq!(caps.clone().restrict_resource_types(&[]), caps);
+    }
+}
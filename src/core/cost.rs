@@ -1,5 +1,12 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::core::resource::CloudResource;
+
+/// Average hours in a month, for turning hourly list prices into monthly ones.
+pub const HOURS_PER_MONTH: f64 = 730.0;
+
 /// Time period for cost queries.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CostPeriod {
@@ -11,6 +18,8 @@ pub enum CostPeriod {
     ThisMonth,
     /// Last 30 days of costs
     Last30Days,
+    /// Costs from the start date up to, but not including, the end date
+    Custom { start: NaiveDate, end: NaiveDate },
 }
 
 impl CostPeriod {
@@ -20,8 +29,225 @@ impl CostPeriod {
             CostPeriod::ThisWeek => "This Week",
             CostPeriod::ThisMonth => "This Month",
             CostPeriod::Last30Days => "Last 30 Days",
+            CostPeriod::Custom { .. } => "Custom Range",
+        }
+    }
+
+    /// Periods offered by the dashboard's period picker, before the custom
+    /// range entry.
+    pub fn presets() -> Vec<CostPeriod> {
+        vec![CostPeriod::ThisWeek, CostPeriod::ThisMonth, CostPeriod::Last30Days]
+    }
+
+    /// Describes the period for titles. Custom ranges show their dates with
+    /// the end inclusive, as they were entered.
+    pub fn label(&self) -> String {
+        match *self {
+            CostPeriod::Custom { start, end } => format!(
+                "{} to {}",
+                start.format("%Y-%m-%d"),
+                (end - Duration::days(1)).format("%Y-%m-%d")
+            ),
+            _ => self.as_str().to_string(),
+        }
+    }
+
+    /// Parses a custom range such as `2024-01-01 to 2024-01-31` or
+    /// `2024-01-01..2024-01-31`. Both dates are inclusive.
+    pub fn parse_range(input: &str) -> Option<CostPeriod> {
+        let input = input.trim();
+        let (start, end) = input.split_once(" to ").or_else(|| input.split_once(".."))?;
+        let start = NaiveDate::parse_from_str(start.trim(), "%Y-%m-%d").ok()?;
+        let end = NaiveDate::parse_from_str(end.trim(), "%Y-%m-%d").ok()?;
+
+        if start > end {
+            return None;
+        }
+        Some(CostPeriod::Custom {
+            start,
+            end: end + Duration::days(1),
+        })
+    }
+
+    /// Returns the start (inclusive) and end (exclusive) dates of the period.
+    pub fn date_range(&self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        match *self {
+            CostPeriod::Today => (today - Duration::days(1), today),
+            CostPeriod::ThisWeek => (today - Duration::days(7), today),
+            CostPeriod::ThisMonth => (today - Duration::days(30), today),
+            CostPeriod::Last30Days => (today - Duration::days(30), today),
+            CostPeriod::Custom { start, end } => (start, end),
+        }
+    }
+}
+
+/// Size of the time buckets cost data is reported in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CostGranularity {
+    Daily,
+    Monthly,
+}
+
+impl CostGranularity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CostGranularity::Daily => "Daily",
+            CostGranularity::Monthly => "Monthly",
+        }
+    }
+}
+
+/// Tag value used for spend on resources without the tag.
+pub const UNTAGGED: &str = "(untagged)";
+
+/// Spend for one time bucket, starting on `date`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostPoint {
+    pub date: NaiveDate,
+    /// Cost in USD
+    pub amount: f64,
+}
+
+impl CostPoint {
+    pub fn new(date: NaiveDate, amount: f64) -> Self {
+        Self { date, amount }
+    }
+}
+
+/// A reservation or savings plan nearing the end of its term.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpiringCommitment {
+    /// Kind of commitment, e.g. "Reserved Instance" or "Savings Plan"
+    pub kind: String,
+    pub id: String,
+    /// What the commitment covers, e.g. "3x m5.large" or "$1.50/hour"
+    pub description: String,
+    pub expires: NaiveDate,
+}
+
+/// How much usage is covered by reservations and savings plans, and how
+/// much of the purchased commitment is being used. Percentages are 0-100
+/// and `None` when the account has no such commitments.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommitmentCoverage {
+    pub reservation_coverage: Option<f64>,
+    pub reservation_utilization: Option<f64>,
+    pub savings_plans_coverage: Option<f64>,
+    pub savings_plans_utilization: Option<f64>,
+    /// Commitments ending soon, soonest first
+    pub expiring: Vec<ExpiringCommitment>,
+}
+
+impl CommitmentCoverage {
+    /// Returns true if there is nothing to report.
+    pub fn is_empty(&self) -> bool {
+        self.reservation_coverage.is_none()
+            && self.reservation_utilization.is_none()
+            && self.savings_plans_coverage.is_none()
+            && self.savings_plans_utilization.is_none()
+            && self.expiring.is_empty()
+    }
+}
+
+/// A spending budget and how much of it has been used this period.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Budget {
+    pub name: String,
+    /// Budgeted amount in USD
+    pub limit: f64,
+    /// Spend so far in USD
+    pub actual: f64,
+    /// Forecast spend for the whole period in USD, if available
+    pub forecast: Option<f64>,
+}
+
+impl Budget {
+    /// Returns actual spend as a fraction of the limit, capped at 1.0.
+    pub fn used_ratio(&self) -> f64 {
+        if self.limit > 0.0 {
+            (self.actual / self.limit).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    pub fn is_exceeded(&self) -> bool {
+        self.actual > self.limit
+    }
+
+    pub fn forecast_exceeds(&self) -> bool {
+        self.forecast.map(|f| f > self.limit).unwrap_or(false)
+    }
+}
+
+/// Unusual spend flagged by the provider's anomaly detection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostAnomaly {
+    pub id: String,
+    /// Service or dimension the anomaly was detected in
+    pub service: String,
+    /// Extra spend attributed to the anomaly in USD
+    pub impact: f64,
+    /// Most likely cause, e.g. "AmazonEC2 / us-east-1 / BoxUsage:m5.large"
+    pub root_cause: Option<String>,
+    pub start: Option<NaiveDate>,
+    /// Last day the anomaly was seen; `None` while it is ongoing
+    pub end: Option<NaiveDate>,
+}
+
+impl CostAnomaly {
+    /// Returns true if the anomaly is ongoing or was seen within the last day.
+    pub fn is_active(&self, today: NaiveDate) -> bool {
+        match self.end {
+            Some(end) => end >= today - chrono::Duration::days(1),
+            None => true,
+        }
+    }
+}
+
+/// Sums cost points from several sources into one series ordered by date.
+pub fn merge_cost_points(points: impl IntoIterator<Item = CostPoint>) -> Vec<CostPoint> {
+    let mut by_date: std::collections::BTreeMap<NaiveDate, f64> = std::collections::BTreeMap::new();
+    for point in points {
+        *by_date.entry(point.date).or_insert(0.0) += point.amount;
+    }
+    by_date
+        .into_iter()
+        .map(|(date, amount)| CostPoint::new(date, amount))
+        .collect()
+}
+
+/// Projected spend for the current month, with the provider's prediction
+/// interval around it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostForecast {
+    /// Expected total for the month in USD, including spend so far
+    pub projected_total: f64,
+    /// Lower bound of the prediction interval in USD
+    pub lower_bound: f64,
+    /// Upper bound of the prediction interval in USD
+    pub upper_bound: f64,
+}
+
+impl CostForecast {
+    pub fn new(projected_total: f64, lower_bound: f64, upper_bound: f64) -> Self {
+        Self {
+            projected_total,
+            lower_bound,
+            upper_bound,
         }
     }
+
+    /// Adds several forecasts together, e.g. one per provider.
+    pub fn sum(forecasts: impl IntoIterator<Item = CostForecast>) -> Option<CostForecast> {
+        forecasts.into_iter().reduce(|a, b| {
+            CostForecast::new(
+                a.projected_total + b.projected_total,
+                a.lower_bound + b.lower_bound,
+                a.upper_bound + b.upper_bound,
+            )
+        })
+    }
 }
 
 /// Breakdown of cloud costs by service and region.
@@ -38,6 +264,8 @@ pub struct CostBreakdown {
     pub by_region: HashMap<String, f64>,
     /// Percentage change from previous period (positive = increase)
     pub trend_percentage: f64,
+    /// Projected end-of-month spend, if the provider forecasts costs
+    pub forecast: Option<CostForecast>,
 }
 
 impl CostBreakdown {
@@ -48,6 +276,7 @@ impl CostBreakdown {
             by_service: HashMap::new(),
             by_region: HashMap::new(),
             trend_percentage: 0.0,
+            forecast: None,
         }
     }
 
@@ -58,6 +287,7 @@ impl CostBreakdown {
             by_service: HashMap::new(),
             by_region: HashMap::new(),
             trend_percentage: 0.0,
+            forecast: None,
         }
     }
 
@@ -97,16 +327,203 @@ impl Default for CostBreakdown {
     }
 }
 
+/// Estimated monthly spend of the listed resources at one point in time,
+/// stored so spend can be charted across sessions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CostSnapshot {
+    pub recorded_at: DateTime<Utc>,
+    /// Monthly cost in USD
+    pub total: f64,
+    /// Monthly cost by service, keyed by resource type name
+    pub by_service: HashMap<String, f64>,
+}
+
+impl CostSnapshot {
+    /// Sums the monthly cost estimates of the resources, now.
+    pub fn from_resources(resources: &[Box<dyn CloudResource>]) -> Self {
+        let mut by_service = HashMap::new();
+        for resource in resources {
+            if let Some(cost) = resource.cost_per_month() {
+                *by_service.entry(resource.resource_type().as_str().to_string()).or_insert(0.0) += cost;
+            }
+        }
+
+        Self {
+            recorded_at: Utc::now(),
+            total: by_service.values().sum(),
+            by_service,
+        }
+    }
+
+    /// Returns the change from the oldest to the newest snapshot as a
+    /// percentage, or `None` with fewer than two snapshots or nothing spent
+    /// at the start.
+    pub fn trend_percentage(snapshots: &[CostSnapshot]) -> Option<f64> {
+        let (first, last) = (snapshots.first()?, snapshots.last()?);
+        if snapshots.len() < 2 || first.total <= 0.0 {
+            return None;
+        }
+        Some((last.total - first.total) / first.total * 100.0)
+    }
+
+    /// Returns the cheapest and costliest snapshots, the earliest of each on
+    /// a tie, or `None` if there are none.
+    pub fn extremes(snapshots: &[CostSnapshot]) -> Option<(&CostSnapshot, &CostSnapshot)> {
+        let first = snapshots.first()?;
+        Some(snapshots.iter().fold((first, first), |(min, max), snapshot| {
+            (
+                if snapshot.total < min.total { snapshot } else { min },
+                if snapshot.total > max.total { snapshot } else { max },
+            )
+        }))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_cost_snapshot_trend() {
+        let snapshot = |total: f64| CostSnapshot {
+            recorded_at: Utc::now(),
+            total,
+            by_service: HashMap::new(),
+        };
+
+        assert_eq!(CostSnapshot::trend_percentage(&[]), None);
+        assert_eq!(CostSnapshot::trend_percentage(&[snapshot(100.0)]), None);
+        assert_eq!(CostSnapshot::trend_percentage(&[snapshot(0.0), snapshot(50.0)]), None);
+        assert_eq!(
+            CostSnapshot::trend_percentage(&[snapshot(100.0), snapshot(90.0), snapshot(125.0)]),
+            Some(25.0)
+        );
+
+        assert_eq!(CostSnapshot::extremes(&[]), None);
+        let snapshots = [snapshot(100.0), snapshot(90.0), snapshot(125.0), snapshot(90.0)];
+        let (min, max) = CostSnapshot::extremes(&snapshots).unwrap();
+        assert!(std::ptr::eq(min, &snapshots[1]));
+        assert_eq!(max.total, 125.0);
+    }
+
     #[test]
     fn test_cost_period_as_str() {
         assert_eq!(CostPeriod::Today.as_str(), "Today");
         assert_eq!(CostPeriod::ThisWeek.as_str(), "This Week");
         assert_eq!(CostPeriod::ThisMonth.as_str(), "This Month");
         assert_eq!(CostPeriod::Last30Days.as_str(), "Last 30 Days");
+
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+        assert_eq!(CostPeriod::Custom { start, end }.as_str(), "Custom Range");
+    }
+
+    #[test]
+    fn test_cost_period_date_range() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+        assert_eq!(
+            CostPeriod::Today.date_range(today),
+            (NaiveDate::from_ymd_opt(2024, 3, 30).unwrap(), today)
+        );
+        assert_eq!(
+            CostPeriod::Last30Days.date_range(today),
+            (NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(), today)
+        );
+
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+        assert_eq!(CostPeriod::Custom { start, end }.date_range(today), (start, end));
+    }
+
+    #[test]
+    fn test_sum_forecasts() {
+        assert_eq!(CostForecast::sum(Vec::new()), None);
+
+        let total = CostForecast::sum(vec![
+            CostForecast::new(100.0, 90.0, 110.0),
+            CostForecast::new(50.0, 40.0, 70.0),
+        ]);
+        assert_eq!(total, Some(CostForecast::new(150.0, 130.0, 180.0)));
+    }
+
+    #[test]
+    fn test_parse_cost_range() {
+        let period = CostPeriod::parse_range("2024-01-01 to 2024-01-31").unwrap();
+        assert_eq!(
+            period,
+            CostPeriod::Custom {
+                start: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+                end: NaiveDate::from_ymd_opt(2024, 2, 1).unwrap(),
+            }
+        );
+        assert_eq!(period.label(), "2024-01-01 to 2024-01-31");
+        assert_eq!(CostPeriod::parse_range(" 2024-03-05..2024-03-05 "), CostPeriod::parse_range("2024-03-05 to 2024-03-05"));
+
+        assert_eq!(CostPeriod::parse_range("2024-02-01 to 2024-01-01"), None);
+        assert_eq!(CostPeriod::parse_range("last month"), None);
+        assert_eq!(CostPeriod::Last30Days.label(), "Last 30 Days");
+    }
+
+    #[test]
+    fn test_commitment_coverage_is_empty() {
+        let mut coverage = CommitmentCoverage::default();
+        assert!(coverage.is_empty());
+
+        coverage.savings_plans_utilization = Some(92.5);
+        assert!(!coverage.is_empty());
+    }
+
+    #[test]
+    fn test_budget_progress() {
+        let mut budget = Budget {
+            name: "monthly".to_string(),
+            limit: 200.0,
+            actual: 50.0,
+            forecast: Some(180.0),
+        };
+        assert_eq!(budget.used_ratio(), 0.25);
+        assert!(!budget.forecast_exceeds());
+        assert!(!budget.is_exceeded());
+
+        budget.forecast = Some(250.0);
+        assert!(budget.forecast_exceeds());
+
+        budget.actual = 300.0;
+        assert!(budget.is_exceeded());
+        assert_eq!(budget.used_ratio(), 1.0);
+    }
+
+    #[test]
+    fn test_cost_anomaly_is_active() {
+        let today = NaiveDate::from_ymd_opt(2024, 5, 10).unwrap();
+        let mut anomaly = CostAnomaly {
+            id: "a-1".to_string(),
+            service: "Amazon EC2".to_string(),
+            impact: 120.0,
+            root_cause: None,
+            start: NaiveDate::from_ymd_opt(2024, 5, 1),
+            end: None,
+        };
+        assert!(anomaly.is_active(today));
+
+        anomaly.end = NaiveDate::from_ymd_opt(2024, 5, 9);
+        assert!(anomaly.is_active(today));
+
+        anomaly.end = NaiveDate::from_ymd_opt(2024, 5, 5);
+        assert!(!anomaly.is_active(today));
+    }
+
+    #[test]
+    fn test_merge_cost_points() {
+        let day1 = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let day2 = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+
+        let merged = merge_cost_points(vec![
+            CostPoint::new(day2, 1.0),
+            CostPoint::new(day1, 2.0),
+            CostPoint::new(day2, 3.0),
+        ]);
+        assert_eq!(merged, vec![CostPoint::new(day1, 2.0), CostPoint::new(day2, 4.0)]);
     }
 
     #[test]
@@ -0,0 +1,130 @@
+use crate::core::action::ModifyOption;
+use crate::core::resource::ResourceType;
+use std::collections::HashMap;
+
+/// Choices offered when creating a resource through `Action::Create`.
+///
+/// Each list is shown as one step of the creation wizard. Instance types
+/// carry their monthly cost so the wizard can preview it before launch.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CreateOptions {
+    /// Machine images to boot from, e.g. AMIs
+    pub images: Vec<ModifyOption>,
+    /// Instance sizes, smallest first
+    pub instance_types: Vec<ModifyOption>,
+    /// SSH key pairs; launching without one is always allowed
+    pub key_pairs: Vec<ModifyOption>,
+}
+
+impl CreateOptions {
+    /// Returns true if nothing can be launched: there are no images or no
+    /// instance types to choose from.
+    pub fn is_empty(&self) -> bool {
+        self.images.is_empty() || self.instance_types.is_empty()
+    }
+}
+
+/// What to launch, as collected by the creation wizard.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateSpec {
+    pub resource_type: ResourceType,
+    pub name: String,
+    pub image: String,
+    pub instance_type: String,
+    pub key_pair: Option<String>,
+    pub tags: HashMap<String, String>,
+}
+
+impl CreateSpec {
+    pub fn new<S1: Into<String>, S2: Into<String>>(resource_type: ResourceType, image: S1, instance_type: S2) -> Self {
+        Self {
+            resource_type,
+            name: String::new(),
+            image: image.into(),
+            instance_type: instance_type.into(),
+            key_pair: None,
+            tags: HashMap::new(),
+        }
+    }
+
+    pub fn with_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn with_key_pair<S: Into<String>>(mut self, key_pair: S) -> Self {
+        self.key_pair = Some(key_pair.into());
+        self
+    }
+
+    pub fn with_tag<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.tags.insert(key.into(), value.into());
+        self
+    }
+
+    /// Tags to apply at launch: the given tags plus `Name` when a name is set.
+    pub fn launch_tags(&self) -> Vec<(String, String)> {
+        let mut tags: Vec<(String, String)> = self
+            .tags
+            .iter()
+            .filter(|(key, _)| key.as_str() != "Name" || self.name.is_empty())
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        if !self.name.is_empty() {
+            tags.push(("Name".to_string(), self.name.clone()));
+        }
+        tags.sort();
+        tags
+    }
+}
+
+/// Parses tags entered as `key=value` pairs separated by commas.
+///
+/// Entries without `=` become tags with an empty value; blank entries are
+/// ignored.
+pub fn parse_tag_list(input: &str) -> HashMap<String, String> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| match entry.split_once('=') {
+            Some((key, value)) => (key.trim().to_string(), value.trim().to_string()),
+            None => (entry.to_string(), String::new()),
+        })
+        .filter(|(key, _)| !key.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launch_tags_include_name() {
+        let spec = CreateSpec::new(ResourceType::Compute, "ami-123", "t3.micro")
+            .with_name("web-1")
+            .with_tag("Name", "ignored")
+            .with_tag("team", "platform");
+
+        assert_eq!(
+            spec.launch_tags(),
+            vec![
+                ("Name".to_string(), "web-1".to_string()),
+                ("team".to_string(), "platform".to_string()),
+            ]
+        );
+
+        let unnamed = CreateSpec::new(ResourceType::Compute, "ami-123", "t3.micro").with_tag("Name", "kept");
+        assert_eq!(unnamed.launch_tags(), vec![("Name".to_string(), "kept".to_string())]);
+    }
+
+    #[test]
+    fn test_parse_tag_list() {
+        let tags = parse_tag_list(" team = platform, env=dev ,, scratch");
+        assert_eq!(tags.len(), 3);
+        assert_eq!(tags.get("team").map(String::as_str), Some("platform"));
+        assert_eq!(tags.get("env").map(String::as_str), Some("dev"));
+        assert_eq!(tags.get("scratch").map(String::as_str), Some(""));
+        assert!(parse_tag_list("  ").is_empty());
+    }
+}
@@ -0,0 +1,72 @@
+/// A DNS record set within a hosted zone.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DnsRecord {
+    pub name: String,
+    /// Record type, e.g. "A", "CNAME", "MX"
+    pub record_type: String,
+    /// Time to live in seconds; `None` for alias records
+    pub ttl: Option<i64>,
+    /// Record values, or the alias target for alias records
+    pub values: Vec<String>,
+}
+
+impl DnsRecord {
+    /// Joins the record values for display on a single line.
+    pub fn values_display(&self) -> String {
+        self.values.join(", ")
+    }
+}
+
+/// Returns the records that must be deleted before a zone can be: all of
+/// them except the SOA and NS records at the zone apex, which the provider
+/// creates and removes with the zone.
+pub fn non_default_records(records: &[DnsRecord]) -> Vec<&DnsRecord> {
+    let apex = records
+        .iter()
+        .find(|r| r.record_type == "SOA")
+        .map(|r| r.name.as_str());
+
+    records
+        .iter()
+        .filter(|r| !(matches!(r.record_type.as_str(), "SOA" | "NS") && Some(r.name.as_str()) == apex))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(name: &str, record_type: &str) -> DnsRecord {
+        DnsRecord {
+            name: name.to_string(),
+            record_type: record_type.to_string(),
+            ttl: Some(300),
+            values: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_values_display() {
+        let record = DnsRecord {
+            name: "example.com.".to_string(),
+            record_type: "A".to_string(),
+            ttl: Some(300),
+            values: vec!["192.0.2.1".to_string(), "192.0.2.2".to_string()],
+        };
+        assert_eq!(record.values_display(), "192.0.2.1, 192.0.2.2");
+    }
+
+    #[test]
+    fn test_non_default_records() {
+        let records = vec![
+            record("example.com.", "SOA"),
+            record("example.com.", "NS"),
+            record("www.example.com.", "A"),
+            record("dev.example.com.", "NS"),
+        ];
+        let names: Vec<_> = non_default_records(&records).iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["www.example.com.", "dev.example.com."]);
+
+        assert!(non_default_records(&records[..2]).is_empty());
+    }
+}
@@ -0,0 +1,190 @@
+/// Health of one backend registered with a load balancer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetHealth {
+    /// Instance ID, IP address, or function ARN of the target
+    pub id: String,
+    pub port: Option<i32>,
+    /// Provider-reported state, e.g. "healthy", "unhealthy", "draining"
+    pub state: String,
+    /// Explanation of a non-healthy state, if the provider gives one
+    pub reason: Option<String>,
+}
+
+impl TargetHealth {
+    pub fn is_healthy(&self) -> bool {
+        self.state == "healthy"
+    }
+}
+
+/// A group of backends that a load balancer routes to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetGroupHealth {
+    pub name: String,
+    pub protocol: Option<String>,
+    pub port: Option<i32>,
+    pub targets: Vec<TargetHealth>,
+}
+
+impl TargetGroupHealth {
+    pub fn healthy_count(&self) -> usize {
+        self.targets.iter().filter(|t| t.is_healthy()).count()
+    }
+
+    pub fn unhealthy_count(&self) -> usize {
+        self.targets.len() - self.healthy_count()
+    }
+}
+
+/// Overall health of a resource, worst first when sorted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HealthStatus {
+    Impaired,
+    Warning,
+    Healthy,
+}
+
+impl HealthStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HealthStatus::Impaired => "Impaired",
+            HealthStatus::Warning => "Warning",
+            HealthStatus::Healthy => "Healthy",
+        }
+    }
+
+    /// Short marker shown in the resource list.
+    pub fn badge(&self) -> &'static str {
+        match self {
+            HealthStatus::Impaired => "✗",
+            HealthStatus::Warning => "!",
+            HealthStatus::Healthy => "✓",
+        }
+    }
+}
+
+/// Result of a provider health check, e.g. EC2 status checks, pending RDS
+/// maintenance, or load balancer target health.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceHealth {
+    pub status: HealthStatus,
+    /// Why the resource is not healthy, if it isn't
+    pub reason: Option<String>,
+}
+
+impl ResourceHealth {
+    pub fn healthy() -> Self {
+        Self {
+            status: HealthStatus::Healthy,
+            reason: None,
+        }
+    }
+
+    pub fn warning<S: Into<String>>(reason: S) -> Self {
+        Self {
+            status: HealthStatus::Warning,
+            reason: Some(reason.into()),
+        }
+    }
+
+    pub fn impaired<S: Into<String>>(reason: S) -> Self {
+        Self {
+            status: HealthStatus::Impaired,
+            reason: Some(reason.into()),
+        }
+    }
+
+    /// Keeps whichever of the two is worse; used when several checks cover
+    /// the same resource.
+    pub fn worst(self, other: ResourceHealth) -> ResourceHealth {
+        if other.status < self.status {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Summarizes a load balancer's target groups. Returns `None` when no
+    /// targets are registered, since there is nothing to judge.
+    pub fn from_target_groups(groups: &[TargetGroupHealth]) -> Option<ResourceHealth> {
+        let total: usize = groups.iter().map(|g| g.targets.len()).sum();
+        let unhealthy: usize = groups.iter().map(|g| g.unhealthy_count()).sum();
+
+        if total == 0 {
+            None
+        } else if unhealthy == total {
+            Some(ResourceHealth::impaired(format!("All {} targets unhealthy", total)))
+        } else if unhealthy > 0 {
+            Some(ResourceHealth::warning(format!("{} of {} targets unhealthy", unhealthy, total)))
+        } else {
+            Some(ResourceHealth::healthy())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(id: &str, state: &str) -> TargetHealth {
+        TargetHealth {
+            id: id.to_string(),
+            port: Some(80),
+            state: state.to_string(),
+            reason: None,
+        }
+    }
+
+    #[test]
+    fn test_target_group_counts() {
+        let group = TargetGroupHealth {
+            name: "web".to_string(),
+            protocol: Some("HTTP".to_string()),
+            port: Some(80),
+            targets: vec![
+                target("i-1", "healthy"),
+                target("i-2", "unhealthy"),
+                target("i-3", "draining"),
+            ],
+        };
+
+        assert_eq!(group.healthy_count(), 1);
+        assert_eq!(group.unhealthy_count(), 2);
+    }
+
+    #[test]
+    fn test_health_from_target_groups() {
+        let group = |targets: Vec<TargetHealth>| TargetGroupHealth {
+            name: "web".to_string(),
+            protocol: None,
+            port: Some(80),
+            targets,
+        };
+
+        assert_eq!(ResourceHealth::from_target_groups(&[group(Vec::new())]), None);
+        assert_eq!(
+            ResourceHealth::from_target_groups(&[group(vec![target("i-1", "healthy")])]),
+            Some(ResourceHealth::healthy())
+        );
+
+        let mixed = ResourceHealth::from_target_groups(&[group(vec![
+            target("i-1", "healthy"),
+            target("i-2", "unhealthy"),
+        ])])
+        .unwrap();
+        assert_eq!(mixed.status, HealthStatus::Warning);
+        assert_eq!(mixed.reason.as_deref(), Some("1 of 2 targets unhealthy"));
+
+        let down = ResourceHealth::from_target_groups(&[group(vec![target("i-2", "unhealthy")])]).unwrap();
+        assert_eq!(down.status, HealthStatus::Impaired);
+    }
+
+    #[test]
+    fn test_worst_health() {
+        let warning = ResourceHealth::warning("maintenance pending");
+        assert_eq!(ResourceHealth::healthy().worst(warning.clone()), warning);
+        assert_eq!(
+            ResourceHealth::impaired("down").worst(warning).status,
+            HealthStatus::Impaired
+        );
+    }
+}
@@ -0,0 +1,46 @@
+/// The account and principal a provider is authenticated as.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountIdentity {
+    pub account_id: String,
+    /// Human-friendly account alias, if one is set
+    pub alias: Option<String>,
+    /// ARN of the authenticated principal (user or assumed role)
+    pub arn: String,
+}
+
+impl AccountIdentity {
+    pub fn new(account_id: impl Into<String>, arn: impl Into<String>) -> Self {
+        Self {
+            account_id: account_id.into(),
+            alias: None,
+            arn: arn.into(),
+        }
+    }
+
+    pub fn with_alias(mut self, alias: Option<String>) -> Self {
+        self.alias = alias;
+        self
+    }
+
+    /// Formats the account as "alias (id)", or just the ID without an alias.
+    pub fn account_label(&self) -> String {
+        match self.alias {
+            Some(ref alias) => format!("{} ({})", alias, self.account_id),
+            None => self.account_id.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_label() {
+        let identity = AccountIdentity::new("123456789012", "arn:aws:iam::123456789012:user/alice");
+        assert_eq!(identity.account_label(), "123456789012");
+
+        let identity = identity.with_alias(Some("prod".to_string()));
+        assert_eq!(identity.account_label(), "prod (123456789012)");
+    }
+}
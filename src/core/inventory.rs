@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use crate::core::resource::{CloudResource, Provider, ResourceState, ResourceType};
+
+/// What a refresh saw of one resource, kept to compare refreshes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InventoryEntry {
+    pub id: String,
+    pub name: String,
+    pub resource_type: ResourceType,
+    pub provider: Provider,
+    pub region: String,
+    pub state: ResourceState,
+}
+
+impl InventoryEntry {
+    pub fn from_resource(resource: &dyn CloudResource) -> Self {
+        Self {
+            id: resource.id().to_string(),
+            name: resource.name().to_string(),
+            resource_type: resource.resource_type(),
+            provider: resource.provider(),
+            region: resource.region().to_string(),
+            state: resource.state(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeKind {
+    Created,
+    Terminated,
+    /// Listed both times, in a different state
+    StateChanged,
+}
+
+impl ChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::Created => "Created",
+            ChangeKind::Terminated => "Terminated",
+            ChangeKind::StateChanged => "State changed",
+        }
+    }
+}
+
+/// A difference between two inventories.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InventoryChange {
+    pub kind: ChangeKind,
+    /// The resource as last seen: in the newer inventory, or the older one
+    /// if it has gone
+    pub entry: InventoryEntry,
+    /// The state in the older inventory, for state changes
+    pub previous_state: Option<ResourceState>,
+}
+
+/// Compares two inventories and returns what was created, terminated, or
+/// changed state in between, grouped by kind and sorted by name. A resource
+/// that is still listed but terminated counts as a state change.
+pub fn diff_inventories(older: &[InventoryEntry], newer: &[InventoryEntry]) -> Vec<InventoryChange> {
+    let mut previous: HashMap<&str, &InventoryEntry> = older.iter().map(|e| (e.id.as_str(), e)).collect();
+    let mut changes = Vec::new();
+
+    for entry in newer {
+        match previous.remove(entry.id.as_str()) {
+            None => changes.push(InventoryChange {
+                kind: ChangeKind::Created,
+                entry: entry.clone(),
+                previous_state: None,
+            }),
+            Some(old) if old.state != entry.state => changes.push(InventoryChange {
+                kind: ChangeKind::StateChanged,
+                entry: entry.clone(),
+                previous_state: Some(old.state),
+            }),
+            Some(_) => {}
+        }
+    }
+
+    changes.extend(previous.into_values().map(|old| InventoryChange {
+        kind: ChangeKind::Terminated,
+        entry: old.clone(),
+        previous_state: None,
+    }));
+
+    changes.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.entry.name.cmp(&b.entry.name)));
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, state: ResourceState) -> InventoryEntry {
+        InventoryEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            resource_type: ResourceType::Compute,
+            provider: Provider::AWS,
+            region: "us-east-1".to_string(),
+            state,
+        }
+    }
+
+    #[test]
+    fn test_diff_inventories() {
+        let older = vec![
+            entry("web-1", ResourceState::Running),
+            entry("web-2", ResourceState::Running),
+            entry("batch", ResourceState::Stopped),
+        ];
+        let newer = vec![
+            entry("web-1", ResourceState::Running),
+            entry("batch", ResourceState::Running),
+            entry("web-3", ResourceState::Pending),
+        ];
+
+        let changes = diff_inventories(&older, &newer);
+        let summary: Vec<(ChangeKind, &str)> = changes.iter().map(|c| (c.kind, c.entry.id.as_str())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ChangeKind::Created, "web-3"),
+                (ChangeKind::Terminated, "web-2"),
+                (ChangeKind::StateChanged, "batch"),
+            ]
+        );
+        assert_eq!(changes[2].previous_state, Some(ResourceState::Stopped));
+        assert!(diff_inventories(&newer, &newer).is_empty());
+    }
+}
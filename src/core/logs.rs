@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+
+/// A single log line emitted by a resource.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    /// When the event was recorded
+    pub timestamp: DateTime<Utc>,
+    /// Raw log message
+    pub message: String,
+    /// Stream or source the event came from, if known
+    pub source: Option<String>,
+}
+
+impl LogEntry {
+    pub fn new<S: Into<String>>(timestamp: DateTime<Utc>, message: S) -> Self {
+        Self {
+            timestamp,
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    pub fn with_source<S: Into<String>>(mut self, source: S) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Formats the entry as a single display line.
+    pub fn display_line(&self) -> String {
+        format!(
+            "{} {}",
+            self.timestamp.format("%H:%M:%S"),
+            self.message.trim_end()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_entry_display_line() {
+        let timestamp = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let entry = LogEntry::new(timestamp, "request handled\n").with_source("i-123");
+
+        assert_eq!(entry.display_line(), "22:13:20 request handled");
+        assert_eq!(entry.source.as_deref(), Some("i-123"));
+    }
+}
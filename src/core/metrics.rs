@@ -0,0 +1,141 @@
+use chrono::{DateTime, Utc};
+
+/// A single timestamped metric sample.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetricPoint {
+    pub timestamp: DateTime<Utc>,
+    pub value: f64,
+}
+
+/// A time series for one resource metric (e.g., CPU utilization).
+///
+/// Datapoints are ordered oldest first so they can be plotted directly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricSeries {
+    /// Human-readable metric name
+    pub label: String,
+    /// Unit of the values (e.g., "Percent", "Bytes")
+    pub unit: String,
+    /// Samples ordered by ascending timestamp
+    pub datapoints: Vec<MetricPoint>,
+}
+
+impl MetricSeries {
+    /// Creates an empty series.
+    pub fn new<S1: Into<String>, S2: Into<String>>(label: S1, unit: S2) -> Self {
+        Self {
+            label: label.into(),
+            unit: unit.into(),
+            datapoints: Vec::new(),
+        }
+    }
+
+    /// Adds a sample, keeping datapoints sorted by timestamp.
+    pub fn push(&mut self, timestamp: DateTime<Utc>, value: f64) {
+        let idx = self
+            .datapoints
+            .partition_point(|point| point.timestamp <= timestamp);
+        self.datapoints.insert(idx, MetricPoint { timestamp, value });
+    }
+
+    /// Returns the most recent value.
+    pub fn latest(&self) -> Option<f64> {
+        self.datapoints.last().map(|point| point.value)
+    }
+
+    /// Returns the largest value in the series, ignoring any NaN samples.
+    pub fn max(&self) -> Option<f64> {
+        self.datapoints
+            .iter()
+            .map(|point| point.value)
+            .filter(|value| !value.is_nan())
+            .max_by(f64::total_cmp)
+    }
+
+    /// Returns the mean of all values.
+    pub fn average(&self) -> Option<f64> {
+        if self.datapoints.is_empty() {
+            return None;
+        }
+        let sum: f64 = self.datapoints.iter().map(|point| point.value).sum();
+        Some(sum / self.datapoints.len() as f64)
+    }
+
+    /// Returns true if the series has no samples.
+    pub fn is_empty(&self) -> bool {
+        self.datapoints.is_empty()
+    }
+
+    /// Formats a value in this series' unit for display.
+    pub fn format_value(&self, value: f64) -> String {
+        match self.unit.as_str() {
+            "Percent" => format!("{:.1}%", value),
+            "Bytes" => format_bytes(value),
+            "Bytes/Second" => format!("{}/s", format_bytes(value)),
+            "Count/Second" => format!("{:.1}/s", value),
+            _ => format!("{:.2}", value),
+        }
+    }
+}
+
+/// Formats a byte count with a binary unit, e.g. "1.5 GB".
+pub fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_metric_series_push_keeps_order() {
+        let now = Utc::now();
+        let mut series = MetricSeries::new("CPU Utilization", "Percent");
+        series.push(now, 30.0);
+        series.push(now - Duration::minutes(10), 10.0);
+        series.push(now - Duration::minutes(5), 20.0);
+
+        let values: Vec<f64> = series.datapoints.iter().map(|p| p.value).collect();
+        assert_eq!(values, vec![10.0, 20.0, 30.0]);
+        assert_eq!(series.latest(), Some(30.0));
+    }
+
+    #[test]
+    fn test_metric_series_stats() {
+        let now = Utc::now();
+        let mut series = MetricSeries::new("CPU Utilization", "Percent");
+        assert!(series.is_empty());
+        assert_eq!(series.average(), None);
+
+        series.push(now - Duration::minutes(5), 10.0);
+        series.push(now, 30.0);
+
+        assert_eq!(series.max(), Some(30.0));
+        assert_eq!(series.average(), Some(20.0));
+
+        series.push(now - Duration::minutes(1), f64::NAN);
+        assert_eq!(series.max(), Some(30.0));
+    }
+
+    #[test]
+    fn test_metric_series_format_value() {
+        let percent = MetricSeries::new("CPU", "Percent");
+        assert_eq!(percent.format_value(12.345), "12.3%");
+
+        let bytes = MetricSeries::new("Network In", "Bytes");
+        assert_eq!(bytes.format_value(2048.0), "2.0 KB");
+
+        let throughput = MetricSeries::new("Network Receive", "Bytes/Second");
+        assert_eq!(throughput.format_value(512.0), "512.0 B/s");
+    }
+}
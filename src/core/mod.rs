@@ -1,9 +1,40 @@
 pub mod action;
+pub mod alerts;
+pub mod audit;
+pub mod capabilities;
 pub mod cost;
+pub mod create;
+pub mod dns;
+pub mod health;
+pub mod identity;
+pub mod inventory;
+pub mod logs;
+pub mod metrics;
+pub mod operation;
 pub mod provider;
+pub mod quota;
 pub mod resource;
+pub mod tags;
+pub mod waste;
 
-pub use action::Action;
-pub use cost::{CostBreakdown, CostPeriod};
-pub use provider::CloudProvider;
-pub use resource::{CloudResource, Provider, ResourceState, ResourceType};
\ No newline at end of file
+pub use action::{Action, ActionParams, ActionRequest, ModifyOption};
+pub use alerts::{CostAlert, CostAlertPolicy};
+pub use audit::{ActionOutcome, ActionRecord};
+pub use capabilities::{CostFeature, ProviderCapabilities};
+pub use cost::{
+    Budget, CommitmentCoverage, CostAnomaly, CostBreakdown, CostForecast, CostGranularity, CostPeriod, CostPoint,
+    CostSnapshot, HOURS_PER_MONTH,
+};
+pub use create::{parse_tag_list, CreateOptions, CreateSpec};
+pub use dns::DnsRecord;
+pub use health::{HealthStatus, ResourceHealth, TargetGroupHealth, TargetHealth};
+pub use identity::AccountIdentity;
+pub use inventory::{diff_inventories, ChangeKind, InventoryChange, InventoryEntry};
+pub use logs::LogEntry;
+pub use metrics::{MetricPoint, MetricSeries};
+pub use operation::{OperationHandle, OperationStatus, OperationTarget};
+pub use provider::{collect_stream, send_batch, send_batch_if, CloudProvider, ListingEvent, ResourceSender};
+pub use quota::{Quota, QUOTA_WARNING_RATIO};
+pub use resource::{CloudResource, Provider, ResourceState, ResourceType};
+pub use tags::{TagChanges, TagPolicy};
+pub use waste::{potential_savings, savings_by_kind, WasteFinding, WasteKind};
\ No newline at end of file
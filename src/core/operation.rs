@@ -0,0 +1,181 @@
+use crate::core::action::{Action, ActionRequest};
+use crate::core::resource::{ResourceState, ResourceType};
+use chrono::{DateTime, Utc};
+
+/// The state a resource settles in once an action has finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationTarget {
+    /// The resource reaches this state
+    State(ResourceState),
+    /// The resource is deleted or reports `Terminated`
+    Gone,
+    /// The action has taken effect by the time the API call returns
+    Immediate,
+}
+
+impl OperationTarget {
+    /// Returns the target for a request, e.g. `Stopped` for `Action::Stop`.
+    pub fn for_request(request: &ActionRequest) -> Self {
+        match request.action {
+            Action::Start | Action::Restart | Action::Create => OperationTarget::State(ResourceState::Running),
+            Action::Stop | Action::Hibernate => OperationTarget::State(ResourceState::Stopped),
+            Action::Terminate => OperationTarget::Gone,
+            Action::Scale => match request.params.desired_capacity {
+                Some(0) => OperationTarget::State(ResourceState::Stopped),
+                _ => OperationTarget::State(ResourceState::Running),
+            },
+            _ => OperationTarget::Immediate,
+        }
+    }
+}
+
+/// Progress of an action after `CloudProvider::execute_action` returned.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OperationStatus {
+    /// Still under way; holds the resource's current state
+    InProgress(ResourceState),
+    Completed,
+    Failed(String),
+}
+
+impl OperationStatus {
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, OperationStatus::InProgress(_))
+    }
+}
+
+/// A started action that may still be running on the provider's side.
+///
+/// Returned by `CloudProvider::execute_action` and passed to
+/// `CloudProvider::poll_operation` until the resource reaches the target.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationHandle {
+    pub resource_id: String,
+    pub resource_type: ResourceType,
+    pub action: Action,
+    pub target: OperationTarget,
+    pub started_at: DateTime<Utc>,
+}
+
+impl OperationHandle {
+    pub fn new<S: Into<String>>(resource_id: S, resource_type: ResourceType, request: &ActionRequest) -> Self {
+        Self {
+            resource_id: resource_id.into(),
+            resource_type,
+            action: request.action,
+            target: OperationTarget::for_request(request),
+            started_at: Utc::now(),
+        }
+    }
+
+    /// Returns true if there is nothing left to wait for.
+    pub fn is_immediate(&self) -> bool {
+        self.target == OperationTarget::Immediate
+    }
+
+    /// Compares the resource's current state with the target. `None` means
+    /// the resource no longer exists.
+    pub fn status_for(&self, state: Option<ResourceState>) -> OperationStatus {
+        match (self.target, state) {
+            (OperationTarget::Immediate, _) => OperationStatus::Completed,
+            (OperationTarget::Gone, None | Some(ResourceState::Terminated)) => OperationStatus::Completed,
+            (OperationTarget::State(_), None) => {
+                OperationStatus::Failed("the resource no longer exists".to_string())
+            }
+            (OperationTarget::State(target), Some(state)) if state == target => OperationStatus::Completed,
+            // Impaired but up still counts as started
+            (OperationTarget::State(ResourceState::Running), Some(ResourceState::Degraded)) => {
+                OperationStatus::Completed
+            }
+            (OperationTarget::State(_), Some(ResourceState::Terminated)) => {
+                OperationStatus::Failed("the resource was terminated".to_string())
+            }
+            (_, Some(ResourceState::Error)) => {
+                OperationStatus::Failed("the resource entered an error state".to_string())
+            }
+            (_, Some(state)) => OperationStatus::InProgress(state),
+        }
+    }
+
+    pub fn elapsed_secs(&self, now: DateTime<Utc>) -> i64 {
+        (now - self.started_at).num_seconds().max(0)
+    }
+
+    /// Describes the operation for progress displays, e.g. "Stopping… 45s".
+    pub fn progress_label(&self, now: DateTime<Utc>) -> String {
+        let secs = self.elapsed_secs(now);
+        let elapsed = if secs < 60 {
+            format!("{}s", secs)
+        } else {
+            format!("{}m {:02}s", secs / 60, secs % 60)
+        };
+        format!("{}… {}", progress_verb(self.action), elapsed)
+    }
+}
+
+fn progress_verb(action: Action) -> &'static str {
+    match action {
+        Action::Start => "Starting",
+        Action::Stop => "Stopping",
+        Action::Hibernate => "Hibernating",
+        Action::Restart => "Restarting",
+        Action::Terminate => "Terminating",
+        Action::Scale => "Scaling",
+        Action::Modify => "Modifying",
+        Action::Snapshot => "Snapshotting",
+        Action::Create => "Creating",
+        _ => "Working",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn handle(request: ActionRequest) -> OperationHandle {
+        OperationHandle::new("i-123", ResourceType::Compute, &request)
+    }
+
+    #[test]
+    fn test_target_for_request() {
+        assert_eq!(handle(Action::Stop.into()).target, OperationTarget::State(ResourceState::Stopped));
+        assert_eq!(handle(Action::Restart.into()).target, OperationTarget::State(ResourceState::Running));
+        assert_eq!(handle(Action::Terminate.into()).target, OperationTarget::Gone);
+        assert!(handle(Action::Tag.into()).is_immediate());
+
+        let scale_down = ActionRequest::new(Action::Scale).with_desired_capacity(0);
+        assert_eq!(handle(scale_down).target, OperationTarget::State(ResourceState::Stopped));
+    }
+
+    #[test]
+    fn test_status_for_state_target() {
+        let stop = handle(Action::Stop.into());
+        assert_eq!(
+            stop.status_for(Some(ResourceState::Stopping)),
+            OperationStatus::InProgress(ResourceState::Stopping)
+        );
+        assert_eq!(stop.status_for(Some(ResourceState::Stopped)), OperationStatus::Completed);
+        assert!(matches!(stop.status_for(Some(ResourceState::Error)), OperationStatus::Failed(_)));
+        assert!(matches!(stop.status_for(None), OperationStatus::Failed(_)));
+
+        let start = handle(Action::Start.into());
+        assert_eq!(start.status_for(Some(ResourceState::Degraded)), OperationStatus::Completed);
+    }
+
+    #[test]
+    fn test_status_for_gone_target() {
+        let terminate = handle(Action::Terminate.into());
+        assert!(!terminate.status_for(Some(ResourceState::Stopping)).is_finished());
+        assert!(!terminate.status_for(Some(ResourceState::Deleting)).is_finished());
+        assert_eq!(terminate.status_for(Some(ResourceState::Terminated)), OperationStatus::Completed);
+        assert_eq!(terminate.status_for(None), OperationStatus::Completed);
+    }
+
+    #[test]
+    fn test_progress_label() {
+        let stop = handle(Action::Stop.into());
+        assert_eq!(stop.progress_label(stop.started_at + Duration::seconds(45)), "Stopping… 45s");
+        assert_eq!(stop.progress_label(stop.started_at + Duration::seconds(125)), "Stopping… 2m 05s");
+    }
+}
@@ -1,10 +1,41 @@
 use async_trait::async_trait;
 use crate::core::{
-    action::Action,
-    cost::{CostBreakdown, CostPeriod},
+    action::{Action, ActionRequest, ModifyOption},
+    capabilities::ProviderCapabilities,
+    cost::{Budget, CommitmentCoverage, CostAnomaly, CostBreakdown, CostGranularity, CostPeriod, CostPoint},
+    create::{CreateOptions, CreateSpec},
+    dns::DnsRecord,
+    health::{ResourceHealth, TargetGroupHealth},
+    identity::AccountIdentity,
+    logs::LogEntry,
+    metrics::MetricSeries,
+    operation::{OperationHandle, OperationStatus},
+    quota::Quota,
     resource::{CloudResource, Provider, ResourceType},
+    tags::TagChanges,
+    waste::WasteFinding,
 };
-use crate::error::Result;
+use crate::error::{NimbusError, Result};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::future::Future;
+use tokio::sync::mpsc;
+
+/// Channel end that `CloudProvider::stream_all_resources` sends listing
+/// progress and batches to.
+pub type ResourceSender = mpsc::UnboundedSender<ListingEvent>;
+
+/// Progress of one listing in `CloudProvider::stream_all_resources`, named
+/// after the service it queries (e.g. "EC2").
+pub enum ListingEvent {
+    /// The listing has started
+    Started(&'static str),
+    /// The listing finished with these resources, which may be none
+    Listed(&'static str, Vec<Box<dyn CloudResource>>),
+    /// The listing of the given resource type failed and its resources
+    /// were skipped
+    Failed(&'static str, ResourceType, String),
+}
 
 /// Core trait for cloud provider implementations.
 /// 
@@ -39,7 +70,7 @@ use crate::error::Result;
 /// #   async fn list_all_resources(&self) -> Result<Vec<Box<dyn nimbus::core::CloudResource>>> { Ok(vec![]) }
 /// #   async fn list_resources_by_type(&self, _: nimbus::core::ResourceType) -> Result<Vec<Box<dyn nimbus::core::CloudResource>>> { Ok(vec![]) }
 /// #   async fn get_resource(&self, _: &str) -> Result<Box<dyn nimbus::core::CloudResource>> { unimplemented!() }
-/// #   async fn execute_action(&self, _: &str, _: nimbus::core::Action) -> Result<()> { Ok(()) }
+/// #   async fn execute_action(&self, id: &str, t: nimbus::core::ResourceType, r: &nimbus::core::ActionRequest) -> Result<nimbus::core::OperationHandle> { Ok(nimbus::core::OperationHandle::new(id, t, r)) }
 /// #   async fn get_total_cost(&self, _: nimbus::core::CostPeriod) -> Result<f64> { Ok(0.0) }
 /// #   async fn get_cost_breakdown(&self) -> Result<nimbus::core::CostBreakdown> { Ok(nimbus::core::CostBreakdown::new()) }
 /// #   fn regions(&self) -> Vec<String> { vec![] }
@@ -55,12 +86,30 @@ pub trait CloudProvider: Send + Sync {
     /// Returns which cloud provider this is (AWS, GCP, Azure).
     fn provider_type(&self) -> Provider;
     
+    /// Returns a short name for the account, project, or cluster, shown to
+    /// tell several of the same provider apart. Defaults to `name`.
+    fn account_name(&self) -> &str {
+        self.name()
+    }
+    
     /// Authenticates with the cloud provider using configured credentials.
     /// 
     /// This should validate credentials and establish a session. Called once
     /// during initialization before any other operations.
     async fn authenticate(&mut self) -> Result<()>;
     
+    /// Returns the account and principal this provider is authenticated as,
+    /// or `None` before authentication or if the provider cannot tell.
+    fn identity(&self) -> Option<AccountIdentity> {
+        None
+    }
+    
+    /// Describes which resource types, actions, and cost features this
+    /// provider supports. Defaults to everything.
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities::default()
+    }
+    
     /// Tests the connection to the cloud provider.
     async fn test_connection(&self) -> Result<bool>;
     
@@ -70,6 +119,19 @@ pub trait CloudProvider: Send + Sync {
     /// `list_resources_by_type` to fetch specific types.
     async fn list_all_resources(&self) -> Result<Vec<Box<dyn CloudResource>>>;
     
+    /// Lists all resources like `list_all_resources`, but sends each
+    /// resource type to `sender` as soon as it has loaded, so callers can
+    /// show results before the slowest service responds.
+    /// 
+    /// The default sends everything from `list_all_resources` as one batch.
+    /// Providers that override this can implement `list_all_resources`
+    /// with `collect_stream`.
+    async fn stream_all_resources(&self, sender: ResourceSender) -> Result<()> {
+        let _ = sender.send(ListingEvent::Started("Resources"));
+        let _ = sender.send(ListingEvent::Listed("Resources", self.list_all_resources().await?));
+        Ok(())
+    }
+    
     /// Lists resources of a specific type in the current region.
     /// 
     /// More efficient than `list_all_resources` when you only need
@@ -83,7 +145,62 @@ pub trait CloudProvider: Send + Sync {
     async fn get_resource(&self, id: &str) -> Result<Box<dyn CloudResource>>;
     
     /// Executes an action on a resource.
-    async fn execute_action(&self, resource_id: &str, action: Action) -> Result<()>;
+    /// 
+    /// The resource type selects which service API handles the action.
+    /// Actions that need more input, such as a target size or a snapshot
+    /// name, read it from the request's parameters.
+    /// 
+    /// Returns as soon as the provider has accepted the request. Actions
+    /// such as stopping an instance carry on afterwards; pass the returned
+    /// handle to `poll_operation` to follow them.
+    async fn execute_action(
+        &self,
+        resource_id: &str,
+        resource_type: ResourceType,
+        request: &ActionRequest,
+    ) -> Result<OperationHandle>;
+    
+    /// Executes the same action on several resources.
+    /// 
+    /// Returns one result per target, in the order given, so that one
+    /// failure does not hide the others. The default calls `execute_action`
+    /// for each target in turn; providers whose APIs accept many resources
+    /// per call can override this.
+    async fn execute_action_bulk(
+        &self,
+        targets: &[(String, ResourceType)],
+        request: &ActionRequest,
+    ) -> Vec<Result<OperationHandle>> {
+        let mut results = Vec::with_capacity(targets.len());
+        for (resource_id, resource_type) in targets {
+            results.push(self.execute_action(resource_id, *resource_type, request).await);
+        }
+        results
+    }
+    
+    /// Checks whether an action started by `execute_action` has finished.
+    /// 
+    /// The default compares the resource's current state with the handle's
+    /// target. Resources that `get_resource` cannot find are looked up in
+    /// the listing for their type before being treated as gone.
+    async fn poll_operation(&self, handle: &OperationHandle) -> Result<OperationStatus> {
+        if handle.is_immediate() {
+            return Ok(OperationStatus::Completed);
+        }
+        
+        let state = match self.get_resource(&handle.resource_id).await {
+            Ok(resource) => Some(resource.state()),
+            Err(NimbusError::ResourceNotFound(_)) => self
+                .list_resources_by_type(handle.resource_type)
+                .await?
+                .iter()
+                .find(|resource| resource.id() == handle.resource_id)
+                .map(|resource| resource.state()),
+            Err(e) => return Err(e),
+        };
+        
+        Ok(handle.status_for(state))
+    }
     
     /// Gets the total cost for a given time period.
     async fn get_total_cost(&self, period: CostPeriod) -> Result<f64>;
@@ -94,6 +211,208 @@ pub trait CloudProvider: Send + Sync {
     /// categorization by service type and geographic region.
     async fn get_cost_breakdown(&self) -> Result<CostBreakdown>;
     
+    /// Gets this month's spend grouped by the values of a cost-allocation tag.
+    /// 
+    /// Spend on resources without the tag is reported under `UNTAGGED`.
+    async fn get_cost_by_tag(&self, _tag_key: &str) -> Result<HashMap<String, f64>> {
+        Ok(HashMap::new())
+    }
+    
+    /// Gets reservation and savings plan coverage over the last 30 days, along
+    /// with commitments that expire soon.
+    async fn get_commitment_coverage(&self) -> Result<CommitmentCoverage> {
+        Ok(CommitmentCoverage::default())
+    }
+    
+    /// Gets the account's spending budgets with actual and forecast spend.
+    async fn get_budgets(&self) -> Result<Vec<Budget>> {
+        Ok(Vec::new())
+    }
+    
+    /// Gets cost anomalies that are ongoing or ended within the last day.
+    async fn get_cost_anomalies(&self) -> Result<Vec<CostAnomaly>> {
+        Ok(Vec::new())
+    }
+    
+    /// Gets usage against the limit for the account's key service quotas.
+    async fn get_quotas(&self) -> Result<Vec<Quota>> {
+        Ok(Vec::new())
+    }
+    
+    /// Gets spend over a period as a series of daily or monthly buckets.
+    /// 
+    /// Returns an empty series if the provider has no billing history API.
+    async fn get_cost_history(
+        &self,
+        _period: CostPeriod,
+        _granularity: CostGranularity,
+    ) -> Result<Vec<CostPoint>> {
+        Ok(Vec::new())
+    }
+    
+    /// Gets recent performance metrics (CPU, network, disk) for a resource.
+    /// 
+    /// Returns one series per metric, oldest sample first. Providers or
+    /// resource types without monitoring data return an empty list.
+    async fn get_metrics(
+        &self,
+        _resource_id: &str,
+        _resource_type: ResourceType,
+    ) -> Result<Vec<MetricSeries>> {
+        Ok(Vec::new())
+    }
+    
+    /// Takes a snapshot of a resource and then terminates it.
+    /// 
+    /// The default runs `Action::Snapshot` followed by `Action::Terminate`.
+    /// Providers that can snapshot as part of deletion should override this.
+    async fn terminate_with_snapshot(
+        &self,
+        resource_id: &str,
+        resource_type: ResourceType,
+    ) -> Result<OperationHandle> {
+        self.execute_action(resource_id, resource_type, &Action::Snapshot.into()).await?;
+        self.execute_action(resource_id, resource_type, &Action::Terminate.into()).await
+    }
+    
+    /// Describes what would make deleting a resource fail, such as a bucket
+    /// that still holds objects, so it can be shown before `Action::Terminate`.
+    /// 
+    /// An empty list means nothing is known to block the deletion.
+    async fn deletion_blockers(
+        &self,
+        _resource_id: &str,
+        _resource_type: ResourceType,
+    ) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+    
+    /// Adds, updates, and removes tags on a resource.
+    async fn update_tags(
+        &self,
+        _resource_id: &str,
+        resource_type: ResourceType,
+        _changes: &TagChanges,
+    ) -> Result<()> {
+        Err(NimbusError::UnsupportedAction(Action::Tag, resource_type))
+    }
+    
+    /// Starts creating a machine image from a resource and returns the image ID.
+    /// 
+    /// Image creation continues in the background; poll `image_state` to
+    /// follow its progress.
+    async fn create_image(&self, _resource_id: &str, _name: &str) -> Result<String> {
+        Err(NimbusError::UnsupportedAction(Action::CreateImage, ResourceType::Compute))
+    }
+    
+    /// Returns the provider's state for an image (e.g., "pending", "available").
+    async fn image_state(&self, image_id: &str) -> Result<String> {
+        Err(NimbusError::ResourceNotFound(image_id.to_string()))
+    }
+    
+    /// Lists the configuration changes available through `Action::Modify`.
+    /// 
+    /// Resources that cannot be modified return an empty list.
+    async fn modify_options(
+        &self,
+        _resource_id: &str,
+        _resource_type: ResourceType,
+    ) -> Result<Vec<ModifyOption>> {
+        Ok(Vec::new())
+    }
+    
+    /// Applies a change previously returned by `modify_options`.
+    async fn modify_resource(
+        &self,
+        _resource_id: &str,
+        resource_type: ResourceType,
+        _value: &str,
+    ) -> Result<()> {
+        Err(NimbusError::UnsupportedAction(Action::Modify, resource_type))
+    }
+    
+    /// Lists the images, sizes, and key pairs offered when creating a
+    /// resource of the given type through `Action::Create`.
+    async fn create_options(&self, resource_type: ResourceType) -> Result<CreateOptions> {
+        Err(NimbusError::UnsupportedAction(Action::Create, resource_type))
+    }
+    
+    /// Launches a new resource and returns a handle that completes once it
+    /// is running.
+    async fn create_resource(&self, spec: &CreateSpec) -> Result<OperationHandle> {
+        Err(NimbusError::UnsupportedAction(Action::Create, spec.resource_type))
+    }
+    
+    /// Gets recent log events for a resource, oldest first.
+    /// 
+    /// When `since` is given only newer events are returned, which lets
+    /// callers tail logs by polling. Resources without logs return an
+    /// empty list.
+    async fn get_logs(
+        &self,
+        _resource_id: &str,
+        _resource_type: ResourceType,
+        _since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<LogEntry>> {
+        Ok(Vec::new())
+    }
+    
+    /// Turns off termination protection so the resource can be terminated.
+    async fn remove_termination_protection(
+        &self,
+        _resource_id: &str,
+        resource_type: ResourceType,
+    ) -> Result<()> {
+        Err(NimbusError::UnsupportedAction(Action::Terminate, resource_type))
+    }
+    
+    /// Lists the record sets in a DNS zone.
+    async fn list_dns_records(&self, _zone_id: &str) -> Result<Vec<DnsRecord>> {
+        Err(NimbusError::UnsupportedAction(Action::ViewRecords, ResourceType::DNS))
+    }
+    
+    /// Gets the backend groups of a load balancer and the health of each
+    /// registered target. Returns an empty list for other resources.
+    async fn get_target_health(
+        &self,
+        _resource_id: &str,
+        _resource_type: ResourceType,
+    ) -> Result<Vec<TargetGroupHealth>> {
+        Ok(Vec::new())
+    }
+    
+    /// Runs the provider's health checks for the given resources, as
+    /// (ID, type) pairs, and returns the result per resource ID. Resources
+    /// without a check are left out.
+    async fn get_health(&self, _resources: &[(String, ResourceType)]) -> Result<HashMap<String, ResourceHealth>> {
+        Ok(HashMap::new())
+    }
+    
+    /// Looks for resources that cost money while sitting idle, such as
+    /// unattached volumes or load balancers without targets.
+    async fn find_waste(&self) -> Result<Vec<WasteFinding>> {
+        Ok(Vec::new())
+    }
+    
+    /// Builds the command that opens an interactive shell on a resource.
+    /// 
+    /// The caller suspends the TUI while the command runs with inherited
+    /// stdio. Returns `None` if the resource cannot be connected to.
+    fn shell_command(
+        &self,
+        _resource_id: &str,
+        _resource_type: ResourceType,
+    ) -> Option<std::process::Command> {
+        None
+    }
+    
+    /// Builds the URL of the resource's page in the provider's web console.
+    /// 
+    /// Returns `None` if the console has no page for the resource.
+    fn console_url(&self, _resource_id: &str, _resource_type: ResourceType) -> Option<String> {
+        None
+    }
+    
     /// Returns all available regions for this provider.
     fn regions(&self) -> Vec<String>;
     
@@ -102,4 +421,59 @@ pub trait CloudProvider: Send + Sync {
     
     /// Changes the active region for resource queries.
     async fn set_region(&mut self, region: &str) -> Result<()>;
-}
\ No newline at end of file
+}
+
+/// Awaits the listing of the named service and sends its results as a
+/// batch. A failed listing is logged and reported along with the resource
+/// type it covers, then skipped so the other resource types still load.
+pub async fn send_batch<F>(
+    sender: &ResourceSender,
+    resource_type: ResourceType,
+    service: &'static str,
+    listing: F,
+) where
+    F: Future<Output = Result<Vec<Box<dyn CloudResource>>>>,
+{
+    // The receiver only goes away when nobody wants the results
+    let _ = sender.send(ListingEvent::Started(service));
+    match listing.await {
+        Ok(resources) => {
+            let _ = sender.send(ListingEvent::Listed(service, resources));
+        }
+        Err(e) => {
+            log::warn!("Skipping {} during refresh: {}", service, e);
+            let _ = sender.send(ListingEvent::Failed(service, resource_type, e.to_string()));
+        }
+    }
+}
+
+/// Like `send_batch`, but skips the listing without running it when the
+/// resource type is turned off.
+pub async fn send_batch_if<F>(
+    sender: &ResourceSender,
+    capabilities: &ProviderCapabilities,
+    resource_type: ResourceType,
+    service: &'static str,
+    listing: F,
+) where
+    F: Future<Output = Result<Vec<Box<dyn CloudResource>>>>,
+{
+    if capabilities.supports_type(resource_type) {
+        send_batch(sender, resource_type, service, listing).await;
+    }
+}
+
+/// Runs `stream_all_resources` to completion and gathers every batch into
+/// one list.
+pub async fn collect_stream<P: CloudProvider + ?Sized>(provider: &P) -> Result<Vec<Box<dyn CloudResource>>> {
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    provider.stream_all_resources(sender).await?;
+
+    let mut all_resources = Vec::new();
+    while let Some(event) = receiver.recv().await {
+        if let ListingEvent::Listed(_, batch) = event {
+            all_resources.extend(batch);
+        }
+    }
+    Ok(all_resources)
+}
@@ -0,0 +1,76 @@
+/// Fraction of a quota in use at which it is flagged as close to exhaustion.
+pub const QUOTA_WARNING_RATIO: f64 = 0.8;
+
+/// A service limit and how much of it is in use.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quota {
+    /// Service the quota belongs to, e.g. "EC2"
+    pub service: String,
+    pub name: String,
+    /// Provider quota code, e.g. "L-1216C47A"
+    pub code: Option<String>,
+    pub usage: f64,
+    pub limit: f64,
+}
+
+impl Quota {
+    pub fn new(service: impl Into<String>, name: impl Into<String>, usage: f64, limit: f64) -> Self {
+        Self {
+            service: service.into(),
+            name: name.into(),
+            code: None,
+            usage,
+            limit,
+        }
+    }
+
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Returns usage as a fraction of the limit, capped at 1.0.
+    pub fn used_ratio(&self) -> f64 {
+        if self.limit > 0.0 {
+            (self.usage / self.limit).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.limit > 0.0 && self.usage >= self.limit
+    }
+
+    pub fn is_near_limit(&self) -> bool {
+        self.limit > 0.0 && self.usage / self.limit >= QUOTA_WARNING_RATIO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quota_thresholds() {
+        let quota = Quota::new("EC2", "Elastic IPs", 2.0, 5.0).with_code("L-0263D0A3");
+        assert_eq!(quota.used_ratio(), 0.4);
+        assert!(!quota.is_near_limit());
+
+        let quota = Quota::new("EC2", "Elastic IPs", 4.0, 5.0);
+        assert!(quota.is_near_limit());
+        assert!(!quota.is_exhausted());
+
+        let quota = Quota::new("EC2", "Elastic IPs", 6.0, 5.0);
+        assert!(quota.is_exhausted());
+        assert_eq!(quota.used_ratio(), 1.0);
+    }
+
+    #[test]
+    fn test_zero_limit_is_never_flagged() {
+        let quota = Quota::new("VPC", "VPCs per Region", 0.0, 0.0);
+        assert_eq!(quota.used_ratio(), 0.0);
+        assert!(!quota.is_near_limit());
+        assert!(!quota.is_exhausted());
+    }
+}
@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use std::fmt;
 use serde::{Deserialize, Serialize}; // CHANGES: Added serde imports for serialization support
 
+use crate::core::health::ResourceHealth;
+
 /// Core trait representing any cloud resource across providers.
 /// 
 /// This trait provides a unified interface for working with resources from different
@@ -57,6 +59,49 @@ pub trait CloudResource: Send + Sync {
     /// Actions depend on resource type and current state.
     fn supported_actions(&self) -> Vec<crate::core::action::Action>;
     
+    /// Returns true if the resource is known to be reachable by anyone.
+    /// Defaults to false when the provider cannot tell.
+    fn is_publicly_accessible(&self) -> bool {
+        false
+    }
+    
+    /// Returns provider-specific fields as (label, value) pairs, in display
+    /// order, e.g. the instance type, IPs, or engine version.
+    fn details(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+    
+    /// Returns health the resource can judge from its own listing, e.g. a
+    /// database reporting full storage. Provider checks that need extra
+    /// calls come from `CloudProvider::get_health` instead.
+    fn health(&self) -> Option<ResourceHealth> {
+        None
+    }
+    
+    /// Returns every known field of the resource as JSON, for the detail
+    /// view's Raw tab. Resources that keep the provider's own response can
+    /// return that instead.
+    fn raw_json(&self) -> serde_json::Value {
+        let details: serde_json::Map<String, serde_json::Value> = self
+            .details()
+            .into_iter()
+            .map(|(label, value)| (label, serde_json::Value::String(value)))
+            .collect();
+        serde_json::json!({
+            "id": self.id(),
+            "name": self.name(),
+            "type": self.resource_type().as_str(),
+            "provider": self.provider().as_str(),
+            "region": self.region(),
+            "state": self.state().as_str(),
+            "cost_per_month": self.cost_per_month(),
+            "created_at": self.created_at().map(|created| created.to_rfc3339()),
+            "public": self.is_publicly_accessible(),
+            "tags": self.tags(),
+            "details": details,
+        })
+    }
+    
     /// Returns a reference to the concrete type for downcasting.
     /// Used when resource-specific fields need to be accessed.
     fn as_any(&self) -> &dyn std::any::Any;
@@ -132,6 +177,8 @@ pub enum Provider {
     AWS,
     GCP,
     Azure,
+    /// Workloads and nodes of a cluster reached through a kubeconfig context
+    Kubernetes,
 }
 
 impl Provider {
@@ -140,12 +187,13 @@ impl Provider {
             Provider::AWS => "AWS",
             Provider::GCP => "GCP",
             Provider::Azure => "Azure",
+            Provider::Kubernetes => "Kubernetes",
         }
     }
 
     /// Returns all available providers.
     pub fn all() -> Vec<Provider> {
-        vec![Provider::AWS, Provider::GCP, Provider::Azure]
+        vec![Provider::AWS, Provider::GCP, Provider::Azure, Provider::Kubernetes]
     }
 }
 
@@ -156,10 +204,14 @@ impl fmt::Display for Provider {
 }
 
 /// Operational state of a cloud resource.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ResourceState {
     /// Resource is active and operational
     Running,
+    /// Resource is running but impaired, e.g. full storage or failing nodes
+    Degraded,
+    /// Resource is being patched, upgraded, or repaired by the provider
+    Maintenance,
     /// Resource is stopped but can be started
     Stopped,
     /// Resource has been terminated/deleted
@@ -170,6 +222,8 @@ pub enum ResourceState {
     Stopping,
     /// Resource is in the process of starting
     Starting,
+    /// Resource is being deleted
+    Deleting,
     /// Resource is in an error state
     Error,
     /// State could not be determined
@@ -180,11 +234,14 @@ impl ResourceState {
     pub fn as_str(&self) -> &'static str {
         match self {
             ResourceState::Running => "Running",
+            ResourceState::Degraded => "Degraded",
+            ResourceState::Maintenance => "Maintenance",
             ResourceState::Stopped => "Stopped",
             ResourceState::Terminated => "Terminated",
             ResourceState::Pending => "Pending",
             ResourceState::Stopping => "Stopping",
             ResourceState::Starting => "Starting",
+            ResourceState::Deleting => "Deleting",
             ResourceState::Error => "Error",
             ResourceState::Unknown => "Unknown",
         }
@@ -192,12 +249,22 @@ impl ResourceState {
 
     /// Returns true if the resource is in an active/running state.
     pub fn is_active(&self) -> bool {
-        matches!(self, ResourceState::Running | ResourceState::Pending | ResourceState::Starting)
+        matches!(
+            self,
+            ResourceState::Running
+                | ResourceState::Degraded
+                | ResourceState::Maintenance
+                | ResourceState::Pending
+                | ResourceState::Starting
+        )
     }
 
     /// Returns true if the resource is in a transitional state.
     pub fn is_transitioning(&self) -> bool {
-        matches!(self, ResourceState::Pending | ResourceState::Starting | ResourceState::Stopping)
+        matches!(
+            self,
+            ResourceState::Pending | ResourceState::Starting | ResourceState::Stopping | ResourceState::Deleting
+        )
     }
 
     /// Returns true if the resource can be started.
@@ -207,7 +274,7 @@ impl ResourceState {
 
     /// Returns true if the resource can be stopped.
     pub fn can_stop(&self) -> bool {
-        matches!(self, ResourceState::Running)
+        matches!(self, ResourceState::Running | ResourceState::Degraded)
     }
 }
 
@@ -247,6 +314,7 @@ mod tests {
         assert_eq!(Provider::AWS.as_str(), "AWS");
         assert_eq!(Provider::GCP.as_str(), "GCP");
         assert_eq!(Provider::Azure.as_str(), "Azure");
+        assert_eq!(Provider::Kubernetes.as_str(), "Kubernetes");
     }
 
     #[test]
@@ -258,9 +326,10 @@ mod tests {
     #[test]
     fn test_provider_all() {
         let all = Provider::all();
-        assert_eq!(all.len(), 3);
+        assert_eq!(all.len(), 4);
         assert!(all.contains(&Provider::AWS));
         assert!(all.contains(&Provider::Azure));
+        assert!(all.contains(&Provider::Kubernetes));
     }
 
     #[test]
@@ -295,6 +364,26 @@ mod tests {
         assert!(!ResourceState::Stopped.is_transitioning());
     }
 
+    #[test]
+    fn test_extended_resource_states() {
+        assert_eq!(ResourceState::Degraded.as_str(), "Degraded");
+        assert!(ResourceState::Degraded.is_active());
+        assert!(ResourceState::Maintenance.is_active());
+        assert!(!ResourceState::Maintenance.is_transitioning());
+        assert!(ResourceState::Deleting.is_transitioning());
+        assert!(!ResourceState::Deleting.is_active());
+        assert!(ResourceState::Degraded.can_stop());
+        assert!(!ResourceState::Maintenance.can_stop());
+    }
+
+    #[test]
+    fn test_resource_state_serde() {
+        let json = serde_json::to_string(&ResourceState::Maintenance).unwrap();
+        assert_eq!(json, "\"Maintenance\"");
+        let state: ResourceState = serde_json::from_str("\"Deleting\"").unwrap();
+        assert_eq!(state, ResourceState::Deleting);
+    }
+
     #[test]
     fn test_resource_state_can_start() {
         assert!(ResourceState::Stopped.can_start());
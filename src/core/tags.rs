@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+
+/// Tag additions and removals to apply to a resource.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TagChanges {
+    /// Tags to create or overwrite
+    pub set: HashMap<String, String>,
+    /// Keys of tags to delete
+    pub remove: Vec<String>,
+}
+
+impl TagChanges {
+    /// Computes the changes needed to turn `current` into `desired`.
+    pub fn diff(current: &HashMap<String, String>, desired: &HashMap<String, String>) -> Self {
+        let set = desired
+            .iter()
+            .filter(|(key, value)| current.get(*key) != Some(*value))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+
+        let mut remove: Vec<String> = current
+            .keys()
+            .filter(|key| !desired.contains_key(*key))
+            .cloned()
+            .collect();
+        remove.sort();
+
+        Self { set, remove }
+    }
+
+    /// Returns the tag set that results from applying these changes to `tags`.
+    pub fn apply_to(&self, tags: &HashMap<String, String>) -> HashMap<String, String> {
+        let mut result = tags.clone();
+        for key in &self.remove {
+            result.remove(key);
+        }
+        result.extend(self.set.iter().map(|(k, v)| (k.clone(), v.clone())));
+        result
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty() && self.remove.is_empty()
+    }
+
+    /// Parses comma-separated edits that apply the same way to any
+    /// resource: `key=value` sets a tag and `-key` removes one. Returns
+    /// `None` if an entry has no key.
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut changes = Self::default();
+        for entry in input.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            match entry.strip_prefix('-') {
+                Some(key) if !key.trim().is_empty() => changes.remove.push(key.trim().to_string()),
+                Some(_) => return None,
+                None => {
+                    let (key, value) = parse_tag(entry)?;
+                    changes.set.insert(key, value);
+                }
+            }
+        }
+        Some(changes)
+    }
+}
+
+/// Tag keys every resource is expected to carry, e.g. `owner` and
+/// `cost-center`.
+///
+/// Keys match case-insensitively, since GCP labels are always lowercase,
+/// and a tag with an empty value counts as missing.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TagPolicy {
+    pub required: Vec<String>,
+}
+
+impl TagPolicy {
+    pub fn new(required: Vec<String>) -> Self {
+        Self { required }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.required.is_empty()
+    }
+
+    /// Returns the required keys absent from `tags`, in policy order.
+    pub fn missing_tags(&self, tags: &HashMap<String, String>) -> Vec<String> {
+        self.required
+            .iter()
+            .filter(|key| {
+                !tags
+                    .iter()
+                    .any(|(k, v)| k.eq_ignore_ascii_case(key) && !v.trim().is_empty())
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub fn is_compliant(&self, tags: &HashMap<String, String>) -> bool {
+        self.missing_tags(tags).is_empty()
+    }
+}
+
+/// Parses `key=value` input into a tag. The value may be empty.
+pub fn parse_tag(input: &str) -> Option<(String, String)> {
+    let (key, value) = match input.split_once('=') {
+        Some((key, value)) => (key.trim(), value.trim()),
+        None => (input.trim(), ""),
+    };
+
+    if key.is_empty() {
+        None
+    } else {
+        Some((key.to_string(), value.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_tag_changes_diff() {
+        let current = tags(&[("Name", "web"), ("Env", "dev"), ("Old", "x")]);
+        let desired = tags(&[("Name", "web"), ("Env", "prod"), ("Team", "core")]);
+
+        let changes = TagChanges::diff(&current, &desired);
+        assert_eq!(changes.set, tags(&[("Env", "prod"), ("Team", "core")]));
+        assert_eq!(changes.remove, vec!["Old".to_string()]);
+        assert_eq!(changes.apply_to(&current), desired);
+    }
+
+    #[test]
+    fn test_tag_changes_empty() {
+        let current = tags(&[("Name", "web")]);
+        assert!(TagChanges::diff(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn test_tag_policy_missing_tags() {
+        let policy = TagPolicy::new(vec!["owner".to_string(), "cost-center".to_string()]);
+
+        assert_eq!(
+            policy.missing_tags(&tags(&[("Owner", "alice"), ("Name", "web")])),
+            vec!["cost-center".to_string()]
+        );
+        assert_eq!(policy.missing_tags(&tags(&[("owner", " "), ("cost-center", "42")])), vec!["owner".to_string()]);
+        assert!(policy.is_compliant(&tags(&[("owner", "alice"), ("cost-center", "42")])));
+        assert!(TagPolicy::default().is_compliant(&HashMap::new()));
+    }
+
+    #[test]
+    fn test_tag_changes_parse() {
+        let changes = TagChanges::parse("team=core, -Old ,env = prod").unwrap();
+        assert_eq!(changes.set, tags(&[("team", "core"), ("env", "prod")]));
+        assert_eq!(changes.remove, vec!["Old".to_string()]);
+        assert!(TagChanges::parse("").unwrap().is_empty());
+        assert_eq!(TagChanges::parse("team=core, -"), None);
+    }
+
+    #[test]
+    fn test_parse_tag() {
+        assert_eq!(parse_tag("Env=prod"), Some(("Env".to_string(), "prod".to_string())));
+        assert_eq!(parse_tag(" Owner = a=b "), Some(("Owner".to_string(), "a=b".to_string())));
+        assert_eq!(parse_tag("Flag"), Some(("Flag".to_string(), String::new())));
+        assert_eq!(parse_tag("=value"), None);
+    }
+}
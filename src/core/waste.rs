@@ -0,0 +1,144 @@
+use crate::core::health::TargetGroupHealth;
+
+/// Why a resource is considered idle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum WasteKind {
+    /// A stopped instance that still pays for its attached volumes
+    StoppedWithVolumes,
+    UnattachedVolume,
+    /// A static public IP not associated with anything
+    UnattachedAddress,
+    /// A load balancer with no registered targets
+    IdleLoadBalancer,
+    /// A bucket with no current objects that keeps versioning on, so old
+    /// versions may still be billed
+    EmptyVersionedBucket,
+}
+
+impl WasteKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WasteKind::StoppedWithVolumes => "Stopped instance with volumes",
+            WasteKind::UnattachedVolume => "Unattached volume",
+            WasteKind::UnattachedAddress => "Unattached IP address",
+            WasteKind::IdleLoadBalancer => "Load balancer without targets",
+            WasteKind::EmptyVersionedBucket => "Empty versioned bucket",
+        }
+    }
+}
+
+/// A resource that costs money without doing anything useful.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WasteFinding {
+    pub kind: WasteKind,
+    pub resource_id: String,
+    pub name: String,
+    pub region: String,
+    /// What removing the resource would save per month in USD, if known
+    pub monthly_cost: Option<f64>,
+    /// Extra context, e.g. "2 volumes, 150 GiB"
+    pub detail: Option<String>,
+}
+
+impl WasteFinding {
+    pub fn new(
+        kind: WasteKind,
+        resource_id: impl Into<String>,
+        name: impl Into<String>,
+        region: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind,
+            resource_id: resource_id.into(),
+            name: name.into(),
+            region: region.into(),
+            monthly_cost: None,
+            detail: None,
+        }
+    }
+
+    pub fn with_monthly_cost(mut self, monthly_cost: f64) -> Self {
+        self.monthly_cost = Some(monthly_cost);
+        self
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+/// Returns the total monthly cost of the findings that have one.
+pub fn potential_savings(findings: &[WasteFinding]) -> f64 {
+    findings.iter().filter_map(|f| f.monthly_cost).sum()
+}
+
+/// Returns the number of findings and their monthly cost per kind, in kind
+/// order, leaving out kinds without findings.
+pub fn savings_by_kind(findings: &[WasteFinding]) -> Vec<(WasteKind, usize, f64)> {
+    let mut totals: Vec<(WasteKind, usize, f64)> = Vec::new();
+    for finding in findings {
+        match totals.iter_mut().find(|(kind, _, _)| *kind == finding.kind) {
+            Some((_, count, cost)) => {
+                *count += 1;
+                *cost += finding.monthly_cost.unwrap_or(0.0);
+            }
+            None => totals.push((finding.kind, 1, finding.monthly_cost.unwrap_or(0.0))),
+        }
+    }
+    totals.sort_by_key(|(kind, _, _)| *kind);
+    totals
+}
+
+/// Returns true if none of a load balancer's target groups has a target.
+/// A load balancer without target groups counts as idle too.
+pub fn is_idle_load_balancer(groups: &[TargetGroupHealth]) -> bool {
+    groups.iter().all(|g| g.targets.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::health::TargetHealth;
+
+    #[test]
+    fn test_savings() {
+        let findings = vec![
+            WasteFinding::new(WasteKind::UnattachedVolume, "vol-1", "data", "us-east-1").with_monthly_cost(8.0),
+            WasteFinding::new(WasteKind::UnattachedAddress, "eipalloc-1", "203.0.113.7", "us-east-1")
+                .with_monthly_cost(3.65),
+            WasteFinding::new(WasteKind::UnattachedVolume, "vol-2", "logs", "us-east-1").with_monthly_cost(2.0),
+            WasteFinding::new(WasteKind::EmptyVersionedBucket, "archive", "archive", "us-east-1"),
+        ];
+
+        assert!((potential_savings(&findings) - 13.65).abs() < 1e-9);
+        assert_eq!(
+            savings_by_kind(&findings),
+            vec![
+                (WasteKind::UnattachedVolume, 2, 10.0),
+                (WasteKind::UnattachedAddress, 1, 3.65),
+                (WasteKind::EmptyVersionedBucket, 1, 0.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_idle_load_balancer() {
+        let group = |targets: Vec<TargetHealth>| TargetGroupHealth {
+            name: "web".to_string(),
+            protocol: Some("HTTP".to_string()),
+            port: Some(80),
+            targets,
+        };
+        let target = TargetHealth {
+            id: "i-123".to_string(),
+            port: Some(80),
+            state: "unhealthy".to_string(),
+            reason: None,
+        };
+
+        assert!(is_idle_load_balancer(&[]));
+        assert!(is_idle_load_balancer(&[group(Vec::new())]));
+        assert!(!is_idle_load_balancer(&[group(Vec::new()), group(vec![target])]));
+    }
+}
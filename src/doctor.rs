@@ -0,0 +1,181 @@
+use std::path::PathBuf;
+
+use crate::cache::CacheStore;
+use crate::config::{CacheConfig, NimbusConfig};
+use crate::core::CloudProvider;
+use crate::error::Result;
+use crate::ui::theme::Palette;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    /// Works, but something is likely not what was intended
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CheckStatus::Pass => "PASS",
+            CheckStatus::Warn => "WARN",
+            CheckStatus::Fail => "FAIL",
+        }
+    }
+}
+
+/// The result of one `nimbus doctor` check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl Check {
+    pub fn pass(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Pass, detail)
+    }
+
+    pub fn warn(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Warn, detail)
+    }
+
+    pub fn fail(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Fail, detail)
+    }
+
+    fn new(name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        }
+    }
+
+    fn from_result<T>(name: &str, result: Result<T>, detail: impl FnOnce(T) -> String) -> Self {
+        match result {
+            Ok(value) => Self::pass(name, detail(value)),
+            Err(e) => Self::fail(name, e.to_string()),
+        }
+    }
+
+    /// Formats the check as one report line, e.g. `[PASS] Cache: ...`.
+    pub fn report_line(&self) -> String {
+        format!("[{}] {}: {}", self.status.as_str(), self.name, self.detail)
+    }
+}
+
+/// Checks the config files were found and parsed, and that their settings
+/// are valid.
+pub fn check_config(paths: &[PathBuf], loaded: &Result<NimbusConfig>) -> Vec<Check> {
+    let config = match loaded {
+        Ok(config) => config,
+        Err(e) => return vec![Check::fail("Config file", e.to_string())],
+    };
+
+    let locations: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+    let mut checks = vec![if locations.is_empty() {
+        Check::warn("Config file", "No config file found; using environment variables")
+    } else {
+        Check::pass("Config file", format!("Loaded {}", locations.join(", ")))
+    }];
+
+    checks.push(Check::from_result("Config settings", config.validate(), |_| "Valid".to_string()));
+    checks.push(Check::from_result("Theme", Palette::from_config(&config.theme), |_| {
+        format!("Preset '{}'", config.theme.preset)
+    }));
+    checks
+}
+
+/// Checks the cache database can be created and written to.
+pub fn check_cache(cache: &CacheConfig) -> Check {
+    if !cache.enabled {
+        return Check::warn("Cache", "Disabled; offline mode and the action history are unavailable");
+    }
+
+    if let Err(e) = cache.resource_ttls() {
+        return Check::fail("Cache", e.to_string());
+    }
+
+    let path = cache.get_db_path();
+    let writable = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::OpenOptions::new().create(true).append(true).open(&path));
+    if let Err(e) = writable {
+        return Check::fail("Cache", format!("{} is not writable: {}", path.display(), e));
+    }
+
+    Check::from_result("Cache", CacheStore::new(&path, cache.max_age_hours), |_| {
+        format!("{} is writable", path.display())
+    })
+}
+
+/// Authenticates the provider, then checks its region exists and that each
+/// resource type it is set to list can be listed, which is where missing
+/// permissions show up.
+pub async fn check_provider(provider: &mut dyn CloudProvider) -> Vec<Check> {
+    let name = provider.name().to_string();
+    let check_name = |what: &str| format!("{}: {}", name, what);
+
+    if let Err(e) = provider.authenticate().await {
+        return vec![Check::fail(check_name("credentials"), e.to_string())];
+    }
+    let identity = match provider.identity() {
+        Some(identity) => format!("Authenticated as {} in {}", identity.arn, identity.account_label()),
+        None => "Authenticated".to_string(),
+    };
+    let mut checks = vec![Check::pass(check_name("credentials"), identity)];
+
+    let region = provider.current_region().to_string();
+    checks.push(if provider.regions().contains(&region) {
+        Check::pass(check_name("region"), region)
+    } else {
+        Check::fail(check_name("region"), format!("'{}' is not available to this account", region))
+    });
+
+    checks.push(Check::from_result(&check_name("connection"), provider.test_connection().await, |_| {
+        "Reachable".to_string()
+    }));
+
+    for resource_type in provider.capabilities().resource_types {
+        let listed = provider.list_resources_by_type(resource_type).await;
+        checks.push(Check::from_result(
+            &check_name(&format!("list {}", resource_type.as_str())),
+            listed,
+            |resources| format!("{} found", resources.len()),
+        ));
+    }
+
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_cache() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut cache = CacheConfig {
+            db_path: Some(temp_dir.path().join("nested").join("cache.db").display().to_string()),
+            ..CacheConfig::default()
+        };
+        assert_eq!(check_cache(&cache).status, CheckStatus::Pass);
+
+        cache.ttl.insert("widgets".to_string(), "1h".to_string());
+        assert_eq!(check_cache(&cache).status, CheckStatus::Fail);
+
+        cache.enabled = false;
+        assert_eq!(check_cache(&cache).status, CheckStatus::Warn);
+    }
+
+    #[test]
+    fn test_check_config() {
+        let loaded = Ok(NimbusConfig::default());
+        let checks = check_config(&[], &loaded);
+        assert_eq!(checks[0].status, CheckStatus::Warn);
+        assert!(checks[0].report_line().starts_with("[WARN] Config file: No config file found"));
+    }
+}
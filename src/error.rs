@@ -31,6 +31,10 @@ pub enum NimbusError {
     #[error("Action {0:?} not supported for resource type {1:?}")]
     UnsupportedAction(crate::core::action::Action, crate::core::resource::ResourceType),
 
+    /// The resource is protected against termination.
+    #[error("{0} has termination protection enabled")]
+    TerminationProtected(String),
+
     /// Cache operation failed.
     #[error("Cache error: {0}")]
     CacheError(String),
@@ -129,6 +133,13 @@ mod tests {
         assert!(err.to_string().contains("Storage"));
     }
 
+    #[test]
+    fn test_termination_protected_error() {
+        let err = NimbusError::TerminationProtected("i-1234567890".to_string());
+        assert_eq!(err.to_string(), "i-1234567890 has termination protection enabled");
+        assert!(!err.is_recoverable());
+    }
+
     #[test]
     fn test_resource_not_found_error() {
         let err = NimbusError::ResourceNotFound("i-1234567890".to_string());
@@ -1,7 +1,9 @@
 pub mod app;
 pub mod cache;
+pub mod cli;
 pub mod config;
 pub mod core;
+pub mod doctor;
 pub mod error;
 pub mod providers;
 pub mod ui;
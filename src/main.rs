@@ -1,26 +1,38 @@
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use log::{error, info, warn};
 use nimbus::{
-    app::{AppState, TabIndex, ViewMode},
-    cache::CacheStore,
-    core::CloudProvider,
-    providers::AWSProvider,
-    ui, NimbusConfig, Result,
+    app::{
+        keymap::{KeyCommand, KeyTranslator, KeymapPreset},
+        AccountPicker, AppState, CreateStep, CreateWizard, DetailPane, DetailTab, ImageJob, ListColumn, OperationJob,
+        RegionPicker, TabIndex, TagEditor, ViewMode,
+    },
+    cache::{AccountListing, CacheStore, CachedResourceView, SyncSummary},
+    config::{AwsConfig, AwsProfileDetector, CacheConfig, ProviderConfigs, RetryConfig, UiConfig},
+    core::{
+        cost::merge_cost_points, ActionRecord, ActionRequest, CloudProvider, CloudResource, CostAlertPolicy,
+        CostFeature, CostForecast, CostGranularity, CostPeriod, CostSnapshot, MetricSeries, ModifyOption,
+        OperationHandle, OperationStatus, Provider, ResourceState, TagPolicy,
+    },
+    providers::{AWSProvider, AzureProvider, GCPProvider, KubernetesProvider, OfflineProvider},
+    doctor, ui, NimbusConfig, Result,
 };
+use clap::Parser;
+use nimbus::cli::{Cli, Command};
 use ratatui::{backend::CrosstermBackend, Terminal};
+use std::collections::BTreeMap;
 use std::io::{self, Write};
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
+use tokio::sync::{oneshot, RwLock};
 
 fn setup_logging() -> Result<()> {
-    let log_dir = dirs::home_dir()
-        .ok_or_else(|| nimbus::NimbusError::ConfigError("Could not determine home directory".to_string()))?
-        .join(".nimbus");
+    let log_dir = NimbusConfig::cache_dir()
+        .ok_or_else(|| nimbus::NimbusError::ConfigError("Could not determine home directory".to_string()))?;
     
     std::fs::create_dir_all(&log_dir)?;
     
@@ -49,45 +61,140 @@ fn setup_logging() -> Result<()> {
     Ok(())
 }
 
+/// Writes the action audit log to stdout, oldest first, as CSV or JSON.
+fn export_history(config: &NimbusConfig, json: bool) -> Result<()> {
+    if !config.cache.enabled {
+        return Err(nimbus::NimbusError::ConfigError(
+            "The action history is kept in the cache, which is disabled".to_string(),
+        ));
+    }
+
+    let store = CacheStore::new(&config.cache.get_db_path(), config.cache.max_age_hours)?;
+    let mut records = store.get_action_log(usize::MAX)?;
+    records.reverse();
+
+    let mut stdout = io::stdout().lock();
+    if json {
+        let output = serde_json::to_string_pretty(&records)
+            .map_err(|e| nimbus::NimbusError::CacheError(format!("Failed to serialize action history: {}", e)))?;
+        writeln!(stdout, "{}", output)?;
+    } else {
+        writeln!(stdout, "{}", ActionRecord::CSV_HEADER)?;
+        for record in &records {
+            writeln!(stdout, "{}", record.to_csv_row())?;
+        }
+    }
+    Ok(())
+}
+
+/// Runs every `nimbus doctor` check and prints one line per check. Fails if
+/// any check failed.
+async fn run_doctor(config_files: Vec<PathBuf>, loaded: Result<NimbusConfig>) -> Result<()> {
+    let mut checks = doctor::check_config(&config_files, &loaded);
+
+    if let Ok(config) = loaded {
+        checks.push(doctor::check_cache(&config.cache));
+        for mut provider in configured_providers(config.providers) {
+            checks.extend(doctor::check_provider(provider.as_mut()).await);
+        }
+    }
+
+    let mut stdout = io::stdout().lock();
+    for check in &checks {
+        writeln!(stdout, "{}", check.report_line())?;
+    }
+
+    let failed = checks.iter().filter(|c| c.status == doctor::CheckStatus::Fail).count();
+    if failed > 0 {
+        return Err(nimbus::NimbusError::ConfigError(format!("{} of {} checks failed", failed, checks.len())));
+    }
+    let warnings = checks.iter().filter(|c| c.status == doctor::CheckStatus::Warn).count();
+    writeln!(stdout, "{} checks passed, {} with warnings", checks.len(), warnings)?;
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    // Before logging starts, since the log file may be one of the files moved
+    let migrated = NimbusConfig::migrate_legacy_dir();
+
     setup_logging()?;
 
     info!("Nimbus - Cloud Resource Manager");
     info!("Starting application...");
 
-    let config = match NimbusConfig::load() {
+    match migrated {
+        Ok(paths) => {
+            for path in paths {
+                info!("Moved {:?} from the legacy ~/.nimbus directory", path);
+            }
+        }
+        Err(e) => warn!("Failed to migrate the legacy ~/.nimbus directory: {}", e),
+    }
+
+    let config_path = cli.config.clone();
+
+    // The command line overrides the environment, which overrides the file
+    let loaded = NimbusConfig::load_from(config_path.as_deref()).and_then(|mut config| {
+        config.select_context(cli.context.as_deref())?;
+        config.apply_env();
+        cli.apply(&mut config);
+        Ok(config)
+    });
+    if cli.command == Some(Command::Doctor) {
+        let config_files = match config_path {
+            Some(path) => vec![path],
+            None => NimbusConfig::config_files(),
+        };
+        return run_doctor(config_files, loaded).await;
+    }
+
+    let config = match loaded {
         Ok(config) => {
             info!("Configuration loaded successfully");
             config
         }
+        // A file or context asked for by name should be there
+        Err(e) if config_path.is_some() || cli.context.is_some() => return Err(e),
         Err(e) => {
             error!("Failed to load configuration: {}", e);
             info!("Using default configuration");
-            NimbusConfig::default()
+            let mut config = NimbusConfig::default();
+            config.apply_env();
+            cli.apply(&mut config);
+            config
         }
     };
 
+    if let Some(Command::History { json }) = cli.command {
+        return export_history(&config, json);
+    }
+
     if let Err(e) = config.validate() {
         error!("Configuration validation failed: {}", e);
         error!("Please configure at least one cloud provider.");
         error!(
             "Configuration file location: {:?}",
-            NimbusConfig::config_file_path()
+            config_path.clone().or_else(NimbusConfig::config_file_path)
         );
         return Err(e);
     }
 
     info!("Configuration validated successfully");
 
+    ui::theme::Theme::init(ui::theme::Palette::from_config(&config.theme)?);
+
     let cache_store = if config.cache.enabled {
         let db_path = config.cache.get_db_path();
         info!("Initializing cache at: {:?}", db_path);
         
+        let ttls = config.cache.resource_ttls()?;
         match CacheStore::new(&db_path, config.cache.max_age_hours) {
             Ok(store) => {
                 info!("Cache initialized successfully");
-                Some(Arc::new(store))
+                Some(Arc::new(store.with_ttls(ttls).with_expiry(!config.cache.offline)))
             }
             Err(e) => {
                 warn!("Failed to initialize cache: {}", e);
@@ -100,48 +207,195 @@ async fn main() -> Result<()> {
         None
     };
 
-    let mut providers: Vec<Arc<RwLock<Box<dyn nimbus::core::CloudProvider>>>> = Vec::new();
+    if config.cache.offline && cache_store.is_none() {
+        return Err(nimbus::NimbusError::ConfigError(
+            "Offline mode needs the cache, which could not be opened".to_string(),
+        ));
+    }
+
+    if let Some(ref name) = config.context {
+        info!("Using context '{}'", name);
+    }
+
+    let aws_accounts = config.providers.aws.clone();
+    let providers = if config.cache.offline {
+        info!("Offline mode: running from the cache without contacting providers");
+        offline_providers(config.providers)
+    } else {
+        connect_providers(config.providers, &config.cache, &config.retry).await
+    };
+
+    if providers.is_empty() {
+        error!("No providers available. Please check your configuration.");
+        return Err(nimbus::NimbusError::ConfigError(
+            "No cloud providers available".to_string(),
+        ));
+    }
+
+    if let Some(ref cache) = cache_store {
+        if config.cache.auto_prune && !config.cache.offline {
+            spawn_cache_maintenance(Arc::clone(cache), config.cache.prune_interval());
+        }
+    }
+
+    let cost_alert_policy = CostAlertPolicy::new(config.alerts.monthly_budget, config.alerts.resource_cost_ceiling);
+    let contexts = ContextConfigs {
+        active: config.context.clone(),
+        providers: config.contexts,
+        aws: aws_accounts,
+        cache: config.cache,
+        retry: config.retry,
+    };
+    run_tui(providers, cache_store, &config.ui, cost_alert_policy, contexts).await?;
+
+    Ok(())
+}
+
+/// Creates an unauthenticated AWS provider that shares the price cache when enabled.
+fn aws_provider(aws_config: AwsConfig, cache: &CacheConfig, retry: &RetryConfig) -> AWSProvider {
+    let account = aws_config.display_name().to_string();
+    let mut provider = AWSProvider::new(aws_config).with_retry_config(retry.clone());
+
+    if cache.enabled {
+        match CacheStore::new(&cache.get_db_path(), cache.max_age_hours) {
+            Ok(store) => provider = provider.with_price_cache(store),
+            Err(e) => warn!("Price cache unavailable for account '{}': {}", account, e),
+        }
+    }
+    provider
+}
+
+/// Authenticates every configured provider, leaving out any that fail.
+async fn connect_providers(
+    provider_configs: ProviderConfigs,
+    cache: &CacheConfig,
+    retry: &RetryConfig,
+) -> Vec<Arc<RwLock<Box<dyn CloudProvider>>>> {
+    let mut providers: Vec<Arc<RwLock<Box<dyn CloudProvider>>>> = Vec::new();
 
-    if let Some(aws_config) = config.providers.aws {
-        info!("Initializing AWS provider...");
-        let mut aws_provider = AWSProvider::new(aws_config);
+    for aws_config in provider_configs.aws {
+        let account = aws_config.display_name().to_string();
+        info!("Initializing AWS provider for account '{}'...", account);
+        let mut aws_provider = aws_provider(aws_config, cache, retry);
 
         match aws_provider.authenticate().await {
             Ok(_) => {
-                info!("AWS provider authenticated successfully");
+                info!("AWS account '{}' authenticated successfully", account);
                 providers.push(Arc::new(RwLock::new(Box::new(aws_provider)
                     as Box<dyn nimbus::core::CloudProvider>)));
             }
             Err(e) => {
-                error!("AWS authentication failed: {}", e);
-                error!("Continuing without AWS provider");
+                error!("AWS authentication failed for account '{}': {}", account, e);
+                error!("Continuing without AWS account '{}'", account);
             }
         }
     }
 
-    if config.providers.gcp.is_some() {
-        info!("GCP provider configured (not implemented yet)");
+    for gcp_config in provider_configs.gcp {
+        info!("Initializing GCP provider...");
+        let mut gcp_provider = GCPProvider::new(gcp_config);
+
+        match gcp_provider.authenticate().await {
+            Ok(_) => {
+                info!("{} authenticated successfully", gcp_provider.name());
+                providers.push(Arc::new(RwLock::new(Box::new(gcp_provider)
+                    as Box<dyn nimbus::core::CloudProvider>)));
+            }
+            Err(e) => {
+                error!("GCP authentication failed: {}", e);
+                error!("Continuing without GCP");
+            }
+        }
     }
-    if config.providers.azure.is_some() {
-        info!("Azure provider configured (not implemented yet)");
+
+    for azure_config in provider_configs.azure {
+        info!("Initializing Azure provider...");
+        let mut azure_provider = AzureProvider::new(azure_config);
+
+        match azure_provider.authenticate().await {
+            Ok(_) => {
+                info!("{} authenticated successfully", azure_provider.name());
+                providers.push(Arc::new(RwLock::new(Box::new(azure_provider)
+                    as Box<dyn nimbus::core::CloudProvider>)));
+            }
+            Err(e) => {
+                error!("Azure authentication failed: {}", e);
+                error!("Continuing without Azure");
+            }
+        }
     }
 
-    if providers.is_empty() {
-        error!("No providers available. Please check your configuration.");
-        return Err(nimbus::NimbusError::ConfigError(
-            "No cloud providers available".to_string(),
-        ));
+    for kubernetes_config in provider_configs.kubernetes {
+        info!("Initializing Kubernetes provider...");
+        let mut kubernetes_provider = KubernetesProvider::new(kubernetes_config);
+
+        match kubernetes_provider.authenticate().await {
+            Ok(_) => {
+                info!("{} authenticated successfully", kubernetes_provider.name());
+                providers.push(Arc::new(RwLock::new(Box::new(kubernetes_provider)
+                    as Box<dyn nimbus::core::CloudProvider>)));
+            }
+            Err(e) => {
+                error!("Kubernetes authentication failed: {}", e);
+                error!("Continuing without Kubernetes");
+            }
+        }
     }
 
-    run_tui(providers, cache_store).await?;
+    providers
+}
 
-    Ok(())
+/// Creates a provider for every configured account, unauthenticated.
+fn configured_providers(provider_configs: ProviderConfigs) -> Vec<Box<dyn CloudProvider>> {
+    let mut configured: Vec<Box<dyn CloudProvider>> = Vec::new();
+    for aws_config in provider_configs.aws {
+        configured.push(Box::new(AWSProvider::new(aws_config)));
+    }
+    for gcp_config in provider_configs.gcp {
+        configured.push(Box::new(GCPProvider::new(gcp_config)));
+    }
+    for azure_config in provider_configs.azure {
+        configured.push(Box::new(AzureProvider::new(azure_config)));
+    }
+    for kubernetes_config in provider_configs.kubernetes {
+        configured.push(Box::new(KubernetesProvider::new(kubernetes_config)));
+    }
+    configured
+}
+
+/// Builds a stand-in for every configured provider without authenticating,
+/// so cached resources can be shown under their accounts.
+fn offline_providers(provider_configs: ProviderConfigs) -> Vec<Arc<RwLock<Box<dyn CloudProvider>>>> {
+    configured_providers(provider_configs)
+        .iter()
+        .map(|provider| {
+            let offline: Box<dyn CloudProvider> = Box::new(OfflineProvider::new(provider.as_ref()));
+            Arc::new(RwLock::new(offline))
+        })
+        .collect()
+}
+
+/// What is needed to connect another context's accounts at runtime.
+struct ContextConfigs {
+    active: Option<String>,
+    providers: BTreeMap<String, ProviderConfigs>,
+    /// AWS accounts of a config file without contexts
+    aws: Vec<AwsConfig>,
+    cache: CacheConfig,
+    retry: RetryConfig,
 }
 
 async fn run_tui(
     providers: Vec<Arc<RwLock<Box<dyn nimbus::core::CloudProvider>>>>,
     cache_store: Option<Arc<CacheStore>>,
+    ui_config: &UiConfig,
+    cost_alert_policy: CostAlertPolicy,
+    contexts: ContextConfigs,
 ) -> Result<()> {
+    let offline = contexts.cache.offline;
+    let columns = ListColumn::parse_list(&ui_config.columns)?;
+    let keymap = KeymapPreset::parse(&ui_config.keymap)?;
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
@@ -151,63 +405,47 @@ async fn run_tui(
     let cache_enabled = cache_store.is_some();
     let mut app_state = AppState::new()
         .with_providers(providers)
-        .with_cache_enabled(cache_enabled);
+        .with_cache_enabled(cache_enabled)
+        .with_offline(offline)
+        .with_contexts(contexts.providers.keys().cloned().collect(), contexts.active.clone())
+        .with_snapshot_before_terminate(ui_config.snapshot_before_terminate)
+        .with_cost_tags(ui_config.cost_tags.clone())
+        .with_tag_policy(TagPolicy::new(ui_config.required_tags.clone()))
+        .with_columns(&columns)
+        .with_keymap(keymap)
+        .with_cost_alert_policy(cost_alert_policy);
+    app_state.load_identities().await;
+    app_state.load_capabilities().await;
 
     info!("Loading initial resources...");
     
     // Try to load from cache first if available
     // This provides instant startup if we have cached data
-    let loaded_from_cache = if let Some(ref cache) = cache_store {
-        info!("Checking cache for existing resources...");
-        match cache.get_all_cached_resources() {
-            Ok(cached_resources) if !cached_resources.is_empty() => {
-                info!("Found {} cached resources", cached_resources.len());
-                
-                if let Some(first) = cached_resources.first() {
-                    app_state.last_refresh = Some(first.cached_at);
-                    let age = chrono::Utc::now().signed_duration_since(first.cached_at);
-                    info!("Cache age: {}", format_duration(age));
-                }
-                
-                // Note: The actual cached resources are in the database
-                // We just set the timestamp here for the cache age display
-                // The refresh call below will populate the actual resources
-                true
-            }
-            Ok(_) => {
-                info!("Cache is empty");
-                false
-            }
-            Err(e) => {
-                warn!("Failed to query cache: {}", e);
-                false
-            }
-        }
-    } else {
-        false
-    };
+    if let Some(ref cache) = cache_store {
+        // Show the cached inventory until the refresh below replaces it
+        load_from_cache(&mut app_state, cache).await;
 
-    // ALWAYS fetch fresh resources on startup
-    // This ensures the user sees data immediately without needing to press 'r'
-    // Even if we have cache, we fetch fresh data to ensure accuracy
-    info!("Fetching fresh resources from cloud providers...");
-    match refresh_and_cache_resources(&mut app_state, &cache_store).await {
-        Ok(_) => {
-            if loaded_from_cache {
-                info!("Fresh resources loaded and cache updated");
-            } else {
-                info!("Initial resources loaded successfully");
-            }
+        match cache.get_cost_snapshots(nimbus::app::state::COST_TREND_DAYS) {
+            Ok(snapshots) => app_state.cost_snapshots = snapshots,
+            Err(e) => warn!("Failed to load cost snapshots: {}", e),
         }
-        Err(e) => {
-            error!("Failed to load resources: {}", e);
-            // If fresh fetch fails but we had cache, the user can still browse cached data
-            // Otherwise they'll see the empty state with an error message
-            app_state.set_error(format!("Failed to load resources: {}", e));
+    }
+
+    if offline {
+        if app_state.last_refresh.is_none() {
+            app_state.set_error("The cache is empty; run nimbus online once to fill it".to_string());
         }
+    } else {
+        // ALWAYS fetch fresh resources on startup
+        // This ensures the user sees data immediately without needing to press 'r'
+        // Even if we have cache, we fetch fresh data to ensure accuracy. Results
+        // stream into the list as each service responds; run_app takes them in
+        // and updates the cache once every provider has finished.
+        info!("Fetching fresh resources from cloud providers...");
+        app_state.start_refresh().await;
     }
 
-    let result = run_app(&mut terminal, &mut app_state, cache_store).await;
+    let result = run_app(&mut terminal, &mut app_state, cache_store, &contexts).await;
 
     disable_raw_mode()?;
     execute!(
@@ -226,6 +464,272 @@ async fn run_tui(
     Ok(())
 }
 
+/// Connects the accounts of another context and loads their resources in
+/// place of the current ones. Returns whether a success message was shown.
+async fn switch_context(
+    app_state: &mut AppState,
+    cache_store: &Option<Arc<CacheStore>>,
+    contexts: &ContextConfigs,
+    name: &str,
+) -> bool {
+    let provider_configs = match contexts.providers.get(name) {
+        Some(provider_configs) => provider_configs.clone(),
+        None => return false,
+    };
+
+    info!("Switching to context '{}'", name);
+    app_state.start_loading();
+    let providers = if contexts.cache.offline {
+        offline_providers(provider_configs)
+    } else {
+        connect_providers(provider_configs, &contexts.cache, &contexts.retry).await
+    };
+    app_state.stop_loading();
+
+    if providers.is_empty() {
+        let msg = format!("No account in context '{}' could be connected", name);
+        error!("{}", msg);
+        app_state.record_action(ActionRecord::failed("Switch context", msg.as_str()));
+        app_state.set_error(msg);
+        return false;
+    }
+
+    app_state.switch_context(name, providers).await;
+    if contexts.cache.offline {
+        if let Some(cache) = cache_store {
+            load_from_cache(app_state, cache).await;
+        }
+    } else {
+        app_state.start_refresh().await;
+    }
+
+    let msg = format!("Switched to context '{}'", name);
+    app_state.record_action(ActionRecord::succeeded("Switch context", msg.as_str()));
+    app_state.set_success(msg);
+    true
+}
+
+/// Opens the region picker for the provider on the active tab. On the All
+/// Clouds tab the selected resource's provider is used.
+async fn open_region_picker(app_state: &mut AppState) {
+    app_state.clear_messages();
+    if app_state.offline {
+        app_state.set_error("Regions cannot be changed while offline".to_string());
+        return;
+    }
+
+    let provider_type = match app_state.active_tab {
+        TabIndex::AWS => Some(Provider::AWS),
+        TabIndex::GCP => Some(Provider::GCP),
+        TabIndex::Azure => Some(Provider::Azure),
+        TabIndex::AllClouds => None,
+    };
+    let selected_idx = match provider_type {
+        Some(_) => None,
+        None => selected_resource(app_state).await.map(|(resource_idx, ..)| resource_idx),
+    };
+    let preferred = selected_idx
+        .and_then(|resource_idx| app_state.provider_for_resource(resource_idx))
+        .and_then(|provider| app_state.providers.iter().position(|p| Arc::ptr_eq(p, &provider)));
+
+    for (provider_idx, provider) in app_state.providers.iter().enumerate() {
+        if preferred.is_some_and(|preferred| preferred != provider_idx) {
+            continue;
+        }
+        let provider = provider.read().await;
+        if provider_type.is_some_and(|t| provider.provider_type() != t) {
+            continue;
+        }
+
+        let regions = provider.regions();
+        if regions.len() > 1 {
+            let picker = RegionPicker::new(provider_idx, provider.name(), regions, provider.current_region());
+            app_state.region_picker = Some(picker);
+            return;
+        }
+    }
+
+    app_state.set_error("No provider on this tab has regions to choose from".to_string());
+}
+
+/// Moves a provider to another region and reloads the resources.
+///
+/// Returns true if a success message was shown.
+async fn switch_region(app_state: &mut AppState, provider_idx: usize, region: &str) -> bool {
+    let provider = match app_state.providers.get(provider_idx) {
+        Some(provider) => Arc::clone(provider),
+        None => return false,
+    };
+    // A running refresh holds the provider until it finishes
+    if app_state.is_refreshing() {
+        app_state.set_error("Wait for the refresh to finish before switching regions".to_string());
+        return false;
+    }
+
+    info!("Switching provider {} to region {}", provider_idx, region);
+    app_state.start_loading();
+    let result = provider.write().await.set_region(region).await;
+    app_state.stop_loading();
+
+    let name = account_name(&provider).await;
+    match result {
+        Ok(()) => {
+            let msg = format!("Switched {} to {}", name, region);
+            app_state.record_action(ActionRecord::succeeded("Switch region", msg.as_str()));
+            app_state.start_refresh().await;
+            app_state.set_success(msg);
+            true
+        }
+        Err(e) => {
+            error!("Failed to switch region: {}", e);
+            let msg = format!("Failed to switch {} to {}: {}", name, region, e);
+            app_state.record_action(ActionRecord::failed("Switch region", msg.as_str()));
+            app_state.set_error(msg);
+            false
+        }
+    }
+}
+
+/// Opens the account switcher for the selected resource's AWS account, or
+/// else the first AWS account.
+async fn open_account_picker(app_state: &mut AppState, contexts: &ContextConfigs) {
+    app_state.clear_messages();
+    if app_state.offline {
+        app_state.set_error("Accounts cannot be switched while offline".to_string());
+        return;
+    }
+
+    let selected = match selected_resource(app_state).await {
+        Some((resource_idx, ..)) => app_state.provider_for_resource(resource_idx),
+        None => None,
+    };
+    let mut target = None;
+    for (provider_idx, provider) in app_state.providers.iter().enumerate() {
+        let provider_ref = provider.read().await;
+        if provider_ref.provider_type() != Provider::AWS {
+            continue;
+        }
+        let is_selected = selected.as_ref().is_some_and(|selected| Arc::ptr_eq(selected, provider));
+        if target.is_none() || is_selected {
+            target = Some((
+                provider_idx,
+                provider_ref.account_name().to_string(),
+                provider_ref.current_region().to_string(),
+            ));
+        }
+        if is_selected {
+            break;
+        }
+    }
+
+    let (provider_idx, current, region) = match target {
+        Some(target) => target,
+        None => {
+            app_state.set_error("No AWS account is connected".to_string());
+            return;
+        }
+    };
+
+    let configured = match app_state.active_context.as_ref().and_then(|name| contexts.providers.get(name)) {
+        Some(provider_configs) => &provider_configs.aws,
+        None => &contexts.aws,
+    };
+    let profiles = AwsProfileDetector::detect_profiles().unwrap_or_else(|e| {
+        warn!("Could not read AWS profiles: {}", e);
+        Vec::new()
+    });
+
+    let picker = AccountPicker::new(provider_idx, &current, &region, configured, profiles);
+    if picker.choices.iter().all(|choice| choice.label == current) {
+        app_state.set_error("No other AWS accounts or profiles to switch to".to_string());
+        return;
+    }
+    app_state.account_picker = Some(picker);
+}
+
+/// Authenticates as another AWS identity in the background; the provider is
+/// swapped in by `poll_account_switch` once it is ready.
+fn switch_account(app_state: &mut AppState, contexts: &ContextConfigs, provider_idx: usize, label: String, config: AwsConfig) {
+    if app_state.is_switching_account() {
+        app_state.set_error("Another account switch is still authenticating".to_string());
+        return;
+    }
+
+    info!("Switching provider {} to AWS account '{}'", provider_idx, label);
+    let mut provider = aws_provider(config, &contexts.cache, &contexts.retry);
+    let (sender, receiver) = oneshot::channel();
+    tokio::spawn(async move {
+        let result = provider
+            .authenticate()
+            .await
+            .map(|_| Box::new(provider) as Box<dyn CloudProvider>);
+        let _ = sender.send(result);
+    });
+
+    app_state.start_account_switch(provider_idx, label.as_str(), receiver);
+    app_state.set_success(format!("Signing in as '{}'...", label));
+}
+
+/// Shows the cached inventory in the resource list.
+async fn load_from_cache(app_state: &mut AppState, cache: &Arc<CacheStore>) {
+    info!("Checking cache for existing resources...");
+    match cache.run_blocking(|cache| cache.get_all_cached_resources()).await {
+        Ok(cached_resources) if !cached_resources.is_empty() => {
+            info!("Found {} cached resources", cached_resources.len());
+
+            if let Some(first) = cached_resources.first() {
+                app_state.last_refresh = Some(first.cached_at);
+                let age = chrono::Utc::now().signed_duration_since(first.cached_at);
+                info!("Cache age: {}", format_duration(age));
+            }
+
+            let views: Vec<(String, Box<dyn CloudResource>)> = cached_resources
+                .iter()
+                .filter_map(|cached| match cached.to_view() {
+                    Ok(view) => Some((cached.account.clone(), Box::new(view) as Box<dyn CloudResource>)),
+                    Err(e) => {
+                        warn!("Skipping cached resource: {}", e);
+                        None
+                    }
+                })
+                .collect();
+            app_state.load_cached_resources(views).await;
+            app_state.update_cost_alerts().await;
+        }
+        Ok(_) => {
+            info!("Cache is empty");
+        }
+        Err(e) => {
+            warn!("Failed to query cache: {}", e);
+        }
+    }
+}
+
+/// Prunes expired cache entries and compacts the database now, then again
+/// after every `interval` for as long as the app runs.
+fn spawn_cache_maintenance(cache: Arc<CacheStore>, interval: Option<Duration>) {
+    tokio::spawn(async move {
+        loop {
+            let result = cache
+                .run_blocking(|cache| {
+                    let pruned = cache.prune_expired()?;
+                    cache.vacuum()?;
+                    Ok(pruned)
+                })
+                .await;
+            match result {
+                Ok(pruned) => info!("Cache maintenance pruned {} expired entries", pruned),
+                Err(e) => warn!("Cache maintenance failed: {}", e),
+            }
+
+            match interval {
+                Some(interval) => tokio::time::sleep(interval).await,
+                None => break,
+            }
+        }
+    });
+}
+
 fn format_duration(duration: chrono::Duration) -> String {
     if duration.num_minutes() < 1 {
         "less than a minute".to_string()
@@ -243,131 +747,2059 @@ async fn refresh_and_cache_resources(
     cache_store: &Option<Arc<CacheStore>>,
 ) -> Result<()> {
     app_state.refresh_resources().await?;
+    after_refresh(app_state, cache_store).await;
+    Ok(())
+}
+
+/// Reloads stale cost history, runs health checks, and syncs freshly listed
+/// resources into the cache. Returns what the sync changed, if the cache is
+/// enabled.
+async fn after_refresh(app_state: &mut AppState, cache_store: &Option<Arc<CacheStore>>) -> Option<SyncSummary> {
+    if app_state.cost_history_stale() {
+        load_cost_history(app_state).await;
+    }
+    load_health(app_state).await;
+    app_state.update_cost_alerts().await;
     
-    if let Some(ref cache) = cache_store {
-        let resources = app_state.resources.read().await;
-        let resource_count = resources.len();
-        
-        info!("Writing {} resources to cache", resource_count);
-        
-        match cache.cache_resources(&resources) {
-            Ok(_) => {
-                info!("Successfully cached {} resources", resource_count);
+    let cache = cache_store.as_ref()?;
+    record_cost_snapshot(app_state, cache).await;
+
+    let mut listings = Vec::with_capacity(app_state.providers.len());
+    for (provider_idx, provider) in app_state.providers.iter().enumerate() {
+        let provider = provider.read().await;
+        let failed_types = app_state.refresh_failures.get(provider_idx).cloned().unwrap_or_default();
+        listings.push(
+            AccountListing::new(provider.provider_type(), provider.account_name(), Vec::new())
+                .with_failed_types(failed_types),
+        );
+    }
+
+    for (resource, &provider_idx) in app_state.resources.read().await.iter().zip(&app_state.resource_sources) {
+        if let Some(listing) = listings.get_mut(provider_idx) {
+            listing.resources.push(Box::new(CachedResourceView::snapshot(resource.as_ref())));
+        }
+    }
+    let count: usize = listings.iter().map(|listing| listing.resources.len()).sum();
+    info!("Syncing {} resources to cache", count);
+    let sync = cache
+        .run_blocking(move |cache| {
+            let summary = cache.sync_resources(&listings);
+            // Taken from the cache so what failed to list is not recorded as terminated
+            let inventory = cache.cached_inventory().and_then(|inventory| cache.record_inventory(&inventory));
+            if let Err(e) = inventory {
+                warn!("Failed to record inventory: {}", e);
             }
-            Err(e) => {
-                warn!("Failed to cache resources: {}", e);
+            summary
+        })
+        .await;
+    match sync {
+        Ok(summary) => {
+            info!("Cache synced: {}", summary);
+            Some(summary)
+        }
+        Err(e) => {
+            warn!("Failed to cache resources: {}", e);
+            None
+        }
+    }
+}
+
+/// Most cached resources a single filter search can match
+const SEARCH_RESULT_LIMIT: usize = 10_000;
+
+/// Looks the filter text up in the cache's search index so tag values match
+/// too. The query runs in the background; `poll_search` applies the hits.
+fn search_cache(app_state: &mut AppState, cache_store: &Option<Arc<CacheStore>>) {
+    let cache = match cache_store {
+        Some(cache) if !app_state.filter_text.is_empty() => Arc::clone(cache),
+        _ => return app_state.set_search_hits(None),
+    };
+
+    let text = app_state.filter_text.clone();
+    let query = text.clone();
+    let (sender, receiver) = oneshot::channel();
+    tokio::spawn(async move {
+        let hits = cache
+            .run_blocking(move |cache| cache.search_resources(&query, SEARCH_RESULT_LIMIT))
+            .await;
+        let _ = sender.send(hits);
+    });
+    app_state.start_search(text, receiver);
+}
+
+/// Stores today's estimated spend and reloads the snapshots the dashboard
+/// charts.
+async fn record_cost_snapshot(app_state: &mut AppState, cache: &Arc<CacheStore>) {
+    let snapshot = CostSnapshot::from_resources(&app_state.resources.read().await);
+    let snapshots = cache
+        .run_blocking(move |cache| {
+            if let Err(e) = cache.record_cost_snapshot(&snapshot) {
+                warn!("Failed to record cost snapshot: {}", e);
+            }
+            cache.get_cost_snapshots(nimbus::app::state::COST_TREND_DAYS)
+        })
+        .await;
+    match snapshots {
+        Ok(snapshots) => app_state.cost_snapshots = snapshots,
+        Err(e) => warn!("Failed to load cost snapshots: {}", e),
+    }
+}
+
+/// Runs each provider's health checks over the resources listed from it.
+async fn load_health(app_state: &mut AppState) {
+    let mut by_provider: Vec<Vec<(String, nimbus::core::ResourceType)>> =
+        vec![Vec::new(); app_state.providers.len()];
+    {
+        let resources = app_state.resources.read().await;
+        for (resource, &provider_idx) in resources.iter().zip(app_state.resource_sources.iter()) {
+            if let Some(list) = by_provider.get_mut(provider_idx) {
+                list.push((resource.id().to_string(), resource.resource_type()));
             }
         }
     }
-    
-    Ok(())
+
+    let mut health = std::collections::HashMap::new();
+    for (provider, resources) in app_state.providers.iter().zip(by_provider) {
+        if resources.is_empty() {
+            continue;
+        }
+        let provider = provider.read().await;
+        match provider.get_health(&resources).await {
+            Ok(results) => health.extend(results),
+            Err(e) => warn!("Failed to run health checks for {}: {}", provider.name(), e),
+        }
+    }
+    app_state.resource_health = health;
 }
 
-async fn run_app(
-    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-    app_state: &mut AppState,
-    cache_store: Option<Arc<CacheStore>>,
-) -> Result<()> {
-    let mut last_message_time: Option<std::time::Instant> = None;
-    const MESSAGE_DISPLAY_DURATION: Duration = Duration::from_secs(3);
+/// Fetches daily spend for the chosen period, this month's spend per
+/// cost-allocation tag, commitment coverage, budgets, the month-end forecast,
+/// and cost anomalies from every provider.
+async fn load_cost_history(app_state: &mut AppState) {
+    let period = app_state.cost_period;
+    let mut points = Vec::new();
+    for provider in cost_providers(app_state, CostFeature::History) {
+        let provider = provider.read().await;
+        match provider.get_cost_history(period, CostGranularity::Daily).await {
+            Ok(history) => points.extend(history),
+            Err(e) => warn!("Failed to fetch cost history for {}: {}", provider.name(), e),
+        }
+    }
+    app_state.set_cost_history(merge_cost_points(points));
 
-    loop {
-        if let Some(msg_time) = last_message_time {
-            if msg_time.elapsed() > MESSAGE_DISPLAY_DURATION {
-                app_state.clear_success();
-                last_message_time = None;
+    for tag_key in app_state.cost_tag_keys.clone() {
+        let mut costs = Vec::new();
+        for provider in cost_providers(app_state, CostFeature::ByTag) {
+            let provider = provider.read().await;
+            match provider.get_cost_by_tag(&tag_key).await {
+                Ok(by_value) => costs.push(by_value),
+                Err(e) => warn!("Failed to fetch cost by tag '{}' for {}: {}", tag_key, provider.name(), e),
             }
         }
+        app_state.set_cost_by_tag(&tag_key, costs);
+    }
 
-        terminal.draw(|f| {
-            let future = ui::render(f, app_state);
-            tokio::task::block_in_place(|| {
-                tokio::runtime::Handle::current().block_on(future)
+    let mut commitments = Vec::new();
+    for provider in cost_providers(app_state, CostFeature::Commitments) {
+        let provider = provider.read().await;
+        match provider.get_commitment_coverage().await {
+            Ok(coverage) if !coverage.is_empty() => commitments.push((provider.name().to_string(), coverage)),
+            Ok(_) => {}
+            Err(e) => warn!("Failed to fetch commitment coverage for {}: {}", provider.name(), e),
+        }
+    }
+    app_state.commitments = commitments;
+
+    let mut budgets = Vec::new();
+    for provider in cost_providers(app_state, CostFeature::Budgets) {
+        let provider = provider.read().await;
+        match provider.get_budgets().await {
+            Ok(provider_budgets) => budgets.extend(provider_budgets),
+            Err(e) => warn!("Failed to fetch budgets for {}: {}", provider.name(), e),
+        }
+    }
+    app_state.budgets = budgets;
+
+    let mut forecasts = Vec::new();
+    for provider in cost_providers(app_state, CostFeature::Forecast) {
+        let provider = provider.read().await;
+        match provider.get_cost_breakdown().await {
+            Ok(breakdown) => forecasts.extend(breakdown.forecast),
+            Err(e) => warn!("Failed to fetch cost forecast for {}: {}", provider.name(), e),
+        }
+    }
+    app_state.cost_forecast = CostForecast::sum(forecasts);
+
+    let mut anomalies = Vec::new();
+    for provider in cost_providers(app_state, CostFeature::Anomalies) {
+        let provider = provider.read().await;
+        match provider.get_cost_anomalies().await {
+            Ok(provider_anomalies) => anomalies.extend(provider_anomalies),
+            Err(e) => warn!("Failed to fetch cost anomalies for {}: {}", provider.name(), e),
+        }
+    }
+    anomalies.sort_by(|a, b| b.impact.partial_cmp(&a.impact).unwrap_or(std::cmp::Ordering::Equal));
+    app_state.anomalies = anomalies;
+}
+
+/// Returns the providers that report the given cost data.
+fn cost_providers(app_state: &AppState, feature: CostFeature) -> Vec<Arc<RwLock<Box<dyn CloudProvider>>>> {
+    app_state
+        .providers
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| app_state.provider_capabilities(*idx).supports_cost(feature))
+        .map(|(_, provider)| provider.clone())
+        .collect()
+}
+
+/// How far back stored metric samples are shown when the provider cannot be
+/// reached.
+const CACHED_METRICS_HOURS: i64 = 24;
+
+/// Reads the metric samples stored for a resource in the last
+/// `CACHED_METRICS_HOURS`.
+async fn cached_metrics(cache_store: &Option<Arc<CacheStore>>, resource_id: &str) -> Vec<MetricSeries> {
+    let cache = match cache_store {
+        Some(cache) => cache,
+        None => return Vec::new(),
+    };
+
+    let id = resource_id.to_string();
+    let since = chrono::Utc::now() - chrono::Duration::hours(CACHED_METRICS_HOURS);
+    match cache.run_blocking(move |cache| cache.get_metrics(&id, since)).await {
+        Ok(series) => series,
+        Err(e) => {
+            warn!("Failed to read cached metrics for {}: {}", resource_id, e);
+            Vec::new()
+        }
+    }
+}
+
+/// Fetches metrics for the selected resource unless they are already loaded,
+/// storing the samples in the cache and falling back to it on failure.
+async fn load_metrics(app_state: &mut AppState, cache_store: &Option<Arc<CacheStore>>, force: bool) {
+    let target = {
+        let resources = app_state.resources.read().await;
+        app_state.get_selected_resource_index().and_then(|resource_idx| {
+            resources
+                .get(resource_idx)
+                .map(|resource| (resource_idx, resource.id().to_string(), resource.resource_type()))
+        })
+    };
+
+    let (resource_idx, resource_id, resource_type) = match target {
+        Some(target) => target,
+        None => return,
+    };
+
+    if !force && !app_state.metrics_stale_for(&resource_id) {
+        return;
+    }
+
+    if app_state.offline {
+        let cached = cached_metrics(cache_store, &resource_id).await;
+        app_state.set_cached_metrics(resource_id, cached);
+        return;
+    }
+
+    let provider = match app_state.provider_for_resource(resource_idx) {
+        Some(provider) => provider,
+        None => {
+            app_state.set_error("No provider found for this resource".to_string());
+            return;
+        }
+    };
+
+    info!("Fetching metrics for {}", resource_id);
+    let result = provider.read().await.get_metrics(&resource_id, resource_type).await;
+
+    match result {
+        Ok(metrics) => {
+            if let Some(cache) = cache_store {
+                let (id, series) = (resource_id.clone(), metrics.clone());
+                if let Err(e) = cache.run_blocking(move |cache| cache.record_metrics(&id, &series)).await {
+                    warn!("Failed to cache metrics for {}: {}", resource_id, e);
+                }
+            }
+            app_state.set_metrics(resource_id, metrics);
+        }
+        Err(e) => {
+            error!("Failed to fetch metrics: {}", e);
+            let cached = cached_metrics(cache_store, &resource_id).await;
+            if cached.is_empty() {
+                app_state.set_metrics(resource_id, Vec::new());
+                app_state.set_error(format!("Failed to fetch metrics: {}", e));
+            } else {
+                app_state.set_cached_metrics(resource_id, cached);
+                app_state.set_error(format!("Failed to fetch metrics, showing cached samples: {}", e));
+            }
+        }
+    }
+}
+
+/// Fetches target health when the selected resource is a load balancer.
+async fn load_target_health(app_state: &mut AppState, force: bool) {
+    let (resource_idx, resource_id, _, resource_type) = match selected_resource(app_state).await {
+        Some(selected) => selected,
+        None => return,
+    };
+
+    if resource_type != nimbus::core::ResourceType::LoadBalancer {
+        return;
+    }
+    if !force && !app_state.target_health_stale_for(&resource_id) {
+        return;
+    }
+
+    let provider = match app_state.provider_for_resource(resource_idx) {
+        Some(provider) => provider,
+        None => return,
+    };
+
+    info!("Fetching target health for {}", resource_id);
+    let result = provider.read().await.get_target_health(&resource_id, resource_type).await;
+
+    match result {
+        Ok(health) => app_state.set_target_health(resource_id, health),
+        Err(e) => {
+            error!("Failed to fetch target health: {}", e);
+            app_state.set_target_health(resource_id, Vec::new());
+            app_state.set_error(format!("Failed to fetch target health: {}", e));
+        }
+    }
+}
+
+/// Returns the index, ID, name, and type of the selected resource.
+async fn selected_resource(app_state: &AppState) -> Option<(usize, String, String, nimbus::core::ResourceType)> {
+    let resources = app_state.resources.read().await;
+    app_state.get_selected_resource_index().and_then(|resource_idx| {
+        resources.get(resource_idx).map(|resource| {
+            (
+                resource_idx,
+                resource.id().to_string(),
+                resource.name().to_string(),
+                resource.resource_type(),
+            )
+        })
+    })
+}
+
+/// Names the account a provider runs in, for the action log.
+async fn account_name(provider: &RwLock<Box<dyn CloudProvider>>) -> String {
+    provider.read().await.account_name().to_string()
+}
+
+/// Records an action taken on a resource, noting the account it ran in.
+async fn record_resource_action(
+    app_state: &mut AppState,
+    resource_idx: usize,
+    resource_id: &str,
+    resource_name: &str,
+    record: ActionRecord,
+) {
+    let mut record = record.with_resource(resource_id, resource_name);
+    if let Some(provider) = app_state.provider_for_resource(resource_idx) {
+        record = record.with_account(account_name(&provider).await);
+    }
+    app_state.record_action(record);
+}
+
+/// Returns the marked resources that are still listed, in list order.
+async fn marked_resources(app_state: &AppState) -> Vec<(usize, String, String, nimbus::core::ResourceType)> {
+    let resources = app_state.resources.read().await;
+    resources
+        .iter()
+        .enumerate()
+        .filter(|(_, resource)| app_state.is_marked(resource.id()))
+        .map(|(resource_idx, resource)| {
+            (
+                resource_idx,
+                resource.id().to_string(),
+                resource.name().to_string(),
+                resource.resource_type(),
+            )
+        })
+        .collect()
+}
+
+/// Opens the bulk action picker with the actions every marked resource supports.
+async fn open_bulk_picker(app_state: &mut AppState) {
+    let actions = {
+        let resources = app_state.resources.read().await;
+        let mut marked = resources
+            .iter()
+            .enumerate()
+            .filter(|(_, resource)| app_state.is_marked(resource.id()));
+        let mut actions: Vec<nimbus::core::Action> = match marked.next() {
+            Some((idx, first)) => app_state
+                .actions_for(idx, first.as_ref())
+                .into_iter()
+                .filter(|a| a.supports_bulk() || *a == nimbus::core::Action::Tag)
+                .collect(),
+            None => return,
+        };
+        for (idx, resource) in marked {
+            let supported = app_state.actions_for(idx, resource.as_ref());
+            actions.retain(|action| supported.contains(action));
+        }
+        // A required snapshot is chosen per resource, so bulk termination is
+        // only offered when snapshots are optional.
+        if app_state.require_snapshot {
+            actions.retain(|action| *action != nimbus::core::Action::Terminate);
+        }
+        actions
+    };
+
+    if actions.is_empty() {
+        app_state.set_error("The marked resources have no action in common".to_string());
+    } else {
+        app_state.clear_messages();
+        app_state.open_bulk_picker(actions);
+    }
+}
+
+/// Asks the providers what would make deleting the targets fail.
+///
+/// Lookup failures are logged and skipped, leaving the provider's own error
+/// to explain a failed deletion. Blockers are prefixed with the resource
+/// name when there is more than one target.
+async fn deletion_blockers(
+    app_state: &AppState,
+    targets: &[(usize, String, String, nimbus::core::ResourceType)],
+) -> Vec<String> {
+    let mut blockers = Vec::new();
+    for (resource_idx, resource_id, resource_name, resource_type) in targets {
+        let provider = match app_state.provider_for_resource(*resource_idx) {
+            Some(provider) => provider,
+            None => continue,
+        };
+
+        let found = provider.read().await.deletion_blockers(resource_id, *resource_type).await;
+        match found {
+            Ok(found) if targets.len() > 1 => {
+                blockers.extend(found.into_iter().map(|b| format!("{}: {}", resource_name, b)));
+            }
+            Ok(found) => blockers.extend(found),
+            Err(e) => warn!("Could not check deletion dependencies of {}: {}", resource_id, e),
+        }
+    }
+    blockers
+}
+
+/// Builds the confirmation for a bulk action, listing every affected resource.
+fn bulk_action_prompt(action: nimbus::core::Action, names: &[String]) -> String {
+    const LISTED: usize = 10;
+
+    let mut lines: Vec<String> = names.iter().take(LISTED).map(|name| format!("• {}", name)).collect();
+    if names.len() > LISTED {
+        lines.push(format!("…and {} more", names.len() - LISTED));
+    }
+    let warning = if action.is_destructive() {
+        "\n\nThis action cannot be undone."
+    } else {
+        ""
+    };
+    let instructions = if action == nimbus::core::Action::Terminate && names.len() == 1 {
+        "Type the name, or the last 4 characters of the ID, then press Enter to confirm or ESC to cancel.".to_string()
+    } else if action == nimbus::core::Action::Terminate {
+        format!(
+            "Type '{}' then press Enter to confirm or ESC to cancel.",
+            AppState::termination_phrase(names.len())
+        )
+    } else {
+        "Press Enter to confirm or ESC to cancel.".to_string()
+    };
+
+    format!(
+        "{} these {} resources?\n\n{}{}\n\n{}",
+        action.as_str(),
+        names.len(),
+        lines.join("\n"),
+        warning,
+        instructions
+    )
+}
+
+/// Runs a confirmed bulk action on every marked resource, one batch per
+/// provider, and clears the marks.
+///
+/// Returns true if a success message was shown.
+async fn run_bulk_action(
+    app_state: &mut AppState,
+    cache_store: &Option<Arc<CacheStore>>,
+    action: nimbus::core::Action,
+) -> bool {
+    let targets = marked_resources(app_state).await;
+    app_state.clear_marks();
+    if targets.is_empty() {
+        return false;
+    }
+
+    let mut by_provider: Vec<(usize, Vec<(usize, String, String, nimbus::core::ResourceType)>)> = Vec::new();
+    for target in targets {
+        let provider_idx = match app_state.resource_sources.get(target.0) {
+            Some(&provider_idx) => provider_idx,
+            None => continue,
+        };
+        match by_provider.iter_mut().find(|(idx, _)| *idx == provider_idx) {
+            Some((_, group)) => group.push(target),
+            None => by_provider.push((provider_idx, vec![target])),
+        }
+    }
+
+    info!("Executing bulk action {:?} on {} provider(s)", action, by_provider.len());
+    app_state.start_loading();
+    let request = ActionRequest::new(action);
+    let mut succeeded = 0;
+    let mut failures = Vec::new();
+
+    for (provider_idx, group) in by_provider {
+        let provider = match app_state.providers.get(provider_idx) {
+            Some(provider) => Arc::clone(provider),
+            None => continue,
+        };
+        let ids: Vec<(String, nimbus::core::ResourceType)> = group
+            .iter()
+            .map(|(_, resource_id, _, resource_type)| (resource_id.clone(), *resource_type))
+            .collect();
+        let results = provider.read().await.execute_action_bulk(&ids, &request).await;
+        let account = account_name(&provider).await;
+
+        for ((resource_idx, resource_id, resource_name, _), result) in group.into_iter().zip(results) {
+            let record = match result {
+                Ok(handle) => {
+                    track_operation(app_state, resource_idx, &resource_name, handle).await;
+                    succeeded += 1;
+                    ActionRecord::succeeded(action.as_str(), format!("{} requested for '{}'", action.as_str(), resource_name))
+                }
+                Err(e) => {
+                    error!("Bulk {} failed on {}: {}", action, resource_id, e);
+                    failures.push(format!("{}: {}", resource_name, e));
+                    ActionRecord::failed(action.as_str(), e.to_string())
+                }
+            };
+            app_state.record_action(record.with_account(account.as_str()).with_resource(resource_id, resource_name));
+        }
+    }
+
+    let summary = format!("{} requested for {} resource(s)", action.as_str(), succeeded);
+
+    let shown = if failures.is_empty() {
+        app_state.set_success(summary);
+        true
+    } else {
+        app_state.set_error(format!(
+            "{} failed for {} of {} resource(s): {}",
+            action.as_str(),
+            failures.len(),
+            succeeded + failures.len(),
+            failures.join("; ")
+        ));
+        false
+    };
+
+    if let Err(e) = refresh_and_cache_resources(app_state, cache_store).await {
+        error!("Failed to refresh after bulk action: {}", e);
+    }
+    shown
+}
+
+/// Fetches the modifications available for the selected resource and opens the picker.
+async fn open_modify_picker(app_state: &mut AppState) {
+    let (resource_idx, resource_id, _, resource_type) = match selected_resource(app_state).await {
+        Some(target) => target,
+        None => return,
+    };
+
+    let provider = match app_state.provider_for_resource(resource_idx) {
+        Some(provider) => provider,
+        None => {
+            app_state.set_error("No provider found for this resource".to_string());
+            return;
+        }
+    };
+
+    app_state.start_loading();
+    let result = provider.read().await.modify_options(&resource_id, resource_type).await;
+    app_state.stop_loading();
+
+    match result {
+        Ok(options) if options.is_empty() => {
+            app_state.set_error("No modifications available for this resource".to_string());
+        }
+        Ok(options) => app_state.open_modify_picker(options),
+        Err(e) => {
+            error!("Failed to load modify options: {}", e);
+            app_state.set_error(format!("Failed to load options: {}", e));
+        }
+    }
+}
+
+/// Builds the confirmation prompt for a modification, including the cost change.
+fn modification_prompt(resource_name: &str, option: &ModifyOption, current_cost: Option<f64>) -> String {
+    let cost_line = match (current_cost, option.monthly_cost, option.cost_delta(current_cost)) {
+        (Some(current), Some(new), Some(delta)) => format!(
+            "Estimated cost: ${:.2}/mo -> ${:.2}/mo ({}${:.2}/mo)",
+            current,
+            new,
+            if delta < 0.0 { "-" } else { "+" },
+            delta.abs()
+        ),
+        (_, Some(new), _) => format!("Estimated cost: ${:.2}/mo", new),
+        _ => "Estimated cost: unknown".to_string(),
+    };
+
+    format!(
+        "Change '{}' to {}?\n\n{}\n\nThe resource may be restarted to apply the change.\n\nPress Enter to confirm or ESC to cancel.",
+        resource_name, option.value, cost_line
+    )
+}
+
+/// Applies a confirmed modification to the selected resource.
+///
+/// Returns true if a success message was shown.
+async fn apply_modification(
+    app_state: &mut AppState,
+    cache_store: &Option<Arc<CacheStore>>,
+    option: ModifyOption,
+) -> bool {
+    let (resource_idx, resource_id, resource_name, resource_type) = match selected_resource(app_state).await {
+        Some(target) => target,
+        None => return false,
+    };
+
+    let provider = match app_state.provider_for_resource(resource_idx) {
+        Some(provider) => provider,
+        None => {
+            app_state.set_error("No provider found for this resource".to_string());
+            return false;
+        }
+    };
+
+    info!("Modifying {} to {}", resource_id, option.value);
+    app_state.start_loading();
+    let request = ActionRequest::new(nimbus::core::Action::Modify).with_instance_type(option.value.as_str());
+    let result = provider
+        .read()
+        .await
+        .execute_action(&resource_id, resource_type, &request)
+        .await;
+
+    match result {
+        Ok(handle) => {
+            track_operation(app_state, resource_idx, &resource_name, handle).await;
+            let success_msg = format!("Successfully changed '{}' to {}", resource_name, option.value);
+            let record = ActionRecord::succeeded("Modify", success_msg.as_str());
+            record_resource_action(app_state, resource_idx, &resource_id, &resource_name, record).await;
+            app_state.set_success(success_msg);
+
+            if let Err(e) = refresh_and_cache_resources(app_state, cache_store).await {
+                error!("Failed to refresh after modification: {}", e);
+            }
+            true
+        }
+        Err(e) => {
+            error!("Modification failed: {}", e);
+            let record = ActionRecord::failed("Modify", e.to_string());
+            record_resource_action(app_state, resource_idx, &resource_id, &resource_name, record).await;
+            app_state.set_error(format!("{}", e));
+            false
+        }
+    }
+}
+
+/// Runs an action whose parameters were entered in a prompt on the selected
+/// resource.
+///
+/// Returns true if a success message was shown.
+async fn run_action_request(
+    app_state: &mut AppState,
+    cache_store: &Option<Arc<CacheStore>>,
+    request: ActionRequest,
+) -> bool {
+    let (resource_idx, resource_id, resource_name, resource_type) = match selected_resource(app_state).await {
+        Some(target) => target,
+        None => return false,
+    };
+
+    let provider = match app_state.provider_for_resource(resource_idx) {
+        Some(provider) => provider,
+        None => {
+            app_state.set_error("No provider found for this resource".to_string());
+            return false;
+        }
+    };
+
+    info!("Executing action {:?} on resource {} with {:?}", request.action, resource_id, request.params);
+    app_state.start_loading();
+    let result = provider
+        .read()
+        .await
+        .execute_action(&resource_id, resource_type, &request)
+        .await;
+
+    match result {
+        Ok(handle) => {
+            let tracked = track_operation(app_state, resource_idx, &resource_name, handle).await;
+            let success_msg = match request.params.snapshot_name {
+                Some(ref name) => format!("Started snapshot '{}' of '{}'", name, resource_name),
+                None if tracked => format!("{} requested for '{}'", request.action.as_str(), resource_name),
+                None => format!("Successfully completed {} on '{}'", request.action.as_str().to_lowercase(), resource_name),
+            };
+            let record = ActionRecord::succeeded(request.action.as_str(), success_msg.as_str());
+            record_resource_action(app_state, resource_idx, &resource_id, &resource_name, record).await;
+            app_state.set_success(success_msg);
+
+            if let Err(e) = refresh_and_cache_resources(app_state, cache_store).await {
+                error!("Failed to refresh after action: {}", e);
+            }
+            true
+        }
+        Err(e) => {
+            error!("Action failed: {}", e);
+            let record = ActionRecord::failed(request.action.as_str(), e.to_string());
+            record_resource_action(app_state, resource_idx, &resource_id, &resource_name, record).await;
+            app_state.set_error(format!("{}", e));
+            false
+        }
+    }
+}
+
+/// Opens the creation wizard for the first provider on the active tab that
+/// can launch compute instances.
+async fn open_create_wizard(app_state: &mut AppState) {
+    let provider_type = match app_state.active_tab {
+        TabIndex::AWS => Some(Provider::AWS),
+        TabIndex::GCP => Some(Provider::GCP),
+        TabIndex::Azure => Some(Provider::Azure),
+        TabIndex::AllClouds => None,
+    };
+    let providers: Vec<_> = app_state.providers.iter().cloned().enumerate().collect();
+
+    app_state.start_loading();
+    let mut last_error = None;
+    for (provider_idx, provider) in providers {
+        let provider = provider.read().await;
+        if provider_type.is_some_and(|t| provider.provider_type() != t) {
+            continue;
+        }
+
+        match provider.create_options(nimbus::core::ResourceType::Compute).await {
+            Ok(options) if !options.is_empty() => {
+                app_state.clear_messages();
+                app_state.stop_loading();
+                app_state.create_wizard = Some(CreateWizard::new(provider_idx, options));
+                return;
+            }
+            Ok(_) => last_error = Some(format!("{} has no images or instance types to offer", provider.name())),
+            Err(nimbus::NimbusError::UnsupportedAction(..)) => {}
+            Err(e) => {
+                error!("Failed to load creation options from {}: {}", provider.name(), e);
+                last_error = Some(format!("Failed to load creation options: {}", e));
+            }
+        }
+    }
+
+    app_state.set_error(last_error.unwrap_or_else(|| "No provider on this tab can create resources".to_string()));
+}
+
+/// Opens the prompt for the creation wizard's name and tags steps.
+fn prompt_create_step(app_state: &mut AppState) {
+    let (title, input) = match app_state.create_wizard {
+        Some(ref wizard) if wizard.step == CreateStep::Name => ("Instance name", wizard.name.clone()),
+        Some(ref wizard) if wizard.step == CreateStep::Tags => {
+            ("Tags (key=value, comma separated)", wizard.tags_input())
+        }
+        _ => return,
+    };
+    app_state.open_prompt(nimbus::core::Action::Create, title.to_string(), input);
+}
+
+/// Launches the resource described by the creation wizard and follows it
+/// until it is running.
+///
+/// Returns true if a success message was shown.
+async fn launch_from_wizard(app_state: &mut AppState, cache_store: &Option<Arc<CacheStore>>) -> bool {
+    let wizard = match app_state.create_wizard.take() {
+        Some(wizard) => wizard,
+        None => return false,
+    };
+    let provider = match app_state.providers.get(wizard.provider_idx) {
+        Some(provider) => Arc::clone(provider),
+        None => return false,
+    };
+
+    let spec = wizard.spec();
+    let display_name = if spec.name.is_empty() {
+        spec.instance_type.clone()
+    } else {
+        spec.name.clone()
+    };
+
+    info!("Creating {} from {}", spec.instance_type, spec.image);
+    app_state.start_loading();
+    let result = provider.read().await.create_resource(&spec).await;
+
+    let account = account_name(&provider).await;
+    match result {
+        Ok(handle) => {
+            let success_msg = format!("Create requested for '{}' ({})", display_name, handle.resource_id);
+            app_state.record_action(
+                ActionRecord::succeeded("Create", success_msg.as_str())
+                    .with_account(account)
+                    .with_resource(handle.resource_id.as_str(), display_name.as_str()),
+            );
+            app_state.set_success(success_msg);
+            app_state.track_operation(OperationJob {
+                handle,
+                resource_name: display_name,
+                provider_idx: wizard.provider_idx,
+                status: OperationStatus::InProgress(ResourceState::Pending),
+            });
+
+            if let Err(e) = refresh_and_cache_resources(app_state, cache_store).await {
+                error!("Failed to refresh after create: {}", e);
+            }
+            true
+        }
+        Err(e) => {
+            error!("Create failed: {}", e);
+            app_state.record_action(ActionRecord::failed("Create", e.to_string()).with_account(account));
+            app_state.set_error(format!("Failed to create '{}': {}", display_name, e));
+            false
+        }
+    }
+}
+
+/// Applies the tag editor's changes to the selected resource.
+///
+/// Returns true if a success message was shown.
+async fn save_tags(app_state: &mut AppState, cache_store: &Option<Arc<CacheStore>>) -> bool {
+    let changes = match app_state.tag_editor.take() {
+        Some(editor) => editor.changes(),
+        None => return false,
+    };
+
+    if changes.is_empty() {
+        return false;
+    }
+
+    let (resource_idx, resource_id, resource_name, resource_type) = match selected_resource(app_state).await {
+        Some(target) => target,
+        None => return false,
+    };
+
+    let provider = match app_state.provider_for_resource(resource_idx) {
+        Some(provider) => provider,
+        None => {
+            app_state.set_error("No provider found for this resource".to_string());
+            return false;
+        }
+    };
+
+    info!("Updating tags on {}", resource_id);
+    app_state.start_loading();
+    let result = provider
+        .read()
+        .await
+        .update_tags(&resource_id, resource_type, &changes)
+        .await;
+
+    match result {
+        Ok(_) => {
+            let success_msg = format!("Successfully updated tags on '{}'", resource_name);
+            let record = ActionRecord::succeeded("Update tags", success_msg.as_str());
+            record_resource_action(app_state, resource_idx, &resource_id, &resource_name, record).await;
+            app_state.set_success(success_msg);
+
+            if let Err(e) = refresh_and_cache_resources(app_state, cache_store).await {
+                error!("Failed to refresh after tagging: {}", e);
+            }
+            true
+        }
+        Err(e) => {
+            error!("Tag update failed: {}", e);
+            let record = ActionRecord::failed("Update tags", e.to_string());
+            record_resource_action(app_state, resource_idx, &resource_id, &resource_name, record).await;
+            app_state.set_error(format!("{}", e));
+            false
+        }
+    }
+}
+
+/// Applies the same tag changes to every marked resource and clears the
+/// marks.
+///
+/// Returns true if a success message was shown.
+async fn bulk_update_tags(
+    app_state: &mut AppState,
+    cache_store: &Option<Arc<CacheStore>>,
+    changes: nimbus::core::tags::TagChanges,
+) -> bool {
+    let targets = marked_resources(app_state).await;
+    app_state.clear_marks();
+    if targets.is_empty() {
+        return false;
+    }
+
+    info!("Updating tags on {} resource(s)", targets.len());
+    app_state.start_loading();
+    let mut succeeded = 0;
+    let mut failures = Vec::new();
+
+    for (resource_idx, resource_id, resource_name, resource_type) in targets {
+        let provider = match app_state.provider_for_resource(resource_idx) {
+            Some(provider) => provider,
+            None => continue,
+        };
+        let result = provider
+            .read()
+            .await
+            .update_tags(&resource_id, resource_type, &changes)
+            .await;
+        let record = match result {
+            Ok(_) => {
+                succeeded += 1;
+                ActionRecord::succeeded("Update tags", format!("Successfully updated tags on '{}'", resource_name))
+            }
+            Err(e) => {
+                error!("Tag update failed on {}: {}", resource_id, e);
+                failures.push(format!("{}: {}", resource_name, e));
+                ActionRecord::failed("Update tags", e.to_string())
+            }
+        };
+        record_resource_action(app_state, resource_idx, &resource_id, &resource_name, record).await;
+    }
+
+    let shown = if failures.is_empty() {
+        app_state.set_success(format!("Updated tags on {} resource(s)", succeeded));
+        true
+    } else {
+        app_state.set_error(format!(
+            "Tag update failed for {} of {} resource(s): {}",
+            failures.len(),
+            succeeded + failures.len(),
+            failures.join("; ")
+        ));
+        false
+    };
+
+    if let Err(e) = refresh_and_cache_resources(app_state, cache_store).await {
+        error!("Failed to refresh after tagging: {}", e);
+    }
+    shown
+}
+
+/// Starts creating an image of the selected resource and tracks its progress.
+///
+/// Returns true if a success message was shown.
+async fn start_image_creation(app_state: &mut AppState, name: String) -> bool {
+    let (resource_idx, resource_id, resource_name, _) = match selected_resource(app_state).await {
+        Some(target) => target,
+        None => return false,
+    };
+
+    let (provider_idx, provider) = match (
+        app_state.resource_sources.get(resource_idx).copied(),
+        app_state.provider_for_resource(resource_idx),
+    ) {
+        (Some(provider_idx), Some(provider)) => (provider_idx, provider),
+        _ => {
+            app_state.set_error("No provider found for this resource".to_string());
+            return false;
+        }
+    };
+
+    info!("Creating image '{}' from {}", name, resource_id);
+    app_state.start_loading();
+    let result = provider.read().await.create_image(&resource_id, &name).await;
+
+    match result {
+        Ok(image_id) => {
+            let success_msg = format!("Creating image {} from '{}'", image_id, resource_name);
+            app_state.track_image(ImageJob {
+                image_id,
+                name,
+                provider_idx,
+                state: "pending".to_string(),
+            });
+            let record = ActionRecord::succeeded("Create image", success_msg.as_str());
+            record_resource_action(app_state, resource_idx, &resource_id, &resource_name, record).await;
+            app_state.set_success(success_msg);
+            true
+        }
+        Err(e) => {
+            error!("Image creation failed: {}", e);
+            let record = ActionRecord::failed("Create image", e.to_string());
+            record_resource_action(app_state, resource_idx, &resource_id, &resource_name, record).await;
+            app_state.set_error(format!("{}", e));
+            false
+        }
+    }
+}
+
+/// Follows an action the provider has accepted until its resource reaches
+/// the target state.
+///
+/// Returns false if the action had already taken effect.
+async fn track_operation(
+    app_state: &mut AppState,
+    resource_idx: usize,
+    resource_name: &str,
+    handle: OperationHandle,
+) -> bool {
+    if handle.is_immediate() {
+        return false;
+    }
+
+    let provider_idx = match app_state.resource_sources.get(resource_idx) {
+        Some(&provider_idx) => provider_idx,
+        None => return false,
+    };
+
+    let state = app_state
+        .resources
+        .read()
+        .await
+        .get(resource_idx)
+        .map(|resource| resource.state())
+        .unwrap_or(ResourceState::Unknown);
+
+    app_state.track_operation(OperationJob {
+        handle,
+        resource_name: resource_name.to_string(),
+        provider_idx,
+        status: OperationStatus::InProgress(state),
+    });
+    true
+}
+
+/// How long to wait for a resource to reach an action's target state before
+/// reporting the action as failed.
+const OPERATION_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Checks the progress of actions still taking effect and reports any that
+/// finished.
+///
+/// Returns true if resources should be refreshed.
+async fn poll_operations(app_state: &mut AppState) -> bool {
+    let mut finished_any = false;
+
+    for job in app_state.operations.clone() {
+        let provider = match app_state.providers.get(job.provider_idx) {
+            Some(provider) => Arc::clone(provider),
+            None => continue,
+        };
+
+        let status = match provider.read().await.poll_operation(&job.handle).await {
+            Ok(status) => status,
+            Err(e) => {
+                warn!("Failed to check {} on {}: {}", job.handle.action, job.handle.resource_id, e);
+                continue;
+            }
+        };
+
+        let status = match status {
+            OperationStatus::InProgress(_)
+                if job.handle.elapsed_secs(chrono::Utc::now()) > OPERATION_TIMEOUT.as_secs() as i64 =>
+            {
+                OperationStatus::Failed("timed out waiting for the resource".to_string())
+            }
+            status => status,
+        };
+
+        if let Some(finished) = app_state.update_operation(&job.handle.resource_id, status) {
+            let elapsed = finished.handle.elapsed_secs(chrono::Utc::now());
+            let action = finished.handle.action.as_str();
+            let record = match finished.status {
+                OperationStatus::Failed(reason) => {
+                    error!("{} of {} failed: {}", finished.handle.action, finished.handle.resource_id, reason);
+                    let msg = format!("{} of '{}' failed: {}", action, finished.resource_name, reason);
+                    app_state.set_error(msg.clone());
+                    ActionRecord::failed(action, msg)
+                }
+                _ => {
+                    let msg = format!("{} of '{}' finished after {}s", action, finished.resource_name, elapsed);
+                    app_state.set_success(msg.clone());
+                    ActionRecord::succeeded(action, msg)
+                }
+            };
+            app_state.record_action(
+                record
+                    .with_account(account_name(&provider).await)
+                    .with_resource(finished.handle.resource_id.as_str(), finished.resource_name.as_str()),
+            );
+            finished_any = true;
+        }
+    }
+
+    finished_any
+}
+
+/// Checks the state of images being created and reports any that finished.
+///
+/// Returns true if a success message was shown.
+async fn poll_image_jobs(app_state: &mut AppState) -> bool {
+    let mut shown = false;
+
+    for job in app_state.image_jobs.clone() {
+        let provider = match app_state.providers.get(job.provider_idx) {
+            Some(provider) => Arc::clone(provider),
+            None => continue,
+        };
+
+        let state = match provider.read().await.image_state(&job.image_id).await {
+            Ok(state) => state,
+            Err(e) => {
+                warn!("Failed to check image {}: {}", job.image_id, e);
+                continue;
+            }
+        };
+
+        if let Some(finished) = app_state.update_image_state(&job.image_id, state) {
+            let record = if finished.state == "available" {
+                let msg = format!("Image {} ({}) is available", finished.image_id, finished.name);
+                app_state.set_success(msg.clone());
+                shown = true;
+                ActionRecord::succeeded("Create image", msg)
+            } else {
+                let msg = format!(
+                    "Image {} ({}) finished with state '{}'",
+                    finished.image_id, finished.name, finished.state
+                );
+                app_state.set_error(msg.clone());
+                ActionRecord::failed("Create image", msg)
+            };
+            app_state.record_action(
+                record
+                    .with_account(account_name(&provider).await)
+                    .with_resource(finished.image_id.as_str(), finished.name.as_str()),
+            );
+        }
+    }
+
+    shown
+}
+
+/// Suspends the TUI, runs an interactive command in the terminal, and restores
+/// the TUI once the command exits.
+fn run_interactive(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    mut command: std::process::Command,
+) -> Result<std::process::ExitStatus> {
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
+    terminal.show_cursor()?;
+
+    let status = command.status();
+
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen, EnableMouseCapture)?;
+    terminal.clear()?;
+
+    Ok(status?)
+}
+
+/// Hands a URL to the platform's default browser without waiting for it.
+fn open_in_browser(url: &str) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+
+    command
+        .arg(url)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
+
+/// Opens the selected resource's page in the cloud console. Returns whether a
+/// success message was shown.
+async fn open_console(app_state: &mut AppState) -> bool {
+    let (resource_idx, resource_id, resource_name, resource_type) = match selected_resource(app_state).await {
+        Some(selected) => selected,
+        None => return false,
+    };
+    app_state.clear_messages();
+
+    let url = match app_state.provider_for_resource(resource_idx) {
+        Some(provider) => provider.read().await.console_url(&resource_id, resource_type),
+        None => None,
+    };
+    let url = match url {
+        Some(url) => url,
+        None => {
+            app_state.set_error(format!("No console page for '{}'", resource_name));
+            return false;
+        }
+    };
+
+    info!("Opening {} in the browser", url);
+    match open_in_browser(&url) {
+        Ok(()) => {
+            app_state.set_success(format!("Opened '{}' in the browser", resource_name));
+            true
+        }
+        Err(e) => {
+            error!("Failed to open browser: {}", e);
+            app_state.set_error(format!("Failed to open browser: {} ({})", e, url));
+            false
+        }
+    }
+}
+
+/// Fetches the records of the selected DNS zone.
+async fn load_dns_records(app_state: &mut AppState) {
+    let target = {
+        let resources = app_state.resources.read().await;
+        app_state.get_selected_resource_index().and_then(|resource_idx| {
+            resources
+                .get(resource_idx)
+                .map(|resource| (resource_idx, resource.id().to_string()))
+        })
+    };
+
+    let (resource_idx, zone_id) = match target {
+        Some(target) => target,
+        None => return,
+    };
+
+    let provider = match app_state.provider_for_resource(resource_idx) {
+        Some(provider) => provider,
+        None => {
+            app_state.set_error("No provider found for this resource".to_string());
+            return;
+        }
+    };
+
+    let result = provider.read().await.list_dns_records(&zone_id).await;
+
+    match result {
+        Ok(records) => app_state.set_dns_records(records),
+        Err(e) => {
+            error!("Failed to list DNS records: {}", e);
+            app_state.set_error(format!("Failed to list DNS records: {}", e));
+        }
+    }
+}
+
+async fn load_quotas(app_state: &mut AppState) {
+    let mut quotas = Vec::new();
+    for provider in app_state.providers.clone() {
+        let provider = provider.read().await;
+        match provider.get_quotas().await {
+            Ok(provider_quotas) => {
+                quotas.extend(provider_quotas.into_iter().map(|q| (provider.name().to_string(), q)))
+            }
+            Err(e) => warn!("Failed to fetch quotas for {}: {}", provider.name(), e),
+        }
+    }
+    app_state.set_quotas(quotas);
+}
+
+async fn load_waste(app_state: &mut AppState) {
+    let mut waste = Vec::new();
+    for provider in app_state.providers.clone() {
+        let provider = provider.read().await;
+        match provider.find_waste().await {
+            Ok(findings) => waste.extend(findings.into_iter().map(|f| (provider.name().to_string(), f))),
+            Err(e) => warn!("Failed to scan for idle resources in {}: {}", provider.name(), e),
+        }
+    }
+    app_state.set_waste(waste);
+}
+
+/// How far back the Changes view compares the inventory.
+const CHANGES_WINDOW_HOURS: i64 = 24;
+
+async fn load_changes(app_state: &mut AppState, cache_store: &Option<Arc<CacheStore>>) {
+    let cache = match cache_store {
+        Some(cache) => cache,
+        None => return app_state.set_error("Cache is not enabled".to_string()),
+    };
+
+    let since = chrono::Utc::now() - chrono::Duration::hours(CHANGES_WINDOW_HOURS);
+    match cache.run_blocking(move |cache| cache.inventory_changes_since(since)).await {
+        Ok(changes) => app_state.inventory_changes = changes,
+        Err(e) => app_state.set_error(format!("Failed to compare inventories: {}", e)),
+    }
+}
+
+/// How many audit log entries the History view shows.
+const HISTORY_LIMIT: usize = 500;
+
+/// Saves the actions recorded since the last flush to the audit log.
+async fn flush_action_log(app_state: &mut AppState, cache_store: &Option<Arc<CacheStore>>) {
+    let records = app_state.take_unsaved_actions();
+    let cache = match cache_store {
+        Some(cache) if !records.is_empty() => cache,
+        _ => return,
+    };
+
+    if let Err(e) = cache.run_blocking(move |cache| cache.record_actions(&records)).await {
+        warn!("Failed to save action log: {}", e);
+    }
+}
+
+/// Fills the History view from the audit log, or from this session's
+/// actions when the cache is disabled.
+async fn load_history(app_state: &mut AppState, cache_store: &Option<Arc<CacheStore>>) {
+    let cache = match cache_store {
+        Some(cache) => cache,
+        None => {
+            app_state.action_history = app_state.action_log.iter().rev().take(HISTORY_LIMIT).cloned().collect();
+            return;
+        }
+    };
+
+    match cache.run_blocking(|cache| cache.get_action_log(HISTORY_LIMIT)).await {
+        Ok(records) => app_state.action_history = records,
+        Err(e) => app_state.set_error(format!("Failed to load action history: {}", e)),
+    }
+}
+
+/// Switches the detail view to the Logs tab and fetches the latest logs.
+async fn open_logs_tab(app_state: &mut AppState) {
+    app_state.open_logs_tab();
+    app_state.start_loading();
+    load_logs(app_state, false).await;
+    app_state.stop_loading();
+}
+
+/// Fetches log events for the selected resource.
+///
+/// With `tail` set only events newer than the last loaded line are fetched and
+/// appended; otherwise the view is reloaded from scratch.
+async fn load_logs(app_state: &mut AppState, tail: bool) {
+    let target = {
+        let resources = app_state.resources.read().await;
+        app_state.get_selected_resource_index().and_then(|resource_idx| {
+            resources
+                .get(resource_idx)
+                .map(|resource| (resource_idx, resource.id().to_string(), resource.resource_type()))
+        })
+    };
+
+    let (resource_idx, resource_id, resource_type) = match target {
+        Some(target) => target,
+        None => return,
+    };
+
+    let provider = match app_state.provider_for_resource(resource_idx) {
+        Some(provider) => provider,
+        None => {
+            app_state.set_error("No provider found for this resource".to_string());
+            return;
+        }
+    };
+
+    let since = if tail { app_state.latest_log_time() } else { None };
+    if !tail {
+        app_state.logs.clear();
+        app_state.logs_scroll = 0;
+    }
+
+    let result = provider.read().await.get_logs(&resource_id, resource_type, since).await;
+
+    match result {
+        Ok(entries) => app_state.append_logs(entries),
+        Err(e) => {
+            error!("Failed to fetch logs: {}", e);
+            app_state.logs_follow = false;
+            app_state.set_error(format!("Failed to fetch logs: {}", e));
+        }
+    }
+}
+
+async fn run_app(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app_state: &mut AppState,
+    cache_store: Option<Arc<CacheStore>>,
+    contexts: &ContextConfigs,
+) -> Result<()> {
+    let mut last_message_time: Option<std::time::Instant> = None;
+    let mut last_log_poll = std::time::Instant::now();
+    let mut last_image_poll = std::time::Instant::now();
+    let mut last_operation_poll = std::time::Instant::now();
+    let mut announce_refresh = false;
+    let mut key_translator = KeyTranslator::new(app_state.keymap);
+    const MESSAGE_DISPLAY_DURATION: Duration = Duration::from_secs(3);
+    const LOG_POLL_INTERVAL: Duration = Duration::from_secs(5);
+    const LOG_PAGE_SIZE: usize = 20;
+    const LIST_PAGE_SIZE: usize = 20;
+    const DETAIL_PAGE_SIZE: usize = 10;
+    const IMAGE_POLL_INTERVAL: Duration = Duration::from_secs(15);
+    const OPERATION_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+    loop {
+        if let Some(msg_time) = last_message_time {
+            if msg_time.elapsed() > MESSAGE_DISPLAY_DURATION {
+                app_state.clear_success();
+                last_message_time = None;
+            }
+        }
+
+        if app_state.view_mode == ViewMode::ResourceDetail
+            && app_state.detail_tab == DetailTab::Logs
+            && app_state.logs_follow
+            && last_log_poll.elapsed() > LOG_POLL_INTERVAL
+        {
+            load_logs(app_state, true).await;
+            last_log_poll = std::time::Instant::now();
+        }
+
+        if !app_state.image_jobs.is_empty() && last_image_poll.elapsed() > IMAGE_POLL_INTERVAL {
+            if poll_image_jobs(app_state).await {
+                last_message_time = Some(std::time::Instant::now());
+            }
+            last_image_poll = std::time::Instant::now();
+        }
+
+        if let Some(Err(e)) = app_state.poll_search() {
+            warn!("Cache search failed: {}", e);
+        }
+
+        if app_state.is_switching_account() {
+            if let Some((label, result)) = app_state.poll_account_switch().await {
+                match result {
+                    Ok(()) => {
+                        let msg = format!("Switched to AWS account '{}'", label);
+                        info!("{}", msg);
+                        app_state.record_action(ActionRecord::succeeded("Switch account", msg.as_str()));
+                        app_state.set_success(msg);
+                        app_state.start_refresh().await;
+                    }
+                    Err(e) => {
+                        error!("Failed to switch to AWS account '{}': {}", label, e);
+                        let msg = format!("Could not sign in as '{}': {}", label, e);
+                        app_state.record_action(ActionRecord::failed("Switch account", msg.as_str()));
+                        app_state.set_error(msg);
+                    }
+                }
+                last_message_time = Some(std::time::Instant::now());
+            }
+        }
+
+        if app_state.is_refreshing() {
+            match app_state.poll_refresh().await {
+                Some(Ok(())) => {
+                    info!("Refresh completed successfully");
+                    let sync = after_refresh(app_state, &cache_store).await;
+                    // Background refreshes only speak up when the inventory changed
+                    if announce_refresh || sync.is_some_and(|summary| !summary.is_empty()) {
+                        let msg = match sync {
+                            Some(summary) => format!("Resources refreshed successfully ({})", summary),
+                            None => "Resources refreshed successfully".to_string(),
+                        };
+                        app_state.record_action(ActionRecord::succeeded("Refresh", msg.as_str()));
+                        app_state.set_success(msg);
+                        last_message_time = Some(std::time::Instant::now());
+                    }
+                    announce_refresh = false;
+                }
+                Some(Err(e)) => {
+                    error!("Refresh failed: {}", e);
+                    if announce_refresh {
+                        app_state.record_action(ActionRecord::failed("Refresh", e.to_string()));
+                    }
+                    announce_refresh = false;
+                }
+                None => {}
+            }
+        }
+
+        if !app_state.operations.is_empty() && last_operation_poll.elapsed() > OPERATION_POLL_INTERVAL {
+            if poll_operations(app_state).await {
+                last_message_time = Some(std::time::Instant::now());
+                if let Err(e) = refresh_and_cache_resources(app_state, &cache_store).await {
+                    error!("Failed to refresh after action finished: {}", e);
+                }
+            }
+            last_operation_poll = std::time::Instant::now();
+        }
+
+        flush_action_log(app_state, &cache_store).await;
+
+        terminal.draw(|f| {
+            let future = ui::render(f, app_state);
+            tokio::task::block_in_place(|| {
+                tokio::runtime::Handle::current().block_on(future)
             });
         })?;
 
-        if app_state.should_quit {
-            break;
-        }
+        if app_state.should_quit {
+            break;
+        }
+
+        if event::poll(Duration::from_millis(100))? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    // Text being typed is never remapped
+                    let mut half_page = false;
+                    let mut search_command = None;
+                    let key = if !app_state.is_typing() {
+                        match key_translator.translate(key.code, key.modifiers) {
+                            None => continue,
+                            Some(KeyCommand::Key(code)) => KeyEvent { code, ..key },
+                            Some(KeyCommand::HalfPage(code)) => {
+                                half_page = true;
+                                KeyEvent { code, ..key }
+                            }
+                            // Only the resource list acts on these; popups ignore them
+                            Some(command) => {
+                                search_command = Some(command);
+                                KeyEvent { code: KeyCode::Null, ..key }
+                            }
+                        }
+                    } else {
+                        key
+                    };
+                    let page = |rows: usize| if half_page { rows / 2 } else { rows };
+
+                    if app_state.show_confirmation {
+                        match key.code {
+                            KeyCode::Enter if app_state.can_confirm() => {
+                                let pending_modification = app_state.pending_modification.take();
+                                let pending_request = app_state.pending_request.take();
+                                let pending_bulk_action = app_state.pending_bulk_action.take();
+                                let snapshot_choice = app_state.snapshot_choice;
+                                let take_snapshot = app_state.wants_snapshot();
+                                let unprotect = app_state.pending_unprotect;
+                                app_state.cancel_confirmation();
+
+                                if let Some(option) = pending_modification {
+                                    if apply_modification(app_state, &cache_store, option).await {
+                                        last_message_time = Some(std::time::Instant::now());
+                                    }
+                                    continue;
+                                }
+
+                                if let Some(action) = pending_bulk_action {
+                                    if run_bulk_action(app_state, &cache_store, action).await {
+                                        last_message_time = Some(std::time::Instant::now());
+                                    }
+                                    last_operation_poll = std::time::Instant::now();
+                                    continue;
+                                }
+                                
+                                let action_info = if let Some(resource_idx) = app_state.get_selected_resource_index() {
+                                    let resources = app_state.resources.read().await;
+                                    if let Some(resource) = resources.get(resource_idx) {
+                                        let actions = app_state.actions_for(resource_idx, resource.as_ref());
+                                        if let Some(action) = actions.get(app_state.selected_action) {
+                                            Some((
+                                                resource.id().to_string(),
+                                                resource.name().to_string(),
+                                                resource_idx,
+                                                resource.resource_type(),
+                                                *action
+                                            ))
+                                        } else {
+                                            None
+                                        }
+                                    } else {
+                                        None
+                                    }
+                                } else {
+                                    None
+                                };
+                                
+                                if let Some((resource_id, resource_name, resource_idx, resource_type, action)) = action_info {
+                                    let request = pending_request
+                                        .filter(|request| request.action == action)
+                                        .unwrap_or_else(|| ActionRequest::new(action));
+                                    info!("Executing action {:?} on resource {}", action, resource_id);
+                                    app_state.start_loading();
+                                    
+                                    let mut action_result = None;
+                                    if let Some(provider) = app_state.provider_for_resource(resource_idx) {
+                                        let provider = provider.read().await;
+                                        let unprotected = if unprotect {
+                                            info!("Removing termination protection from {}", resource_id);
+                                            provider.remove_termination_protection(&resource_id, resource_type).await
+                                        } else {
+                                            Ok(())
+                                        };
+
+                                        if let Err(e) = unprotected {
+                                            action_result = Some(Err(e));
+                                        } else if take_snapshot && action == nimbus::core::Action::Terminate {
+                                            info!("Taking snapshot of {} before terminating", resource_id);
+                                            action_result = Some(provider.terminate_with_snapshot(&resource_id, resource_type).await);
+                                        } else {
+                                            action_result = Some(provider.execute_action(&resource_id, resource_type, &request).await);
+                                        }
+                                    }
+                                    
+                                    match action_result {
+                                        Some(Ok(handle)) => {
+                                            info!("Action accepted");
+                                            let success_msg = if track_operation(app_state, resource_idx, &resource_name, handle).await {
+                                                last_operation_poll = std::time::Instant::now();
+                                                format!("{} requested for '{}'", action.as_str(), resource_name)
+                                            } else {
+                                                format!("Successfully completed {} on '{}'", action.as_str().to_lowercase(), resource_name)
+                                            };
+                                            let record = ActionRecord::succeeded(action.as_str(), success_msg.as_str());
+                                            record_resource_action(app_state, resource_idx, &resource_id, &resource_name, record).await;
+                                            app_state.set_success(success_msg);
+                                            last_message_time = Some(std::time::Instant::now());
+                                            
+                                            if let Err(e) = refresh_and_cache_resources(app_state, &cache_store).await {
+                                                error!("Failed to refresh after action: {}", e);
+                                            }
+                                        }
+                                        Some(Err(nimbus::NimbusError::TerminationProtected(_))) => {
+                                            app_state.stop_loading();
+                                            app_state.confirm_unprotect(&resource_name, snapshot_choice);
+                                        }
+                                        Some(Err(e)) => {
+                                            error!("Action failed: {}", e);
+                                            let record = ActionRecord::failed(action.as_str(), e.to_string());
+                                            record_resource_action(app_state, resource_idx, &resource_id, &resource_name, record).await;
+                                            app_state.set_error(format!("{}", e));
+                                        }
+                                        None => {
+                                            error!("No provider found for resource");
+                                            app_state.set_error("No provider found for this resource".to_string());
+                                        }
+                                    }
+                                }
+                            }
+                            // While a name is being typed, s is part of it
+                            KeyCode::Char('s') if !app_state.needs_typed_confirmation() => {
+                                app_state.toggle_snapshot_choice();
+                            }
+                            KeyCode::Tab if app_state.needs_typed_confirmation() => {
+                                app_state.toggle_snapshot_choice();
+                            }
+                            KeyCode::Char(c) if app_state.needs_typed_confirmation() => {
+                                app_state.push_confirm_char(c);
+                            }
+                            KeyCode::Backspace => app_state.pop_confirm_char(),
+                            KeyCode::Esc => {
+                                app_state.cancel_confirmation();
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    if app_state.is_prompting() {
+                        match key.code {
+                            KeyCode::Char(c) => app_state.prompt_input.push(c),
+                            KeyCode::Backspace => {
+                                app_state.prompt_input.pop();
+                            }
+                            KeyCode::Enter => {
+                                match app_state.take_prompt() {
+                                    Some((nimbus::core::Action::CreateImage, input)) => {
+                                        if start_image_creation(app_state, input.trim().to_string()).await {
+                                            last_message_time = Some(std::time::Instant::now());
+                                            last_image_poll = std::time::Instant::now();
+                                        }
+                                    }
+                                    Some((nimbus::core::Action::Snapshot, input)) => {
+                                        let request = ActionRequest::new(nimbus::core::Action::Snapshot).with_snapshot_name(input.trim());
+                                        if run_action_request(app_state, &cache_store, request).await {
+                                            last_message_time = Some(std::time::Instant::now());
+                                        }
+                                    }
+                                    Some((nimbus::core::Action::Scale, input)) => match input.trim().parse::<u32>() {
+                                        Ok(replicas) => {
+                                            let resource_name = selected_resource(app_state)
+                                                .await
+                                                .map(|(_, _, name, _)| name)
+                                                .unwrap_or_default();
+                                            app_state.confirm_request(
+                                                format!(
+                                                    "Scale '{}' to {} replica(s)?\n\nPress Enter to confirm or ESC to cancel.",
+                                                    resource_name, replicas
+                                                ),
+                                                ActionRequest::new(nimbus::core::Action::Scale).with_desired_capacity(replicas),
+                                            );
+                                        }
+                                        Err(_) => app_state.set_error("Replica count must be a whole number".to_string()),
+                                    },
+                                    Some((nimbus::core::Action::Create, input)) => {
+                                        if let Some(ref mut wizard) = app_state.create_wizard {
+                                            match wizard.step {
+                                                CreateStep::Name => wizard.set_name(&input),
+                                                CreateStep::Tags => wizard.set_tags(&input),
+                                                _ => {}
+                                            }
+                                        }
+                                        prompt_create_step(app_state);
+                                    }
+                                    Some((nimbus::core::Action::Tag, input)) if app_state.tag_editor.is_none() => {
+                                        match nimbus::core::tags::TagChanges::parse(&input) {
+                                            Some(changes) if !changes.is_empty() => {
+                                                if bulk_update_tags(app_state, &cache_store, changes).await {
+                                                    last_message_time = Some(std::time::Instant::now());
+                                                }
+                                            }
+                                            Some(_) => {}
+                                            None => app_state.set_error("Tags must be entered as key=value or -key".to_string()),
+                                        }
+                                    }
+                                    Some((nimbus::core::Action::Tag, input)) => {
+                                        match nimbus::core::tags::parse_tag(&input) {
+                                            Some((key, value)) => {
+                                                if let Some(ref mut editor) = app_state.tag_editor {
+                                                    editor.set(key, value);
+                                                }
+                                            }
+                                            None => app_state.set_error("Tags must be entered as key=value".to_string()),
+                                        }
+                                    }
+                                    _ => {}
+                                }
+                            }
+                            KeyCode::Esc => {
+                                if let Some((nimbus::core::Action::Create, _)) = app_state.take_prompt() {
+                                    if let Some(ref mut wizard) = app_state.create_wizard {
+                                        wizard.back();
+                                    }
+                                    prompt_create_step(app_state);
+                                }
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    if app_state.create_wizard.is_some() {
+                        match key.code {
+                            KeyCode::Up => {
+                                if let Some(ref mut wizard) = app_state.create_wizard {
+                                    wizard.prev();
+                                }
+                            }
+                            KeyCode::Down => {
+                                if let Some(ref mut wizard) = app_state.create_wizard {
+                                    wizard.next();
+                                }
+                            }
+                            KeyCode::Enter => {
+                                let step = app_state.create_wizard.as_ref().map(|wizard| wizard.step);
+                                match step {
+                                    Some(CreateStep::Review) => {
+                                        if launch_from_wizard(app_state, &cache_store).await {
+                                            last_message_time = Some(std::time::Instant::now());
+                                        }
+                                    }
+                                    Some(step) if step.is_text() => prompt_create_step(app_state),
+                                    Some(_) => {
+                                        if let Some(ref mut wizard) = app_state.create_wizard {
+                                            wizard.choose();
+                                        }
+                                        prompt_create_step(app_state);
+                                    }
+                                    None => {}
+                                }
+                            }
+                            KeyCode::Backspace | KeyCode::Left => {
+                                let went_back = app_state.create_wizard.as_mut().is_some_and(|wizard| wizard.back());
+                                if went_back {
+                                    prompt_create_step(app_state);
+                                } else {
+                                    app_state.create_wizard = None;
+                                }
+                            }
+                            KeyCode::Esc => {
+                                app_state.create_wizard = None;
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    if app_state.tag_editor.is_some() {
+                        match key.code {
+                            KeyCode::Up => {
+                                if let Some(ref mut editor) = app_state.tag_editor {
+                                    editor.prev();
+                                }
+                            }
+                            KeyCode::Down => {
+                                if let Some(ref mut editor) = app_state.tag_editor {
+                                    editor.next();
+                                }
+                            }
+                            KeyCode::Char('a') => {
+                                app_state.open_prompt(
+                                    nimbus::core::Action::Tag,
+                                    "Add tag (key=value)".to_string(),
+                                    String::new(),
+                                );
+                            }
+                            KeyCode::Char('e') => {
+                                let selected = app_state
+                                    .tag_editor
+                                    .as_ref()
+                                    .and_then(|editor| editor.tags.get(editor.selected).cloned());
+                                if let Some((key, value)) = selected {
+                                    app_state.open_prompt(
+                                        nimbus::core::Action::Tag,
+                                        "Edit tag (key=value)".to_string(),
+                                        format!("{}={}", key, value),
+                                    );
+                                }
+                            }
+                            KeyCode::Char('d') => {
+                                if let Some(ref mut editor) = app_state.tag_editor {
+                                    editor.remove_selected();
+                                }
+                            }
+                            KeyCode::Enter => {
+                                if save_tags(app_state, &cache_store).await {
+                                    last_message_time = Some(std::time::Instant::now());
+                                }
+                            }
+                            KeyCode::Esc => {
+                                app_state.tag_editor = None;
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    if app_state.show_period_picker {
+                        match (key.code, app_state.period_input.is_some()) {
+                            (KeyCode::Char(c), true) => {
+                                if let Some(ref mut input) = app_state.period_input {
+                                    input.push(c);
+                                }
+                            }
+                            (KeyCode::Backspace, true) => {
+                                if let Some(ref mut input) = app_state.period_input {
+                                    input.pop();
+                                }
+                            }
+                            (KeyCode::Enter, true) => {
+                                let input = app_state.period_input.clone().unwrap_or_default();
+                                match CostPeriod::parse_range(&input) {
+                                    Some(period) => {
+                                        app_state.set_cost_period(period);
+                                        load_cost_history(app_state).await;
+                                    }
+                                    None => app_state.set_error(
+                                        "Enter the range as YYYY-MM-DD to YYYY-MM-DD, start first".to_string(),
+                                    ),
+                                }
+                            }
+                            (KeyCode::Esc, true) => app_state.period_input = None,
+                            (KeyCode::Up, false) => app_state.prev_period(),
+                            (KeyCode::Down, false) => app_state.next_period(),
+                            (KeyCode::Enter, false) => match app_state.highlighted_period() {
+                                Some(period) => {
+                                    app_state.set_cost_period(period);
+                                    load_cost_history(app_state).await;
+                                }
+                                None => {
+                                    let input = match app_state.cost_period {
+                                        period @ CostPeriod::Custom { .. } => period.label(),
+                                        _ => String::new(),
+                                    };
+                                    app_state.period_input = Some(input);
+                                }
+                            },
+                            (KeyCode::Esc, false) => app_state.close_period_picker(),
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    if app_state.show_help {
+                        match key.code {
+                            KeyCode::Up => app_state.scroll_help_up(1),
+                            KeyCode::Down => app_state.scroll_help_down(1),
+                            KeyCode::PageUp => app_state.scroll_help_up(page(LIST_PAGE_SIZE)),
+                            KeyCode::PageDown => app_state.scroll_help_down(page(LIST_PAGE_SIZE)),
+                            KeyCode::Char('?') | KeyCode::Esc => app_state.close_help(),
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    if app_state.show_notifications {
+                        match key.code {
+                            KeyCode::Up => app_state.scroll_notifications_up(1),
+                            KeyCode::Down => app_state.scroll_notifications_down(1),
+                            KeyCode::PageUp => app_state.scroll_notifications_up(page(LIST_PAGE_SIZE)),
+                            KeyCode::PageDown => app_state.scroll_notifications_down(page(LIST_PAGE_SIZE)),
+                            KeyCode::Char('m') | KeyCode::Esc => app_state.toggle_notifications(),
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    if app_state.cache_stats.is_some() {
+                        match key.code {
+                            KeyCode::Char('p') => {
+                                if let Some(ref cache) = cache_store {
+                                    match cache.prune_expired().and_then(|pruned| Ok((pruned, cache.stats()?))) {
+                                        Ok((pruned, stats)) => {
+                                            app_state.show_cache_stats(stats);
+                                            app_state.set_success(format!("Pruned {} expired cache entries", pruned));
+                                            last_message_time = Some(std::time::Instant::now());
+                                        }
+                                        Err(e) => app_state.set_error(format!("Failed to prune cache: {}", e)),
+                                    }
+                                }
+                            }
+                            KeyCode::Char('C') | KeyCode::Esc => app_state.close_cache_stats(),
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    if app_state.show_bulk_picker {
+                        match key.code {
+                            KeyCode::Up => app_state.prev_bulk_action(),
+                            KeyCode::Down => app_state.next_bulk_action(),
+                            KeyCode::Enter => {
+                                let targets = marked_resources(app_state).await;
+                                let names: Vec<String> = targets.iter().map(|(_, _, name, _)| name.clone()).collect();
+                                match app_state.highlighted_bulk_action() {
+                                    Some(nimbus::core::Action::Tag) if !names.is_empty() => {
+                                        app_state.close_bulk_picker();
+                                        app_state.open_prompt(
+                                            nimbus::core::Action::Tag,
+                                            format!("Tags for {} resources (key=value, -key to remove)", names.len()),
+                                            String::new(),
+                                        );
+                                    }
+                                    Some(action) if !names.is_empty() => {
+                                        let blockers = if action == nimbus::core::Action::Terminate {
+                                            deletion_blockers(app_state, &targets).await
+                                        } else {
+                                            Vec::new()
+                                        };
+                                        app_state.confirm_bulk_action(bulk_action_prompt(action, &names));
+                                        app_state.set_deletion_blockers(blockers);
+                                        if action == nimbus::core::Action::Terminate {
+                                            let targets: Vec<(&str, &str)> = targets
+                                                .iter()
+                                                .map(|(_, id, name, _)| (name.as_str(), id.as_str()))
+                                                .collect();
+                                            app_state.require_termination_confirmation(&targets);
+                                        }
+                                    }
+                                    _ => app_state.close_bulk_picker(),
+                                }
+                            }
+                            KeyCode::Esc => app_state.close_bulk_picker(),
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    if app_state.show_column_picker {
+                        match key.code {
+                            KeyCode::Up => app_state.prev_column(),
+                            KeyCode::Down => app_state.next_column(),
+                            KeyCode::Char(' ') | KeyCode::Enter => app_state.toggle_column(),
+                            KeyCode::Char('[') => app_state.move_column(false),
+                            KeyCode::Char(']') => app_state.move_column(true),
+                            KeyCode::Char('v') | KeyCode::Esc => app_state.close_column_picker(),
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    if let Some(ref mut picker) = app_state.account_picker {
+                        match key.code {
+                            KeyCode::Up => picker.prev(),
+                            KeyCode::Down => picker.next(),
+                            KeyCode::Enter => {
+                                let choice = picker
+                                    .chosen()
+                                    .map(|choice| (picker.provider_idx, choice.label.clone(), choice.config.clone()));
+                                app_state.account_picker = None;
+                                if let Some((provider_idx, label, config)) = choice {
+                                    app_state.clear_messages();
+                                    switch_account(app_state, contexts, provider_idx, label, config);
+                                }
+                            }
+                            KeyCode::Esc => app_state.account_picker = None,
+                            _ => {}
+                        }
+                        continue;
+                    }
 
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    if app_state.show_confirmation {
+                    if let Some(ref mut picker) = app_state.region_picker {
                         match key.code {
+                            KeyCode::Up => picker.prev(),
+                            KeyCode::Down => picker.next(),
                             KeyCode::Enter => {
-                                app_state.cancel_confirmation();
-                                
-                                let action_info = if let Some(resource_idx) = app_state.get_selected_resource_index() {
-                                    let resources = app_state.resources.read().await;
-                                    if let Some(resource) = resources.get(resource_idx) {
-                                        let actions = resource.supported_actions();
-                                        if let Some(action) = actions.get(app_state.selected_action) {
-                                            Some((
-                                                resource.id().to_string(),
-                                                resource.name().to_string(),
-                                                resource.provider(),
-                                                *action
-                                            ))
-                                        } else {
-                                            None
-                                        }
-                                    } else {
-                                        None
-                                    }
-                                } else {
-                                    None
-                                };
-                                
-                                if let Some((resource_id, resource_name, resource_provider, action)) = action_info {
-                                    info!("Executing action {:?} on resource {}", action, resource_id);
-                                    app_state.start_loading();
-                                    
-                                    let mut action_result = None;
-                                    for provider in &app_state.providers {
-                                        let provider = provider.read().await;
-                                        if provider.provider_type() == resource_provider {
-                                            action_result = Some(provider.execute_action(&resource_id, action).await);
-                                            break;
-                                        }
+                                let choice = picker.chosen().map(|region| (picker.provider_idx, region.to_string()));
+                                app_state.region_picker = None;
+                                if let Some((provider_idx, region)) = choice {
+                                    app_state.clear_messages();
+                                    if switch_region(app_state, provider_idx, &region).await {
+                                        last_message_time = Some(std::time::Instant::now());
                                     }
-                                    
-                                    match action_result {
-                                        Some(Ok(_)) => {
-                                            info!("Action executed successfully");
-                                            let success_msg = format!(
-                                                "Successfully {} '{}'",
-                                                match action {
-                                                    nimbus::core::Action::Start => "started",
-                                                    nimbus::core::Action::Stop => "stopped",
-                                                    nimbus::core::Action::Restart => "restarted",
-                                                    nimbus::core::Action::Terminate => "terminated",
-                                                    _ => "completed action on",
-                                                },
-                                                resource_name
-                                            );
-                                            app_state.record_action(success_msg.clone());
-                                            app_state.set_success(success_msg);
+                                }
+                            }
+                            KeyCode::Esc => app_state.region_picker = None,
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    if app_state.show_context_picker {
+                        match key.code {
+                            KeyCode::Up => app_state.prev_context(),
+                            KeyCode::Down => app_state.next_context(),
+                            KeyCode::Enter => {
+                                let name = app_state.highlighted_context().map(str::to_string);
+                                app_state.close_context_picker();
+                                if let Some(name) = name {
+                                    if app_state.active_context.as_deref() != Some(name.as_str()) {
+                                        app_state.clear_messages();
+                                        if switch_context(app_state, &cache_store, contexts, &name).await {
                                             last_message_time = Some(std::time::Instant::now());
-                                            
-                                            if let Err(e) = refresh_and_cache_resources(app_state, &cache_store).await {
-                                                error!("Failed to refresh after action: {}", e);
-                                            }
-                                        }
-                                        Some(Err(e)) => {
-                                            error!("Action failed: {}", e);
-                                            app_state.set_error(format!("{}", e));
-                                        }
-                                        None => {
-                                            error!("No provider found for resource");
-                                            app_state.set_error("No provider found for this resource".to_string());
                                         }
                                     }
                                 }
                             }
-                            KeyCode::Esc => {
-                                app_state.cancel_confirmation();
+                            KeyCode::Esc => app_state.close_context_picker(),
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    if app_state.show_modify_picker {
+                        match key.code {
+                            KeyCode::Up => app_state.prev_modify_option(),
+                            KeyCode::Down => app_state.next_modify_option(),
+                            KeyCode::Enter => {
+                                let current_cost = {
+                                    let resources = app_state.resources.read().await;
+                                    app_state
+                                        .get_selected_resource_index()
+                                        .and_then(|idx| resources.get(idx))
+                                        .map(|r| (r.name().to_string(), r.cost_per_month()))
+                                };
+                                let option = app_state.modify_options.get(app_state.selected_modify_option).cloned();
+                                if let (Some((resource_name, cost)), Some(option)) = (current_cost, option) {
+                                    let message = modification_prompt(&resource_name, &option, cost);
+                                    app_state.confirm_modify_option(message);
+                                } else {
+                                    app_state.close_modify_picker();
+                                }
                             }
+                            KeyCode::Esc => app_state.close_modify_picker(),
                             _ => {}
                         }
                         continue;
@@ -377,9 +2809,11 @@ async fn run_app(
                         match key.code {
                             KeyCode::Char(c) => {
                                 app_state.push_filter_char(c);
+                                search_cache(app_state, &cache_store);
                             }
                             KeyCode::Backspace => {
                                 app_state.pop_filter_char();
+                                search_cache(app_state, &cache_store);
                             }
                             KeyCode::Esc => {
                                 app_state.exit_filter_mode();
@@ -392,6 +2826,18 @@ async fn run_app(
                             }
                             _ => {}
                         }
+                    } else if app_state.is_searching() {
+                        match key.code {
+                            KeyCode::Char(c) => app_state.push_search_char(c).await,
+                            KeyCode::Backspace => app_state.pop_search_char().await,
+                            KeyCode::Esc => app_state.exit_search_mode(false),
+                            KeyCode::Enter => app_state.exit_search_mode(true),
+                            _ => {}
+                        }
+                    } else if key.code == KeyCode::Char('?') {
+                        app_state.open_help();
+                    } else if key.code == KeyCode::Char('m') {
+                        app_state.toggle_notifications();
                     } else {
                         match app_state.view_mode {
                             ViewMode::Dashboard | ViewMode::ResourceList => {
@@ -408,12 +2854,14 @@ async fn run_app(
                                         app_state.clear_messages();
                                     }
                                     KeyCode::Char('c') => {
-                                        if let Some(ref cache) = cache_store {
+                                        if app_state.offline {
+                                            app_state.set_error("The cache cannot be cleared in offline mode".to_string());
+                                        } else if let Some(ref cache) = cache_store {
                                             info!("User requested cache clear");
                                             match cache.clear_cache(None) {
                                                 Ok(_) => {
                                                     let msg = "Cache cleared successfully".to_string();
-                                                    app_state.record_action(msg.clone());
+                                                    app_state.record_action(ActionRecord::succeeded("Clear cache", msg.as_str()));
                                                     app_state.set_success(msg);
                                                     last_message_time = Some(std::time::Instant::now());
                                                     info!("Cache cleared");
@@ -427,29 +2875,128 @@ async fn run_app(
                                             app_state.set_error("Cache is not enabled".to_string());
                                         }
                                     }
+                                    KeyCode::Char('C') => match cache_store {
+                                        Some(ref cache) => match cache.stats() {
+                                            Ok(stats) => app_state.show_cache_stats(stats),
+                                            Err(e) => {
+                                                app_state.set_error(format!("Failed to read cache statistics: {}", e))
+                                            }
+                                        },
+                                        None => app_state.set_error("Cache is not enabled".to_string()),
+                                    },
                                     KeyCode::Char('/') => {
                                         if matches!(app_state.view_mode, ViewMode::ResourceList) {
                                             app_state.enter_filter_mode();
                                         }
                                     }
+                                    KeyCode::Null => {
+                                        if matches!(app_state.view_mode, ViewMode::ResourceList) {
+                                            match search_command {
+                                                Some(KeyCommand::Search) => app_state.enter_search_mode(),
+                                                Some(KeyCommand::SearchNext) | Some(KeyCommand::SearchPrevious) => {
+                                                    let forward = search_command == Some(KeyCommand::SearchNext);
+                                                    if !app_state.search_next(forward).await && !app_state.search_text.is_empty() {
+                                                        app_state.set_error(format!("No match for '{}'", app_state.search_text));
+                                                    }
+                                                }
+                                                _ => {}
+                                            }
+                                        }
+                                    }
+                                    KeyCode::Char('x') => {
+                                        app_state.clear_messages();
+                                        app_state.open_context_picker();
+                                    }
+                                    KeyCode::Char('R') => open_region_picker(app_state).await,
+                                    KeyCode::Char('P') => open_account_picker(app_state, contexts).await,
+                                    KeyCode::Char('g') => {
+                                        if matches!(app_state.view_mode, ViewMode::ResourceList) {
+                                            app_state.cycle_grouping();
+                                        }
+                                    }
+                                    KeyCode::Char('v') => {
+                                        if matches!(app_state.view_mode, ViewMode::ResourceList) {
+                                            app_state.clear_messages();
+                                            app_state.open_column_picker();
+                                        }
+                                    }
+                                    KeyCode::Char('n') => {
+                                        if app_state.any_provider_supports(nimbus::core::Action::Create) {
+                                            open_create_wizard(app_state).await;
+                                        }
+                                    }
+                                    KeyCode::Char('p') => {
+                                        if matches!(app_state.view_mode, ViewMode::Dashboard)
+                                            && app_state.supports_cost(CostFeature::History)
+                                        {
+                                            app_state.clear_messages();
+                                            app_state.open_period_picker();
+                                        }
+                                    }
+                                    KeyCode::Char('u') => {
+                                        if matches!(app_state.view_mode, ViewMode::Dashboard) && !app_state.offline {
+                                            app_state.clear_messages();
+                                            app_state.enter_quotas_view();
+                                            app_state.start_loading();
+                                            load_quotas(app_state).await;
+                                            app_state.stop_loading();
+                                        }
+                                    }
+                                    KeyCode::Char('t') => {
+                                        if matches!(app_state.view_mode, ViewMode::Dashboard)
+                                            && !app_state.tag_policy.is_empty()
+                                        {
+                                            app_state.clear_messages();
+                                            app_state.enter_compliance_view();
+                                        }
+                                    }
+                                    KeyCode::Char('h') => {
+                                        if matches!(app_state.view_mode, ViewMode::Dashboard) {
+                                            app_state.clear_messages();
+                                            app_state.enter_history_view();
+                                            app_state.start_loading();
+                                            load_history(app_state, &cache_store).await;
+                                            app_state.stop_loading();
+                                        }
+                                    }
+                                    KeyCode::Char('y') => {
+                                        if matches!(app_state.view_mode, ViewMode::Dashboard) {
+                                            app_state.clear_messages();
+                                            app_state.enter_changes_view();
+                                            app_state.start_loading();
+                                            load_changes(app_state, &cache_store).await;
+                                            app_state.stop_loading();
+                                        }
+                                    }
+                                    KeyCode::Char('w') => {
+                                        if matches!(app_state.view_mode, ViewMode::Dashboard) && !app_state.offline {
+                                            app_state.clear_messages();
+                                            app_state.enter_waste_view();
+                                            app_state.start_loading();
+                                            load_waste(app_state).await;
+                                            app_state.stop_loading();
+                                        }
+                                    }
                                     KeyCode::Esc => {
                                         if !app_state.filter_text.is_empty() {
                                             app_state.clear_filter();
+                                        } else if !app_state.marked_resources.is_empty() {
+                                            app_state.clear_marks();
                                         } else {
                                             app_state.clear_messages();
                                         }
                                     }
                                     KeyCode::Char('r') => {
-                                        info!("User requested manual refresh");
-                                        app_state.clear_messages();
-                                        if let Err(e) = refresh_and_cache_resources(app_state, &cache_store).await {
-                                            error!("Refresh failed: {}", e);
-                                        } else {
-                                            info!("Refresh completed successfully");
-                                            let msg = "Resources refreshed successfully".to_string();
-                                            app_state.record_action(msg.clone());
-                                            app_state.set_success(msg);
-                                            last_message_time = Some(std::time::Instant::now());
+                                        if app_state.offline {
+                                            app_state.clear_messages();
+                                            if let Some(ref cache) = cache_store {
+                                                load_from_cache(app_state, cache).await;
+                                            }
+                                        } else if !app_state.is_refreshing() {
+                                            info!("User requested manual refresh");
+                                            app_state.clear_messages();
+                                            app_state.start_refresh().await;
+                                            announce_refresh = true;
                                         }
                                     }
                                     KeyCode::Up => {
@@ -462,10 +3009,57 @@ async fn run_app(
                                             app_state.next_resource();
                                         }
                                     }
+                                    KeyCode::PageUp => {
+                                        if matches!(app_state.view_mode, ViewMode::ResourceList) {
+                                            app_state.page_up(page(LIST_PAGE_SIZE));
+                                        }
+                                    }
+                                    KeyCode::PageDown => {
+                                        if matches!(app_state.view_mode, ViewMode::ResourceList) {
+                                            app_state.page_down(page(LIST_PAGE_SIZE));
+                                        }
+                                    }
+                                    KeyCode::Home => {
+                                        if matches!(app_state.view_mode, ViewMode::ResourceList) {
+                                            app_state.select_first_resource();
+                                        }
+                                    }
+                                    KeyCode::End => {
+                                        if matches!(app_state.view_mode, ViewMode::ResourceList) {
+                                            app_state.select_last_resource();
+                                        }
+                                    }
+                                    KeyCode::Char('a') => {
+                                        if matches!(app_state.view_mode, ViewMode::ResourceList) {
+                                            app_state.toggle_mark_all().await;
+                                        }
+                                    }
+                                    KeyCode::Char('o') => {
+                                        if matches!(app_state.view_mode, ViewMode::ResourceList)
+                                            && open_console(app_state).await
+                                        {
+                                            last_message_time = Some(std::time::Instant::now());
+                                        }
+                                    }
+                                    KeyCode::Char(' ') => {
+                                        if matches!(app_state.view_mode, ViewMode::ResourceList) {
+                                            if let Some((_, resource_id, _, _)) = selected_resource(app_state).await {
+                                                app_state.toggle_mark(&resource_id);
+                                                app_state.next_resource();
+                                            }
+                                        }
+                                    }
                                     KeyCode::Enter => {
                                         if matches!(app_state.view_mode, ViewMode::ResourceList) {
-                                            app_state.clear_messages();
-                                            app_state.enter_detail_view();
+                                            if app_state.selected_group().is_some() {
+                                                app_state.toggle_group();
+                                            } else if !app_state.marked_resources.is_empty() {
+                                                open_bulk_picker(app_state).await;
+                                            } else {
+                                                app_state.clear_messages();
+                                                app_state.enter_detail_view();
+                                                load_target_health(app_state, false).await;
+                                            }
                                         }
                                     }
                                     _ => {}
@@ -478,12 +3072,91 @@ async fn run_app(
                                         app_state.clear_messages();
                                         app_state.exit_detail_view();
                                     }
+                                    KeyCode::Right | KeyCode::Left => {
+                                        if key.code == KeyCode::Right {
+                                            app_state.next_detail_tab();
+                                        } else {
+                                            app_state.prev_detail_tab();
+                                        }
+                                        match app_state.detail_tab {
+                                            DetailTab::Metrics => load_metrics(app_state, &cache_store, false).await,
+                                            DetailTab::Logs => {
+                                                open_logs_tab(app_state).await;
+                                                last_log_poll = std::time::Instant::now();
+                                            }
+                                            _ => {}
+                                        }
+                                    }
+                                    KeyCode::Tab if app_state.detail_tab == DetailTab::Overview => {
+                                        app_state.next_detail_pane();
+                                    }
+                                    KeyCode::BackTab if app_state.detail_tab == DetailTab::Overview => {
+                                        app_state.prev_detail_pane();
+                                    }
+                                    KeyCode::Char('r') => match app_state.detail_tab {
+                                        DetailTab::Metrics => load_metrics(app_state, &cache_store, true).await,
+                                        DetailTab::Logs => {
+                                            app_state.clear_messages();
+                                            app_state.start_loading();
+                                            load_logs(app_state, false).await;
+                                            app_state.stop_loading();
+                                            last_log_poll = std::time::Instant::now();
+                                        }
+                                        _ => load_target_health(app_state, true).await,
+                                    },
+                                    KeyCode::Char('f') if app_state.detail_tab == DetailTab::Logs => {
+                                        app_state.toggle_log_follow();
+                                    }
+                                    KeyCode::Char('o') => {
+                                        if open_console(app_state).await {
+                                            last_message_time = Some(std::time::Instant::now());
+                                        }
+                                    }
+                                    KeyCode::Up | KeyCode::PageUp if app_state.detail_tab == DetailTab::Logs => {
+                                        let lines = if key.code == KeyCode::Up { 1 } else { page(LOG_PAGE_SIZE) };
+                                        app_state.scroll_logs_up(lines);
+                                    }
+                                    KeyCode::Down | KeyCode::PageDown if app_state.detail_tab == DetailTab::Logs => {
+                                        let lines = if key.code == KeyCode::Down { 1 } else { page(LOG_PAGE_SIZE) };
+                                        app_state.scroll_logs_down(lines);
+                                    }
+                                    KeyCode::Up | KeyCode::PageUp if app_state.detail_tab == DetailTab::Raw => {
+                                        let lines = if key.code == KeyCode::Up { 1 } else { page(DETAIL_PAGE_SIZE) };
+                                        app_state.scroll_raw_up(lines);
+                                    }
+                                    KeyCode::Down | KeyCode::PageDown if app_state.detail_tab == DetailTab::Raw => {
+                                        let lines = if key.code == KeyCode::Down { 1 } else { page(DETAIL_PAGE_SIZE) };
+                                        let len = {
+                                            let resources = app_state.resources.read().await;
+                                            app_state
+                                                .get_selected_resource_index()
+                                                .and_then(|idx| resources.get(idx))
+                                                .and_then(|resource| serde_json::to_string_pretty(&resource.raw_json()).ok())
+                                                .map_or(0, |text| text.lines().count())
+                                        };
+                                        app_state.scroll_raw_down(lines, len);
+                                    }
+                                    KeyCode::Up | KeyCode::PageUp if app_state.detail_pane != DetailPane::Actions => {
+                                        let lines = if key.code == KeyCode::Up { 1 } else { page(DETAIL_PAGE_SIZE) };
+                                        app_state.scroll_detail_pane_up(lines);
+                                    }
+                                    KeyCode::Down | KeyCode::PageDown if app_state.detail_pane != DetailPane::Actions => {
+                                        let lines = if key.code == KeyCode::Down { 1 } else { page(DETAIL_PAGE_SIZE) };
+                                        let len = {
+                                            let resources = app_state.resources.read().await;
+                                            app_state
+                                                .get_selected_resource_index()
+                                                .and_then(|idx| resources.get(idx))
+                                                .map_or(0, |resource| app_state.detail_pane_len(resource.as_ref()))
+                                        };
+                                        app_state.scroll_detail_pane_down(lines, len);
+                                    }
                                     KeyCode::Up => {
                                         let action_count = {
                                             let resources = app_state.resources.read().await;
                                             if let Some(resource_idx) = app_state.get_selected_resource_index() {
                                                 if let Some(resource) = resources.get(resource_idx) {
-                                                    resource.supported_actions().len()
+                                                    app_state.actions_for(resource_idx, resource.as_ref()).len()
                                                 } else {
                                                     0
                                                 }
@@ -498,7 +3171,7 @@ async fn run_app(
                                             let resources = app_state.resources.read().await;
                                             if let Some(resource_idx) = app_state.get_selected_resource_index() {
                                                 if let Some(resource) = resources.get(resource_idx) {
-                                                    resource.supported_actions().len()
+                                                    app_state.actions_for(resource_idx, resource.as_ref()).len()
                                                 } else {
                                                     0
                                                 }
@@ -513,12 +3186,14 @@ async fn run_app(
                                             let resources = app_state.resources.read().await;
                                             if let Some(resource_idx) = app_state.get_selected_resource_index() {
                                                 if let Some(resource) = resources.get(resource_idx) {
-                                                    let actions = resource.supported_actions();
+                                                    let actions = app_state.actions_for(resource_idx, resource.as_ref());
                                                     if let Some(action) = actions.get(app_state.selected_action) {
                                                         Some((
                                                             resource.id().to_string(),
                                                             resource.name().to_string(),
-                                                            resource.provider(),
+                                                            resource_idx,
+                                                            resource.resource_type(),
+                                                            actions.contains(&nimbus::core::Action::Snapshot),
                                                             *action
                                                         ))
                                                     } else {
@@ -532,41 +3207,145 @@ async fn run_app(
                                             }
                                         };
                                         
-                                        if let Some((resource_id, resource_name, resource_provider, action)) = action_info {
-                                            if action.is_destructive() {
+                                        if let Some((resource_id, resource_name, resource_idx, resource_type, supports_snapshot, action)) = action_info {
+                                            if action == nimbus::core::Action::Connect {
+                                                app_state.clear_messages();
+                                                let command = match app_state.provider_for_resource(resource_idx) {
+                                                    Some(provider) => {
+                                                        let provider = provider.read().await;
+                                                        provider.shell_command(&resource_id, resource_type)
+                                                    }
+                                                    None => None,
+                                                };
+
+                                                match command {
+                                                    Some(command) => {
+                                                        info!("Opening shell session to {}", resource_id);
+                                                        match run_interactive(terminal, command) {
+                                                            Ok(status) if status.success() => {
+                                                                let record = ActionRecord::succeeded(
+                                                                    "Connect",
+                                                                    format!("Closed session to '{}'", resource_name),
+                                                                );
+                                                                record_resource_action(app_state, resource_idx, &resource_id, &resource_name, record).await;
+                                                            }
+                                                            Ok(status) => {
+                                                                warn!("Session to {} exited with {}", resource_id, status);
+                                                                app_state.set_error(format!(
+                                                                    "Session to '{}' exited with {}",
+                                                                    resource_name, status
+                                                                ));
+                                                            }
+                                                            Err(e) => {
+                                                                error!("Failed to start session: {}", e);
+                                                                app_state.set_error(format!(
+                                                                    "Failed to start session: {}",
+                                                                    e
+                                                                ));
+                                                            }
+                                                        }
+                                                    }
+                                                    None => {
+                                                        app_state.set_error("This resource does not support shell sessions".to_string());
+                                                    }
+                                                }
+                                            } else if action == nimbus::core::Action::Tag {
+                                                app_state.clear_messages();
+                                                let tags = {
+                                                    let resources = app_state.resources.read().await;
+                                                    resources.get(resource_idx).map(|r| r.tags().clone())
+                                                };
+                                                app_state.tag_editor = tags.map(TagEditor::new);
+                                            } else if action == nimbus::core::Action::CreateImage {
+                                                app_state.clear_messages();
+                                                let default_name = format!(
+                                                    "{}-{}",
+                                                    resource_name,
+                                                    chrono::Utc::now().format("%Y%m%d-%H%M")
+                                                );
+                                                app_state.open_prompt(
+                                                    action,
+                                                    format!("Image name for '{}'", resource_name),
+                                                    default_name,
+                                                );
+                                            } else if action == nimbus::core::Action::Modify {
+                                                app_state.clear_messages();
+                                                open_modify_picker(app_state).await;
+                                            } else if action == nimbus::core::Action::ViewLogs {
+                                                app_state.clear_messages();
+                                                open_logs_tab(app_state).await;
+                                                last_log_poll = std::time::Instant::now();
+                                            } else if action == nimbus::core::Action::ViewRecords {
+                                                app_state.clear_messages();
+                                                app_state.enter_records_view();
+                                                app_state.start_loading();
+                                                load_dns_records(app_state).await;
+                                                app_state.stop_loading();
+                                            } else if action == nimbus::core::Action::Snapshot {
+                                                app_state.clear_messages();
+                                                let default_name = format!(
+                                                    "{}-{}",
+                                                    resource_name,
+                                                    chrono::Utc::now().format("%Y%m%d-%H%M")
+                                                );
+                                                app_state.open_prompt(
+                                                    action,
+                                                    format!("Snapshot name for '{}'", resource_name),
+                                                    default_name,
+                                                );
+                                            } else if action == nimbus::core::Action::Scale {
+                                                app_state.clear_messages();
+                                                app_state.open_prompt(
+                                                    action,
+                                                    format!("Replica count for '{}'", resource_name),
+                                                    String::new(),
+                                                );
+                                            } else if action.is_destructive() {
+                                                let instructions = if action == nimbus::core::Action::Terminate {
+                                                    "Type the name, or the last 4 characters of the ID, then press Enter to confirm or ESC to cancel."
+                                                } else {
+                                                    "Press Enter to confirm or ESC to cancel."
+                                                };
                                                 let message = format!(
-                                                    "Are you sure you want to {} '{}'?\n\nThis action cannot be undone.\n\nPress Enter to confirm or ESC to cancel.",
+                                                    "Are you sure you want to {} '{}'?\n\nThis action cannot be undone.\n\n{}",
                                                     action.as_str().to_lowercase(),
-                                                    resource_name
+                                                    resource_name,
+                                                    instructions
                                                 );
+                                                let blockers = deletion_blockers(
+                                                    app_state,
+                                                    &[(resource_idx, resource_id.clone(), resource_name.clone(), resource_type)],
+                                                )
+                                                .await;
                                                 app_state.show_action_confirmation(message);
+                                                app_state.set_deletion_blockers(blockers);
+                                                if action == nimbus::core::Action::Terminate {
+                                                    app_state.require_termination_confirmation(&[(resource_name.as_str(), resource_id.as_str())]);
+                                                    if supports_snapshot {
+                                                        app_state.offer_snapshot();
+                                                    }
+                                                }
                                             } else {
                                                 info!("Executing non-destructive action {:?}", action);
                                                 app_state.start_loading();
                                                 
                                                 let mut action_result = None;
-                                                for provider in &app_state.providers {
+                                                if let Some(provider) = app_state.provider_for_resource(resource_idx) {
                                                     let provider = provider.read().await;
-                                                    if provider.provider_type() == resource_provider {
-                                                        action_result = Some(provider.execute_action(&resource_id, action).await);
-                                                        break;
-                                                    }
+                                                    action_result = Some(provider.execute_action(&resource_id, resource_type, &action.into()).await);
                                                 }
                                                 
                                                 match action_result {
-                                                    Some(Ok(_)) => {
-                                                        info!("Action executed successfully");
-                                                        let success_msg = format!(
-                                                            "Successfully {} '{}'",
-                                                            match action {
-                                                                nimbus::core::Action::Start => "started",
-                                                                nimbus::core::Action::Stop => "stopped",
-                                                                nimbus::core::Action::Restart => "restarted",
-                                                                _ => "completed action on",
-                                                            },
-                                                            resource_name
-                                                        );
-                                                        app_state.record_action(success_msg.clone());
+                                                    Some(Ok(handle)) => {
+                                                        info!("Action accepted");
+                                                        let success_msg = if track_operation(app_state, resource_idx, &resource_name, handle).await {
+                                                            last_operation_poll = std::time::Instant::now();
+                                                            format!("{} requested for '{}'", action.as_str(), resource_name)
+                                                        } else {
+                                                            format!("Successfully completed {} on '{}'", action.as_str().to_lowercase(), resource_name)
+                                                        };
+                                                        let record = ActionRecord::succeeded(action.as_str(), success_msg.as_str());
+                                                        record_resource_action(app_state, resource_idx, &resource_id, &resource_name, record).await;
                                                         app_state.set_success(success_msg);
                                                         last_message_time = Some(std::time::Instant::now());
                                                         
@@ -576,6 +3355,8 @@ async fn run_app(
                                                     }
                                                     Some(Err(e)) => {
                                                         error!("Action failed: {}", e);
+                                                        let record = ActionRecord::failed(action.as_str(), e.to_string());
+                                                        record_resource_action(app_state, resource_idx, &resource_id, &resource_name, record).await;
                                                         app_state.set_error(format!("{}", e));
                                                     }
                                                     None => {
@@ -589,6 +3370,98 @@ async fn run_app(
                                     _ => {}
                                 }
                             }
+                            ViewMode::Records => {
+                                match key.code {
+                                    KeyCode::Char('q') => app_state.quit(),
+                                    KeyCode::Esc => {
+                                        app_state.clear_messages();
+                                        app_state.exit_records_view();
+                                    }
+                                    KeyCode::Char('r') => {
+                                        app_state.clear_messages();
+                                        app_state.start_loading();
+                                        load_dns_records(app_state).await;
+                                        app_state.stop_loading();
+                                    }
+                                    KeyCode::Right | KeyCode::PageDown => app_state.next_records_page(),
+                                    KeyCode::Left | KeyCode::PageUp => app_state.prev_records_page(),
+                                    _ => {}
+                                }
+                            }
+                            ViewMode::Quotas => {
+                                match key.code {
+                                    KeyCode::Char('q') => app_state.quit(),
+                                    KeyCode::Esc => {
+                                        app_state.clear_messages();
+                                        app_state.exit_quotas_view();
+                                    }
+                                    KeyCode::Char('r') => {
+                                        app_state.clear_messages();
+                                        app_state.start_loading();
+                                        load_quotas(app_state).await;
+                                        app_state.stop_loading();
+                                    }
+                                    _ => {}
+                                }
+                            }
+                            ViewMode::Compliance => {
+                                match key.code {
+                                    KeyCode::Char('q') => app_state.quit(),
+                                    KeyCode::Esc => {
+                                        app_state.clear_messages();
+                                        app_state.exit_compliance_view();
+                                    }
+                                    _ => {}
+                                }
+                            }
+                            ViewMode::Waste => {
+                                match key.code {
+                                    KeyCode::Char('q') => app_state.quit(),
+                                    KeyCode::Esc => {
+                                        app_state.clear_messages();
+                                        app_state.exit_waste_view();
+                                    }
+                                    KeyCode::Char('r') => {
+                                        app_state.clear_messages();
+                                        app_state.start_loading();
+                                        load_waste(app_state).await;
+                                        app_state.stop_loading();
+                                    }
+                                    _ => {}
+                                }
+                            }
+                            ViewMode::Changes => {
+                                match key.code {
+                                    KeyCode::Char('q') => app_state.quit(),
+                                    KeyCode::Esc => {
+                                        app_state.clear_messages();
+                                        app_state.exit_changes_view();
+                                    }
+                                    KeyCode::Char('r') => {
+                                        app_state.clear_messages();
+                                        app_state.start_loading();
+                                        load_changes(app_state, &cache_store).await;
+                                        app_state.stop_loading();
+                                    }
+                                    _ => {}
+                                }
+                            }
+                            ViewMode::History => {
+                                match key.code {
+                                    KeyCode::Char('q') => app_state.quit(),
+                                    KeyCode::Esc => {
+                                        app_state.clear_messages();
+                                        app_state.exit_history_view();
+                                    }
+                                    KeyCode::Char('r') => {
+                                        app_state.clear_messages();
+                                        app_state.start_loading();
+                                        load_history(app_state, &cache_store).await;
+                                        app_state.stop_loading();
+                                    }
+                                    _ => {}
+                                }
+                            }
                         }
                     }
                 }
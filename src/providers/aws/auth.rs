@@ -1,14 +1,42 @@
-use crate::config::AwsConfig;
+use crate::config::{AwsConfig, RetryConfig};
 use crate::error::{NimbusError, Result};
+use aws_config::retry::RetryConfig as SdkRetryConfig;
+use aws_config::sts::AssumeRoleProvider;
 use aws_config::{BehaviorVersion, Region, SdkConfig};
 use aws_credential_types::provider::SharedCredentialsProvider;
 use aws_credential_types::Credentials;
+use std::time::Duration;
+
+/// Session name recorded in CloudTrail for assumed-role calls.
+const ROLE_SESSION_NAME: &str = "nimbus";
+
+/// Placeholder key for custom endpoints, which accept any credentials.
+const LOCAL_ENDPOINT_KEY: &str = "test";
 
 pub struct AwsAuth;
 
 impl AwsAuth {
-    pub async fn create_config(aws_config: &AwsConfig) -> Result<SdkConfig> {
-        let region = Region::new(aws_config.region.clone());
+    pub async fn create_config(aws_config: &AwsConfig, retry: &RetryConfig) -> Result<SdkConfig> {
+        let base = Self::base_config(aws_config, retry).await;
+
+        match &aws_config.role_arn {
+            Some(role_arn) => Ok(Self::assume_role(&base, aws_config, role_arn).await),
+            None => Ok(base),
+        }
+    }
+
+    /// Loads the configuration for the account's own credentials: static
+    /// keys, then a named profile, then the default credential chain. With a
+    /// custom endpoint and no credentials configured, placeholder keys are
+    /// used so that LocalStack and MinIO work without an AWS account.
+    async fn base_config(aws_config: &AwsConfig, retry: &RetryConfig) -> SdkConfig {
+        let mut loader = aws_config::defaults(BehaviorVersion::latest())
+            .region(Region::new(aws_config.region.clone()))
+            .retry_config(Self::sdk_retry_config(retry));
+
+        if let Some(endpoint_url) = &aws_config.endpoint_url {
+            loader = loader.endpoint_url(endpoint_url);
+        }
 
         if let (Some(access_key), Some(secret_key)) =
             (&aws_config.access_key_id, &aws_config.secret_access_key)
@@ -20,33 +48,76 @@ impl AwsAuth {
                 None,
                 "nimbus-static-credentials",
             );
-            let creds_provider = SharedCredentialsProvider::new(creds);
-
-            let config = aws_config::defaults(BehaviorVersion::latest())
-                .region(region)
-                .credentials_provider(creds_provider)
+            return loader
+                .credentials_provider(SharedCredentialsProvider::new(creds))
                 .load()
                 .await;
+        }
 
-            return Ok(config);
+        if let Some(profile) = Self::source_profile(aws_config) {
+            return loader.profile_name(profile).load().await;
         }
 
-        if let Some(profile) = &aws_config.profile {
-            let config = aws_config::defaults(BehaviorVersion::latest())
-                .region(region)
-                .profile_name(profile)
+        if aws_config.endpoint_url.is_some() {
+            let creds = Credentials::new(
+                LOCAL_ENDPOINT_KEY,
+                LOCAL_ENDPOINT_KEY,
+                None,
+                None,
+                "nimbus-endpoint-credentials",
+            );
+            return loader
+                .credentials_provider(SharedCredentialsProvider::new(creds))
                 .load()
                 .await;
+        }
+
+        loader.load().await
+    }
+
+    /// Wraps the base configuration with credentials for `role_arn`. The
+    /// provider refreshes the temporary credentials before they expire.
+    async fn assume_role(base: &SdkConfig, aws_config: &AwsConfig, role_arn: &str) -> SdkConfig {
+        let mut builder = AssumeRoleProvider::builder(role_arn)
+            .session_name(ROLE_SESSION_NAME)
+            .region(Region::new(aws_config.region.clone()))
+            .configure(base);
 
-            return Ok(config);
+        if let Some(external_id) = &aws_config.external_id {
+            builder = builder.external_id(external_id);
         }
 
-        let config = aws_config::defaults(BehaviorVersion::latest())
-            .region(region)
-            .load()
-            .await;
+        let provider = builder.build().await;
 
-        Ok(config)
+        base.to_builder()
+            .credentials_provider(SharedCredentialsProvider::new(provider))
+            .build()
+    }
+
+    /// Returns the profile to load credentials from. When assuming a role,
+    /// `source_profile` takes precedence over `profile`.
+    fn source_profile(aws_config: &AwsConfig) -> Option<&str> {
+        match aws_config.role_arn {
+            Some(_) => aws_config
+                .source_profile
+                .as_deref()
+                .or(aws_config.profile.as_deref()),
+            None => aws_config.profile.as_deref(),
+        }
+    }
+
+    /// Translates Nimbus retry settings into the SDK's retry configuration,
+    /// which retries throttling and transient errors with jittered backoff.
+    pub fn sdk_retry_config(retry: &RetryConfig) -> SdkRetryConfig {
+        let base = if retry.adaptive {
+            SdkRetryConfig::adaptive()
+        } else {
+            SdkRetryConfig::standard()
+        };
+
+        base.with_max_attempts(retry.max_attempts.max(1))
+            .with_initial_backoff(Duration::from_millis(retry.initial_backoff_ms))
+            .with_max_backoff(Duration::from_secs(retry.max_backoff_secs))
     }
 
     pub async fn test_credentials(config: &SdkConfig) -> Result<bool> {
@@ -69,13 +140,63 @@ mod tests {
     #[tokio::test]
     async fn test_create_config_with_defaults() {
         let aws_config = AwsConfig {
+            label: None,
             profile: None,
             region: "us-east-1".to_string(),
             access_key_id: None,
             secret_access_key: None,
+            role_arn: None,
+            external_id: None,
+            source_profile: None,
+            s3_metrics: true,
+            endpoint_url: None,
         };
 
-        let result = AwsAuth::create_config(&aws_config).await;
+        let result = AwsAuth::create_config(&aws_config, &RetryConfig::default()).await;
         assert!(result.is_ok());
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_create_config_with_endpoint_url() {
+        let aws_config = AwsConfig {
+            endpoint_url: Some("http://localhost:4566".to_string()),
+            ..AwsConfig::default()
+        };
+
+        let sdk_config = AwsAuth::create_config(&aws_config, &RetryConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(sdk_config.endpoint_url(), Some("http://localhost:4566"));
+        assert!(sdk_config.credentials_provider().is_some());
+    }
+
+    #[test]
+    fn test_sdk_retry_config() {
+        let retry = RetryConfig {
+            max_attempts: 3,
+            initial_backoff_ms: 250,
+            max_backoff_secs: 10,
+            adaptive: false,
+        };
+
+        let sdk_retry = AwsAuth::sdk_retry_config(&retry);
+        assert_eq!(sdk_retry.max_attempts(), 3);
+        assert_eq!(sdk_retry.initial_backoff(), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn test_source_profile_prefers_role_source() {
+        let mut aws_config = AwsConfig {
+            profile: Some("dev".to_string()),
+            source_profile: Some("org-admin".to_string()),
+            ..AwsConfig::default()
+        };
+        assert_eq!(AwsAuth::source_profile(&aws_config), Some("dev"));
+
+        aws_config.role_arn = Some("arn:aws:iam::123456789012:role/Nimbus".to_string());
+        assert_eq!(AwsAuth::source_profile(&aws_config), Some("org-admin"));
+
+        aws_config.source_profile = None;
+        assert_eq!(AwsAuth::source_profile(&aws_config), Some("dev"));
+    }
+}
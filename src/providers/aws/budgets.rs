@@ -0,0 +1,57 @@
+use crate::core::Budget;
+use crate::error::{NimbusError, Result};
+use aws_sdk_budgets::types::Spend;
+use aws_sdk_budgets::Client as BudgetsClient;
+
+/// Reads cost budgets configured in AWS Budgets.
+pub struct AwsBudgets {
+    client: BudgetsClient,
+}
+
+impl AwsBudgets {
+    pub fn new(client: BudgetsClient) -> Self {
+        Self { client }
+    }
+
+    /// Lists the account's budgets. Budgets without a limit, such as
+    /// auto-adjusting ones that have not been calculated yet, are skipped.
+    pub async fn list(&self, account_id: &str) -> Result<Vec<Budget>> {
+        let mut pages = self
+            .client
+            .describe_budgets()
+            .account_id(account_id)
+            .into_paginator()
+            .send();
+
+        let mut budgets = Vec::new();
+        while let Some(page) = pages.next().await {
+            let page = page.map_err(|e| {
+                NimbusError::provider("AWS", format!("Failed to list budgets: {}", e))
+            })?;
+
+            for budget in page.budgets() {
+                let limit = match budget.budget_limit().and_then(spend_amount) {
+                    Some(limit) => limit,
+                    None => continue,
+                };
+                let spend = budget.calculated_spend();
+
+                budgets.push(Budget {
+                    name: budget.budget_name().to_string(),
+                    limit,
+                    actual: spend
+                        .and_then(|s| s.actual_spend())
+                        .and_then(spend_amount)
+                        .unwrap_or(0.0),
+                    forecast: spend.and_then(|s| s.forecasted_spend()).and_then(spend_amount),
+                });
+            }
+        }
+
+        Ok(budgets)
+    }
+}
+
+fn spend_amount(spend: &Spend) -> Option<f64> {
+    spend.amount().parse::<f64>().ok()
+}
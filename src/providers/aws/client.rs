@@ -1,10 +1,17 @@
 use aws_config::SdkConfig;
+use aws_sdk_budgets::Client as BudgetsClient;
+use aws_sdk_cloudwatch::Client as CloudWatchClient;
+use aws_sdk_cloudwatchlogs::Client as CloudWatchLogsClient;
 use aws_sdk_costexplorer::Client as CostExplorerClient;
 use aws_sdk_ec2::Client as Ec2Client;
+use aws_sdk_iam::Client as IamClient;
 use aws_sdk_elasticloadbalancingv2::Client as ElbClient;
 use aws_sdk_rds::Client as RdsClient;
 use aws_sdk_route53::Client as Route53Client;
 use aws_sdk_s3::Client as S3Client;
+use aws_sdk_savingsplans::Client as SavingsPlansClient;
+use aws_sdk_servicequotas::Client as ServiceQuotasClient;
+use aws_sdk_sts::Client as StsClient;
 
 pub struct AwsClient {
     pub ec2: Ec2Client,
@@ -13,6 +20,13 @@ pub struct AwsClient {
     pub elb: ElbClient,
     pub route53: Route53Client,
     pub cost_explorer: CostExplorerClient,
+    pub cloudwatch: CloudWatchClient,
+    pub logs: CloudWatchLogsClient,
+    pub sts: StsClient,
+    pub iam: IamClient,
+    pub savings_plans: SavingsPlansClient,
+    pub budgets: BudgetsClient,
+    pub service_quotas: ServiceQuotasClient,
 }
 
 impl AwsClient {
@@ -20,10 +34,26 @@ impl AwsClient {
         Self {
             ec2: Ec2Client::new(config),
             rds: RdsClient::new(config),
-            s3: S3Client::new(config),
+            s3: Self::s3_client(config),
             elb: ElbClient::new(config),
             route53: Route53Client::new(config),
             cost_explorer: CostExplorerClient::new(config),
+            cloudwatch: CloudWatchClient::new(config),
+            logs: CloudWatchLogsClient::new(config),
+            sts: StsClient::new(config),
+            iam: IamClient::new(config),
+            savings_plans: SavingsPlansClient::new(config),
+            budgets: BudgetsClient::new(config),
+            service_quotas: ServiceQuotasClient::new(config),
         }
     }
+
+    /// S3-compatible servers behind a custom endpoint rarely resolve
+    /// bucket subdomains, so buckets are addressed by path there.
+    fn s3_client(config: &SdkConfig) -> S3Client {
+        let s3_config = aws_sdk_s3::config::Builder::from(config)
+            .force_path_style(config.endpoint_url().is_some())
+            .build();
+        S3Client::from_conf(s3_config)
+    }
 }
\ No newline at end of file
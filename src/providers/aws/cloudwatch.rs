@@ -0,0 +1,212 @@
+use crate::core::MetricSeries;
+use crate::error::{NimbusError, Result};
+use aws_sdk_cloudwatch::primitives::DateTime as AwsDateTime;
+use aws_sdk_cloudwatch::types::{Dimension, Metric, MetricDataQuery, MetricStat};
+use aws_sdk_cloudwatch::Client as CloudWatchClient;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+
+/// GetMetricData accepts at most 500 queries per request.
+const MAX_QUERIES_PER_REQUEST: usize = 500;
+
+/// Storage metrics reported daily by S3 to CloudWatch.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BucketMetrics {
+    pub size_bytes: Option<u64>,
+    pub object_count: Option<u64>,
+}
+
+pub struct AwsCloudWatch {
+    client: CloudWatchClient,
+}
+
+impl AwsCloudWatch {
+    pub fn new(client: CloudWatchClient) -> Self {
+        Self { client }
+    }
+
+    /// Fetches size and object count for each bucket in batched requests.
+    ///
+    /// S3 publishes these metrics once a day in the bucket's home region, so
+    /// buckets outside the client's region (or created in the last day) are
+    /// simply absent from the result.
+    pub async fn bucket_metrics(&self, buckets: &[String]) -> Result<HashMap<String, BucketMetrics>> {
+        let mut queries = Vec::with_capacity(buckets.len() * 2);
+
+        for (idx, bucket) in buckets.iter().enumerate() {
+            queries.push(Self::s3_query(
+                format!("size_{}", idx),
+                bucket,
+                "BucketSizeBytes",
+                "StandardStorage",
+            )?);
+            queries.push(Self::s3_query(
+                format!("count_{}", idx),
+                bucket,
+                "NumberOfObjects",
+                "AllStorageTypes",
+            )?);
+        }
+
+        let values = self.latest_values(queries, Duration::days(3)).await?;
+        let mut metrics: HashMap<String, BucketMetrics> = HashMap::new();
+
+        for (idx, bucket) in buckets.iter().enumerate() {
+            let size_bytes = values.get(&format!("size_{}", idx)).map(|v| *v as u64);
+            let object_count = values.get(&format!("count_{}", idx)).map(|v| *v as u64);
+
+            if size_bytes.is_some() || object_count.is_some() {
+                metrics.insert(
+                    bucket.clone(),
+                    BucketMetrics {
+                        size_bytes,
+                        object_count,
+                    },
+                );
+            }
+        }
+
+        Ok(metrics)
+    }
+
+    /// Fetches recent samples for the given metrics of a single resource.
+    ///
+    /// Each spec is `(metric name, display label, unit, statistic)`.
+    pub async fn resource_metrics(
+        &self,
+        namespace: &str,
+        dimension: (&str, &str),
+        specs: &[(&str, &str, &str, &str)],
+        lookback: Duration,
+        period_secs: i32,
+    ) -> Result<Vec<MetricSeries>> {
+        let mut queries = Vec::with_capacity(specs.len());
+
+        for (idx, (metric_name, _, _, stat)) in specs.iter().enumerate() {
+            let metric = Metric::builder()
+                .namespace(namespace)
+                .metric_name(*metric_name)
+                .dimensions(Self::dimension(dimension.0, dimension.1)?)
+                .build();
+            queries.push(Self::query(format!("m_{}", idx), metric, period_secs, stat)?);
+        }
+
+        let samples = self.fetch(queries, lookback).await?;
+
+        Ok(specs
+            .iter()
+            .enumerate()
+            .map(|(idx, (_, label, unit, _))| {
+                let mut series = MetricSeries::new(*label, *unit);
+                if let Some(points) = samples.get(&format!("m_{}", idx)) {
+                    for (timestamp, value) in points {
+                        series.push(*timestamp, *value);
+                    }
+                }
+                series
+            })
+            .collect())
+    }
+
+    /// Runs the queries and returns the most recent datapoint for each query ID.
+    async fn latest_values(
+        &self,
+        queries: Vec<MetricDataQuery>,
+        lookback: Duration,
+    ) -> Result<HashMap<String, f64>> {
+        let samples = self.fetch(queries, lookback).await?;
+
+        Ok(samples
+            .into_iter()
+            .filter_map(|(id, points)| {
+                points
+                    .into_iter()
+                    .max_by_key(|(timestamp, _)| *timestamp)
+                    .map(|(_, value)| (id, value))
+            })
+            .collect())
+    }
+
+    /// Runs the queries in batches and returns all samples keyed by query ID.
+    async fn fetch(
+        &self,
+        queries: Vec<MetricDataQuery>,
+        lookback: Duration,
+    ) -> Result<HashMap<String, Vec<(DateTime<Utc>, f64)>>> {
+        let end = Utc::now();
+        let start = end - lookback;
+        let mut samples: HashMap<String, Vec<(DateTime<Utc>, f64)>> = HashMap::new();
+
+        for batch in queries.chunks(MAX_QUERIES_PER_REQUEST) {
+            let mut next_token: Option<String> = None;
+
+            loop {
+                let response = self
+                    .client
+                    .get_metric_data()
+                    .start_time(AwsDateTime::from_secs(start.timestamp()))
+                    .end_time(AwsDateTime::from_secs(end.timestamp()))
+                    .set_metric_data_queries(Some(batch.to_vec()))
+                    .set_next_token(next_token.take())
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        NimbusError::provider("AWS", format!("Failed to fetch CloudWatch metrics: {}", e))
+                    })?;
+
+                for result in response.metric_data_results() {
+                    if let Some(id) = result.id() {
+                        let points = samples.entry(id.to_string()).or_default();
+                        for (timestamp, value) in result.timestamps().iter().zip(result.values()) {
+                            if let Some(timestamp) = DateTime::from_timestamp(timestamp.secs(), 0) {
+                                points.push((timestamp, *value));
+                            }
+                        }
+                    }
+                }
+
+                match response.next_token() {
+                    Some(token) => next_token = Some(token.to_string()),
+                    None => break,
+                }
+            }
+        }
+
+        Ok(samples)
+    }
+
+    fn s3_query(id: String, bucket: &str, metric_name: &str, storage_type: &str) -> Result<MetricDataQuery> {
+        let metric = Metric::builder()
+            .namespace("AWS/S3")
+            .metric_name(metric_name)
+            .dimensions(Self::dimension("BucketName", bucket)?)
+            .dimensions(Self::dimension("StorageType", storage_type)?)
+            .build();
+
+        Self::query(id, metric, 86400, "Average")
+    }
+
+    fn query(id: String, metric: Metric, period_secs: i32, stat: &str) -> Result<MetricDataQuery> {
+        let stat = MetricStat::builder()
+            .metric(metric)
+            .period(period_secs)
+            .stat(stat)
+            .build()
+            .map_err(|e| NimbusError::provider("AWS", format!("Invalid metric query: {}", e)))?;
+
+        MetricDataQuery::builder()
+            .id(id)
+            .metric_stat(stat)
+            .return_data(true)
+            .build()
+            .map_err(|e| NimbusError::provider("AWS", format!("Invalid metric query: {}", e)))
+    }
+
+    fn dimension(name: &str, value: &str) -> Result<Dimension> {
+        Dimension::builder()
+            .name(name)
+            .value(value)
+            .build()
+            .map_err(|e| NimbusError::provider("AWS", format!("Invalid metric dimension: {}", e)))
+    }
+}
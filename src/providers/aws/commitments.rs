@@ -0,0 +1,234 @@
+use crate::core::cost::ExpiringCommitment;
+use crate::core::CommitmentCoverage;
+use crate::error::{NimbusError, Result};
+use super::client::AwsClient;
+use aws_sdk_costexplorer::types::DateInterval;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+/// Commitments ending within this many days are reported as expiring.
+const EXPIRY_WINDOW_DAYS: i64 = 60;
+
+/// Reads Reserved Instance and Savings Plans coverage from Cost Explorer
+/// and lists active commitments nearing the end of their term.
+pub struct AwsCommitments<'a> {
+    client: &'a AwsClient,
+}
+
+impl<'a> AwsCommitments<'a> {
+    pub fn new(client: &'a AwsClient) -> Self {
+        Self { client }
+    }
+
+    /// Fetches coverage and utilization for the last 30 days. Each figure is
+    /// fetched independently since Cost Explorer reports an error rather than
+    /// zero for accounts without that kind of commitment.
+    pub async fn coverage(&self) -> Result<CommitmentCoverage> {
+        let today = Utc::now().date_naive();
+        let interval = DateInterval::builder()
+            .start((today - Duration::days(30)).format("%Y-%m-%d").to_string())
+            .end(today.format("%Y-%m-%d").to_string())
+            .build()
+            .map_err(|e| NimbusError::provider("AWS", format!("Invalid date range: {}", e)))?;
+
+        let (ri_coverage, ri_utilization, sp_coverage, sp_utilization, expiring) = tokio::join!(
+            self.reservation_coverage(&interval),
+            self.reservation_utilization(&interval),
+            self.savings_plans_coverage(&interval),
+            self.savings_plans_utilization(&interval),
+            self.expiring_commitments(today),
+        );
+
+        Ok(CommitmentCoverage {
+            reservation_coverage: Self::optional(ri_coverage, "reservation coverage"),
+            reservation_utilization: Self::optional(ri_utilization, "reservation utilization"),
+            savings_plans_coverage: Self::optional(sp_coverage, "savings plans coverage"),
+            savings_plans_utilization: Self::optional(sp_utilization, "savings plans utilization"),
+            expiring: expiring?,
+        })
+    }
+
+    async fn reservation_coverage(&self, interval: &DateInterval) -> Result<Option<f64>> {
+        let response = self
+            .client
+            .cost_explorer
+            .get_reservation_coverage()
+            .time_period(interval.clone())
+            .send()
+            .await
+            .map_err(|e| Self::error("reservation coverage", e))?;
+
+        Ok(response
+            .total()
+            .and_then(|total| total.coverage_hours())
+            .and_then(|hours| hours.coverage_hours_percentage())
+            .and_then(|pct| pct.parse::<f64>().ok()))
+    }
+
+    async fn reservation_utilization(&self, interval: &DateInterval) -> Result<Option<f64>> {
+        let response = self
+            .client
+            .cost_explorer
+            .get_reservation_utilization()
+            .time_period(interval.clone())
+            .send()
+            .await
+            .map_err(|e| Self::error("reservation utilization", e))?;
+
+        Ok(response
+            .total()
+            .and_then(|total| total.utilization_percentage())
+            .and_then(|pct| pct.parse::<f64>().ok()))
+    }
+
+    async fn savings_plans_coverage(&self, interval: &DateInterval) -> Result<Option<f64>> {
+        let response = self
+            .client
+            .cost_explorer
+            .get_savings_plans_coverage()
+            .time_period(interval.clone())
+            .send()
+            .await
+            .map_err(|e| Self::error("savings plans coverage", e))?;
+
+        // Coverage is reported per period, so weight it by spend
+        let mut covered = 0.0;
+        let mut total = 0.0;
+        for entry in response.savings_plans_coverages() {
+            if let Some(data) = entry.coverage() {
+                covered += Self::parse_amount(data.spend_covered_by_savings_plans());
+                total += Self::parse_amount(data.total_cost());
+            }
+        }
+
+        Ok(coverage_percentage(covered, total))
+    }
+
+    async fn savings_plans_utilization(&self, interval: &DateInterval) -> Result<Option<f64>> {
+        let response = self
+            .client
+            .cost_explorer
+            .get_savings_plans_utilization()
+            .time_period(interval.clone())
+            .send()
+            .await
+            .map_err(|e| Self::error("savings plans utilization", e))?;
+
+        Ok(response
+            .total()
+            .and_then(|total| total.utilization())
+            .and_then(|utilization| utilization.utilization_percentage())
+            .and_then(|pct| pct.parse::<f64>().ok()))
+    }
+
+    async fn expiring_commitments(&self, today: NaiveDate) -> Result<Vec<ExpiringCommitment>> {
+        use aws_sdk_ec2::types::Filter;
+        use aws_sdk_savingsplans::types::SavingsPlanState;
+
+        let cutoff = today + Duration::days(EXPIRY_WINDOW_DAYS);
+        let mut expiring = Vec::new();
+
+        let reserved = self
+            .client
+            .ec2
+            .describe_reserved_instances()
+            .filters(Filter::builder().name("state").values("active").build())
+            .send()
+            .await
+            .map_err(|e| Self::error("reserved instances", e))?;
+
+        for ri in reserved.reserved_instances() {
+            let expires = match ri.end().and_then(|end| DateTime::<Utc>::from_timestamp(end.secs(), 0)) {
+                Some(end) => end.date_naive(),
+                None => continue,
+            };
+            if expires > cutoff {
+                continue;
+            }
+
+            expiring.push(ExpiringCommitment {
+                kind: "Reserved Instance".to_string(),
+                id: ri.reserved_instances_id().unwrap_or_default().to_string(),
+                description: format!(
+                    "{}x {}",
+                    ri.instance_count().unwrap_or(1),
+                    ri.instance_type().map(|t| t.as_str()).unwrap_or("unknown")
+                ),
+                expires,
+            });
+        }
+
+        let plans = self
+            .client
+            .savings_plans
+            .describe_savings_plans()
+            .states(SavingsPlanState::Active)
+            .send()
+            .await
+            .map_err(|e| Self::error("savings plans", e))?;
+
+        for plan in plans.savings_plans() {
+            let expires = match plan
+                .end()
+                .and_then(|end| DateTime::parse_from_rfc3339(end).ok())
+            {
+                Some(end) => end.with_timezone(&Utc).date_naive(),
+                None => continue,
+            };
+            if expires > cutoff {
+                continue;
+            }
+
+            expiring.push(ExpiringCommitment {
+                kind: "Savings Plan".to_string(),
+                id: plan.savings_plan_id().unwrap_or_default().to_string(),
+                description: format!(
+                    "{} ${}/hour",
+                    plan.savings_plan_type().map(|t| t.as_str()).unwrap_or("Savings Plan"),
+                    plan.commitment().unwrap_or("?")
+                ),
+                expires,
+            });
+        }
+
+        expiring.sort_by_key(|c| c.expires);
+        Ok(expiring)
+    }
+
+    fn optional(result: Result<Option<f64>>, what: &str) -> Option<f64> {
+        match result {
+            Ok(value) => value,
+            Err(e) => {
+                log::debug!("No {} data: {}", what, e);
+                None
+            }
+        }
+    }
+
+    fn parse_amount(amount: Option<&str>) -> f64 {
+        amount.and_then(|a| a.parse::<f64>().ok()).unwrap_or(0.0)
+    }
+
+    fn error<E: std::fmt::Display>(what: &str, e: E) -> NimbusError {
+        NimbusError::provider("AWS", format!("Failed to fetch {}: {}", what, e))
+    }
+}
+
+/// Returns `covered` as a percentage of `total`, or `None` without any spend.
+fn coverage_percentage(covered: f64, total: f64) -> Option<f64> {
+    if total > 0.0 {
+        Some(covered / total * 100.0)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coverage_percentage() {
+        assert_eq!(coverage_percentage(25.0, 100.0), Some(25.0));
+        assert_eq!(coverage_percentage(0.0, 0.0), None);
+    }
+}
@@ -1,8 +1,12 @@
-use crate::core::{CostBreakdown, CostPeriod};
+use crate::core::cost::UNTAGGED;
+use crate::core::{CostAnomaly, CostBreakdown, CostForecast, CostGranularity, CostPeriod, CostPoint};
 use crate::error::{NimbusError, Result};
-use aws_sdk_costexplorer::types::{DateInterval, Granularity, GroupDefinition};
+use aws_sdk_costexplorer::types::{
+    AnomalyDateInterval, DateInterval, Granularity, GroupDefinition, Metric, RootCause,
+};
 use aws_sdk_costexplorer::Client as CostExplorerClient;
-use chrono::{Duration, Utc};
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use std::collections::HashMap;
 
 pub struct AwsCostExplorer {
     client: CostExplorerClient,
@@ -14,6 +18,9 @@ impl AwsCostExplorer {
     }
 
     pub async fn get_total_cost(&self, period: CostPeriod) -> Result<f64> {
+        if Self::is_empty_range(period) {
+            return Ok(0.0);
+        }
         let (start, end) = Self::get_date_range(period);
 
         let response = self
@@ -48,6 +55,167 @@ impl AwsCostExplorer {
         Ok(total)
     }
 
+    /// Fetches this month's spend grouped by the values of a cost-allocation tag.
+    pub async fn get_cost_by_tag(&self, tag_key: &str) -> Result<HashMap<String, f64>> {
+        let (start, end) = Self::get_date_range(CostPeriod::ThisMonth);
+        let interval = DateInterval::builder()
+            .start(start)
+            .end(end)
+            .build()
+            .map_err(|e| NimbusError::provider("AWS", format!("Invalid date range: {}", e)))?;
+
+        let mut costs = HashMap::new();
+        let mut next_token: Option<String> = None;
+
+        loop {
+            let response = self
+                .client
+                .get_cost_and_usage()
+                .time_period(interval.clone())
+                .granularity(Granularity::Monthly)
+                .metrics("UnblendedCost")
+                .group_by(
+                    GroupDefinition::builder()
+                        .r#type(aws_sdk_costexplorer::types::GroupDefinitionType::Tag)
+                        .key(tag_key)
+                        .build(),
+                )
+                .set_next_page_token(next_token.take())
+                .send()
+                .await
+                .map_err(|e| {
+                    NimbusError::provider("AWS", format!("Failed to fetch cost by tag {}: {}", tag_key, e))
+                })?;
+
+            for result in response.results_by_time() {
+                for group in result.groups() {
+                    let value = match group.keys().first() {
+                        Some(key) => tag_value_from_group_key(key),
+                        None => continue,
+                    };
+                    let amount = group
+                        .metrics()
+                        .and_then(|metrics| metrics.get("UnblendedCost"))
+                        .and_then(|metric| metric.amount())
+                        .and_then(|amount| amount.parse::<f64>().ok());
+
+                    if let Some(amount) = amount {
+                        *costs.entry(value).or_insert(0.0) += amount;
+                    }
+                }
+            }
+
+            match response.next_page_token() {
+                Some(token) => next_token = Some(token.to_string()),
+                None => break,
+            }
+        }
+
+        Ok(costs)
+    }
+
+    /// Fetches anomalies detected in the last 30 days that are still active,
+    /// largest impact first.
+    pub async fn get_anomalies(&self) -> Result<Vec<CostAnomaly>> {
+        let today = Utc::now().date_naive();
+        let interval = AnomalyDateInterval::builder()
+            .start_date((today - Duration::days(30)).format("%Y-%m-%d").to_string())
+            .end_date(today.format("%Y-%m-%d").to_string())
+            .build()
+            .map_err(|e| NimbusError::provider("AWS", format!("Invalid date range: {}", e)))?;
+
+        let mut anomalies = Vec::new();
+        let mut next_token: Option<String> = None;
+
+        loop {
+            let response = self
+                .client
+                .get_anomalies()
+                .date_interval(interval.clone())
+                .set_next_page_token(next_token.take())
+                .send()
+                .await
+                .map_err(|e| {
+                    NimbusError::provider("AWS", format!("Failed to fetch cost anomalies: {}", e))
+                })?;
+
+            for anomaly in response.anomalies() {
+                let anomaly = CostAnomaly {
+                    id: anomaly.anomaly_id().to_string(),
+                    service: anomaly.dimension_value().unwrap_or("Unknown").to_string(),
+                    impact: anomaly.impact().map(|i| i.total_impact()).unwrap_or(0.0),
+                    root_cause: anomaly.root_causes().first().and_then(describe_root_cause),
+                    start: anomaly.anomaly_start_date().and_then(parse_anomaly_date),
+                    end: anomaly.anomaly_end_date().and_then(parse_anomaly_date),
+                };
+                if anomaly.is_active(today) {
+                    anomalies.push(anomaly);
+                }
+            }
+
+            match response.next_page_token() {
+                Some(token) => next_token = Some(token.to_string()),
+                None => break,
+            }
+        }
+
+        anomalies.sort_by(|a, b| b.impact.partial_cmp(&a.impact).unwrap_or(std::cmp::Ordering::Equal));
+        Ok(anomalies)
+    }
+
+    /// Fetches spend per day or month over the period, oldest first.
+    pub async fn get_cost_history(&self, period: CostPeriod, granularity: CostGranularity) -> Result<Vec<CostPoint>> {
+        if Self::is_empty_range(period) {
+            return Ok(Vec::new());
+        }
+        let (start, end) = Self::get_date_range(period);
+        let interval = DateInterval::builder()
+            .start(start)
+            .end(end)
+            .build()
+            .map_err(|e| NimbusError::provider("AWS", format!("Invalid date range: {}", e)))?;
+
+        let mut points = Vec::new();
+        let mut next_token: Option<String> = None;
+
+        loop {
+            let response = self
+                .client
+                .get_cost_and_usage()
+                .time_period(interval.clone())
+                .granularity(Self::sdk_granularity(granularity))
+                .metrics("UnblendedCost")
+                .set_next_page_token(next_token.take())
+                .send()
+                .await
+                .map_err(|e| {
+                    NimbusError::provider("AWS", format!("Failed to fetch cost history: {}", e))
+                })?;
+
+            for result in response.results_by_time() {
+                let date = result
+                    .time_period()
+                    .and_then(|p| NaiveDate::parse_from_str(p.start(), "%Y-%m-%d").ok());
+                let amount = result
+                    .total()
+                    .and_then(|total_map| total_map.get("UnblendedCost"))
+                    .and_then(|metric| metric.amount())
+                    .and_then(|amount| amount.parse::<f64>().ok());
+
+                if let (Some(date), Some(amount)) = (date, amount) {
+                    points.push(CostPoint::new(date, amount));
+                }
+            }
+
+            match response.next_page_token() {
+                Some(token) => next_token = Some(token.to_string()),
+                None => break,
+            }
+        }
+
+        Ok(points)
+    }
+
     pub async fn get_cost_breakdown(&self) -> Result<CostBreakdown> {
         let period = CostPeriod::ThisMonth;
         let (start, end) = Self::get_date_range(period);
@@ -152,9 +320,67 @@ impl AwsCostExplorer {
             breakdown.trend_percentage = ((breakdown.total - prev_total) / prev_total) * 100.0;
         }
 
+        breakdown.forecast = match self.get_month_end_forecast().await {
+            Ok(forecast) => Some(forecast),
+            Err(e) => {
+                log::warn!("Failed to fetch cost forecast: {}", e);
+                None
+            }
+        };
+
         Ok(breakdown)
     }
 
+    /// Projects this month's total: spend so far plus the Cost Explorer
+    /// forecast for the remaining days, with an 80% prediction interval.
+    pub async fn get_month_end_forecast(&self) -> Result<CostForecast> {
+        let today = Utc::now().date_naive();
+        let (month_start, next_month) = month_bounds(today);
+
+        // On the first of the month nothing has been billed yet
+        let spent = if today > month_start {
+            self.get_total_cost_for_range(
+                month_start.format("%Y-%m-%d").to_string(),
+                today.format("%Y-%m-%d").to_string(),
+            )
+            .await?
+        } else {
+            0.0
+        };
+
+        let response = self
+            .client
+            .get_cost_forecast()
+            .time_period(
+                DateInterval::builder()
+                    .start(today.format("%Y-%m-%d").to_string())
+                    .end(next_month.format("%Y-%m-%d").to_string())
+                    .build()
+                    .map_err(|e| NimbusError::provider("AWS", format!("Invalid date range: {}", e)))?,
+            )
+            .metric(Metric::UnblendedCost)
+            .granularity(Granularity::Monthly)
+            .prediction_interval_level(80)
+            .send()
+            .await
+            .map_err(|e| NimbusError::provider("AWS", format!("Failed to fetch cost forecast: {}", e)))?;
+
+        let parse = |amount: Option<&str>| amount.and_then(|a| a.parse::<f64>().ok()).unwrap_or(0.0);
+
+        let remaining = parse(response.total().and_then(|total| total.amount()));
+        let (lower, upper) = response
+            .forecast_results_by_time()
+            .iter()
+            .fold((0.0, 0.0), |(lower, upper), result| {
+                (
+                    lower + parse(result.prediction_interval_lower_bound()),
+                    upper + parse(result.prediction_interval_upper_bound()),
+                )
+            });
+
+        Ok(CostForecast::new(spent + remaining, spent + lower, spent + upper))
+    }
+
     async fn get_total_cost_for_range(&self, start: String, end: String) -> Result<f64> {
         let response = self
             .client
@@ -188,14 +414,27 @@ impl AwsCostExplorer {
         Ok(total)
     }
 
+    fn sdk_granularity(granularity: CostGranularity) -> Granularity {
+        match granularity {
+            CostGranularity::Daily => Granularity::Daily,
+            CostGranularity::Monthly => Granularity::Monthly,
+        }
+    }
+
+    /// True when the period has no days before today. Cost Explorer rejects
+    /// such a range, and a custom range starting today has nothing billed yet.
+    fn is_empty_range(period: CostPeriod) -> bool {
+        let today = Utc::now().date_naive();
+        let (start, end) = period.date_range(today);
+        start >= end.min(today)
+    }
+
     fn get_date_range(period: CostPeriod) -> (String, String) {
-        let end = Utc::now();
-        let start = match period {
-            CostPeriod::Today => end - Duration::days(1),
-            CostPeriod::ThisWeek => end - Duration::days(7),
-            CostPeriod::ThisMonth => end - Duration::days(30),
-            CostPeriod::Last30Days => end - Duration::days(30),
-        };
+        let today = Utc::now().date_naive();
+        let (start, end) = period.date_range(today);
+        // Cost Explorer rejects ranges reaching past today, so a custom
+        // range ending in the future stops at today like the presets
+        let end = end.min(today);
 
         (
             start.format("%Y-%m-%d").to_string(),
@@ -204,6 +443,50 @@ impl AwsCostExplorer {
     }
 }
 
+/// Returns the first day of the month containing `today` and the first day
+/// of the following month.
+fn month_bounds(today: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let month_start = today.with_day(1).unwrap_or(today);
+    let next_month = if today.month() == 12 {
+        NaiveDate::from_ymd_opt(today.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(today.year(), today.month() + 1, 1)
+    }
+    .unwrap_or(today);
+
+    (month_start, next_month)
+}
+
+/// Joins the populated parts of a root cause, e.g. "AmazonEC2 / us-east-1".
+fn describe_root_cause(cause: &RootCause) -> Option<String> {
+    let parts: Vec<&str> = [cause.service(), cause.region(), cause.usage_type(), cause.linked_account()]
+        .into_iter()
+        .flatten()
+        .collect();
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" / "))
+    }
+}
+
+/// Parses anomaly dates, which may be plain dates or full timestamps.
+fn parse_anomaly_date(date: &str) -> Option<NaiveDate> {
+    date.get(..10)
+        .and_then(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").ok())
+}
+
+/// Extracts the tag value from a Cost Explorer group key, which has the
+/// form `key$value` and an empty value for untagged spend.
+fn tag_value_from_group_key(group_key: &str) -> String {
+    match group_key.split_once('$') {
+        Some((_, value)) if !value.is_empty() => value.to_string(),
+        Some(_) => UNTAGGED.to_string(),
+        None => group_key.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -215,4 +498,60 @@ mod tests {
         assert_eq!(start.len(), 10);
         assert_eq!(end.len(), 10);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_month_bounds() {
+        let (start, end) = month_bounds(NaiveDate::from_ymd_opt(2024, 2, 14).unwrap());
+        assert_eq!(start, NaiveDate::from_ymd_opt(2024, 2, 1).unwrap());
+        assert_eq!(end, NaiveDate::from_ymd_opt(2024, 3, 1).unwrap());
+
+        let (start, end) = month_bounds(NaiveDate::from_ymd_opt(2024, 12, 31).unwrap());
+        assert_eq!(start, NaiveDate::from_ymd_opt(2024, 12, 1).unwrap());
+        assert_eq!(end, NaiveDate::from_ymd_opt(2025, 1, 1).unwrap());
+    }
+
+    #[test]
+    fn test_get_date_range_custom() {
+        let start = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+
+        let range = AwsCostExplorer::get_date_range(CostPeriod::Custom { start, end });
+        assert_eq!(range, ("2024-03-01".to_string(), "2024-03-15".to_string()));
+
+        let today = Utc::now().date_naive();
+        let range = AwsCostExplorer::get_date_range(CostPeriod::Custom {
+            start,
+            end: today + Duration::days(10),
+        });
+        assert_eq!(range.1, today.format("%Y-%m-%d").to_string());
+    }
+
+    #[test]
+    fn test_is_empty_range() {
+        let today = Utc::now().date_naive();
+        assert!(!AwsCostExplorer::is_empty_range(CostPeriod::Today));
+        assert!(AwsCostExplorer::is_empty_range(CostPeriod::Custom {
+            start: today,
+            end: today + Duration::days(1),
+        }));
+        assert!(!AwsCostExplorer::is_empty_range(CostPeriod::Custom {
+            start: today - Duration::days(1),
+            end: today + Duration::days(1),
+        }));
+    }
+
+    #[test]
+    fn test_tag_value_from_group_key() {
+        assert_eq!(tag_value_from_group_key("team$payments"), "payments");
+        assert_eq!(tag_value_from_group_key("team$"), UNTAGGED);
+        assert_eq!(tag_value_from_group_key("plain"), "plain");
+    }
+
+    #[test]
+    fn test_parse_anomaly_date() {
+        let expected = NaiveDate::from_ymd_opt(2024, 5, 1);
+        assert_eq!(parse_anomaly_date("2024-05-01"), expected);
+        assert_eq!(parse_anomaly_date("2024-05-01T00:00:00Z"), expected);
+        assert_eq!(parse_anomaly_date("bad"), None);
+    }
+}
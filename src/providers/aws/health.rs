@@ -0,0 +1,148 @@
+use crate::core::ResourceHealth;
+use crate::error::{NimbusError, Result};
+use aws_sdk_ec2::Client as Ec2Client;
+use aws_sdk_rds::Client as RdsClient;
+use std::collections::HashMap;
+
+/// EC2 status checks and RDS pending maintenance, the account-wide health
+/// checks behind `CloudProvider::get_health`.
+pub struct AwsHealthChecks {
+    ec2: Ec2Client,
+    rds: RdsClient,
+}
+
+impl AwsHealthChecks {
+    pub fn new(ec2: Ec2Client, rds: RdsClient) -> Self {
+        Self { ec2, rds }
+    }
+
+    /// Returns the status check result of every running instance, by
+    /// instance ID.
+    pub async fn instance_status_checks(&self) -> Result<HashMap<String, ResourceHealth>> {
+        let mut pages = self.ec2.describe_instance_status().into_paginator().send();
+
+        let mut health = HashMap::new();
+        while let Some(page) = pages.next().await {
+            let response = page.map_err(|e| {
+                NimbusError::provider("AWS", format!("Failed to describe instance status: {}", e))
+            })?;
+
+            for status in response.instance_statuses() {
+                let instance_id = match status.instance_id() {
+                    Some(id) => id,
+                    None => continue,
+                };
+                let summary = |s: Option<&aws_sdk_ec2::types::InstanceStatusSummary>| {
+                    s.and_then(|s| s.status())
+                        .map(|s| s.as_str().to_string())
+                        .unwrap_or_default()
+                };
+                let event = status
+                    .events()
+                    .iter()
+                    .find_map(|event| event.code().map(|code| code.as_str().to_string()));
+
+                health.insert(
+                    instance_id.to_string(),
+                    status_check_health(
+                        &summary(status.instance_status()),
+                        &summary(status.system_status()),
+                        event.as_deref(),
+                    ),
+                );
+            }
+        }
+
+        Ok(health)
+    }
+
+    /// Returns a warning for every database with maintenance waiting to be
+    /// applied, by DB instance identifier.
+    pub async fn pending_maintenance(&self) -> Result<HashMap<String, ResourceHealth>> {
+        let response = self
+            .rds
+            .describe_pending_maintenance_actions()
+            .send()
+            .await
+            .map_err(|e| {
+                NimbusError::provider("AWS", format!("Failed to describe pending maintenance: {}", e))
+            })?;
+
+        let mut health = HashMap::new();
+        for pending in response.pending_maintenance_actions() {
+            let db_id = match pending.resource_identifier().and_then(db_identifier_from_arn) {
+                Some(id) => id,
+                None => continue,
+            };
+            let actions: Vec<&str> = pending
+                .pending_maintenance_action_details()
+                .iter()
+                .filter_map(|detail| detail.action())
+                .collect();
+            if actions.is_empty() {
+                continue;
+            }
+
+            health.insert(
+                db_id.to_string(),
+                ResourceHealth::warning(format!("Maintenance pending: {}", actions.join(", "))),
+            );
+        }
+
+        Ok(health)
+    }
+}
+
+/// Combines the instance and system status checks of an EC2 instance with
+/// its first scheduled event, if any.
+fn status_check_health(instance: &str, system: &str, event: Option<&str>) -> ResourceHealth {
+    if system == "impaired" {
+        ResourceHealth::impaired("System status check failed")
+    } else if instance == "impaired" {
+        ResourceHealth::impaired("Instance status check failed")
+    } else if let Some(event) = event {
+        ResourceHealth::warning(format!("Scheduled event: {}", event))
+    } else if instance == "ok" && system == "ok" {
+        ResourceHealth::healthy()
+    } else {
+        ResourceHealth::warning("Status checks not passed yet")
+    }
+}
+
+/// Extracts the DB instance identifier from an ARN such as
+/// `arn:aws:rds:us-east-1:123456789012:db:orders`.
+fn db_identifier_from_arn(arn: &str) -> Option<&str> {
+    arn.split_once(":db:").map(|(_, id)| id).filter(|id| !id.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::HealthStatus;
+
+    #[test]
+    fn test_status_check_health() {
+        assert_eq!(status_check_health("ok", "ok", None), ResourceHealth::healthy());
+        assert_eq!(
+            status_check_health("ok", "impaired", None),
+            ResourceHealth::impaired("System status check failed")
+        );
+        assert_eq!(
+            status_check_health("ok", "ok", Some("system-reboot")),
+            ResourceHealth::warning("Scheduled event: system-reboot")
+        );
+        assert_eq!(
+            status_check_health("initializing", "ok", None).status,
+            HealthStatus::Warning
+        );
+    }
+
+    #[test]
+    fn test_db_identifier_from_arn() {
+        assert_eq!(
+            db_identifier_from_arn("arn:aws:rds:us-east-1:123456789012:db:orders"),
+            Some("orders")
+        );
+        assert_eq!(db_identifier_from_arn("arn:aws:rds:us-east-1:123456789012:cluster:orders"), None);
+    }
+}
@@ -0,0 +1,211 @@
+use crate::core::CreateSpec;
+use crate::error::{NimbusError, Result};
+use aws_sdk_ec2::types::{Filter, Image, InstanceType, ResourceType as Ec2ResourceType, Tag, TagSpecification};
+use aws_sdk_ec2::Client as Ec2Client;
+
+use super::resize::InstanceTypeOption;
+
+/// Public images offered alongside the account's own AMIs, as
+/// (owner, name pattern). Only the newest match of each is listed.
+const PUBLIC_IMAGES: [(&str, &str); 2] = [
+    ("amazon", "al2023-ami-2023.*-x86_64"),
+    ("099720109477", "ubuntu/images/hvm-ssd*/ubuntu-jammy-22.04-amd64-server-*"),
+];
+
+/// Instance families offered by the creation wizard. Kept short because
+/// every size is priced before the wizard opens.
+const LAUNCH_FAMILIES: [&str; 3] = ["t3", "m6i", "c6i"];
+
+/// An image the creation wizard can launch from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageOption {
+    pub image_id: String,
+    pub name: String,
+}
+
+impl ImageOption {
+    pub fn label(&self) -> String {
+        format!("{} ({})", self.name, self.image_id)
+    }
+}
+
+/// Lookups and the `RunInstances` call behind `Action::Create` for EC2.
+pub struct Ec2Launcher {
+    client: Ec2Client,
+}
+
+impl Ec2Launcher {
+    pub fn new(client: Ec2Client) -> Self {
+        Self { client }
+    }
+
+    /// Lists the account's own x86_64 AMIs followed by the newest
+    /// Amazon Linux and Ubuntu images.
+    pub async fn images(&self) -> Result<Vec<ImageOption>> {
+        let mut own = self.describe_images("self", None).await?;
+        newest_first(&mut own);
+
+        let mut images = Vec::new();
+        for (owner, pattern) in PUBLIC_IMAGES {
+            let mut public = self.describe_images(owner, Some(pattern)).await?;
+            newest_first(&mut public);
+            images.extend(public.into_iter().take(1));
+        }
+
+        images.extend(own);
+        Ok(images
+            .iter()
+            .filter_map(|image| {
+                Some(ImageOption {
+                    image_id: image.image_id()?.to_string(),
+                    name: image.name().unwrap_or("unnamed").to_string(),
+                })
+            })
+            .collect())
+    }
+
+    /// Lists x86_64 sizes of the launch families, smallest first.
+    pub async fn instance_types(&self) -> Result<Vec<InstanceTypeOption>> {
+        let patterns: Vec<String> = LAUNCH_FAMILIES.iter().map(|f| format!("{}.*", f)).collect();
+        let mut pages = self
+            .client
+            .describe_instance_types()
+            .filters(Filter::builder().name("instance-type").set_values(Some(patterns)).build())
+            .filters(Self::filter("processor-info.supported-architecture", "x86_64"))
+            .into_paginator()
+            .send();
+
+        let mut options = Vec::new();
+        while let Some(page) = pages.next().await {
+            let page = page.map_err(|e| {
+                NimbusError::provider("AWS", format!("Failed to list instance types: {}", e))
+            })?;
+
+            for info in page.instance_types() {
+                let instance_type = match info.instance_type() {
+                    Some(t) => t.as_str().to_string(),
+                    None => continue,
+                };
+
+                options.push(InstanceTypeOption {
+                    instance_type,
+                    vcpus: info.v_cpu_info().and_then(|v| v.default_v_cpus()).unwrap_or(0),
+                    memory_mib: info.memory_info().and_then(|m| m.size_in_mib()).unwrap_or(0),
+                });
+            }
+        }
+
+        options.sort_by_key(|o| (o.vcpus, o.memory_mib));
+        Ok(options)
+    }
+
+    /// Lists the names of the region's SSH key pairs.
+    pub async fn key_pairs(&self) -> Result<Vec<String>> {
+        let response = self.client.describe_key_pairs().send().await.map_err(|e| {
+            NimbusError::provider("AWS", format!("Failed to list key pairs: {}", e))
+        })?;
+
+        let mut names: Vec<String> = response
+            .key_pairs()
+            .iter()
+            .filter_map(|k| k.key_name().map(String::from))
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// Launches one instance and returns its ID.
+    pub async fn launch(&self, spec: &CreateSpec) -> Result<String> {
+        let tags: Vec<Tag> = spec
+            .launch_tags()
+            .into_iter()
+            .map(|(key, value)| Tag::builder().key(key).value(value).build())
+            .collect();
+
+        let mut request = self
+            .client
+            .run_instances()
+            .image_id(&spec.image)
+            .instance_type(InstanceType::from(spec.instance_type.as_str()))
+            .min_count(1)
+            .max_count(1)
+            .set_key_name(spec.key_pair.clone());
+        if !tags.is_empty() {
+            request = request.tag_specifications(
+                TagSpecification::builder()
+                    .resource_type(Ec2ResourceType::Instance)
+                    .set_tags(Some(tags))
+                    .build(),
+            );
+        }
+
+        let response = request.send().await.map_err(|e| {
+            NimbusError::provider(
+                "AWS",
+                format!("Failed to launch {} from {}: {}", spec.instance_type, spec.image, e),
+            )
+        })?;
+
+        response
+            .instances()
+            .first()
+            .and_then(|i| i.instance_id())
+            .map(String::from)
+            .ok_or_else(|| NimbusError::provider("AWS", "RunInstances returned no instance"))
+    }
+
+    async fn describe_images(&self, owner: &str, name_pattern: Option<&str>) -> Result<Vec<Image>> {
+        let mut request = self
+            .client
+            .describe_images()
+            .owners(owner)
+            .filters(Self::filter("architecture", "x86_64"))
+            .filters(Self::filter("state", "available"));
+        if let Some(pattern) = name_pattern {
+            request = request.filters(Self::filter("name", pattern));
+        }
+
+        let response = request.send().await.map_err(|e| {
+            NimbusError::provider("AWS", format!("Failed to list images owned by {}: {}", owner, e))
+        })?;
+        Ok(response.images().to_vec())
+    }
+
+    fn filter(name: &str, value: &str) -> Filter {
+        Filter::builder().name(name).values(value).build()
+    }
+}
+
+/// Sorts images by creation date, newest first. The dates are ISO 8601
+/// strings, so they order correctly as text.
+fn newest_first(images: &mut [Image]) {
+    images.sort_by(|a, b| b.creation_date().cmp(&a.creation_date()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_newest_first() {
+        let image = |id: &str, date: &str| Image::builder().image_id(id).creation_date(date).build();
+        let mut images = vec![
+            image("ami-old", "2024-01-10T08:00:00.000Z"),
+            image("ami-new", "2024-06-02T08:00:00.000Z"),
+            image("ami-mid", "2024-03-15T08:00:00.000Z"),
+        ];
+        newest_first(&mut images);
+
+        let ids: Vec<_> = images.iter().filter_map(|i| i.image_id()).collect();
+        assert_eq!(ids, vec!["ami-new", "ami-mid", "ami-old"]);
+    }
+
+    #[test]
+    fn test_image_option_label() {
+        let option = ImageOption {
+            image_id: "ami-0abc".to_string(),
+            name: "al2023-ami-2023.4".to_string(),
+        };
+        assert_eq!(option.label(), "al2023-ami-2023.4 (ami-0abc)");
+    }
+}
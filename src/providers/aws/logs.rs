@@ -0,0 +1,305 @@
+use crate::core::{LogEntry, ResourceType};
+use crate::error::{NimbusError, Result};
+use aws_sdk_cloudwatchlogs::Client as CloudWatchLogsClient;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+/// Windows tried, narrowest first, when a log view is first opened. A wider
+/// window is only read if the narrower one had too few events.
+const INITIAL_LOOKBACK_MINUTES: [i64; 3] = [1, 5, 30];
+/// Maximum number of events returned by a single fetch.
+const MAX_EVENTS_PER_FETCH: usize = 500;
+/// Maximum number of log groups scanned when looking for agent streams.
+const MAX_AGENT_LOG_GROUPS: usize = 50;
+
+/// A log group, optionally narrowed to specific streams.
+#[derive(Debug, Clone, PartialEq)]
+struct LogSource {
+    group: String,
+    streams: Vec<String>,
+}
+
+/// An event as read from CloudWatch, with its ID if it had one.
+type FetchedEvent = (Option<String>, LogEntry);
+
+/// The newest millisecond returned for a resource and the IDs of the events
+/// in it, so that tailing from that millisecond does not repeat them.
+#[derive(Debug, Default)]
+struct TailPosition {
+    millis: i64,
+    event_ids: HashSet<String>,
+}
+
+/// Reads EC2 instance logs from CloudWatch Logs.
+///
+/// Instances only have logs if the CloudWatch agent ships them, in which case
+/// the agent names streams after the instance ID; those streams are
+/// discovered once and remembered so that tailing does not repeat the scan.
+pub struct AwsLogs {
+    client: CloudWatchLogsClient,
+    sources: Mutex<HashMap<String, Vec<LogSource>>>,
+    tails: Mutex<HashMap<String, TailPosition>>,
+}
+
+impl AwsLogs {
+    pub fn new(client: CloudWatchLogsClient) -> Self {
+        Self {
+            client,
+            sources: Mutex::new(HashMap::new()),
+            tails: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns events newer than `since`, or the latest logs from the last
+    /// few minutes if `since` is `None`. Events are ordered oldest first.
+    pub async fn recent_events(
+        &self,
+        resource_id: &str,
+        resource_type: ResourceType,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<LogEntry>> {
+        let sources = self.sources_for(resource_id, resource_type).await?;
+
+        let mut events = match since {
+            Some(since) => {
+                // CloudWatch filters on an inclusive start time in milliseconds,
+                // so events sharing the last one's millisecond come back again
+                let start = since.timestamp_millis();
+                let events = self.read_sources(&sources, start).await?;
+                let seen = self
+                    .tails
+                    .lock()
+                    .ok()
+                    .and_then(|tails| {
+                        tails
+                            .get(resource_id)
+                            .filter(|tail| tail.millis == start)
+                            .map(|tail| tail.event_ids.clone())
+                    })
+                    .unwrap_or_default();
+                drop_seen(events, start, &seen)
+            }
+            None => {
+                let mut events = Vec::new();
+                for minutes in INITIAL_LOOKBACK_MINUTES {
+                    let start = (Utc::now() - Duration::minutes(minutes)).timestamp_millis();
+                    events = self.read_sources(&sources, start).await?;
+                    if events.len() >= MAX_EVENTS_PER_FETCH {
+                        break;
+                    }
+                }
+                events
+            }
+        };
+
+        keep_newest(&mut events, MAX_EVENTS_PER_FETCH);
+        self.remember_tail(resource_id, &events, since.is_none());
+        Ok(events.into_iter().map(|(_, entry)| entry).collect())
+    }
+
+    async fn read_sources(&self, sources: &[LogSource], start_millis: i64) -> Result<Vec<FetchedEvent>> {
+        let mut events = Vec::new();
+        for source in sources {
+            events.extend(self.filter_events(source, start_millis).await?);
+        }
+        Ok(events)
+    }
+
+    /// Records the newest millisecond returned for a resource. A fresh read
+    /// replaces the old position; a tail adds to it if nothing newer came.
+    fn remember_tail(&self, resource_id: &str, events: &[FetchedEvent], fresh: bool) {
+        let Ok(mut tails) = self.tails.lock() else {
+            return;
+        };
+        if fresh {
+            tails.remove(resource_id);
+        }
+        let Some(millis) = events.last().map(|(_, entry)| entry.timestamp.timestamp_millis()) else {
+            return;
+        };
+
+        let tail = tails.entry(resource_id.to_string()).or_default();
+        if tail.millis != millis {
+            tail.millis = millis;
+            tail.event_ids.clear();
+        }
+        tail.event_ids.extend(
+            events
+                .iter()
+                .filter(|(_, entry)| entry.timestamp.timestamp_millis() == millis)
+                .filter_map(|(id, _)| id.clone()),
+        );
+    }
+
+    async fn sources_for(&self, resource_id: &str, resource_type: ResourceType) -> Result<Vec<LogSource>> {
+        if resource_type != ResourceType::Compute {
+            return Ok(Vec::new());
+        }
+
+        if let Some(sources) = self
+            .sources
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(resource_id).cloned())
+        {
+            return Ok(sources);
+        }
+
+        let sources = self.find_agent_sources(resource_id).await?;
+        if let Ok(mut cache) = self.sources.lock() {
+            cache.insert(resource_id.to_string(), sources.clone());
+        }
+        Ok(sources)
+    }
+
+    /// Finds log streams written by the CloudWatch agent for an instance.
+    async fn find_agent_sources(&self, instance_id: &str) -> Result<Vec<LogSource>> {
+        let mut groups = Vec::new();
+        let mut pages = self.client.describe_log_groups().into_paginator().send();
+
+        while let Some(page) = pages.next().await {
+            let page = page.map_err(|e| {
+                NimbusError::provider("AWS", format!("Failed to list log groups: {}", e))
+            })?;
+
+            groups.extend(
+                page.log_groups()
+                    .iter()
+                    .filter_map(|group| group.log_group_name().map(String::from)),
+            );
+
+            if groups.len() >= MAX_AGENT_LOG_GROUPS {
+                groups.truncate(MAX_AGENT_LOG_GROUPS);
+                break;
+            }
+        }
+
+        let mut sources = Vec::new();
+        for group in groups {
+            let response = self
+                .client
+                .describe_log_streams()
+                .log_group_name(&group)
+                .log_stream_name_prefix(instance_id)
+                .send()
+                .await
+                .map_err(|e| {
+                    NimbusError::provider("AWS", format!("Failed to list log streams for {}: {}", group, e))
+                })?;
+
+            let streams: Vec<String> = response
+                .log_streams()
+                .iter()
+                .filter_map(|stream| stream.log_stream_name().map(String::from))
+                .collect();
+
+            if !streams.is_empty() {
+                sources.push(LogSource { group, streams });
+            }
+        }
+
+        Ok(sources)
+    }
+
+    /// Reads every event in the group from `start_millis` on.
+    async fn filter_events(&self, source: &LogSource, start_millis: i64) -> Result<Vec<FetchedEvent>> {
+        let mut entries = Vec::new();
+        let mut next_token: Option<String> = None;
+
+        loop {
+            let mut request = self
+                .client
+                .filter_log_events()
+                .log_group_name(&source.group)
+                .start_time(start_millis)
+                .set_next_token(next_token.take());
+
+            if !source.streams.is_empty() {
+                request = request.set_log_stream_names(Some(source.streams.clone()));
+            }
+
+            let response = match request.send().await {
+                Ok(response) => response,
+                Err(e) => {
+                    // The agent's group may have been deleted since it was found
+                    if e.as_service_error()
+                        .map(|err| err.is_resource_not_found_exception())
+                        .unwrap_or(false)
+                    {
+                        return Ok(entries);
+                    }
+                    return Err(NimbusError::provider(
+                        "AWS",
+                        format!("Failed to read logs from {}: {}", source.group, e),
+                    ));
+                }
+            };
+
+            for event in response.events() {
+                let timestamp = event
+                    .timestamp()
+                    .and_then(DateTime::from_timestamp_millis)
+                    .unwrap_or_else(Utc::now);
+                let mut entry = LogEntry::new(timestamp, event.message().unwrap_or_default());
+                if let Some(stream) = event.log_stream_name() {
+                    entry = entry.with_source(stream);
+                }
+                entries.push((event.event_id().map(String::from), entry));
+            }
+
+            match response.next_token() {
+                Some(token) => next_token = Some(token.to_string()),
+                None => break,
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+/// Drops events at `start_millis` whose IDs were already returned.
+fn drop_seen(events: Vec<FetchedEvent>, start_millis: i64, seen: &HashSet<String>) -> Vec<FetchedEvent> {
+    events
+        .into_iter()
+        .filter(|(id, entry)| {
+            entry.timestamp.timestamp_millis() != start_millis || !id.as_ref().is_some_and(|id| seen.contains(id))
+        })
+        .collect()
+}
+
+/// Sorts events oldest first and keeps only the newest `max`.
+fn keep_newest(events: &mut Vec<FetchedEvent>, max: usize) {
+    events.sort_by_key(|(_, entry)| entry.timestamp);
+    if events.len() > max {
+        events.drain(..events.len() - max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: &str, millis: i64) -> FetchedEvent {
+        let timestamp = DateTime::from_timestamp_millis(millis).unwrap();
+        (Some(id.to_string()), LogEntry::new(timestamp, id))
+    }
+
+    #[test]
+    fn test_drop_seen() {
+        let seen: HashSet<String> = ["a".to_string()].into_iter().collect();
+        let events = vec![event("a", 1_000), event("b", 1_000), event("c", 1_001)];
+
+        let ids: Vec<_> = drop_seen(events, 1_000, &seen).into_iter().filter_map(|(id, _)| id).collect();
+        assert_eq!(ids, vec!["b", "c"]);
+    }
+
+    #[test]
+    fn test_keep_newest() {
+        let mut events = vec![event("c", 3), event("a", 1), event("b", 2)];
+        keep_newest(&mut events, 2);
+
+        let ids: Vec<_> = events.into_iter().filter_map(|(id, _)| id).collect();
+        assert_eq!(ids, vec!["b", "c"]);
+    }
+}
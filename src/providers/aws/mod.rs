@@ -1,41 +1,129 @@
 use async_trait::async_trait;
 use aws_config::SdkConfig;
 
-use crate::config::AwsConfig;
+use crate::cache::CacheStore;
+use crate::config::{parse_resource_types, AwsConfig, RetryConfig};
 use crate::core::{
-    Action, CloudProvider, CloudResource, CostBreakdown, CostPeriod, Provider, ResourceType,
+    collect_stream, send_batch_if, AccountIdentity, Action, ActionRequest, Budget, CloudProvider, CloudResource,
+    CommitmentCoverage, CostAnomaly, CostBreakdown, CostFeature, CostGranularity, CostPeriod, CostPoint,
+    CreateOptions, CreateSpec, DnsRecord, LogEntry, MetricSeries, ModifyOption, OperationHandle, Provider,
+    ProviderCapabilities, Quota, ResourceHealth, ResourceSender, ResourceType, TagChanges, TargetGroupHealth,
+    TargetHealth, WasteFinding, WasteKind, HOURS_PER_MONTH,
 };
 use crate::error::{NimbusError, Result};
+use futures::stream::{self, StreamExt};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 mod auth;
+mod budgets;
 mod client;
+mod cloudwatch;
+mod commitments;
 mod cost;
+mod health;
+mod launch;
+mod logs;
+mod pricing;
+mod quotas;
+mod resize;
 pub mod resources;
+mod tagging;
+mod waste;
 
 use auth::AwsAuth;
+use budgets::AwsBudgets;
 use client::AwsClient;
+use cloudwatch::AwsCloudWatch;
+use commitments::AwsCommitments;
 use cost::AwsCostExplorer;
-use resources::{EC2Instance, ELBLoadBalancer, RDSInstance, Route53Zone, S3Bucket};
+use health::AwsHealthChecks;
+use launch::Ec2Launcher;
+use logs::AwsLogs;
+use pricing::AwsPricing;
+use quotas::AwsQuotas;
+use resize::Ec2Resizer;
+use tagging::AwsTagger;
+use waste::AwsWasteScanner;
+use resources::{
+    EC2Instance, ELBLoadBalancer, PublicAccessBlock, RDSInstance, Route53Zone, S3Bucket, SecurityGroup,
+};
+
+/// Regions offered before the account's own list has been discovered.
+const FALLBACK_REGIONS: [&str; 15] = [
+    "us-east-1",
+    "us-east-2",
+    "us-west-1",
+    "us-west-2",
+    "eu-west-1",
+    "eu-west-2",
+    "eu-west-3",
+    "eu-central-1",
+    "ap-northeast-1",
+    "ap-northeast-2",
+    "ap-southeast-1",
+    "ap-southeast-2",
+    "ap-south-1",
+    "sa-east-1",
+    "ca-central-1",
+];
+
+/// Hourly base charge of an Application or Network Load Balancer, before
+/// capacity units
+const LOAD_BALANCER_HOURLY_PRICE: f64 = 0.0225;
+
+/// Pricing API lookups run at once while listing, kept low since the API
+/// throttles quickly
+const PRICE_LOOKUP_CONCURRENCY: usize = 4;
 
 pub struct AWSProvider {
     name: String,
     config: AwsConfig,
+    retry: RetryConfig,
     sdk_config: Option<SdkConfig>,
     client: Option<AwsClient>,
     cost_explorer: Option<AwsCostExplorer>,
+    pricing: Option<AwsPricing>,
+    logs: Option<AwsLogs>,
+    price_cache: Option<Arc<Mutex<CacheStore>>>,
+    /// Regions enabled for the account, discovered after authentication
+    discovered_regions: Option<Vec<String>>,
+    identity: Option<AccountIdentity>,
 }
 
 impl AWSProvider {
     pub fn new(config: AwsConfig) -> Self {
+        let name = match config.label {
+            Some(ref label) => format!("AWS ({})", label),
+            None => "AWS".to_string(),
+        };
+
         Self {
-            name: "AWS".to_string(),
+            name,
             config,
+            retry: RetryConfig::default(),
             sdk_config: None,
             client: None,
             cost_explorer: None,
+            pricing: None,
+            logs: None,
+            price_cache: None,
+            discovered_regions: None,
+            identity: None,
         }
     }
 
+    pub fn with_retry_config(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Persists Pricing API lookups in the given cache store.
+    pub fn with_price_cache(mut self, store: CacheStore) -> Self {
+        self.price_cache = Some(Arc::new(Mutex::new(store)));
+        self
+    }
+
     async fn ensure_authenticated(&self) -> Result<()> {
         if self.sdk_config.is_none() {
             return Err(NimbusError::auth(
@@ -55,6 +143,15 @@ impl AWSProvider {
         })
     }
 
+    fn get_logs_reader(&self) -> Result<&AwsLogs> {
+        self.logs.as_ref().ok_or_else(|| {
+            NimbusError::auth(
+                "AWS",
+                "Log reader not initialized. Call authenticate() first.",
+            )
+        })
+    }
+
     fn get_cost_explorer(&self) -> Result<&AwsCostExplorer> {
         self.cost_explorer.as_ref().ok_or_else(|| {
             NimbusError::auth(
@@ -66,46 +163,226 @@ impl AWSProvider {
 
     async fn list_ec2_instances(&self) -> Result<Vec<Box<dyn CloudResource>>> {
         let client = self.get_client()?;
-        let response = client
-            .ec2
-            .describe_instances()
-            .send()
-            .await
-            .map_err(|e| {
+        let mut pages = client.ec2.describe_instances().into_paginator().send();
+
+        let mut instances: Vec<EC2Instance> = Vec::new();
+
+        while let Some(page) = pages.next().await {
+            let response = page.map_err(|e| {
                 NimbusError::provider("AWS", format!("Failed to list EC2 instances: {}", e))
             })?;
 
-        let mut instances: Vec<Box<dyn CloudResource>> = Vec::new();
+            for reservation in response.reservations() {
+                for instance in reservation.instances() {
+                    instances.push(EC2Instance::from_aws_instance(instance, &self.config.region));
+                }
+            }
+        }
+
+        // Instance descriptions only name their security groups, so fetch the
+        // rules for every attached group in one pass
+        let groups = match self.describe_security_groups(&instances).await {
+            Ok(groups) => groups,
+            Err(e) => {
+                log::warn!("Showing EC2 instances without security group rules: {}", e);
+                HashMap::new()
+            }
+        };
+
+        let prices = self.ec2_prices(&instances).await;
+        let priced = instances
+            .into_iter()
+            .map(|instance| {
+                let key = (instance.instance_type().to_string(), instance.pricing_os());
+                let price = prices.get(&key).copied().flatten();
+                Box::new(instance.with_security_group_rules(&groups).with_hourly_price(price)) as Box<dyn CloudResource>
+            })
+            .collect();
+
+        Ok(priced)
+    }
+
+    async fn describe_security_groups(&self, instances: &[EC2Instance]) -> Result<HashMap<String, SecurityGroup>> {
+        let client = self.get_client()?;
+
+        let mut group_ids: Vec<String> = instances
+            .iter()
+            .flat_map(|i| i.security_group_ids())
+            .map(String::from)
+            .collect();
+        group_ids.sort();
+        group_ids.dedup();
+
+        let mut groups = HashMap::new();
+        if group_ids.is_empty() {
+            return Ok(groups);
+        }
+
+        let mut pages = client
+            .ec2
+            .describe_security_groups()
+            .set_group_ids(Some(group_ids))
+            .into_paginator()
+            .send();
+
+        while let Some(page) = pages.next().await {
+            let response = page.map_err(|e| {
+                NimbusError::provider("AWS", format!("Failed to describe security groups: {}", e))
+            })?;
 
-        for reservation in response.reservations() {
-            for instance in reservation.instances() {
-                let ec2_instance = EC2Instance::from_aws_instance(instance, &self.config.region);
-                instances.push(Box::new(ec2_instance));
+            for group in response.security_groups() {
+                let group = SecurityGroup::from_aws_group(group);
+                groups.insert(group.id.clone(), group);
             }
         }
 
-        Ok(instances)
+        Ok(groups)
     }
 
-    async fn list_rds_instances(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+    async fn ec2_termination_protected(&self, instance_id: &str) -> Result<bool> {
         let client = self.get_client()?;
         let response = client
-            .rds
-            .describe_db_instances()
+            .ec2
+            .describe_instance_attribute()
+            .instance_id(instance_id)
+            .attribute(aws_sdk_ec2::types::InstanceAttributeName::DisableApiTermination)
             .send()
             .await
             .map_err(|e| {
-                NimbusError::provider("AWS", format!("Failed to list RDS instances: {}", e))
+                NimbusError::provider(
+                    "AWS",
+                    format!("Failed to check termination protection on EC2 instance {}: {}", instance_id, e),
+                )
             })?;
 
-        let mut instances: Vec<Box<dyn CloudResource>> = Vec::new();
+        Ok(response
+            .disable_api_termination()
+            .and_then(|attr| attr.value())
+            .unwrap_or(false))
+    }
+
+    /// Looks up the authenticated principal and the account alias. The alias
+    /// is optional since listing it needs an IAM permission many roles lack.
+    async fn fetch_identity(&self) -> Result<AccountIdentity> {
+        let client = self.get_client()?;
+        let caller = client.sts.get_caller_identity().send().await.map_err(|e| {
+            NimbusError::provider("AWS", format!("Failed to get caller identity: {}", e))
+        })?;
+
+        let alias = match client.iam.list_account_aliases().send().await {
+            Ok(response) => response.account_aliases().first().cloned(),
+            Err(e) => {
+                log::debug!("Account alias unavailable: {}", e);
+                None
+            }
+        };
+
+        Ok(AccountIdentity::new(
+            caller.account().unwrap_or_default(),
+            caller.arn().unwrap_or_default(),
+        )
+        .with_alias(alias))
+    }
+
+    /// Lists the regions enabled for the account, sorted by name.
+    async fn discover_regions(&self) -> Result<Vec<String>> {
+        let client = self.get_client()?;
+        let response = client.ec2.describe_regions().send().await.map_err(|e| {
+            NimbusError::provider("AWS", format!("Failed to list regions: {}", e))
+        })?;
+
+        let mut regions: Vec<String> = response
+            .regions()
+            .iter()
+            .filter_map(|r| r.region_name().map(String::from))
+            .collect();
+        regions.sort();
+        Ok(regions)
+    }
+
+    async fn price_ec2_instance(&self, instance: EC2Instance) -> EC2Instance {
+        let price = match self.pricing {
+            Some(ref pricing) => {
+                pricing
+                    .ec2_hourly_price(&self.config.region, instance.instance_type(), instance.pricing_os())
+                    .await
+            }
+            None => None,
+        };
+        instance.with_hourly_price(price)
+    }
+
+    /// Looks up the price of each distinct instance type and OS among
+    /// `instances`, several at a time.
+    async fn ec2_prices(&self, instances: &[EC2Instance]) -> HashMap<(String, &'static str), Option<f64>> {
+        let Some(ref pricing) = self.pricing else {
+            return HashMap::new();
+        };
+
+        let mut keys: Vec<(String, &'static str)> = instances
+            .iter()
+            .map(|instance| (instance.instance_type().to_string(), instance.pricing_os()))
+            .collect();
+        keys.sort();
+        keys.dedup();
+
+        stream::iter(keys)
+            .map(|(instance_type, os)| async move {
+                let price = pricing.ec2_hourly_price(&self.config.region, &instance_type, os).await;
+                ((instance_type, os), price)
+            })
+            .buffer_unordered(PRICE_LOOKUP_CONCURRENCY)
+            .collect()
+            .await
+    }
+
+    /// Looks up the price of each distinct instance class, engine, and
+    /// deployment among `instances`, several at a time.
+    async fn rds_prices(&self, instances: &[RDSInstance]) -> HashMap<(String, String, bool), Option<f64>> {
+        let Some(ref pricing) = self.pricing else {
+            return HashMap::new();
+        };
+
+        let mut keys: Vec<(String, String, bool)> = instances.iter().map(rds_price_key).collect();
+        keys.sort();
+        keys.dedup();
+
+        stream::iter(keys)
+            .map(|(instance_class, engine, multi_az)| async move {
+                let price = pricing
+                    .rds_hourly_price(&self.config.region, &instance_class, &engine, multi_az)
+                    .await;
+                ((instance_class, engine, multi_az), price)
+            })
+            .buffer_unordered(PRICE_LOOKUP_CONCURRENCY)
+            .collect()
+            .await
+    }
+
+    async fn list_rds_instances(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let client = self.get_client()?;
+        let mut pages = client.rds.describe_db_instances().into_paginator().send();
+
+        let mut instances: Vec<RDSInstance> = Vec::new();
+
+        while let Some(page) = pages.next().await {
+            let response = page.map_err(|e| {
+                NimbusError::provider("AWS", format!("Failed to list RDS instances: {}", e))
+            })?;
 
-        for db_instance in response.db_instances() {
-            let rds_instance = RDSInstance::from_aws_instance(db_instance, &self.config.region);
-            instances.push(Box::new(rds_instance));
+            for db_instance in response.db_instances() {
+                instances.push(RDSInstance::from_aws_instance(db_instance, &self.config.region));
+            }
         }
 
-        Ok(instances)
+        let prices = self.rds_prices(&instances).await;
+        Ok(instances
+            .into_iter()
+            .map(|instance| {
+                let price = prices.get(&rds_price_key(&instance)).copied().flatten();
+                Box::new(instance.with_hourly_price(price)) as Box<dyn CloudResource>
+            })
+            .collect())
     }
 
     async fn list_s3_buckets(&self) -> Result<Vec<Box<dyn CloudResource>>> {
@@ -114,6 +391,22 @@ impl AWSProvider {
             NimbusError::provider("AWS", format!("Failed to list S3 buckets: {}", e))
         })?;
 
+        let bucket_names: Vec<String> = response
+            .buckets()
+            .iter()
+            .filter_map(|bucket| bucket.name().map(|name| name.to_string()))
+            .collect();
+
+        let metrics = if self.config.s3_metrics && !bucket_names.is_empty() {
+            let cloudwatch = AwsCloudWatch::new(client.cloudwatch.clone());
+            cloudwatch.bucket_metrics(&bucket_names).await.unwrap_or_else(|e| {
+                log::warn!("Skipping S3 bucket metrics: {}", e);
+                Default::default()
+            })
+        } else {
+            Default::default()
+        };
+
         let mut buckets: Vec<Box<dyn CloudResource>> = Vec::new();
 
         for bucket in response.buckets() {
@@ -125,13 +418,20 @@ impl AWSProvider {
                 });
 
                 let tags = self.get_bucket_tags(name).await.unwrap_or_default();
+                let (public_access_block, policy_is_public) = self.get_bucket_public_access(name).await;
 
-                let s3_bucket = S3Bucket::new(
+                let mut s3_bucket = S3Bucket::new(
                     name.to_string(),
                     self.config.region.clone(),
                     created_at,
                     tags,
-                );
+                )
+                .with_public_access(public_access_block, policy_is_public);
+
+                if let Some(size_bytes) = metrics.get(name).and_then(|m| m.size_bytes) {
+                    let object_count = metrics.get(name).and_then(|m| m.object_count).unwrap_or(0);
+                    s3_bucket = s3_bucket.with_size_info(size_bytes, object_count);
+                }
 
                 buckets.push(Box::new(s3_bucket));
             }
@@ -140,6 +440,55 @@ impl AWSProvider {
         Ok(buckets)
     }
 
+    /// Fetches the bucket's Public Access Block and whether its policy is public.
+    ///
+    /// Either half is `None` when it could not be read; a bucket without a
+    /// policy is reported as not public.
+    async fn get_bucket_public_access(&self, bucket_name: &str) -> (Option<PublicAccessBlock>, Option<bool>) {
+        use aws_sdk_s3::error::ProvideErrorMetadata;
+
+        let client = match self.get_client() {
+            Ok(client) => client,
+            Err(_) => return (None, None),
+        };
+
+        let (block, policy) = tokio::join!(
+            client.s3.get_public_access_block().bucket(bucket_name).send(),
+            client.s3.get_bucket_policy_status().bucket(bucket_name).send(),
+        );
+
+        let public_access_block = match block {
+            Ok(response) => response.public_access_block_configuration().map(|config| PublicAccessBlock {
+                block_public_acls: config.block_public_acls().unwrap_or(false),
+                ignore_public_acls: config.ignore_public_acls().unwrap_or(false),
+                block_public_policy: config.block_public_policy().unwrap_or(false),
+                restrict_public_buckets: config.restrict_public_buckets().unwrap_or(false),
+            }),
+            Err(e) if e.as_service_error().and_then(|err| err.code())
+                == Some("NoSuchPublicAccessBlockConfiguration") =>
+            {
+                Some(PublicAccessBlock::default())
+            }
+            Err(e) => {
+                log::debug!("Could not read public access block for {}: {}", bucket_name, e);
+                None
+            }
+        };
+
+        let policy_is_public = match policy {
+            Ok(response) => response.policy_status().and_then(|status| status.is_public()),
+            Err(e) if e.as_service_error().and_then(|err| err.code()) == Some("NoSuchBucketPolicy") => {
+                Some(false)
+            }
+            Err(e) => {
+                log::debug!("Could not read policy status for {}: {}", bucket_name, e);
+                None
+            }
+        };
+
+        (public_access_block, policy_is_public)
+    }
+
     async fn get_bucket_tags(&self, bucket_name: &str) -> Result<std::collections::HashMap<String, String>> {
         let client = self.get_client()?;
         
@@ -158,32 +507,96 @@ impl AWSProvider {
 
     async fn list_load_balancers(&self) -> Result<Vec<Box<dyn CloudResource>>> {
         let client = self.get_client()?;
-        let response = client
-            .elb
-            .describe_load_balancers()
-            .send()
-            .await
-            .map_err(|e| {
+        let mut pages = client.elb.describe_load_balancers().into_paginator().send();
+
+        let mut load_balancers: Vec<Box<dyn CloudResource>> = Vec::new();
+
+        while let Some(page) = pages.next().await {
+            let response = page.map_err(|e| {
                 NimbusError::provider("AWS", format!("Failed to list load balancers: {}", e))
             })?;
 
-        let mut load_balancers: Vec<Box<dyn CloudResource>> = Vec::new();
+            for lb in response.load_balancers() {
+                let elb = ELBLoadBalancer::from_aws_lb(lb, &self.config.region);
 
-        for lb in response.load_balancers() {
-            let elb = ELBLoadBalancer::from_aws_lb(lb, &self.config.region);
-            
-            if let Some(arn) = lb.load_balancer_arn() {
-                let tags = self.get_lb_tags(arn).await.unwrap_or_default();
-                load_balancers.push(Box::new(elb.with_tags(tags)));
-            } else {
-                load_balancers.push(Box::new(elb));
+                if let Some(arn) = lb.load_balancer_arn() {
+                    let tags = self.get_lb_tags(arn).await.unwrap_or_default();
+                    load_balancers.push(Box::new(elb.with_tags(tags)));
+                } else {
+                    load_balancers.push(Box::new(elb));
+                }
             }
         }
 
         Ok(load_balancers)
     }
 
-    async fn get_lb_tags(&self, lb_arn: &str) -> Result<std::collections::HashMap<String, String>> {
+    async fn describe_target_health(&self, lb_arn: &str) -> Result<Vec<TargetGroupHealth>> {
+        let client = self.get_client()?;
+        let mut pages = client
+            .elb
+            .describe_target_groups()
+            .load_balancer_arn(lb_arn)
+            .into_paginator()
+            .send();
+
+        let mut groups = Vec::new();
+        while let Some(page) = pages.next().await {
+            let response = page.map_err(|e| {
+                NimbusError::provider("AWS", format!("Failed to list target groups for {}: {}", lb_arn, e))
+            })?;
+
+            for target_group in response.target_groups() {
+                let group_arn = match target_group.target_group_arn() {
+                    Some(arn) => arn,
+                    None => continue,
+                };
+
+                let health = client
+                    .elb
+                    .describe_target_health()
+                    .target_group_arn(group_arn)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        NimbusError::provider(
+                            "AWS",
+                            format!("Failed to get target health for {}: {}", group_arn, e),
+                        )
+                    })?;
+
+                let targets = health
+                    .target_health_descriptions()
+                    .iter()
+                    .map(|desc| {
+                        let target_health = desc.target_health();
+                        TargetHealth {
+                            id: desc.target().and_then(|t| t.id()).unwrap_or("unknown").to_string(),
+                            port: desc.target().and_then(|t| t.port()),
+                            state: target_health
+                                .and_then(|h| h.state())
+                                .map(|s| s.as_str().to_string())
+                                .unwrap_or_else(|| "unknown".to_string()),
+                            reason: target_health
+                                .and_then(|h| h.description())
+                                .map(String::from),
+                        }
+                    })
+                    .collect();
+
+                groups.push(TargetGroupHealth {
+                    name: target_group.target_group_name().unwrap_or("Unknown").to_string(),
+                    protocol: target_group.protocol().map(|p| p.as_str().to_string()),
+                    port: target_group.port(),
+                    targets,
+                });
+            }
+        }
+
+        Ok(groups)
+    }
+
+    async fn get_lb_tags(&self, lb_arn: &str) -> Result<HashMap<String, String>> {
         let client = self.get_client()?;
         
         match client.elb.describe_tags().resource_arns(lb_arn).send().await {
@@ -206,29 +619,108 @@ impl AWSProvider {
     }
 
     async fn list_route53_zones(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let client = self.get_client()?;
+        let mut zones: Vec<Box<dyn CloudResource>> = Vec::new();
+        let mut marker: Option<String> = None;
+
+        loop {
+            let response = client
+                .route53
+                .list_hosted_zones()
+                .set_marker(marker.take())
+                .send()
+                .await
+                .map_err(|e| {
+                    NimbusError::provider("AWS", format!("Failed to list Route53 zones: {}", e))
+                })?;
+
+            for zone in response.hosted_zones() {
+                let route53_zone = Route53Zone::from_aws_zone(zone, "global");
+                let zone_id = zone.id();
+                let tags = self.get_zone_tags(zone_id).await.unwrap_or_default();
+                zones.push(Box::new(route53_zone.with_tags(tags)));
+            }
+
+            match response.next_marker() {
+                Some(next) if response.is_truncated() => marker = Some(next.to_string()),
+                _ => break,
+            }
+        }
+
+        Ok(zones)
+    }
+
+    /// Returns true if a bucket holds any object, object version, or delete
+    /// marker, any of which makes `DeleteBucket` fail.
+    async fn bucket_has_objects(&self, bucket_name: &str) -> Result<bool> {
         let client = self.get_client()?;
         let response = client
-            .route53
-            .list_hosted_zones()
+            .s3
+            .list_object_versions()
+            .bucket(bucket_name)
+            .max_keys(1)
             .send()
             .await
             .map_err(|e| {
-                NimbusError::provider("AWS", format!("Failed to list Route53 zones: {}", e))
+                NimbusError::provider("AWS", format!("Failed to list objects in S3 bucket {}: {}", bucket_name, e))
             })?;
 
-        let mut zones: Vec<Box<dyn CloudResource>> = Vec::new();
+        Ok(!response.versions().is_empty() || !response.delete_markers().is_empty())
+    }
+
+    async fn list_record_sets(&self, zone_id: &str) -> Result<Vec<DnsRecord>> {
+        let client = self.get_client()?;
+        let zone_id = zone_id.trim_start_matches("/hostedzone/");
+        let mut records = Vec::new();
+        let mut start: Option<(String, Option<aws_sdk_route53::types::RrType>, Option<String>)> = None;
+
+        loop {
+            let mut request = client.route53.list_resource_record_sets().hosted_zone_id(zone_id);
+            if let Some((name, record_type, identifier)) = start.take() {
+                request = request
+                    .start_record_name(name)
+                    .set_start_record_type(record_type)
+                    .set_start_record_identifier(identifier);
+            }
 
-        for zone in response.hosted_zones() {
-            let route53_zone = Route53Zone::from_aws_zone(zone, "global");
-            let zone_id = zone.id();
-            let tags = self.get_zone_tags(zone_id).await.unwrap_or_default();
-            zones.push(Box::new(route53_zone.with_tags(tags)));
+            let response = request.send().await.map_err(|e| {
+                NimbusError::provider("AWS", format!("Failed to list records for zone {}: {}", zone_id, e))
+            })?;
+
+            for record_set in response.resource_record_sets() {
+                let values = match record_set.alias_target() {
+                    Some(alias) => vec![format!("ALIAS {}", alias.dns_name())],
+                    None => record_set
+                        .resource_records()
+                        .iter()
+                        .map(|record| record.value().to_string())
+                        .collect(),
+                };
+
+                records.push(DnsRecord {
+                    name: record_set.name().to_string(),
+                    record_type: record_set.r#type().as_str().to_string(),
+                    ttl: record_set.ttl(),
+                    values,
+                });
+            }
+
+            match response.next_record_name() {
+                Some(name) if response.is_truncated() => {
+                    start = Some((
+                        name.to_string(),
+                        response.next_record_type().cloned(),
+                        response.next_record_identifier().map(String::from),
+                    ));
+                }
+                _ => break,
+            }
         }
 
-        Ok(zones)
+        Ok(records)
     }
 
-    async fn get_zone_tags(&self, zone_id: &str) -> Result<std::collections::HashMap<String, String>> {
+    async fn get_zone_tags(&self, zone_id: &str) -> Result<HashMap<String, String>> {
         let client = self.get_client()?;
         
         match client.route53.list_tags_for_resource()
@@ -257,6 +749,448 @@ impl AWSProvider {
             Err(_) => Ok(std::collections::HashMap::new()),
         }
     }
+
+    /// Starts or stops several EC2 instances with a single API call.
+    async fn execute_ec2_batch(&self, instance_ids: &[String], action: Action) -> Result<()> {
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+        let ids = Some(instance_ids.to_vec());
+
+        let result = match action {
+            Action::Start => client
+                .ec2
+                .start_instances()
+                .set_instance_ids(ids)
+                .send()
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Action::Stop => client
+                .ec2
+                .stop_instances()
+                .set_instance_ids(ids)
+                .send()
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            other => return Err(NimbusError::UnsupportedAction(other, ResourceType::Compute)),
+        };
+
+        result.map_err(|e| {
+            NimbusError::provider(
+                "AWS",
+                format!("Failed to {} {} EC2 instances: {}", action.as_str().to_lowercase(), instance_ids.len(), e),
+            )
+        })
+    }
+
+    async fn execute_ec2_action(&self, resource_id: &str, action: Action) -> Result<()> {
+        let client = self.get_client()?;
+
+        match action {
+            Action::Start => {
+                client
+                    .ec2
+                    .start_instances()
+                    .instance_ids(resource_id)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        let error_msg = e.to_string();
+                        if error_msg.contains("InvalidInstanceID") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("EC2 instance {} not found. It may have been terminated.", resource_id),
+                            )
+                        } else if error_msg.contains("IncorrectInstanceState") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("EC2 instance {} is not in a state where it can be started. Wait a moment and try again.", resource_id),
+                            )
+                        } else {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("Failed to start EC2 instance {}: {}", resource_id, error_msg),
+                            )
+                        }
+                    })?;
+                Ok(())
+            }
+            Action::Stop => {
+                client
+                    .ec2
+                    .stop_instances()
+                    .instance_ids(resource_id)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        let error_msg = e.to_string();
+                        if error_msg.contains("InvalidInstanceID") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("EC2 instance {} not found. It may have been terminated.", resource_id),
+                            )
+                        } else if error_msg.contains("IncorrectInstanceState") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("EC2 instance {} is not in a state where it can be stopped. Wait a moment and try again.", resource_id),
+                            )
+                        } else {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("Failed to stop EC2 instance {}: {}", resource_id, error_msg),
+                            )
+                        }
+                    })?;
+                Ok(())
+            }
+            Action::Hibernate => {
+                client
+                    .ec2
+                    .stop_instances()
+                    .instance_ids(resource_id)
+                    .hibernate(true)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        let error_msg = e.to_string();
+                        if error_msg.contains("InvalidInstanceID") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("EC2 instance {} not found. It may have been terminated.", resource_id),
+                            )
+                        } else if error_msg.contains("UnsupportedHibernationConfiguration") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("EC2 instance {} is not configured for hibernation. Stop it instead.", resource_id),
+                            )
+                        } else if error_msg.contains("IncorrectInstanceState") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("EC2 instance {} is not in a state where it can be hibernated. Wait a moment and try again.", resource_id),
+                            )
+                        } else {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("Failed to hibernate EC2 instance {}: {}", resource_id, error_msg),
+                            )
+                        }
+                    })?;
+                Ok(())
+            }
+            Action::Restart => {
+                client
+                    .ec2
+                    .reboot_instances()
+                    .instance_ids(resource_id)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        let error_msg = e.to_string();
+                        if error_msg.contains("InvalidInstanceID") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("EC2 instance {} not found. It may have been terminated.", resource_id),
+                            )
+                        } else if error_msg.contains("IncorrectInstanceState") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("EC2 instance {} must be running to restart. Start it first.", resource_id),
+                            )
+                        } else {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("Failed to restart EC2 instance {}: {}", resource_id, error_msg),
+                            )
+                        }
+                    })?;
+                Ok(())
+            }
+            Action::Terminate => {
+                // Termination protection fails the call with a generic
+                // OperationNotPermitted, so check for it up front
+                if self.ec2_termination_protected(resource_id).await? {
+                    return Err(NimbusError::TerminationProtected(resource_id.to_string()));
+                }
+
+                client
+                    .ec2
+                    .terminate_instances()
+                    .instance_ids(resource_id)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        let error_msg = e.to_string();
+                        if error_msg.contains("InvalidInstanceID") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("EC2 instance {} not found. It may already be terminated.", resource_id),
+                            )
+                        } else {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("Failed to terminate EC2 instance {}: {}", resource_id, error_msg),
+                            )
+                        }
+                    })?;
+                Ok(())
+            }
+            _ => Err(NimbusError::UnsupportedAction(action, ResourceType::Compute)),
+        }
+    }
+
+    async fn execute_rds_action(&self, resource_id: &str, request: &ActionRequest) -> Result<()> {
+        let client = self.get_client()?;
+
+        match request.action {
+            Action::Start => {
+                client
+                    .rds
+                    .start_db_instance()
+                    .db_instance_identifier(resource_id)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        let error_msg = e.to_string();
+                        if error_msg.contains("DBInstanceNotFound") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("RDS instance {} not found. It may have been deleted.", resource_id),
+                            )
+                        } else if error_msg.contains("InvalidDBInstanceState") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("RDS instance {} is not in a state where it can be started. Wait a moment and try again.", resource_id),
+                            )
+                        } else {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("Failed to start RDS instance {}: {}", resource_id, error_msg),
+                            )
+                        }
+                    })?;
+                Ok(())
+            }
+            Action::Stop => {
+                client
+                    .rds
+                    .stop_db_instance()
+                    .db_instance_identifier(resource_id)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        let error_msg = e.to_string();
+                        if error_msg.contains("DBInstanceNotFound") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("RDS instance {} not found. It may have been deleted.", resource_id),
+                            )
+                        } else if error_msg.contains("InvalidDBInstanceState") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("RDS instance {} is not in a state where it can be stopped. Wait a moment and try again.", resource_id),
+                            )
+                        } else {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("Failed to stop RDS instance {}: {}", resource_id, error_msg),
+                            )
+                        }
+                    })?;
+                Ok(())
+            }
+            Action::Restart => {
+                client
+                    .rds
+                    .reboot_db_instance()
+                    .db_instance_identifier(resource_id)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        let error_msg = e.to_string();
+                        if error_msg.contains("DBInstanceNotFound") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("RDS instance {} not found. It may have been deleted.", resource_id),
+                            )
+                        } else if error_msg.contains("InvalidDBInstanceState") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("RDS instance {} must be available to restart.", resource_id),
+                            )
+                        } else {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("Failed to restart RDS instance {}: {}", resource_id, error_msg),
+                            )
+                        }
+                    })?;
+                Ok(())
+            }
+            Action::Snapshot => {
+                let snapshot_id = match request.params.snapshot_name {
+                    Some(ref name) => name.clone(),
+                    None => rds_snapshot_identifier(resource_id, chrono::Utc::now()),
+                };
+                client
+                    .rds
+                    .create_db_snapshot()
+                    .db_instance_identifier(resource_id)
+                    .db_snapshot_identifier(&snapshot_id)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        let error_msg = e.to_string();
+                        if error_msg.contains("InvalidDBInstanceState") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("RDS instance {} must be available to take a snapshot.", resource_id),
+                            )
+                        } else {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("Failed to snapshot RDS instance {}: {}", resource_id, error_msg),
+                            )
+                        }
+                    })?;
+                Ok(())
+            }
+            Action::Terminate => {
+                client
+                    .rds
+                    .delete_db_instance()
+                    .db_instance_identifier(resource_id)
+                    .skip_final_snapshot(true)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        let error_msg = e.to_string();
+                        if error_msg.contains("DBInstanceNotFound") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("RDS instance {} not found. It may already be deleted.", resource_id),
+                            )
+                        } else if error_msg.contains("InvalidDBInstanceState") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("RDS instance {} cannot be deleted in its current state. Stop it first or wait for pending operations to complete.", resource_id),
+                            )
+                        } else {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("Failed to terminate RDS instance {}: {}", resource_id, error_msg),
+                            )
+                        }
+                    })?;
+                Ok(())
+            }
+            action => Err(NimbusError::UnsupportedAction(action, ResourceType::Database)),
+        }
+    }
+
+    async fn execute_elb_action(&self, lb_arn: &str, action: Action) -> Result<()> {
+        let client = self.get_client()?;
+
+        match action {
+            Action::Terminate => {
+                client
+                    .elb
+                    .delete_load_balancer()
+                    .load_balancer_arn(lb_arn)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        let error_msg = e.to_string();
+                        if error_msg.contains("OperationNotPermitted") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("Load balancer {} has deletion protection enabled.", lb_arn),
+                            )
+                        } else {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("Failed to delete load balancer {}: {}", lb_arn, error_msg),
+                            )
+                        }
+                    })?;
+                Ok(())
+            }
+            _ => Err(NimbusError::UnsupportedAction(action, ResourceType::LoadBalancer)),
+        }
+    }
+
+    async fn execute_s3_action(&self, bucket_name: &str, action: Action) -> Result<()> {
+        let client = self.get_client()?;
+
+        match action {
+            Action::Terminate => {
+                client
+                    .s3
+                    .delete_bucket()
+                    .bucket(bucket_name)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        let error_msg = e.to_string();
+                        if error_msg.contains("NoSuchBucket") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("S3 bucket {} not found. It may already be deleted.", bucket_name),
+                            )
+                        } else if error_msg.contains("BucketNotEmpty") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("S3 bucket {} is not empty. Delete its objects first.", bucket_name),
+                            )
+                        } else {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("Failed to delete S3 bucket {}: {}", bucket_name, error_msg),
+                            )
+                        }
+                    })?;
+                Ok(())
+            }
+            _ => Err(NimbusError::UnsupportedAction(action, ResourceType::Storage)),
+        }
+    }
+
+    async fn execute_route53_action(&self, zone_id: &str, action: Action) -> Result<()> {
+        let client = self.get_client()?;
+        let zone_id = zone_id.trim_start_matches("/hostedzone/");
+
+        match action {
+            Action::Terminate => {
+                client
+                    .route53
+                    .delete_hosted_zone()
+                    .id(zone_id)
+                    .send()
+                    .await
+                    .map_err(|e| {
+                        let error_msg = e.to_string();
+                        if error_msg.contains("NoSuchHostedZone") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("Route53 zone {} not found. It may already be deleted.", zone_id),
+                            )
+                        } else if error_msg.contains("HostedZoneNotEmpty") {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("Route53 zone {} still has records. Delete all records except SOA and NS first.", zone_id),
+                            )
+                        } else {
+                            NimbusError::provider(
+                                "AWS",
+                                format!("Failed to delete Route53 zone {}: {}", zone_id, error_msg),
+                            )
+                        }
+                    })?;
+                Ok(())
+            }
+            _ => Err(NimbusError::UnsupportedAction(action, ResourceType::DNS)),
+        }
+    }
 }
 
 #[async_trait]
@@ -269,21 +1203,76 @@ impl CloudProvider for AWSProvider {
         Provider::AWS
     }
 
+    fn account_name(&self) -> &str {
+        self.config.display_name()
+    }
+
     async fn authenticate(&mut self) -> Result<()> {
-        let sdk_config = AwsAuth::create_config(&self.config).await?;
+        let sdk_config = AwsAuth::create_config(&self.config, &self.retry).await?;
 
         AwsAuth::test_credentials(&sdk_config).await?;
 
         let client = AwsClient::new(&sdk_config);
         let cost_explorer = AwsCostExplorer::new(client.cost_explorer.clone());
+        let pricing = AwsPricing::new(&sdk_config, self.price_cache.clone());
+        let logs = AwsLogs::new(client.logs.clone());
 
         self.sdk_config = Some(sdk_config);
         self.client = Some(client);
         self.cost_explorer = Some(cost_explorer);
+        self.pricing = Some(pricing);
+        self.logs = Some(logs);
+
+        match self.fetch_identity().await {
+            Ok(identity) => self.identity = Some(identity),
+            Err(e) => log::warn!("Could not determine AWS caller identity: {}", e),
+        }
+
+        // The region list is account-wide, so switching regions reuses it
+        if self.discovered_regions.is_none() {
+            match self.discover_regions().await {
+                Ok(regions) if !regions.is_empty() => self.discovered_regions = Some(regions),
+                Ok(_) => {}
+                Err(e) => log::warn!("Using built-in AWS region list: {}", e),
+            }
+        }
 
         Ok(())
     }
 
+    fn identity(&self) -> Option<AccountIdentity> {
+        self.identity.clone()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities::none()
+            .with_resource_types(&[
+                ResourceType::Compute,
+                ResourceType::Database,
+                ResourceType::Storage,
+                ResourceType::LoadBalancer,
+                ResourceType::DNS,
+            ])
+            .restrict_resource_types(&parse_resource_types(&self.config.resource_types).unwrap_or_default())
+            .with_actions(&[
+                Action::Start,
+                Action::Stop,
+                Action::Hibernate,
+                Action::Restart,
+                Action::Terminate,
+                Action::ViewLogs,
+                Action::ViewRecords,
+                Action::Modify,
+                Action::Connect,
+                Action::Snapshot,
+                Action::CreateImage,
+                Action::Tag,
+                Action::Create,
+            ])
+            .with_cost_features(&CostFeature::all())
+            .with_metrics(true)
+    }
+
     async fn test_connection(&self) -> Result<bool> {
         self.ensure_authenticated().await?;
         let client = self.get_client()?;
@@ -298,37 +1287,31 @@ impl CloudProvider for AWSProvider {
     }
 
     async fn list_all_resources(&self) -> Result<Vec<Box<dyn CloudResource>>> {
-        self.ensure_authenticated().await?;
-
-        let mut all_resources: Vec<Box<dyn CloudResource>> = Vec::new();
-
-        if let Ok(ec2) = self.list_ec2_instances().await {
-            all_resources.extend(ec2);
-        }
-
-        if let Ok(rds) = self.list_rds_instances().await {
-            all_resources.extend(rds);
-        }
-
-        if let Ok(s3) = self.list_s3_buckets().await {
-            all_resources.extend(s3);
-        }
+        collect_stream(self).await
+    }
 
-        if let Ok(elb) = self.list_load_balancers().await {
-            all_resources.extend(elb);
-        }
+    async fn stream_all_resources(&self, sender: ResourceSender) -> Result<()> {
+        self.ensure_authenticated().await?;
+        let caps = self.capabilities();
 
-        if let Ok(route53) = self.list_route53_zones().await {
-            all_resources.extend(route53);
-        }
+        tokio::join!(
+            send_batch_if(&sender, &caps, ResourceType::Compute, "EC2", self.list_ec2_instances()),
+            send_batch_if(&sender, &caps, ResourceType::Database, "RDS", self.list_rds_instances()),
+            send_batch_if(&sender, &caps, ResourceType::Storage, "S3", self.list_s3_buckets()),
+            send_batch_if(&sender, &caps, ResourceType::LoadBalancer, "ELB", self.list_load_balancers()),
+            send_batch_if(&sender, &caps, ResourceType::DNS, "Route 53", self.list_route53_zones()),
+        );
 
-        Ok(all_resources)
+        Ok(())
     }
 
     async fn list_resources_by_type(
         &self,
         resource_type: ResourceType,
     ) -> Result<Vec<Box<dyn CloudResource>>> {
+        if !self.capabilities().supports_type(resource_type) {
+            return Ok(Vec::new());
+        }
         self.ensure_authenticated().await?;
 
         match resource_type {
@@ -361,7 +1344,7 @@ impl CloudProvider for AWSProvider {
                     if instance.instance_id() == Some(id) {
                         let ec2_instance =
                             EC2Instance::from_aws_instance(instance, &self.config.region);
-                        return Ok(Box::new(ec2_instance));
+                        return Ok(Box::new(self.price_ec2_instance(ec2_instance).await));
                     }
                 }
             }
@@ -371,239 +1354,303 @@ impl CloudProvider for AWSProvider {
     }
 
     // CHANGES: Added RDS start/stop/restart/terminate support and improved error messages
-    async fn execute_action(&self, resource_id: &str, action: Action) -> Result<()> {
+    async fn execute_action(
+        &self,
+        resource_id: &str,
+        resource_type: ResourceType,
+        request: &ActionRequest,
+    ) -> Result<OperationHandle> {
         self.ensure_authenticated().await?;
-        let client = self.get_client()?;
+        let action = request.action;
 
-        if resource_id.starts_with("i-") {
-            match action {
-                Action::Start => {
-                    client
-                        .ec2
-                        .start_instances()
-                        .instance_ids(resource_id)
-                        .send()
-                        .await
-                        .map_err(|e| {
-                            let error_msg = e.to_string();
-                            if error_msg.contains("InvalidInstanceID") {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("EC2 instance {} not found. It may have been terminated.", resource_id),
-                                )
-                            } else if error_msg.contains("IncorrectInstanceState") {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("EC2 instance {} is not in a state where it can be started. Wait a moment and try again.", resource_id),
-                                )
-                            } else {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("Failed to start EC2 instance {}: {}", resource_id, error_msg),
-                                )
-                            }
-                        })?;
-                    Ok(())
-                }
-                Action::Stop => {
-                    client
-                        .ec2
-                        .stop_instances()
-                        .instance_ids(resource_id)
-                        .send()
-                        .await
-                        .map_err(|e| {
-                            let error_msg = e.to_string();
-                            if error_msg.contains("InvalidInstanceID") {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("EC2 instance {} not found. It may have been terminated.", resource_id),
-                                )
-                            } else if error_msg.contains("IncorrectInstanceState") {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("EC2 instance {} is not in a state where it can be stopped. Wait a moment and try again.", resource_id),
-                                )
-                            } else {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("Failed to stop EC2 instance {}: {}", resource_id, error_msg),
-                                )
-                            }
-                        })?;
-                    Ok(())
-                }
-                Action::Restart => {
-                    client
-                        .ec2
-                        .reboot_instances()
-                        .instance_ids(resource_id)
-                        .send()
-                        .await
-                        .map_err(|e| {
-                            let error_msg = e.to_string();
-                            if error_msg.contains("InvalidInstanceID") {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("EC2 instance {} not found. It may have been terminated.", resource_id),
-                                )
-                            } else if error_msg.contains("IncorrectInstanceState") {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("EC2 instance {} must be running to restart. Start it first.", resource_id),
-                                )
-                            } else {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("Failed to restart EC2 instance {}: {}", resource_id, error_msg),
-                                )
-                            }
-                        })?;
-                    Ok(())
-                }
-                Action::Terminate => {
-                    client
-                        .ec2
-                        .terminate_instances()
-                        .instance_ids(resource_id)
-                        .send()
-                        .await
-                        .map_err(|e| {
-                            let error_msg = e.to_string();
-                            if error_msg.contains("InvalidInstanceID") {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("EC2 instance {} not found. It may already be terminated.", resource_id),
-                                )
-                            } else {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("Failed to terminate EC2 instance {}: {}", resource_id, error_msg),
-                                )
-                            }
-                        })?;
-                    Ok(())
+        match resource_type {
+            ResourceType::Compute if action == Action::Modify => match request.params.instance_type {
+                Some(ref instance_type) => self.modify_resource(resource_id, resource_type, instance_type).await,
+                None => Err(NimbusError::provider("AWS", "Resizing needs a target instance type")),
+            },
+            ResourceType::Compute => self.execute_ec2_action(resource_id, action).await,
+            ResourceType::Database => self.execute_rds_action(resource_id, request).await,
+            ResourceType::LoadBalancer => self.execute_elb_action(resource_id, action).await,
+            ResourceType::Storage => self.execute_s3_action(resource_id, action).await,
+            ResourceType::DNS => self.execute_route53_action(resource_id, action).await,
+            other => Err(NimbusError::UnsupportedAction(action, other)),
+        }?;
+
+        Ok(OperationHandle::new(resource_id, resource_type, request))
+    }
+
+    async fn execute_action_bulk(
+        &self,
+        targets: &[(String, ResourceType)],
+        request: &ActionRequest,
+    ) -> Vec<Result<OperationHandle>> {
+        let instance_ids: Vec<String> = targets
+            .iter()
+            .filter(|(_, resource_type)| *resource_type == ResourceType::Compute)
+            .map(|(resource_id, _)| resource_id.clone())
+            .collect();
+
+        // EC2 starts and stops many instances per call. If the batch is
+        // rejected, e.g. because one instance is in the wrong state, fall
+        // back to one call per instance so each failure is reported.
+        let batched = matches!(request.action, Action::Start | Action::Stop)
+            && instance_ids.len() > 1
+            && match self.execute_ec2_batch(&instance_ids, request.action).await {
+                Ok(()) => true,
+                Err(e) => {
+                    log::warn!("Batch {} failed, retrying per instance: {}", request.action, e);
+                    false
                 }
-                _ => Err(NimbusError::UnsupportedAction(action, ResourceType::Compute)),
+            };
+
+        let mut results = Vec::with_capacity(targets.len());
+        for (resource_id, resource_type) in targets {
+            if batched && *resource_type == ResourceType::Compute {
+                results.push(Ok(OperationHandle::new(resource_id.as_str(), *resource_type, request)));
+            } else {
+                results.push(self.execute_action(resource_id, *resource_type, request).await);
             }
-        } else {
-            match action {
-                Action::Start => {
-                    client
-                        .rds
-                        .start_db_instance()
-                        .db_instance_identifier(resource_id)
-                        .send()
-                        .await
-                        .map_err(|e| {
-                            let error_msg = e.to_string();
-                            if error_msg.contains("DBInstanceNotFound") {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("RDS instance {} not found. It may have been deleted.", resource_id),
-                                )
-                            } else if error_msg.contains("InvalidDBInstanceState") {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("RDS instance {} is not in a state where it can be started. Wait a moment and try again.", resource_id),
-                                )
-                            } else {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("Failed to start RDS instance {}: {}", resource_id, error_msg),
-                                )
-                            }
-                        })?;
-                    Ok(())
+        }
+        results
+    }
+
+    async fn update_tags(
+        &self,
+        resource_id: &str,
+        resource_type: ResourceType,
+        changes: &TagChanges,
+    ) -> Result<()> {
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+        AwsTagger::new(client).apply(resource_id, resource_type, changes).await
+    }
+
+    async fn create_image(&self, resource_id: &str, name: &str) -> Result<String> {
+        validate_image_name(name)?;
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+
+        // Skip the reboot AWS performs by default so creating an image never
+        // causes unexpected downtime.
+        let response = client
+            .ec2
+            .create_image()
+            .instance_id(resource_id)
+            .name(name)
+            .no_reboot(true)
+            .send()
+            .await
+            .map_err(|e| {
+                NimbusError::provider(
+                    "AWS",
+                    format!("Failed to create image from EC2 instance {}: {}", resource_id, e),
+                )
+            })?;
+
+        response
+            .image_id()
+            .map(String::from)
+            .ok_or_else(|| NimbusError::provider("AWS", "CreateImage returned no image ID"))
+    }
+
+    async fn image_state(&self, image_id: &str) -> Result<String> {
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+
+        let response = client
+            .ec2
+            .describe_images()
+            .image_ids(image_id)
+            .send()
+            .await
+            .map_err(|e| {
+                NimbusError::provider("AWS", format!("Failed to describe image {}: {}", image_id, e))
+            })?;
+
+        response
+            .images()
+            .first()
+            .and_then(|image| image.state())
+            .map(|state| state.as_str().to_string())
+            .ok_or_else(|| NimbusError::ResourceNotFound(image_id.to_string()))
+    }
+
+    async fn get_target_health(&self, resource_id: &str, resource_type: ResourceType) -> Result<Vec<TargetGroupHealth>> {
+        if resource_type != ResourceType::LoadBalancer {
+            return Ok(Vec::new());
+        }
+
+        self.ensure_authenticated().await?;
+        self.describe_target_health(resource_id).await
+    }
+
+    async fn get_health(&self, resources: &[(String, ResourceType)]) -> Result<HashMap<String, ResourceHealth>> {
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+        let checks = AwsHealthChecks::new(client.ec2.clone(), client.rds.clone());
+
+        // Each check needs its own permission, so a denied one only drops
+        // those results
+        let mut health = HashMap::new();
+        match checks.instance_status_checks().await {
+            Ok(statuses) => health.extend(statuses),
+            Err(e) => log::warn!("Skipping EC2 status checks: {}", e),
+        }
+        match checks.pending_maintenance().await {
+            Ok(maintenance) => health.extend(maintenance),
+            Err(e) => log::warn!("Skipping RDS maintenance checks: {}", e),
+        }
+
+        for (lb_arn, _) in resources.iter().filter(|(_, t)| *t == ResourceType::LoadBalancer) {
+            match self.describe_target_health(lb_arn).await {
+                Ok(groups) => health.extend(
+                    ResourceHealth::from_target_groups(&groups).map(|h| (lb_arn.clone(), h)),
+                ),
+                Err(e) => log::warn!("Skipping target health for {}: {}", lb_arn, e),
+            }
+        }
+
+        let wanted: std::collections::HashSet<&str> = resources.iter().map(|(id, _)| id.as_str()).collect();
+        health.retain(|id, _| wanted.contains(id.as_str()));
+        Ok(health)
+    }
+
+    async fn find_waste(&self) -> Result<Vec<WasteFinding>> {
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+        let scanner = AwsWasteScanner::new(client.ec2.clone(), client.s3.clone(), &self.config.region);
+
+        // Like the health checks, each scan needs its own permission
+        let mut findings = Vec::new();
+        for scan in [
+            scanner.stopped_instances_with_volumes().await,
+            scanner.unattached_volumes().await,
+            scanner.unassociated_addresses().await,
+            scanner.empty_versioned_buckets().await,
+        ] {
+            match scan {
+                Ok(found) => findings.extend(found),
+                Err(e) => log::warn!("Skipping waste scan: {}", e),
+            }
+        }
+
+        match self.list_load_balancers().await {
+            Ok(load_balancers) => {
+                for lb in load_balancers {
+                    match self.describe_target_health(lb.id()).await {
+                        Ok(groups) if crate::core::waste::is_idle_load_balancer(&groups) => findings.push(
+                            WasteFinding::new(WasteKind::IdleLoadBalancer, lb.id(), lb.name(), lb.region())
+                                .with_monthly_cost(LOAD_BALANCER_HOURLY_PRICE * HOURS_PER_MONTH)
+                                .with_detail(format!("{} target group(s)", groups.len())),
+                        ),
+                        Ok(_) => {}
+                        Err(e) => log::warn!("Skipping target health for {}: {}", lb.id(), e),
+                    }
                 }
-                Action::Stop => {
-                    client
-                        .rds
-                        .stop_db_instance()
-                        .db_instance_identifier(resource_id)
-                        .send()
-                        .await
-                        .map_err(|e| {
-                            let error_msg = e.to_string();
-                            if error_msg.contains("DBInstanceNotFound") {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("RDS instance {} not found. It may have been deleted.", resource_id),
-                                )
-                            } else if error_msg.contains("InvalidDBInstanceState") {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("RDS instance {} is not in a state where it can be stopped. Wait a moment and try again.", resource_id),
-                                )
-                            } else {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("Failed to stop RDS instance {}: {}", resource_id, error_msg),
-                                )
-                            }
-                        })?;
-                    Ok(())
+            }
+            Err(e) => log::warn!("Skipping idle load balancer scan: {}", e),
+        }
+
+        Ok(findings)
+    }
+
+    async fn deletion_blockers(&self, resource_id: &str, resource_type: ResourceType) -> Result<Vec<String>> {
+        self.ensure_authenticated().await?;
+
+        let mut blockers = Vec::new();
+        match resource_type {
+            ResourceType::Storage => {
+                if self.bucket_has_objects(resource_id).await? {
+                    blockers.push("The bucket still holds objects. Empty it first.".to_string());
                 }
-                Action::Restart => {
-                    client
-                        .rds
-                        .reboot_db_instance()
-                        .db_instance_identifier(resource_id)
-                        .send()
-                        .await
-                        .map_err(|e| {
-                            let error_msg = e.to_string();
-                            if error_msg.contains("DBInstanceNotFound") {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("RDS instance {} not found. It may have been deleted.", resource_id),
-                                )
-                            } else if error_msg.contains("InvalidDBInstanceState") {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("RDS instance {} must be available to restart.", resource_id),
-                                )
-                            } else {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("Failed to restart RDS instance {}: {}", resource_id, error_msg),
-                                )
-                            }
-                        })?;
-                    Ok(())
+            }
+            ResourceType::DNS => {
+                let records = self.list_record_sets(resource_id).await?;
+                let count = crate::core::dns::non_default_records(&records).len();
+                if count > 0 {
+                    blockers.push(format!("The zone has {} record(s) besides its SOA and NS records.", count));
                 }
-                Action::Terminate => {
-                    client
-                        .rds
-                        .delete_db_instance()
-                        .db_instance_identifier(resource_id)
-                        .skip_final_snapshot(true)
-                        .send()
-                        .await
-                        .map_err(|e| {
-                            let error_msg = e.to_string();
-                            if error_msg.contains("DBInstanceNotFound") {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("RDS instance {} not found. It may already be deleted.", resource_id),
-                                )
-                            } else if error_msg.contains("InvalidDBInstanceState") {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("RDS instance {} cannot be deleted in its current state. Stop it first or wait for pending operations to complete.", resource_id),
-                                )
-                            } else {
-                                NimbusError::provider(
-                                    "AWS",
-                                    format!("Failed to terminate RDS instance {}: {}", resource_id, error_msg),
-                                )
-                            }
-                        })?;
-                    Ok(())
+            }
+            ResourceType::LoadBalancer => {
+                for group in self.describe_target_health(resource_id).await? {
+                    if !group.targets.is_empty() {
+                        blockers.push(format!(
+                            "{} target(s) are still registered in target group '{}'.",
+                            group.targets.len(),
+                            group.name
+                        ));
+                    }
                 }
-                _ => Err(NimbusError::UnsupportedAction(action, ResourceType::Database)),
             }
+            _ => {}
+        }
+        Ok(blockers)
+    }
+
+    async fn list_dns_records(&self, zone_id: &str) -> Result<Vec<DnsRecord>> {
+        self.ensure_authenticated().await?;
+        self.list_record_sets(zone_id).await
+    }
+
+    async fn remove_termination_protection(&self, resource_id: &str, resource_type: ResourceType) -> Result<()> {
+        if resource_type != ResourceType::Compute {
+            return Err(NimbusError::UnsupportedAction(Action::Terminate, resource_type));
+        }
+
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+
+        client
+            .ec2
+            .modify_instance_attribute()
+            .instance_id(resource_id)
+            .disable_api_termination(
+                aws_sdk_ec2::types::AttributeBooleanValue::builder()
+                    .value(false)
+                    .build(),
+            )
+            .send()
+            .await
+            .map_err(|e| {
+                NimbusError::provider(
+                    "AWS",
+                    format!("Failed to disable termination protection on EC2 instance {}: {}", resource_id, e),
+                )
+            })?;
+        Ok(())
+    }
+
+    async fn terminate_with_snapshot(
+        &self,
+        resource_id: &str,
+        resource_type: ResourceType,
+    ) -> Result<OperationHandle> {
+        // Only RDS instances can be snapshotted on the way out
+        if resource_type != ResourceType::Database {
+            return Err(NimbusError::UnsupportedAction(Action::Snapshot, resource_type));
         }
+
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+        let snapshot_id = rds_snapshot_identifier(resource_id, chrono::Utc::now());
+
+        // RDS takes the final snapshot as part of deletion, which avoids
+        // racing a separate snapshot that would leave the instance busy.
+        client
+            .rds
+            .delete_db_instance()
+            .db_instance_identifier(resource_id)
+            .skip_final_snapshot(false)
+            .final_db_snapshot_identifier(&snapshot_id)
+            .send()
+            .await
+            .map_err(|e| {
+                NimbusError::provider(
+                    "AWS",
+                    format!("Failed to terminate RDS instance {} with final snapshot: {}", resource_id, e),
+                )
+            })?;
+        Ok(OperationHandle::new(resource_id, resource_type, &Action::Terminate.into()))
     }
 
     async fn get_total_cost(&self, period: CostPeriod) -> Result<f64> {
@@ -618,24 +1665,293 @@ impl CloudProvider for AWSProvider {
         cost_explorer.get_cost_breakdown().await
     }
 
+    async fn get_cost_by_tag(&self, tag_key: &str) -> Result<HashMap<String, f64>> {
+        self.ensure_authenticated().await?;
+        let cost_explorer = self.get_cost_explorer()?;
+        cost_explorer.get_cost_by_tag(tag_key).await
+    }
+
+    async fn get_cost_anomalies(&self) -> Result<Vec<CostAnomaly>> {
+        self.ensure_authenticated().await?;
+        let cost_explorer = self.get_cost_explorer()?;
+        cost_explorer.get_anomalies().await
+    }
+
+    async fn get_budgets(&self) -> Result<Vec<Budget>> {
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+        let account_id = match self.identity {
+            Some(ref identity) => identity.account_id.clone(),
+            None => {
+                return Err(NimbusError::provider(
+                    "AWS",
+                    "Account ID unknown, cannot list budgets",
+                ))
+            }
+        };
+
+        AwsBudgets::new(client.budgets.clone()).list(&account_id).await
+    }
+
+    async fn get_quotas(&self) -> Result<Vec<Quota>> {
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+        let quotas = AwsQuotas::new(client.service_quotas.clone(), client.ec2.clone());
+
+        let mut result = Vec::new();
+        for quota in [quotas.vcpus().await, quotas.elastic_ips().await, quotas.vpcs().await] {
+            match quota {
+                Ok(quota) => result.push(quota),
+                Err(e) => log::warn!("Skipping quota: {}", e),
+            }
+        }
+        Ok(result)
+    }
+
+    async fn get_commitment_coverage(&self) -> Result<CommitmentCoverage> {
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+        AwsCommitments::new(client).coverage().await
+    }
+
+    async fn get_cost_history(&self, period: CostPeriod, granularity: CostGranularity) -> Result<Vec<CostPoint>> {
+        self.ensure_authenticated().await?;
+        let cost_explorer = self.get_cost_explorer()?;
+        cost_explorer.get_cost_history(period, granularity).await
+    }
+
+    async fn get_metrics(
+        &self,
+        resource_id: &str,
+        resource_type: ResourceType,
+    ) -> Result<Vec<MetricSeries>> {
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+        let cloudwatch = AwsCloudWatch::new(client.cloudwatch.clone());
+        let lookback = chrono::Duration::hours(3);
+
+        match resource_type {
+            ResourceType::Compute => {
+                cloudwatch
+                    .resource_metrics(
+                        "AWS/EC2",
+                        ("InstanceId", resource_id),
+                        &[
+                            ("CPUUtilization", "CPU Utilization", "Percent", "Average"),
+                            ("NetworkIn", "Network In", "Bytes", "Sum"),
+                            ("NetworkOut", "Network Out", "Bytes", "Sum"),
+                            ("EBSReadBytes", "Disk Read", "Bytes", "Sum"),
+                            ("EBSWriteBytes", "Disk Write", "Bytes", "Sum"),
+                        ],
+                        lookback,
+                        300,
+                    )
+                    .await
+            }
+            ResourceType::Database => {
+                cloudwatch
+                    .resource_metrics(
+                        "AWS/RDS",
+                        ("DBInstanceIdentifier", resource_id),
+                        &[
+                            ("CPUUtilization", "CPU Utilization", "Percent", "Average"),
+                            ("NetworkReceiveThroughput", "Network In", "Bytes/Second", "Average"),
+                            ("NetworkTransmitThroughput", "Network Out", "Bytes/Second", "Average"),
+                            ("ReadIOPS", "Disk Read IOPS", "Count/Second", "Average"),
+                            ("WriteIOPS", "Disk Write IOPS", "Count/Second", "Average"),
+                        ],
+                        lookback,
+                        300,
+                    )
+                    .await
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    async fn modify_options(
+        &self,
+        resource_id: &str,
+        resource_type: ResourceType,
+    ) -> Result<Vec<ModifyOption>> {
+        if resource_type != ResourceType::Compute {
+            return Ok(Vec::new());
+        }
+
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+        let resizer = Ec2Resizer::new(client.ec2.clone());
+
+        let described = resizer.describe(resource_id).await?;
+        let architecture = described
+            .architecture()
+            .map(|a| a.as_str().to_string())
+            .unwrap_or_else(|| "x86_64".to_string());
+        let instance = EC2Instance::from_aws_instance(&described, &self.config.region);
+
+        let candidates = resizer
+            .compatible_types(instance.instance_type(), &architecture)
+            .await?;
+
+        let mut options = Vec::with_capacity(candidates.len());
+        for candidate in candidates {
+            let price = match self.pricing {
+                Some(ref pricing) => {
+                    pricing
+                        .ec2_hourly_price(&self.config.region, &candidate.instance_type, instance.pricing_os())
+                        .await
+                }
+                None => None,
+            };
+
+            options.push(
+                ModifyOption::new(candidate.instance_type.clone(), candidate.label())
+                    .with_monthly_cost(price.map(|p| p * HOURS_PER_MONTH)),
+            );
+        }
+
+        Ok(options)
+    }
+
+    async fn modify_resource(
+        &self,
+        resource_id: &str,
+        resource_type: ResourceType,
+        value: &str,
+    ) -> Result<()> {
+        if resource_type != ResourceType::Compute {
+            return Err(NimbusError::UnsupportedAction(Action::Modify, resource_type));
+        }
+
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+        Ec2Resizer::new(client.ec2.clone()).resize(resource_id, value).await
+    }
+
+    async fn create_options(&self, resource_type: ResourceType) -> Result<CreateOptions> {
+        if resource_type != ResourceType::Compute {
+            return Err(NimbusError::UnsupportedAction(Action::Create, resource_type));
+        }
+
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+        let launcher = Ec2Launcher::new(client.ec2.clone());
+
+        let (images, instance_types, key_pairs) =
+            tokio::try_join!(launcher.images(), launcher.instance_types(), launcher.key_pairs())?;
+
+        let mut options = CreateOptions {
+            images: images
+                .iter()
+                .map(|image| ModifyOption::new(image.image_id.clone(), image.label()))
+                .collect(),
+            key_pairs: key_pairs
+                .into_iter()
+                .map(|name| ModifyOption::new(name.clone(), name))
+                .collect(),
+            ..Default::default()
+        };
+
+        for candidate in instance_types {
+            let price = match self.pricing {
+                Some(ref pricing) => {
+                    pricing
+                        .ec2_hourly_price(&self.config.region, &candidate.instance_type, "Linux")
+                        .await
+                }
+                None => None,
+            };
+
+            options.instance_types.push(
+                ModifyOption::new(candidate.instance_type.clone(), candidate.label())
+                    .with_monthly_cost(price.map(|p| p * HOURS_PER_MONTH)),
+            );
+        }
+
+        Ok(options)
+    }
+
+    async fn create_resource(&self, spec: &CreateSpec) -> Result<OperationHandle> {
+        if spec.resource_type != ResourceType::Compute {
+            return Err(NimbusError::UnsupportedAction(Action::Create, spec.resource_type));
+        }
+
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+        let instance_id = Ec2Launcher::new(client.ec2.clone()).launch(spec).await?;
+        log::info!("Launched EC2 instance {} ({})", instance_id, spec.instance_type);
+
+        Ok(OperationHandle::new(instance_id, ResourceType::Compute, &Action::Create.into()))
+    }
+
+    async fn get_logs(
+        &self,
+        resource_id: &str,
+        resource_type: ResourceType,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<LogEntry>> {
+        self.ensure_authenticated().await?;
+        let logs = self.get_logs_reader()?;
+        logs.recent_events(resource_id, resource_type, since).await
+    }
+
+    fn shell_command(&self, resource_id: &str, resource_type: ResourceType) -> Option<std::process::Command> {
+        if resource_type != ResourceType::Compute {
+            return None;
+        }
+
+        let mut command = std::process::Command::new("aws");
+        command
+            .args(["ssm", "start-session", "--target", resource_id])
+            .args(["--region", &self.config.region]);
+
+        if let Some(ref profile) = self.config.profile {
+            command.args(["--profile", profile]);
+        }
+
+        if let (Some(ref access_key), Some(ref secret_key)) =
+            (&self.config.access_key_id, &self.config.secret_access_key)
+        {
+            command
+                .env("AWS_ACCESS_KEY_ID", access_key)
+                .env("AWS_SECRET_ACCESS_KEY", secret_key);
+        }
+
+        Some(command)
+    }
+
+    fn console_url(&self, resource_id: &str, resource_type: ResourceType) -> Option<String> {
+        // Emulators such as LocalStack have no console
+        if self.config.endpoint_url.is_some() {
+            return None;
+        }
+
+        let region = &self.config.region;
+        let console = format!("https://{region}.console.aws.amazon.com");
+        match resource_type {
+            ResourceType::Compute => Some(format!(
+                "{console}/ec2/home?region={region}#InstanceDetails:instanceId={resource_id}"
+            )),
+            ResourceType::Database => Some(format!("{console}/rds/home?region={region}#database:id={resource_id}")),
+            ResourceType::Storage => Some(format!(
+                "https://s3.console.aws.amazon.com/s3/buckets/{resource_id}?region={region}"
+            )),
+            ResourceType::LoadBalancer => Some(format!(
+                "{console}/ec2/home?region={region}#LoadBalancer:loadBalancerArn={resource_id}"
+            )),
+            ResourceType::DNS => Some(format!(
+                "https://console.aws.amazon.com/route53/v2/hostedzones#ListRecordSets/{}",
+                resource_id.trim_start_matches("/hostedzone/")
+            )),
+            _ => None,
+        }
+    }
+
     fn regions(&self) -> Vec<String> {
-        vec![
-            "us-east-1".to_string(),
-            "us-east-2".to_string(),
-            "us-west-1".to_string(),
-            "us-west-2".to_string(),
-            "eu-west-1".to_string(),
-            "eu-west-2".to_string(),
-            "eu-west-3".to_string(),
-            "eu-central-1".to_string(),
-            "ap-northeast-1".to_string(),
-            "ap-northeast-2".to_string(),
-            "ap-southeast-1".to_string(),
-            "ap-southeast-2".to_string(),
-            "ap-south-1".to_string(),
-            "sa-east-1".to_string(),
-            "ca-central-1".to_string(),
-        ]
+        match self.discovered_regions {
+            Some(ref regions) => regions.clone(),
+            None => FALLBACK_REGIONS.iter().map(|r| r.to_string()).collect(),
+        }
     }
 
     fn current_region(&self) -> &str {
@@ -652,6 +1968,35 @@ impl CloudProvider for AWSProvider {
     }
 }
 
+/// What an RDS instance's price depends on.
+fn rds_price_key(instance: &RDSInstance) -> (String, String, bool) {
+    (
+        instance.instance_class().to_string(),
+        instance.engine().to_string(),
+        instance.is_multi_az(),
+    )
+}
+
+/// Checks an AMI name against the EC2 naming rules.
+fn validate_image_name(name: &str) -> Result<()> {
+    let valid_chars = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || " ()[]./-'@_".contains(c));
+
+    if name.len() < 3 || name.len() > 128 || !valid_chars {
+        return Err(NimbusError::provider(
+            "AWS",
+            "Image names must be 3-128 characters of letters, numbers, spaces, and ()[]./-'@_",
+        ));
+    }
+    Ok(())
+}
+
+/// Builds a snapshot name such as `mydb-nimbus-20240101-120000`.
+fn rds_snapshot_identifier(db_instance_id: &str, time: chrono::DateTime<chrono::Utc>) -> String {
+    format!("{}-nimbus-{}", db_instance_id, time.format("%Y%m%d-%H%M%S"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -659,10 +2004,16 @@ mod tests {
     #[test]
     fn test_provider_creation() {
         let config = AwsConfig {
+            label: None,
             profile: Some("default".to_string()),
             region: "us-east-1".to_string(),
             access_key_id: None,
             secret_access_key: None,
+            role_arn: None,
+            external_id: None,
+            source_profile: None,
+            s3_metrics: true,
+            endpoint_url: None,
         };
 
         let provider = AWSProvider::new(config);
@@ -671,6 +2022,82 @@ mod tests {
         assert_eq!(provider.current_region(), "us-east-1");
     }
 
+    #[test]
+    fn test_provider_name_uses_label() {
+        let config = AwsConfig {
+            label: Some("staging".to_string()),
+            ..AwsConfig::default()
+        };
+
+        let provider = AWSProvider::new(config);
+        assert_eq!(provider.name(), "AWS (staging)");
+    }
+
+    #[test]
+    fn test_shell_command_uses_ssm() {
+        let config = AwsConfig {
+            profile: Some("dev".to_string()),
+            region: "eu-west-1".to_string(),
+            ..AwsConfig::default()
+        };
+        let provider = AWSProvider::new(config);
+
+        let command = provider
+            .shell_command("i-123", ResourceType::Compute)
+            .unwrap();
+        let args: Vec<_> = command.get_args().map(|a| a.to_string_lossy().to_string()).collect();
+        assert_eq!(command.get_program(), "aws");
+        assert_eq!(
+            args,
+            vec!["ssm", "start-session", "--target", "i-123", "--region", "eu-west-1", "--profile", "dev"]
+        );
+
+        assert!(provider.shell_command("db-1", ResourceType::Database).is_none());
+    }
+
+    #[test]
+    fn test_console_url() {
+        let config = AwsConfig {
+            region: "eu-west-1".to_string(),
+            ..AwsConfig::default()
+        };
+        let provider = AWSProvider::new(config.clone());
+
+        assert_eq!(
+            provider.console_url("i-123", ResourceType::Compute).as_deref(),
+            Some("https://eu-west-1.console.aws.amazon.com/ec2/home?region=eu-west-1#InstanceDetails:instanceId=i-123")
+        );
+        assert_eq!(
+            provider.console_url("/hostedzone/Z1", ResourceType::DNS).as_deref(),
+            Some("https://console.aws.amazon.com/route53/v2/hostedzones#ListRecordSets/Z1")
+        );
+        assert!(provider.console_url("q", ResourceType::Queue).is_none());
+
+        let localstack = AWSProvider::new(AwsConfig {
+            endpoint_url: Some("http://localhost:4566".to_string()),
+            ..config
+        });
+        assert!(localstack.console_url("i-123", ResourceType::Compute).is_none());
+    }
+
+    #[test]
+    fn test_validate_image_name() {
+        assert!(validate_image_name("web-server-2024.01.01").is_ok());
+        assert!(validate_image_name("backup (pre-upgrade)").is_ok());
+        assert!(validate_image_name("ab").is_err());
+        assert!(validate_image_name("bad#name").is_err());
+        assert!(validate_image_name(&"x".repeat(129)).is_err());
+    }
+
+    #[test]
+    fn test_rds_snapshot_identifier() {
+        let time = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        assert_eq!(
+            rds_snapshot_identifier("orders-db", time),
+            "orders-db-nimbus-20231114-221320"
+        );
+    }
+
     #[test]
     fn test_provider_regions() {
         let config = AwsConfig::default();
@@ -682,6 +2109,14 @@ mod tests {
         assert!(regions.len() > 10);
     }
 
+    #[test]
+    fn test_provider_regions_prefers_discovered() {
+        let mut provider = AWSProvider::new(AwsConfig::default());
+        provider.discovered_regions = Some(vec!["il-central-1".to_string(), "us-east-1".to_string()]);
+
+        assert_eq!(provider.regions(), vec!["il-central-1".to_string(), "us-east-1".to_string()]);
+    }
+
     #[tokio::test]
     async fn test_unauthenticated_error() {
         let config = AwsConfig::default();
@@ -691,4 +2126,15 @@ mod tests {
         assert!(result.is_err());
         
     }
+
+    #[tokio::test]
+    async fn test_snapshot_before_terminate_is_rds_only() {
+        let provider = AWSProvider::new(AwsConfig::default());
+
+        let result = provider.terminate_with_snapshot("i-1234", ResourceType::Compute).await;
+        assert!(matches!(
+            result,
+            Err(NimbusError::UnsupportedAction(Action::Snapshot, ResourceType::Compute))
+        ));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,222 @@
+use crate::cache::CacheStore;
+use crate::error::{NimbusError, Result};
+use aws_config::SdkConfig;
+use aws_sdk_pricing::config::Region;
+use aws_sdk_pricing::types::{Filter, FilterType};
+use aws_sdk_pricing::Client as PricingClient;
+use chrono::Duration;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// The Pricing API is only served from a handful of regions.
+const PRICING_API_REGION: &str = "us-east-1";
+const PRICE_CACHE_TTL_DAYS: i64 = 7;
+
+/// On-demand price lookups backed by the AWS Pricing API.
+///
+/// Prices found are memoized in memory for the life of the provider and
+/// persisted to the SQLite cache when one is available. A `None` result means
+/// the price could not be determined and callers should fall back to static
+/// estimates; it is not remembered, so a failed lookup is retried next time.
+pub struct AwsPricing {
+    client: PricingClient,
+    store: Option<Arc<Mutex<CacheStore>>>,
+    memo: Mutex<HashMap<String, f64>>,
+}
+
+impl AwsPricing {
+    pub fn new(sdk_config: &SdkConfig, store: Option<Arc<Mutex<CacheStore>>>) -> Self {
+        let config = aws_sdk_pricing::config::Builder::from(sdk_config)
+            .region(Region::new(PRICING_API_REGION))
+            .build();
+
+        Self {
+            client: PricingClient::from_conf(config),
+            store,
+            memo: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the hourly on-demand price for an EC2 instance type.
+    pub async fn ec2_hourly_price(&self, region: &str, instance_type: &str, os: &str) -> Option<f64> {
+        let key = format!("ec2:{}:{}:{}", region, instance_type, os);
+        let filters = [
+            ("instanceType", instance_type),
+            ("regionCode", region),
+            ("operatingSystem", os),
+            ("tenancy", "Shared"),
+            ("preInstalledSw", "NA"),
+            ("capacitystatus", "Used"),
+        ];
+
+        self.lookup(&key, "AmazonEC2", &filters).await
+    }
+
+    /// Returns the hourly on-demand price for an RDS instance class.
+    pub async fn rds_hourly_price(
+        &self,
+        region: &str,
+        instance_class: &str,
+        engine: &str,
+        multi_az: bool,
+    ) -> Option<f64> {
+        let database_engine = rds_pricing_engine(engine)?;
+        let deployment = if multi_az { "Multi-AZ" } else { "Single-AZ" };
+        let key = format!("rds:{}:{}:{}:{}", region, instance_class, engine, deployment);
+        let filters = [
+            ("instanceType", instance_class),
+            ("regionCode", region),
+            ("databaseEngine", database_engine),
+            ("deploymentOption", deployment),
+        ];
+
+        self.lookup(&key, "AmazonRDS", &filters).await
+    }
+
+    async fn lookup(&self, key: &str, service_code: &str, filters: &[(&str, &str)]) -> Option<f64> {
+        if let Some(price) = self.memo.lock().ok().and_then(|memo| memo.get(key).copied()) {
+            return Some(price);
+        }
+
+        if let Some(price) = self.cached_price(key) {
+            self.remember(key, price);
+            return Some(price);
+        }
+
+        let price = match self.fetch_price(service_code, filters).await {
+            Ok(price) => price,
+            Err(e) => {
+                log::debug!("Pricing lookup for {} failed: {}", key, e);
+                None
+            }
+        };
+
+        let price = price?;
+        if let Some(ref store) = self.store {
+            if let Ok(store) = store.lock() {
+                if let Err(e) = store.cache_price(key, price) {
+                    log::warn!("Failed to cache price for {}: {}", key, e);
+                }
+            }
+        }
+
+        self.remember(key, price);
+        Some(price)
+    }
+
+    fn cached_price(&self, key: &str) -> Option<f64> {
+        let store = self.store.as_ref()?.lock().ok()?;
+        store
+            .get_cached_price(key, Duration::days(PRICE_CACHE_TTL_DAYS))
+            .ok()
+            .flatten()
+    }
+
+    fn remember(&self, key: &str, price: f64) {
+        if let Ok(mut memo) = self.memo.lock() {
+            memo.insert(key.to_string(), price);
+        }
+    }
+
+    async fn fetch_price(&self, service_code: &str, filters: &[(&str, &str)]) -> Result<Option<f64>> {
+        let mut request = self
+            .client
+            .get_products()
+            .service_code(service_code)
+            .max_results(1);
+
+        for (field, value) in filters {
+            let filter = Filter::builder()
+                .r#type(FilterType::TermMatch)
+                .field(*field)
+                .value(*value)
+                .build()
+                .map_err(|e| NimbusError::provider("AWS", format!("Invalid pricing filter: {}", e)))?;
+            request = request.filters(filter);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            NimbusError::provider("AWS", format!("Failed to query pricing: {}", e))
+        })?;
+
+        Ok(response
+            .price_list()
+            .iter()
+            .find_map(|product| parse_on_demand_price(product)))
+    }
+}
+
+/// Maps an RDS engine identifier to the Pricing API's `databaseEngine` value.
+fn rds_pricing_engine(engine: &str) -> Option<&'static str> {
+    match engine {
+        "postgres" => Some("PostgreSQL"),
+        "mysql" => Some("MySQL"),
+        "mariadb" => Some("MariaDB"),
+        "aurora-postgresql" => Some("Aurora PostgreSQL"),
+        "aurora-mysql" | "aurora" => Some("Aurora MySQL"),
+        e if e.starts_with("oracle") => Some("Oracle"),
+        e if e.starts_with("sqlserver") => Some("SQL Server"),
+        _ => None,
+    }
+}
+
+/// Extracts the USD on-demand hourly rate from a Pricing API product document.
+fn parse_on_demand_price(product: &str) -> Option<f64> {
+    let document: serde_json::Value = serde_json::from_str(product).ok()?;
+    let on_demand = document.get("terms")?.get("OnDemand")?.as_object()?;
+
+    on_demand.values().find_map(|term| {
+        term.get("priceDimensions")?
+            .as_object()?
+            .values()
+            .find_map(|dimension| {
+                dimension
+                    .get("pricePerUnit")?
+                    .get("USD")?
+                    .as_str()?
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|price| *price > 0.0)
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_on_demand_price() {
+        let product = r#"{
+            "product": {"attributes": {"instanceType": "t3.micro"}},
+            "terms": {
+                "OnDemand": {
+                    "ABC.JRTCKXETXF": {
+                        "priceDimensions": {
+                            "ABC.JRTCKXETXF.6YS6EN2CT7": {
+                                "unit": "Hrs",
+                                "pricePerUnit": {"USD": "0.0104000000"}
+                            }
+                        }
+                    }
+                }
+            }
+        }"#;
+
+        assert_eq!(parse_on_demand_price(product), Some(0.0104));
+    }
+
+    #[test]
+    fn test_parse_on_demand_price_invalid() {
+        assert_eq!(parse_on_demand_price("not json"), None);
+        assert_eq!(parse_on_demand_price(r#"{"terms": {}}"#), None);
+    }
+
+    #[test]
+    fn test_rds_pricing_engine() {
+        assert_eq!(rds_pricing_engine("postgres"), Some("PostgreSQL"));
+        assert_eq!(rds_pricing_engine("aurora-mysql"), Some("Aurora MySQL"));
+        assert_eq!(rds_pricing_engine("sqlserver-se"), Some("SQL Server"));
+        assert_eq!(rds_pricing_engine("unknown"), None);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::core::Quota;
+use crate::error::{NimbusError, Result};
+use aws_sdk_ec2::types::{Filter, InstanceLifecycleType};
+use aws_sdk_ec2::Client as Ec2Client;
+use aws_sdk_servicequotas::Client as ServiceQuotasClient;
+
+/// Running On-Demand Standard (A, C, D, H, I, M, R, T, Z) instances, in vCPUs
+const VCPU_QUOTA: (&str, &str, &str) = ("ec2", "L-1216C47A", "On-Demand Standard vCPUs");
+const EIP_QUOTA: (&str, &str, &str) = ("ec2", "L-0263D0A3", "Elastic IPs");
+const VPC_QUOTA: (&str, &str, &str) = ("vpc", "L-F678F1CE", "VPCs per Region");
+
+/// Reads limits from Service Quotas and counts their usage with EC2.
+pub struct AwsQuotas {
+    service_quotas: ServiceQuotasClient,
+    ec2: Ec2Client,
+}
+
+impl AwsQuotas {
+    pub fn new(service_quotas: ServiceQuotasClient, ec2: Ec2Client) -> Self {
+        Self { service_quotas, ec2 }
+    }
+
+    pub async fn vcpus(&self) -> Result<Quota> {
+        let limit = self.limit(VCPU_QUOTA).await?;
+        Ok(Quota::new("EC2", VCPU_QUOTA.2, self.standard_vcpus_in_use().await?, limit).with_code(VCPU_QUOTA.1))
+    }
+
+    pub async fn elastic_ips(&self) -> Result<Quota> {
+        let limit = self.limit(EIP_QUOTA).await?;
+        let response = self.ec2.describe_addresses().send().await.map_err(|e| {
+            NimbusError::provider("AWS", format!("Failed to describe addresses: {}", e))
+        })?;
+
+        let usage = response.addresses().len() as f64;
+        Ok(Quota::new("EC2", EIP_QUOTA.2, usage, limit).with_code(EIP_QUOTA.1))
+    }
+
+    pub async fn vpcs(&self) -> Result<Quota> {
+        let limit = self.limit(VPC_QUOTA).await?;
+        let mut pages = self.ec2.describe_vpcs().into_paginator().send();
+
+        let mut usage = 0;
+        while let Some(page) = pages.next().await {
+            let page = page.map_err(|e| {
+                NimbusError::provider("AWS", format!("Failed to describe VPCs: {}", e))
+            })?;
+            usage += page.vpcs().len();
+        }
+
+        Ok(Quota::new("VPC", VPC_QUOTA.2, usage as f64, limit).with_code(VPC_QUOTA.1))
+    }
+
+    /// Returns the applied value of a quota, falling back to the AWS default
+    /// for quotas that have never been raised in this account.
+    async fn limit(&self, (service, code, name): (&str, &str, &str)) -> Result<f64> {
+        let applied = self
+            .service_quotas
+            .get_service_quota()
+            .service_code(service)
+            .quota_code(code)
+            .send()
+            .await
+            .ok()
+            .and_then(|response| response.quota().and_then(|q| q.value()));
+        if let Some(value) = applied {
+            return Ok(value);
+        }
+
+        let response = self
+            .service_quotas
+            .get_aws_default_service_quota()
+            .service_code(service)
+            .quota_code(code)
+            .send()
+            .await
+            .map_err(|e| NimbusError::provider("AWS", format!("Failed to get quota {}: {}", name, e)))?;
+
+        response
+            .quota()
+            .and_then(|q| q.value())
+            .ok_or_else(|| NimbusError::provider("AWS", format!("Quota {} has no value", name)))
+    }
+
+    /// Sums the vCPUs of pending and running On-Demand instances in the
+    /// standard families. Spot instances count against a separate quota.
+    async fn standard_vcpus_in_use(&self) -> Result<f64> {
+        let mut pages = self
+            .ec2
+            .describe_instances()
+            .filters(
+                Filter::builder()
+                    .name("instance-state-name")
+                    .values("pending")
+                    .values("running")
+                    .build(),
+            )
+            .into_paginator()
+            .send();
+
+        let mut vcpus = 0;
+        while let Some(page) = pages.next().await {
+            let page = page.map_err(|e| {
+                NimbusError::provider("AWS", format!("Failed to describe instances: {}", e))
+            })?;
+
+            for instance in page.reservations().iter().flat_map(|r| r.instances()) {
+                if instance.instance_lifecycle() == Some(&InstanceLifecycleType::Spot) {
+                    continue;
+                }
+                let is_standard = instance
+                    .instance_type()
+                    .map(|t| is_standard_family(t.as_str()))
+                    .unwrap_or(false);
+                if !is_standard {
+                    continue;
+                }
+                if let Some(cpu) = instance.cpu_options() {
+                    vcpus += cpu.core_count().unwrap_or(0) * cpu.threads_per_core().unwrap_or(1);
+                }
+            }
+        }
+
+        Ok(vcpus as f64)
+    }
+}
+
+/// Returns true if an instance type counts against the standard vCPU quota,
+/// which covers the A, C, D, H, I, M, R, T and Z families. Families such as
+/// `inf` and `dl` share a first letter with them but have their own quotas.
+fn is_standard_family(instance_type: &str) -> bool {
+    let family: String = instance_type.chars().take_while(|c| c.is_ascii_alphabetic()).collect();
+    if matches!(family.as_str(), "inf" | "dl" | "hpc") {
+        return false;
+    }
+    family
+        .chars()
+        .next()
+        .map(|c| "acdhimrtz".contains(c))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_standard_family() {
+        assert!(is_standard_family("m5.large"));
+        assert!(is_standard_family("t3a.micro"));
+        assert!(is_standard_family("c7gn.xlarge"));
+        assert!(!is_standard_family("p4d.24xlarge"));
+        assert!(!is_standard_family("g5.xlarge"));
+        assert!(!is_standard_family("inf2.xlarge"));
+        assert!(!is_standard_family("dl1.24xlarge"));
+        assert!(!is_standard_family(""));
+    }
+}
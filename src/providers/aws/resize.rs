@@ -0,0 +1,228 @@
+use crate::error::{NimbusError, Result};
+use aws_sdk_ec2::types::{AttributeValue, Filter, Instance};
+use aws_sdk_ec2::Client as Ec2Client;
+use std::time::Duration;
+
+/// Interval between instance state checks while waiting for a stop.
+const STATE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Give up waiting for an instance to stop after this many checks.
+const MAX_STATE_POLLS: u32 = 120;
+
+/// An instance type the current instance can be resized to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstanceTypeOption {
+    pub instance_type: String,
+    pub vcpus: i32,
+    pub memory_mib: i64,
+}
+
+impl InstanceTypeOption {
+    pub fn label(&self) -> String {
+        format!(
+            "{} ({} vCPU, {} GiB)",
+            self.instance_type,
+            self.vcpus,
+            format_memory_gib(self.memory_mib)
+        )
+    }
+}
+
+/// Stop-modify-start orchestration for changing an EC2 instance's type.
+pub struct Ec2Resizer {
+    client: Ec2Client,
+}
+
+impl Ec2Resizer {
+    pub fn new(client: Ec2Client) -> Self {
+        Self { client }
+    }
+
+    /// Fetches the full description of a single instance.
+    pub async fn describe(&self, instance_id: &str) -> Result<Instance> {
+        let response = self
+            .client
+            .describe_instances()
+            .instance_ids(instance_id)
+            .send()
+            .await
+            .map_err(|e| {
+                NimbusError::provider("AWS", format!("Failed to describe EC2 instance {}: {}", instance_id, e))
+            })?;
+
+        response
+            .reservations()
+            .iter()
+            .flat_map(|r| r.instances())
+            .next()
+            .cloned()
+            .ok_or_else(|| NimbusError::ResourceNotFound(instance_id.to_string()))
+    }
+
+    /// Lists the other instance types in the same family and architecture,
+    /// ordered from smallest to largest.
+    ///
+    /// Staying within the family keeps the network and storage drivers the
+    /// instance already has, so the resize is safe without an AMI change.
+    pub async fn compatible_types(&self, current_type: &str, architecture: &str) -> Result<Vec<InstanceTypeOption>> {
+        let family = instance_family(current_type);
+        let mut pages = self
+            .client
+            .describe_instance_types()
+            .filters(Self::filter("instance-type", &format!("{}.*", family)))
+            .filters(Self::filter("processor-info.supported-architecture", architecture))
+            .into_paginator()
+            .send();
+
+        let mut options = Vec::new();
+        while let Some(page) = pages.next().await {
+            let page = page.map_err(|e| {
+                NimbusError::provider("AWS", format!("Failed to list instance types: {}", e))
+            })?;
+
+            for info in page.instance_types() {
+                let instance_type = match info.instance_type() {
+                    Some(t) => t.as_str().to_string(),
+                    None => continue,
+                };
+                if instance_type == current_type {
+                    continue;
+                }
+
+                options.push(InstanceTypeOption {
+                    instance_type,
+                    vcpus: info.v_cpu_info().and_then(|v| v.default_v_cpus()).unwrap_or(0),
+                    memory_mib: info.memory_info().and_then(|m| m.size_in_mib()).unwrap_or(0),
+                });
+            }
+        }
+
+        options.sort_by_key(|o| (o.vcpus, o.memory_mib));
+        Ok(options)
+    }
+
+    /// Changes the instance type, stopping and restarting the instance if it
+    /// is currently running.
+    pub async fn resize(&self, instance_id: &str, target_type: &str) -> Result<()> {
+        let was_running = match self.instance_state(instance_id).await?.as_str() {
+            "running" => true,
+            "stopped" => false,
+            state => {
+                return Err(NimbusError::provider(
+                    "AWS",
+                    format!("EC2 instance {} is {}. Wait until it is running or stopped and try again.", instance_id, state),
+                ))
+            }
+        };
+
+        if was_running {
+            self.client
+                .stop_instances()
+                .instance_ids(instance_id)
+                .send()
+                .await
+                .map_err(|e| {
+                    NimbusError::provider("AWS", format!("Failed to stop EC2 instance {}: {}", instance_id, e))
+                })?;
+            self.wait_until_stopped(instance_id).await?;
+        }
+
+        self.client
+            .modify_instance_attribute()
+            .instance_id(instance_id)
+            .instance_type(AttributeValue::builder().value(target_type).build())
+            .send()
+            .await
+            .map_err(|e| {
+                NimbusError::provider(
+                    "AWS",
+                    format!("Failed to change EC2 instance {} to {}: {}", instance_id, target_type, e),
+                )
+            })?;
+
+        if was_running {
+            self.client
+                .start_instances()
+                .instance_ids(instance_id)
+                .send()
+                .await
+                .map_err(|e| {
+                    NimbusError::provider(
+                        "AWS",
+                        format!("Resized EC2 instance {} but failed to start it: {}", instance_id, e),
+                    )
+                })?;
+        }
+
+        Ok(())
+    }
+
+    async fn wait_until_stopped(&self, instance_id: &str) -> Result<()> {
+        for _ in 0..MAX_STATE_POLLS {
+            if self.instance_state(instance_id).await? == "stopped" {
+                return Ok(());
+            }
+            tokio::time::sleep(STATE_POLL_INTERVAL).await;
+        }
+
+        Err(NimbusError::provider(
+            "AWS",
+            format!("Timed out waiting for EC2 instance {} to stop", instance_id),
+        ))
+    }
+
+    async fn instance_state(&self, instance_id: &str) -> Result<String> {
+        let instance = self.describe(instance_id).await?;
+        Ok(instance
+            .state()
+            .and_then(|s| s.name())
+            .map(|name| name.as_str().to_string())
+            .unwrap_or_default())
+    }
+
+    fn filter(name: &str, value: &str) -> Filter {
+        Filter::builder().name(name).values(value).build()
+    }
+}
+
+/// Returns the family part of an instance type (e.g. `m5` for `m5.large`).
+fn instance_family(instance_type: &str) -> &str {
+    instance_type.split('.').next().unwrap_or(instance_type)
+}
+
+fn format_memory_gib(memory_mib: i64) -> String {
+    let gib = memory_mib as f64 / 1024.0;
+    if gib.fract() == 0.0 {
+        format!("{}", gib as i64)
+    } else {
+        format!("{:.1}", gib)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instance_family() {
+        assert_eq!(instance_family("m5.large"), "m5");
+        assert_eq!(instance_family("t3a.2xlarge"), "t3a");
+        assert_eq!(instance_family("weird"), "weird");
+    }
+
+    #[test]
+    fn test_instance_type_option_label() {
+        let option = InstanceTypeOption {
+            instance_type: "t3.micro".to_string(),
+            vcpus: 2,
+            memory_mib: 1024,
+        };
+        assert_eq!(option.label(), "t3.micro (2 vCPU, 1 GiB)");
+
+        let option = InstanceTypeOption {
+            instance_type: "t3.nano".to_string(),
+            vcpus: 2,
+            memory_mib: 512,
+        };
+        assert_eq!(option.label(), "t3.nano (2 vCPU, 0.5 GiB)");
+    }
+}
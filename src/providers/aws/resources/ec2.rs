@@ -1,4 +1,5 @@
-use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType};
+use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType, HOURS_PER_MONTH};
+use super::network::{NetworkInterface, SecurityGroup};
 use aws_sdk_ec2::types::Instance as Ec2Instance;
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
@@ -13,6 +14,13 @@ pub struct EC2Instance {
     launch_time: Option<DateTime<Utc>>,
     public_ip: Option<String>,
     private_ip: Option<String>,
+    platform: String,
+    hourly_price: Option<f64>,
+    vpc_id: Option<String>,
+    subnet_id: Option<String>,
+    security_groups: Vec<SecurityGroup>,
+    network_interfaces: Vec<NetworkInterface>,
+    hibernation_enabled: bool,
 }
 
 impl EC2Instance {
@@ -60,6 +68,23 @@ impl EC2Instance {
             .private_ip_address()
             .map(|ip| ip.to_string());
 
+        let platform = instance
+            .platform_details()
+            .unwrap_or("Linux/UNIX")
+            .to_string();
+
+        let security_groups = instance
+            .security_groups()
+            .iter()
+            .map(SecurityGroup::from_aws_identifier)
+            .collect();
+
+        let network_interfaces = instance
+            .network_interfaces()
+            .iter()
+            .map(NetworkInterface::from_aws_interface)
+            .collect();
+
         Self {
             instance_id,
             name,
@@ -70,9 +95,61 @@ impl EC2Instance {
             launch_time,
             public_ip,
             private_ip,
+            platform,
+            hourly_price: None,
+            vpc_id: instance.vpc_id().map(String::from),
+            subnet_id: instance.subnet_id().map(String::from),
+            security_groups,
+            network_interfaces,
+            hibernation_enabled: instance
+                .hibernation_options()
+                .and_then(|h| h.configured())
+                .unwrap_or(false),
         }
     }
 
+    pub fn with_hourly_price(mut self, price: Option<f64>) -> Self {
+        self.hourly_price = price;
+        self
+    }
+
+    /// Replaces attached security groups with fully described ones, which
+    /// include their inbound rules.
+    pub fn with_security_group_rules(mut self, groups: &HashMap<String, SecurityGroup>) -> Self {
+        for group in &mut self.security_groups {
+            if let Some(described) = groups.get(&group.id) {
+                *group = described.clone();
+            }
+        }
+        self
+    }
+
+    /// Returns the IDs of the attached security groups.
+    pub fn security_group_ids(&self) -> impl Iterator<Item = &str> {
+        self.security_groups.iter().map(|g| g.id.as_str())
+    }
+
+    pub fn security_groups(&self) -> &[SecurityGroup] {
+        &self.security_groups
+    }
+
+    pub fn network_interfaces(&self) -> &[NetworkInterface] {
+        &self.network_interfaces
+    }
+
+    pub fn vpc_id(&self) -> Option<&str> {
+        self.vpc_id.as_deref()
+    }
+
+    pub fn subnet_id(&self) -> Option<&str> {
+        self.subnet_id.as_deref()
+    }
+
+    /// Returns true if the instance was launched with hibernation enabled.
+    pub fn hibernation_enabled(&self) -> bool {
+        self.hibernation_enabled
+    }
+
     pub fn instance_type(&self) -> &str {
         &self.instance_type
     }
@@ -84,6 +161,23 @@ impl EC2Instance {
     pub fn private_ip(&self) -> Option<&str> {
         self.private_ip.as_deref()
     }
+
+    pub fn platform(&self) -> &str {
+        &self.platform
+    }
+
+    /// Returns the operating system name used by the Pricing API.
+    pub fn pricing_os(&self) -> &'static str {
+        if self.platform.contains("Windows") {
+            "Windows"
+        } else if self.platform.contains("Red Hat") {
+            "RHEL"
+        } else if self.platform.contains("SUSE") {
+            "SUSE"
+        } else {
+            "Linux"
+        }
+    }
 }
 
 impl CloudResource for EC2Instance {
@@ -114,13 +208,16 @@ impl CloudResource for EC2Instance {
             "terminated" => ResourceState::Terminated,
             "pending" => ResourceState::Pending,
             "stopping" => ResourceState::Stopping,
-            "shutting-down" => ResourceState::Stopping,
+            "shutting-down" => ResourceState::Deleting,
             _ => ResourceState::Unknown,
         }
     }
 
     fn cost_per_month(&self) -> Option<f64> {
-        Some(estimate_ec2_cost(&self.instance_type))
+        match self.hourly_price {
+            Some(price) => Some(price * HOURS_PER_MONTH),
+            None => Some(estimate_ec2_cost(&self.instance_type)),
+        }
     }
 
     fn tags(&self) -> &HashMap<String, String> {
@@ -133,22 +230,54 @@ impl CloudResource for EC2Instance {
 
     fn supported_actions(&self) -> Vec<Action> {
         match self.state() {
-            ResourceState::Running => vec![
-                Action::Stop,
-                Action::Restart,
-                Action::Terminate,
-                Action::ViewDetails,
-            ],
+            ResourceState::Running => {
+                let mut actions = vec![Action::Stop];
+                if self.hibernation_enabled {
+                    actions.push(Action::Hibernate);
+                }
+                actions.extend([
+                    Action::Restart,
+                    Action::Terminate,
+                    Action::ViewDetails,
+                    Action::ViewLogs,
+                    Action::Modify,
+                    Action::CreateImage,
+                    Action::Tag,
+                    Action::Connect,
+                ]);
+                actions
+            }
             ResourceState::Stopped => vec![
                 Action::Start,
                 Action::Terminate,
                 Action::ViewDetails,
+                Action::ViewLogs,
+                Action::Modify,
+                Action::CreateImage,
+                Action::Tag,
             ],
             ResourceState::Pending | ResourceState::Stopping => vec![Action::ViewDetails],
             _ => vec![Action::ViewDetails],
         }
     }
 
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = vec![
+            ("Instance type".to_string(), self.instance_type.clone()),
+            ("Platform".to_string(), self.platform.clone()),
+        ];
+        if let Some(ref ip) = self.private_ip {
+            details.push(("Private IP".to_string(), ip.clone()));
+        }
+        if let Some(ref ip) = self.public_ip {
+            details.push(("Public IP".to_string(), ip.clone()));
+        }
+        if self.hibernation_enabled {
+            details.push(("Hibernation".to_string(), "Enabled".to_string()));
+        }
+        details
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -173,6 +302,7 @@ fn estimate_ec2_cost(instance_type: &str) -> f64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::providers::aws::resources::network::SecurityGroupRule;
 
     #[test]
     fn test_estimate_ec2_cost() {
@@ -193,6 +323,13 @@ mod tests {
             launch_time: None,
             public_ip: None,
             private_ip: None,
+            platform: "Linux/UNIX".to_string(),
+            hourly_price: None,
+            vpc_id: None,
+            subnet_id: None,
+            security_groups: Vec::new(),
+            network_interfaces: Vec::new(),
+            hibernation_enabled: false,
         };
 
         assert_eq!(instance.state(), ResourceState::Running);
@@ -212,16 +349,105 @@ mod tests {
             launch_time: None,
             public_ip: None,
             private_ip: None,
+            platform: "Linux/UNIX".to_string(),
+            hourly_price: None,
+            vpc_id: None,
+            subnet_id: None,
+            security_groups: Vec::new(),
+            network_interfaces: Vec::new(),
+            hibernation_enabled: false,
         };
 
         let actions = instance.supported_actions();
         assert!(actions.contains(&Action::Stop));
         assert!(actions.contains(&Action::Terminate));
+        assert!(actions.contains(&Action::ViewLogs));
+        assert!(actions.contains(&Action::Connect));
         assert!(!actions.contains(&Action::Start));
+        assert!(!actions.contains(&Action::Hibernate));
+
+        instance.hibernation_enabled = true;
+        assert!(instance.supported_actions().contains(&Action::Hibernate));
 
         instance.state = "stopped".to_string();
         let actions = instance.supported_actions();
         assert!(actions.contains(&Action::Start));
+        assert!(actions.contains(&Action::Modify));
+        assert!(actions.contains(&Action::CreateImage));
         assert!(!actions.contains(&Action::Stop));
+        assert!(!actions.contains(&Action::Connect));
+    }
+
+    #[test]
+    fn test_ec2_cost_prefers_live_price() {
+        let instance = EC2Instance {
+            instance_id: "i-123".to_string(),
+            name: "test".to_string(),
+            instance_type: "t3.micro".to_string(),
+            state: "running".to_string(),
+            region: "us-east-1".to_string(),
+            tags: HashMap::new(),
+            launch_time: None,
+            public_ip: None,
+            private_ip: None,
+            platform: "Windows".to_string(),
+            hourly_price: None,
+            vpc_id: None,
+            subnet_id: None,
+            security_groups: Vec::new(),
+            network_interfaces: Vec::new(),
+            hibernation_enabled: false,
+        };
+
+        assert_eq!(instance.pricing_os(), "Windows");
+        assert_eq!(instance.cost_per_month(), Some(7.59));
+
+        let instance = instance.with_hourly_price(Some(0.02));
+        assert_eq!(instance.cost_per_month(), Some(0.02 * HOURS_PER_MONTH));
+    }
+
+    #[test]
+    fn test_with_security_group_rules() {
+        let instance = EC2Instance {
+            instance_id: "i-123".to_string(),
+            name: "test".to_string(),
+            instance_type: "t3.micro".to_string(),
+            state: "running".to_string(),
+            region: "us-east-1".to_string(),
+            tags: HashMap::new(),
+            launch_time: None,
+            public_ip: None,
+            private_ip: None,
+            platform: "Linux/UNIX".to_string(),
+            hourly_price: None,
+            vpc_id: Some("vpc-1".to_string()),
+            subnet_id: Some("subnet-1".to_string()),
+            security_groups: vec![SecurityGroup {
+                id: "sg-1".to_string(),
+                name: "web".to_string(),
+                ingress: Vec::new(),
+            }],
+            network_interfaces: Vec::new(),
+            hibernation_enabled: false,
+        };
+
+        let mut described = HashMap::new();
+        described.insert(
+            "sg-1".to_string(),
+            SecurityGroup {
+                id: "sg-1".to_string(),
+                name: "web".to_string(),
+                ingress: vec![SecurityGroupRule {
+                    protocol: "tcp".to_string(),
+                    from_port: Some(22),
+                    to_port: Some(22),
+                    sources: vec!["0.0.0.0/0".to_string()],
+                }],
+            },
+        );
+
+        let instance = instance.with_security_group_rules(&described);
+        assert_eq!(instance.security_group_ids().collect::<Vec<_>>(), vec!["sg-1"]);
+        assert_eq!(instance.security_groups()[0].open_rules().len(), 1);
     }
-}
\ No newline at end of file
+}
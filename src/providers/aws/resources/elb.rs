@@ -102,6 +102,7 @@ impl CloudResource for ELBLoadBalancer {
         match self.state.as_str() {
             "active" => ResourceState::Running,
             "provisioning" => ResourceState::Pending,
+            "active_impaired" => ResourceState::Degraded,
             "failed" => ResourceState::Error,
             _ => ResourceState::Unknown,
         }
@@ -120,7 +121,21 @@ impl CloudResource for ELBLoadBalancer {
     }
 
     fn supported_actions(&self) -> Vec<Action> {
-        vec![Action::ViewDetails, Action::Terminate]
+        vec![Action::ViewDetails, Action::Tag, Action::Terminate]
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = vec![
+            ("Type".to_string(), self.lb_type.clone()),
+            ("Scheme".to_string(), self.scheme.clone()),
+        ];
+        if let Some(ref dns_name) = self.dns_name {
+            details.push(("DNS name".to_string(), dns_name.clone()));
+        }
+        if !self.availability_zones.is_empty() {
+            details.push(("Zones".to_string(), self.availability_zones.join(", ")));
+        }
+        details
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -211,6 +226,7 @@ mod tests {
         let actions = lb.supported_actions();
         assert!(actions.contains(&Action::ViewDetails));
         assert!(actions.contains(&Action::Terminate));
+        assert!(actions.contains(&Action::Tag));
         assert!(!actions.contains(&Action::Start));
     }
 }
\ No newline at end of file
@@ -2,10 +2,12 @@ pub mod ec2;
 pub mod rds;
 pub mod s3;
 pub mod elb;
+pub mod network;
 pub mod route53;
 
 pub use ec2::EC2Instance;
 pub use rds::RDSInstance;
-pub use s3::S3Bucket;
+pub use s3::{PublicAccessBlock, S3Bucket};
 pub use elb::ELBLoadBalancer;
+pub use network::{NetworkInterface, SecurityGroup, SecurityGroupRule};
 pub use route53::Route53Zone;
\ No newline at end of file
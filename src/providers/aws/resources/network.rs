@@ -0,0 +1,161 @@
+use aws_sdk_ec2::types::{
+    GroupIdentifier, InstanceNetworkInterface, IpPermission, SecurityGroup as Ec2SecurityGroup,
+};
+
+const WORLD_CIDRS: [&str; 2] = ["0.0.0.0/0", "::/0"];
+
+/// An inbound rule of a security group.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecurityGroupRule {
+    /// IP protocol ("tcp", "udp", "icmp", or "-1" for all)
+    pub protocol: String,
+    pub from_port: Option<i32>,
+    pub to_port: Option<i32>,
+    /// CIDR blocks, security group IDs, or prefix lists allowed in
+    pub sources: Vec<String>,
+}
+
+impl SecurityGroupRule {
+    pub fn from_aws_permission(permission: &IpPermission) -> Self {
+        let mut sources: Vec<String> = Vec::new();
+        sources.extend(permission.ip_ranges().iter().filter_map(|r| r.cidr_ip().map(String::from)));
+        sources.extend(permission.ipv6_ranges().iter().filter_map(|r| r.cidr_ipv6().map(String::from)));
+        sources.extend(
+            permission
+                .user_id_group_pairs()
+                .iter()
+                .filter_map(|p| p.group_id().map(String::from)),
+        );
+        sources.extend(
+            permission
+                .prefix_list_ids()
+                .iter()
+                .filter_map(|p| p.prefix_list_id().map(String::from)),
+        );
+
+        Self {
+            protocol: permission.ip_protocol().unwrap_or("-1").to_string(),
+            from_port: permission.from_port(),
+            to_port: permission.to_port(),
+            sources,
+        }
+    }
+
+    /// Returns true if the rule allows traffic from any address.
+    pub fn is_open_to_world(&self) -> bool {
+        self.sources.iter().any(|s| WORLD_CIDRS.contains(&s.as_str()))
+    }
+
+    /// Formats the protocol and ports, e.g. "tcp/22", "tcp/8000-8080", or "all".
+    pub fn port_range(&self) -> String {
+        if self.protocol == "-1" {
+            return "all".to_string();
+        }
+
+        match (self.from_port, self.to_port) {
+            (Some(-1), _) | (None, _) => self.protocol.clone(),
+            (Some(from), Some(to)) if from == to => format!("{}/{}", self.protocol, from),
+            (Some(0), Some(65535)) => format!("{}/all", self.protocol),
+            (Some(from), Some(to)) => format!("{}/{}-{}", self.protocol, from, to),
+            (Some(from), None) => format!("{}/{}", self.protocol, from),
+        }
+    }
+}
+
+/// A security group attached to an instance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecurityGroup {
+    pub id: String,
+    pub name: String,
+    /// Inbound rules; empty until rules have been fetched
+    pub ingress: Vec<SecurityGroupRule>,
+}
+
+impl SecurityGroup {
+    pub fn from_aws_identifier(group: &GroupIdentifier) -> Self {
+        Self {
+            id: group.group_id().unwrap_or_default().to_string(),
+            name: group.group_name().unwrap_or_default().to_string(),
+            ingress: Vec::new(),
+        }
+    }
+
+    pub fn from_aws_group(group: &Ec2SecurityGroup) -> Self {
+        Self {
+            id: group.group_id().unwrap_or_default().to_string(),
+            name: group.group_name().unwrap_or_default().to_string(),
+            ingress: group
+                .ip_permissions()
+                .iter()
+                .map(SecurityGroupRule::from_aws_permission)
+                .collect(),
+        }
+    }
+
+    /// Returns the inbound rules reachable from the internet.
+    pub fn open_rules(&self) -> Vec<&SecurityGroupRule> {
+        self.ingress.iter().filter(|rule| rule.is_open_to_world()).collect()
+    }
+}
+
+/// A network interface (ENI) attached to an instance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkInterface {
+    pub id: String,
+    pub subnet_id: Option<String>,
+    pub private_ip: Option<String>,
+    pub public_ip: Option<String>,
+}
+
+impl NetworkInterface {
+    pub fn from_aws_interface(interface: &InstanceNetworkInterface) -> Self {
+        Self {
+            id: interface.network_interface_id().unwrap_or_default().to_string(),
+            subnet_id: interface.subnet_id().map(String::from),
+            private_ip: interface.private_ip_address().map(String::from),
+            public_ip: interface
+                .association()
+                .and_then(|a| a.public_ip())
+                .map(String::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(protocol: &str, from: Option<i32>, to: Option<i32>, sources: &[&str]) -> SecurityGroupRule {
+        SecurityGroupRule {
+            protocol: protocol.to_string(),
+            from_port: from,
+            to_port: to,
+            sources: sources.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_rule_port_range() {
+        assert_eq!(rule("tcp", Some(22), Some(22), &[]).port_range(), "tcp/22");
+        assert_eq!(rule("tcp", Some(8000), Some(8080), &[]).port_range(), "tcp/8000-8080");
+        assert_eq!(rule("tcp", Some(0), Some(65535), &[]).port_range(), "tcp/all");
+        assert_eq!(rule("icmp", Some(-1), Some(-1), &[]).port_range(), "icmp");
+        assert_eq!(rule("-1", None, None, &[]).port_range(), "all");
+    }
+
+    #[test]
+    fn test_open_rules() {
+        let group = SecurityGroup {
+            id: "sg-1".to_string(),
+            name: "web".to_string(),
+            ingress: vec![
+                rule("tcp", Some(443), Some(443), &["0.0.0.0/0"]),
+                rule("tcp", Some(22), Some(22), &["10.0.0.0/8"]),
+                rule("tcp", Some(80), Some(80), &["::/0"]),
+            ],
+        };
+
+        let open: Vec<String> = group.open_rules().iter().map(|r| r.port_range()).collect();
+        assert_eq!(open, vec!["tcp/443", "tcp/80"]);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType};
+use crate::core::{Action, CloudResource, Provider, ResourceHealth, ResourceState, ResourceType, HOURS_PER_MONTH};
 use aws_sdk_rds::types::DbInstance;
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
@@ -17,6 +17,7 @@ pub struct RDSInstance {
     port: Option<i32>,
     storage_gb: Option<i32>,
     multi_az: bool,
+    hourly_price: Option<f64>,
 }
 
 impl RDSInstance {
@@ -79,9 +80,15 @@ impl RDSInstance {
             port,
             storage_gb,
             multi_az,
+            hourly_price: None,
         }
     }
 
+    pub fn with_hourly_price(mut self, price: Option<f64>) -> Self {
+        self.hourly_price = price;
+        self
+    }
+
     pub fn engine(&self) -> &str {
         &self.engine
     }
@@ -139,14 +146,24 @@ impl CloudResource for RDSInstance {
             "stopping" => ResourceState::Stopping,
             "starting" => ResourceState::Starting,
             "creating" => ResourceState::Pending,
-            "deleting" => ResourceState::Stopping,
+            "deleting" => ResourceState::Deleting,
+            "maintenance" | "upgrading" | "modifying" | "storage-optimization" => ResourceState::Maintenance,
+            "storage-full" | "incompatible-parameters" | "incompatible-network" => ResourceState::Degraded,
+            "backing-up" | "configuring-enhanced-monitoring" | "renaming" => ResourceState::Running,
+            "rebooting" => ResourceState::Starting,
             "failed" | "inaccessible-encryption-credentials" => ResourceState::Error,
             _ => ResourceState::Unknown,
         }
     }
 
     fn cost_per_month(&self) -> Option<f64> {
-        Some(estimate_rds_cost(&self.instance_class, self.storage_gb, self.multi_az))
+        match self.hourly_price {
+            Some(price) => {
+                let storage_cost = estimate_rds_storage_cost(self.storage_gb, self.multi_az);
+                Some(price * HOURS_PER_MONTH + storage_cost)
+            }
+            None => Some(estimate_rds_cost(&self.instance_class, self.storage_gb, self.multi_az)),
+        }
     }
 
     fn tags(&self) -> &HashMap<String, String> {
@@ -159,13 +176,20 @@ impl CloudResource for RDSInstance {
 
     fn supported_actions(&self) -> Vec<Action> {
         match self.state() {
-            ResourceState::Running => vec![
+            ResourceState::Running | ResourceState::Degraded => vec![
                 Action::Stop,
                 Action::Restart,
+                Action::Snapshot,
+                Action::Terminate,
+                Action::ViewDetails,
+                Action::Tag,
+            ],
+            ResourceState::Stopped => vec![
+                Action::Start,
                 Action::Terminate,
                 Action::ViewDetails,
+                Action::Tag,
             ],
-            ResourceState::Stopped => vec![Action::Start, Action::Terminate, Action::ViewDetails],
             ResourceState::Pending | ResourceState::Stopping | ResourceState::Starting => {
                 vec![Action::ViewDetails]
             }
@@ -173,6 +197,40 @@ impl CloudResource for RDSInstance {
         }
     }
 
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = vec![
+            ("Engine".to_string(), format!("{} {}", self.engine, self.engine_version)),
+            ("Instance class".to_string(), self.instance_class.clone()),
+        ];
+        if let Some(ref endpoint) = self.endpoint {
+            let endpoint = match self.port {
+                Some(port) => format!("{}:{}", endpoint, port),
+                None => endpoint.clone(),
+            };
+            details.push(("Endpoint".to_string(), endpoint));
+        }
+        if let Some(storage_gb) = self.storage_gb {
+            details.push(("Storage".to_string(), format!("{} GB", storage_gb)));
+        }
+        details.push(("Multi-AZ".to_string(), if self.multi_az { "Yes" } else { "No" }.to_string()));
+        details
+    }
+
+    fn health(&self) -> Option<ResourceHealth> {
+        match self.state.as_str() {
+            "available" => Some(ResourceHealth::healthy()),
+            "storage-full" => Some(ResourceHealth::impaired("Storage is full")),
+            "failed" | "inaccessible-encryption-credentials" | "incompatible-network"
+            | "incompatible-parameters" | "incompatible-restore" => {
+                Some(ResourceHealth::impaired(format!("Status: {}", self.state)))
+            }
+            "maintenance" | "upgrading" | "storage-optimization" | "rebooting" | "modifying" => {
+                Some(ResourceHealth::warning(format!("Status: {}", self.state)))
+            }
+            _ => None,
+        }
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -204,6 +262,16 @@ fn estimate_rds_cost(instance_class: &str, storage_gb: Option<i32>, multi_az: bo
     }
 }
 
+fn estimate_rds_storage_cost(storage_gb: Option<i32>, multi_az: bool) -> f64 {
+    let storage_cost = storage_gb.map(|gb| gb as f64 * 0.115).unwrap_or(0.0);
+
+    if multi_az {
+        storage_cost * 2.0
+    } else {
+        storage_cost
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,10 +302,51 @@ mod tests {
             port: None,
             storage_gb: None,
             multi_az: false,
+            hourly_price: None,
         };
 
         assert_eq!(instance.state(), ResourceState::Running);
         assert_eq!(instance.resource_type(), ResourceType::Database);
+
+        let mut instance = instance;
+        for (status, expected) in [
+            ("deleting", ResourceState::Deleting),
+            ("upgrading", ResourceState::Maintenance),
+            ("storage-full", ResourceState::Degraded),
+            ("backing-up", ResourceState::Running),
+        ] {
+            instance.state = status.to_string();
+            assert_eq!(instance.state(), expected, "{}", status);
+        }
+    }
+
+    #[test]
+    fn test_rds_details() {
+        let instance = RDSInstance {
+            db_instance_id: "db-123".to_string(),
+            name: "test-db".to_string(),
+            engine: "postgres".to_string(),
+            engine_version: "14.7".to_string(),
+            instance_class: "db.t3.micro".to_string(),
+            state: "available".to_string(),
+            region: "us-east-1".to_string(),
+            tags: HashMap::new(),
+            created_at: None,
+            endpoint: Some("test-db.abc.us-east-1.rds.amazonaws.com".to_string()),
+            port: Some(5432),
+            storage_gb: Some(20),
+            multi_az: false,
+            hourly_price: None,
+        };
+
+        let details = instance.details();
+        assert_eq!(details[0], ("Engine".to_string(), "postgres 14.7".to_string()));
+        assert!(details.contains(&(
+            "Endpoint".to_string(),
+            "test-db.abc.us-east-1.rds.amazonaws.com:5432".to_string()
+        )));
+        assert_eq!(details.last().unwrap(), &("Multi-AZ".to_string(), "No".to_string()));
+        assert_eq!(instance.health(), Some(ResourceHealth::healthy()));
     }
 
     #[test]
@@ -256,15 +365,24 @@ mod tests {
             port: None,
             storage_gb: None,
             multi_az: false,
+            hourly_price: None,
         };
 
         let actions = instance.supported_actions();
         assert!(actions.contains(&Action::Stop));
         assert!(actions.contains(&Action::Restart));
+        assert!(actions.contains(&Action::Snapshot));
 
         instance.state = "stopped".to_string();
         let actions = instance.supported_actions();
         assert!(actions.contains(&Action::Start));
         assert!(!actions.contains(&Action::Stop));
     }
+
+    #[test]
+    fn test_estimate_rds_storage_cost() {
+        assert_eq!(estimate_rds_storage_cost(None, false), 0.0);
+        assert_eq!(estimate_rds_storage_cost(Some(100), false), 11.5);
+        assert_eq!(estimate_rds_storage_cost(Some(100), true), 23.0);
+    }
 }
\ No newline at end of file
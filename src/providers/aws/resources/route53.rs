@@ -83,7 +83,18 @@ impl CloudResource for Route53Zone {
     }
 
     fn supported_actions(&self) -> Vec<Action> {
-        vec![Action::ViewDetails, Action::Terminate]
+        vec![Action::ViewDetails, Action::ViewRecords, Action::Tag, Action::Terminate]
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = vec![
+            ("Domain".to_string(), self.name.clone()),
+            ("Visibility".to_string(), if self.is_private { "Private" } else { "Public" }.to_string()),
+        ];
+        if let Some(count) = self.resource_record_set_count {
+            details.push(("Records".to_string(), count.to_string()));
+        }
+        details
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -162,7 +173,9 @@ mod tests {
 
         let actions = zone.supported_actions();
         assert!(actions.contains(&Action::ViewDetails));
+        assert!(actions.contains(&Action::ViewRecords));
         assert!(actions.contains(&Action::Terminate));
+        assert!(actions.contains(&Action::Tag));
         assert!(!actions.contains(&Action::Start));
     }
 }
\ No newline at end of file
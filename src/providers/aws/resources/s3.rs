@@ -1,7 +1,26 @@
 use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType};
+use crate::core::metrics::format_bytes;
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 
+/// Bucket-level Public Access Block settings.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PublicAccessBlock {
+    pub block_public_acls: bool,
+    pub ignore_public_acls: bool,
+    pub block_public_policy: bool,
+    pub restrict_public_buckets: bool,
+}
+
+impl PublicAccessBlock {
+    pub fn all_blocked(&self) -> bool {
+        self.block_public_acls
+            && self.ignore_public_acls
+            && self.block_public_policy
+            && self.restrict_public_buckets
+    }
+}
+
 pub struct S3Bucket {
     name: String,
     region: String,
@@ -9,6 +28,8 @@ pub struct S3Bucket {
     tags: HashMap<String, String>,
     size_bytes: Option<u64>,
     object_count: Option<u64>,
+    public_access_block: Option<PublicAccessBlock>,
+    policy_is_public: Option<bool>,
 }
 
 impl S3Bucket {
@@ -25,6 +46,8 @@ impl S3Bucket {
             tags,
             size_bytes: None,
             object_count: None,
+            public_access_block: None,
+            policy_is_public: None,
         }
     }
 
@@ -34,6 +57,25 @@ impl S3Bucket {
         self
     }
 
+    pub fn with_public_access(
+        mut self,
+        public_access_block: Option<PublicAccessBlock>,
+        policy_is_public: Option<bool>,
+    ) -> Self {
+        self.public_access_block = public_access_block;
+        self.policy_is_public = policy_is_public;
+        self
+    }
+
+    pub fn public_access_block(&self) -> Option<PublicAccessBlock> {
+        self.public_access_block
+    }
+
+    /// Whether the bucket policy grants public access, if it could be determined.
+    pub fn policy_is_public(&self) -> Option<bool> {
+        self.policy_is_public
+    }
+
     pub fn size_bytes(&self) -> Option<u64> {
         self.size_bytes
     }
@@ -85,7 +127,27 @@ impl CloudResource for S3Bucket {
     }
 
     fn supported_actions(&self) -> Vec<Action> {
-        vec![Action::ViewDetails, Action::Terminate]
+        vec![Action::ViewDetails, Action::Tag, Action::Terminate]
+    }
+
+    fn is_publicly_accessible(&self) -> bool {
+        // RestrictPublicBuckets neutralises a public policy
+        let restricted = self
+            .public_access_block
+            .map(|block| block.restrict_public_buckets)
+            .unwrap_or(false);
+        self.policy_is_public == Some(true) && !restricted
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = Vec::new();
+        if let Some(size_bytes) = self.size_bytes {
+            details.push(("Size".to_string(), format_bytes(size_bytes as f64)));
+        }
+        if let Some(object_count) = self.object_count {
+            details.push(("Objects".to_string(), object_count.to_string()));
+        }
+        details
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -147,6 +209,31 @@ mod tests {
         assert!(bucket.cost_per_month().is_some());
     }
 
+    #[test]
+    fn test_s3_bucket_public_access() {
+        let bucket = || {
+            S3Bucket::new(
+                "my-bucket".to_string(),
+                "us-east-1".to_string(),
+                None,
+                HashMap::new(),
+            )
+        };
+
+        assert!(!bucket().is_publicly_accessible());
+        assert!(!bucket().with_public_access(None, Some(false)).is_publicly_accessible());
+        assert!(bucket().with_public_access(None, Some(true)).is_publicly_accessible());
+
+        let restricted = PublicAccessBlock {
+            restrict_public_buckets: true,
+            ..Default::default()
+        };
+        assert!(!restricted.all_blocked());
+        assert!(!bucket()
+            .with_public_access(Some(restricted), Some(true))
+            .is_publicly_accessible());
+    }
+
     #[test]
     fn test_s3_supported_actions() {
         let bucket = S3Bucket::new(
@@ -159,6 +246,7 @@ mod tests {
         let actions = bucket.supported_actions();
         assert!(actions.contains(&Action::ViewDetails));
         assert!(actions.contains(&Action::Terminate));
+        assert!(actions.contains(&Action::Tag));
         assert!(!actions.contains(&Action::Start));
     }
 }
\ No newline at end of file
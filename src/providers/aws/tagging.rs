@@ -0,0 +1,253 @@
+use crate::core::{Action, ResourceType, TagChanges};
+use crate::error::{NimbusError, Result};
+use super::client::AwsClient;
+use std::collections::HashMap;
+
+/// Applies tag changes using each service's tagging API.
+pub struct AwsTagger<'a> {
+    client: &'a AwsClient,
+}
+
+impl<'a> AwsTagger<'a> {
+    pub fn new(client: &'a AwsClient) -> Self {
+        Self { client }
+    }
+
+    pub async fn apply(&self, resource_id: &str, resource_type: ResourceType, changes: &TagChanges) -> Result<()> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        match resource_type {
+            ResourceType::Compute => self.tag_ec2(resource_id, changes).await,
+            ResourceType::Database => self.tag_rds(resource_id, changes).await,
+            ResourceType::Storage => self.tag_s3(resource_id, changes).await,
+            ResourceType::LoadBalancer => self.tag_elb(resource_id, changes).await,
+            ResourceType::DNS => self.tag_route53(resource_id, changes).await,
+            _ => Err(NimbusError::UnsupportedAction(Action::Tag, resource_type)),
+        }
+    }
+
+    async fn tag_ec2(&self, instance_id: &str, changes: &TagChanges) -> Result<()> {
+        use aws_sdk_ec2::types::Tag;
+
+        if !changes.set.is_empty() {
+            let tags = changes
+                .set
+                .iter()
+                .map(|(key, value)| Tag::builder().key(key).value(value).build())
+                .collect();
+
+            self.client
+                .ec2
+                .create_tags()
+                .resources(instance_id)
+                .set_tags(Some(tags))
+                .send()
+                .await
+                .map_err(|e| Self::error(instance_id, e))?;
+        }
+
+        if !changes.remove.is_empty() {
+            let tags = changes
+                .remove
+                .iter()
+                .map(|key| Tag::builder().key(key).build())
+                .collect();
+
+            self.client
+                .ec2
+                .delete_tags()
+                .resources(instance_id)
+                .set_tags(Some(tags))
+                .send()
+                .await
+                .map_err(|e| Self::error(instance_id, e))?;
+        }
+
+        Ok(())
+    }
+
+    async fn tag_rds(&self, db_instance_id: &str, changes: &TagChanges) -> Result<()> {
+        use aws_sdk_rds::types::Tag;
+
+        // RDS tagging APIs take the instance ARN rather than its identifier
+        let response = self
+            .client
+            .rds
+            .describe_db_instances()
+            .db_instance_identifier(db_instance_id)
+            .send()
+            .await
+            .map_err(|e| Self::error(db_instance_id, e))?;
+
+        let arn = response
+            .db_instances()
+            .first()
+            .and_then(|db| db.db_instance_arn())
+            .ok_or_else(|| NimbusError::ResourceNotFound(db_instance_id.to_string()))?
+            .to_string();
+
+        if !changes.set.is_empty() {
+            let tags = changes
+                .set
+                .iter()
+                .map(|(key, value)| Tag::builder().key(key).value(value).build())
+                .collect();
+
+            self.client
+                .rds
+                .add_tags_to_resource()
+                .resource_name(&arn)
+                .set_tags(Some(tags))
+                .send()
+                .await
+                .map_err(|e| Self::error(db_instance_id, e))?;
+        }
+
+        if !changes.remove.is_empty() {
+            self.client
+                .rds
+                .remove_tags_from_resource()
+                .resource_name(&arn)
+                .set_tag_keys(Some(changes.remove.clone()))
+                .send()
+                .await
+                .map_err(|e| Self::error(db_instance_id, e))?;
+        }
+
+        Ok(())
+    }
+
+    async fn tag_s3(&self, bucket: &str, changes: &TagChanges) -> Result<()> {
+        use aws_sdk_s3::error::ProvideErrorMetadata;
+        use aws_sdk_s3::types::{Tag, Tagging};
+
+        // S3 replaces the whole tag set, so merge with the current tags first.
+        // Buckets without tags report NoSuchTagSet, which means an empty set.
+        let current: HashMap<String, String> = match self.client.s3.get_bucket_tagging().bucket(bucket).send().await {
+            Ok(response) => response
+                .tag_set()
+                .iter()
+                .map(|tag| (tag.key().to_string(), tag.value().to_string()))
+                .collect(),
+            Err(e) => {
+                let is_untagged = e
+                    .as_service_error()
+                    .and_then(|err| err.code())
+                    .map(|code| code == "NoSuchTagSet")
+                    .unwrap_or(false);
+                if !is_untagged {
+                    return Err(Self::error(bucket, e));
+                }
+                HashMap::new()
+            }
+        };
+
+        let desired = changes.apply_to(&current);
+
+        if desired.is_empty() {
+            self.client
+                .s3
+                .delete_bucket_tagging()
+                .bucket(bucket)
+                .send()
+                .await
+                .map_err(|e| Self::error(bucket, e))?;
+            return Ok(());
+        }
+
+        let mut tag_set = Vec::with_capacity(desired.len());
+        for (key, value) in &desired {
+            tag_set.push(
+                Tag::builder()
+                    .key(key)
+                    .value(value)
+                    .build()
+                    .map_err(|e| NimbusError::provider("AWS", format!("Invalid tag {}: {}", key, e)))?,
+            );
+        }
+
+        let tagging = Tagging::builder()
+            .set_tag_set(Some(tag_set))
+            .build()
+            .map_err(|e| NimbusError::provider("AWS", format!("Invalid tag set: {}", e)))?;
+
+        self.client
+            .s3
+            .put_bucket_tagging()
+            .bucket(bucket)
+            .tagging(tagging)
+            .send()
+            .await
+            .map_err(|e| Self::error(bucket, e))?;
+
+        Ok(())
+    }
+
+    async fn tag_elb(&self, arn: &str, changes: &TagChanges) -> Result<()> {
+        use aws_sdk_elasticloadbalancingv2::types::Tag;
+
+        if !changes.set.is_empty() {
+            let tags = changes
+                .set
+                .iter()
+                .map(|(key, value)| Tag::builder().key(key).value(value).build())
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(|e| NimbusError::provider("AWS", format!("Invalid tag: {}", e)))?;
+
+            self.client
+                .elb
+                .add_tags()
+                .resource_arns(arn)
+                .set_tags(Some(tags))
+                .send()
+                .await
+                .map_err(|e| Self::error(arn, e))?;
+        }
+
+        if !changes.remove.is_empty() {
+            self.client
+                .elb
+                .remove_tags()
+                .resource_arns(arn)
+                .set_tag_keys(Some(changes.remove.clone()))
+                .send()
+                .await
+                .map_err(|e| Self::error(arn, e))?;
+        }
+
+        Ok(())
+    }
+
+    async fn tag_route53(&self, zone_id: &str, changes: &TagChanges) -> Result<()> {
+        use aws_sdk_route53::types::{Tag, TagResourceType};
+
+        let add_tags: Vec<Tag> = changes
+            .set
+            .iter()
+            .map(|(key, value)| Tag::builder().key(key).value(value).build())
+            .collect();
+
+        let mut request = self
+            .client
+            .route53
+            .change_tags_for_resource()
+            .resource_type(TagResourceType::Hostedzone)
+            .resource_id(zone_id.trim_start_matches("/hostedzone/"));
+
+        if !add_tags.is_empty() {
+            request = request.set_add_tags(Some(add_tags));
+        }
+        if !changes.remove.is_empty() {
+            request = request.set_remove_tag_keys(Some(changes.remove.clone()));
+        }
+
+        request.send().await.map_err(|e| Self::error(zone_id, e))?;
+        Ok(())
+    }
+
+    fn error<E: std::fmt::Display>(resource_id: &str, e: E) -> NimbusError {
+        NimbusError::provider("AWS", format!("Failed to update tags on {}: {}", resource_id, e))
+    }
+}
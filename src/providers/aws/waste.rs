@@ -0,0 +1,244 @@
+use crate::core::{WasteFinding, WasteKind, HOURS_PER_MONTH};
+use crate::error::{NimbusError, Result};
+use aws_sdk_ec2::types::{Filter, Tag, VolumeType};
+use aws_sdk_ec2::Client as Ec2Client;
+use aws_sdk_s3::types::BucketVersioningStatus;
+use aws_sdk_s3::Client as S3Client;
+use std::collections::HashMap;
+
+/// Hourly charge for a public IPv4 address, attached or not
+const ADDRESS_HOURLY_PRICE: f64 = 0.005;
+
+/// Finds EC2 and S3 resources that are billed while sitting idle.
+pub struct AwsWasteScanner {
+    ec2: Ec2Client,
+    s3: S3Client,
+    region: String,
+}
+
+impl AwsWasteScanner {
+    pub fn new(ec2: Ec2Client, s3: S3Client, region: impl Into<String>) -> Self {
+        Self {
+            ec2,
+            s3,
+            region: region.into(),
+        }
+    }
+
+    /// Returns volumes that are not attached to any instance.
+    pub async fn unattached_volumes(&self) -> Result<Vec<WasteFinding>> {
+        let mut pages = self
+            .ec2
+            .describe_volumes()
+            .filters(Filter::builder().name("status").values("available").build())
+            .into_paginator()
+            .send();
+
+        let mut findings = Vec::new();
+        while let Some(page) = pages.next().await {
+            let page = page.map_err(|e| {
+                NimbusError::provider("AWS", format!("Failed to describe volumes: {}", e))
+            })?;
+
+            for volume in page.volumes() {
+                let volume_id = match volume.volume_id() {
+                    Some(id) => id,
+                    None => continue,
+                };
+                let size = volume.size().unwrap_or(0);
+                findings.push(
+                    WasteFinding::new(
+                        WasteKind::UnattachedVolume,
+                        volume_id,
+                        name_tag(volume.tags()).unwrap_or(volume_id),
+                        &self.region,
+                    )
+                    .with_monthly_cost(volume_monthly_cost(volume.volume_type(), size))
+                    .with_detail(format!("{} GiB", size)),
+                );
+            }
+        }
+
+        Ok(findings)
+    }
+
+    /// Returns stopped instances whose EBS volumes are still billed, with
+    /// the monthly cost of those volumes.
+    pub async fn stopped_instances_with_volumes(&self) -> Result<Vec<WasteFinding>> {
+        let mut pages = self
+            .ec2
+            .describe_instances()
+            .filters(Filter::builder().name("instance-state-name").values("stopped").build())
+            .into_paginator()
+            .send();
+
+        let mut names = HashMap::new();
+        while let Some(page) = pages.next().await {
+            let page = page.map_err(|e| {
+                NimbusError::provider("AWS", format!("Failed to describe instances: {}", e))
+            })?;
+
+            for instance in page.reservations().iter().flat_map(|r| r.instances()) {
+                if let Some(instance_id) = instance.instance_id() {
+                    let name = name_tag(instance.tags()).unwrap_or(instance_id);
+                    names.insert(instance_id.to_string(), name.to_string());
+                }
+            }
+        }
+        if names.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut filter = Filter::builder().name("attachment.instance-id");
+        for instance_id in names.keys() {
+            filter = filter.values(instance_id);
+        }
+        let mut pages = self.ec2.describe_volumes().filters(filter.build()).into_paginator().send();
+
+        // Per instance: volume count, total GiB, monthly cost
+        let mut volumes: HashMap<String, (usize, i32, f64)> = HashMap::new();
+        while let Some(page) = pages.next().await {
+            let page = page.map_err(|e| {
+                NimbusError::provider("AWS", format!("Failed to describe volumes: {}", e))
+            })?;
+
+            for volume in page.volumes() {
+                let size = volume.size().unwrap_or(0);
+                for instance_id in volume.attachments().iter().filter_map(|a| a.instance_id()) {
+                    let entry = volumes.entry(instance_id.to_string()).or_default();
+                    entry.0 += 1;
+                    entry.1 += size;
+                    entry.2 += volume_monthly_cost(volume.volume_type(), size);
+                }
+            }
+        }
+
+        let mut findings: Vec<WasteFinding> = volumes
+            .into_iter()
+            .map(|(instance_id, (count, size, cost))| {
+                let name = names.get(&instance_id).cloned().unwrap_or_else(|| instance_id.clone());
+                WasteFinding::new(WasteKind::StoppedWithVolumes, instance_id, name, &self.region)
+                    .with_monthly_cost(cost)
+                    .with_detail(format!("{} volume(s), {} GiB", count, size))
+            })
+            .collect();
+        findings.sort_by(|a, b| a.resource_id.cmp(&b.resource_id));
+
+        Ok(findings)
+    }
+
+    /// Returns Elastic IPs that are not associated with an instance or
+    /// network interface.
+    pub async fn unassociated_addresses(&self) -> Result<Vec<WasteFinding>> {
+        let response = self.ec2.describe_addresses().send().await.map_err(|e| {
+            NimbusError::provider("AWS", format!("Failed to describe addresses: {}", e))
+        })?;
+
+        let findings = response
+            .addresses()
+            .iter()
+            .filter(|address| address.association_id().is_none())
+            .filter_map(|address| {
+                let public_ip = address.public_ip()?;
+                let id = address.allocation_id().unwrap_or(public_ip);
+                Some(
+                    WasteFinding::new(
+                        WasteKind::UnattachedAddress,
+                        id,
+                        name_tag(address.tags()).unwrap_or(public_ip),
+                        &self.region,
+                    )
+                    .with_monthly_cost(ADDRESS_HOURLY_PRICE * HOURS_PER_MONTH)
+                    .with_detail(public_ip),
+                )
+            })
+            .collect();
+
+        Ok(findings)
+    }
+
+    /// Returns buckets that have versioning on but no current objects. Their
+    /// noncurrent versions are still billed, so no saving is estimated.
+    pub async fn empty_versioned_buckets(&self) -> Result<Vec<WasteFinding>> {
+        let response = self.s3.list_buckets().send().await.map_err(|e| {
+            NimbusError::provider("AWS", format!("Failed to list S3 buckets: {}", e))
+        })?;
+
+        let mut findings = Vec::new();
+        for name in response.buckets().iter().filter_map(|b| b.name()) {
+            // Buckets in other regions reject requests from this client, so
+            // they are left to a scan of their own region
+            let versioning = match self.s3.get_bucket_versioning().bucket(name).send().await {
+                Ok(versioning) => versioning,
+                Err(e) => {
+                    log::debug!("Could not read versioning for {}: {}", name, e);
+                    continue;
+                }
+            };
+            let status = match versioning.status() {
+                Some(status @ (BucketVersioningStatus::Enabled | BucketVersioningStatus::Suspended)) => status,
+                _ => continue,
+            };
+
+            let objects = match self.s3.list_objects_v2().bucket(name).max_keys(1).send().await {
+                Ok(objects) => objects,
+                Err(e) => {
+                    log::debug!("Could not list objects in {}: {}", name, e);
+                    continue;
+                }
+            };
+            if objects.contents().is_empty() {
+                findings.push(
+                    WasteFinding::new(WasteKind::EmptyVersionedBucket, name, name, &self.region)
+                        .with_detail(format!("Versioning {}", status.as_str().to_lowercase())),
+                );
+            }
+        }
+
+        Ok(findings)
+    }
+}
+
+fn name_tag(tags: &[Tag]) -> Option<&str> {
+    tags.iter()
+        .find(|tag| tag.key() == Some("Name"))
+        .and_then(|tag| tag.value())
+        .filter(|name| !name.is_empty())
+}
+
+/// Estimates the monthly storage cost of an EBS volume from us-east-1 list
+/// prices. Provisioned IOPS and throughput are not included.
+fn volume_monthly_cost(volume_type: Option<&VolumeType>, size_gib: i32) -> f64 {
+    let per_gib = match volume_type {
+        Some(VolumeType::Gp2) => 0.10,
+        Some(VolumeType::Io1) | Some(VolumeType::Io2) => 0.125,
+        Some(VolumeType::St1) => 0.045,
+        Some(VolumeType::Sc1) => 0.015,
+        Some(VolumeType::Standard) => 0.05,
+        _ => 0.08,
+    };
+    per_gib * size_gib.max(0) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_volume_monthly_cost() {
+        assert_eq!(volume_monthly_cost(Some(&VolumeType::Gp3), 100), 8.0);
+        assert_eq!(volume_monthly_cost(Some(&VolumeType::Gp2), 50), 5.0);
+        assert_eq!(volume_monthly_cost(None, 10), 0.8);
+        assert_eq!(volume_monthly_cost(Some(&VolumeType::Sc1), -1), 0.0);
+    }
+
+    #[test]
+    fn test_name_tag() {
+        let tags = vec![
+            Tag::builder().key("env").value("prod").build(),
+            Tag::builder().key("Name").value("web-data").build(),
+        ];
+        assert_eq!(name_tag(&tags), Some("web-data"));
+        assert_eq!(name_tag(&[Tag::builder().key("Name").value("").build()]), None);
+    }
+}
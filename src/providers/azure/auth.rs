@@ -0,0 +1,42 @@
+use crate::config::AzureConfig;
+use crate::error::{NimbusError, Result};
+use azure_core::auth::TokenCredential;
+use azure_identity::{AzureCliCredential, ClientSecretCredential};
+use std::sync::Arc;
+
+/// Token scope for the Azure Resource Manager API.
+pub const MANAGEMENT_SCOPE: &str = "https://management.azure.com/.default";
+
+pub struct AzureAuth;
+
+impl AzureAuth {
+    /// Creates a credential from the configured service principal, falling
+    /// back to the account signed in with `az login`.
+    pub fn create_credential(azure_config: &AzureConfig) -> Arc<dyn TokenCredential> {
+        match (
+            &azure_config.tenant_id,
+            &azure_config.client_id,
+            &azure_config.client_secret,
+        ) {
+            (Some(tenant_id), Some(client_id), Some(client_secret)) => {
+                Arc::new(ClientSecretCredential::new(
+                    azure_core::new_http_client(),
+                    azure_core::authority_hosts::AZURE_PUBLIC_CLOUD.clone(),
+                    tenant_id.clone(),
+                    client_id.clone(),
+                    client_secret.clone(),
+                ))
+            }
+            _ => Arc::new(AzureCliCredential::new()),
+        }
+    }
+
+    /// Verifies the credential by requesting a management token.
+    pub async fn test_credentials(credential: &dyn TokenCredential) -> Result<()> {
+        credential
+            .get_token(&[MANAGEMENT_SCOPE])
+            .await
+            .map(|_| ())
+            .map_err(|e| NimbusError::auth("Azure", format!("Failed to obtain an access token: {}", e)))
+    }
+}
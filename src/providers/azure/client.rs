@@ -0,0 +1,187 @@
+use crate::error::{NimbusError, Result};
+use azure_core::auth::TokenCredential;
+use reqwest::{RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use super::auth::MANAGEMENT_SCOPE;
+
+/// Base URL of the Azure Resource Manager API.
+pub const MANAGEMENT_API: &str = "https://management.azure.com";
+
+/// Thin authenticated wrapper over the Azure Resource Manager REST API.
+pub struct AzureClient {
+    http: reqwest::Client,
+    credential: Arc<dyn TokenCredential>,
+    subscription_id: String,
+}
+
+/// One page of an ARM list operation.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Page<T> {
+    #[serde(default = "Vec::new")]
+    value: Vec<T>,
+    next_link: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: ErrorDetail,
+}
+
+#[derive(Deserialize)]
+struct ErrorDetail {
+    code: String,
+    message: String,
+}
+
+impl AzureClient {
+    pub fn new(credential: Arc<dyn TokenCredential>, subscription_id: String) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            credential,
+            subscription_id,
+        }
+    }
+
+    pub fn subscription_id(&self) -> &str {
+        &self.subscription_id
+    }
+
+    /// Builds the URL of a subscription-scoped path, e.g.
+    /// `/providers/Microsoft.Compute/virtualMachines`.
+    pub fn subscription_url(&self, path: &str) -> String {
+        format!("{}/subscriptions/{}{}", MANAGEMENT_API, self.subscription_id, path)
+    }
+
+    /// Builds the URL of a resource from its ARM ID.
+    pub fn resource_url(&self, resource_id: &str) -> String {
+        format!("{}{}", MANAGEMENT_API, resource_id)
+    }
+
+    /// Fetches a single resource. `query` must include the `api-version`.
+    pub async fn get<T: DeserializeOwned>(&self, url: &str, query: &[(&str, &str)]) -> Result<T> {
+        let response = self.send(self.http.get(url).query(query)).await?;
+        response
+            .json()
+            .await
+            .map_err(|e| NimbusError::provider("Azure", format!("Invalid response from {}: {}", url, e)))
+    }
+
+    /// Fetches every page of a list operation by following `nextLink`.
+    pub async fn list<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        let mut response = self.send(self.http.get(url).query(query)).await?;
+
+        loop {
+            let page: Page<T> = response
+                .json()
+                .await
+                .map_err(|e| NimbusError::provider("Azure", format!("Invalid response from {}: {}", url, e)))?;
+            items.extend(page.value);
+
+            // The next link already carries the api-version and skip token
+            match page.next_link {
+                Some(next) if !next.is_empty() => response = self.send(self.http.get(&next)).await?,
+                _ => break,
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// Sends a body-less POST, as used by resource operations such as `start`.
+    /// Long-running operations are accepted and left to complete on their own.
+    pub async fn post(&self, url: &str, api_version: &str) -> Result<()> {
+        self.send(
+            self.http
+                .post(url)
+                .query(&[("api-version", api_version)])
+                .header(reqwest::header::CONTENT_LENGTH, 0),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    pub async fn patch<B: Serialize>(&self, url: &str, api_version: &str, body: &B) -> Result<()> {
+        self.send(self.http.patch(url).query(&[("api-version", api_version)]).json(body))
+            .await
+            .map(|_| ())
+    }
+
+    pub async fn delete(&self, url: &str, api_version: &str) -> Result<()> {
+        self.send(self.http.delete(url).query(&[("api-version", api_version)]))
+            .await
+            .map(|_| ())
+    }
+
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let token = self
+            .credential
+            .get_token(&[MANAGEMENT_SCOPE])
+            .await
+            .map_err(|e| NimbusError::auth("Azure", format!("Failed to obtain an access token: {}", e)))?;
+
+        let response = request
+            .bearer_auth(token.token.secret())
+            .send()
+            .await
+            .map_err(|e| NimbusError::provider("Azure", format!("Request failed: {}", e)))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Err(NimbusError::ResourceNotFound(error_message(&body)));
+        }
+        Err(NimbusError::provider(
+            "Azure",
+            format!("{} ({})", error_message(&body), status.as_u16()),
+        ))
+    }
+}
+
+/// Extracts the code and message from an ARM error body.
+fn error_message(body: &str) -> String {
+    match serde_json::from_str::<ErrorResponse>(body) {
+        Ok(response) => format!("{}: {}", response.error.code, response.error.message),
+        Err(_) if body.trim().is_empty() => "Empty error response".to_string(),
+        Err(_) => body.trim().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_message() {
+        let body = r#"{"error": {"code": "ResourceNotFound", "message": "The Resource 'Microsoft.Compute/virtualMachines/web' under resource group 'prod' was not found."}}"#;
+        assert_eq!(
+            error_message(body),
+            "ResourceNotFound: The Resource 'Microsoft.Compute/virtualMachines/web' under resource group 'prod' was not found."
+        );
+        assert_eq!(error_message("Bad Gateway"), "Bad Gateway");
+        assert_eq!(error_message(""), "Empty error response");
+    }
+
+    #[test]
+    fn test_page_parsing() {
+        let page: Page<serde_json::Value> = serde_json::from_value(serde_json::json!({
+            "value": [{ "name": "a" }, { "name": "b" }],
+            "nextLink": "https://management.azure.com/subscriptions/s/providers/x?$skiptoken=abc"
+        }))
+        .unwrap();
+        assert_eq!(page.value.len(), 2);
+        assert!(page.next_link.is_some());
+    }
+}
@@ -0,0 +1,900 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::config::{parse_resource_types, AzureConfig};
+use crate::core::{
+    collect_stream, send_batch_if, AccountIdentity, Action, ActionRequest, CloudProvider, CloudResource,
+    CostBreakdown, CostPeriod, DnsRecord, OperationHandle, Provider, ProviderCapabilities, ResourceSender,
+    ResourceType, TagChanges,
+};
+use crate::error::{NimbusError, Result};
+use std::collections::HashMap;
+
+mod auth;
+mod client;
+mod monitor;
+pub mod resources;
+
+use auth::AzureAuth;
+use client::AzureClient;
+use monitor::AzureMonitor;
+use resources::aks::ManagedCluster;
+use resources::app_gateway::ApplicationGateway;
+use resources::dns::{is_private_zone, DnsZone, RecordSet};
+use resources::flexible_server::FlexibleServer;
+use resources::function_app::{normalize_location, Site};
+use resources::loadbalancer::LoadBalancer;
+use resources::sql::SqlDatabase;
+use resources::storage::StorageAccount;
+use resources::vm::VirtualMachine;
+use resources::{
+    AzureAksCluster, AzureApplicationGateway, AzureDnsZone, AzureFlexibleServer, AzureFunctionApp,
+    AzureLoadBalancer, AzureSqlDatabase, AzureStorageAccount, AzureVM,
+};
+
+const COMPUTE_API_VERSION: &str = "2024-03-01";
+const SQL_API_VERSION: &str = "2021-11-01";
+const POSTGRES_API_VERSION: &str = "2022-12-01";
+const MYSQL_API_VERSION: &str = "2023-06-30";
+const STORAGE_API_VERSION: &str = "2023-01-01";
+const NETWORK_API_VERSION: &str = "2023-09-01";
+const DNS_API_VERSION: &str = "2018-05-01";
+const PRIVATE_DNS_API_VERSION: &str = "2020-06-01";
+const TAGS_API_VERSION: &str = "2021-04-01";
+const AKS_API_VERSION: &str = "2024-02-01";
+const WEB_API_VERSION: &str = "2023-01-01";
+const SUBSCRIPTIONS_API_VERSION: &str = "2022-12-01";
+
+/// Regions offered before the subscription's own list has been discovered.
+const FALLBACK_REGIONS: [&str; 12] = [
+    "eastus",
+    "eastus2",
+    "centralus",
+    "westus2",
+    "westus3",
+    "canadacentral",
+    "northeurope",
+    "westeurope",
+    "uksouth",
+    "southeastasia",
+    "japaneast",
+    "australiaeast",
+];
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Subscription {
+    subscription_id: String,
+    display_name: String,
+    state: String,
+}
+
+#[derive(Deserialize)]
+struct SqlServer {
+    id: String,
+    location: String,
+}
+
+#[derive(Deserialize)]
+struct Location {
+    name: String,
+    metadata: Option<LocationMetadata>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LocationMetadata {
+    region_type: Option<String>,
+}
+
+pub struct AzureProvider {
+    name: String,
+    config: AzureConfig,
+    client: Option<AzureClient>,
+    subscription_name: Option<String>,
+    /// Regions available to the subscription, discovered after authentication
+    discovered_regions: Option<Vec<String>>,
+}
+
+impl AzureProvider {
+    pub fn new(config: AzureConfig) -> Self {
+        let name = match config.label {
+            Some(ref label) => format!("Azure ({})", label),
+            None => "Azure".to_string(),
+        };
+
+        Self {
+            name,
+            config,
+            client: None,
+            subscription_name: None,
+            discovered_regions: None,
+        }
+    }
+
+    async fn ensure_authenticated(&self) -> Result<()> {
+        if self.client.is_none() {
+            return Err(NimbusError::auth(
+                "Azure",
+                "Provider not authenticated. Call authenticate() first.",
+            ));
+        }
+        Ok(())
+    }
+
+    fn get_client(&self) -> Result<&AzureClient> {
+        self.client.as_ref().ok_or_else(|| {
+            NimbusError::auth(
+                "Azure",
+                "Client not initialized. Call authenticate() first.",
+            )
+        })
+    }
+
+    /// Resolves the subscription to manage: the configured one, else the only
+    /// enabled subscription the credential can see.
+    async fn resolve_subscription(&self, client: &AzureClient) -> Result<Subscription> {
+        if !self.config.subscription_id.is_empty() {
+            let url = client.subscription_url("");
+            return client
+                .get(&url, &[("api-version", SUBSCRIPTIONS_API_VERSION)])
+                .await
+                .map_err(|e| {
+                    NimbusError::auth(
+                        "Azure",
+                        format!("Cannot access subscription {}: {}", self.config.subscription_id, e),
+                    )
+                });
+        }
+
+        let url = format!("{}/subscriptions", client::MANAGEMENT_API);
+        let subscriptions: Vec<Subscription> = client
+            .list(&url, &[("api-version", SUBSCRIPTIONS_API_VERSION)])
+            .await?;
+
+        let mut enabled = subscriptions.into_iter().filter(|s| s.state == "Enabled");
+        match (enabled.next(), enabled.next()) {
+            (Some(subscription), None) => Ok(subscription),
+            _ => Err(NimbusError::MissingConfig("providers.azure.subscription_id".to_string())),
+        }
+    }
+
+    async fn discover_regions(&self) -> Result<Vec<String>> {
+        let client = self.get_client()?;
+        let url = client.subscription_url("/locations");
+        let locations: Vec<Location> = client
+            .list(&url, &[("api-version", SUBSCRIPTIONS_API_VERSION)])
+            .await?;
+
+        // Logical locations such as "global" or "europe" cannot host resources
+        Ok(locations
+            .into_iter()
+            .filter(|location| {
+                location
+                    .metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.region_type.as_deref())
+                    == Some("Physical")
+            })
+            .map(|location| location.name)
+            .collect())
+    }
+
+    async fn list_virtual_machines(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let client = self.get_client()?;
+        let url = client.subscription_url("/providers/Microsoft.Compute/virtualMachines");
+
+        // statusOnly adds each VM's instance view, which carries the power state
+        let vms: Vec<VirtualMachine> = client
+            .list(&url, &[("api-version", COMPUTE_API_VERSION), ("statusOnly", "true")])
+            .await
+            .map_err(|e| NimbusError::provider("Azure", format!("Failed to list virtual machines: {}", e)))?;
+
+        Ok(vms
+            .iter()
+            .filter(|vm| vm.location.eq_ignore_ascii_case(&self.config.region))
+            .map(|vm| Box::new(AzureVM::from_api(vm)) as Box<dyn CloudResource>)
+            .collect())
+    }
+
+    async fn execute_vm_action(&self, resource_id: &str, action: Action) -> Result<()> {
+        let client = self.get_client()?;
+        let url = client.resource_url(resource_id);
+
+        // Stopping deallocates, as a VM that is only powered off is still billed
+        let (result, verb) = match action {
+            Action::Start => (client.post(&format!("{}/start", url), COMPUTE_API_VERSION).await, "start"),
+            Action::Stop => (
+                client.post(&format!("{}/deallocate", url), COMPUTE_API_VERSION).await,
+                "deallocate",
+            ),
+            Action::Restart => (
+                client.post(&format!("{}/restart", url), COMPUTE_API_VERSION).await,
+                "restart",
+            ),
+            Action::Terminate => (client.delete(&url, COMPUTE_API_VERSION).await, "delete"),
+            _ => return Err(NimbusError::UnsupportedAction(action, ResourceType::Compute)),
+        };
+
+        result.map_err(|e| {
+            NimbusError::provider("Azure", format!("Failed to {} VM {}: {}", verb, resource_id, e))
+        })
+    }
+
+    async fn list_databases(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let (sql, postgres, mysql) = tokio::join!(
+            self.list_sql_databases(),
+            self.list_flexible_servers("Microsoft.DBforPostgreSQL", POSTGRES_API_VERSION),
+            self.list_flexible_servers("Microsoft.DBforMySQL", MYSQL_API_VERSION),
+        );
+
+        let mut databases = sql?;
+        databases.extend(postgres?);
+        databases.extend(mysql?);
+        Ok(databases)
+    }
+
+    async fn list_sql_databases(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let client = self.get_client()?;
+        let url = client.subscription_url("/providers/Microsoft.Sql/servers");
+        let servers: Vec<SqlServer> = client
+            .list(&url, &[("api-version", SQL_API_VERSION)])
+            .await
+            .map_err(|e| NimbusError::provider("Azure", format!("Failed to list SQL servers: {}", e)))?;
+
+        let mut databases: Vec<Box<dyn CloudResource>> = Vec::new();
+
+        for server in servers
+            .iter()
+            .filter(|server| server.location.eq_ignore_ascii_case(&self.config.region))
+        {
+            let url = format!("{}/databases", client.resource_url(&server.id));
+            let server_databases: Vec<SqlDatabase> = client
+                .list(&url, &[("api-version", SQL_API_VERSION)])
+                .await
+                .map_err(|e| {
+                    NimbusError::provider("Azure", format!("Failed to list databases on {}: {}", server.id, e))
+                })?;
+
+            // Every server has a system master database, which is free
+            databases.extend(
+                server_databases
+                    .iter()
+                    .filter(|database| database.name != "master")
+                    .map(|database| Box::new(AzureSqlDatabase::from_api(database)) as Box<dyn CloudResource>),
+            );
+        }
+
+        Ok(databases)
+    }
+
+    async fn list_flexible_servers(
+        &self,
+        namespace: &str,
+        api_version: &str,
+    ) -> Result<Vec<Box<dyn CloudResource>>> {
+        let client = self.get_client()?;
+        let url = client.subscription_url(&format!("/providers/{}/flexibleServers", namespace));
+        let servers: Vec<FlexibleServer> = client
+            .list(&url, &[("api-version", api_version)])
+            .await
+            .map_err(|e| {
+                NimbusError::provider("Azure", format!("Failed to list {} flexible servers: {}", namespace, e))
+            })?;
+
+        Ok(servers
+            .iter()
+            .filter(|server| server.location.eq_ignore_ascii_case(&self.config.region))
+            .map(|server| Box::new(AzureFlexibleServer::from_api(server)) as Box<dyn CloudResource>)
+            .collect())
+    }
+
+    async fn list_storage_accounts(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let client = self.get_client()?;
+        let url = client.subscription_url("/providers/Microsoft.Storage/storageAccounts");
+        let accounts: Vec<StorageAccount> = client
+            .list(&url, &[("api-version", STORAGE_API_VERSION)])
+            .await
+            .map_err(|e| NimbusError::provider("Azure", format!("Failed to list storage accounts: {}", e)))?;
+
+        let monitor = AzureMonitor::new(client);
+        let mut storage_accounts: Vec<Box<dyn CloudResource>> = Vec::new();
+
+        for account in accounts
+            .iter()
+            .filter(|account| account.location.eq_ignore_ascii_case(&self.config.region))
+        {
+            let storage_account = AzureStorageAccount::from_api(account);
+            let storage_account = match monitor.blob_capacity(&account.id).await {
+                Ok(Some(bytes)) => storage_account.with_blob_capacity(bytes),
+                Ok(None) => storage_account,
+                Err(e) => {
+                    log::warn!("Skipping blob capacity for {}: {}", account.name, e);
+                    storage_account
+                }
+            };
+            storage_accounts.push(Box::new(storage_account));
+        }
+
+        Ok(storage_accounts)
+    }
+
+    async fn execute_storage_action(&self, resource_id: &str, action: Action) -> Result<()> {
+        let client = self.get_client()?;
+
+        match action {
+            Action::Terminate => {
+                let url = client.resource_url(resource_id);
+                client.delete(&url, STORAGE_API_VERSION).await.map_err(|e| {
+                    NimbusError::provider("Azure", format!("Failed to delete storage account {}: {}", resource_id, e))
+                })
+            }
+            _ => Err(NimbusError::UnsupportedAction(action, ResourceType::Storage)),
+        }
+    }
+
+    async fn list_load_balancers(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let client = self.get_client()?;
+        let lb_url = client.subscription_url("/providers/Microsoft.Network/loadBalancers");
+        let gateway_url = client.subscription_url("/providers/Microsoft.Network/applicationGateways");
+        let query = [("api-version", NETWORK_API_VERSION)];
+
+        let (load_balancers, gateways) = tokio::join!(
+            client.list::<LoadBalancer>(&lb_url, &query),
+            client.list::<ApplicationGateway>(&gateway_url, &query),
+        );
+        let load_balancers = load_balancers
+            .map_err(|e| NimbusError::provider("Azure", format!("Failed to list load balancers: {}", e)))?;
+        let gateways = gateways
+            .map_err(|e| NimbusError::provider("Azure", format!("Failed to list application gateways: {}", e)))?;
+
+        let mut resources: Vec<Box<dyn CloudResource>> = load_balancers
+            .iter()
+            .filter(|lb| lb.location.eq_ignore_ascii_case(&self.config.region))
+            .map(|lb| Box::new(AzureLoadBalancer::from_api(lb)) as Box<dyn CloudResource>)
+            .collect();
+        resources.extend(
+            gateways
+                .iter()
+                .filter(|gateway| gateway.location.eq_ignore_ascii_case(&self.config.region))
+                .map(|gateway| Box::new(AzureApplicationGateway::from_api(gateway)) as Box<dyn CloudResource>),
+        );
+
+        Ok(resources)
+    }
+
+    async fn execute_lb_action(&self, resource_id: &str, action: Action) -> Result<()> {
+        let client = self.get_client()?;
+        let url = client.resource_url(resource_id);
+        let is_gateway = resource_id.contains("/applicationGateways/");
+
+        let (result, verb) = match action {
+            Action::Start if is_gateway => (client.post(&format!("{}/start", url), NETWORK_API_VERSION).await, "start"),
+            Action::Stop if is_gateway => (client.post(&format!("{}/stop", url), NETWORK_API_VERSION).await, "stop"),
+            Action::Terminate => (client.delete(&url, NETWORK_API_VERSION).await, "delete"),
+            _ => return Err(NimbusError::UnsupportedAction(action, ResourceType::LoadBalancer)),
+        };
+
+        result.map_err(|e| {
+            NimbusError::provider("Azure", format!("Failed to {} {}: {}", verb, resource_id, e))
+        })
+    }
+
+    async fn list_dns_zones(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let client = self.get_client()?;
+        let public_url = client.subscription_url("/providers/Microsoft.Network/dnszones");
+        let private_url = client.subscription_url("/providers/Microsoft.Network/privateDnsZones");
+
+        let (public, private) = tokio::join!(
+            client.list::<DnsZone>(&public_url, &[("api-version", DNS_API_VERSION)]),
+            client.list::<DnsZone>(&private_url, &[("api-version", PRIVATE_DNS_API_VERSION)]),
+        );
+        let mut zones = public
+            .map_err(|e| NimbusError::provider("Azure", format!("Failed to list DNS zones: {}", e)))?;
+        zones.extend(
+            private.map_err(|e| NimbusError::provider("Azure", format!("Failed to list private DNS zones: {}", e)))?,
+        );
+
+        // Zones are global like Route53, so they are not filtered by region
+        Ok(zones
+            .iter()
+            .map(|zone| Box::new(AzureDnsZone::from_api(zone)) as Box<dyn CloudResource>)
+            .collect())
+    }
+
+    async fn list_record_sets(&self, zone_id: &str) -> Result<Vec<DnsRecord>> {
+        let client = self.get_client()?;
+
+        let (url, api_version) = if is_private_zone(zone_id) {
+            (format!("{}/ALL", client.resource_url(zone_id)), PRIVATE_DNS_API_VERSION)
+        } else {
+            (format!("{}/recordsets", client.resource_url(zone_id)), DNS_API_VERSION)
+        };
+
+        let record_sets: Vec<RecordSet> = client
+            .list(&url, &[("api-version", api_version)])
+            .await
+            .map_err(|e| {
+                NimbusError::provider("Azure", format!("Failed to list record sets for {}: {}", zone_id, e))
+            })?;
+
+        Ok(record_sets.into_iter().map(DnsRecord::from).collect())
+    }
+
+    async fn execute_dns_action(&self, resource_id: &str, action: Action) -> Result<()> {
+        let client = self.get_client()?;
+
+        match action {
+            Action::Terminate => {
+                let api_version = if is_private_zone(resource_id) {
+                    PRIVATE_DNS_API_VERSION
+                } else {
+                    DNS_API_VERSION
+                };
+                let url = client.resource_url(resource_id);
+                client.delete(&url, api_version).await.map_err(|e| {
+                    NimbusError::provider("Azure", format!("Failed to delete DNS zone {}: {}", resource_id, e))
+                })
+            }
+            _ => Err(NimbusError::UnsupportedAction(action, ResourceType::DNS)),
+        }
+    }
+
+    async fn list_aks_clusters(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let client = self.get_client()?;
+        let url = client.subscription_url("/providers/Microsoft.ContainerService/managedClusters");
+        let clusters: Vec<ManagedCluster> = client
+            .list(&url, &[("api-version", AKS_API_VERSION)])
+            .await
+            .map_err(|e| NimbusError::provider("Azure", format!("Failed to list AKS clusters: {}", e)))?;
+
+        Ok(clusters
+            .iter()
+            .filter(|cluster| cluster.location.eq_ignore_ascii_case(&self.config.region))
+            .map(|cluster| Box::new(AzureAksCluster::from_api(cluster)) as Box<dyn CloudResource>)
+            .collect())
+    }
+
+    async fn execute_aks_action(&self, resource_id: &str, action: Action) -> Result<()> {
+        let client = self.get_client()?;
+        let url = client.resource_url(resource_id);
+
+        // Stopping deallocates the node pools and control plane until started again
+        let (result, verb) = match action {
+            Action::Start => (client.post(&format!("{}/start", url), AKS_API_VERSION).await, "start"),
+            Action::Stop => (client.post(&format!("{}/stop", url), AKS_API_VERSION).await, "stop"),
+            Action::Terminate => (client.delete(&url, AKS_API_VERSION).await, "delete"),
+            _ => return Err(NimbusError::UnsupportedAction(action, ResourceType::Container)),
+        };
+
+        result.map_err(|e| {
+            NimbusError::provider("Azure", format!("Failed to {} AKS cluster {}: {}", verb, resource_id, e))
+        })
+    }
+
+    async fn list_function_apps(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let client = self.get_client()?;
+        let url = client.subscription_url("/providers/Microsoft.Web/sites");
+        let sites: Vec<Site> = client
+            .list(&url, &[("api-version", WEB_API_VERSION)])
+            .await
+            .map_err(|e| NimbusError::provider("Azure", format!("Failed to list function apps: {}", e)))?;
+
+        // Web apps share the sites endpoint, and report display locations
+        Ok(sites
+            .iter()
+            .filter(|site| site.is_function_app())
+            .filter(|site| normalize_location(&site.location).eq_ignore_ascii_case(&self.config.region))
+            .map(|site| Box::new(AzureFunctionApp::from_api(site)) as Box<dyn CloudResource>)
+            .collect())
+    }
+
+    async fn execute_function_action(&self, resource_id: &str, action: Action) -> Result<()> {
+        let client = self.get_client()?;
+        let url = client.resource_url(resource_id);
+
+        let (result, verb) = match action {
+            Action::Start => (client.post(&format!("{}/start", url), WEB_API_VERSION).await, "start"),
+            Action::Stop => (client.post(&format!("{}/stop", url), WEB_API_VERSION).await, "stop"),
+            Action::Terminate => (client.delete(&url, WEB_API_VERSION).await, "delete"),
+            _ => return Err(NimbusError::UnsupportedAction(action, ResourceType::Serverless)),
+        };
+
+        result.map_err(|e| {
+            NimbusError::provider("Azure", format!("Failed to {} function app {}: {}", verb, resource_id, e))
+        })
+    }
+
+    async fn execute_database_action(&self, resource_id: &str, action: Action) -> Result<()> {
+        let client = self.get_client()?;
+        let url = client.resource_url(resource_id);
+
+        if resource_id.contains("/providers/Microsoft.Sql/") {
+            return match action {
+                Action::Terminate => client.delete(&url, SQL_API_VERSION).await.map_err(|e| {
+                    NimbusError::provider("Azure", format!("Failed to delete database {}: {}", resource_id, e))
+                }),
+                _ => Err(NimbusError::UnsupportedAction(action, ResourceType::Database)),
+            };
+        }
+
+        let api_version = flexible_server_api_version(resource_id);
+        let (result, verb) = match action {
+            Action::Start => (client.post(&format!("{}/start", url), api_version).await, "start"),
+            Action::Stop => (client.post(&format!("{}/stop", url), api_version).await, "stop"),
+            Action::Terminate => (client.delete(&url, api_version).await, "delete"),
+            _ => return Err(NimbusError::UnsupportedAction(action, ResourceType::Database)),
+        };
+
+        result.map_err(|e| {
+            NimbusError::provider("Azure", format!("Failed to {} server {}: {}", verb, resource_id, e))
+        })
+    }
+}
+
+fn flexible_server_api_version(resource_id: &str) -> &'static str {
+    if resource_id.contains("/providers/Microsoft.DBforMySQL/") {
+        MYSQL_API_VERSION
+    } else {
+        POSTGRES_API_VERSION
+    }
+}
+
+#[async_trait]
+impl CloudProvider for AzureProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn provider_type(&self) -> Provider {
+        Provider::Azure
+    }
+
+    fn account_name(&self) -> &str {
+        self.config
+            .label
+            .as_deref()
+            .or(self.subscription_name.as_deref())
+            .unwrap_or(&self.config.subscription_id)
+    }
+
+    async fn authenticate(&mut self) -> Result<()> {
+        let credential = AzureAuth::create_credential(&self.config);
+        AzureAuth::test_credentials(credential.as_ref()).await?;
+
+        let client = AzureClient::new(credential.clone(), self.config.subscription_id.clone());
+        let subscription = self.resolve_subscription(&client).await?;
+
+        if self.config.label.is_none() {
+            self.name = format!("Azure ({})", subscription.display_name);
+        }
+        self.subscription_name = Some(subscription.display_name);
+        self.client = Some(AzureClient::new(credential, subscription.subscription_id));
+
+        // The region list is subscription-wide, so switching regions reuses it
+        if self.discovered_regions.is_none() {
+            match self.discover_regions().await {
+                Ok(regions) if !regions.is_empty() => self.discovered_regions = Some(regions),
+                Ok(_) => {}
+                Err(e) => log::warn!("Using built-in Azure region list: {}", e),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn identity(&self) -> Option<AccountIdentity> {
+        let subscription_id = self.client.as_ref()?.subscription_id();
+        Some(
+            AccountIdentity::new(subscription_id, format!("/subscriptions/{}", subscription_id))
+                .with_alias(self.subscription_name.clone()),
+        )
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities::none()
+            .with_resource_types(&[
+                ResourceType::Compute,
+                ResourceType::Database,
+                ResourceType::Storage,
+                ResourceType::LoadBalancer,
+                ResourceType::DNS,
+                ResourceType::Container,
+                ResourceType::Serverless,
+            ])
+            .restrict_resource_types(&parse_resource_types(&self.config.resource_types).unwrap_or_default())
+            .with_actions(&[
+                Action::Start,
+                Action::Stop,
+                Action::Restart,
+                Action::Terminate,
+                Action::ViewRecords,
+                Action::Tag,
+            ])
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+        let url = client.subscription_url("");
+
+        match client
+            .get::<serde_json::Value>(&url, &[("api-version", SUBSCRIPTIONS_API_VERSION)])
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(e) => Err(NimbusError::provider(
+                "Azure",
+                format!("Connection test failed: {}", e),
+            )),
+        }
+    }
+
+    async fn list_all_resources(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        collect_stream(self).await
+    }
+
+    async fn stream_all_resources(&self, sender: ResourceSender) -> Result<()> {
+        self.ensure_authenticated().await?;
+        let caps = self.capabilities();
+
+        tokio::join!(
+            send_batch_if(&sender, &caps, ResourceType::Compute, "Virtual Machines", self.list_virtual_machines()),
+            send_batch_if(&sender, &caps, ResourceType::Database, "SQL Database", self.list_databases()),
+            send_batch_if(&sender, &caps, ResourceType::Storage, "Storage", self.list_storage_accounts()),
+            send_batch_if(&sender, &caps, ResourceType::LoadBalancer, "Load Balancer", self.list_load_balancers()),
+            send_batch_if(&sender, &caps, ResourceType::DNS, "DNS", self.list_dns_zones()),
+            send_batch_if(&sender, &caps, ResourceType::Container, "AKS", self.list_aks_clusters()),
+            send_batch_if(&sender, &caps, ResourceType::Serverless, "Functions", self.list_function_apps()),
+        );
+
+        Ok(())
+    }
+
+    async fn list_resources_by_type(
+        &self,
+        resource_type: ResourceType,
+    ) -> Result<Vec<Box<dyn CloudResource>>> {
+        if !self.capabilities().supports_type(resource_type) {
+            return Ok(Vec::new());
+        }
+        self.ensure_authenticated().await?;
+
+        match resource_type {
+            ResourceType::Compute => self.list_virtual_machines().await,
+            ResourceType::Database => self.list_databases().await,
+            ResourceType::Storage => self.list_storage_accounts().await,
+            ResourceType::LoadBalancer => self.list_load_balancers().await,
+            ResourceType::DNS => self.list_dns_zones().await,
+            ResourceType::Container => self.list_aks_clusters().await,
+            ResourceType::Serverless => self.list_function_apps().await,
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    async fn get_resource(&self, id: &str) -> Result<Box<dyn CloudResource>> {
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+
+        if id.contains("/providers/Microsoft.Compute/virtualMachines/") {
+            let url = client.resource_url(id);
+            let vm: VirtualMachine = client
+                .get(&url, &[("api-version", COMPUTE_API_VERSION), ("$expand", "instanceView")])
+                .await?;
+            return Ok(Box::new(AzureVM::from_api(&vm)));
+        }
+
+        if id.contains("/providers/Microsoft.Sql/") {
+            let url = client.resource_url(id);
+            let database: SqlDatabase = client.get(&url, &[("api-version", SQL_API_VERSION)]).await?;
+            return Ok(Box::new(AzureSqlDatabase::from_api(&database)));
+        }
+
+        if id.contains("/flexibleServers/") {
+            let url = client.resource_url(id);
+            let server: FlexibleServer = client
+                .get(&url, &[("api-version", flexible_server_api_version(id))])
+                .await?;
+            return Ok(Box::new(AzureFlexibleServer::from_api(&server)));
+        }
+
+        if id.contains("/providers/Microsoft.Storage/storageAccounts/") {
+            let url = client.resource_url(id);
+            let account: StorageAccount = client.get(&url, &[("api-version", STORAGE_API_VERSION)]).await?;
+            return Ok(Box::new(AzureStorageAccount::from_api(&account)));
+        }
+
+        if id.contains("/providers/Microsoft.Network/loadBalancers/") {
+            let url = client.resource_url(id);
+            let lb: LoadBalancer = client.get(&url, &[("api-version", NETWORK_API_VERSION)]).await?;
+            return Ok(Box::new(AzureLoadBalancer::from_api(&lb)));
+        }
+
+        if id.contains("/providers/Microsoft.Network/applicationGateways/") {
+            let url = client.resource_url(id);
+            let gateway: ApplicationGateway = client.get(&url, &[("api-version", NETWORK_API_VERSION)]).await?;
+            return Ok(Box::new(AzureApplicationGateway::from_api(&gateway)));
+        }
+
+        if id.contains("/providers/Microsoft.ContainerService/managedClusters/") {
+            let url = client.resource_url(id);
+            let cluster: ManagedCluster = client.get(&url, &[("api-version", AKS_API_VERSION)]).await?;
+            return Ok(Box::new(AzureAksCluster::from_api(&cluster)));
+        }
+
+        if id.contains("/providers/Microsoft.Web/sites/") {
+            let url = client.resource_url(id);
+            let site: Site = client.get(&url, &[("api-version", WEB_API_VERSION)]).await?;
+            return Ok(Box::new(AzureFunctionApp::from_api(&site)));
+        }
+
+        let lower_id = id.to_ascii_lowercase();
+        if lower_id.contains("/providers/microsoft.network/dnszones/")
+            || lower_id.contains("/providers/microsoft.network/privatednszones/")
+        {
+            let api_version = if is_private_zone(id) {
+                PRIVATE_DNS_API_VERSION
+            } else {
+                DNS_API_VERSION
+            };
+            let url = client.resource_url(id);
+            let zone: DnsZone = client.get(&url, &[("api-version", api_version)]).await?;
+            return Ok(Box::new(AzureDnsZone::from_api(&zone)));
+        }
+
+        Err(NimbusError::ResourceNotFound(id.to_string()))
+    }
+
+    async fn execute_action(
+        &self,
+        resource_id: &str,
+        resource_type: ResourceType,
+        request: &ActionRequest,
+    ) -> Result<OperationHandle> {
+        self.ensure_authenticated().await?;
+        let action = request.action;
+
+        match resource_type {
+            ResourceType::Compute => self.execute_vm_action(resource_id, action).await,
+            ResourceType::Database => self.execute_database_action(resource_id, action).await,
+            ResourceType::Storage => self.execute_storage_action(resource_id, action).await,
+            ResourceType::LoadBalancer => self.execute_lb_action(resource_id, action).await,
+            ResourceType::DNS => self.execute_dns_action(resource_id, action).await,
+            ResourceType::Container => self.execute_aks_action(resource_id, action).await,
+            ResourceType::Serverless => self.execute_function_action(resource_id, action).await,
+            other => Err(NimbusError::UnsupportedAction(action, other)),
+        }?;
+
+        Ok(OperationHandle::new(resource_id, resource_type, request))
+    }
+
+    async fn update_tags(
+        &self,
+        resource_id: &str,
+        _resource_type: ResourceType,
+        changes: &TagChanges,
+    ) -> Result<()> {
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+
+        // The Tags API works on any resource, merging or deleting only the given keys
+        let url = format!("{}/providers/Microsoft.Resources/tags/default", client.resource_url(resource_id));
+
+        if !changes.set.is_empty() {
+            let body = serde_json::json!({
+                "operation": "Merge",
+                "properties": { "tags": changes.set },
+            });
+            client.patch(&url, TAGS_API_VERSION, &body).await.map_err(|e| {
+                NimbusError::provider("Azure", format!("Failed to tag {}: {}", resource_id, e))
+            })?;
+        }
+
+        if !changes.remove.is_empty() {
+            // Delete matches on key and value, so removals carry their current values
+            let resource = self.get_resource(resource_id).await?;
+            let removed: HashMap<&String, &String> = changes
+                .remove
+                .iter()
+                .filter_map(|key| resource.tags().get_key_value(key))
+                .collect();
+            let body = serde_json::json!({
+                "operation": "Delete",
+                "properties": { "tags": removed },
+            });
+            client.patch(&url, TAGS_API_VERSION, &body).await.map_err(|e| {
+                NimbusError::provider("Azure", format!("Failed to remove tags from {}: {}", resource_id, e))
+            })?;
+        }
+
+        Ok(())
+    }
+
+    async fn list_dns_records(&self, zone_id: &str) -> Result<Vec<DnsRecord>> {
+        self.ensure_authenticated().await?;
+        self.list_record_sets(zone_id).await
+    }
+
+    // Spend is not read from Cost Management yet
+    async fn get_total_cost(&self, _period: CostPeriod) -> Result<f64> {
+        Ok(0.0)
+    }
+
+    async fn get_cost_breakdown(&self) -> Result<CostBreakdown> {
+        Ok(CostBreakdown::new())
+    }
+
+    fn console_url(&self, resource_id: &str, _resource_type: ResourceType) -> Option<String> {
+        // Every resource is addressed by its full ARM id
+        if !resource_id.starts_with("/subscriptions/") {
+            return None;
+        }
+        let tenant = self.config.tenant_id.as_deref().unwrap_or_default();
+        Some(format!("https://portal.azure.com/#@{}/resource{}/overview", tenant, resource_id))
+    }
+
+    fn regions(&self) -> Vec<String> {
+        match self.discovered_regions {
+            Some(ref regions) => regions.clone(),
+            None => FALLBACK_REGIONS.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+
+    fn current_region(&self) -> &str {
+        &self.config.region
+    }
+
+    async fn set_region(&mut self, region: &str) -> Result<()> {
+        if !self.regions().contains(&region.to_string()) {
+            return Err(NimbusError::InvalidRegion(region.to_string()));
+        }
+
+        // Resource Manager is a global endpoint, so no need to re-authenticate
+        self.config.region = region.to_string();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_defaults() {
+        let provider = AzureProvider::new(AzureConfig::default());
+        assert_eq!(provider.name(), "Azure");
+        assert_eq!(provider.provider_type(), Provider::Azure);
+        assert_eq!(provider.current_region(), "eastus");
+        assert!(provider.regions().contains(&"westeurope".to_string()));
+        assert!(provider.identity().is_none());
+    }
+
+    #[test]
+    fn test_console_url() {
+        let provider = AzureProvider::new(AzureConfig {
+            tenant_id: Some("contoso.onmicrosoft.com".to_string()),
+            ..AzureConfig::default()
+        });
+        let id = "/subscriptions/sub/resourceGroups/rg/providers/Microsoft.Compute/virtualMachines/web";
+        assert_eq!(
+            provider.console_url(id, ResourceType::Compute).as_deref(),
+            Some("https://portal.azure.com/#@contoso.onmicrosoft.com/resource/subscriptions/sub/resourceGroups/rg/providers/Microsoft.Compute/virtualMachines/web/overview")
+        );
+        assert!(provider.console_url("web", ResourceType::Compute).is_none());
+    }
+
+    #[test]
+    fn test_location_parsing() {
+        let location: Location = serde_json::from_value(serde_json::json!({
+            "name": "eastus",
+            "displayName": "East US",
+            "metadata": { "regionType": "Physical", "regionCategory": "Recommended" }
+        }))
+        .unwrap();
+        assert_eq!(location.name, "eastus");
+        assert_eq!(
+            location.metadata.and_then(|metadata| metadata.region_type).as_deref(),
+            Some("Physical")
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use crate::error::Result;
+use chrono::{Duration, SecondsFormat, Utc};
+use serde::Deserialize;
+
+use super::client::AzureClient;
+
+const METRICS_API_VERSION: &str = "2023-10-01";
+
+#[derive(Deserialize)]
+struct MetricsResponse {
+    #[serde(default)]
+    value: Vec<Metric>,
+}
+
+#[derive(Deserialize)]
+struct Metric {
+    #[serde(default)]
+    timeseries: Vec<MetricTimeSeries>,
+}
+
+#[derive(Deserialize)]
+struct MetricTimeSeries {
+    #[serde(default)]
+    data: Vec<MetricValue>,
+}
+
+#[derive(Deserialize)]
+struct MetricValue {
+    average: Option<f64>,
+}
+
+pub struct AzureMonitor<'a> {
+    client: &'a AzureClient,
+}
+
+impl<'a> AzureMonitor<'a> {
+    pub fn new(client: &'a AzureClient) -> Self {
+        Self { client }
+    }
+
+    /// Fetches the bytes stored in blobs of a storage account.
+    ///
+    /// Blob capacity is reported about once an hour and can lag behind by
+    /// several, so the newest sample of the last day is used. Returns `None`
+    /// for accounts that have not reported yet.
+    pub async fn blob_capacity(&self, account_id: &str) -> Result<Option<u64>> {
+        let url = format!(
+            "{}/blobServices/default/providers/Microsoft.Insights/metrics",
+            self.client.resource_url(account_id)
+        );
+        let end = Utc::now();
+        let timespan = format!(
+            "{}/{}",
+            (end - Duration::days(1)).to_rfc3339_opts(SecondsFormat::Secs, true),
+            end.to_rfc3339_opts(SecondsFormat::Secs, true)
+        );
+
+        let response: MetricsResponse = self
+            .client
+            .get(
+                &url,
+                &[
+                    ("api-version", METRICS_API_VERSION),
+                    ("metricnames", "BlobCapacity"),
+                    ("aggregation", "Average"),
+                    ("interval", "PT1H"),
+                    ("timespan", timespan.as_str()),
+                ],
+            )
+            .await?;
+
+        Ok(latest_average(&response).map(|bytes| bytes as u64))
+    }
+}
+
+/// Returns the newest non-empty average across the response's series.
+fn latest_average(response: &MetricsResponse) -> Option<f64> {
+    // Data points are returned oldest first
+    response
+        .value
+        .iter()
+        .flat_map(|metric| &metric.timeseries)
+        .filter_map(|series| series.data.iter().rev().find_map(|value| value.average))
+        .last()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_average() {
+        let response: MetricsResponse = serde_json::from_value(serde_json::json!({
+            "value": [{
+                "name": { "value": "BlobCapacity" },
+                "timeseries": [{
+                    "data": [
+                        { "timeStamp": "2024-06-01T10:00:00Z", "average": 1024.0 },
+                        { "timeStamp": "2024-06-01T11:00:00Z", "average": 2048.0 },
+                        { "timeStamp": "2024-06-01T12:00:00Z" }
+                    ]
+                }]
+            }]
+        }))
+        .unwrap();
+        assert_eq!(latest_average(&response), Some(2048.0));
+
+        let empty: MetricsResponse = serde_json::from_value(serde_json::json!({
+            "value": [{ "timeseries": [] }]
+        }))
+        .unwrap();
+        assert_eq!(latest_average(&empty), None);
+    }
+}
@@ -0,0 +1,293 @@
+use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType, HOURS_PER_MONTH};
+use super::vm::estimate_vm_cost;
+use super::{resource_group_of, Sku};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// AKS managed cluster as returned by the Microsoft.ContainerService REST API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ManagedCluster {
+    /// Full ARM resource ID
+    pub id: String,
+    pub name: String,
+    pub location: String,
+    /// Control plane tier: `Free`, `Standard` or `Premium`
+    pub sku: Option<Sku>,
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
+    pub properties: ManagedClusterProperties,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagedClusterProperties {
+    pub current_kubernetes_version: Option<String>,
+    pub kubernetes_version: Option<String>,
+    pub provisioning_state: Option<String>,
+    pub power_state: Option<PowerState>,
+    #[serde(default)]
+    pub agent_pool_profiles: Vec<AgentPoolProfile>,
+    pub fqdn: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PowerState {
+    /// `Running` or `Stopped`
+    pub code: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentPoolProfile {
+    pub name: String,
+    pub count: Option<i64>,
+    pub vm_size: Option<String>,
+    /// `System` pools run cluster services, `User` pools run workloads
+    pub mode: Option<String>,
+    pub orchestrator_version: Option<String>,
+}
+
+pub struct AzureAksCluster {
+    /// Full ARM resource ID
+    id: String,
+    name: String,
+    resource_group: String,
+    region: String,
+    tier: String,
+    kubernetes_version: Option<String>,
+    power_state: Option<String>,
+    provisioning_state: String,
+    node_pools: Vec<AgentPoolProfile>,
+    fqdn: Option<String>,
+    tags: HashMap<String, String>,
+}
+
+impl AzureAksCluster {
+    pub fn from_api(cluster: &ManagedCluster) -> Self {
+        let properties = &cluster.properties;
+
+        Self {
+            id: cluster.id.clone(),
+            name: cluster.name.clone(),
+            resource_group: resource_group_of(&cluster.id).unwrap_or_default().to_string(),
+            region: cluster.location.clone(),
+            tier: cluster
+                .sku
+                .as_ref()
+                .and_then(|sku| sku.tier.clone())
+                .unwrap_or_else(|| "Free".to_string()),
+            kubernetes_version: properties
+                .current_kubernetes_version
+                .clone()
+                .or_else(|| properties.kubernetes_version.clone()),
+            power_state: properties.power_state.as_ref().map(|state| state.code.clone()),
+            provisioning_state: properties.provisioning_state.clone().unwrap_or_default(),
+            node_pools: properties.agent_pool_profiles.clone(),
+            fqdn: properties.fqdn.clone(),
+            tags: cluster.tags.clone(),
+        }
+    }
+
+    pub fn resource_group(&self) -> &str {
+        &self.resource_group
+    }
+
+    /// Control plane tier: `Free`, `Standard` or `Premium`.
+    pub fn tier(&self) -> &str {
+        &self.tier
+    }
+
+    /// Kubernetes version of the control plane.
+    pub fn kubernetes_version(&self) -> Option<&str> {
+        self.kubernetes_version.as_deref()
+    }
+
+    pub fn node_count(&self) -> i64 {
+        self.node_pools.iter().filter_map(|pool| pool.count).sum()
+    }
+
+    pub fn node_pool_count(&self) -> usize {
+        self.node_pools.len()
+    }
+
+    pub fn node_pools(&self) -> &[AgentPoolProfile] {
+        &self.node_pools
+    }
+
+    /// API server address.
+    pub fn fqdn(&self) -> Option<&str> {
+        self.fqdn.as_deref()
+    }
+}
+
+impl CloudResource for AzureAksCluster {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Container
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::Azure
+    }
+
+    fn region(&self) -> &str {
+        &self.region
+    }
+
+    fn state(&self) -> ResourceState {
+        // Start and stop show up as provisioning states while in progress
+        match (self.provisioning_state.as_str(), self.power_state.as_deref()) {
+            ("Starting", _) => ResourceState::Starting,
+            ("Stopping", _) => ResourceState::Stopping,
+            ("Deleting", _) => ResourceState::Deleting,
+            ("Upgrading", _) => ResourceState::Maintenance,
+            ("Creating", _) | ("Updating", _) | ("Scaling", _) => ResourceState::Pending,
+            ("Failed", _) => ResourceState::Error,
+            (_, Some("Running")) => ResourceState::Running,
+            (_, Some("Stopped")) => ResourceState::Stopped,
+            _ => ResourceState::Unknown,
+        }
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        if self.state() == ResourceState::Stopped {
+            return Some(0.0);
+        }
+
+        // Nodes run in scale sets, which are not listed as virtual machines
+        let control_plane_hourly = match self.tier.as_str() {
+            "Standard" => 0.10,
+            "Premium" => 0.60,
+            _ => 0.0,
+        };
+        let nodes: f64 = self
+            .node_pools
+            .iter()
+            .map(|pool| {
+                let size = pool.vm_size.as_deref().unwrap_or_default();
+                pool.count.unwrap_or(0) as f64 * estimate_vm_cost(size)
+            })
+            .sum();
+
+        Some(control_plane_hourly * HOURS_PER_MONTH + nodes)
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.tags
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        match self.state() {
+            ResourceState::Running => vec![Action::Stop, Action::Terminate, Action::ViewDetails],
+            ResourceState::Stopped => vec![Action::Start, Action::Terminate, Action::ViewDetails],
+            _ => vec![Action::ViewDetails],
+        }
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = vec![
+            ("Resource group".to_string(), self.resource_group.clone()),
+            ("Tier".to_string(), self.tier.clone()),
+        ];
+        if let Some(ref version) = self.kubernetes_version {
+            details.push(("Kubernetes".to_string(), version.clone()));
+        }
+        if let Some(ref fqdn) = self.fqdn {
+            details.push(("API server".to_string(), fqdn.clone()));
+        }
+        details
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::azure::resources::fixtures::arm_resource;
+    use serde_json::{json, Value};
+
+    fn cluster(fields: Value) -> AzureAksCluster {
+        AzureAksCluster::from_api(&arm_resource("Microsoft.ContainerService/managedClusters", fields))
+    }
+
+    fn cluster_in_state(power_state: &str, provisioning_state: &str) -> AzureAksCluster {
+        cluster(json!({
+            "properties": {
+                "provisioningState": provisioning_state,
+                "powerState": { "code": power_state }
+            }
+        }))
+    }
+
+    #[test]
+    fn test_aks_cluster_from_api() {
+        let cluster = cluster(json!({
+            "location": "westeurope",
+            "sku": { "name": "Base", "tier": "Standard" },
+            "properties": {
+                "kubernetesVersion": "1.29",
+                "currentKubernetesVersion": "1.29.2",
+                "provisioningState": "Succeeded",
+                "powerState": { "code": "Running" },
+                "fqdn": "web-1-dns-abc123.hcp.westeurope.azmk8s.io",
+                "agentPoolProfiles": [
+                    { "name": "system", "count": 3, "vmSize": "Standard_D2s_v3", "mode": "System" },
+                    { "name": "work", "count": 2, "vmSize": "Standard_D4s_v3", "mode": "User" }
+                ]
+            }
+        }));
+        assert_eq!(cluster.resource_group(), "prod-rg");
+        assert_eq!(cluster.region(), "westeurope");
+        assert_eq!(cluster.kubernetes_version(), Some("1.29.2"));
+        assert_eq!(cluster.node_pool_count(), 2);
+        assert_eq!(cluster.node_count(), 5);
+        assert_eq!(cluster.resource_type(), ResourceType::Container);
+        assert_eq!(cluster.state(), ResourceState::Running);
+        assert!(cluster.supported_actions().contains(&Action::Stop));
+
+        let expected = 0.10 * HOURS_PER_MONTH
+            + 3.0 * estimate_vm_cost("Standard_D2s_v3")
+            + 2.0 * estimate_vm_cost("Standard_D4s_v3");
+        assert!((cluster.cost_per_month().unwrap() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stopped_and_stopping_clusters() {
+        let stopped = cluster_in_state("Stopped", "Succeeded");
+        assert_eq!(stopped.state(), ResourceState::Stopped);
+        assert_eq!(stopped.cost_per_month(), Some(0.0));
+        assert!(stopped.supported_actions().contains(&Action::Start));
+
+        let stopping = cluster_in_state("Running", "Stopping");
+        assert_eq!(stopping.state(), ResourceState::Stopping);
+        assert_eq!(stopping.supported_actions(), vec![Action::ViewDetails]);
+    }
+
+    #[test]
+    fn test_cluster_without_power_state_or_pools() {
+        let bare = cluster(json!({ "properties": { "kubernetesVersion": "1.28" } }));
+        assert_eq!(bare.state(), ResourceState::Unknown);
+        assert_eq!(bare.tier(), "Free");
+        assert_eq!(bare.kubernetes_version(), Some("1.28"));
+        assert_eq!(bare.node_count(), 0);
+        // A free control plane with no nodes costs nothing
+        assert_eq!(bare.cost_per_month(), Some(0.0));
+
+        assert_eq!(cluster_in_state("Deallocated", "Succeeded").state(), ResourceState::Unknown);
+    }
+}
@@ -0,0 +1,284 @@
+use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType, HOURS_PER_MONTH};
+use super::loadbalancer::{last_segment, provisioning_state, FrontendIpConfiguration};
+use super::{resource_group_of, Sku};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Application gateway as returned by the Microsoft.Network REST API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApplicationGateway {
+    /// Full ARM resource ID
+    pub id: String,
+    pub name: String,
+    pub location: String,
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
+    pub properties: ApplicationGatewayProperties,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplicationGatewayProperties {
+    /// Unlike most resources, the gateway's SKU sits under its properties
+    pub sku: Option<Sku>,
+    /// `Running`, `Stopped`, `Starting` or `Stopping`
+    pub operational_state: Option<String>,
+    pub provisioning_state: Option<String>,
+    pub autoscale_configuration: Option<AutoscaleConfiguration>,
+    #[serde(default, rename = "frontendIPConfigurations")]
+    pub frontend_ip_configurations: Vec<FrontendIpConfiguration>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoscaleConfiguration {
+    pub min_capacity: Option<i64>,
+    pub max_capacity: Option<i64>,
+}
+
+pub struct AzureApplicationGateway {
+    /// Full ARM resource ID
+    id: String,
+    name: String,
+    resource_group: String,
+    region: String,
+    sku: String,
+    /// Fixed instance count, or the autoscale minimum
+    capacity: i64,
+    max_capacity: Option<i64>,
+    operational_state: Option<String>,
+    provisioning_state: String,
+    private_ips: Vec<String>,
+    public_ip_names: Vec<String>,
+    tags: HashMap<String, String>,
+}
+
+impl AzureApplicationGateway {
+    pub fn from_api(gateway: &ApplicationGateway) -> Self {
+        let properties = &gateway.properties;
+        let autoscale = properties.autoscale_configuration.as_ref();
+        let frontends = &properties.frontend_ip_configurations;
+
+        Self {
+            id: gateway.id.clone(),
+            name: gateway.name.clone(),
+            resource_group: resource_group_of(&gateway.id).unwrap_or_default().to_string(),
+            region: gateway.location.clone(),
+            sku: properties
+                .sku
+                .as_ref()
+                .map(|sku| sku.name.clone())
+                .unwrap_or_else(|| "Standard_v2".to_string()),
+            capacity: autoscale
+                .and_then(|config| config.min_capacity)
+                .or_else(|| properties.sku.as_ref().and_then(|sku| sku.capacity))
+                .unwrap_or(0),
+            max_capacity: autoscale.and_then(|config| config.max_capacity),
+            operational_state: properties.operational_state.clone(),
+            provisioning_state: properties.provisioning_state.clone().unwrap_or_default(),
+            private_ips: frontends
+                .iter()
+                .filter_map(|frontend| frontend.properties.private_ip_address.clone())
+                .collect(),
+            public_ip_names: frontends
+                .iter()
+                .filter_map(|frontend| frontend.properties.public_ip_address.as_ref())
+                .map(|public_ip| last_segment(&public_ip.id).to_string())
+                .collect(),
+            tags: gateway.tags.clone(),
+        }
+    }
+
+    pub fn resource_group(&self) -> &str {
+        &self.resource_group
+    }
+
+    /// e.g. `Standard_v2`, `WAF_v2` or the v1 `Standard_Medium`.
+    pub fn sku(&self) -> &str {
+        &self.sku
+    }
+
+    /// Whether the gateway includes the web application firewall.
+    pub fn has_waf(&self) -> bool {
+        self.sku.starts_with("WAF")
+    }
+
+    /// Instance count for v1 gateways, or the autoscale minimum for v2.
+    pub fn capacity(&self) -> i64 {
+        self.capacity
+    }
+
+    /// Autoscale ceiling, if autoscaling is enabled.
+    pub fn max_capacity(&self) -> Option<i64> {
+        self.max_capacity
+    }
+
+    pub fn private_ips(&self) -> &[String] {
+        &self.private_ips
+    }
+
+    pub fn public_ip_names(&self) -> &[String] {
+        &self.public_ip_names
+    }
+}
+
+impl CloudResource for AzureApplicationGateway {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::LoadBalancer
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::Azure
+    }
+
+    fn region(&self) -> &str {
+        &self.region
+    }
+
+    fn state(&self) -> ResourceState {
+        match self.operational_state.as_deref() {
+            Some("Running") => ResourceState::Running,
+            Some("Stopped") => ResourceState::Stopped,
+            Some("Starting") => ResourceState::Starting,
+            Some("Stopping") => ResourceState::Stopping,
+            _ => provisioning_state(&self.provisioning_state),
+        }
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        match self.state() {
+            ResourceState::Stopped => Some(0.0),
+            _ => Some(estimate_gateway_cost(&self.sku, self.capacity)),
+        }
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.tags
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        match self.state() {
+            ResourceState::Running => vec![Action::Stop, Action::Terminate, Action::ViewDetails],
+            ResourceState::Stopped => vec![Action::Start, Action::Terminate, Action::ViewDetails],
+            _ => vec![Action::ViewDetails],
+        }
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let capacity = match self.max_capacity {
+            Some(max) => format!("{}-{} (autoscale)", self.capacity, max),
+            None => self.capacity.to_string(),
+        };
+        let mut details = vec![
+            ("Resource group".to_string(), self.resource_group.clone()),
+            ("SKU".to_string(), self.sku.clone()),
+            ("Capacity".to_string(), capacity),
+        ];
+        if !self.private_ips.is_empty() {
+            details.push(("Private IPs".to_string(), self.private_ips.join(", ")));
+        }
+        if !self.public_ip_names.is_empty() {
+            details.push(("Public IPs".to_string(), self.public_ip_names.join(", ")));
+        }
+        details
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Approximate monthly price in East US, excluding data processing.
+///
+/// v2 gateways pay a fixed hourly fee plus one charge per capacity unit
+/// (the autoscale minimum is used); v1 gateways pay per instance.
+fn estimate_gateway_cost(sku: &str, capacity: i64) -> f64 {
+    let capacity = capacity as f64;
+    let hourly = match sku {
+        "Standard_v2" => 0.246 + capacity * 0.008,
+        "WAF_v2" => 0.443 + capacity * 0.0144,
+        "Standard_Small" => capacity * 0.025,
+        "Standard_Medium" => capacity * 0.07,
+        "Standard_Large" => capacity * 0.32,
+        "WAF_Medium" => capacity * 0.126,
+        "WAF_Large" => capacity * 0.448,
+        _ => 0.246,
+    };
+    hourly * HOURS_PER_MONTH
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::azure::resources::fixtures::arm_resource;
+    use serde_json::{json, Value};
+
+    fn gateway(properties: Value) -> AzureApplicationGateway {
+        AzureApplicationGateway::from_api(&arm_resource(
+            "Microsoft.Network/applicationGateways",
+            json!({ "properties": properties }),
+        ))
+    }
+
+    #[test]
+    fn test_application_gateway_from_api() {
+        let gateway = gateway(json!({
+            "sku": { "name": "WAF_v2", "tier": "WAF_v2", "capacity": 2 },
+            "operationalState": "Running",
+            "provisioningState": "Succeeded",
+            "autoscaleConfiguration": { "minCapacity": 1, "maxCapacity": 10 },
+            "frontendIPConfigurations": [
+                { "properties": { "publicIPAddress": { "id": "/subscriptions/abc/resourceGroups/prod-rg/providers/Microsoft.Network/publicIPAddresses/agw-pip" } } }
+            ]
+        }));
+        assert_eq!(gateway.resource_group(), "prod-rg");
+        assert!(gateway.has_waf());
+        assert_eq!(gateway.capacity(), 1);
+        assert_eq!(gateway.max_capacity(), Some(10));
+        assert_eq!(gateway.public_ip_names(), ["agw-pip".to_string()]);
+        assert_eq!(gateway.resource_type(), ResourceType::LoadBalancer);
+        assert_eq!(gateway.state(), ResourceState::Running);
+        assert!(gateway.supported_actions().contains(&Action::Stop));
+
+        let expected = (0.443 + 0.0144) * HOURS_PER_MONTH;
+        assert!((gateway.cost_per_month().unwrap() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stopped_gateway_is_free() {
+        let gateway = gateway(json!({
+            "sku": { "name": "Standard_v2", "capacity": 2 },
+            "operationalState": "Stopped"
+        }));
+        assert_eq!(gateway.state(), ResourceState::Stopped);
+        assert_eq!(gateway.cost_per_month(), Some(0.0));
+        assert!(gateway.supported_actions().contains(&Action::Start));
+    }
+
+    #[test]
+    fn test_gateway_without_sku_or_operational_state() {
+        // Falls back to the provisioning state, then to Unknown
+        let provisioned = gateway(json!({ "provisioningState": "Updating" }));
+        assert_eq!(provisioned.state(), ResourceState::Pending);
+        assert_eq!(provisioned.sku(), "Standard_v2");
+        assert_eq!(provisioned.capacity(), 0);
+        assert_eq!(provisioned.max_capacity(), None);
+
+        let unknown = gateway(json!({ "operationalState": "Migrating" }));
+        assert_eq!(unknown.state(), ResourceState::Unknown);
+        assert_eq!(unknown.supported_actions(), vec![Action::ViewDetails]);
+    }
+}
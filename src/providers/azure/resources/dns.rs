@@ -0,0 +1,381 @@
+use crate::core::{Action, CloudResource, DnsRecord, Provider, ResourceState, ResourceType};
+use super::loadbalancer::last_segment;
+use super::resource_group_of;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Monthly price of a hosted zone, public or private, for the first 25 zones.
+const ZONE_MONTHLY: f64 = 0.50;
+
+/// Public or private DNS zone as returned by the Microsoft.Network REST API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DnsZone {
+    /// Full ARM resource ID, under `dnsZones` or `privateDnsZones`
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
+    pub properties: DnsZoneProperties,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DnsZoneProperties {
+    pub number_of_record_sets: Option<i64>,
+    /// Only set on public zones
+    #[serde(default)]
+    pub name_servers: Vec<String>,
+}
+
+/// Record set of a public or private zone. Public zones use the
+/// `ARecords`/`TTL` spelling and private zones `aRecords`/`ttl`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecordSet {
+    pub name: String,
+    /// e.g. `Microsoft.Network/dnszones/CNAME`
+    #[serde(rename = "type")]
+    pub record_type: String,
+    pub properties: RecordSetProperties,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RecordSetProperties {
+    pub fqdn: Option<String>,
+    #[serde(rename = "TTL", alias = "ttl")]
+    pub ttl: Option<i64>,
+    #[serde(default, rename = "ARecords", alias = "aRecords")]
+    pub a_records: Vec<ARecord>,
+    #[serde(default, rename = "AAAARecords", alias = "aaaaRecords")]
+    pub aaaa_records: Vec<AaaaRecord>,
+    #[serde(rename = "CNAMERecord", alias = "cnameRecord")]
+    pub cname_record: Option<CnameRecord>,
+    #[serde(default, rename = "MXRecords", alias = "mxRecords")]
+    pub mx_records: Vec<MxRecord>,
+    #[serde(default, rename = "NSRecords", alias = "nsRecords")]
+    pub ns_records: Vec<NsRecord>,
+    #[serde(default, rename = "TXTRecords", alias = "txtRecords")]
+    pub txt_records: Vec<TxtRecord>,
+    #[serde(default, rename = "SRVRecords", alias = "srvRecords")]
+    pub srv_records: Vec<SrvRecord>,
+    #[serde(default, rename = "PTRRecords", alias = "ptrRecords")]
+    pub ptr_records: Vec<PtrRecord>,
+    #[serde(default, rename = "CAARecords", alias = "caaRecords")]
+    pub caa_records: Vec<CaaRecord>,
+    #[serde(rename = "SOARecord", alias = "soaRecord")]
+    pub soa_record: Option<SoaRecord>,
+    /// Alias target, in place of record values
+    #[serde(rename = "targetResource")]
+    pub target_resource: Option<TargetResource>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ARecord {
+    pub ipv4_address: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AaaaRecord {
+    pub ipv6_address: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CnameRecord {
+    pub cname: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MxRecord {
+    pub preference: i64,
+    pub exchange: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NsRecord {
+    pub nsdname: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TxtRecord {
+    #[serde(default)]
+    pub value: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SrvRecord {
+    pub priority: i64,
+    pub weight: i64,
+    pub port: i64,
+    pub target: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PtrRecord {
+    pub ptrdname: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CaaRecord {
+    pub flags: i64,
+    pub tag: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SoaRecord {
+    pub host: String,
+    pub email: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TargetResource {
+    pub id: Option<String>,
+}
+
+impl From<RecordSet> for DnsRecord {
+    fn from(record_set: RecordSet) -> Self {
+        let properties = record_set.properties;
+
+        let values = match properties.target_resource.and_then(|target| target.id) {
+            Some(target) => vec![format!("ALIAS {}", last_segment(&target))],
+            None => {
+                let mut values: Vec<String> = Vec::new();
+                values.extend(properties.a_records.into_iter().map(|r| r.ipv4_address));
+                values.extend(properties.aaaa_records.into_iter().map(|r| r.ipv6_address));
+                values.extend(properties.cname_record.map(|r| r.cname));
+                values.extend(
+                    properties
+                        .mx_records
+                        .into_iter()
+                        .map(|r| format!("{} {}", r.preference, r.exchange)),
+                );
+                values.extend(properties.ns_records.into_iter().map(|r| r.nsdname));
+                values.extend(
+                    properties
+                        .txt_records
+                        .into_iter()
+                        .map(|r| format!("\"{}\"", r.value.concat())),
+                );
+                values.extend(
+                    properties
+                        .srv_records
+                        .into_iter()
+                        .map(|r| format!("{} {} {} {}", r.priority, r.weight, r.port, r.target)),
+                );
+                values.extend(properties.ptr_records.into_iter().map(|r| r.ptrdname));
+                values.extend(
+                    properties
+                        .caa_records
+                        .into_iter()
+                        .map(|r| format!("{} {} \"{}\"", r.flags, r.tag, r.value)),
+                );
+                values.extend(properties.soa_record.map(|r| format!("{} {}", r.host, r.email)));
+                values
+            }
+        };
+
+        DnsRecord {
+            name: properties.fqdn.unwrap_or(record_set.name),
+            record_type: last_segment(&record_set.record_type).to_string(),
+            ttl: properties.ttl,
+            values,
+        }
+    }
+}
+
+pub struct AzureDnsZone {
+    /// Full ARM resource ID
+    id: String,
+    name: String,
+    resource_group: String,
+    is_private: bool,
+    record_count: Option<i64>,
+    name_servers: Vec<String>,
+    tags: HashMap<String, String>,
+}
+
+impl AzureDnsZone {
+    pub fn from_api(zone: &DnsZone) -> Self {
+        Self {
+            id: zone.id.clone(),
+            name: zone.name.clone(),
+            resource_group: resource_group_of(&zone.id).unwrap_or_default().to_string(),
+            is_private: is_private_zone(&zone.id),
+            record_count: zone.properties.number_of_record_sets,
+            name_servers: zone.properties.name_servers.clone(),
+            tags: zone.tags.clone(),
+        }
+    }
+
+    pub fn resource_group(&self) -> &str {
+        &self.resource_group
+    }
+
+    /// Private zones resolve only inside linked virtual networks.
+    pub fn is_private(&self) -> bool {
+        self.is_private
+    }
+
+    pub fn record_count(&self) -> Option<i64> {
+        self.record_count
+    }
+
+    /// Azure name servers to delegate a public zone to.
+    pub fn name_servers(&self) -> &[String] {
+        &self.name_servers
+    }
+}
+
+impl CloudResource for AzureDnsZone {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::DNS
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::Azure
+    }
+
+    fn region(&self) -> &str {
+        "global"
+    }
+
+    fn state(&self) -> ResourceState {
+        ResourceState::Running
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        Some(ZONE_MONTHLY)
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.tags
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        vec![Action::ViewDetails, Action::ViewRecords, Action::Tag, Action::Terminate]
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = vec![
+            ("Resource group".to_string(), self.resource_group.clone()),
+            ("Visibility".to_string(), if self.is_private { "Private" } else { "Public" }.to_string()),
+        ];
+        if let Some(count) = self.record_count {
+            details.push(("Records".to_string(), count.to_string()));
+        }
+        if !self.name_servers.is_empty() {
+            details.push(("Name servers".to_string(), self.name_servers.join(", ")));
+        }
+        details
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Whether an ARM resource ID refers to a private DNS zone.
+pub fn is_private_zone(zone_id: &str) -> bool {
+    zone_id.to_ascii_lowercase().contains("/privatednszones/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_public_zone_from_api() {
+        let zone: DnsZone = serde_json::from_value(serde_json::json!({
+            "id": "/subscriptions/abc/resourceGroups/dns-rg/providers/Microsoft.Network/dnszones/example.com",
+            "name": "example.com",
+            "location": "global",
+            "properties": {
+                "numberOfRecordSets": 12,
+                "nameServers": ["ns1-01.azure-dns.com.", "ns2-01.azure-dns.net."]
+            }
+        }))
+        .unwrap();
+
+        let dns = AzureDnsZone::from_api(&zone);
+        assert_eq!(dns.resource_group(), "dns-rg");
+        assert!(!dns.is_private());
+        assert_eq!(dns.record_count(), Some(12));
+        assert_eq!(dns.name_servers().len(), 2);
+        assert!(dns.supported_actions().contains(&Action::ViewRecords));
+    }
+
+    #[test]
+    fn test_private_zone_is_private() {
+        assert!(is_private_zone(
+            "/subscriptions/abc/resourceGroups/dns-rg/providers/Microsoft.Network/privateDnsZones/internal.example"
+        ));
+    }
+
+    #[test]
+    fn test_public_record_set_to_dns_record() {
+        let record_set: RecordSet = serde_json::from_value(serde_json::json!({
+            "name": "@",
+            "type": "Microsoft.Network/dnszones/MX",
+            "properties": {
+                "fqdn": "example.com.",
+                "TTL": 3600,
+                "MXRecords": [
+                    { "preference": 10, "exchange": "mail1.example.com" },
+                    { "preference": 20, "exchange": "mail2.example.com" }
+                ]
+            }
+        }))
+        .unwrap();
+
+        let record = DnsRecord::from(record_set);
+        assert_eq!(record.name, "example.com.");
+        assert_eq!(record.record_type, "MX");
+        assert_eq!(record.ttl, Some(3600));
+        assert_eq!(record.values, vec!["10 mail1.example.com", "20 mail2.example.com"]);
+    }
+
+    #[test]
+    fn test_private_and_alias_record_sets() {
+        let private: RecordSet = serde_json::from_value(serde_json::json!({
+            "name": "db",
+            "type": "Microsoft.Network/privateDnsZones/A",
+            "properties": {
+                "fqdn": "db.internal.example.",
+                "ttl": 300,
+                "aRecords": [{ "ipv4Address": "10.0.2.5" }]
+            }
+        }))
+        .unwrap();
+        let record = DnsRecord::from(private);
+        assert_eq!(record.record_type, "A");
+        assert_eq!(record.ttl, Some(300));
+        assert_eq!(record.values, vec!["10.0.2.5"]);
+
+        let alias: RecordSet = serde_json::from_value(serde_json::json!({
+            "name": "www",
+            "type": "Microsoft.Network/dnszones/A",
+            "properties": {
+                "TTL": 60,
+                "targetResource": { "id": "/subscriptions/abc/resourceGroups/web-rg/providers/Microsoft.Network/publicIPAddresses/web-pip" }
+            }
+        }))
+        .unwrap();
+        let record = DnsRecord::from(alias);
+        assert_eq!(record.name, "www");
+        assert_eq!(record.values, vec!["ALIAS web-pip"]);
+    }
+}
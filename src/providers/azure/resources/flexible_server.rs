@@ -0,0 +1,303 @@
+use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType, HOURS_PER_MONTH};
+use super::{resource_group_of, Sku};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Storage price per provisioned GB, billed even while the server is stopped.
+const STORAGE_GB_MONTHLY: f64 = 0.115;
+
+/// PostgreSQL or MySQL flexible server as returned by the
+/// Microsoft.DBforPostgreSQL and Microsoft.DBforMySQL REST APIs.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlexibleServer {
+    /// Full ARM resource ID
+    pub id: String,
+    pub name: String,
+    pub location: String,
+    pub sku: Option<Sku>,
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
+    pub properties: FlexibleServerProperties,
+    pub system_data: Option<SystemData>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlexibleServerProperties {
+    /// e.g. `Ready`, `Stopped`, `Starting`, `Stopping`, `Updating`
+    pub state: Option<String>,
+    pub version: Option<String>,
+    pub fully_qualified_domain_name: Option<String>,
+    pub storage: Option<FlexibleServerStorage>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlexibleServerStorage {
+    #[serde(rename = "storageSizeGB")]
+    pub storage_size_gb: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemData {
+    pub created_at: Option<String>,
+}
+
+pub struct AzureFlexibleServer {
+    /// Full ARM resource ID
+    id: String,
+    name: String,
+    engine: String,
+    resource_group: String,
+    region: String,
+    sku_name: String,
+    tier: String,
+    version: Option<String>,
+    state: String,
+    endpoint: Option<String>,
+    storage_gb: Option<i64>,
+    tags: HashMap<String, String>,
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl AzureFlexibleServer {
+    pub fn from_api(server: &FlexibleServer) -> Self {
+        let engine = if server.id.contains("Microsoft.DBforMySQL") {
+            "mysql"
+        } else {
+            "postgres"
+        };
+
+        let created_at = server
+            .system_data
+            .as_ref()
+            .and_then(|data| data.created_at.as_deref())
+            .and_then(|ts| {
+                DateTime::parse_from_rfc3339(ts)
+                    .ok()
+                    .map(|dt| dt.with_timezone(&Utc))
+            });
+
+        let sku = server.sku.as_ref();
+
+        Self {
+            id: server.id.clone(),
+            name: server.name.clone(),
+            engine: engine.to_string(),
+            resource_group: resource_group_of(&server.id).unwrap_or_default().to_string(),
+            region: server.location.clone(),
+            sku_name: sku.map(|s| s.name.clone()).unwrap_or_default(),
+            tier: sku.and_then(|s| s.tier.clone()).unwrap_or_default(),
+            version: server.properties.version.clone(),
+            state: server.properties.state.clone().unwrap_or_default(),
+            endpoint: server.properties.fully_qualified_domain_name.clone(),
+            storage_gb: server.properties.storage.as_ref().and_then(|s| s.storage_size_gb),
+            tags: server.tags.clone(),
+            created_at,
+        }
+    }
+
+    /// `postgres` or `mysql`.
+    pub fn engine(&self) -> &str {
+        &self.engine
+    }
+
+    pub fn resource_group(&self) -> &str {
+        &self.resource_group
+    }
+
+    /// Compute tier, e.g. `Burstable`, `GeneralPurpose` or `MemoryOptimized`.
+    pub fn tier(&self) -> &str {
+        &self.tier
+    }
+
+    /// Compute size, e.g. `Standard_D2ds_v4`.
+    pub fn sku_name(&self) -> &str {
+        &self.sku_name
+    }
+
+    /// Major engine version, e.g. `16` or `8.0.21`.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    pub fn endpoint(&self) -> Option<&str> {
+        self.endpoint.as_deref()
+    }
+
+    pub fn storage_gb(&self) -> Option<i64> {
+        self.storage_gb
+    }
+}
+
+impl CloudResource for AzureFlexibleServer {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Database
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::Azure
+    }
+
+    fn region(&self) -> &str {
+        &self.region
+    }
+
+    fn state(&self) -> ResourceState {
+        match self.state.as_str() {
+            "Ready" => ResourceState::Running,
+            "Stopped" => ResourceState::Stopped,
+            "Starting" => ResourceState::Starting,
+            "Stopping" => ResourceState::Stopping,
+            "Dropping" => ResourceState::Deleting,
+            "Updating" | "Provisioning" => ResourceState::Pending,
+            "Disabled" => ResourceState::Error,
+            _ => ResourceState::Unknown,
+        }
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        let storage = self.storage_gb.unwrap_or(32) as f64 * STORAGE_GB_MONTHLY;
+        match self.state() {
+            ResourceState::Stopped => Some(storage),
+            _ => Some(estimate_flexible_compute_cost(&self.sku_name) + storage),
+        }
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.tags
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        match self.state() {
+            ResourceState::Running => vec![Action::Stop, Action::Terminate, Action::ViewDetails],
+            ResourceState::Stopped => vec![Action::Start, Action::Terminate, Action::ViewDetails],
+            _ => vec![Action::ViewDetails],
+        }
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let engine = match self.version {
+            Some(ref version) => format!("{} {}", self.engine, version),
+            None => self.engine.clone(),
+        };
+        let mut details = vec![
+            ("Resource group".to_string(), self.resource_group.clone()),
+            ("Engine".to_string(), engine),
+            ("SKU".to_string(), format!("{} ({})", self.sku_name, self.tier)),
+        ];
+        if let Some(ref endpoint) = self.endpoint {
+            details.push(("Endpoint".to_string(), endpoint.clone()));
+        }
+        if let Some(storage_gb) = self.storage_gb {
+            details.push(("Storage".to_string(), format!("{} GB", storage_gb)));
+        }
+        details
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Approximate pay-as-you-go compute price in East US; PostgreSQL and MySQL
+/// are priced alike. Unknown sizes are estimated from their vCPU count.
+fn estimate_flexible_compute_cost(sku_name: &str) -> f64 {
+    const VCORE_HOURLY: f64 = 0.086;
+
+    match sku_name {
+        "Standard_B1ms" => 12.41,
+        "Standard_B2s" => 24.82,
+        "Standard_B2ms" => 49.64,
+        "Standard_D2ds_v4" | "Standard_D2ds_v5" => 125.56,
+        "Standard_D4ds_v4" | "Standard_D4ds_v5" => 251.12,
+        "Standard_E2ds_v4" | "Standard_E2ds_v5" => 164.98,
+        "Standard_E4ds_v4" | "Standard_E4ds_v5" => 329.96,
+        other => {
+            // Sizes are named Standard_<family><vCPUs><features>_<version>
+            let vcores: f64 = other
+                .trim_start_matches("Standard_")
+                .chars()
+                .skip_while(|c| c.is_ascii_alphabetic())
+                .take_while(|c| c.is_ascii_digit())
+                .collect::<String>()
+                .parse()
+                .unwrap_or(2.0);
+            vcores * VCORE_HOURLY * HOURS_PER_MONTH
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::azure::resources::fixtures::arm_resource;
+    use serde_json::json;
+
+    const POSTGRES: &str = "Microsoft.DBforPostgreSQL/flexibleServers";
+    const MYSQL: &str = "Microsoft.DBforMySQL/flexibleServers";
+
+    #[test]
+    fn test_postgres_flexible_server() {
+        let server = AzureFlexibleServer::from_api(&arm_resource(POSTGRES, json!({
+            "sku": { "name": "Standard_D2ds_v4", "tier": "GeneralPurpose" },
+            "properties": {
+                "state": "Ready",
+                "version": "16",
+                "fullyQualifiedDomainName": "web-1.postgres.database.azure.com",
+                "storage": { "storageSizeGB": 128 }
+            },
+            "systemData": { "createdAt": "2024-02-20T16:45:00.000Z" }
+        })));
+        assert_eq!(server.engine(), "postgres");
+        assert_eq!(server.version(), Some("16"));
+        assert_eq!(server.tier(), "GeneralPurpose");
+        assert_eq!(server.state(), ResourceState::Running);
+        assert_eq!(server.cost_per_month(), Some(125.56 + 128.0 * STORAGE_GB_MONTHLY));
+        assert!(server.supported_actions().contains(&Action::Stop));
+        assert!(server.created_at().is_some());
+    }
+
+    #[test]
+    fn test_stopped_server_pays_for_storage() {
+        let server = AzureFlexibleServer::from_api(&arm_resource(MYSQL, json!({
+            "sku": { "name": "Standard_D2ds_v4" },
+            "properties": { "state": "Stopped", "storage": { "storageSizeGB": 128 } }
+        })));
+        assert_eq!(server.engine(), "mysql");
+        assert_eq!(server.cost_per_month(), Some(128.0 * STORAGE_GB_MONTHLY));
+        assert!(server.supported_actions().contains(&Action::Start));
+    }
+
+    #[test]
+    fn test_server_without_sku_state_or_storage() {
+        let server = AzureFlexibleServer::from_api(&arm_resource(POSTGRES, json!({})));
+        assert_eq!(server.state(), ResourceState::Unknown);
+        assert_eq!(server.sku_name(), "");
+        assert_eq!(server.tier(), "");
+        assert!(server.created_at().is_none());
+        // Unknown sizes count as two vCores, on the 32 GB minimum of storage
+        let expected = 2.0 * 0.086 * HOURS_PER_MONTH + 32.0 * STORAGE_GB_MONTHLY;
+        assert!((server.cost_per_month().unwrap() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_estimate_from_vcores() {
+        assert!((estimate_flexible_compute_cost("Standard_D16ds_v5") - 16.0 * 0.086 * HOURS_PER_MONTH).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,262 @@
+use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType};
+use super::resource_group_of;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// App Service site as returned by the Microsoft.Web REST API. Function
+/// apps are the sites whose `kind` includes `functionapp`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Site {
+    /// Full ARM resource ID
+    pub id: String,
+    pub name: String,
+    /// Display name of the region, e.g. `East US`
+    pub location: String,
+    /// Comma-separated, e.g. `functionapp,linux`
+    pub kind: Option<String>,
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
+    pub properties: SiteProperties,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SiteProperties {
+    /// `Running` or `Stopped`
+    pub state: Option<String>,
+    pub enabled: Option<bool>,
+    /// Plan SKU, e.g. `Dynamic`, `FlexConsumption` or `ElasticPremium`
+    pub sku: Option<String>,
+    pub server_farm_id: Option<String>,
+    pub default_host_name: Option<String>,
+}
+
+impl Site {
+    pub fn is_function_app(&self) -> bool {
+        self.kind
+            .as_deref()
+            .is_some_and(|kind| kind.split(',').any(|part| part == "functionapp"))
+    }
+}
+
+pub struct AzureFunctionApp {
+    /// Full ARM resource ID
+    id: String,
+    name: String,
+    resource_group: String,
+    region: String,
+    state: String,
+    enabled: bool,
+    plan_type: String,
+    plan_name: Option<String>,
+    os: String,
+    host_name: Option<String>,
+    tags: HashMap<String, String>,
+}
+
+impl AzureFunctionApp {
+    pub fn from_api(site: &Site) -> Self {
+        let properties = &site.properties;
+        let kind = site.kind.as_deref().unwrap_or_default();
+
+        Self {
+            id: site.id.clone(),
+            name: site.name.clone(),
+            resource_group: resource_group_of(&site.id).unwrap_or_default().to_string(),
+            region: normalize_location(&site.location),
+            state: properties.state.clone().unwrap_or_default(),
+            enabled: properties.enabled.unwrap_or(true),
+            plan_type: plan_type(properties.sku.as_deref()).to_string(),
+            plan_name: properties
+                .server_farm_id
+                .as_deref()
+                .and_then(|id| id.rsplit('/').next())
+                .map(str::to_string),
+            os: if kind.contains("linux") { "Linux" } else { "Windows" }.to_string(),
+            host_name: properties.default_host_name.clone(),
+            tags: site.tags.clone(),
+        }
+    }
+
+    pub fn resource_group(&self) -> &str {
+        &self.resource_group
+    }
+
+    /// Hosting plan type, e.g. `Consumption` or `Premium`.
+    pub fn plan_type(&self) -> &str {
+        &self.plan_type
+    }
+
+    /// Name of the App Service plan the app runs on.
+    pub fn plan_name(&self) -> Option<&str> {
+        self.plan_name.as_deref()
+    }
+
+    /// `Linux` or `Windows`.
+    pub fn os(&self) -> &str {
+        &self.os
+    }
+
+    pub fn host_name(&self) -> Option<&str> {
+        self.host_name.as_deref()
+    }
+}
+
+impl CloudResource for AzureFunctionApp {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Serverless
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::Azure
+    }
+
+    fn region(&self) -> &str {
+        &self.region
+    }
+
+    fn state(&self) -> ResourceState {
+        match (self.state.as_str(), self.enabled) {
+            (_, false) => ResourceState::Stopped,
+            ("Running", _) => ResourceState::Running,
+            ("Stopped", _) => ResourceState::Stopped,
+            _ => ResourceState::Unknown,
+        }
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        // Billed per execution on consumption plans and through the shared
+        // App Service plan otherwise, so there is no per-app price
+        None
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.tags
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        match self.state() {
+            ResourceState::Running => vec![Action::Stop, Action::Terminate, Action::ViewDetails],
+            ResourceState::Stopped => vec![Action::Start, Action::Terminate, Action::ViewDetails],
+            _ => vec![Action::ViewDetails],
+        }
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let plan = match self.plan_name {
+            Some(ref name) => format!("{} ({})", self.plan_type, name),
+            None => self.plan_type.clone(),
+        };
+        let mut details = vec![
+            ("Resource group".to_string(), self.resource_group.clone()),
+            ("Plan".to_string(), plan),
+            ("OS".to_string(), self.os.clone()),
+        ];
+        if let Some(ref host_name) = self.host_name {
+            details.push(("Host name".to_string(), host_name.clone()));
+        }
+        details
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Turns a display location such as `East US` into its name, `eastus`.
+pub fn normalize_location(location: &str) -> String {
+    location.replace(' ', "").to_ascii_lowercase()
+}
+
+fn plan_type(sku: Option<&str>) -> &'static str {
+    match sku {
+        Some("Dynamic") => "Consumption",
+        Some("FlexConsumption") => "Flex Consumption",
+        Some("ElasticPremium") => "Premium",
+        Some(_) => "Dedicated",
+        None => "Unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::azure::resources::fixtures::arm_resource;
+    use serde_json::{json, Value};
+
+    fn site(fields: Value) -> Site {
+        arm_resource("Microsoft.Web/sites", fields)
+    }
+
+    #[test]
+    fn test_function_app_from_api() {
+        let site = site(json!({
+            "location": "East US 2",
+            "kind": "functionapp,linux",
+            "properties": {
+                "state": "Running",
+                "enabled": true,
+                "sku": "Dynamic",
+                "serverFarmId": "/subscriptions/abc/resourceGroups/prod-rg/providers/Microsoft.Web/serverfarms/EastUS2LinuxDynamicPlan",
+                "defaultHostName": "web-1.azurewebsites.net"
+            }
+        }));
+        assert!(site.is_function_app());
+
+        let app = AzureFunctionApp::from_api(&site);
+        assert_eq!(app.resource_group(), "prod-rg");
+        assert_eq!(app.region(), "eastus2");
+        assert_eq!(app.plan_type(), "Consumption");
+        assert_eq!(app.plan_name(), Some("EastUS2LinuxDynamicPlan"));
+        assert_eq!(app.os(), "Linux");
+        assert_eq!(app.resource_type(), ResourceType::Serverless);
+        assert_eq!(app.state(), ResourceState::Running);
+        assert!(app.supported_actions().contains(&Action::Stop));
+        assert_eq!(app.cost_per_month(), None);
+    }
+
+    #[test]
+    fn test_web_apps_and_stopped_function_apps() {
+        assert!(!site(json!({ "kind": "app,linux" })).is_function_app());
+        assert!(!site(json!({})).is_function_app());
+
+        let stopped = AzureFunctionApp::from_api(&site(json!({
+            "kind": "functionapp",
+            "properties": { "state": "Stopped", "sku": "ElasticPremium" }
+        })));
+        assert_eq!(stopped.plan_type(), "Premium");
+        assert_eq!(stopped.os(), "Windows");
+        assert_eq!(stopped.state(), ResourceState::Stopped);
+        assert!(stopped.supported_actions().contains(&Action::Start));
+
+        // A disabled app is stopped whatever its state says
+        let disabled = AzureFunctionApp::from_api(&site(json!({
+            "kind": "functionapp",
+            "properties": { "state": "Running", "enabled": false }
+        })));
+        assert_eq!(disabled.state(), ResourceState::Stopped);
+    }
+
+    #[test]
+    fn test_function_app_without_state_or_plan() {
+        let app = AzureFunctionApp::from_api(&site(json!({ "kind": "functionapp", "tags": null })));
+        assert_eq!(app.state(), ResourceState::Unknown);
+        assert_eq!(app.plan_type(), "Unknown");
+        assert_eq!(app.plan_name(), None);
+        assert!(app.tags().is_empty());
+        assert_eq!(app.supported_actions(), vec![Action::ViewDetails]);
+    }
+}
@@ -0,0 +1,285 @@
+use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType, HOURS_PER_MONTH};
+use super::{resource_group_of, Sku};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Standard load balancers are billed hourly for the first five rules, then
+/// per additional rule. Basic load balancers are free.
+const STANDARD_HOURLY: f64 = 0.025;
+const EXTRA_RULE_HOURLY: f64 = 0.01;
+const INCLUDED_RULES: usize = 5;
+
+/// Load balancer as returned by the Microsoft.Network REST API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LoadBalancer {
+    /// Full ARM resource ID
+    pub id: String,
+    pub name: String,
+    pub location: String,
+    pub sku: Option<Sku>,
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
+    pub properties: LoadBalancerProperties,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadBalancerProperties {
+    pub provisioning_state: Option<String>,
+    #[serde(default, rename = "frontendIPConfigurations")]
+    pub frontend_ip_configurations: Vec<FrontendIpConfiguration>,
+    #[serde(default)]
+    pub load_balancing_rules: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub backend_address_pools: Vec<serde_json::Value>,
+}
+
+/// Frontend IP configuration, shared with application gateways.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FrontendIpConfiguration {
+    pub properties: FrontendIpProperties,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrontendIpProperties {
+    #[serde(rename = "privateIPAddress")]
+    pub private_ip_address: Option<String>,
+    /// Reference to a public IP address resource, for public frontends
+    #[serde(rename = "publicIPAddress")]
+    pub public_ip_address: Option<ResourceReference>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResourceReference {
+    pub id: String,
+}
+
+pub struct AzureLoadBalancer {
+    /// Full ARM resource ID
+    id: String,
+    name: String,
+    resource_group: String,
+    region: String,
+    sku: String,
+    provisioning_state: String,
+    private_ips: Vec<String>,
+    public_ip_names: Vec<String>,
+    rule_count: usize,
+    backend_pool_count: usize,
+    tags: HashMap<String, String>,
+}
+
+impl AzureLoadBalancer {
+    pub fn from_api(lb: &LoadBalancer) -> Self {
+        let frontends = &lb.properties.frontend_ip_configurations;
+
+        Self {
+            id: lb.id.clone(),
+            name: lb.name.clone(),
+            resource_group: resource_group_of(&lb.id).unwrap_or_default().to_string(),
+            region: lb.location.clone(),
+            sku: lb
+                .sku
+                .as_ref()
+                .map(|sku| sku.name.clone())
+                .unwrap_or_else(|| "Basic".to_string()),
+            provisioning_state: lb.properties.provisioning_state.clone().unwrap_or_default(),
+            private_ips: frontends
+                .iter()
+                .filter_map(|frontend| frontend.properties.private_ip_address.clone())
+                .collect(),
+            public_ip_names: frontends
+                .iter()
+                .filter_map(|frontend| frontend.properties.public_ip_address.as_ref())
+                .map(|public_ip| last_segment(&public_ip.id).to_string())
+                .collect(),
+            rule_count: lb.properties.load_balancing_rules.len(),
+            backend_pool_count: lb.properties.backend_address_pools.len(),
+            tags: lb.tags.clone(),
+        }
+    }
+
+    pub fn resource_group(&self) -> &str {
+        &self.resource_group
+    }
+
+    /// `Basic`, `Standard` or `Gateway`.
+    pub fn sku(&self) -> &str {
+        &self.sku
+    }
+
+    /// Public load balancers have at least one public IP frontend.
+    pub fn is_internal(&self) -> bool {
+        self.public_ip_names.is_empty()
+    }
+
+    pub fn private_ips(&self) -> &[String] {
+        &self.private_ips
+    }
+
+    /// Names of the public IP address resources used as frontends.
+    pub fn public_ip_names(&self) -> &[String] {
+        &self.public_ip_names
+    }
+
+    pub fn rule_count(&self) -> usize {
+        self.rule_count
+    }
+
+    pub fn backend_pool_count(&self) -> usize {
+        self.backend_pool_count
+    }
+}
+
+impl CloudResource for AzureLoadBalancer {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::LoadBalancer
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::Azure
+    }
+
+    fn region(&self) -> &str {
+        &self.region
+    }
+
+    fn state(&self) -> ResourceState {
+        provisioning_state(&self.provisioning_state)
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        // Data processing charges depend on traffic, so only rules are counted
+        match self.sku.as_str() {
+            "Basic" => Some(0.0),
+            _ => {
+                let extra_rules = self.rule_count.saturating_sub(INCLUDED_RULES) as f64;
+                Some((STANDARD_HOURLY + extra_rules * EXTRA_RULE_HOURLY) * HOURS_PER_MONTH)
+            }
+        }
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.tags
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        vec![Action::ViewDetails, Action::Terminate]
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = vec![
+            ("Resource group".to_string(), self.resource_group.clone()),
+            ("SKU".to_string(), self.sku.clone()),
+        ];
+        if !self.private_ips.is_empty() {
+            details.push(("Private IPs".to_string(), self.private_ips.join(", ")));
+        }
+        if !self.public_ip_names.is_empty() {
+            details.push(("Public IPs".to_string(), self.public_ip_names.join(", ")));
+        }
+        details.push(("Rules".to_string(), self.rule_count.to_string()));
+        details.push(("Backend pools".to_string(), self.backend_pool_count.to_string()));
+        details
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Maps the ARM provisioning state of a network resource.
+pub fn provisioning_state(state: &str) -> ResourceState {
+    match state {
+        "Succeeded" => ResourceState::Running,
+        "Creating" | "Updating" => ResourceState::Pending,
+        "Deleting" => ResourceState::Deleting,
+        "Failed" => ResourceState::Error,
+        _ => ResourceState::Unknown,
+    }
+}
+
+pub fn last_segment(id: &str) -> &str {
+    id.rsplit('/').next().unwrap_or(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::azure::resources::fixtures::arm_resource;
+    use serde_json::{json, Value};
+
+    fn load_balancer(fields: Value) -> AzureLoadBalancer {
+        AzureLoadBalancer::from_api(&arm_resource("Microsoft.Network/loadBalancers", fields))
+    }
+
+    fn load_balancer_with_rules(sku: &str, rules: usize) -> AzureLoadBalancer {
+        load_balancer(json!({
+            "sku": { "name": sku },
+            "properties": { "loadBalancingRules": vec![json!({}); rules] }
+        }))
+    }
+
+    #[test]
+    fn test_load_balancer_from_api() {
+        let lb = load_balancer(json!({
+            "sku": { "name": "Standard", "tier": "Regional" },
+            "properties": {
+                "provisioningState": "Succeeded",
+                "frontendIPConfigurations": [
+                    { "properties": { "publicIPAddress": { "id": "/subscriptions/abc/resourceGroups/prod-rg/providers/Microsoft.Network/publicIPAddresses/web-pip" } } },
+                    { "properties": { "privateIPAddress": "10.0.1.4" } }
+                ],
+                "loadBalancingRules": [{}, {}],
+                "backendAddressPools": [{}]
+            }
+        }));
+        assert_eq!(lb.resource_group(), "prod-rg");
+        assert_eq!(lb.sku(), "Standard");
+        assert_eq!(lb.public_ip_names(), ["web-pip".to_string()]);
+        assert_eq!(lb.private_ips(), ["10.0.1.4".to_string()]);
+        assert!(!lb.is_internal());
+        assert_eq!(lb.rule_count(), 2);
+        assert_eq!(lb.backend_pool_count(), 1);
+        assert_eq!(lb.state(), ResourceState::Running);
+        assert_eq!(lb.cost_per_month(), Some(STANDARD_HOURLY * HOURS_PER_MONTH));
+    }
+
+    #[test]
+    fn test_internal_load_balancer_without_sku() {
+        let lb = load_balancer(json!({
+            "properties": {
+                "provisioningState": "Migrating",
+                "frontendIPConfigurations": [{ "properties": { "privateIPAddress": "10.0.1.5" } }]
+            }
+        }));
+        assert!(lb.is_internal());
+        assert_eq!(lb.sku(), "Basic");
+        assert_eq!(lb.rule_count(), 0);
+        assert_eq!(lb.state(), ResourceState::Unknown);
+    }
+
+    #[test]
+    fn test_load_balancer_cost_by_sku() {
+        let lb = load_balancer_with_rules("Standard", 7);
+        let expected = (STANDARD_HOURLY + 2.0 * EXTRA_RULE_HOURLY) * HOURS_PER_MONTH;
+        assert!((lb.cost_per_month().unwrap() - expected).abs() < 1e-9);
+
+        let basic = load_balancer_with_rules("Basic", 7);
+        assert_eq!(basic.cost_per_month(), Some(0.0));
+    }
+}
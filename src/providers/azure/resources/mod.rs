@@ -0,0 +1,77 @@
+pub mod aks;
+pub mod app_gateway;
+pub mod dns;
+pub mod flexible_server;
+pub mod function_app;
+pub mod loadbalancer;
+pub mod sql;
+pub mod storage;
+pub mod vm;
+
+pub use aks::AzureAksCluster;
+pub use app_gateway::AzureApplicationGateway;
+pub use dns::AzureDnsZone;
+pub use flexible_server::AzureFlexibleServer;
+pub use function_app::AzureFunctionApp;
+pub use loadbalancer::AzureLoadBalancer;
+pub use sql::AzureSqlDatabase;
+pub use storage::AzureStorageAccount;
+pub use vm::AzureVM;
+
+use serde::Deserialize;
+
+/// Pricing tier of an ARM resource.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Sku {
+    pub name: String,
+    pub tier: Option<String>,
+    /// vCores or DTUs, depending on the purchasing model
+    pub capacity: Option<i64>,
+}
+
+/// Returns the resource group named in an ARM resource ID such as
+/// `/subscriptions/<id>/resourceGroups/<group>/providers/...`.
+pub fn resource_group_of(resource_id: &str) -> Option<&str> {
+    let mut segments = resource_id.split('/');
+    // ARM is inconsistent about the casing of `resourceGroups`
+    segments.find(|segment| segment.eq_ignore_ascii_case("resourceGroups"))?;
+    segments.next().filter(|group| !group.is_empty())
+}
+
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use serde::de::DeserializeOwned;
+    use serde_json::{json, Value};
+
+    /// Parses an ARM resource named `web-1` in `prod-rg`/`eastus` and tagged
+    /// `team=platform`, with `fields` laid over it. `resource_type` is the
+    /// provider path in its ID, e.g. `Microsoft.Compute/virtualMachines`.
+    pub fn arm_resource<T: DeserializeOwned>(resource_type: &str, fields: Value) -> T {
+        let defaults = json!({
+            "id": format!("/subscriptions/abc/resourceGroups/prod-rg/providers/{}/web-1", resource_type),
+            "name": "web-1",
+            "location": "eastus",
+            "tags": { "team": "platform" },
+            "properties": {}
+        });
+        crate::providers::fixtures::api_response(defaults, fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_group_of() {
+        assert_eq!(
+            resource_group_of("/subscriptions/abc/resourceGroups/prod-rg/providers/Microsoft.Compute/virtualMachines/web"),
+            Some("prod-rg")
+        );
+        assert_eq!(
+            resource_group_of("/subscriptions/abc/resourcegroups/PROD-RG/providers/Microsoft.Compute/virtualMachines/web"),
+            Some("PROD-RG")
+        );
+        assert_eq!(resource_group_of("/subscriptions/abc"), None);
+    }
+}
@@ -0,0 +1,275 @@
+use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType};
+use crate::core::metrics::format_bytes;
+use super::{resource_group_of, Sku};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Azure SQL database as returned by the Microsoft.Sql REST API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SqlDatabase {
+    /// Full ARM resource ID, `.../servers/<server>/databases/<name>`
+    pub id: String,
+    pub name: String,
+    pub location: String,
+    pub sku: Option<Sku>,
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
+    pub properties: SqlDatabaseProperties,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SqlDatabaseProperties {
+    /// e.g. `Online`, `Paused`, `Offline`, `Creating`
+    pub status: Option<String>,
+    pub max_size_bytes: Option<i64>,
+    pub creation_date: Option<String>,
+}
+
+pub struct AzureSqlDatabase {
+    /// Full ARM resource ID
+    id: String,
+    name: String,
+    server: String,
+    resource_group: String,
+    region: String,
+    sku_name: String,
+    tier: String,
+    capacity: Option<i64>,
+    status: String,
+    max_size_bytes: Option<i64>,
+    tags: HashMap<String, String>,
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl AzureSqlDatabase {
+    pub fn from_api(database: &SqlDatabase) -> Self {
+        let server = database
+            .id
+            .split("/servers/")
+            .nth(1)
+            .and_then(|rest| rest.split('/').next())
+            .unwrap_or_default()
+            .to_string();
+
+        let created_at = database.properties.creation_date.as_deref().and_then(|ts| {
+            DateTime::parse_from_rfc3339(ts)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc))
+        });
+
+        let sku = database.sku.as_ref();
+
+        Self {
+            id: database.id.clone(),
+            name: database.name.clone(),
+            server,
+            resource_group: resource_group_of(&database.id).unwrap_or_default().to_string(),
+            region: database.location.clone(),
+            sku_name: sku.map(|s| s.name.clone()).unwrap_or_default(),
+            tier: sku.and_then(|s| s.tier.clone()).unwrap_or_default(),
+            capacity: sku.and_then(|s| s.capacity),
+            status: database.properties.status.clone().unwrap_or_default(),
+            max_size_bytes: database.properties.max_size_bytes,
+            tags: database.tags.clone(),
+            created_at,
+        }
+    }
+
+    /// Logical server hosting the database.
+    pub fn server(&self) -> &str {
+        &self.server
+    }
+
+    pub fn resource_group(&self) -> &str {
+        &self.resource_group
+    }
+
+    pub fn engine(&self) -> &str {
+        "sqlserver"
+    }
+
+    /// Service tier, e.g. `GeneralPurpose`, `BusinessCritical` or `Standard`.
+    pub fn tier(&self) -> &str {
+        &self.tier
+    }
+
+    /// Service objective, e.g. `GP_Gen5_2` or `S1`.
+    pub fn sku_name(&self) -> &str {
+        &self.sku_name
+    }
+
+    /// Serverless databases are billed per second of use and pause when idle.
+    pub fn is_serverless(&self) -> bool {
+        self.sku_name.contains("_S_")
+    }
+
+    pub fn max_size_gb(&self) -> Option<f64> {
+        self.max_size_bytes
+            .map(|bytes| bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+    }
+}
+
+impl CloudResource for AzureSqlDatabase {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Database
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::Azure
+    }
+
+    fn region(&self) -> &str {
+        &self.region
+    }
+
+    fn state(&self) -> ResourceState {
+        match self.status.as_str() {
+            "Online" => ResourceState::Running,
+            "Paused" => ResourceState::Stopped,
+            "Creating" | "Copying" | "Restoring" | "Scaling" | "OnlineChangingDwPerformanceTiers" => {
+                ResourceState::Pending
+            }
+            "Resuming" => ResourceState::Starting,
+            "Pausing" => ResourceState::Stopping,
+            "Recovering" | "AutoClosed" => ResourceState::Degraded,
+            "Offline" | "Suspect" | "EmergencyMode" | "Inaccessible" => ResourceState::Error,
+            _ => ResourceState::Unknown,
+        }
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        // Serverless compute depends on usage, which is not fetched
+        if self.is_serverless() {
+            return None;
+        }
+        Some(estimate_sql_cost(&self.sku_name, &self.tier, self.capacity))
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.tags
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        vec![Action::ViewDetails, Action::Terminate]
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = vec![
+            ("Server".to_string(), self.server.clone()),
+            ("Resource group".to_string(), self.resource_group.clone()),
+            ("SKU".to_string(), format!("{} ({})", self.sku_name, self.tier)),
+        ];
+        if let Some(capacity) = self.capacity {
+            details.push(("Capacity".to_string(), capacity.to_string()));
+        }
+        if let Some(max_size_bytes) = self.max_size_bytes {
+            details.push(("Max size".to_string(), format_bytes(max_size_bytes as f64)));
+        }
+        details
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Approximate pay-as-you-go monthly price in East US, license included.
+///
+/// DTU databases are priced per service objective; vCore databases per
+/// vCore of their tier.
+fn estimate_sql_cost(sku_name: &str, tier: &str, capacity: Option<i64>) -> f64 {
+    match sku_name {
+        "Basic" => 4.90,
+        "S0" => 14.72,
+        "S1" => 29.43,
+        "S2" => 73.61,
+        "S3" => 147.19,
+        "P1" => 456.25,
+        "P2" => 912.50,
+        _ => {
+            let per_vcore = match tier {
+                "BusinessCritical" => 498.0,
+                "Hyperscale" => 146.0,
+                _ => 185.0,
+            };
+            per_vcore * capacity.unwrap_or(2) as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::azure::resources::fixtures::arm_resource;
+    use serde_json::{json, Value};
+
+    fn database(fields: Value) -> AzureSqlDatabase {
+        AzureSqlDatabase::from_api(&arm_resource("Microsoft.Sql/servers/orders-sql/databases", fields))
+    }
+
+    fn online_database(sku: Value) -> AzureSqlDatabase {
+        database(json!({ "sku": sku, "properties": { "status": "Online" } }))
+    }
+
+    #[test]
+    fn test_vcore_database() {
+        let database = database(json!({
+            "sku": { "name": "GP_Gen5_4", "tier": "GeneralPurpose", "capacity": 4 },
+            "properties": {
+                "status": "Online",
+                "maxSizeBytes": 34359738368_i64,
+                "creationDate": "2024-01-15T09:00:00.000Z"
+            }
+        }));
+        assert_eq!(database.server(), "orders-sql");
+        assert_eq!(database.resource_group(), "prod-rg");
+        assert_eq!(database.tier(), "GeneralPurpose");
+        assert_eq!(database.state(), ResourceState::Running);
+        assert_eq!(database.max_size_gb(), Some(32.0));
+        assert_eq!(database.cost_per_month(), Some(740.0));
+        assert!(database.created_at().is_some());
+    }
+
+    #[test]
+    fn test_dtu_database() {
+        let database = online_database(json!({ "name": "S1", "tier": "Standard", "capacity": 20 }));
+        assert_eq!(database.cost_per_month(), Some(29.43));
+    }
+
+    #[test]
+    fn test_paused_serverless_database() {
+        let database = database(json!({
+            "sku": { "name": "GP_S_Gen5_2", "tier": "GeneralPurpose", "capacity": 2 },
+            "properties": { "status": "Paused" }
+        }));
+        assert!(database.is_serverless());
+        assert_eq!(database.state(), ResourceState::Stopped);
+        assert_eq!(database.cost_per_month(), None);
+    }
+
+    #[test]
+    fn test_database_without_sku_or_status() {
+        let bare = database(json!({}));
+        assert_eq!(bare.state(), ResourceState::Unknown);
+        assert_eq!(bare.max_size_gb(), None);
+        // Priced as a two-vCore General Purpose database
+        assert_eq!(bare.cost_per_month(), Some(370.0));
+
+        let unknown = database(json!({ "properties": { "status": "Standby" } }));
+        assert_eq!(unknown.state(), ResourceState::Unknown);
+    }
+}
@@ -0,0 +1,266 @@
+use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType};
+use crate::core::metrics::format_bytes;
+use super::{resource_group_of, Sku};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Storage account as returned by the Microsoft.Storage REST API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct StorageAccount {
+    /// Full ARM resource ID
+    pub id: String,
+    pub name: String,
+    pub location: String,
+    /// e.g. `StorageV2`, `BlobStorage` or `BlockBlobStorage`
+    pub kind: Option<String>,
+    pub sku: Option<Sku>,
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
+    pub properties: StorageAccountProperties,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageAccountProperties {
+    /// Default blob tier: `Hot`, `Cool` or `Cold`
+    pub access_tier: Option<String>,
+    pub provisioning_state: Option<String>,
+    pub creation_time: Option<String>,
+}
+
+pub struct AzureStorageAccount {
+    /// Full ARM resource ID
+    id: String,
+    name: String,
+    resource_group: String,
+    region: String,
+    kind: String,
+    sku_name: String,
+    access_tier: String,
+    provisioning_state: String,
+    tags: HashMap<String, String>,
+    created_at: Option<DateTime<Utc>>,
+    blob_capacity_bytes: Option<u64>,
+}
+
+impl AzureStorageAccount {
+    pub fn from_api(account: &StorageAccount) -> Self {
+        let created_at = account.properties.creation_time.as_deref().and_then(|ts| {
+            DateTime::parse_from_rfc3339(ts)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc))
+        });
+
+        Self {
+            id: account.id.clone(),
+            name: account.name.clone(),
+            resource_group: resource_group_of(&account.id).unwrap_or_default().to_string(),
+            region: account.location.clone(),
+            kind: account.kind.clone().unwrap_or_else(|| "StorageV2".to_string()),
+            sku_name: account
+                .sku
+                .as_ref()
+                .map(|sku| sku.name.clone())
+                .unwrap_or_else(|| "Standard_LRS".to_string()),
+            access_tier: account
+                .properties
+                .access_tier
+                .clone()
+                .unwrap_or_else(|| "Hot".to_string()),
+            provisioning_state: account.properties.provisioning_state.clone().unwrap_or_default(),
+            tags: account.tags.clone(),
+            created_at,
+            blob_capacity_bytes: None,
+        }
+    }
+
+    pub fn with_blob_capacity(mut self, bytes: u64) -> Self {
+        self.blob_capacity_bytes = Some(bytes);
+        self
+    }
+
+    pub fn resource_group(&self) -> &str {
+        &self.resource_group
+    }
+
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Replication setting, e.g. `LRS`, `ZRS`, `GRS` or `RAGZRS`.
+    pub fn redundancy(&self) -> &str {
+        self.sku_name
+            .split_once('_')
+            .map_or(self.sku_name.as_str(), |(_, redundancy)| redundancy)
+    }
+
+    /// Premium accounts store data on SSDs and ignore access tiers.
+    pub fn is_premium(&self) -> bool {
+        self.sku_name.starts_with("Premium")
+    }
+
+    pub fn access_tier(&self) -> &str {
+        &self.access_tier
+    }
+
+    pub fn blob_capacity_bytes(&self) -> Option<u64> {
+        self.blob_capacity_bytes
+    }
+
+    pub fn blob_capacity_gb(&self) -> Option<f64> {
+        self.blob_capacity_bytes
+            .map(|bytes| bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+    }
+}
+
+impl CloudResource for AzureStorageAccount {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Storage
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::Azure
+    }
+
+    fn region(&self) -> &str {
+        &self.region
+    }
+
+    fn state(&self) -> ResourceState {
+        match self.provisioning_state.as_str() {
+            "Succeeded" => ResourceState::Running,
+            "Creating" | "ResolvingDNS" => ResourceState::Pending,
+            "Deleting" => ResourceState::Deleting,
+            _ => ResourceState::Unknown,
+        }
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        let price = blob_price_per_gb(&self.access_tier, self.redundancy(), self.is_premium());
+        self.blob_capacity_gb().map(|gb| gb * price)
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.tags
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        vec![Action::ViewDetails, Action::Terminate]
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = vec![
+            ("Resource group".to_string(), self.resource_group.clone()),
+            ("Kind".to_string(), self.kind.clone()),
+            ("SKU".to_string(), self.sku_name.clone()),
+            ("Access tier".to_string(), self.access_tier.clone()),
+        ];
+        if let Some(bytes) = self.blob_capacity_bytes {
+            details.push(("Blob capacity".to_string(), format_bytes(bytes as f64)));
+        }
+        details
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Approximate monthly block blob price per GB in East US, for the first
+/// 50 TB. Geo-replicated copies cost roughly a multiple of the LRS price.
+fn blob_price_per_gb(access_tier: &str, redundancy: &str, premium: bool) -> f64 {
+    let lrs_price = match (premium, access_tier) {
+        (true, _) => 0.15,
+        (false, "Cool") => 0.01,
+        (false, "Cold") => 0.0036,
+        (false, _) => 0.0184,
+    };
+
+    let multiplier = match redundancy {
+        "ZRS" => 1.25,
+        "GRS" => 2.0,
+        "GZRS" => 2.25,
+        "RAGRS" => 2.5,
+        "RAGZRS" => 2.8,
+        _ => 1.0,
+    };
+
+    lrs_price * multiplier
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::azure::resources::fixtures::arm_resource;
+    use serde_json::{json, Value};
+
+    fn account(fields: Value) -> AzureStorageAccount {
+        AzureStorageAccount::from_api(&arm_resource("Microsoft.Storage/storageAccounts", fields))
+    }
+
+    fn account_with_tier(sku: &str, access_tier: &str) -> AzureStorageAccount {
+        account(json!({
+            "sku": { "name": sku },
+            "properties": { "accessTier": access_tier }
+        }))
+    }
+
+    #[test]
+    fn test_storage_account_from_api() {
+        let account = account(json!({
+            "kind": "StorageV2",
+            "sku": { "name": "Standard_RAGRS", "tier": "Standard" },
+            "properties": {
+                "accessTier": "Hot",
+                "provisioningState": "Succeeded",
+                "creationTime": "2023-11-08T14:02:11.3456789Z"
+            }
+        }));
+        assert_eq!(account.resource_group(), "prod-rg");
+        assert_eq!(account.redundancy(), "RAGRS");
+        assert_eq!(account.access_tier(), "Hot");
+        assert!(!account.is_premium());
+        assert_eq!(account.state(), ResourceState::Running);
+        assert_eq!(account.resource_type(), ResourceType::Storage);
+        assert_eq!(account.cost_per_month(), None);
+        assert!(account.created_at().is_some());
+    }
+
+    #[test]
+    fn test_storage_account_defaults() {
+        let bare = account(json!({ "tags": null }));
+        assert_eq!(bare.kind(), "StorageV2");
+        assert_eq!(bare.redundancy(), "LRS");
+        assert_eq!(bare.access_tier(), "Hot");
+        assert_eq!(bare.state(), ResourceState::Unknown);
+        assert!(bare.tags().is_empty());
+        assert!(bare.created_at().is_none());
+    }
+
+    #[test]
+    fn test_storage_account_cost() {
+        let gb = 1024 * 1024 * 1024;
+
+        let hot_lrs = account_with_tier("Standard_LRS", "Hot").with_blob_capacity(100 * gb);
+        assert!((hot_lrs.cost_per_month().unwrap() - 1.84).abs() < 1e-9);
+
+        let cool_grs = account_with_tier("Standard_GRS", "Cool").with_blob_capacity(100 * gb);
+        assert!((cool_grs.cost_per_month().unwrap() - 2.0).abs() < 1e-9);
+
+        let premium = account_with_tier("Premium_LRS", "Hot").with_blob_capacity(10 * gb);
+        assert!((premium.cost_per_month().unwrap() - 1.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,322 @@
+use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType};
+use super::resource_group_of;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Virtual machine as returned by the Compute Resource Provider REST API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct VirtualMachine {
+    /// Full ARM resource ID
+    pub id: String,
+    pub name: String,
+    pub location: String,
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
+    pub properties: VmProperties,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VmProperties {
+    pub hardware_profile: Option<VmHardwareProfile>,
+    pub storage_profile: Option<VmStorageProfile>,
+    pub provisioning_state: Option<String>,
+    pub time_created: Option<String>,
+    /// Only present when requested with `statusOnly` or `$expand=instanceView`
+    pub instance_view: Option<VmInstanceView>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VmHardwareProfile {
+    pub vm_size: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VmStorageProfile {
+    pub os_disk: Option<VmOsDisk>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VmOsDisk {
+    pub os_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct VmInstanceView {
+    #[serde(default)]
+    pub statuses: Vec<VmStatus>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct VmStatus {
+    /// e.g. `ProvisioningState/succeeded` or `PowerState/running`
+    pub code: String,
+}
+
+pub struct AzureVM {
+    /// Full ARM resource ID
+    id: String,
+    name: String,
+    resource_group: String,
+    region: String,
+    vm_size: String,
+    os_type: Option<String>,
+    /// Power state without its `PowerState/` prefix, e.g. `deallocated`
+    power_state: Option<String>,
+    provisioning_state: String,
+    tags: HashMap<String, String>,
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl AzureVM {
+    pub fn from_api(vm: &VirtualMachine) -> Self {
+        let properties = &vm.properties;
+
+        let power_state = properties.instance_view.as_ref().and_then(|view| {
+            view.statuses
+                .iter()
+                .find_map(|status| status.code.strip_prefix("PowerState/"))
+                .map(|state| state.to_string())
+        });
+
+        let created_at = properties.time_created.as_deref().and_then(|ts| {
+            DateTime::parse_from_rfc3339(ts)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc))
+        });
+
+        Self {
+            id: vm.id.clone(),
+            name: vm.name.clone(),
+            resource_group: resource_group_of(&vm.id).unwrap_or_default().to_string(),
+            region: vm.location.clone(),
+            vm_size: properties
+                .hardware_profile
+                .as_ref()
+                .map(|profile| profile.vm_size.clone())
+                .unwrap_or_default(),
+            os_type: properties
+                .storage_profile
+                .as_ref()
+                .and_then(|profile| profile.os_disk.as_ref())
+                .and_then(|disk| disk.os_type.clone()),
+            power_state,
+            provisioning_state: properties.provisioning_state.clone().unwrap_or_default(),
+            tags: vm.tags.clone(),
+            created_at,
+        }
+    }
+
+    pub fn resource_group(&self) -> &str {
+        &self.resource_group
+    }
+
+    pub fn vm_size(&self) -> &str {
+        &self.vm_size
+    }
+
+    /// `Linux` or `Windows`.
+    pub fn os_type(&self) -> Option<&str> {
+        self.os_type.as_deref()
+    }
+
+    /// A VM stopped from inside the guest keeps its hardware and is still
+    /// billed; only a deallocated VM stops accruing compute charges.
+    pub fn is_deallocated(&self) -> bool {
+        self.power_state.as_deref() == Some("deallocated")
+    }
+}
+
+impl CloudResource for AzureVM {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Compute
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::Azure
+    }
+
+    fn region(&self) -> &str {
+        &self.region
+    }
+
+    fn state(&self) -> ResourceState {
+        match self.power_state.as_deref() {
+            Some("running") => ResourceState::Running,
+            Some("stopped") | Some("deallocated") => ResourceState::Stopped,
+            Some("starting") => ResourceState::Starting,
+            Some("stopping") | Some("deallocating") => ResourceState::Stopping,
+            _ => match self.provisioning_state.as_str() {
+                "Creating" | "Updating" => ResourceState::Pending,
+                "Deleting" => ResourceState::Deleting,
+                "Failed" => ResourceState::Error,
+                _ => ResourceState::Unknown,
+            },
+        }
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        if self.is_deallocated() {
+            Some(0.0)
+        } else {
+            Some(estimate_vm_cost(&self.vm_size))
+        }
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.tags
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        match self.state() {
+            ResourceState::Running => vec![
+                Action::Stop,
+                Action::Restart,
+                Action::Terminate,
+                Action::ViewDetails,
+            ],
+            // A guest-stopped VM still needs deallocating to stop billing
+            ResourceState::Stopped if !self.is_deallocated() => {
+                vec![Action::Start, Action::Stop, Action::Terminate, Action::ViewDetails]
+            }
+            ResourceState::Stopped => vec![Action::Start, Action::Terminate, Action::ViewDetails],
+            _ => vec![Action::ViewDetails],
+        }
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = vec![
+            ("Resource group".to_string(), self.resource_group.clone()),
+            ("VM size".to_string(), self.vm_size.clone()),
+        ];
+        if let Some(ref os_type) = self.os_type {
+            details.push(("OS".to_string(), os_type.clone()));
+        }
+        details
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Approximate pay-as-you-go Linux monthly price in East US.
+pub fn estimate_vm_cost(vm_size: &str) -> f64 {
+    match vm_size {
+        "Standard_B1s" => 7.59,
+        "Standard_B1ms" => 15.18,
+        "Standard_B2s" => 30.37,
+        "Standard_B2ms" => 60.74,
+        "Standard_D2s_v3" | "Standard_D2s_v5" => 70.08,
+        "Standard_D4s_v3" | "Standard_D4s_v5" => 140.16,
+        "Standard_D8s_v3" | "Standard_D8s_v5" => 280.32,
+        "Standard_E2s_v3" => 91.98,
+        "Standard_E4s_v3" => 183.96,
+        "Standard_F2s_v2" => 61.76,
+        _ => 70.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::azure::resources::fixtures::arm_resource;
+    use serde_json::{json, Value};
+
+    const VM: &str = "Microsoft.Compute/virtualMachines";
+
+    fn vm(properties: Value) -> AzureVM {
+        AzureVM::from_api(&arm_resource(VM, json!({ "properties": properties })))
+    }
+
+    fn vm_with_power_state(power_state: &str) -> AzureVM {
+        vm(json!({
+            "hardwareProfile": { "vmSize": "Standard_B2s" },
+            "instanceView": { "statuses": [
+                { "code": "ProvisioningState/succeeded" },
+                { "code": power_state }
+            ] }
+        }))
+    }
+
+    #[test]
+    fn test_azure_vm_from_api() {
+        let vm = vm(json!({
+            "hardwareProfile": { "vmSize": "Standard_B2s" },
+            "storageProfile": { "osDisk": { "osType": "Linux" } },
+            "provisioningState": "Succeeded",
+            "timeCreated": "2024-04-02T10:15:00.1234567+00:00",
+            "instanceView": { "statuses": [
+                { "code": "ProvisioningState/succeeded" },
+                { "code": "PowerState/running" }
+            ] }
+        }));
+        assert_eq!(vm.name(), "web-1");
+        assert_eq!(vm.resource_group(), "prod-rg");
+        assert_eq!(vm.region(), "eastus");
+        assert_eq!(vm.vm_size(), "Standard_B2s");
+        assert_eq!(vm.os_type(), Some("Linux"));
+        assert_eq!(vm.provider(), Provider::Azure);
+        assert_eq!(vm.state(), ResourceState::Running);
+        assert_eq!(vm.cost_per_month(), Some(30.37));
+        assert_eq!(vm.tags().get("team"), Some(&"platform".to_string()));
+        assert!(vm.created_at().is_some());
+        assert!(vm.supported_actions().contains(&Action::Restart));
+    }
+
+    #[test]
+    fn test_deallocated_vm_is_free() {
+        let vm = vm_with_power_state("PowerState/deallocated");
+        assert_eq!(vm.state(), ResourceState::Stopped);
+        assert_eq!(vm.cost_per_month(), Some(0.0));
+        assert!(!vm.supported_actions().contains(&Action::Stop));
+    }
+
+    #[test]
+    fn test_guest_stopped_vm_is_still_billed() {
+        let vm = vm_with_power_state("PowerState/stopped");
+        assert_eq!(vm.state(), ResourceState::Stopped);
+        assert_eq!(vm.cost_per_month(), Some(30.37));
+        assert!(vm.supported_actions().contains(&Action::Stop));
+    }
+
+    #[test]
+    fn test_vm_without_instance_view() {
+        let creating = vm(json!({ "provisioningState": "Creating" }));
+        assert_eq!(creating.state(), ResourceState::Pending);
+        assert_eq!(creating.supported_actions(), vec![Action::ViewDetails]);
+
+        // Nothing to go on at all, e.g. a VM listed without its status
+        let bare = vm(json!({}));
+        assert_eq!(bare.state(), ResourceState::Unknown);
+        assert_eq!(bare.vm_size(), "");
+        assert_eq!(bare.os_type(), None);
+        assert!(bare.created_at().is_none());
+    }
+
+    #[test]
+    fn test_vm_with_unknown_power_state_or_no_tags() {
+        let vm = AzureVM::from_api(&arm_resource(VM, json!({
+            "tags": null,
+            "properties": { "instanceView": { "statuses": [{ "code": "PowerState/hibernated" }] } }
+        })));
+        assert_eq!(vm.state(), ResourceState::Unknown);
+        assert!(vm.tags().is_empty());
+    }
+}
@@ -0,0 +1,20 @@
+//! Builders for provider API responses in unit tests.
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Parses `defaults` with `fields` laid over them, as a provider API would
+/// return the resource. A `null` field removes the default, since APIs leave
+/// unset values out.
+pub fn api_response<T: DeserializeOwned>(mut defaults: Value, fields: Value) -> T {
+    if let (Some(response), Value::Object(fields)) = (defaults.as_object_mut(), fields) {
+        for (key, value) in fields {
+            if value.is_null() {
+                response.remove(&key);
+            } else {
+                response.insert(key, value);
+            }
+        }
+    }
+    serde_json::from_value(defaults).expect("fixture does not match the API response")
+}
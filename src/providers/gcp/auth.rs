@@ -0,0 +1,80 @@
+use crate::config::GcpConfig;
+use crate::error::{NimbusError, Result};
+use gcp_auth::{CustomServiceAccount, TokenProvider};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// OAuth scope granting access to every Cloud Platform API the service
+/// account is authorized for.
+pub const CLOUD_PLATFORM_SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
+
+pub struct GcpAuth;
+
+impl GcpAuth {
+    /// Creates a token provider from the configured service account key,
+    /// falling back to Application Default Credentials (the
+    /// `GOOGLE_APPLICATION_CREDENTIALS` file, gcloud user credentials, or the
+    /// metadata server).
+    pub async fn create_provider(gcp_config: &GcpConfig) -> Result<Arc<dyn TokenProvider>> {
+        match &gcp_config.credentials_file {
+            Some(path) => {
+                let path = expand_home(path);
+                let account = CustomServiceAccount::from_file(&path).map_err(|e| {
+                    NimbusError::auth(
+                        "GCP",
+                        format!("Failed to load credentials from {}: {}", path.display(), e),
+                    )
+                })?;
+                Ok(Arc::new(account))
+            }
+            None => gcp_auth::provider().await.map_err(|e| {
+                NimbusError::auth("GCP", format!("No application default credentials found: {}", e))
+            }),
+        }
+    }
+
+    /// Resolves the project to manage: the configured one, else the project
+    /// the credentials belong to.
+    pub async fn resolve_project(gcp_config: &GcpConfig, provider: &dyn TokenProvider) -> Result<String> {
+        if !gcp_config.project_id.is_empty() {
+            return Ok(gcp_config.project_id.clone());
+        }
+
+        provider
+            .project_id()
+            .await
+            .map(|project| project.to_string())
+            .map_err(|_| NimbusError::MissingConfig("providers.gcp.project_id".to_string()))
+    }
+
+    /// Verifies the credentials by requesting an access token.
+    pub async fn test_credentials(provider: &dyn TokenProvider) -> Result<()> {
+        provider
+            .token(&[CLOUD_PLATFORM_SCOPE])
+            .await
+            .map(|_| ())
+            .map_err(|e| NimbusError::auth("GCP", format!("Failed to obtain an access token: {}", e)))
+    }
+}
+
+/// Expands a leading `~/` to the user's home directory.
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_home() {
+        assert_eq!(expand_home("/etc/key.json"), PathBuf::from("/etc/key.json"));
+
+        if let Some(home) = dirs::home_dir() {
+            assert_eq!(expand_home("~/key.json"), home.join("key.json"));
+        }
+    }
+}
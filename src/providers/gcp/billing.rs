@@ -0,0 +1,242 @@
+use crate::core::cost::UNTAGGED;
+use crate::core::{CostBreakdown, CostGranularity, CostPeriod, CostPoint};
+use crate::error::{NimbusError, Result};
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+
+use super::client::GcpClient;
+
+const BIGQUERY_API: &str = "https://bigquery.googleapis.com/bigquery/v2";
+
+/// Cost after credits such as sustained-use discounts and free tier usage.
+const NET_COST: &str = "cost + IFNULL((SELECT SUM(c.amount) FROM UNNEST(credits) c), 0)";
+
+/// How long BigQuery may run a query before the request gives up.
+const QUERY_TIMEOUT_MS: u64 = 30_000;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueryResponse {
+    job_complete: bool,
+    #[serde(default)]
+    rows: Vec<TableRow>,
+}
+
+#[derive(Deserialize)]
+struct TableRow {
+    f: Vec<TableCell>,
+}
+
+#[derive(Deserialize)]
+struct TableCell {
+    v: Option<String>,
+}
+
+/// Reads spend from a Cloud Billing export to BigQuery.
+///
+/// The Cloud Billing API itself only exposes prices and budgets, so actual
+/// spend has to come from the export table. Costs are limited to the
+/// provider's project.
+pub struct GcpBilling<'a> {
+    client: &'a GcpClient,
+    /// Fully qualified export table, `project.dataset.table`
+    table: &'a str,
+}
+
+impl<'a> GcpBilling<'a> {
+    pub fn new(client: &'a GcpClient, table: &'a str) -> Self {
+        Self { client, table }
+    }
+
+    pub async fn get_total_cost(&self, period: CostPeriod) -> Result<f64> {
+        let sql = format!(
+            "SELECT '', SUM({}) FROM `{}` WHERE {}",
+            NET_COST,
+            self.table,
+            Self::filter()
+        );
+        let rows = self.query(&sql, period, &[]).await?;
+        Ok(rows.iter().map(|(_, amount)| amount).sum())
+    }
+
+    pub async fn get_cost_breakdown(&self) -> Result<CostBreakdown> {
+        let period = CostPeriod::ThisMonth;
+        let by_service = format!(
+            "SELECT service.description, SUM({}) FROM `{}` WHERE {} GROUP BY 1",
+            NET_COST,
+            self.table,
+            Self::filter()
+        );
+        let by_region = format!(
+            "SELECT IFNULL(location.region, 'global'), SUM({}) FROM `{}` WHERE {} GROUP BY 1",
+            NET_COST,
+            self.table,
+            Self::filter()
+        );
+
+        let mut breakdown = CostBreakdown::new();
+        for (service, amount) in self.query(&by_service, period, &[]).await? {
+            breakdown.add_service_cost(service, amount);
+            breakdown.total += amount;
+        }
+        for (region, amount) in self.query(&by_region, period, &[]).await? {
+            breakdown.add_region_cost(region, amount);
+        }
+
+        Ok(breakdown)
+    }
+
+    pub async fn get_cost_history(
+        &self,
+        period: CostPeriod,
+        granularity: CostGranularity,
+    ) -> Result<Vec<CostPoint>> {
+        let bucket = match granularity {
+            CostGranularity::Daily => "DATE(usage_start_time)",
+            CostGranularity::Monthly => "DATE_TRUNC(DATE(usage_start_time), MONTH)",
+        };
+        let sql = format!(
+            "SELECT CAST({} AS STRING), SUM({}) FROM `{}` WHERE {} GROUP BY 1 ORDER BY 1",
+            bucket,
+            NET_COST,
+            self.table,
+            Self::filter()
+        );
+
+        let rows = self.query(&sql, period, &[]).await?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|(date, amount)| {
+                NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                    .ok()
+                    .map(|date| CostPoint::new(date, amount))
+            })
+            .collect())
+    }
+
+    /// Gets this month's spend grouped by the values of a resource label.
+    pub async fn get_cost_by_label(&self, label_key: &str) -> Result<HashMap<String, f64>> {
+        let sql = format!(
+            "SELECT (SELECT l.value FROM UNNEST(labels) l WHERE l.key = @label_key LIMIT 1), SUM({}) \
+             FROM `{}` WHERE {} GROUP BY 1",
+            NET_COST,
+            self.table,
+            Self::filter()
+        );
+
+        let rows = self
+            .query(&sql, CostPeriod::ThisMonth, &[("label_key", label_key)])
+            .await?;
+
+        let mut by_value = HashMap::new();
+        for (value, amount) in rows {
+            let value = if value.is_empty() { UNTAGGED.to_string() } else { value };
+            *by_value.entry(value).or_insert(0.0) += amount;
+        }
+        Ok(by_value)
+    }
+
+    fn filter() -> &'static str {
+        "project.id = @project AND DATE(usage_start_time) >= @start AND DATE(usage_start_time) < @end"
+    }
+
+    /// Runs a query whose rows are a label and an amount.
+    async fn query(
+        &self,
+        sql: &str,
+        period: CostPeriod,
+        params: &[(&str, &str)],
+    ) -> Result<Vec<(String, f64)>> {
+        let (start, end) = period.date_range(Utc::now().date_naive());
+
+        let mut parameters = vec![
+            string_parameter("project", self.client.project_id()),
+            date_parameter("start", start),
+            date_parameter("end", end),
+        ];
+        parameters.extend(params.iter().map(|(name, value)| string_parameter(name, value)));
+
+        let body = json!({
+            "query": sql,
+            "useLegacySql": false,
+            "parameterMode": "NAMED",
+            "queryParameters": parameters,
+            "timeoutMs": QUERY_TIMEOUT_MS,
+        });
+
+        let url = format!("{}/projects/{}/queries", BIGQUERY_API, self.client.project_id());
+        let response: QueryResponse = self.client.post_json(&url, &body).await.map_err(|e| {
+            NimbusError::provider("GCP", format!("Failed to query billing export {}: {}", self.table, e))
+        })?;
+
+        if !response.job_complete {
+            return Err(NimbusError::provider(
+                "GCP",
+                format!("Billing export query on {} timed out", self.table),
+            ));
+        }
+
+        Ok(parse_rows(response.rows))
+    }
+}
+
+fn string_parameter(name: &str, value: &str) -> serde_json::Value {
+    json!({
+        "name": name,
+        "parameterType": { "type": "STRING" },
+        "parameterValue": { "value": value },
+    })
+}
+
+fn date_parameter(name: &str, value: NaiveDate) -> serde_json::Value {
+    json!({
+        "name": name,
+        "parameterType": { "type": "DATE" },
+        "parameterValue": { "value": value.format("%Y-%m-%d").to_string() },
+    })
+}
+
+/// Reads `(label, amount)` pairs from query rows. A null label becomes an
+/// empty string; rows without a numeric amount are skipped.
+fn parse_rows(rows: Vec<TableRow>) -> Vec<(String, f64)> {
+    rows.into_iter()
+        .filter_map(|row| {
+            let mut cells = row.f.into_iter();
+            let label = cells.next().and_then(|cell| cell.v).unwrap_or_default();
+            let amount = cells.next().and_then(|cell| cell.v)?.parse::<f64>().ok()?;
+            Some((label, amount))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rows() {
+        let response: QueryResponse = serde_json::from_value(json!({
+            "jobComplete": true,
+            "rows": [
+                { "f": [{ "v": "Compute Engine" }, { "v": "12.5" }] },
+                { "f": [{ "v": null }, { "v": "3" }] },
+                { "f": [{ "v": "Cloud Storage" }, { "v": null }] }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            parse_rows(response.rows),
+            vec![("Compute Engine".to_string(), 12.5), (String::new(), 3.0)]
+        );
+    }
+
+    #[test]
+    fn test_query_response_without_rows() {
+        let response: QueryResponse = serde_json::from_value(json!({ "jobComplete": false })).unwrap();
+        assert!(!response.job_complete);
+        assert!(parse_rows(response.rows).is_empty());
+    }
+}
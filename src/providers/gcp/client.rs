@@ -0,0 +1,123 @@
+use crate::error::{NimbusError, Result};
+use gcp_auth::TokenProvider;
+use reqwest::{RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use super::auth::CLOUD_PLATFORM_SCOPE;
+
+/// Thin authenticated wrapper over the Google Cloud REST APIs.
+pub struct GcpClient {
+    http: reqwest::Client,
+    auth: Arc<dyn TokenProvider>,
+    project_id: String,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: ErrorDetail,
+}
+
+#[derive(Deserialize)]
+struct ErrorDetail {
+    message: String,
+}
+
+impl GcpClient {
+    pub fn new(auth: Arc<dyn TokenProvider>, project_id: String) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            auth,
+            project_id,
+        }
+    }
+
+    pub fn project_id(&self) -> &str {
+        &self.project_id
+    }
+
+    pub async fn get<T: DeserializeOwned>(&self, url: &str, query: &[(&str, &str)]) -> Result<T> {
+        let response = self.send(self.http.get(url).query(query)).await?;
+        response
+            .json()
+            .await
+            .map_err(|e| NimbusError::provider("GCP", format!("Invalid response from {}: {}", url, e)))
+    }
+
+    /// Sends a body-less POST, as used by resource methods such as `start`.
+    pub async fn post(&self, url: &str) -> Result<()> {
+        self.send(self.http.post(url).header(reqwest::header::CONTENT_LENGTH, 0))
+            .await
+            .map(|_| ())
+    }
+
+    pub async fn post_json<B: Serialize, T: DeserializeOwned>(&self, url: &str, body: &B) -> Result<T> {
+        let response = self.send(self.http.post(url).json(body)).await?;
+        response
+            .json()
+            .await
+            .map_err(|e| NimbusError::provider("GCP", format!("Invalid response from {}: {}", url, e)))
+    }
+
+    pub async fn patch<B: Serialize>(&self, url: &str, body: &B) -> Result<()> {
+        self.send(self.http.patch(url).json(body)).await.map(|_| ())
+    }
+
+    pub async fn delete(&self, url: &str) -> Result<()> {
+        self.send(self.http.delete(url)).await.map(|_| ())
+    }
+
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let token = self
+            .auth
+            .token(&[CLOUD_PLATFORM_SCOPE])
+            .await
+            .map_err(|e| NimbusError::auth("GCP", format!("Failed to obtain an access token: {}", e)))?;
+
+        let response = request
+            .bearer_auth(token.as_str())
+            .send()
+            .await
+            .map_err(|e| NimbusError::provider("GCP", format!("Request failed: {}", e)))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Err(NimbusError::ResourceNotFound(error_message(&body)));
+        }
+        Err(NimbusError::provider(
+            "GCP",
+            format!("{} ({})", error_message(&body), status.as_u16()),
+        ))
+    }
+}
+
+/// Extracts the human-readable message from a Google API error body.
+fn error_message(body: &str) -> String {
+    match serde_json::from_str::<ErrorResponse>(body) {
+        Ok(response) => response.error.message,
+        Err(_) if body.trim().is_empty() => "Empty error response".to_string(),
+        Err(_) => body.trim().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_message() {
+        let body = r#"{"error": {"code": 404, "message": "The resource 'projects/p/zones/z/instances/web' was not found", "status": "NOT_FOUND"}}"#;
+        assert_eq!(
+            error_message(body),
+            "The resource 'projects/p/zones/z/instances/web' was not found"
+        );
+        assert_eq!(error_message("Bad Gateway"), "Bad Gateway");
+        assert_eq!(error_message(""), "Empty error response");
+    }
+}
@@ -0,0 +1,1075 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::config::{parse_resource_types, GcpConfig};
+use crate::core::{
+    collect_stream, send_batch_if, AccountIdentity, Action, ActionRequest, CloudProvider, CloudResource,
+    CostBreakdown, CostFeature, CostGranularity, CostPeriod, CostPoint, DnsRecord, OperationHandle, Provider,
+    ProviderCapabilities, ResourceSender, ResourceType,
+};
+use crate::error::{NimbusError, Result};
+use std::collections::HashMap;
+
+mod auth;
+mod billing;
+mod client;
+mod monitoring;
+pub mod resources;
+
+use auth::GcpAuth;
+use billing::GcpBilling;
+use client::GcpClient;
+use monitoring::GcpMonitoring;
+use resources::cloudrun::RunService;
+use resources::cloudsql::SqlInstance;
+use resources::compute::{region_of_zone, ComputeInstance};
+use resources::dns::{ManagedZone, ResourceRecordSet};
+use resources::functions::FunctionInfo;
+use resources::gke::{region_of_location, GkeCluster};
+use resources::loadbalancer::ForwardingRule;
+use resources::storage::StorageBucket;
+use resources::{
+    CloudDnsZone, CloudFunction, CloudRunService, CloudSqlInstance, GCEInstance, GCPLoadBalancer,
+    GCSBucket, GKECluster,
+};
+
+const COMPUTE_API: &str = "https://compute.googleapis.com/compute/v1";
+const SQL_ADMIN_API: &str = "https://sqladmin.googleapis.com/v1";
+const STORAGE_API: &str = "https://storage.googleapis.com/storage/v1";
+const DNS_API: &str = "https://dns.googleapis.com/dns/v1";
+const CONTAINER_API: &str = "https://container.googleapis.com/v1";
+const RUN_API: &str = "https://run.googleapis.com/v2";
+const FUNCTIONS_API: &str = "https://cloudfunctions.googleapis.com/v2";
+
+/// Regions offered before the project's own list has been discovered.
+const FALLBACK_REGIONS: [&str; 12] = [
+    "us-central1",
+    "us-east1",
+    "us-east4",
+    "us-west1",
+    "us-west2",
+    "europe-west1",
+    "europe-west2",
+    "europe-west3",
+    "europe-west4",
+    "asia-east1",
+    "asia-northeast1",
+    "asia-southeast1",
+];
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AggregatedInstanceList {
+    #[serde(default)]
+    items: HashMap<String, InstancesScopedList>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct InstancesScopedList {
+    #[serde(default)]
+    instances: Vec<ComputeInstance>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AggregatedForwardingRuleList {
+    #[serde(default)]
+    items: HashMap<String, ForwardingRulesScopedList>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ForwardingRulesScopedList {
+    #[serde(default)]
+    forwarding_rules: Vec<ForwardingRule>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SqlInstanceList {
+    #[serde(default)]
+    items: Vec<SqlInstance>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ManagedZoneList {
+    #[serde(default)]
+    managed_zones: Vec<ManagedZone>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecordSetList {
+    #[serde(default)]
+    rrsets: Vec<ResourceRecordSet>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BucketList {
+    #[serde(default)]
+    items: Vec<StorageBucket>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RunServiceList {
+    #[serde(default)]
+    services: Vec<RunService>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FunctionList {
+    #[serde(default)]
+    functions: Vec<FunctionInfo>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ClusterList {
+    #[serde(default)]
+    clusters: Vec<GkeCluster>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RegionList {
+    #[serde(default)]
+    items: Vec<RegionItem>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RegionItem {
+    name: String,
+}
+
+pub struct GCPProvider {
+    name: String,
+    config: GcpConfig,
+    client: Option<GcpClient>,
+    /// Regions available to the project, discovered after authentication
+    discovered_regions: Option<Vec<String>>,
+}
+
+impl GCPProvider {
+    pub fn new(config: GcpConfig) -> Self {
+        let name = match config.label {
+            Some(ref label) => format!("GCP ({})", label),
+            None if config.project_id.is_empty() => "GCP".to_string(),
+            None => format!("GCP ({})", config.project_id),
+        };
+
+        Self {
+            name,
+            config,
+            client: None,
+            discovered_regions: None,
+        }
+    }
+
+    async fn ensure_authenticated(&self) -> Result<()> {
+        if self.client.is_none() {
+            return Err(NimbusError::auth(
+                "GCP",
+                "Provider not authenticated. Call authenticate() first.",
+            ));
+        }
+        Ok(())
+    }
+
+    fn get_client(&self) -> Result<&GcpClient> {
+        self.client.as_ref().ok_or_else(|| {
+            NimbusError::auth(
+                "GCP",
+                "Client not initialized. Call authenticate() first.",
+            )
+        })
+    }
+
+    /// Returns the billing export reader, or `None` if no export table is configured.
+    async fn billing(&self) -> Result<Option<GcpBilling<'_>>> {
+        match self.config.billing_export_table {
+            Some(ref table) => {
+                self.ensure_authenticated().await?;
+                Ok(Some(GcpBilling::new(self.get_client()?, table)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn project_url(&self, client: &GcpClient) -> String {
+        format!("{}/projects/{}", COMPUTE_API, client.project_id())
+    }
+
+    async fn discover_regions(&self) -> Result<Vec<String>> {
+        let client = self.get_client()?;
+        let url = format!("{}/regions", self.project_url(client));
+        let mut regions = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut query = vec![("fields", "items/name,nextPageToken")];
+            if let Some(ref token) = page_token {
+                query.push(("pageToken", token.as_str()));
+            }
+
+            let page: RegionList = client.get(&url, &query).await?;
+            regions.extend(page.items.into_iter().map(|region| region.name));
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+
+        regions.sort();
+        Ok(regions)
+    }
+
+    async fn list_compute_instances(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let client = self.get_client()?;
+        let url = format!("{}/aggregated/instances", self.project_url(client));
+        let mut instances: Vec<Box<dyn CloudResource>> = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut query = Vec::new();
+            if let Some(ref token) = page_token {
+                query.push(("pageToken", token.as_str()));
+            }
+
+            let page: AggregatedInstanceList = client.get(&url, &query).await.map_err(|e| {
+                NimbusError::provider("GCP", format!("Failed to list Compute Engine instances: {}", e))
+            })?;
+
+            // Results are keyed by "zones/<zone>"; keep the current region's zones
+            for (scope, list) in page.items {
+                let zone = scope.trim_start_matches("zones/");
+                if region_of_zone(zone) != self.config.region {
+                    continue;
+                }
+                for instance in &list.instances {
+                    instances.push(Box::new(GCEInstance::from_api(instance)));
+                }
+            }
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+
+        Ok(instances)
+    }
+
+    async fn list_load_balancers(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let client = self.get_client()?;
+        let url = format!("{}/aggregated/forwardingRules", self.project_url(client));
+        let mut load_balancers: Vec<Box<dyn CloudResource>> = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut query = Vec::new();
+            if let Some(ref token) = page_token {
+                query.push(("pageToken", token.as_str()));
+            }
+
+            let page: AggregatedForwardingRuleList = client.get(&url, &query).await.map_err(|e| {
+                NimbusError::provider("GCP", format!("Failed to list forwarding rules: {}", e))
+            })?;
+
+            // Results are keyed by "regions/<region>" or "global"
+            for (scope, list) in page.items {
+                let region = scope.trim_start_matches("regions/");
+                if region != "global" && region != self.config.region {
+                    continue;
+                }
+                for rule in &list.forwarding_rules {
+                    load_balancers.push(Box::new(GCPLoadBalancer::from_api(rule)));
+                }
+            }
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+
+        Ok(load_balancers)
+    }
+
+    async fn execute_lb_action(&self, resource_id: &str, action: Action) -> Result<()> {
+        let client = self.get_client()?;
+
+        match action {
+            Action::Terminate => {
+                let url = format!("{}/{}", self.project_url(client), resource_id);
+                client.delete(&url).await.map_err(|e| {
+                    NimbusError::provider("GCP", format!("Failed to delete forwarding rule {}: {}", resource_id, e))
+                })
+            }
+            _ => Err(NimbusError::UnsupportedAction(action, ResourceType::LoadBalancer)),
+        }
+    }
+
+    async fn list_cloudsql_instances(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let client = self.get_client()?;
+        let url = format!("{}/projects/{}/instances", SQL_ADMIN_API, client.project_id());
+        let mut instances: Vec<Box<dyn CloudResource>> = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut query = Vec::new();
+            if let Some(ref token) = page_token {
+                query.push(("pageToken", token.as_str()));
+            }
+
+            let page: SqlInstanceList = client.get(&url, &query).await.map_err(|e| {
+                NimbusError::provider("GCP", format!("Failed to list Cloud SQL instances: {}", e))
+            })?;
+
+            instances.extend(
+                page.items
+                    .iter()
+                    .filter(|instance| instance.region == self.config.region)
+                    .map(|instance| Box::new(CloudSqlInstance::from_api(instance)) as Box<dyn CloudResource>),
+            );
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+
+        Ok(instances)
+    }
+
+    async fn list_gke_clusters(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let client = self.get_client()?;
+        // "-" lists clusters in every location; the API does not paginate
+        let url = format!("{}/projects/{}/locations/-/clusters", CONTAINER_API, client.project_id());
+
+        let list: ClusterList = client.get(&url, &[]).await.map_err(|e| {
+            NimbusError::provider("GCP", format!("Failed to list GKE clusters: {}", e))
+        })?;
+
+        Ok(list
+            .clusters
+            .iter()
+            .filter(|cluster| region_of_location(&cluster.location) == self.config.region)
+            .map(|cluster| Box::new(GKECluster::from_api(cluster)) as Box<dyn CloudResource>)
+            .collect())
+    }
+
+    async fn execute_gke_action(&self, resource_id: &str, action: Action) -> Result<()> {
+        let client = self.get_client()?;
+
+        match action {
+            Action::Terminate => {
+                let url = format!("{}/projects/{}/{}", CONTAINER_API, client.project_id(), resource_id);
+                client.delete(&url).await.map_err(|e| {
+                    NimbusError::provider("GCP", format!("Failed to delete GKE cluster {}: {}", resource_id, e))
+                })
+            }
+            _ => Err(NimbusError::UnsupportedAction(action, ResourceType::Container)),
+        }
+    }
+
+    async fn list_cloud_run_services(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let client = self.get_client()?;
+        let url = format!(
+            "{}/projects/{}/locations/{}/services",
+            RUN_API,
+            client.project_id(),
+            self.config.region
+        );
+        let mut services = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut query = Vec::new();
+            if let Some(ref token) = page_token {
+                query.push(("pageToken", token.as_str()));
+            }
+
+            let page: RunServiceList = client.get(&url, &query).await.map_err(|e| {
+                NimbusError::provider("GCP", format!("Failed to list Cloud Run services: {}", e))
+            })?;
+            services.extend(page.services);
+
+            match page.next_page_token {
+                Some(token) if !token.is_empty() => page_token = Some(token),
+                _ => break,
+            }
+        }
+
+        // 2nd gen functions run on Cloud Run and are listed as functions instead
+        services.retain(|service| {
+            service.labels.get("goog-managed-by").map(String::as_str) != Some("cloudfunctions")
+        });
+
+        let requests = if services.is_empty() {
+            None
+        } else {
+            GcpMonitoring::new(client)
+                .run_request_counts(&self.config.region)
+                .await
+                .map_err(|e| log::warn!("Skipping Cloud Run request counts: {}", e))
+                .ok()
+        };
+
+        // Services missing from the counts served no requests
+        Ok(services
+            .iter()
+            .map(|service| {
+                let run_service = CloudRunService::from_api(service);
+                let run_service = match requests {
+                    Some(ref counts) => {
+                        let count = counts.get(run_service.name()).copied().unwrap_or(0);
+                        run_service.with_requests(count)
+                    }
+                    None => run_service,
+                };
+                Box::new(run_service) as Box<dyn CloudResource>
+            })
+            .collect())
+    }
+
+    async fn list_cloud_functions(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let client = self.get_client()?;
+        let url = format!(
+            "{}/projects/{}/locations/{}/functions",
+            FUNCTIONS_API,
+            client.project_id(),
+            self.config.region
+        );
+        let mut functions = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut query = Vec::new();
+            if let Some(ref token) = page_token {
+                query.push(("pageToken", token.as_str()));
+            }
+
+            let page: FunctionList = client.get(&url, &query).await.map_err(|e| {
+                NimbusError::provider("GCP", format!("Failed to list Cloud Functions: {}", e))
+            })?;
+            functions.extend(page.functions);
+
+            match page.next_page_token {
+                Some(token) if !token.is_empty() => page_token = Some(token),
+                _ => break,
+            }
+        }
+
+        let invocations = if functions.is_empty() {
+            None
+        } else {
+            GcpMonitoring::new(client)
+                .function_invocation_counts(&self.config.region)
+                .await
+                .map_err(|e| log::warn!("Skipping Cloud Functions invocation counts: {}", e))
+                .ok()
+        };
+
+        Ok(functions
+            .iter()
+            .map(|function| {
+                let cloud_function = CloudFunction::from_api(function);
+                let cloud_function = match invocations {
+                    Some(ref counts) => {
+                        let count = counts.get(cloud_function.name()).copied().unwrap_or(0);
+                        cloud_function.with_invocations(count)
+                    }
+                    None => cloud_function,
+                };
+                Box::new(cloud_function) as Box<dyn CloudResource>
+            })
+            .collect())
+    }
+
+    async fn execute_serverless_action(&self, resource_id: &str, action: Action) -> Result<()> {
+        let client = self.get_client()?;
+
+        let (api, kind) = if resource_id.contains("/functions/") {
+            (FUNCTIONS_API, "Cloud Function")
+        } else {
+            (RUN_API, "Cloud Run service")
+        };
+
+        match action {
+            Action::Terminate => {
+                let url = format!("{}/projects/{}/{}", api, client.project_id(), resource_id);
+                client.delete(&url).await.map_err(|e| {
+                    NimbusError::provider("GCP", format!("Failed to delete {} {}: {}", kind, resource_id, e))
+                })
+            }
+            _ => Err(NimbusError::UnsupportedAction(action, ResourceType::Serverless)),
+        }
+    }
+
+    async fn list_dns_zones(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let client = self.get_client()?;
+        let url = format!("{}/projects/{}/managedZones", DNS_API, client.project_id());
+        let mut zones: Vec<Box<dyn CloudResource>> = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut query = Vec::new();
+            if let Some(ref token) = page_token {
+                query.push(("pageToken", token.as_str()));
+            }
+
+            let page: ManagedZoneList = client.get(&url, &query).await.map_err(|e| {
+                NimbusError::provider("GCP", format!("Failed to list Cloud DNS zones: {}", e))
+            })?;
+
+            // Managed zones carry no record count, so count the record sets
+            for zone in &page.managed_zones {
+                let record_count = match self.list_record_sets(&zone.name).await {
+                    Ok(records) => Some(records.len() as i64),
+                    Err(e) => {
+                        log::debug!("Could not count records in zone {}: {}", zone.name, e);
+                        None
+                    }
+                };
+                zones.push(Box::new(CloudDnsZone::from_api(zone).with_record_count(record_count)));
+            }
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+
+        Ok(zones)
+    }
+
+    async fn list_record_sets(&self, zone_name: &str) -> Result<Vec<DnsRecord>> {
+        let client = self.get_client()?;
+        let zone_name = zone_name.trim_start_matches("managedZones/");
+        let url = format!(
+            "{}/projects/{}/managedZones/{}/rrsets",
+            DNS_API,
+            client.project_id(),
+            zone_name
+        );
+        let mut records = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut query = Vec::new();
+            if let Some(ref token) = page_token {
+                query.push(("pageToken", token.as_str()));
+            }
+
+            let page: RecordSetList = client.get(&url, &query).await.map_err(|e| {
+                NimbusError::provider("GCP", format!("Failed to list records for zone {}: {}", zone_name, e))
+            })?;
+            records.extend(page.rrsets.into_iter().map(DnsRecord::from));
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+
+        Ok(records)
+    }
+
+    async fn execute_dns_action(&self, resource_id: &str, action: Action) -> Result<()> {
+        let client = self.get_client()?;
+
+        match action {
+            Action::Terminate => {
+                let url = format!("{}/projects/{}/{}", DNS_API, client.project_id(), resource_id);
+                client.delete(&url).await.map_err(|e| {
+                    NimbusError::provider("GCP", format!("Failed to delete zone {}: {}", resource_id, e))
+                })
+            }
+            _ => Err(NimbusError::UnsupportedAction(action, ResourceType::DNS)),
+        }
+    }
+
+    async fn list_gcs_buckets(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let client = self.get_client()?;
+        let url = format!("{}/b", STORAGE_API);
+        let mut buckets = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut query = vec![("project", client.project_id())];
+            if let Some(ref token) = page_token {
+                query.push(("pageToken", token.as_str()));
+            }
+
+            let page: BucketList = client.get(&url, &query).await.map_err(|e| {
+                NimbusError::provider("GCP", format!("Failed to list Cloud Storage buckets: {}", e))
+            })?;
+            buckets.extend(page.items);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+
+        let sizes = if buckets.is_empty() {
+            HashMap::new()
+        } else {
+            GcpMonitoring::new(client).bucket_sizes().await.unwrap_or_else(|e| {
+                log::warn!("Skipping Cloud Storage bucket sizes: {}", e);
+                HashMap::new()
+            })
+        };
+
+        // Buckets are project-wide like S3, so they are not filtered by region
+        Ok(buckets
+            .iter()
+            .map(|bucket| {
+                let gcs_bucket = GCSBucket::from_api(bucket);
+                let gcs_bucket = match sizes.get(&bucket.name) {
+                    Some(&size) => gcs_bucket.with_size(size),
+                    None => gcs_bucket,
+                };
+                Box::new(gcs_bucket) as Box<dyn CloudResource>
+            })
+            .collect())
+    }
+
+    async fn execute_gcs_action(&self, resource_id: &str, action: Action) -> Result<()> {
+        let client = self.get_client()?;
+
+        match action {
+            Action::Terminate => {
+                let url = format!("{}/{}", STORAGE_API, resource_id);
+                client.delete(&url).await.map_err(|e| {
+                    NimbusError::provider("GCP", format!("Failed to delete bucket {}: {}", resource_id, e))
+                })
+            }
+            _ => Err(NimbusError::UnsupportedAction(action, ResourceType::Storage)),
+        }
+    }
+
+    async fn execute_cloudsql_action(&self, resource_id: &str, action: Action) -> Result<()> {
+        let client = self.get_client()?;
+        let url = format!("{}/projects/{}/{}", SQL_ADMIN_API, client.project_id(), resource_id);
+
+        // Cloud SQL starts and stops by switching the activation policy
+        let (activation_policy, verb) = match action {
+            Action::Start => ("ALWAYS", "start"),
+            Action::Stop => ("NEVER", "stop"),
+            _ => return Err(NimbusError::UnsupportedAction(action, ResourceType::Database)),
+        };
+
+        let body = serde_json::json!({ "settings": { "activationPolicy": activation_policy } });
+        client.patch(&url, &body).await.map_err(|e| {
+            NimbusError::provider("GCP", format!("Failed to {} Cloud SQL instance {}: {}", verb, resource_id, e))
+        })
+    }
+
+    async fn execute_compute_action(&self, resource_id: &str, action: Action) -> Result<()> {
+        let client = self.get_client()?;
+        let url = format!("{}/{}", self.project_url(client), resource_id);
+
+        let (result, verb) = match action {
+            Action::Start => (client.post(&format!("{}/start", url)).await, "start"),
+            Action::Stop => (client.post(&format!("{}/stop", url)).await, "stop"),
+            Action::Restart => (client.post(&format!("{}/reset", url)).await, "reset"),
+            Action::Terminate => (client.delete(&url).await, "delete"),
+            _ => return Err(NimbusError::UnsupportedAction(action, ResourceType::Compute)),
+        };
+
+        result.map_err(|e| {
+            NimbusError::provider("GCP", format!("Failed to {} instance {}: {}", verb, resource_id, e))
+        })
+    }
+}
+
+#[async_trait]
+impl CloudProvider for GCPProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn provider_type(&self) -> Provider {
+        Provider::GCP
+    }
+
+    fn account_name(&self) -> &str {
+        match (self.config.label.as_deref(), self.client.as_ref()) {
+            (Some(label), _) => label,
+            (None, Some(client)) => client.project_id(),
+            (None, None) if !self.config.project_id.is_empty() => &self.config.project_id,
+            (None, None) => self.name(),
+        }
+    }
+
+    async fn authenticate(&mut self) -> Result<()> {
+        let token_provider = GcpAuth::create_provider(&self.config).await?;
+        GcpAuth::test_credentials(token_provider.as_ref()).await?;
+        let project_id = GcpAuth::resolve_project(&self.config, token_provider.as_ref()).await?;
+
+        if self.config.label.is_none() && self.config.project_id.is_empty() {
+            self.name = format!("GCP ({})", project_id);
+        }
+        self.client = Some(GcpClient::new(token_provider, project_id));
+
+        // The region list is project-wide, so switching regions reuses it
+        if self.discovered_regions.is_none() {
+            match self.discover_regions().await {
+                Ok(regions) if !regions.is_empty() => self.discovered_regions = Some(regions),
+                Ok(_) => {}
+                Err(e) => log::warn!("Using built-in GCP region list: {}", e),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn identity(&self) -> Option<AccountIdentity> {
+        let project_id = self.client.as_ref()?.project_id();
+        Some(AccountIdentity::new(project_id, format!("projects/{}", project_id)))
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        let capabilities = ProviderCapabilities::none()
+            .with_resource_types(&[
+                ResourceType::Compute,
+                ResourceType::Database,
+                ResourceType::Storage,
+                ResourceType::LoadBalancer,
+                ResourceType::DNS,
+                ResourceType::Container,
+                ResourceType::Serverless,
+            ])
+            .restrict_resource_types(&parse_resource_types(&self.config.resource_types).unwrap_or_default())
+            .with_actions(&[
+                Action::Start,
+                Action::Stop,
+                Action::Restart,
+                Action::Terminate,
+                Action::ViewRecords,
+            ]);
+
+        // Spend history and labels come from the billing export
+        match self.config.billing_export_table {
+            Some(_) => capabilities.with_cost_features(&[CostFeature::History, CostFeature::ByTag]),
+            None => capabilities,
+        }
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+        let url = self.project_url(client);
+
+        match client.get::<serde_json::Value>(&url, &[("fields", "name")]).await {
+            Ok(_) => Ok(true),
+            Err(e) => Err(NimbusError::provider(
+                "GCP",
+                format!("Connection test failed: {}", e),
+            )),
+        }
+    }
+
+    async fn list_all_resources(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        collect_stream(self).await
+    }
+
+    async fn stream_all_resources(&self, sender: ResourceSender) -> Result<()> {
+        self.ensure_authenticated().await?;
+        let caps = self.capabilities();
+
+        tokio::join!(
+            send_batch_if(&sender, &caps, ResourceType::Compute, "Compute Engine", self.list_compute_instances()),
+            send_batch_if(&sender, &caps, ResourceType::Database, "Cloud SQL", self.list_cloudsql_instances()),
+            send_batch_if(&sender, &caps, ResourceType::Storage, "Cloud Storage", self.list_gcs_buckets()),
+            send_batch_if(&sender, &caps, ResourceType::LoadBalancer, "Load Balancing", self.list_load_balancers()),
+            send_batch_if(&sender, &caps, ResourceType::DNS, "Cloud DNS", self.list_dns_zones()),
+            send_batch_if(&sender, &caps, ResourceType::Container, "GKE", self.list_gke_clusters()),
+            send_batch_if(&sender, &caps, ResourceType::Serverless, "Cloud Run", self.list_cloud_run_services()),
+            send_batch_if(&sender, &caps, ResourceType::Serverless, "Cloud Functions", self.list_cloud_functions()),
+        );
+
+        Ok(())
+    }
+
+    async fn list_resources_by_type(
+        &self,
+        resource_type: ResourceType,
+    ) -> Result<Vec<Box<dyn CloudResource>>> {
+        if !self.capabilities().supports_type(resource_type) {
+            return Ok(Vec::new());
+        }
+        self.ensure_authenticated().await?;
+
+        match resource_type {
+            ResourceType::Compute => self.list_compute_instances().await,
+            ResourceType::Database => self.list_cloudsql_instances().await,
+            ResourceType::Storage => self.list_gcs_buckets().await,
+            ResourceType::LoadBalancer => self.list_load_balancers().await,
+            ResourceType::DNS => self.list_dns_zones().await,
+            ResourceType::Container => self.list_gke_clusters().await,
+            ResourceType::Serverless => {
+                let (run, functions) =
+                    tokio::join!(self.list_cloud_run_services(), self.list_cloud_functions());
+                let mut resources = run?;
+                resources.extend(functions?);
+                Ok(resources)
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    async fn get_resource(&self, id: &str) -> Result<Box<dyn CloudResource>> {
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?;
+
+        if id.starts_with("zones/") {
+            let url = format!("{}/{}", self.project_url(client), id);
+            let instance: ComputeInstance = client.get(&url, &[]).await?;
+            return Ok(Box::new(GCEInstance::from_api(&instance)));
+        }
+
+        if id.starts_with("instances/") {
+            let url = format!("{}/projects/{}/{}", SQL_ADMIN_API, client.project_id(), id);
+            let instance: SqlInstance = client.get(&url, &[]).await?;
+            return Ok(Box::new(CloudSqlInstance::from_api(&instance)));
+        }
+
+        if id.contains("/forwardingRules/") {
+            let url = format!("{}/{}", self.project_url(client), id);
+            let rule: ForwardingRule = client.get(&url, &[]).await?;
+            return Ok(Box::new(GCPLoadBalancer::from_api(&rule)));
+        }
+
+        if id.starts_with("managedZones/") {
+            let url = format!("{}/projects/{}/{}", DNS_API, client.project_id(), id);
+            let zone: ManagedZone = client.get(&url, &[]).await?;
+            return Ok(Box::new(CloudDnsZone::from_api(&zone)));
+        }
+
+        if id.contains("/services/") {
+            let url = format!("{}/projects/{}/{}", RUN_API, client.project_id(), id);
+            let service: RunService = client.get(&url, &[]).await?;
+            return Ok(Box::new(CloudRunService::from_api(&service)));
+        }
+
+        if id.contains("/functions/") {
+            let url = format!("{}/projects/{}/{}", FUNCTIONS_API, client.project_id(), id);
+            let function: FunctionInfo = client.get(&url, &[]).await?;
+            return Ok(Box::new(CloudFunction::from_api(&function)));
+        }
+
+        if id.contains("/clusters/") {
+            let url = format!("{}/projects/{}/{}", CONTAINER_API, client.project_id(), id);
+            let cluster: GkeCluster = client.get(&url, &[]).await?;
+            return Ok(Box::new(GKECluster::from_api(&cluster)));
+        }
+
+        if id.starts_with("b/") {
+            let url = format!("{}/{}", STORAGE_API, id);
+            let bucket: StorageBucket = client.get(&url, &[]).await?;
+            return Ok(Box::new(GCSBucket::from_api(&bucket)));
+        }
+
+        Err(NimbusError::ResourceNotFound(id.to_string()))
+    }
+
+    async fn execute_action(
+        &self,
+        resource_id: &str,
+        resource_type: ResourceType,
+        request: &ActionRequest,
+    ) -> Result<OperationHandle> {
+        self.ensure_authenticated().await?;
+        let action = request.action;
+
+        match resource_type {
+            ResourceType::Compute => self.execute_compute_action(resource_id, action).await,
+            ResourceType::Database => self.execute_cloudsql_action(resource_id, action).await,
+            ResourceType::Storage => self.execute_gcs_action(resource_id, action).await,
+            ResourceType::LoadBalancer => self.execute_lb_action(resource_id, action).await,
+            ResourceType::DNS => self.execute_dns_action(resource_id, action).await,
+            ResourceType::Container => self.execute_gke_action(resource_id, action).await,
+            ResourceType::Serverless => self.execute_serverless_action(resource_id, action).await,
+            other => Err(NimbusError::UnsupportedAction(action, other)),
+        }?;
+
+        Ok(OperationHandle::new(resource_id, resource_type, request))
+    }
+
+    async fn list_dns_records(&self, zone_id: &str) -> Result<Vec<DnsRecord>> {
+        self.ensure_authenticated().await?;
+        self.list_record_sets(zone_id).await
+    }
+
+    // Spend is only available through a BigQuery billing export; without
+    // one configured, GCP contributes nothing to the cost totals.
+    async fn get_total_cost(&self, period: CostPeriod) -> Result<f64> {
+        match self.billing().await? {
+            Some(billing) => billing.get_total_cost(period).await,
+            None => Ok(0.0),
+        }
+    }
+
+    async fn get_cost_breakdown(&self) -> Result<CostBreakdown> {
+        match self.billing().await? {
+            Some(billing) => billing.get_cost_breakdown().await,
+            None => Ok(CostBreakdown::new()),
+        }
+    }
+
+    async fn get_cost_by_tag(&self, tag_key: &str) -> Result<HashMap<String, f64>> {
+        match self.billing().await? {
+            Some(billing) => billing.get_cost_by_label(tag_key).await,
+            None => Ok(HashMap::new()),
+        }
+    }
+
+    async fn get_cost_history(
+        &self,
+        period: CostPeriod,
+        granularity: CostGranularity,
+    ) -> Result<Vec<CostPoint>> {
+        match self.billing().await? {
+            Some(billing) => billing.get_cost_history(period, granularity).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn console_url(&self, resource_id: &str, resource_type: ResourceType) -> Option<String> {
+        let project = match self.client.as_ref() {
+            Some(client) => client.project_id(),
+            None => self.config.project_id.as_str(),
+        };
+        if project.is_empty() {
+            return None;
+        }
+
+        let parts: Vec<&str> = resource_id.split('/').collect();
+        let path = match (resource_type, parts.as_slice()) {
+            (ResourceType::Compute, ["zones", _, "instances", _]) => format!("compute/instancesDetail/{}", resource_id),
+            (ResourceType::Database, ["instances", name]) => format!("sql/instances/{}/overview", name),
+            (ResourceType::Storage, ["b", name]) => format!("storage/browser/{}", name),
+            (ResourceType::Container, ["locations", location, "clusters", name]) => {
+                format!("kubernetes/clusters/details/{}/{}/details", location, name)
+            }
+            (ResourceType::Serverless, ["locations", region, "services", name]) => {
+                format!("run/detail/{}/{}", region, name)
+            }
+            (ResourceType::Serverless, ["locations", region, "functions", name]) => {
+                format!("functions/details/{}/{}", region, name)
+            }
+            (ResourceType::DNS, ["managedZones", name]) => format!("net-services/dns/zones/{}/details", name),
+            (ResourceType::LoadBalancer, _) => "net-services/loadbalancing/list/loadBalancers".to_string(),
+            _ => return None,
+        };
+        Some(format!("https://console.cloud.google.com/{}?project={}", path, project))
+    }
+
+    fn regions(&self) -> Vec<String> {
+        match self.discovered_regions {
+            Some(ref regions) => regions.clone(),
+            None => FALLBACK_REGIONS.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+
+    fn current_region(&self) -> &str {
+        &self.config.region
+    }
+
+    async fn set_region(&mut self, region: &str) -> Result<()> {
+        if !self.regions().contains(&region.to_string()) {
+            return Err(NimbusError::InvalidRegion(region.to_string()));
+        }
+
+        // Compute Engine endpoints are global, so no need to re-authenticate
+        self.config.region = region.to_string();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_name_uses_project() {
+        let provider = GCPProvider::new(GcpConfig {
+            project_id: "demo-project".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(provider.name(), "GCP (demo-project)");
+        assert_eq!(provider.provider_type(), Provider::GCP);
+        assert!(provider.identity().is_none());
+    }
+
+    #[test]
+    fn test_provider_regions_fallback() {
+        let provider = GCPProvider::new(GcpConfig::default());
+        assert_eq!(provider.name(), "GCP");
+        assert!(provider.regions().contains(&"us-central1".to_string()));
+        assert_eq!(provider.current_region(), "us-central1");
+    }
+
+    #[test]
+    fn test_console_url() {
+        let provider = GCPProvider::new(GcpConfig {
+            project_id: "demo-project".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(
+            provider
+                .console_url("zones/us-central1-a/instances/web", ResourceType::Compute)
+                .as_deref(),
+            Some("https://console.cloud.google.com/compute/instancesDetail/zones/us-central1-a/instances/web?project=demo-project")
+        );
+        assert_eq!(
+            provider
+                .console_url("locations/europe-west1/services/api", ResourceType::Serverless)
+                .as_deref(),
+            Some("https://console.cloud.google.com/run/detail/europe-west1/api?project=demo-project")
+        );
+        assert!(provider.console_url("b/assets/extra", ResourceType::Storage).is_none());
+        assert!(GCPProvider::new(GcpConfig::default())
+            .console_url("b/assets", ResourceType::Storage)
+            .is_none());
+    }
+
+    #[test]
+    fn test_aggregated_list_parsing() {
+        let page: AggregatedInstanceList = serde_json::from_value(serde_json::json!({
+            "items": {
+                "zones/us-central1-a": {
+                    "instances": [{
+                        "name": "web-1",
+                        "zone": "projects/demo/zones/us-central1-a",
+                        "machineType": "zones/us-central1-a/machineTypes/e2-small",
+                        "status": "RUNNING"
+                    }]
+                },
+                "zones/europe-west1-b": {
+                    "warning": { "code": "NO_RESULTS_ON_PAGE" }
+                }
+            }
+        }))
+        .unwrap();
+
+        assert!(page.next_page_token.is_none());
+        assert_eq!(page.items["zones/us-central1-a"].instances.len(), 1);
+        assert!(page.items["zones/europe-west1-b"].instances.is_empty());
+    }
+}
@@ -0,0 +1,243 @@
+use crate::error::Result;
+use chrono::{Duration, SecondsFormat, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::client::GcpClient;
+
+const MONITORING_API: &str = "https://monitoring.googleapis.com/v3";
+
+/// Daily sample of the bytes stored in a bucket, one series per storage class.
+const BUCKET_BYTES_FILTER: &str = r#"metric.type = "storage.googleapis.com/storage/total_bytes""#;
+
+const RUN_REQUESTS_METRIC: &str = "run.googleapis.com/request_count";
+const FUNCTION_EXECUTIONS_METRIC: &str = "cloudfunctions.googleapis.com/function/execution_count";
+
+/// Window request and invocation counts are summed over, as a duration string.
+const USAGE_WINDOW: &str = "2592000s";
+const USAGE_WINDOW_DAYS: i64 = 30;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TimeSeriesList {
+    #[serde(default)]
+    time_series: Vec<TimeSeries>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TimeSeries {
+    resource: MonitoredResource,
+    #[serde(default)]
+    points: Vec<Point>,
+}
+
+#[derive(Deserialize)]
+struct MonitoredResource {
+    #[serde(default)]
+    labels: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct Point {
+    value: TypedValue,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TypedValue {
+    double_value: Option<f64>,
+    /// 64-bit integers are encoded as strings in JSON
+    int64_value: Option<String>,
+}
+
+pub struct GcpMonitoring<'a> {
+    client: &'a GcpClient,
+}
+
+impl<'a> GcpMonitoring<'a> {
+    pub fn new(client: &'a GcpClient) -> Self {
+        Self { client }
+    }
+
+    /// Fetches the stored bytes of every bucket in the project.
+    ///
+    /// Cloud Storage reports sizes once a day, so buckets created in the last
+    /// day are absent from the result.
+    pub async fn bucket_sizes(&self) -> Result<HashMap<String, u64>> {
+        let series = self.list_time_series(BUCKET_BYTES_FILTER, Duration::days(2), &[]).await?;
+        Ok(sum_latest_by_bucket(&series))
+    }
+
+    /// Fetches the requests served by each Cloud Run service in `region`
+    /// over the last 30 days. Services without traffic are absent.
+    pub async fn run_request_counts(&self, region: &str) -> Result<HashMap<String, u64>> {
+        let filter = format!(
+            r#"metric.type = "{}" AND resource.labels.location = "{}""#,
+            RUN_REQUESTS_METRIC, region
+        );
+        self.usage_counts(&filter, "service_name").await
+    }
+
+    /// Fetches the invocations of each Cloud Function in `region` over the
+    /// last 30 days. Functions without invocations are absent.
+    pub async fn function_invocation_counts(&self, region: &str) -> Result<HashMap<String, u64>> {
+        let filter = format!(
+            r#"metric.type = "{}" AND resource.labels.region = "{}""#,
+            FUNCTION_EXECUTIONS_METRIC, region
+        );
+        self.usage_counts(&filter, "function_name").await
+    }
+
+    /// Sums a counter metric over the usage window, one total per value of
+    /// the `name_label` resource label.
+    async fn usage_counts(&self, filter: &str, name_label: &str) -> Result<HashMap<String, u64>> {
+        let group_by = format!("resource.label.{}", name_label);
+        let aggregation = [
+            ("aggregation.alignmentPeriod", USAGE_WINDOW),
+            ("aggregation.perSeriesAligner", "ALIGN_SUM"),
+            ("aggregation.crossSeriesReducer", "REDUCE_SUM"),
+            ("aggregation.groupByFields", group_by.as_str()),
+        ];
+
+        let series = self
+            .list_time_series(filter, Duration::days(USAGE_WINDOW_DAYS), &aggregation)
+            .await?;
+        Ok(sum_counts_by_label(&series, name_label))
+    }
+
+    async fn list_time_series(
+        &self,
+        filter: &str,
+        window: Duration,
+        params: &[(&str, &str)],
+    ) -> Result<Vec<TimeSeries>> {
+        let url = format!("{}/projects/{}/timeSeries", MONITORING_API, self.client.project_id());
+        let end = Utc::now();
+        let start = (end - window).to_rfc3339_opts(SecondsFormat::Secs, true);
+        let end = end.to_rfc3339_opts(SecondsFormat::Secs, true);
+
+        let mut series = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut query = vec![
+                ("filter", filter),
+                ("interval.startTime", start.as_str()),
+                ("interval.endTime", end.as_str()),
+            ];
+            query.extend_from_slice(params);
+            if let Some(ref token) = page_token {
+                query.push(("pageToken", token.as_str()));
+            }
+
+            let page: TimeSeriesList = self.client.get(&url, &query).await?;
+            series.extend(page.time_series);
+
+            match page.next_page_token {
+                Some(token) if !token.is_empty() => page_token = Some(token),
+                _ => break,
+            }
+        }
+
+        Ok(series)
+    }
+}
+
+/// Adds up the newest sample of each storage class series per bucket.
+fn sum_latest_by_bucket(series: &[TimeSeries]) -> HashMap<String, u64> {
+    let mut sizes: HashMap<String, u64> = HashMap::new();
+
+    for entry in series {
+        let bucket = match entry.resource.labels.get("bucket_name") {
+            Some(bucket) => bucket,
+            None => continue,
+        };
+
+        // Points are returned newest first
+        if let Some(bytes) = entry.points.first().and_then(|point| point.value.double_value) {
+            *sizes.entry(bucket.clone()).or_insert(0) += bytes as u64;
+        }
+    }
+
+    sizes
+}
+
+/// Adds up every point of each series per value of `label`. A window that
+/// straddles an alignment boundary yields two points per series.
+fn sum_counts_by_label(series: &[TimeSeries], label: &str) -> HashMap<String, u64> {
+    let mut counts: HashMap<String, u64> = HashMap::new();
+
+    for entry in series {
+        let name = match entry.resource.labels.get(label) {
+            Some(name) => name,
+            None => continue,
+        };
+
+        let total: u64 = entry
+            .points
+            .iter()
+            .filter_map(|point| point.value.int64_value.as_deref())
+            .filter_map(|value| value.parse::<u64>().ok())
+            .sum();
+        *counts.entry(name.clone()).or_insert(0) += total;
+    }
+
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sum_latest_by_bucket() {
+        let page: TimeSeriesList = serde_json::from_value(serde_json::json!({
+            "timeSeries": [
+                {
+                    "resource": { "labels": { "bucket_name": "assets" } },
+                    "points": [{ "value": { "doubleValue": 300.0 } }, { "value": { "doubleValue": 100.0 } }]
+                },
+                {
+                    "resource": { "labels": { "bucket_name": "assets" } },
+                    "points": [{ "value": { "doubleValue": 50.0 } }]
+                },
+                {
+                    "resource": { "labels": { "bucket_name": "logs" } },
+                    "points": []
+                }
+            ]
+        }))
+        .unwrap();
+
+        let sizes = sum_latest_by_bucket(&page.time_series);
+        assert_eq!(sizes.get("assets"), Some(&350));
+        assert!(!sizes.contains_key("logs"));
+    }
+
+    #[test]
+    fn test_sum_counts_by_label() {
+        let page: TimeSeriesList = serde_json::from_value(serde_json::json!({
+            "timeSeries": [
+                {
+                    "resource": { "labels": { "service_name": "api" } },
+                    "points": [{ "value": { "int64Value": "1200" } }, { "value": { "int64Value": "34" } }]
+                },
+                {
+                    "resource": { "labels": { "service_name": "worker" } },
+                    "points": [{ "value": { "int64Value": "7" } }]
+                },
+                {
+                    "resource": { "labels": {} },
+                    "points": [{ "value": { "int64Value": "99" } }]
+                }
+            ]
+        }))
+        .unwrap();
+
+        let counts = sum_counts_by_label(&page.time_series, "service_name");
+        assert_eq!(counts.get("api"), Some(&1234));
+        assert_eq!(counts.get("worker"), Some(&7));
+        assert_eq!(counts.len(), 2);
+    }
+}
@@ -0,0 +1,289 @@
+use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType};
+use super::{estimate_request_cost, parse_cpu, parse_memory_gib};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Cloud Run defaults when a container sets no resource limits.
+const DEFAULT_CPU: f64 = 1.0;
+const DEFAULT_MEMORY_GIB: f64 = 0.5;
+
+/// Cloud Run service as returned by the Cloud Run Admin API v2.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunService {
+    /// Full name, `projects/<project>/locations/<location>/services/<name>`
+    pub name: String,
+    pub uri: Option<String>,
+    #[serde(default)]
+    pub reconciling: bool,
+    pub terminal_condition: Option<RunCondition>,
+    pub latest_ready_revision: Option<String>,
+    pub template: Option<RunRevisionTemplate>,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    pub create_time: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RunCondition {
+    pub state: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RunRevisionTemplate {
+    #[serde(default)]
+    pub containers: Vec<RunContainer>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RunContainer {
+    pub image: Option<String>,
+    pub resources: Option<RunResources>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RunResources {
+    #[serde(default)]
+    pub limits: HashMap<String, String>,
+}
+
+pub struct CloudRunService {
+    /// Relative resource path, `locations/<location>/services/<name>`
+    id: String,
+    name: String,
+    region: String,
+    url: Option<String>,
+    image: Option<String>,
+    revision: Option<String>,
+    reconciling: bool,
+    condition: Option<String>,
+    vcpu: f64,
+    memory_gib: f64,
+    labels: HashMap<String, String>,
+    created_at: Option<DateTime<Utc>>,
+    requests: Option<u64>,
+}
+
+impl CloudRunService {
+    pub fn from_api(service: &RunService) -> Self {
+        let id = service
+            .name
+            .split_once("/locations/")
+            .map(|(_, path)| format!("locations/{}", path))
+            .unwrap_or_else(|| service.name.clone());
+        let name = service.name.rsplit('/').next().unwrap_or(&service.name).to_string();
+        let region = id.split('/').nth(1).unwrap_or_default().to_string();
+
+        let container = service
+            .template
+            .as_ref()
+            .and_then(|template| template.containers.first());
+        let limits = container.and_then(|c| c.resources.as_ref()).map(|r| &r.limits);
+
+        let created_at = service.create_time.as_deref().and_then(|ts| {
+            DateTime::parse_from_rfc3339(ts)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc))
+        });
+
+        Self {
+            id,
+            name,
+            region,
+            url: service.uri.clone(),
+            image: container.and_then(|c| c.image.clone()),
+            revision: service
+                .latest_ready_revision
+                .as_deref()
+                .map(|rev| rev.rsplit('/').next().unwrap_or(rev).to_string()),
+            reconciling: service.reconciling,
+            condition: service.terminal_condition.as_ref().and_then(|c| c.state.clone()),
+            vcpu: limits
+                .and_then(|l| l.get("cpu"))
+                .and_then(|cpu| parse_cpu(cpu))
+                .unwrap_or(DEFAULT_CPU),
+            memory_gib: limits
+                .and_then(|l| l.get("memory"))
+                .and_then(|memory| parse_memory_gib(memory))
+                .unwrap_or(DEFAULT_MEMORY_GIB),
+            labels: service.labels.clone(),
+            created_at,
+            requests: None,
+        }
+    }
+
+    /// Sets the number of requests served over the last 30 days.
+    pub fn with_requests(mut self, requests: u64) -> Self {
+        self.requests = Some(requests);
+        self
+    }
+
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    pub fn image(&self) -> Option<&str> {
+        self.image.as_deref()
+    }
+
+    /// Name of the revision currently serving traffic.
+    pub fn revision(&self) -> Option<&str> {
+        self.revision.as_deref()
+    }
+
+    pub fn requests(&self) -> Option<u64> {
+        self.requests
+    }
+}
+
+impl CloudResource for CloudRunService {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Serverless
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::GCP
+    }
+
+    fn region(&self) -> &str {
+        &self.region
+    }
+
+    fn state(&self) -> ResourceState {
+        if self.reconciling {
+            return ResourceState::Pending;
+        }
+        match self.condition.as_deref() {
+            Some("CONDITION_SUCCEEDED") => ResourceState::Running,
+            Some("CONDITION_PENDING") | Some("CONDITION_RECONCILING") => ResourceState::Pending,
+            Some("CONDITION_FAILED") => ResourceState::Error,
+            _ => ResourceState::Unknown,
+        }
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        self.requests
+            .map(|requests| estimate_request_cost(requests, self.vcpu, self.memory_gib))
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.labels
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        vec![Action::ViewDetails, Action::Terminate]
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = Vec::new();
+        if let Some(ref url) = self.url {
+            details.push(("URL".to_string(), url.clone()));
+        }
+        if let Some(ref image) = self.image {
+            details.push(("Image".to_string(), image.clone()));
+        }
+        if let Some(ref revision) = self.revision {
+            details.push(("Revision".to_string(), revision.clone()));
+        }
+        details.push((
+            "Resources".to_string(),
+            format!("{} vCPU, {} GiB", self.vcpu, self.memory_gib),
+        ));
+        details
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::gcp::resources::fixtures::gcp_resource;
+    use serde_json::json;
+
+    const SERVICE_NAME: &str = "projects/demo/locations/us-central1/services/api";
+
+    #[test]
+    fn test_cloud_run_service_from_api() {
+        let service = CloudRunService::from_api(&gcp_resource(json!({
+            "name": SERVICE_NAME,
+            "uri": "https://api-abc123-uc.a.run.app",
+            "terminalCondition": { "state": "CONDITION_SUCCEEDED" },
+            "latestReadyRevision": "projects/demo/locations/us-central1/services/api/revisions/api-00042-xyz",
+            "template": {
+                "containers": [{
+                    "image": "us-docker.pkg.dev/demo/app/api:1.4",
+                    "resources": { "limits": { "cpu": "2", "memory": "1Gi" } }
+                }]
+            },
+            "createTime": "2024-05-01T08:00:00.123456Z"
+        })));
+
+        assert_eq!(service.id(), "locations/us-central1/services/api");
+        assert_eq!(service.name(), "api");
+        assert_eq!(service.region(), "us-central1");
+        assert_eq!(service.revision(), Some("api-00042-xyz"));
+        assert_eq!(service.image(), Some("us-docker.pkg.dev/demo/app/api:1.4"));
+        assert_eq!(service.state(), ResourceState::Running);
+        assert_eq!(service.resource_type(), ResourceType::Serverless);
+        assert!(service.created_at().is_some());
+
+        assert_eq!(service.cost_per_month(), None);
+        let service = service.with_requests(3_000_000);
+        assert_eq!(
+            service.cost_per_month(),
+            Some(estimate_request_cost(3_000_000, 2.0, 1.0))
+        );
+    }
+
+    #[test]
+    fn test_cloud_run_service_state() {
+        let state = |condition: &str| {
+            CloudRunService::from_api(&gcp_resource(json!({
+                "name": SERVICE_NAME,
+                "terminalCondition": { "state": condition }
+            })))
+            .state()
+        };
+        assert_eq!(state("CONDITION_FAILED"), ResourceState::Error);
+        assert_eq!(state("SOMETHING_NEW"), ResourceState::Unknown);
+
+        let reconciling = CloudRunService::from_api(&gcp_resource(json!({
+            "name": SERVICE_NAME,
+            "reconciling": true,
+            "terminalCondition": { "state": "CONDITION_SUCCEEDED" }
+        })));
+        assert_eq!(reconciling.state(), ResourceState::Pending);
+
+        let no_condition = CloudRunService::from_api(&gcp_resource(json!({ "name": SERVICE_NAME })));
+        assert_eq!(no_condition.state(), ResourceState::Unknown);
+    }
+
+    #[test]
+    fn test_cloud_run_service_without_template() {
+        let service = CloudRunService::from_api(&gcp_resource(json!({ "name": SERVICE_NAME, "labels": null })))
+            .with_requests(1_000_000);
+
+        assert_eq!(service.image(), None);
+        assert!(service.tags().is_empty());
+        assert_eq!(
+            service.cost_per_month(),
+            Some(estimate_request_cost(1_000_000, DEFAULT_CPU, DEFAULT_MEMORY_GIB))
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType, HOURS_PER_MONTH};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Cloud SQL instance as returned by the SQL Admin REST API.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SqlInstance {
+    pub name: String,
+    /// Engine and major version, e.g. `POSTGRES_15` or `MYSQL_8_0`
+    pub database_version: String,
+    pub region: String,
+    pub state: String,
+    pub settings: SqlSettings,
+    pub create_time: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SqlSettings {
+    pub tier: String,
+    /// `ALWAYS` while running, `NEVER` once stopped
+    pub activation_policy: Option<String>,
+    #[serde(default)]
+    pub user_labels: HashMap<String, String>,
+}
+
+pub struct CloudSqlInstance {
+    /// Relative resource path, `instances/<name>`
+    id: String,
+    name: String,
+    database_version: String,
+    tier: String,
+    region: String,
+    state: String,
+    activation_policy: String,
+    labels: HashMap<String, String>,
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl CloudSqlInstance {
+    pub fn from_api(instance: &SqlInstance) -> Self {
+        let created_at = instance.create_time.as_deref().and_then(|ts| {
+            DateTime::parse_from_rfc3339(ts)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc))
+        });
+
+        Self {
+            id: format!("instances/{}", instance.name),
+            name: instance.name.clone(),
+            database_version: instance.database_version.clone(),
+            tier: instance.settings.tier.clone(),
+            region: instance.region.clone(),
+            state: instance.state.clone(),
+            activation_policy: instance
+                .settings
+                .activation_policy
+                .clone()
+                .unwrap_or_else(|| "ALWAYS".to_string()),
+            labels: instance.settings.user_labels.clone(),
+            created_at,
+        }
+    }
+
+    /// Database engine, e.g. `postgres` or `mysql`.
+    pub fn engine(&self) -> String {
+        self.database_version
+            .split('_')
+            .next()
+            .unwrap_or(&self.database_version)
+            .to_lowercase()
+    }
+
+    pub fn database_version(&self) -> &str {
+        &self.database_version
+    }
+
+    pub fn tier(&self) -> &str {
+        &self.tier
+    }
+}
+
+impl CloudResource for CloudSqlInstance {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Database
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::GCP
+    }
+
+    fn region(&self) -> &str {
+        &self.region
+    }
+
+    fn state(&self) -> ResourceState {
+        // A stopped instance stays RUNNABLE with its activation policy set to NEVER
+        match (self.state.as_str(), self.activation_policy.as_str()) {
+            ("RUNNABLE", "NEVER") => ResourceState::Stopped,
+            ("RUNNABLE", _) => ResourceState::Running,
+            ("SUSPENDED", _) => ResourceState::Stopped,
+            ("PENDING_CREATE", _) => ResourceState::Pending,
+            ("MAINTENANCE", _) => ResourceState::Maintenance,
+            ("FAILED", _) => ResourceState::Error,
+            _ => ResourceState::Unknown,
+        }
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        match self.state() {
+            ResourceState::Stopped => Some(0.0),
+            _ => Some(estimate_cloudsql_cost(&self.tier)),
+        }
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.labels
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        match self.state() {
+            ResourceState::Running => vec![Action::Stop, Action::ViewDetails],
+            ResourceState::Stopped => vec![Action::Start, Action::ViewDetails],
+            _ => vec![Action::ViewDetails],
+        }
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        vec![
+            ("Version".to_string(), self.database_version.clone()),
+            ("Tier".to_string(), self.tier.clone()),
+            ("Activation policy".to_string(), self.activation_policy.clone()),
+        ]
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Approximate monthly compute price in us-central1, excluding storage.
+///
+/// Custom tiers are named `db-custom-<vCPUs>-<memory MB>` and billed per
+/// vCPU and per GB of memory.
+fn estimate_cloudsql_cost(tier: &str) -> f64 {
+    const VCPU_HOURLY: f64 = 0.0413;
+    const GB_HOURLY: f64 = 0.007;
+
+    match tier {
+        "db-f1-micro" => 7.67,
+        "db-g1-small" => 25.55,
+        custom => {
+            let mut parts = custom.trim_start_matches("db-custom-").split('-');
+            match (
+                parts.next().and_then(|cpus| cpus.parse::<f64>().ok()),
+                parts.next().and_then(|mb| mb.parse::<f64>().ok()),
+            ) {
+                (Some(cpus), Some(memory_mb)) => {
+                    (cpus * VCPU_HOURLY + memory_mb / 1024.0 * GB_HOURLY) * HOURS_PER_MONTH
+                }
+                _ => 50.0,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::gcp::resources::fixtures::gcp_resource;
+    use serde_json::json;
+
+    fn instance(state: &str, settings: serde_json::Value) -> CloudSqlInstance {
+        CloudSqlInstance::from_api(&gcp_resource(json!({
+            "name": "orders-db",
+            "databaseVersion": "POSTGRES_15",
+            "region": "us-central1",
+            "state": state,
+            "settings": settings
+        })))
+    }
+
+    #[test]
+    fn test_cloudsql_instance_from_api() {
+        let instance = CloudSqlInstance::from_api(&gcp_resource(json!({
+            "name": "orders-db",
+            "databaseVersion": "POSTGRES_15",
+            "region": "us-central1",
+            "state": "RUNNABLE",
+            "createTime": "2024-03-01T09:30:00.000Z",
+            "settings": {
+                "tier": "db-custom-2-7680",
+                "activationPolicy": "ALWAYS",
+                "userLabels": { "team": "payments" }
+            }
+        })));
+
+        assert_eq!(instance.id(), "instances/orders-db");
+        assert_eq!(instance.engine(), "postgres");
+        assert_eq!(instance.database_version(), "POSTGRES_15");
+        assert_eq!(instance.tier(), "db-custom-2-7680");
+        assert_eq!(instance.resource_type(), ResourceType::Database);
+        assert_eq!(instance.provider(), Provider::GCP);
+        // Cloud SQL keeps labels under its settings, not at the top level
+        assert_eq!(instance.tags().get("team"), Some(&"payments".to_string()));
+        assert!(instance.created_at().is_some());
+    }
+
+    #[test]
+    fn test_cloudsql_state_and_actions() {
+        let running = instance("RUNNABLE", json!({ "tier": "db-f1-micro", "activationPolicy": "ALWAYS" }));
+        assert_eq!(running.state(), ResourceState::Running);
+        assert!(running.supported_actions().contains(&Action::Stop));
+
+        let stopped = instance("RUNNABLE", json!({ "tier": "db-f1-micro", "activationPolicy": "NEVER" }));
+        assert_eq!(stopped.state(), ResourceState::Stopped);
+        assert!(stopped.supported_actions().contains(&Action::Start));
+        assert_eq!(stopped.cost_per_month(), Some(0.0));
+
+        let unknown = instance("SOMETHING_NEW", json!({ "tier": "db-f1-micro" }));
+        assert_eq!(unknown.state(), ResourceState::Unknown);
+    }
+
+    #[test]
+    fn test_cloudsql_without_activation_policy_or_labels() {
+        let instance = instance("RUNNABLE", json!({ "tier": "db-f1-micro" }));
+        assert_eq!(instance.state(), ResourceState::Running);
+        assert!(instance.tags().is_empty());
+        assert!(instance.created_at().is_none());
+    }
+
+    #[test]
+    fn test_estimate_cloudsql_cost() {
+        assert_eq!(estimate_cloudsql_cost("db-f1-micro"), 7.67);
+        let expected = (2.0 * 0.0413 + 7.5 * 0.007) * HOURS_PER_MONTH;
+        assert!((estimate_cloudsql_cost("db-custom-2-7680") - expected).abs() < 1e-9);
+        assert_eq!(estimate_cloudsql_cost("db-unknown"), 50.0);
+    }
+}
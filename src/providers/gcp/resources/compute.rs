@@ -0,0 +1,290 @@
+use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Compute Engine instance as returned by the `instances` REST API.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComputeInstance {
+    pub name: String,
+    /// URL of the zone, ending in `zones/<zone>`
+    #[serde(default)]
+    pub zone: String,
+    /// URL of the machine type, ending in `machineTypes/<type>`
+    pub machine_type: String,
+    pub status: String,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    pub creation_timestamp: Option<String>,
+    #[serde(default)]
+    pub network_interfaces: Vec<ComputeNetworkInterface>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComputeNetworkInterface {
+    #[serde(rename = "networkIP")]
+    pub network_ip: Option<String>,
+    #[serde(default)]
+    pub access_configs: Vec<ComputeAccessConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComputeAccessConfig {
+    #[serde(rename = "natIP")]
+    pub nat_ip: Option<String>,
+}
+
+pub struct GCEInstance {
+    /// Relative resource path, `zones/<zone>/instances/<name>`
+    id: String,
+    name: String,
+    zone: String,
+    region: String,
+    machine_type: String,
+    status: String,
+    labels: HashMap<String, String>,
+    created_at: Option<DateTime<Utc>>,
+    internal_ip: Option<String>,
+    external_ip: Option<String>,
+}
+
+impl GCEInstance {
+    pub fn from_api(instance: &ComputeInstance) -> Self {
+        let zone = last_segment(&instance.zone).to_string();
+        let region = region_of_zone(&zone).to_string();
+
+        let created_at = instance.creation_timestamp.as_deref().and_then(|ts| {
+            DateTime::parse_from_rfc3339(ts)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc))
+        });
+
+        let primary_interface = instance.network_interfaces.first();
+
+        Self {
+            id: format!("zones/{}/instances/{}", zone, instance.name),
+            name: instance.name.clone(),
+            zone,
+            region,
+            machine_type: last_segment(&instance.machine_type).to_string(),
+            status: instance.status.clone(),
+            labels: instance.labels.clone(),
+            created_at,
+            internal_ip: primary_interface.and_then(|nic| nic.network_ip.clone()),
+            external_ip: primary_interface
+                .and_then(|nic| nic.access_configs.iter().find_map(|config| config.nat_ip.clone())),
+        }
+    }
+
+    pub fn zone(&self) -> &str {
+        &self.zone
+    }
+
+    pub fn machine_type(&self) -> &str {
+        &self.machine_type
+    }
+
+    pub fn internal_ip(&self) -> Option<&str> {
+        self.internal_ip.as_deref()
+    }
+
+    pub fn external_ip(&self) -> Option<&str> {
+        self.external_ip.as_deref()
+    }
+}
+
+impl CloudResource for GCEInstance {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Compute
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::GCP
+    }
+
+    fn region(&self) -> &str {
+        &self.region
+    }
+
+    fn state(&self) -> ResourceState {
+        // TERMINATED is Compute Engine's name for a stopped instance
+        match self.status.as_str() {
+            "RUNNING" => ResourceState::Running,
+            "TERMINATED" | "STOPPED" | "SUSPENDED" => ResourceState::Stopped,
+            "PROVISIONING" | "STAGING" => ResourceState::Pending,
+            "STOPPING" | "SUSPENDING" => ResourceState::Stopping,
+            "REPAIRING" => ResourceState::Maintenance,
+            _ => ResourceState::Unknown,
+        }
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        match self.state() {
+            ResourceState::Stopped => Some(0.0),
+            _ => Some(estimate_gce_cost(&self.machine_type)),
+        }
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.labels
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        match self.state() {
+            ResourceState::Running => vec![
+                Action::Stop,
+                Action::Restart,
+                Action::Terminate,
+                Action::ViewDetails,
+            ],
+            ResourceState::Stopped => vec![Action::Start, Action::Terminate, Action::ViewDetails],
+            _ => vec![Action::ViewDetails],
+        }
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = vec![
+            ("Machine type".to_string(), self.machine_type.clone()),
+            ("Zone".to_string(), self.zone.clone()),
+        ];
+        if let Some(ref ip) = self.internal_ip {
+            details.push(("Internal IP".to_string(), ip.clone()));
+        }
+        if let Some(ref ip) = self.external_ip {
+            details.push(("External IP".to_string(), ip.clone()));
+        }
+        details
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Returns the final path segment of a Compute Engine resource URL.
+fn last_segment(url: &str) -> &str {
+    url.rsplit('/').next().unwrap_or(url)
+}
+
+/// Strips the zone letter, e.g. `us-central1-a` becomes `us-central1`.
+pub fn region_of_zone(zone: &str) -> &str {
+    match zone.rsplit_once('-') {
+        Some((region, _)) => region,
+        None => zone,
+    }
+}
+
+/// Approximate on-demand monthly price in us-central1.
+fn estimate_gce_cost(machine_type: &str) -> f64 {
+    match machine_type {
+        "e2-micro" => 6.11,
+        "e2-small" => 12.23,
+        "e2-medium" => 24.46,
+        "e2-standard-2" => 48.92,
+        "e2-standard-4" => 97.83,
+        "n1-standard-1" => 24.27,
+        "n1-standard-2" => 48.55,
+        "n1-standard-4" => 97.09,
+        "n2-standard-2" => 70.90,
+        "n2-standard-4" => 141.79,
+        _ => 50.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::gcp::resources::fixtures::gcp_resource;
+    use serde_json::json;
+
+    fn instance_with_status(status: &str) -> GCEInstance {
+        GCEInstance::from_api(&gcp_resource(json!({
+            "zone": "zones/us-central1-a",
+            "machineType": "machineTypes/e2-medium",
+            "status": status
+        })))
+    }
+
+    #[test]
+    fn test_gce_instance_from_api() {
+        let instance = GCEInstance::from_api(&gcp_resource(json!({
+            "zone": "https://www.googleapis.com/compute/v1/projects/demo/zones/us-central1-a",
+            "machineType": "https://www.googleapis.com/compute/v1/projects/demo/zones/us-central1-a/machineTypes/e2-medium",
+            "status": "RUNNING",
+            "creationTimestamp": "2024-03-01T09:30:00.000-08:00",
+            "networkInterfaces": [{
+                "networkIP": "10.128.0.2",
+                "accessConfigs": [{ "natIP": "34.1.2.3" }]
+            }]
+        })));
+
+        assert_eq!(instance.id(), "zones/us-central1-a/instances/web-1");
+        assert_eq!(instance.region(), "us-central1");
+        assert_eq!(instance.machine_type(), "e2-medium");
+        assert_eq!(instance.internal_ip(), Some("10.128.0.2"));
+        assert_eq!(instance.external_ip(), Some("34.1.2.3"));
+        assert_eq!(instance.tags().get("team"), Some(&"platform".to_string()));
+        assert!(instance.created_at().is_some());
+        assert_eq!(instance.cost_per_month(), Some(24.46));
+    }
+
+    #[test]
+    fn test_gce_instance_with_missing_fields() {
+        let instance = GCEInstance::from_api(&gcp_resource(json!({
+            "machineType": "machineTypes/e2-medium",
+            "status": "RUNNING",
+            "labels": null
+        })));
+
+        assert_eq!(instance.zone(), "");
+        assert_eq!(instance.region(), "");
+        assert!(instance.tags().is_empty());
+        assert_eq!(instance.internal_ip(), None);
+        assert_eq!(instance.external_ip(), None);
+        assert!(instance.created_at().is_none());
+    }
+
+    #[test]
+    fn test_gce_instance_state() {
+        assert_eq!(instance_with_status("RUNNING").state(), ResourceState::Running);
+        assert_eq!(instance_with_status("TERMINATED").state(), ResourceState::Stopped);
+        assert_eq!(instance_with_status("STAGING").state(), ResourceState::Pending);
+        assert_eq!(instance_with_status("STOPPING").state(), ResourceState::Stopping);
+        assert_eq!(instance_with_status("SOMETHING_NEW").state(), ResourceState::Unknown);
+        assert_eq!(instance_with_status("TERMINATED").cost_per_month(), Some(0.0));
+    }
+
+    #[test]
+    fn test_gce_supported_actions() {
+        let running = instance_with_status("RUNNING").supported_actions();
+        assert!(running.contains(&Action::Stop));
+        assert!(running.contains(&Action::Restart));
+        assert!(!running.contains(&Action::Start));
+
+        let stopped = instance_with_status("TERMINATED").supported_actions();
+        assert!(stopped.contains(&Action::Start));
+        assert!(stopped.contains(&Action::Terminate));
+        assert!(!stopped.contains(&Action::Stop));
+    }
+
+    #[test]
+    fn test_region_of_zone() {
+        assert_eq!(region_of_zone("us-central1-a"), "us-central1");
+        assert_eq!(region_of_zone("europe-west4-b"), "europe-west4");
+    }
+}
@@ -0,0 +1,190 @@
+use crate::core::{Action, CloudResource, DnsRecord, Provider, ResourceState, ResourceType};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Monthly price of a managed zone within the first 25 zones of a project.
+const ZONE_MONTHLY: f64 = 0.20;
+
+/// Cloud DNS managed zone as returned by the REST API.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagedZone {
+    pub name: String,
+    pub dns_name: String,
+    /// `public` or `private`
+    pub visibility: Option<String>,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    pub creation_time: Option<String>,
+}
+
+/// Resource record set as returned by the Cloud DNS REST API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResourceRecordSet {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub record_type: String,
+    pub ttl: Option<i64>,
+    #[serde(default)]
+    pub rrdatas: Vec<String>,
+}
+
+impl From<ResourceRecordSet> for DnsRecord {
+    fn from(record_set: ResourceRecordSet) -> Self {
+        DnsRecord {
+            name: record_set.name,
+            record_type: record_set.record_type,
+            ttl: record_set.ttl,
+            values: record_set.rrdatas,
+        }
+    }
+}
+
+pub struct CloudDnsZone {
+    /// Relative resource path, `managedZones/<name>`
+    id: String,
+    name: String,
+    dns_name: String,
+    is_private: bool,
+    record_count: Option<i64>,
+    labels: HashMap<String, String>,
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl CloudDnsZone {
+    pub fn from_api(zone: &ManagedZone) -> Self {
+        let created_at = zone.creation_time.as_deref().and_then(|ts| {
+            DateTime::parse_from_rfc3339(ts)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc))
+        });
+
+        Self {
+            id: format!("managedZones/{}", zone.name),
+            name: zone.name.clone(),
+            dns_name: zone.dns_name.clone(),
+            is_private: zone.visibility.as_deref() == Some("private"),
+            record_count: None,
+            labels: zone.labels.clone(),
+            created_at,
+        }
+    }
+
+    pub fn with_record_count(mut self, record_count: Option<i64>) -> Self {
+        self.record_count = record_count;
+        self
+    }
+
+    /// The domain the zone serves, e.g. `example.com.`
+    pub fn dns_name(&self) -> &str {
+        &self.dns_name
+    }
+
+    pub fn is_private(&self) -> bool {
+        self.is_private
+    }
+
+    pub fn record_count(&self) -> Option<i64> {
+        self.record_count
+    }
+}
+
+impl CloudResource for CloudDnsZone {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::DNS
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::GCP
+    }
+
+    fn region(&self) -> &str {
+        "global"
+    }
+
+    fn state(&self) -> ResourceState {
+        ResourceState::Running
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        // Queries are billed separately and depend on traffic
+        Some(ZONE_MONTHLY)
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.labels
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        vec![Action::ViewDetails, Action::ViewRecords, Action::Terminate]
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = vec![
+            ("DNS name".to_string(), self.dns_name.clone()),
+            ("Visibility".to_string(), if self.is_private { "Private" } else { "Public" }.to_string()),
+        ];
+        if let Some(count) = self.record_count {
+            details.push(("Records".to_string(), count.to_string()));
+        }
+        details
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cloud_dns_zone_from_api() {
+        let zone: ManagedZone = serde_json::from_value(serde_json::json!({
+            "name": "example-com",
+            "dnsName": "example.com.",
+            "visibility": "private",
+            "creationTime": "2024-03-01T09:30:00.000Z"
+        }))
+        .unwrap();
+
+        let zone = CloudDnsZone::from_api(&zone).with_record_count(Some(12));
+        assert_eq!(zone.id(), "managedZones/example-com");
+        assert_eq!(zone.dns_name(), "example.com.");
+        assert!(zone.is_private());
+        assert_eq!(zone.record_count(), Some(12));
+        assert_eq!(zone.resource_type(), ResourceType::DNS);
+        assert_eq!(zone.cost_per_month(), Some(ZONE_MONTHLY));
+        assert!(zone.supported_actions().contains(&Action::ViewRecords));
+    }
+
+    #[test]
+    fn test_record_set_into_dns_record() {
+        let record_set: ResourceRecordSet = serde_json::from_value(serde_json::json!({
+            "name": "www.example.com.",
+            "type": "A",
+            "ttl": 300,
+            "rrdatas": ["192.0.2.1"]
+        }))
+        .unwrap();
+
+        let record = DnsRecord::from(record_set);
+        assert_eq!(record.record_type, "A");
+        assert_eq!(record.ttl, Some(300));
+        assert_eq!(record.values, vec!["192.0.2.1".to_string()]);
+    }
+}
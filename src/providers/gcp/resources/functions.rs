@@ -0,0 +1,262 @@
+use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType};
+use super::{estimate_request_cost, parse_cpu, parse_memory_gib};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Defaults for a function deployed without explicit sizing: 256 MB with
+/// the matching sixth of a vCPU.
+const DEFAULT_CPU: f64 = 0.1666;
+const DEFAULT_MEMORY: &str = "256M";
+
+/// Cloud Function as returned by the Cloud Functions API v2, which lists
+/// both 1st and 2nd gen functions.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionInfo {
+    /// Full name, `projects/<project>/locations/<location>/functions/<name>`
+    pub name: String,
+    pub state: Option<String>,
+    /// `GEN_1` or `GEN_2`
+    pub environment: Option<String>,
+    pub build_config: Option<FunctionBuildConfig>,
+    pub service_config: Option<FunctionServiceConfig>,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    pub update_time: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionBuildConfig {
+    pub runtime: Option<String>,
+    pub entry_point: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionServiceConfig {
+    pub uri: Option<String>,
+    pub available_memory: Option<String>,
+    pub available_cpu: Option<String>,
+    pub timeout_seconds: Option<i64>,
+}
+
+pub struct CloudFunction {
+    /// Relative resource path, `locations/<location>/functions/<name>`
+    id: String,
+    name: String,
+    region: String,
+    state: String,
+    generation: String,
+    runtime: Option<String>,
+    entry_point: Option<String>,
+    url: Option<String>,
+    memory: String,
+    vcpu: f64,
+    labels: HashMap<String, String>,
+    updated_at: Option<DateTime<Utc>>,
+    invocations: Option<u64>,
+}
+
+impl CloudFunction {
+    pub fn from_api(function: &FunctionInfo) -> Self {
+        let id = function
+            .name
+            .split_once("/locations/")
+            .map(|(_, path)| format!("locations/{}", path))
+            .unwrap_or_else(|| function.name.clone());
+        let name = function.name.rsplit('/').next().unwrap_or(&function.name).to_string();
+        let region = id.split('/').nth(1).unwrap_or_default().to_string();
+
+        let build = function.build_config.as_ref();
+        let service = function.service_config.as_ref();
+
+        // The API only reports when a function was last deployed
+        let updated_at = function.update_time.as_deref().and_then(|ts| {
+            DateTime::parse_from_rfc3339(ts)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc))
+        });
+
+        Self {
+            id,
+            name,
+            region,
+            state: function.state.clone().unwrap_or_default(),
+            generation: match function.environment.as_deref() {
+                Some("GEN_2") => "2nd gen".to_string(),
+                _ => "1st gen".to_string(),
+            },
+            runtime: build.and_then(|b| b.runtime.clone()),
+            entry_point: build.and_then(|b| b.entry_point.clone()),
+            url: service.and_then(|s| s.uri.clone()),
+            memory: service
+                .and_then(|s| s.available_memory.clone())
+                .unwrap_or_else(|| DEFAULT_MEMORY.to_string()),
+            vcpu: service
+                .and_then(|s| s.available_cpu.as_deref())
+                .and_then(parse_cpu)
+                .unwrap_or(DEFAULT_CPU),
+            labels: function.labels.clone(),
+            updated_at,
+            invocations: None,
+        }
+    }
+
+    /// Sets the number of invocations over the last 30 days.
+    pub fn with_invocations(mut self, invocations: u64) -> Self {
+        self.invocations = Some(invocations);
+        self
+    }
+
+    /// `1st gen` or `2nd gen`.
+    pub fn generation(&self) -> &str {
+        &self.generation
+    }
+
+    /// Language runtime, e.g. `python312`.
+    pub fn runtime(&self) -> Option<&str> {
+        self.runtime.as_deref()
+    }
+
+    pub fn entry_point(&self) -> Option<&str> {
+        self.entry_point.as_deref()
+    }
+
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    /// Memory allocated to each instance, e.g. `256M`.
+    pub fn memory(&self) -> &str {
+        &self.memory
+    }
+
+    pub fn invocations(&self) -> Option<u64> {
+        self.invocations
+    }
+}
+
+impl CloudResource for CloudFunction {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Serverless
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::GCP
+    }
+
+    fn region(&self) -> &str {
+        &self.region
+    }
+
+    fn state(&self) -> ResourceState {
+        match self.state.as_str() {
+            "ACTIVE" => ResourceState::Running,
+            "DEPLOYING" => ResourceState::Pending,
+            "DELETING" => ResourceState::Deleting,
+            "FAILED" => ResourceState::Error,
+            _ => ResourceState::Unknown,
+        }
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        let memory_gib = parse_memory_gib(&self.memory)?;
+        self.invocations
+            .map(|invocations| estimate_request_cost(invocations, self.vcpu, memory_gib))
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.labels
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.updated_at
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        match self.state() {
+            ResourceState::Pending | ResourceState::Deleting => vec![Action::ViewDetails],
+            _ => vec![Action::ViewDetails, Action::Terminate],
+        }
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = vec![("Generation".to_string(), self.generation.clone())];
+        if let Some(ref runtime) = self.runtime {
+            details.push(("Runtime".to_string(), runtime.clone()));
+        }
+        if let Some(ref entry_point) = self.entry_point {
+            details.push(("Entry point".to_string(), entry_point.clone()));
+        }
+        if let Some(ref url) = self.url {
+            details.push(("URL".to_string(), url.clone()));
+        }
+        details.push(("Memory".to_string(), self.memory.clone()));
+        details
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cloud_function_from_api() {
+        let info: FunctionInfo = serde_json::from_value(serde_json::json!({
+            "name": "projects/demo/locations/europe-west1/functions/resize-image",
+            "state": "ACTIVE",
+            "environment": "GEN_2",
+            "buildConfig": { "runtime": "python312", "entryPoint": "handler" },
+            "serviceConfig": {
+                "uri": "https://resize-image-abc123-ew.a.run.app",
+                "availableMemory": "512Mi",
+                "availableCpu": "0.3333"
+            }
+        }))
+        .unwrap();
+
+        let function = CloudFunction::from_api(&info);
+        assert_eq!(function.id(), "locations/europe-west1/functions/resize-image");
+        assert_eq!(function.name(), "resize-image");
+        assert_eq!(function.region(), "europe-west1");
+        assert_eq!(function.generation(), "2nd gen");
+        assert_eq!(function.runtime(), Some("python312"));
+        assert_eq!(function.state(), ResourceState::Running);
+        assert_eq!(function.cost_per_month(), None);
+
+        let function = function.with_invocations(500_000);
+        assert_eq!(
+            function.cost_per_month(),
+            Some(estimate_request_cost(500_000, 0.3333, 0.5))
+        );
+    }
+
+    #[test]
+    fn test_cloud_function_defaults() {
+        let info: FunctionInfo = serde_json::from_value(serde_json::json!({
+            "name": "projects/demo/locations/us-central1/functions/nightly",
+            "state": "DEPLOYING"
+        }))
+        .unwrap();
+
+        let function = CloudFunction::from_api(&info);
+        assert_eq!(function.generation(), "1st gen");
+        assert_eq!(function.memory(), "256M");
+        assert_eq!(function.state(), ResourceState::Pending);
+        assert_eq!(function.supported_actions(), vec![Action::ViewDetails]);
+    }
+}
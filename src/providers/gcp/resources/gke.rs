@@ -0,0 +1,255 @@
+use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType, HOURS_PER_MONTH};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Hourly cluster management fee. Nodes are billed as Compute Engine
+/// instances and already show up in the instance list.
+const CLUSTER_MANAGEMENT_HOURLY: f64 = 0.10;
+
+/// GKE cluster as returned by the Kubernetes Engine REST API.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GkeCluster {
+    pub name: String,
+    /// Region for regional clusters, zone for zonal ones
+    pub location: String,
+    pub status: String,
+    pub current_master_version: Option<String>,
+    pub current_node_version: Option<String>,
+    pub current_node_count: Option<i64>,
+    #[serde(default)]
+    pub node_pools: Vec<GkeNodePool>,
+    pub autopilot: Option<GkeAutopilot>,
+    #[serde(default)]
+    pub resource_labels: HashMap<String, String>,
+    pub create_time: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GkeNodePool {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GkeAutopilot {
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+pub struct GKECluster {
+    /// Relative resource path, `locations/<location>/clusters/<name>`
+    id: String,
+    name: String,
+    location: String,
+    region: String,
+    status: String,
+    master_version: Option<String>,
+    node_version: Option<String>,
+    node_count: i64,
+    node_pools: Vec<String>,
+    autopilot: bool,
+    labels: HashMap<String, String>,
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl GKECluster {
+    pub fn from_api(cluster: &GkeCluster) -> Self {
+        let created_at = cluster.create_time.as_deref().and_then(|ts| {
+            DateTime::parse_from_rfc3339(ts)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc))
+        });
+
+        Self {
+            id: format!("locations/{}/clusters/{}", cluster.location, cluster.name),
+            name: cluster.name.clone(),
+            location: cluster.location.clone(),
+            region: region_of_location(&cluster.location).to_string(),
+            status: cluster.status.clone(),
+            master_version: cluster.current_master_version.clone(),
+            node_version: cluster.current_node_version.clone(),
+            node_count: cluster.current_node_count.unwrap_or(0),
+            node_pools: cluster.node_pools.iter().map(|pool| pool.name.clone()).collect(),
+            autopilot: cluster.autopilot.as_ref().is_some_and(|a| a.enabled),
+            labels: cluster.resource_labels.clone(),
+            created_at,
+        }
+    }
+
+    /// Region or zone the cluster runs in.
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    /// Kubernetes version of the control plane.
+    pub fn master_version(&self) -> Option<&str> {
+        self.master_version.as_deref()
+    }
+
+    /// Kubernetes version of the nodes. Reports the oldest version while
+    /// node pools are mid-upgrade.
+    pub fn node_version(&self) -> Option<&str> {
+        self.node_version.as_deref()
+    }
+
+    pub fn node_count(&self) -> i64 {
+        self.node_count
+    }
+
+    pub fn node_pool_count(&self) -> usize {
+        self.node_pools.len()
+    }
+
+    pub fn node_pools(&self) -> &[String] {
+        &self.node_pools
+    }
+
+    /// Whether Google manages the nodes (Autopilot) rather than the user.
+    pub fn is_autopilot(&self) -> bool {
+        self.autopilot
+    }
+}
+
+impl CloudResource for GKECluster {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Container
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::GCP
+    }
+
+    fn region(&self) -> &str {
+        &self.region
+    }
+
+    fn state(&self) -> ResourceState {
+        match self.status.as_str() {
+            "RUNNING" => ResourceState::Running,
+            "PROVISIONING" | "RECONCILING" => ResourceState::Pending,
+            "STOPPING" => ResourceState::Deleting,
+            "DEGRADED" => ResourceState::Degraded,
+            "ERROR" => ResourceState::Error,
+            _ => ResourceState::Unknown,
+        }
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        Some(CLUSTER_MANAGEMENT_HOURLY * HOURS_PER_MONTH)
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.labels
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        match self.state() {
+            ResourceState::Running | ResourceState::Degraded | ResourceState::Error => {
+                vec![Action::ViewDetails, Action::Terminate]
+            }
+            _ => vec![Action::ViewDetails],
+        }
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = vec![
+            ("Location".to_string(), self.location.clone()),
+            ("Mode".to_string(), if self.autopilot { "Autopilot" } else { "Standard" }.to_string()),
+        ];
+        if let Some(ref version) = self.master_version {
+            details.push(("Control plane".to_string(), version.clone()));
+        }
+        details.push(("Nodes".to_string(), self.node_count.to_string()));
+        details
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Returns the region of a GKE location. Zonal clusters live in a zone such
+/// as `us-central1-a`; regional clusters already name a region.
+pub fn region_of_location(location: &str) -> &str {
+    match location.matches('-').count() {
+        0 | 1 => location,
+        _ => location.rsplit_once('-').map_or(location, |(region, _)| region),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gke_cluster_from_api() {
+        let cluster: GkeCluster = serde_json::from_value(serde_json::json!({
+            "name": "prod",
+            "location": "us-central1-a",
+            "status": "RUNNING",
+            "currentMasterVersion": "1.29.4-gke.1043002",
+            "currentNodeVersion": "1.28.9-gke.1000000",
+            "currentNodeCount": 6,
+            "nodePools": [
+                { "name": "default-pool", "version": "1.28.9-gke.1000000" },
+                { "name": "highmem", "version": "1.29.4-gke.1043002" }
+            ],
+            "resourceLabels": { "env": "prod" },
+            "createTime": "2024-03-01T12:00:00+00:00"
+        }))
+        .unwrap();
+
+        let gke = GKECluster::from_api(&cluster);
+        assert_eq!(gke.id(), "locations/us-central1-a/clusters/prod");
+        assert_eq!(gke.location(), "us-central1-a");
+        assert_eq!(gke.region(), "us-central1");
+        assert_eq!(gke.resource_type(), ResourceType::Container);
+        assert_eq!(gke.state(), ResourceState::Running);
+        assert_eq!(gke.master_version(), Some("1.29.4-gke.1043002"));
+        assert_eq!(gke.node_version(), Some("1.28.9-gke.1000000"));
+        assert_eq!(gke.node_count(), 6);
+        assert_eq!(gke.node_pool_count(), 2);
+        assert!(!gke.is_autopilot());
+        assert!(gke.supported_actions().contains(&Action::Terminate));
+    }
+
+    #[test]
+    fn test_autopilot_cluster_while_provisioning() {
+        let cluster: GkeCluster = serde_json::from_value(serde_json::json!({
+            "name": "auto",
+            "location": "europe-west1",
+            "status": "PROVISIONING",
+            "autopilot": { "enabled": true }
+        }))
+        .unwrap();
+
+        let gke = GKECluster::from_api(&cluster);
+        assert_eq!(gke.region(), "europe-west1");
+        assert_eq!(gke.state(), ResourceState::Pending);
+        assert_eq!(gke.node_count(), 0);
+        assert!(gke.is_autopilot());
+        assert_eq!(gke.supported_actions(), vec![Action::ViewDetails]);
+    }
+
+    #[test]
+    fn test_region_of_location() {
+        assert_eq!(region_of_location("us-central1"), "us-central1");
+        assert_eq!(region_of_location("us-central1-f"), "us-central1");
+        assert_eq!(region_of_location("northamerica-northeast1-b"), "northamerica-northeast1");
+    }
+}
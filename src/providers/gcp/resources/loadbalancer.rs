@@ -0,0 +1,222 @@
+use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType, HOURS_PER_MONTH};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Hourly price of a forwarding rule, the entry point of every GCP load balancer.
+const FORWARDING_RULE_HOURLY: f64 = 0.025;
+
+/// Forwarding rule as returned by the Compute Engine REST API.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForwardingRule {
+    pub name: String,
+    #[serde(rename = "IPAddress")]
+    pub ip_address: Option<String>,
+    #[serde(rename = "IPProtocol")]
+    pub ip_protocol: Option<String>,
+    pub port_range: Option<String>,
+    /// URL of the target proxy or pool, for proxy and pool based balancers
+    pub target: Option<String>,
+    /// URL of the backend service, for passthrough balancers
+    pub backend_service: Option<String>,
+    pub load_balancing_scheme: Option<String>,
+    /// URL of the region; absent for global rules
+    pub region: Option<String>,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    pub creation_timestamp: Option<String>,
+}
+
+pub struct GCPLoadBalancer {
+    /// Relative resource path, `regions/<region>/forwardingRules/<name>`
+    /// or `global/forwardingRules/<name>`
+    id: String,
+    name: String,
+    region: String,
+    ip_address: Option<String>,
+    protocol: Option<String>,
+    port_range: Option<String>,
+    scheme: String,
+    backend: Option<String>,
+    labels: HashMap<String, String>,
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl GCPLoadBalancer {
+    pub fn from_api(rule: &ForwardingRule) -> Self {
+        let region = rule
+            .region
+            .as_deref()
+            .map(last_segment)
+            .unwrap_or("global")
+            .to_string();
+
+        let id = if region == "global" {
+            format!("global/forwardingRules/{}", rule.name)
+        } else {
+            format!("regions/{}/forwardingRules/{}", region, rule.name)
+        };
+
+        let created_at = rule.creation_timestamp.as_deref().and_then(|ts| {
+            DateTime::parse_from_rfc3339(ts)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc))
+        });
+
+        Self {
+            id,
+            name: rule.name.clone(),
+            region,
+            ip_address: rule.ip_address.clone(),
+            protocol: rule.ip_protocol.clone(),
+            port_range: rule.port_range.clone(),
+            scheme: rule
+                .load_balancing_scheme
+                .clone()
+                .unwrap_or_else(|| "EXTERNAL".to_string()),
+            backend: rule
+                .backend_service
+                .as_deref()
+                .or(rule.target.as_deref())
+                .map(|url| last_segment(url).to_string()),
+            labels: rule.labels.clone(),
+            created_at,
+        }
+    }
+
+    pub fn ip_address(&self) -> Option<&str> {
+        self.ip_address.as_deref()
+    }
+
+    pub fn protocol(&self) -> Option<&str> {
+        self.protocol.as_deref()
+    }
+
+    pub fn port_range(&self) -> Option<&str> {
+        self.port_range.as_deref()
+    }
+
+    /// Whether the balancer is internal or external, e.g. `EXTERNAL_MANAGED`.
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    /// Name of the backend service or target proxy the rule forwards to.
+    pub fn backend(&self) -> Option<&str> {
+        self.backend.as_deref()
+    }
+}
+
+impl CloudResource for GCPLoadBalancer {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::LoadBalancer
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::GCP
+    }
+
+    fn region(&self) -> &str {
+        &self.region
+    }
+
+    fn state(&self) -> ResourceState {
+        ResourceState::Running
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        // Data processing charges depend on traffic, so only the rule is counted
+        Some(FORWARDING_RULE_HOURLY * HOURS_PER_MONTH)
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.labels
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        vec![Action::ViewDetails, Action::Terminate]
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = vec![("Scheme".to_string(), self.scheme.clone())];
+        if let Some(ref ip) = self.ip_address {
+            details.push(("IP address".to_string(), ip.clone()));
+        }
+        if let Some(ref protocol) = self.protocol {
+            details.push(("Protocol".to_string(), protocol.clone()));
+        }
+        if let Some(ref ports) = self.port_range {
+            details.push(("Ports".to_string(), ports.clone()));
+        }
+        if let Some(ref backend) = self.backend {
+            details.push(("Backend".to_string(), backend.clone()));
+        }
+        details
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+fn last_segment(url: &str) -> &str {
+    url.rsplit('/').next().unwrap_or(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regional_forwarding_rule() {
+        let rule: ForwardingRule = serde_json::from_value(serde_json::json!({
+            "name": "api-lb",
+            "IPAddress": "34.1.2.3",
+            "IPProtocol": "TCP",
+            "portRange": "443-443",
+            "backendService": "projects/demo/regions/us-central1/backendServices/api-backend",
+            "loadBalancingScheme": "EXTERNAL",
+            "region": "https://www.googleapis.com/compute/v1/projects/demo/regions/us-central1"
+        }))
+        .unwrap();
+
+        let lb = GCPLoadBalancer::from_api(&rule);
+        assert_eq!(lb.id(), "regions/us-central1/forwardingRules/api-lb");
+        assert_eq!(lb.region(), "us-central1");
+        assert_eq!(lb.ip_address(), Some("34.1.2.3"));
+        assert_eq!(lb.protocol(), Some("TCP"));
+        assert_eq!(lb.port_range(), Some("443-443"));
+        assert_eq!(lb.backend(), Some("api-backend"));
+        assert_eq!(lb.resource_type(), ResourceType::LoadBalancer);
+        assert_eq!(lb.cost_per_month(), Some(FORWARDING_RULE_HOURLY * HOURS_PER_MONTH));
+    }
+
+    #[test]
+    fn test_global_forwarding_rule() {
+        let rule: ForwardingRule = serde_json::from_value(serde_json::json!({
+            "name": "web-https",
+            "target": "projects/demo/global/targetHttpsProxies/web-proxy",
+            "loadBalancingScheme": "EXTERNAL_MANAGED"
+        }))
+        .unwrap();
+
+        let lb = GCPLoadBalancer::from_api(&rule);
+        assert_eq!(lb.id(), "global/forwardingRules/web-https");
+        assert_eq!(lb.region(), "global");
+        assert_eq!(lb.scheme(), "EXTERNAL_MANAGED");
+        assert_eq!(lb.backend(), Some("web-proxy"));
+    }
+}
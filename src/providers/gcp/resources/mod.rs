@@ -0,0 +1,107 @@
+pub mod cloudrun;
+pub mod cloudsql;
+pub mod compute;
+pub mod dns;
+pub mod functions;
+pub mod gke;
+pub mod loadbalancer;
+pub mod storage;
+
+pub use cloudrun::CloudRunService;
+pub use cloudsql::CloudSqlInstance;
+pub use compute::GCEInstance;
+pub use dns::CloudDnsZone;
+pub use functions::CloudFunction;
+pub use gke::GKECluster;
+pub use loadbalancer::GCPLoadBalancer;
+pub use storage::GCSBucket;
+
+/// Monthly cost of a request-driven workload on Cloud Run or Cloud Functions,
+/// from the requests or invocations seen over the last 30 days.
+///
+/// Only request counts are fetched, so each request is assumed to keep one
+/// instance busy for 200 ms. Free tier allowances are
+/// ignored.
+pub fn estimate_request_cost(requests: u64, vcpu: f64, memory_gib: f64) -> f64 {
+    const PER_MILLION_REQUESTS: f64 = 0.40;
+    const VCPU_SECOND: f64 = 0.000024;
+    const GIB_SECOND: f64 = 0.0000025;
+    const ASSUMED_REQUEST_SECONDS: f64 = 0.2;
+
+    let requests = requests as f64;
+    let compute_seconds = requests * ASSUMED_REQUEST_SECONDS;
+
+    requests / 1_000_000.0 * PER_MILLION_REQUESTS
+        + compute_seconds * (vcpu * VCPU_SECOND + memory_gib * GIB_SECOND)
+}
+
+/// Parses a memory quantity such as `512Mi`, `1Gi` or `256M` into GiB.
+pub fn parse_memory_gib(quantity: &str) -> Option<f64> {
+    let split = quantity
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(quantity.len());
+    let (amount, unit) = quantity.split_at(split);
+    let amount: f64 = amount.parse().ok()?;
+
+    let bytes = match unit {
+        "" => amount,
+        "k" | "K" => amount * 1e3,
+        "Ki" => amount * 1024.0,
+        "M" => amount * 1e6,
+        "Mi" => amount * 1024.0 * 1024.0,
+        "G" => amount * 1e9,
+        "Gi" => amount * 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some(bytes / (1024.0 * 1024.0 * 1024.0))
+}
+
+/// Parses a CPU quantity such as `1`, `0.5` or `1000m` into vCPUs.
+pub fn parse_cpu(quantity: &str) -> Option<f64> {
+    match quantity.strip_suffix('m') {
+        Some(millis) => millis.parse::<f64>().ok().map(|m| m / 1000.0),
+        None => quantity.parse().ok(),
+    }
+}
+
+/// Builders for the resource tests.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use serde::de::DeserializeOwned;
+    use serde_json::{json, Value};
+
+    /// Parses a resource named `web-1` and labelled `team=platform`, with
+    /// `fields` laid over it. A `null` field is left out of the response.
+    pub fn gcp_resource<T: DeserializeOwned>(fields: Value) -> T {
+        let defaults = json!({
+            "name": "web-1",
+            "labels": { "team": "platform" }
+        });
+        crate::providers::fixtures::api_response(defaults, fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quantities() {
+        assert_eq!(parse_memory_gib("1Gi"), Some(1.0));
+        assert_eq!(parse_memory_gib("512Mi"), Some(0.5));
+        assert!((parse_memory_gib("256M").unwrap() - 0.2384).abs() < 1e-4);
+        assert_eq!(parse_memory_gib("lots"), None);
+        assert_eq!(parse_cpu("2"), Some(2.0));
+        assert_eq!(parse_cpu("500m"), Some(0.5));
+        assert_eq!(parse_cpu("0.1666"), Some(0.1666));
+    }
+
+    #[test]
+    fn test_estimate_request_cost() {
+        assert_eq!(estimate_request_cost(0, 1.0, 0.5), 0.0);
+
+        // 1M requests: $0.40 plus 200k seconds of 1 vCPU and 0.5 GiB
+        let cost = estimate_request_cost(1_000_000, 1.0, 0.5);
+        assert!((cost - (0.40 + 200_000.0 * (0.000024 + 0.5 * 0.0000025))).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,209 @@
+use crate::core::{Action, CloudResource, Provider, ResourceState, ResourceType};
+use crate::core::metrics::format_bytes;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Cloud Storage bucket as returned by the JSON API.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageBucket {
+    pub name: String,
+    /// Region, dual-region, or multi-region code in upper case, e.g. `US`
+    pub location: String,
+    pub storage_class: Option<String>,
+    pub time_created: Option<String>,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+}
+
+pub struct GCSBucket {
+    /// Relative resource path, `b/<name>`
+    id: String,
+    name: String,
+    location: String,
+    storage_class: String,
+    labels: HashMap<String, String>,
+    created_at: Option<DateTime<Utc>>,
+    size_bytes: Option<u64>,
+}
+
+impl GCSBucket {
+    pub fn from_api(bucket: &StorageBucket) -> Self {
+        let created_at = bucket.time_created.as_deref().and_then(|ts| {
+            DateTime::parse_from_rfc3339(ts)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc))
+        });
+
+        Self {
+            id: format!("b/{}", bucket.name),
+            name: bucket.name.clone(),
+            location: bucket.location.to_lowercase(),
+            storage_class: bucket
+                .storage_class
+                .clone()
+                .unwrap_or_else(|| "STANDARD".to_string()),
+            labels: bucket.labels.clone(),
+            created_at,
+            size_bytes: None,
+        }
+    }
+
+    pub fn with_size(mut self, size_bytes: u64) -> Self {
+        self.size_bytes = Some(size_bytes);
+        self
+    }
+
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    pub fn storage_class(&self) -> &str {
+        &self.storage_class
+    }
+
+    pub fn size_bytes(&self) -> Option<u64> {
+        self.size_bytes
+    }
+
+    pub fn size_gb(&self) -> Option<f64> {
+        self.size_bytes.map(|bytes| bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+    }
+
+    /// Multi-region locations (`us`, `eu`, `asia`) have no region suffix.
+    fn is_multi_region(&self) -> bool {
+        !self.location.contains('-')
+    }
+}
+
+impl CloudResource for GCSBucket {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Storage
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::GCP
+    }
+
+    fn region(&self) -> &str {
+        &self.location
+    }
+
+    fn state(&self) -> ResourceState {
+        ResourceState::Running
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        self.size_gb()
+            .map(|gb| gb * gcs_price_per_gb(&self.storage_class, self.is_multi_region()))
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.labels
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        vec![Action::ViewDetails, Action::Terminate]
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = vec![
+            ("Location".to_string(), self.location.clone()),
+            ("Storage class".to_string(), self.storage_class.clone()),
+        ];
+        if let Some(size_bytes) = self.size_bytes {
+            details.push(("Size".to_string(), format_bytes(size_bytes as f64)));
+        }
+        details
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Monthly at-rest price per GB for a storage class in US locations.
+fn gcs_price_per_gb(storage_class: &str, multi_region: bool) -> f64 {
+    match (storage_class, multi_region) {
+        ("NEARLINE", false) => 0.010,
+        ("NEARLINE", true) => 0.013,
+        ("COLDLINE", false) => 0.004,
+        ("COLDLINE", true) => 0.007,
+        ("ARCHIVE", false) => 0.0012,
+        ("ARCHIVE", true) => 0.0024,
+        (_, false) => 0.020,
+        (_, true) => 0.026,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::gcp::resources::fixtures::gcp_resource;
+    use serde_json::json;
+
+    fn bucket(location: &str, storage_class: &str) -> GCSBucket {
+        GCSBucket::from_api(&gcp_resource(json!({
+            "name": "assets",
+            "location": location,
+            "storageClass": storage_class
+        })))
+    }
+
+    #[test]
+    fn test_gcs_bucket_from_api() {
+        let bucket = GCSBucket::from_api(&gcp_resource(json!({
+            "name": "assets",
+            "location": "US-CENTRAL1",
+            "storageClass": "NEARLINE",
+            "timeCreated": "2024-03-01T09:30:00.000Z"
+        })));
+
+        assert_eq!(bucket.id(), "b/assets");
+        assert_eq!(bucket.location(), "us-central1");
+        assert_eq!(bucket.region(), "us-central1");
+        assert_eq!(bucket.storage_class(), "NEARLINE");
+        assert_eq!(bucket.resource_type(), ResourceType::Storage);
+        assert!(bucket.created_at().is_some());
+        assert!(bucket.cost_per_month().is_none());
+    }
+
+    #[test]
+    fn test_gcs_bucket_with_missing_fields() {
+        let bucket = GCSBucket::from_api(&gcp_resource(json!({
+            "location": "EU",
+            "labels": null
+        })));
+
+        assert_eq!(bucket.storage_class(), "STANDARD");
+        assert!(bucket.tags().is_empty());
+        assert!(bucket.created_at().is_none());
+    }
+
+    #[test]
+    fn test_gcs_bucket_cost() {
+        let gb = 1024 * 1024 * 1024;
+
+        let regional = bucket("US-CENTRAL1", "STANDARD").with_size(100 * gb);
+        assert!((regional.cost_per_month().unwrap() - 2.0).abs() < 1e-9);
+
+        let multi_region = bucket("US", "STANDARD").with_size(100 * gb);
+        assert!((multi_region.cost_per_month().unwrap() - 2.6).abs() < 1e-9);
+
+        let archive = bucket("US-CENTRAL1", "ARCHIVE").with_size(1000 * gb);
+        assert!((archive.cost_per_month().unwrap() - 1.2).abs() < 1e-9);
+    }
+}
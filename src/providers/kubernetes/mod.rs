@@ -0,0 +1,472 @@
+use async_trait::async_trait;
+use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
+use k8s_openapi::api::core::v1::{Namespace, Node};
+use k8s_openapi::NamespaceResourceScope;
+use kube::api::{Api, DeleteParams, ListParams, Patch, PatchParams};
+use kube::config::{KubeConfigOptions, Kubeconfig};
+use kube::{Client, Resource};
+use serde::de::DeserializeOwned;
+
+use crate::config::{parse_resource_types, KubernetesConfig};
+use crate::core::{
+    collect_stream, send_batch_if, AccountIdentity, Action, ActionRequest, CloudProvider, CloudResource,
+    CostBreakdown, CostPeriod, OperationHandle, Provider, ProviderCapabilities, ResourceSender, ResourceType,
+};
+use crate::error::{NimbusError, Result};
+
+pub mod resources;
+
+use resources::workload::{parse_workload_id, stopped_replicas};
+use resources::{KubernetesNode, KubernetesWorkload, WorkloadKind, STOPPED_REPLICAS_ANNOTATION};
+
+/// The pseudo-region that lists workloads from every namespace.
+pub const ALL_NAMESPACES: &str = "all";
+
+/// Pod template annotation `kubectl rollout restart` sets to roll every pod.
+const RESTARTED_AT_ANNOTATION: &str = "kubectl.kubernetes.io/restartedAt";
+
+/// Treats a cluster as a provider: namespaces take the place of regions,
+/// workloads are listed as containers and nodes as compute.
+pub struct KubernetesProvider {
+    name: String,
+    config: KubernetesConfig,
+    client: Option<Client>,
+    context: Option<String>,
+    server: Option<String>,
+    /// Selected namespace, or `ALL_NAMESPACES`
+    namespace: String,
+    /// Namespaces in the cluster, discovered after authentication
+    namespaces: Vec<String>,
+}
+
+impl KubernetesProvider {
+    pub fn new(config: KubernetesConfig) -> Self {
+        let namespace = config
+            .namespace
+            .clone()
+            .unwrap_or_else(|| ALL_NAMESPACES.to_string());
+
+        let name = match config.label {
+            Some(ref label) => format!("Kubernetes ({})", label),
+            None => "Kubernetes".to_string(),
+        };
+
+        Self {
+            name,
+            config,
+            client: None,
+            context: None,
+            server: None,
+            namespace,
+            namespaces: Vec::new(),
+        }
+    }
+
+    async fn ensure_authenticated(&self) -> Result<()> {
+        if self.client.is_none() {
+            return Err(NimbusError::auth(
+                "Kubernetes",
+                "Provider not authenticated. Call authenticate() first.",
+            ));
+        }
+        Ok(())
+    }
+
+    fn get_client(&self) -> Result<&Client> {
+        self.client.as_ref().ok_or_else(|| {
+            NimbusError::auth(
+                "Kubernetes",
+                "Client not initialized. Call authenticate() first.",
+            )
+        })
+    }
+
+    /// Returns an API for a namespaced kind, scoped to the selected namespace.
+    fn namespaced_api<K>(&self) -> Result<Api<K>>
+    where
+        K: Resource<Scope = NamespaceResourceScope>,
+        <K as Resource>::DynamicType: Default,
+    {
+        let client = self.get_client()?.clone();
+        Ok(if self.namespace == ALL_NAMESPACES {
+            Api::all(client)
+        } else {
+            Api::namespaced(client, &self.namespace)
+        })
+    }
+
+    async fn list_namespaces(&self) -> Result<Vec<String>> {
+        let api: Api<Namespace> = Api::all(self.get_client()?.clone());
+        let namespaces = api
+            .list(&ListParams::default())
+            .await
+            .map_err(|e| api_error("Failed to list namespaces", e))?;
+
+        let mut names: Vec<String> = namespaces.items.into_iter().filter_map(|ns| ns.metadata.name).collect();
+        names.sort();
+        Ok(names)
+    }
+
+    async fn list_workloads(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let deployments: Api<Deployment> = self.namespaced_api()?;
+        let stateful_sets: Api<StatefulSet> = self.namespaced_api()?;
+        let params = ListParams::default();
+
+        let (deployments, stateful_sets) = tokio::join!(deployments.list(&params), stateful_sets.list(&params));
+        let deployments = deployments.map_err(|e| api_error("Failed to list deployments", e))?;
+        let stateful_sets = stateful_sets.map_err(|e| api_error("Failed to list stateful sets", e))?;
+
+        Ok(deployments
+            .items
+            .iter()
+            .map(|d| Box::new(KubernetesWorkload::from_deployment(d)) as Box<dyn CloudResource>)
+            .chain(
+                stateful_sets
+                    .items
+                    .iter()
+                    .map(|s| Box::new(KubernetesWorkload::from_stateful_set(s)) as Box<dyn CloudResource>),
+            )
+            .collect())
+    }
+
+    async fn list_nodes(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        let api: Api<Node> = Api::all(self.get_client()?.clone());
+        let nodes = api
+            .list(&ListParams::default())
+            .await
+            .map_err(|e| api_error("Failed to list nodes", e))?;
+
+        Ok(nodes
+            .items
+            .iter()
+            .map(|node| Box::new(KubernetesNode::from_api(node)) as Box<dyn CloudResource>)
+            .collect())
+    }
+
+    async fn execute_workload_action(&self, resource_id: &str, request: &ActionRequest) -> Result<()> {
+        let (kind, namespace, name) = parse_workload_id(resource_id)
+            .ok_or_else(|| NimbusError::ResourceNotFound(resource_id.to_string()))?;
+        let client = self.get_client()?.clone();
+
+        match kind {
+            WorkloadKind::Deployment => {
+                apply_workload_action::<Deployment>(Api::namespaced(client, namespace), name, request).await
+            }
+            WorkloadKind::StatefulSet => {
+                apply_workload_action::<StatefulSet>(Api::namespaced(client, namespace), name, request).await
+            }
+        }
+    }
+}
+
+/// Applies an action to a Deployment or StatefulSet through its scale
+/// subresource. Stopping scales to zero and records the previous replica
+/// count in an annotation so that starting restores it.
+async fn apply_workload_action<K>(api: Api<K>, name: &str, request: &ActionRequest) -> Result<()>
+where
+    K: Resource + Clone + DeserializeOwned + std::fmt::Debug,
+{
+    let params = PatchParams::default();
+
+    match request.action {
+        Action::Stop => {
+            let scale = api
+                .get_scale(name)
+                .await
+                .map_err(|e| api_error("Failed to read replica count", e))?;
+            let replicas = scale.spec.and_then(|spec| spec.replicas).unwrap_or(0);
+            if replicas == 0 {
+                return Ok(());
+            }
+
+            let annotation = serde_json::json!({
+                "metadata": { "annotations": { STOPPED_REPLICAS_ANNOTATION: replicas.to_string() } }
+            });
+            api.patch(name, &params, &Patch::Merge(&annotation))
+                .await
+                .map_err(|e| api_error("Failed to record replica count", e))?;
+            scale_to(&api, name, 0).await
+        }
+        Action::Start => {
+            let object = api.get(name).await.map_err(|e| api_error("Failed to read workload", e))?;
+            let replicas = stopped_replicas(object.meta()).filter(|&n| n > 0).unwrap_or(1);
+            scale_to(&api, name, replicas).await?;
+
+            let annotation = serde_json::json!({
+                "metadata": { "annotations": { STOPPED_REPLICAS_ANNOTATION: null } }
+            });
+            api.patch(name, &params, &Patch::Merge(&annotation))
+                .await
+                .map_err(|e| api_error("Failed to clear replica count", e))?;
+            Ok(())
+        }
+        Action::Scale => match request.params.desired_capacity {
+            Some(replicas) => scale_to(&api, name, replicas as i32).await,
+            None => Err(NimbusError::provider("Kubernetes", "Scaling needs a desired replica count")),
+        },
+        Action::Restart => {
+            // Same as `kubectl rollout restart`: changing the template rolls every pod
+            let restart = serde_json::json!({
+                "spec": { "template": { "metadata": { "annotations": {
+                    RESTARTED_AT_ANNOTATION: chrono::Utc::now().to_rfc3339()
+                } } } }
+            });
+            api.patch(name, &params, &Patch::Merge(&restart))
+                .await
+                .map_err(|e| api_error("Failed to restart workload", e))?;
+            Ok(())
+        }
+        Action::Terminate => {
+            api.delete(name, &DeleteParams::default())
+                .await
+                .map_err(|e| api_error("Failed to delete workload", e))?;
+            Ok(())
+        }
+        action => Err(NimbusError::UnsupportedAction(action, ResourceType::Container)),
+    }
+}
+
+async fn scale_to<K>(api: &Api<K>, name: &str, replicas: i32) -> Result<()>
+where
+    K: Resource + Clone + DeserializeOwned + std::fmt::Debug,
+{
+    let patch = serde_json::json!({ "spec": { "replicas": replicas } });
+    api.patch_scale(name, &PatchParams::default(), &Patch::Merge(&patch))
+        .await
+        .map_err(|e| api_error(&format!("Failed to scale to {} replicas", replicas), e))?;
+    Ok(())
+}
+
+fn api_error(context: &str, error: kube::Error) -> NimbusError {
+    match error {
+        kube::Error::Api(ref response) if response.code == 404 => {
+            NimbusError::ResourceNotFound(format!("{}: {}", context, response.message))
+        }
+        _ => NimbusError::provider("Kubernetes", format!("{}: {}", context, error)),
+    }
+}
+
+#[async_trait]
+impl CloudProvider for KubernetesProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn provider_type(&self) -> Provider {
+        Provider::Kubernetes
+    }
+
+    fn account_name(&self) -> &str {
+        self.config
+            .label
+            .as_deref()
+            .or(self.context.as_deref())
+            .unwrap_or(&self.name)
+    }
+
+    async fn authenticate(&mut self) -> Result<()> {
+        let kubeconfig = match self.config.kubeconfig {
+            Some(ref path) => Kubeconfig::read_from(path),
+            None => Kubeconfig::read(),
+        }
+        .map_err(|e| NimbusError::auth("Kubernetes", format!("Failed to read kubeconfig: {}", e)))?;
+
+        let context = self
+            .config
+            .context
+            .clone()
+            .or_else(|| kubeconfig.current_context.clone());
+        let options = KubeConfigOptions {
+            context: self.config.context.clone(),
+            ..Default::default()
+        };
+        let client_config = kube::Config::from_custom_kubeconfig(kubeconfig, &options)
+            .await
+            .map_err(|e| NimbusError::auth("Kubernetes", format!("Invalid kubeconfig context: {}", e)))?;
+        let server = client_config.cluster_url.to_string();
+
+        let client = Client::try_from(client_config)
+            .map_err(|e| NimbusError::auth("Kubernetes", format!("Failed to create client: {}", e)))?;
+        client
+            .apiserver_version()
+            .await
+            .map_err(|e| NimbusError::auth("Kubernetes", format!("Cluster unreachable at {}: {}", server, e)))?;
+
+        self.name = match (&self.config.label, &context) {
+            (Some(label), _) => format!("Kubernetes ({})", label),
+            (None, Some(context)) => format!("Kubernetes ({})", context),
+            (None, None) => "Kubernetes".to_string(),
+        };
+        self.context = context;
+        self.server = Some(server);
+        self.client = Some(client);
+
+        // Listing namespaces may be forbidden; the namespace picker just stays short
+        match self.list_namespaces().await {
+            Ok(namespaces) => self.namespaces = namespaces,
+            Err(e) => log::warn!("Could not list namespaces: {}", e),
+        }
+
+        Ok(())
+    }
+
+    fn identity(&self) -> Option<AccountIdentity> {
+        self.client.as_ref()?;
+        let server = self.server.clone().unwrap_or_default();
+        Some(AccountIdentity::new(self.context.clone().unwrap_or_else(|| server.clone()), server))
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities::none()
+            .with_resource_types(&[ResourceType::Compute, ResourceType::Container])
+            .restrict_resource_types(&parse_resource_types(&self.config.resource_types).unwrap_or_default())
+            .with_actions(&[
+                Action::Start,
+                Action::Stop,
+                Action::Restart,
+                Action::Terminate,
+                Action::Scale,
+            ])
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        self.ensure_authenticated().await?;
+
+        match self.get_client()?.apiserver_version().await {
+            Ok(_) => Ok(true),
+            Err(e) => Err(NimbusError::provider(
+                "Kubernetes",
+                format!("Connection test failed: {}", e),
+            )),
+        }
+    }
+
+    async fn list_all_resources(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        collect_stream(self).await
+    }
+
+    async fn stream_all_resources(&self, sender: ResourceSender) -> Result<()> {
+        self.ensure_authenticated().await?;
+        let caps = self.capabilities();
+
+        tokio::join!(
+            send_batch_if(&sender, &caps, ResourceType::Container, "Workloads", self.list_workloads()),
+            send_batch_if(&sender, &caps, ResourceType::Compute, "Nodes", self.list_nodes()),
+        );
+
+        Ok(())
+    }
+
+    async fn list_resources_by_type(
+        &self,
+        resource_type: ResourceType,
+    ) -> Result<Vec<Box<dyn CloudResource>>> {
+        if !self.capabilities().supports_type(resource_type) {
+            return Ok(Vec::new());
+        }
+        self.ensure_authenticated().await?;
+
+        match resource_type {
+            ResourceType::Container => self.list_workloads().await,
+            ResourceType::Compute => self.list_nodes().await,
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    async fn get_resource(&self, id: &str) -> Result<Box<dyn CloudResource>> {
+        self.ensure_authenticated().await?;
+        let client = self.get_client()?.clone();
+
+        if let Some(name) = id.strip_prefix("nodes/") {
+            let node = Api::<Node>::all(client)
+                .get(name)
+                .await
+                .map_err(|e| api_error("Failed to get node", e))?;
+            return Ok(Box::new(KubernetesNode::from_api(&node)));
+        }
+
+        match parse_workload_id(id) {
+            Some((WorkloadKind::Deployment, namespace, name)) => {
+                let deployment = Api::<Deployment>::namespaced(client, namespace)
+                    .get(name)
+                    .await
+                    .map_err(|e| api_error("Failed to get deployment", e))?;
+                Ok(Box::new(KubernetesWorkload::from_deployment(&deployment)))
+            }
+            Some((WorkloadKind::StatefulSet, namespace, name)) => {
+                let stateful_set = Api::<StatefulSet>::namespaced(client, namespace)
+                    .get(name)
+                    .await
+                    .map_err(|e| api_error("Failed to get stateful set", e))?;
+                Ok(Box::new(KubernetesWorkload::from_stateful_set(&stateful_set)))
+            }
+            None => Err(NimbusError::ResourceNotFound(id.to_string())),
+        }
+    }
+
+    async fn execute_action(
+        &self,
+        resource_id: &str,
+        resource_type: ResourceType,
+        request: &ActionRequest,
+    ) -> Result<OperationHandle> {
+        self.ensure_authenticated().await?;
+        let action = request.action;
+
+        match resource_type {
+            ResourceType::Container => self.execute_workload_action(resource_id, request).await,
+            other => Err(NimbusError::UnsupportedAction(action, other)),
+        }?;
+
+        Ok(OperationHandle::new(resource_id, resource_type, request))
+    }
+
+    async fn get_total_cost(&self, _period: CostPeriod) -> Result<f64> {
+        // Spend is reported by the cloud providers hosting the nodes
+        Ok(0.0)
+    }
+
+    async fn get_cost_breakdown(&self) -> Result<CostBreakdown> {
+        Ok(CostBreakdown::new())
+    }
+
+    fn regions(&self) -> Vec<String> {
+        std::iter::once(ALL_NAMESPACES.to_string())
+            .chain(self.namespaces.iter().cloned())
+            .collect()
+    }
+
+    fn current_region(&self) -> &str {
+        &self.namespace
+    }
+
+    async fn set_region(&mut self, region: &str) -> Result<()> {
+        if region != ALL_NAMESPACES && !self.namespaces.iter().any(|ns| ns == region) {
+            return Err(NimbusError::InvalidRegion(region.to_string()));
+        }
+
+        self.namespace = region.to_string();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_defaults() {
+        let provider = KubernetesProvider::new(KubernetesConfig::default());
+        assert_eq!(provider.name(), "Kubernetes");
+        assert_eq!(provider.provider_type(), Provider::Kubernetes);
+        assert_eq!(provider.current_region(), ALL_NAMESPACES);
+        assert_eq!(provider.regions(), vec![ALL_NAMESPACES.to_string()]);
+        assert!(provider.identity().is_none());
+
+        let scoped = KubernetesProvider::new(KubernetesConfig {
+            namespace: Some("shop".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(scoped.current_region(), "shop");
+    }
+}
@@ -0,0 +1,47 @@
+pub mod node;
+pub mod workload;
+
+pub use node::KubernetesNode;
+pub use workload::{KubernetesWorkload, WorkloadKind};
+
+use chrono::{DateTime, Utc};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use std::collections::HashMap;
+
+/// Annotation recording a workload's replica count while it is scaled to zero,
+/// so starting it again restores the previous size.
+pub const STOPPED_REPLICAS_ANNOTATION: &str = "nimbus/stopped-replicas";
+
+/// Labels of an object, as the tags shown for the resource.
+pub(crate) fn labels_of(metadata: &ObjectMeta) -> HashMap<String, String> {
+    metadata
+        .labels
+        .as_ref()
+        .map(|labels| labels.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+        .unwrap_or_default()
+}
+
+pub(crate) fn created_at_of(metadata: &ObjectMeta) -> Option<DateTime<Utc>> {
+    metadata.creation_timestamp.as_ref().map(|time| time.0)
+}
+
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use serde::de::DeserializeOwned;
+    use serde_json::{json, Value};
+
+    /// Parses an object named `checkout` in the `shop` namespace and labelled
+    /// `app=checkout`, with `fields` laid over it. Passing `metadata` replaces
+    /// all of the default metadata.
+    pub fn k8s_object<T: DeserializeOwned>(fields: Value) -> T {
+        let defaults = json!({
+            "metadata": {
+                "name": "checkout",
+                "namespace": "shop",
+                "labels": { "app": "checkout" },
+                "creationTimestamp": "2024-03-01T09:30:00Z"
+            }
+        });
+        crate::providers::fixtures::api_response(defaults, fields)
+    }
+}
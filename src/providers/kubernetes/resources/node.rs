@@ -0,0 +1,233 @@
+use crate::core::{Action, CloudResource, Provider, ResourceHealth, ResourceState, ResourceType};
+use super::{created_at_of, labels_of};
+use chrono::{DateTime, Utc};
+use k8s_openapi::api::core::v1::Node;
+use std::collections::HashMap;
+
+const REGION_LABEL: &str = "topology.kubernetes.io/region";
+const INSTANCE_TYPE_LABEL: &str = "node.kubernetes.io/instance-type";
+
+pub struct KubernetesNode {
+    /// `nodes/<name>`
+    id: String,
+    name: String,
+    region: String,
+    /// Status of the `Ready` condition, if reported
+    ready: Option<bool>,
+    unschedulable: bool,
+    kubelet_version: Option<String>,
+    instance_type: Option<String>,
+    provider_id: Option<String>,
+    cpu: Option<String>,
+    memory: Option<String>,
+    labels: HashMap<String, String>,
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl KubernetesNode {
+    pub fn from_api(node: &Node) -> Self {
+        let name = node.metadata.name.clone().unwrap_or_default();
+        let labels = labels_of(&node.metadata);
+        let spec = node.spec.as_ref();
+        let status = node.status.as_ref();
+
+        let ready = status
+            .and_then(|s| s.conditions.as_ref())
+            .and_then(|conditions| conditions.iter().find(|c| c.type_ == "Ready"))
+            .map(|condition| condition.status == "True");
+        let capacity = |resource: &str| {
+            status
+                .and_then(|s| s.capacity.as_ref())
+                .and_then(|capacity| capacity.get(resource))
+                .map(|quantity| quantity.0.clone())
+        };
+
+        Self {
+            id: format!("nodes/{}", name),
+            name,
+            region: labels.get(REGION_LABEL).cloned().unwrap_or_else(|| "cluster".to_string()),
+            ready,
+            unschedulable: spec.and_then(|s| s.unschedulable).unwrap_or(false),
+            kubelet_version: status
+                .and_then(|s| s.node_info.as_ref())
+                .map(|info| info.kubelet_version.clone()),
+            instance_type: labels.get(INSTANCE_TYPE_LABEL).cloned(),
+            provider_id: spec.and_then(|s| s.provider_id.clone()),
+            cpu: capacity("cpu"),
+            memory: capacity("memory"),
+            labels,
+            created_at: created_at_of(&node.metadata),
+        }
+    }
+
+    pub fn is_unschedulable(&self) -> bool {
+        self.unschedulable
+    }
+
+    pub fn kubelet_version(&self) -> Option<&str> {
+        self.kubelet_version.as_deref()
+    }
+
+    pub fn instance_type(&self) -> Option<&str> {
+        self.instance_type.as_deref()
+    }
+
+    /// Cloud provider's reference to the machine hosting the node, e.g.
+    /// `aws:///us-east-1a/i-0abc123`.
+    pub fn provider_id(&self) -> Option<&str> {
+        self.provider_id.as_deref()
+    }
+
+    /// The hosting machine's ID within its cloud: the last segment of the
+    /// provider ID, such as an EC2 instance ID.
+    pub fn host_instance_id(&self) -> Option<&str> {
+        self.provider_id
+            .as_deref()
+            .and_then(|id| id.rsplit('/').next())
+            .filter(|id| !id.is_empty())
+    }
+
+    /// CPU capacity, e.g. `4`.
+    pub fn cpu(&self) -> Option<&str> {
+        self.cpu.as_deref()
+    }
+
+    /// Memory capacity, e.g. `16318480Ki`.
+    pub fn memory(&self) -> Option<&str> {
+        self.memory.as_deref()
+    }
+}
+
+impl CloudResource for KubernetesNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Compute
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::Kubernetes
+    }
+
+    fn region(&self) -> &str {
+        &self.region
+    }
+
+    fn state(&self) -> ResourceState {
+        match self.ready {
+            Some(true) => ResourceState::Running,
+            Some(false) => ResourceState::Error,
+            None => ResourceState::Unknown,
+        }
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        // The machine is priced by the cloud provider that hosts it
+        None
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.labels
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        vec![Action::ViewDetails]
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = Vec::new();
+        if let Some(ref version) = self.kubelet_version {
+            details.push(("Kubelet".to_string(), version.clone()));
+        }
+        if let Some(ref instance_type) = self.instance_type {
+            details.push(("Instance type".to_string(), instance_type.clone()));
+        }
+        if let Some(ref provider_id) = self.provider_id {
+            details.push(("Provider ID".to_string(), provider_id.clone()));
+        }
+        if let Some(ref cpu) = self.cpu {
+            details.push(("CPU".to_string(), cpu.clone()));
+        }
+        if let Some(ref memory) = self.memory {
+            details.push(("Memory".to_string(), memory.clone()));
+        }
+        if self.unschedulable {
+            details.push(("Scheduling".to_string(), "Cordoned".to_string()));
+        }
+        details
+    }
+
+    fn health(&self) -> Option<ResourceHealth> {
+        match self.ready {
+            Some(false) => Some(ResourceHealth::impaired("Node is not ready")),
+            Some(true) if self.unschedulable => Some(ResourceHealth::warning("Cordoned")),
+            Some(true) => Some(ResourceHealth::healthy()),
+            None => None,
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_from_api() {
+        let node: Node = serde_json::from_value(serde_json::json!({
+            "metadata": {
+                "name": "ip-10-0-1-23.ec2.internal",
+                "labels": {
+                    "topology.kubernetes.io/region": "us-east-1",
+                    "node.kubernetes.io/instance-type": "m5.large"
+                },
+                "creationTimestamp": "2024-03-01T09:30:00Z"
+            },
+            "spec": { "providerID": "aws:///us-east-1a/i-0abc123def4567890" },
+            "status": {
+                "capacity": { "cpu": "2", "memory": "7935284Ki" },
+                "conditions": [
+                    { "type": "MemoryPressure", "status": "False" },
+                    { "type": "Ready", "status": "True" }
+                ],
+                "nodeInfo": {
+                    "architecture": "amd64",
+                    "bootID": "",
+                    "containerRuntimeVersion": "containerd://1.7.11",
+                    "kernelVersion": "5.10.210",
+                    "kubeProxyVersion": "v1.29.0",
+                    "kubeletVersion": "v1.29.0",
+                    "machineID": "",
+                    "operatingSystem": "linux",
+                    "osImage": "Amazon Linux 2",
+                    "systemUUID": ""
+                }
+            }
+        }))
+        .unwrap();
+
+        let node = KubernetesNode::from_api(&node);
+        assert_eq!(node.id(), "nodes/ip-10-0-1-23.ec2.internal");
+        assert_eq!(node.region(), "us-east-1");
+        assert_eq!(node.instance_type(), Some("m5.large"));
+        assert_eq!(node.host_instance_id(), Some("i-0abc123def4567890"));
+        assert_eq!(node.kubelet_version(), Some("v1.29.0"));
+        assert_eq!(node.cpu(), Some("2"));
+        assert_eq!(node.resource_type(), ResourceType::Compute);
+        assert_eq!(node.state(), ResourceState::Running);
+        assert!(!node.is_unschedulable());
+    }
+}
@@ -0,0 +1,332 @@
+use crate::core::{Action, CloudResource, Provider, ResourceHealth, ResourceState, ResourceType};
+use super::{created_at_of, labels_of, STOPPED_REPLICAS_ANNOTATION};
+use chrono::{DateTime, Utc};
+use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
+use k8s_openapi::api::core::v1::PodTemplateSpec;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkloadKind {
+    Deployment,
+    StatefulSet,
+}
+
+impl WorkloadKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WorkloadKind::Deployment => "Deployment",
+            WorkloadKind::StatefulSet => "StatefulSet",
+        }
+    }
+
+    /// Plural resource name used as the ID prefix, as in the API paths.
+    pub fn plural(&self) -> &'static str {
+        match self {
+            WorkloadKind::Deployment => "deployments",
+            WorkloadKind::StatefulSet => "statefulsets",
+        }
+    }
+
+    fn from_plural(plural: &str) -> Option<Self> {
+        match plural {
+            "deployments" => Some(WorkloadKind::Deployment),
+            "statefulsets" => Some(WorkloadKind::StatefulSet),
+            _ => None,
+        }
+    }
+}
+
+/// Splits a workload ID, `<kind>/<namespace>/<name>`, into its parts.
+pub fn parse_workload_id(id: &str) -> Option<(WorkloadKind, &str, &str)> {
+    let mut parts = id.splitn(3, '/');
+    let kind = WorkloadKind::from_plural(parts.next()?)?;
+    match (parts.next(), parts.next()) {
+        (Some(namespace), Some(name)) if !namespace.is_empty() && !name.is_empty() => {
+            Some((kind, namespace, name))
+        }
+        _ => None,
+    }
+}
+
+/// A Deployment or StatefulSet.
+pub struct KubernetesWorkload {
+    /// `<kind>/<namespace>/<name>`, e.g. `deployments/shop/checkout`
+    id: String,
+    name: String,
+    namespace: String,
+    kind: WorkloadKind,
+    /// Desired replica count
+    replicas: i32,
+    ready_replicas: i32,
+    /// Replica count to restore on start, set while scaled to zero
+    stopped_replicas: Option<i32>,
+    images: Vec<String>,
+    labels: HashMap<String, String>,
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl KubernetesWorkload {
+    pub fn from_deployment(deployment: &Deployment) -> Self {
+        let spec = deployment.spec.as_ref();
+        Self::from_parts(
+            WorkloadKind::Deployment,
+            &deployment.metadata,
+            spec.and_then(|s| s.replicas),
+            spec.map(|s| &s.template),
+            deployment.status.as_ref().and_then(|s| s.ready_replicas),
+        )
+    }
+
+    pub fn from_stateful_set(stateful_set: &StatefulSet) -> Self {
+        let spec = stateful_set.spec.as_ref();
+        Self::from_parts(
+            WorkloadKind::StatefulSet,
+            &stateful_set.metadata,
+            spec.and_then(|s| s.replicas),
+            spec.map(|s| &s.template),
+            stateful_set.status.as_ref().and_then(|s| s.ready_replicas),
+        )
+    }
+
+    fn from_parts(
+        kind: WorkloadKind,
+        metadata: &ObjectMeta,
+        replicas: Option<i32>,
+        template: Option<&PodTemplateSpec>,
+        ready_replicas: Option<i32>,
+    ) -> Self {
+        let name = metadata.name.clone().unwrap_or_default();
+        let namespace = metadata.namespace.clone().unwrap_or_else(|| "default".to_string());
+        let images = template
+            .and_then(|t| t.spec.as_ref())
+            .map(|spec| spec.containers.iter().filter_map(|c| c.image.clone()).collect())
+            .unwrap_or_default();
+
+        Self {
+            id: format!("{}/{}/{}", kind.plural(), namespace, name),
+            name,
+            namespace,
+            kind,
+            // The API server defaults an unset replica count to one
+            replicas: replicas.unwrap_or(1),
+            ready_replicas: ready_replicas.unwrap_or(0),
+            stopped_replicas: stopped_replicas(metadata),
+            images,
+            labels: labels_of(metadata),
+            created_at: created_at_of(metadata),
+        }
+    }
+
+    pub fn kind(&self) -> WorkloadKind {
+        self.kind
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    pub fn replicas(&self) -> i32 {
+        self.replicas
+    }
+
+    pub fn ready_replicas(&self) -> i32 {
+        self.ready_replicas
+    }
+
+    /// Replica count that starting the workload restores.
+    pub fn replicas_to_restore(&self) -> i32 {
+        self.stopped_replicas.filter(|&n| n > 0).unwrap_or(1)
+    }
+
+    /// Container images of the pod template.
+    pub fn images(&self) -> &[String] {
+        &self.images
+    }
+}
+
+/// Reads the replica count saved when a workload was scaled to zero.
+pub fn stopped_replicas(metadata: &ObjectMeta) -> Option<i32> {
+    metadata
+        .annotations
+        .as_ref()?
+        .get(STOPPED_REPLICAS_ANNOTATION)?
+        .parse()
+        .ok()
+}
+
+impl CloudResource for KubernetesWorkload {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn resource_type(&self) -> ResourceType {
+        ResourceType::Container
+    }
+
+    fn provider(&self) -> Provider {
+        Provider::Kubernetes
+    }
+
+    fn region(&self) -> &str {
+        &self.namespace
+    }
+
+    fn state(&self) -> ResourceState {
+        if self.replicas == 0 {
+            ResourceState::Stopped
+        } else if self.ready_replicas >= self.replicas {
+            ResourceState::Running
+        } else {
+            ResourceState::Pending
+        }
+    }
+
+    fn cost_per_month(&self) -> Option<f64> {
+        // Pods are paid for through the nodes they are scheduled on
+        None
+    }
+
+    fn tags(&self) -> &HashMap<String, String> {
+        &self.labels
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
+    fn supported_actions(&self) -> Vec<Action> {
+        match self.state() {
+            ResourceState::Stopped => vec![Action::Start, Action::Scale, Action::Terminate, Action::ViewDetails],
+            _ => vec![Action::Stop, Action::Restart, Action::Scale, Action::Terminate, Action::ViewDetails],
+        }
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = vec![
+            ("Kind".to_string(), self.kind.as_str().to_string()),
+            ("Namespace".to_string(), self.namespace.clone()),
+            ("Replicas".to_string(), format!("{}/{} ready", self.ready_replicas, self.replicas)),
+        ];
+        if !self.images.is_empty() {
+            details.push(("Images".to_string(), self.images.join(", ")));
+        }
+        details
+    }
+
+    fn health(&self) -> Option<ResourceHealth> {
+        if self.replicas == 0 {
+            None
+        } else if self.ready_replicas == 0 {
+            Some(ResourceHealth::impaired(format!("0/{} replicas ready", self.replicas)))
+        } else if self.ready_replicas < self.replicas {
+            Some(ResourceHealth::warning(format!(
+                "{}/{} replicas ready",
+                self.ready_replicas, self.replicas
+            )))
+        } else {
+            Some(ResourceHealth::healthy())
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::kubernetes::resources::fixtures::k8s_object;
+    use serde_json::json;
+
+    fn deployment(replicas: i32, ready: i32) -> KubernetesWorkload {
+        KubernetesWorkload::from_deployment(&k8s_object(json!({
+            "spec": {
+                "replicas": replicas,
+                "selector": { "matchLabels": { "app": "checkout" } },
+                "template": {}
+            },
+            "status": { "readyReplicas": ready }
+        })))
+    }
+
+    #[test]
+    fn test_workload_from_deployment() {
+        let workload = KubernetesWorkload::from_deployment(&k8s_object(json!({
+            "spec": {
+                "replicas": 3,
+                "selector": { "matchLabels": { "app": "checkout" } },
+                "template": {
+                    "spec": {
+                        "containers": [{ "name": "app", "image": "ghcr.io/acme/checkout:1.4.2" }]
+                    }
+                }
+            },
+            "status": { "readyReplicas": 3 }
+        })));
+        assert_eq!(workload.id(), "deployments/shop/checkout");
+        assert_eq!(workload.region(), "shop");
+        assert_eq!(workload.kind(), WorkloadKind::Deployment);
+        assert_eq!(workload.images(), ["ghcr.io/acme/checkout:1.4.2".to_string()]);
+        assert_eq!(workload.tags().get("app"), Some(&"checkout".to_string()));
+        assert_eq!(workload.provider(), Provider::Kubernetes);
+        assert_eq!(workload.state(), ResourceState::Running);
+        assert!(workload.supported_actions().contains(&Action::Stop));
+        assert!(workload.created_at().is_some());
+    }
+
+    #[test]
+    fn test_workload_with_missing_fields() {
+        let workload = KubernetesWorkload::from_stateful_set(&k8s_object(json!({
+            "metadata": { "name": "postgres" }
+        })));
+        assert_eq!(workload.id(), "statefulsets/default/postgres");
+        assert_eq!(workload.namespace(), "default");
+        assert!(workload.tags().is_empty());
+        assert!(workload.images().is_empty());
+        assert!(workload.created_at().is_none());
+        // No spec means the API server's default of one replica, none ready
+        assert_eq!(workload.replicas(), 1);
+        assert_eq!(workload.ready_replicas(), 0);
+        assert_eq!(workload.health(), Some(ResourceHealth::impaired("0/1 replicas ready")));
+    }
+
+    #[test]
+    fn test_scaled_to_zero_workload() {
+        let stopped = KubernetesWorkload::from_deployment(&k8s_object(json!({
+            "metadata": {
+                "name": "checkout",
+                "namespace": "shop",
+                "annotations": { "nimbus/stopped-replicas": "3" }
+            },
+            "spec": { "replicas": 0, "selector": {}, "template": {} }
+        })));
+        assert_eq!(stopped.state(), ResourceState::Stopped);
+        assert_eq!(stopped.replicas_to_restore(), 3);
+        assert!(stopped.supported_actions().contains(&Action::Start));
+        assert_eq!(stopped.health(), None);
+
+        // Without the annotation there is nothing to restore but one replica
+        assert_eq!(deployment(0, 0).replicas_to_restore(), 1);
+
+        let rolling = deployment(3, 1);
+        assert_eq!(rolling.state(), ResourceState::Pending);
+        assert!(rolling.details().contains(&("Replicas".to_string(), "1/3 ready".to_string())));
+        assert_eq!(rolling.health(), Some(ResourceHealth::warning("1/3 replicas ready")));
+    }
+
+    #[test]
+    fn test_parse_workload_id() {
+        assert_eq!(
+            parse_workload_id("statefulsets/data/postgres"),
+            Some((WorkloadKind::StatefulSet, "data", "postgres"))
+        );
+        assert_eq!(parse_workload_id("nodes/worker-1"), None);
+        assert_eq!(parse_workload_id("deployments/shop"), None);
+    }
+}
@@ -1,3 +1,14 @@
 pub mod aws;
+pub mod azure;
+pub mod gcp;
+pub mod kubernetes;
+pub mod offline;
 
-pub use aws::AWSProvider;
\ No newline at end of file
+#[cfg(test)]
+pub(crate) mod fixtures;
+
+pub use aws::AWSProvider;
+pub use azure::AzureProvider;
+pub use gcp::GCPProvider;
+pub use kubernetes::KubernetesProvider;
+pub use offline::OfflineProvider;
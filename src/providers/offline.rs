@@ -0,0 +1,134 @@
+use async_trait::async_trait;
+
+use crate::core::{
+    ActionRequest, CloudProvider, CloudResource, CostBreakdown, CostPeriod, OperationHandle, Provider,
+    ProviderCapabilities, ResourceType,
+};
+use crate::error::{NimbusError, Result};
+
+/// Stands in for a configured provider when running from the cache.
+///
+/// It never authenticates or calls the cloud: it keeps the provider's name,
+/// type, and regions so cached resources can be shown under it, offers no
+/// actions or cost data, and fails anything that would need the network.
+pub struct OfflineProvider {
+    name: String,
+    account_name: String,
+    provider_type: Provider,
+    resource_types: Vec<ResourceType>,
+    metrics: bool,
+    regions: Vec<String>,
+    current_region: String,
+}
+
+impl OfflineProvider {
+    /// Describes an unauthenticated provider for offline use.
+    pub fn new(provider: &dyn CloudProvider) -> Self {
+        let capabilities = provider.capabilities();
+        Self {
+            name: provider.name().to_string(),
+            account_name: provider.account_name().to_string(),
+            provider_type: provider.provider_type(),
+            resource_types: capabilities.resource_types,
+            metrics: capabilities.metrics,
+            regions: provider.regions(),
+            current_region: provider.current_region().to_string(),
+        }
+    }
+
+    fn offline_error(&self) -> NimbusError {
+        NimbusError::provider(self.provider_type.as_str(), "not available in offline mode")
+    }
+}
+
+#[async_trait]
+impl CloudProvider for OfflineProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn provider_type(&self) -> Provider {
+        self.provider_type
+    }
+
+    fn account_name(&self) -> &str {
+        &self.account_name
+    }
+
+    async fn authenticate(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        // Metrics stay on so the detail view can show stored samples
+        ProviderCapabilities::none()
+            .with_resource_types(&self.resource_types)
+            .with_metrics(self.metrics)
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        Ok(false)
+    }
+
+    async fn list_all_resources(&self) -> Result<Vec<Box<dyn CloudResource>>> {
+        Err(self.offline_error())
+    }
+
+    async fn list_resources_by_type(&self, _resource_type: ResourceType) -> Result<Vec<Box<dyn CloudResource>>> {
+        Err(self.offline_error())
+    }
+
+    async fn get_resource(&self, _id: &str) -> Result<Box<dyn CloudResource>> {
+        Err(self.offline_error())
+    }
+
+    async fn execute_action(
+        &self,
+        _resource_id: &str,
+        _resource_type: ResourceType,
+        _request: &ActionRequest,
+    ) -> Result<OperationHandle> {
+        Err(self.offline_error())
+    }
+
+    async fn get_total_cost(&self, _period: CostPeriod) -> Result<f64> {
+        Err(self.offline_error())
+    }
+
+    async fn get_cost_breakdown(&self) -> Result<CostBreakdown> {
+        Err(self.offline_error())
+    }
+
+    fn regions(&self) -> Vec<String> {
+        self.regions.clone()
+    }
+
+    fn current_region(&self) -> &str {
+        &self.current_region
+    }
+
+    async fn set_region(&mut self, region: &str) -> Result<()> {
+        self.current_region = region.to_string();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::KubernetesConfig;
+    use crate::core::Action;
+    use crate::providers::KubernetesProvider;
+
+    #[tokio::test]
+    async fn test_offline_provider_offers_nothing_mutating() {
+        let provider = OfflineProvider::new(&KubernetesProvider::new(KubernetesConfig::default()));
+
+        assert_eq!(provider.provider_type(), Provider::Kubernetes);
+        let capabilities = provider.capabilities();
+        assert!(capabilities.supports_type(ResourceType::Container));
+        assert_eq!(capabilities.actions, vec![Action::ViewDetails]);
+        assert!(capabilities.cost_features.is_empty());
+        assert!(provider.list_all_resources().await.is_err());
+    }
+}
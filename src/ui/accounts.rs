@@ -0,0 +1,40 @@
+use ratatui::{
+    layout::Rect,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::ui::detail::centered_rect;
+use crate::ui::theme::Theme;
+
+pub fn render_account_picker(frame: &mut Frame, area: Rect, state: &AppState) {
+    let picker = match state.account_picker {
+        Some(ref picker) => picker,
+        None => return,
+    };
+    let popup_area = centered_rect(50, 50, area);
+
+    let items: Vec<ListItem> = picker
+        .choices
+        .iter()
+        .enumerate()
+        .map(|(idx, choice)| {
+            let prefix = if idx == picker.selected { "> " } else { "  " };
+            let source = if choice.detected { "profile" } else { "configured" };
+            let current = if choice.label == picker.current { ", current" } else { "" };
+            ListItem::new(format!("{}{} ({}{})", prefix, choice.label, source, current))
+        })
+        .collect();
+
+    let mut list_state = ListState::default().with_selected(Some(picker.selected));
+    let list = List::new(items).highlight_style(Theme::selected_row()).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Switch AWS account (↑↓ select, Enter switch, ESC cancel)")
+            .style(Theme::border()),
+    );
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(list, popup_area, &mut list_state);
+}
@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::cache::CacheStats;
+use crate::ui::detail::centered_rect;
+use crate::ui::theme::Theme;
+
+pub fn render_cache_stats(frame: &mut Frame, area: Rect, stats: &CacheStats) {
+    let popup_area = centered_rect(60, 60, area);
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Entries:  ", Theme::help_text()),
+            Span::raw(stats.total.to_string()),
+        ]),
+        Line::from(vec![
+            Span::styled("Size:     ", Theme::help_text()),
+            Span::raw(format_size(stats.size_bytes)),
+        ]),
+        Line::from(vec![
+            Span::styled("Oldest:   ", Theme::help_text()),
+            Span::raw(format_time(stats.oldest)),
+        ]),
+        Line::from(vec![
+            Span::styled("Newest:   ", Theme::help_text()),
+            Span::raw(format_time(stats.newest)),
+        ]),
+        Line::from(vec![
+            Span::styled("Expired:  ", Theme::help_text()),
+            Span::styled(
+                stats.expired.to_string(),
+                if stats.expired > 0 { Theme::warning() } else { Theme::success() },
+            ),
+        ]),
+        Line::from(""),
+    ];
+
+    if stats.by_type.is_empty() {
+        lines.push(Line::from(Span::styled("The cache is empty", Theme::help_text())));
+    }
+    for (provider, resource_type, count) in &stats.by_type {
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:>6}", count), Theme::success()),
+            Span::raw(format!("  {} {}", provider, resource_type)),
+        ]));
+    }
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Cache")
+            .style(Theme::border()),
+    );
+
+    frame.render_widget(ratatui::widgets::Clear, popup_area);
+    frame.render_widget(paragraph, popup_area);
+}
+
+fn format_time(time: Option<DateTime<Utc>>) -> String {
+    match time {
+        Some(time) => time.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        None => "-".to_string(),
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    let bytes = bytes as f64;
+    if bytes >= KIB * KIB {
+        format!("{:.1} MiB", bytes / (KIB * KIB))
+    } else if bytes >= KIB {
+        format!("{:.1} KiB", bytes / KIB)
+    } else {
+        format!("{} B", bytes)
+    }
+}
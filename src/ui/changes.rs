@@ -0,0 +1,102 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    text::Line,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::core::{ChangeKind, InventoryChange};
+use crate::ui::theme::{resource_icon, Theme};
+
+pub async fn render_changes_view(frame: &mut Frame<'_>, area: Rect, state: &AppState) {
+    let (since, changes) = match state.inventory_changes {
+        Some((since, ref changes)) if !changes.is_empty() => (since, changes),
+        ref other => {
+            let message = if state.loading {
+                "Comparing inventories..."
+            } else if other.is_some() {
+                "Nothing changed"
+            } else {
+                "Changes show up after the cache has recorded two refreshes"
+            };
+            let paragraph = Paragraph::new(vec![Line::from(""), Line::from(message)])
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("What Changed")
+                        .style(Theme::border()),
+                )
+                .style(Theme::help_text())
+                .alignment(ratatui::layout::Alignment::Center);
+            frame.render_widget(paragraph, area);
+            return;
+        }
+    };
+
+    let header_cells = ["Change", "Type", "Name", "ID", "Region", "State"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Theme::table_header()));
+    let header = Row::new(header_cells).height(1).style(Theme::table_header());
+
+    let rows: Vec<Row> = changes
+        .iter()
+        .map(|change| {
+            let entry = &change.entry;
+            Row::new(vec![
+                Cell::from(change.kind.as_str()).style(change_style(change)),
+                Cell::from(format!("{} {}", resource_icon(entry.resource_type), entry.resource_type.as_str())),
+                Cell::from(entry.name.clone()),
+                Cell::from(entry.id.clone()),
+                Cell::from(entry.region.clone()),
+                Cell::from(state_text(change)),
+            ])
+            .height(1)
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(14),
+        Constraint::Length(18),
+        Constraint::Min(20),
+        Constraint::Min(18),
+        Constraint::Length(12),
+        Constraint::Length(22),
+    ];
+
+    let count = |kind: ChangeKind| changes.iter().filter(|c| c.kind == kind).count();
+    let title = format!(
+        "What Changed since {} ({} created, {} terminated, {} changed state)",
+        since.format("%Y-%m-%d %H:%M UTC"),
+        count(ChangeKind::Created),
+        count(ChangeKind::Terminated),
+        count(ChangeKind::StateChanged),
+    );
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .style(Theme::border()),
+        )
+        .column_spacing(1);
+
+    frame.render_widget(table, area);
+}
+
+fn change_style(change: &InventoryChange) -> ratatui::style::Style {
+    match change.kind {
+        ChangeKind::Created => Theme::success(),
+        ChangeKind::Terminated => Theme::error(),
+        ChangeKind::StateChanged => Theme::warning(),
+    }
+}
+
+fn state_text(change: &InventoryChange) -> String {
+    match change.previous_state {
+        Some(previous) => format!("{} → {}", previous.as_str(), change.entry.state.as_str()),
+        None => change.entry.state.as_str().to_string(),
+    }
+}
@@ -0,0 +1,124 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::ui::theme::{resource_icon, Theme};
+
+pub async fn render_compliance_view(frame: &mut Frame<'_>, area: Rect, state: &AppState) {
+    let resources = match state.resources.try_read() {
+        Ok(resources) => resources,
+        Err(_) => return,
+    };
+
+    if state.tag_policy.is_empty() || resources.is_empty() {
+        let message = if state.tag_policy.is_empty() {
+            "No required tags configured. Set ui.required_tags in the config file."
+        } else {
+            "No resources loaded"
+        };
+        let paragraph = Paragraph::new(vec![Line::from(""), Line::from(message)])
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Tag Compliance")
+                    .style(Theme::border()),
+            )
+            .style(Theme::help_text())
+            .alignment(ratatui::layout::Alignment::Center);
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let violations: Vec<(usize, Vec<String>)> = resources
+        .iter()
+        .enumerate()
+        .map(|(idx, resource)| (idx, state.missing_tags(resource.as_ref())))
+        .filter(|(_, missing)| !missing.is_empty())
+        .collect();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(state.tag_policy.required.len() as u16 + 3),
+            Constraint::Min(5),
+        ])
+        .split(area);
+
+    let compliant = resources.len() - violations.len();
+    let mut summary = vec![Line::from(vec![
+        Span::styled(
+            format!("{} of {} resources compliant", compliant, resources.len()),
+            if violations.is_empty() { Theme::success() } else { Theme::warning() },
+        ),
+        Span::styled(
+            format!(" ({:.0}%)", compliant as f64 / resources.len() as f64 * 100.0),
+            Theme::help_text(),
+        ),
+    ])];
+    for key in &state.tag_policy.required {
+        let count = violations.iter().filter(|(_, missing)| missing.contains(key)).count();
+        let style = if count > 0 { Theme::error() } else { Theme::success() };
+        summary.push(Line::from(vec![
+            Span::styled(format!("{:>6}", count), style),
+            Span::raw(format!("  missing {}", key)),
+        ]));
+    }
+
+    let paragraph = Paragraph::new(summary).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Tag Compliance")
+            .style(Theme::border()),
+    );
+    frame.render_widget(paragraph, chunks[0]);
+
+    let header_cells = ["Type", "Name", "ID", "Region", "Missing Tags"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Theme::table_header()));
+    let header = Row::new(header_cells).height(1).style(Theme::table_header());
+
+    let rows: Vec<Row> = violations
+        .iter()
+        .filter_map(|(idx, missing)| {
+            resources.get(*idx).map(|resource| {
+                let type_display = format!(
+                    "{} {}",
+                    resource_icon(resource.resource_type()),
+                    resource.resource_type().as_str()
+                );
+                Row::new(vec![
+                    Cell::from(type_display),
+                    Cell::from(resource.name()),
+                    Cell::from(resource.id()),
+                    Cell::from(resource.region()),
+                    Cell::from(missing.join(", ")).style(Theme::error()),
+                ])
+                .height(1)
+            })
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(18),
+        Constraint::Min(20),
+        Constraint::Min(18),
+        Constraint::Length(12),
+        Constraint::Min(20),
+    ];
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Non-compliant Resources ({})", violations.len()))
+                .style(Theme::border()),
+        )
+        .column_spacing(1);
+
+    frame.render_widget(table, chunks[1]);
+}
@@ -5,7 +5,9 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::{AppState, ViewMode};
+use crate::app::keymap::KeymapPreset;
+use crate::app::{AppState, DetailTab, ViewMode};
+use crate::core::{Action, CostFeature};
 use crate::ui::theme::Theme;
 
 pub fn render_status_bar(frame: &mut Frame, area: Rect, state: &AppState) {
@@ -38,46 +40,220 @@ pub fn render_status_bar(frame: &mut Frame, area: Rect, state: &AppState) {
             ("Backspace", "Delete"),
             ("Enter", "Apply"),
         ]
+    } else if state.is_searching() {
+        vec![
+            ("Type", "to search"),
+            ("Enter", "Keep"),
+            ("ESC", "Cancel"),
+            ("n/N", "Next/Prev after Enter"),
+        ]
+    } else if state.is_prompting() {
+        vec![
+            ("Type", "to edit"),
+            ("Backspace", "Delete"),
+            ("Enter", "Submit"),
+            ("ESC", "Cancel"),
+        ]
+    } else if state.show_confirmation && !state.deletion_blockers.is_empty() {
+        vec![("ESC", "Close")]
+    } else if state.show_confirmation && state.needs_typed_confirmation() {
+        let mut shortcuts = vec![("Type", "to confirm"), ("Backspace", "Delete")];
+        if state.can_confirm() {
+            shortcuts.push(("Enter", "Confirm"));
+        }
+        shortcuts.push(("ESC", "Cancel"));
+        shortcuts
     } else if state.show_confirmation {
         vec![
             ("Enter", "Confirm"),
             ("ESC", "Cancel"),
         ]
+    } else if state.create_wizard.is_some() {
+        vec![
+            ("↑↓", "Select"),
+            ("Enter", "Next"),
+            ("Backspace", "Back"),
+            ("ESC", "Cancel"),
+        ]
+    } else if state.period_input.is_some() {
+        vec![("Enter", "Apply"), ("ESC", "Back")]
+    } else if state.show_period_picker {
+        vec![
+            ("↑↓", "Select"),
+            ("Enter", "Choose"),
+            ("ESC", "Cancel"),
+        ]
+    } else if state.show_help {
+        vec![
+            ("↑↓", "Scroll"),
+            ("PgUp/PgDn", "Page"),
+            ("ESC", "Close"),
+        ]
+    } else if state.cache_stats.is_some() {
+        vec![("p", "Prune Expired"), ("ESC", "Close")]
+    } else if state.show_bulk_picker {
+        vec![
+            ("↑↓", "Select"),
+            ("Enter", "Choose"),
+            ("ESC", "Cancel"),
+        ]
+    } else if state.show_column_picker {
+        vec![
+            ("↑↓", "Select"),
+            ("Space", "Show/Hide"),
+            ("[ ]", "Move"),
+            ("ESC", "Close"),
+        ]
+    } else if state.show_context_picker || state.region_picker.is_some() || state.account_picker.is_some() {
+        vec![
+            ("↑↓", "Select"),
+            ("Enter", "Switch"),
+            ("ESC", "Cancel"),
+        ]
     } else {
         match state.view_mode {
             ViewMode::Dashboard => {
-                vec![
+                let mut shortcuts = vec![
                     ("q", "Quit"),
+                    ("?", "Help"),
+                    ("m", "Notifications"),
                     ("Tab", "Next Tab"),
                     ("1-4", "Jump to Tab"),
                     ("r", "Refresh"),
                     ("d", "View List"),
+                    ("n", "New Instance"),
+                    ("p", "Period"),
+                    ("u", "Quotas"),
+                    ("w", "Waste"),
+                    ("t", "Tag Compliance"),
+                    ("y", "Changes"),
+                    ("h", "History"),
                     ("c", "Clear Cache"), // CHANGES: Added cache clear shortcut
+                    ("C", "Cache Stats"),
+                    ("x", "Context"),
+                    ("R", "Region"),
+                    ("P", "Account"),
+                ];
+                shortcuts.retain(|(key, _)| match *key {
+                    "n" => state.any_provider_supports(Action::Create),
+                    "x" => state.contexts.len() > 1,
+                    "p" => state.supports_cost(CostFeature::History),
+                    "t" => !state.tag_policy.is_empty(),
+                    "y" => state.cache_enabled,
+                    "u" | "w" | "c" | "R" | "P" => !state.offline,
+                    _ => true,
+                });
+                shortcuts
+            }
+            ViewMode::ResourceList if !state.marked_resources.is_empty() => {
+                vec![
+                    ("↑↓", "Navigate"),
+                    ("Space", "Mark"),
+                    ("a", "Mark All"),
+                    ("Enter", "Bulk Action"),
+                    ("ESC", "Clear Marks"),
                 ]
             }
             ViewMode::ResourceList => {
-                vec![
+                let mut shortcuts = vec![
                     ("q", "Quit"),
+                    ("?", "Help"),
+                    ("m", "Notifications"),
                     ("Tab", "Next Tab"),
                     ("r", "Refresh"),
                     ("d", "Dashboard"),
                     ("/", "Filter"),
+                    ("n", "New"),
                     ("↑↓", "Navigate"),
+                    ("PgUp/PgDn", "Page"),
+                    ("Space", "Mark"),
+                    ("a", "Mark All"),
                     ("Enter", "Details"),
+                    ("g", "Group"),
+                    ("v", "Columns"),
+                    ("o", "Console"),
                     ("c", "Clear Cache"), // CHANGES: Added cache clear shortcut
+                    ("C", "Cache Stats"),
+                    ("x", "Context"),
+                    ("R", "Region"),
+                    ("P", "Account"),
+                ];
+                if !state.any_provider_supports(Action::Create) {
+                    shortcuts.retain(|(key, _)| *key != "n");
+                }
+                if state.contexts.len() < 2 {
+                    shortcuts.retain(|(key, _)| *key != "x");
+                }
+                if state.offline {
+                    shortcuts.retain(|(key, _)| !matches!(*key, "c" | "R" | "P"));
+                }
+                shortcuts
+            }
+            ViewMode::ResourceDetail if state.detail_tab == DetailTab::Logs => {
+                vec![
+                    ("q", "Quit"),
+                    ("←→", "Tabs"),
+                    ("f", "Follow"),
+                    ("↑↓", "Scroll"),
+                    ("PgUp/PgDn", "Page"),
+                    ("r", "Reload"),
+                    ("Enter", "Execute"),
+                    ("ESC", "Back to List"),
                 ]
             }
             ViewMode::ResourceDetail => {
-                vec![
+                let mut shortcuts = vec![
                     ("q", "Quit"),
-                    ("↑↓", "Select Action"),
+                    ("?", "Help"),
+                    ("m", "Notifications"),
+                    ("←→", "Tabs"),
+                    ("Tab", "Focus"),
+                    ("r", "Refresh"),
+                    ("↑↓", "Select/Scroll"),
                     ("Enter", "Execute"),
+                    ("o", "Console"),
                     ("ESC", "Back to List"),
+                ];
+                if state.detail_tab != DetailTab::Overview {
+                    shortcuts.retain(|(key, _)| *key != "Tab");
+                }
+                shortcuts
+            }
+            ViewMode::Records => {
+                vec![
+                    ("q", "Quit"),
+                    ("←→", "Page"),
+                    ("r", "Reload"),
+                    ("ESC", "Back to Details"),
+                ]
+            }
+            ViewMode::Quotas | ViewMode::Waste | ViewMode::Changes | ViewMode::History => {
+                vec![
+                    ("q", "Quit"),
+                    ("r", "Reload"),
+                    ("ESC", "Back to Dashboard"),
                 ]
             }
+            ViewMode::Compliance => {
+                vec![("q", "Quit"), ("ESC", "Back to Dashboard")]
+            }
         }
     };
 
+    // The vim preset moves these keys to make room for its own
+    let shortcuts: Vec<(&str, &str)> = match state.keymap {
+        KeymapPreset::Default => shortcuts,
+        KeymapPreset::Vim => shortcuts
+            .into_iter()
+            .map(|(key, desc)| match key {
+                "/" => ("F", desc),
+                "g" => ("z", desc),
+                "n" => ("i", desc),
+                _ => (key, desc),
+            })
+            .collect(),
+    };
+
     let mut spans: Vec<Span> = shortcuts
         .iter()
         .flat_map(|(key, desc)| {
@@ -90,6 +266,27 @@ pub fn render_status_bar(frame: &mut Frame, area: Rect, state: &AppState) {
         })
         .collect();
 
+    if state.offline {
+        spans.push(Span::styled(" | ", Theme::help_text()));
+        spans.push(Span::styled("OFFLINE", Theme::warning()));
+    }
+
+    if let Some(ref context) = state.active_context {
+        spans.push(Span::styled(" | ", Theme::help_text()));
+        spans.push(Span::styled("Context: ", Theme::help_text()));
+        spans.push(Span::styled(context.as_str(), Theme::help_key()));
+    }
+
+    // Stays up until a refresh finds the limits respected again
+    if let Some(alert) = state.cost_alerts.first() {
+        let text = match state.cost_alerts.len() {
+            1 => format!("⚠ {}", alert.message()),
+            count => format!("⚠ {} cost alerts: {}", count, alert.message()),
+        };
+        spans.push(Span::styled(" | ", Theme::help_text()));
+        spans.push(Span::styled(text, Theme::warning()));
+    }
+
     // CHANGES: Added cache age display
     if let Some(cache_age) = state.cache_age_display() {
         spans.push(Span::styled(" | ", Theme::help_text()));
@@ -97,16 +294,32 @@ pub fn render_status_bar(frame: &mut Frame, area: Rect, state: &AppState) {
         spans.push(Span::styled(cache_age, Theme::cache_age()));
     }
 
-    if let Some(ref last_action) = state.last_action {
-        if let Some(ref last_time) = state.last_action_time {
-            let time_str = last_time.format("%H:%M:%S").to_string();
-            spans.push(Span::styled(" | ", Theme::help_text()));
-            spans.push(Span::styled("Last: ", Theme::help_text()));
-            spans.push(Span::styled(last_action, Theme::help_key()));
-            spans.push(Span::styled(" (", Theme::help_text()));
-            spans.push(Span::styled(time_str, Theme::help_text()));
-            spans.push(Span::styled(")", Theme::help_text()));
-        }
+    for job in &state.image_jobs {
+        spans.push(Span::styled(" | ", Theme::help_text()));
+        spans.push(Span::styled(format!("{} ", job.image_id), Theme::help_key()));
+        spans.push(Span::styled(format!("({}): {}", job.name, job.state), Theme::spinner()));
+    }
+
+    let now = chrono::Utc::now();
+    for job in &state.operations {
+        spans.push(Span::styled(" | ", Theme::help_text()));
+        spans.push(Span::styled(format!("{} ", job.resource_name), Theme::help_key()));
+        spans.push(Span::styled(job.handle.progress_label(now), Theme::spinner()));
+    }
+
+    if let Some(last_action) = state.last_action() {
+        let time_str = last_action.recorded_at.format("%H:%M:%S").to_string();
+        let style = if last_action.is_failure() {
+            Theme::error()
+        } else {
+            Theme::help_key()
+        };
+        spans.push(Span::styled(" | ", Theme::help_text()));
+        spans.push(Span::styled("Last: ", Theme::help_text()));
+        spans.push(Span::styled(last_action.message.as_str(), style));
+        spans.push(Span::styled(" (", Theme::help_text()));
+        spans.push(Span::styled(time_str, Theme::help_text()));
+        spans.push(Span::styled(")", Theme::help_text()));
     }
 
     let status_line = Line::from(spans);
@@ -0,0 +1,43 @@
+use ratatui::{
+    layout::Rect,
+    widgets::{Block, Borders, List, ListItem},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::ui::detail::centered_rect;
+use crate::ui::theme::Theme;
+
+pub fn render_context_picker(frame: &mut Frame, area: Rect, state: &AppState) {
+    let popup_area = centered_rect(40, 40, area);
+
+    let items: Vec<ListItem> = state
+        .contexts
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| {
+            let prefix = if idx == state.selected_context { "> " } else { "  " };
+            let active = state.active_context.as_deref() == Some(name.as_str());
+            let label = if active {
+                format!("{}{} (active)", prefix, name)
+            } else {
+                format!("{}{}", prefix, name)
+            };
+            if idx == state.selected_context {
+                ListItem::new(label).style(Theme::selected_row())
+            } else {
+                ListItem::new(label)
+            }
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Switch context (↑↓ select, Enter switch, ESC cancel)")
+            .style(Theme::border()),
+    );
+
+    frame.render_widget(ratatui::widgets::Clear, popup_area);
+    frame.render_widget(list, popup_area);
+}
@@ -0,0 +1,115 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::{AppState, CreateStep, CreateWizard};
+use crate::ui::detail::{centered_rect, render_prompt};
+use crate::ui::theme::Theme;
+
+pub fn render_create_wizard(frame: &mut Frame, area: Rect, state: &AppState) {
+    let wizard = match state.create_wizard {
+        Some(ref wizard) => wizard,
+        None => return,
+    };
+
+    let popup_area = centered_rect(70, 60, area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(4), Constraint::Length(3)])
+        .split(popup_area);
+
+    frame.render_widget(ratatui::widgets::Clear, popup_area);
+    match wizard.step {
+        CreateStep::Image | CreateStep::InstanceType | CreateStep::KeyPair => {
+            render_choices(frame, chunks[0], wizard)
+        }
+        CreateStep::Name | CreateStep::Tags | CreateStep::Review => render_review(frame, chunks[0], wizard),
+    }
+    render_help(frame, chunks[1], wizard);
+
+    if state.is_prompting() {
+        render_prompt(frame, centered_rect(60, 20, area), state);
+    }
+}
+
+fn render_choices(frame: &mut Frame, area: Rect, wizard: &CreateWizard) {
+    let items: Vec<ListItem> = wizard
+        .choices()
+        .iter()
+        .enumerate()
+        .map(|(idx, option)| {
+            let selected = idx == wizard.selected;
+            let prefix = if selected { "> " } else { "  " };
+            let cost = match option.monthly_cost {
+                Some(cost) => format!("  ${:.2}/mo", cost),
+                None => String::new(),
+            };
+            let style = if selected {
+                Theme::selected_row()
+            } else {
+                Style::default()
+            };
+
+            ListItem::new(format!("{}{}{}", prefix, option.label, cost)).style(style)
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Create Instance — {}", wizard.step.title()))
+            .style(Theme::border()),
+    );
+
+    frame.render_widget(list, area);
+}
+
+fn render_review(frame: &mut Frame, area: Rect, wizard: &CreateWizard) {
+    let lines = wizard.review_lines();
+    let cost_line = lines.len().saturating_sub(1);
+    let text: Vec<Line> = lines
+        .into_iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            if idx == cost_line {
+                Line::from(Span::styled(line, Theme::warning()))
+            } else {
+                Line::from(Span::styled(line, Theme::title()))
+            }
+        })
+        .collect();
+
+    let review = Paragraph::new(text).wrap(Wrap { trim: true }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Create Instance — {}", wizard.step.title()))
+            .style(Theme::border()),
+    );
+
+    frame.render_widget(review, area);
+}
+
+fn render_help(frame: &mut Frame, area: Rect, wizard: &CreateWizard) {
+    let enter = if wizard.step == CreateStep::Review {
+        ": Launch  "
+    } else {
+        ": Next  "
+    };
+
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("Enter", Theme::help_key()),
+        Span::raw(enter),
+        Span::styled("Backspace", Theme::help_key()),
+        Span::raw(": Back  "),
+        Span::styled("ESC", Theme::help_key()),
+        Span::raw(": Cancel"),
+    ]))
+    .alignment(ratatui::layout::Alignment::Center)
+    .block(Block::default().borders(Borders::ALL));
+
+    frame.render_widget(help, area);
+}
@@ -2,18 +2,27 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Row, Table},
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, LineGauge, List, ListItem, Paragraph, Row, Sparkline, Table},
     Frame,
 };
 
 use crate::app::AppState;
-use crate::core::{ResourceState, ResourceType};
+use crate::core::{
+    Budget, CommitmentCoverage, CostAlert, CostAnomaly, CostForecast, CostPeriod, CostPoint, CostSnapshot, ResourceState,
+    ResourceType,
+};
+use crate::ui::detail::centered_rect;
+use crate::ui::refresh_progress::render_refresh_progress;
 use crate::ui::theme::Theme;
 use std::collections::HashMap;
 
 pub async fn render_dashboard(frame: &mut Frame<'_>, area: Rect, state: &AppState) {
     if state.loading {
-        render_loading_dashboard(frame, area);
+        if state.is_refreshing() {
+            render_refresh_progress(frame, area, state);
+        } else {
+            render_loading_dashboard(frame, area);
+        }
         return;
     }
 
@@ -34,29 +43,119 @@ pub async fn render_dashboard(frame: &mut Frame<'_>, area: Rect, state: &AppStat
         return;
     }
 
-    let stats = calculate_dashboard_stats(&resources);
+    let mut stats = calculate_dashboard_stats(&resources);
+    stats.trend_percentage = CostSnapshot::trend_percentage(&state.cost_snapshots);
 
-    let chunks = Layout::default()
+    // Budgets get a row of their own, one gauge per budget
+    let budget_height = if state.budgets.is_empty() {
+        0
+    } else {
+        state.budgets.len().min(MAX_BUDGET_ROWS) as u16 + 2
+    };
+
+    let anomaly_height = if state.anomalies.is_empty() {
+        0
+    } else {
+        state.anomalies.len().min(MAX_ANOMALY_ROWS) as u16 + 2
+    };
+
+    let alert_height = if state.cost_alerts.is_empty() {
+        0
+    } else {
+        state.cost_alerts.len().min(MAX_ALERT_ROWS) as u16 + 2
+    };
+
+    let rows = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(7),
+            Constraint::Length(alert_height),
+            Constraint::Length(anomaly_height),
+            Constraint::Length(budget_height),
             Constraint::Min(10),
             Constraint::Min(8),
         ])
         .split(area);
+    let chunks = [rows[0], rows[4], rows[5]];
+
+    if !state.cost_alerts.is_empty() {
+        render_cost_alerts(frame, rows[1], &state.cost_alerts);
+    }
+    if !state.anomalies.is_empty() {
+        render_anomalies(frame, rows[2], &state.anomalies);
+    }
+    if !state.budgets.is_empty() {
+        render_budgets(frame, rows[3], &state.budgets);
+    }
+
+    // The overview takes the row alone, or shares it with whichever spend
+    // charts have data
+    let show_daily_spend = !state.cost_history.is_empty();
+    let show_spend_history = state.cost_snapshots.len() >= 2;
+    let constraints = match show_daily_spend as u8 + show_spend_history as u8 {
+        0 => vec![Constraint::Percentage(100)],
+        1 => vec![Constraint::Percentage(55), Constraint::Percentage(45)],
+        _ => vec![
+            Constraint::Percentage(40),
+            Constraint::Percentage(30),
+            Constraint::Percentage(30),
+        ],
+    };
+    let summary_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(constraints)
+        .split(chunks[0]);
+    render_cost_summary(frame, summary_chunks[0], &stats, &state.cost_snapshots, state.cost_forecast);
+    let mut chart_areas = summary_chunks.iter().skip(1);
+    if let (true, Some(area)) = (show_daily_spend, chart_areas.next()) {
+        render_daily_spend(frame, *area, &state.cost_history, state.cost_period);
+    }
+    if let (true, Some(area)) = (show_spend_history, chart_areas.next()) {
+        render_spend_history(frame, *area, &state.cost_snapshots);
+    }
+    if state.commitments.is_empty() {
+        render_resource_breakdown(frame, chunks[1], &stats);
+    } else {
+        let middle_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
+            .split(chunks[1]);
+        render_resource_breakdown(frame, middle_chunks[0], &stats);
+        render_commitments(frame, middle_chunks[1], &state.commitments);
+    }
+    if state.cost_by_tag.is_empty() {
+        render_top_resources(frame, chunks[2], &stats);
+    } else {
+        let bottom_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(chunks[2]);
+        render_top_resources(frame, bottom_chunks[0], &stats);
+        render_cost_by_tag(frame, bottom_chunks[1], &state.cost_by_tag);
+    }
 
-    render_cost_summary(frame, chunks[0], &stats);
-    render_resource_breakdown(frame, chunks[1], &stats);
-    render_top_resources(frame, chunks[2], &stats);
+    if state.show_period_picker {
+        render_period_picker(frame, centered_rect(40, 40, area), state);
+    }
 }
 
+/// Most budgets shown on the dashboard before the rest are cut off.
+const MAX_BUDGET_ROWS: usize = 5;
+/// Most cost anomalies shown on the dashboard.
+const MAX_ANOMALY_ROWS: usize = 3;
+/// Most cost alerts shown on the dashboard.
+const MAX_ALERT_ROWS: usize = 4;
+
 struct DashboardStats {
     total_cost: f64,
-    trend_percentage: f64,
+    /// Change in estimated monthly cost across the stored snapshots
+    trend_percentage: Option<f64>,
     total_resources: usize,
     by_type: HashMap<ResourceType, TypeStats>,
     by_region: HashMap<String, RegionStats>,
     top_expensive: Vec<(String, String, f64)>,
+    /// Names of resources open to the public internet.
+    public_resources: Vec<String>,
 }
 
 struct TypeStats {
@@ -76,6 +175,7 @@ fn calculate_dashboard_stats(resources: &[Box<dyn crate::core::CloudResource>])
     let mut by_region: HashMap<String, RegionStats> = HashMap::new();
     let mut total_cost = 0.0;
     let mut expensive_resources: Vec<(String, String, f64)> = Vec::new();
+    let mut public_resources = Vec::new();
 
     for resource in resources {
         let cost = resource.cost_per_month().unwrap_or(0.0);
@@ -105,6 +205,10 @@ fn calculate_dashboard_stats(resources: &[Box<dyn crate::core::CloudResource>])
         region_stats.count += 1;
         region_stats.total_cost += cost;
 
+        if resource.is_publicly_accessible() {
+            public_resources.push(resource.name().to_string());
+        }
+
         if cost > 0.0 {
             expensive_resources.push((
                 resource.name().to_string(),
@@ -119,33 +223,31 @@ fn calculate_dashboard_stats(resources: &[Box<dyn crate::core::CloudResource>])
 
     DashboardStats {
         total_cost,
-        trend_percentage: 0.0,
+        trend_percentage: None,
         total_resources: resources.len(),
         by_type,
         by_region,
         top_expensive: expensive_resources,
+        public_resources,
     }
 }
 
-fn render_cost_summary(frame: &mut Frame, area: Rect, stats: &DashboardStats) {
-    let trend_indicator = if stats.trend_percentage > 0.0 {
-        format!("↑ {:.1}%", stats.trend_percentage)
-    } else if stats.trend_percentage < 0.0 {
-        format!("↓ {:.1}%", stats.trend_percentage.abs())
-    } else {
-        "→ 0%".to_string()
+fn render_cost_summary(
+    frame: &mut Frame,
+    area: Rect,
+    stats: &DashboardStats,
+    snapshots: &[CostSnapshot],
+    forecast: Option<CostForecast>,
+) {
+    let (trend_indicator, trend_style) = match stats.trend_percentage {
+        Some(trend) if trend > 0.0 => (format!("↑ {:.1}%", trend), Theme::warning()),
+        Some(trend) if trend < 0.0 => (format!("↓ {:.1}%", trend.abs()), Theme::success()),
+        Some(_) => ("→ 0%".to_string(), Theme::help_text()),
+        None => (String::new(), Theme::help_text()),
     };
 
-    let trend_style = if stats.trend_percentage > 0.0 {
-        Theme::warning()
-    } else if stats.trend_percentage < 0.0 {
-        Theme::success()
-    } else {
-        Theme::help_text()
-    };
-
-    let lines = vec![
-        Line::from(""),
+    let mut lines = vec![
+        trend_line(snapshots),
         Line::from(vec![
             Span::styled("Monthly Cost: ", Theme::help_text()),
             Span::styled(
@@ -161,7 +263,7 @@ fn render_cost_summary(frame: &mut Frame, area: Rect, stats: &DashboardStats) {
                 Theme::title(),
             ),
         ]),
-        Line::from(""),
+        forecast_line(forecast),
         Line::from(vec![
             Span::styled("Regions: ", Theme::help_text()),
             Span::raw(format!("{}  ", stats.by_region.len())),
@@ -170,6 +272,17 @@ fn render_cost_summary(frame: &mut Frame, area: Rect, stats: &DashboardStats) {
         ]),
     ];
 
+    if !stats.public_resources.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "⚠ Publicly accessible: {} ({})",
+                stats.public_resources.len(),
+                stats.public_resources.join(", ")
+            ),
+            Theme::warning(),
+        )));
+    }
+
     let paragraph = Paragraph::new(lines)
         .block(
             Block::default()
@@ -182,14 +295,368 @@ fn render_cost_summary(frame: &mut Frame, area: Rect, stats: &DashboardStats) {
     frame.render_widget(paragraph, area);
 }
 
+/// Block characters used to draw the spend trend inline, lowest first.
+const SPARK_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Charts the estimated monthly cost of each stored day, or a blank line
+/// until there are two days to compare.
+fn trend_line(snapshots: &[CostSnapshot]) -> Line<'static> {
+    if snapshots.len() < 2 {
+        return Line::from("");
+    }
+
+    let totals: Vec<f64> = snapshots.iter().map(|s| s.total).collect();
+    Line::from(vec![
+        Span::styled(format!("Trend ({}d): ", snapshots.len()), Theme::help_text()),
+        Span::styled(spark_text(&totals), Theme::success()),
+    ])
+}
+
+/// Scales values between their minimum and maximum onto `SPARK_BARS`.
+fn spark_text(values: &[f64]) -> String {
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+
+    values
+        .iter()
+        .map(|value| {
+            let level = if range > 0.0 {
+                ((value - min) / range * (SPARK_BARS.len() - 1) as f64).round() as usize
+            } else {
+                SPARK_BARS.len() / 2
+            };
+            SPARK_BARS[level.min(SPARK_BARS.len() - 1)]
+        })
+        .collect()
+}
+
+/// Shows the projected month-end spend and its range, or a blank line when
+/// no provider forecasts costs.
+fn forecast_line(forecast: Option<CostForecast>) -> Line<'static> {
+    match forecast {
+        Some(forecast) => Line::from(vec![
+            Span::styled("Projected: ", Theme::help_text()),
+            Span::styled(format!("${:.2}", forecast.projected_total), Theme::title()),
+            Span::raw(" by month end "),
+            Span::styled(
+                format!("(${:.0}–${:.0})", forecast.lower_bound, forecast.upper_bound),
+                Theme::help_text(),
+            ),
+        ]),
+        None => Line::from(""),
+    }
+}
+
+/// Charts the stored daily estimates of monthly spend, naming the lowest
+/// and highest days in the title.
+fn render_spend_history(frame: &mut Frame, area: Rect, snapshots: &[CostSnapshot]) {
+    let (min, max) = match CostSnapshot::extremes(snapshots) {
+        Some(extremes) => extremes,
+        None => return,
+    };
+
+    // Plot cents above the cheapest day, plus one so it still gets a bar;
+    // day-to-day changes are small next to the total and would look flat
+    let data: Vec<u64> = snapshots
+        .iter()
+        .map(|snapshot| ((snapshot.total - min.total) * 100.0).round() as u64 + 1)
+        .collect();
+
+    let title = format!(
+        "Spend, {}d (min ${:.0} {}, max ${:.0} {})",
+        snapshots.len(),
+        min.total,
+        min.recorded_at.format("%b %d"),
+        max.total,
+        max.recorded_at.format("%b %d")
+    );
+
+    let sparkline = Sparkline::default()
+        .block(Block::default().borders(Borders::ALL).title(title).style(Theme::border()))
+        .data(&data)
+        .style(Theme::success());
+
+    frame.render_widget(sparkline, area);
+}
+
+fn render_daily_spend(frame: &mut Frame, area: Rect, history: &[CostPoint], period: CostPeriod) {
+    // Sparklines take integers, so plot whole cents
+    let data: Vec<u64> = history
+        .iter()
+        .map(|point| (point.amount.max(0.0) * 100.0).round() as u64)
+        .collect();
+
+    let total: f64 = history.iter().map(|point| point.amount).sum();
+    let latest = history.last().map(|point| point.amount).unwrap_or(0.0);
+
+    let sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(
+                    "Daily Spend ({}: ${:.2}, latest ${:.2})",
+                    period.label(),
+                    total,
+                    latest
+                ))
+                .style(Theme::border()),
+        )
+        .data(&data)
+        .style(Theme::success());
+
+    frame.render_widget(sparkline, area);
+}
+
+fn render_period_picker(frame: &mut Frame, area: Rect, state: &AppState) {
+    let presets = CostPeriod::presets();
+    let mut items: Vec<ListItem> = presets
+        .iter()
+        .map(|period| period.label())
+        .chain(std::iter::once("Custom range…".to_string()))
+        .enumerate()
+        .map(|(idx, label)| {
+            let selected = idx == state.selected_period;
+            let prefix = if selected { "> " } else { "  " };
+            let style = if selected {
+                Theme::selected_row()
+            } else {
+                Style::default()
+            };
+            ListItem::new(format!("{}{}", prefix, label)).style(style)
+        })
+        .collect();
+
+    if let Some(ref input) = state.period_input {
+        items.push(ListItem::new(""));
+        items.push(ListItem::new(format!("From YYYY-MM-DD to YYYY-MM-DD: {}█", input)).style(Theme::warning()));
+    }
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Cost Period")
+            .style(Theme::border()),
+    );
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(list, area);
+}
+
+fn render_cost_alerts(frame: &mut Frame, area: Rect, alerts: &[CostAlert]) {
+    let lines: Vec<Line> = alerts
+        .iter()
+        .take(MAX_ALERT_ROWS)
+        .map(|alert| {
+            let style = match alert {
+                CostAlert::BudgetExceeded { forecast: false, .. } => Theme::error(),
+                _ => Theme::warning(),
+            };
+            Line::from(vec![
+                Span::styled("⚠ ", Theme::warning()),
+                Span::styled(alert.message(), style),
+            ])
+        })
+        .collect();
+
+    let title = if alerts.len() > MAX_ALERT_ROWS {
+        format!("Cost Alerts ({} more)", alerts.len() - MAX_ALERT_ROWS)
+    } else {
+        "Cost Alerts".to_string()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .style(Theme::border()),
+    );
+
+    frame.render_widget(paragraph, area);
+}
+
+fn render_anomalies(frame: &mut Frame, area: Rect, anomalies: &[CostAnomaly]) {
+    let lines: Vec<Line> = anomalies
+        .iter()
+        .take(MAX_ANOMALY_ROWS)
+        .map(|anomaly| {
+            let since = anomaly
+                .start
+                .map(|start| format!(" since {}", start.format("%Y-%m-%d")))
+                .unwrap_or_default();
+            let cause = anomaly
+                .root_cause
+                .as_ref()
+                .map(|cause| format!("  ({})", cause))
+                .unwrap_or_default();
+
+            Line::from(vec![
+                Span::styled("⚠ ", Theme::warning()),
+                Span::styled(format!("${:.2}", anomaly.impact), Theme::error()),
+                Span::raw(format!(" {}{}", anomaly.service, since)),
+                Span::styled(cause, Theme::help_text()),
+            ])
+        })
+        .collect();
+
+    let title = if anomalies.len() > MAX_ANOMALY_ROWS {
+        format!("Cost Anomalies ({} more)", anomalies.len() - MAX_ANOMALY_ROWS)
+    } else {
+        "Cost Anomalies".to_string()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .style(Theme::border()),
+    );
+
+    frame.render_widget(paragraph, area);
+}
+
+fn render_budgets(frame: &mut Frame, area: Rect, budgets: &[Budget]) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Budgets")
+        .style(Theme::border());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let shown = &budgets[..budgets.len().min(MAX_BUDGET_ROWS)];
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(shown.iter().map(|_| Constraint::Length(1)).collect::<Vec<_>>())
+        .split(inner);
+
+    for (budget, row) in shown.iter().zip(rows.iter()) {
+        let style = if budget.is_exceeded() {
+            Theme::error()
+        } else if budget.forecast_exceeds() {
+            Theme::warning()
+        } else {
+            Theme::success()
+        };
+
+        let forecast = match budget.forecast {
+            Some(forecast) => format!(", forecast ${:.0}", forecast),
+            None => String::new(),
+        };
+
+        let gauge = LineGauge::default()
+            .label(format!(
+                "{}: ${:.0} / ${:.0}{}",
+                budget.name, budget.actual, budget.limit, forecast
+            ))
+            .ratio(budget.used_ratio())
+            .gauge_style(style);
+
+        frame.render_widget(gauge, *row);
+    }
+}
+
+fn render_commitments(frame: &mut Frame, area: Rect, commitments: &[(String, CommitmentCoverage)]) {
+    let percentage = |value: Option<f64>| match value {
+        Some(pct) => format!("{:.1}%", pct),
+        None => "-".to_string(),
+    };
+
+    let mut lines = Vec::new();
+    for (provider_name, coverage) in commitments {
+        if commitments.len() > 1 {
+            lines.push(Line::from(Span::styled(provider_name.as_str(), Theme::title())));
+        }
+        lines.push(Line::from(vec![
+            Span::styled("RI  ", Theme::help_text()),
+            Span::raw(format!(
+                "coverage {}  used {}",
+                percentage(coverage.reservation_coverage),
+                percentage(coverage.reservation_utilization)
+            )),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("SP  ", Theme::help_text()),
+            Span::raw(format!(
+                "coverage {}  used {}",
+                percentage(coverage.savings_plans_coverage),
+                percentage(coverage.savings_plans_utilization)
+            )),
+        ]));
+        for commitment in &coverage.expiring {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "Expires {}: {} {}",
+                    commitment.expires.format("%Y-%m-%d"),
+                    commitment.kind,
+                    commitment.description
+                ),
+                Theme::warning(),
+            )));
+        }
+    }
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Commitments")
+            .style(Theme::border()),
+    );
+
+    frame.render_widget(paragraph, area);
+}
+
 fn render_resource_breakdown(frame: &mut Frame, area: Rect, stats: &DashboardStats) {
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .constraints([
+            Constraint::Percentage(30),
+            Constraint::Percentage(40),
+            Constraint::Percentage(30),
+        ])
         .split(area);
 
-    render_type_breakdown(frame, chunks[0], stats);
-    render_region_breakdown(frame, chunks[1], stats);
+    render_cost_chart(frame, chunks[0], stats);
+    render_type_breakdown(frame, chunks[1], stats);
+    render_region_breakdown(frame, chunks[2], stats);
+}
+
+/// Draws a horizontal bar per resource type with a monthly cost, costliest
+/// first.
+fn render_cost_chart(frame: &mut Frame, area: Rect, stats: &DashboardStats) {
+    let mut costs: Vec<(&ResourceType, f64)> = stats
+        .by_type
+        .iter()
+        .map(|(resource_type, type_stats)| (resource_type, type_stats.total_cost))
+        .filter(|(_, cost)| *cost > 0.0)
+        .collect();
+    costs.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    // Bars take integers, so plot whole dollars
+    let bars: Vec<Bar> = costs
+        .iter()
+        .map(|(resource_type, cost)| {
+            Bar::default()
+                .label(Line::from(resource_type.as_str()))
+                .value(cost.round() as u64)
+                .text_value(format!("${:.0}", cost))
+        })
+        .collect();
+
+    let chart = BarChart::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Cost by Type")
+                .style(Theme::border()),
+        )
+        .direction(Direction::Horizontal)
+        .bar_width(1)
+        .bar_gap(0)
+        .bar_style(Theme::success())
+        .value_style(Theme::help_key())
+        .data(BarGroup::default().bars(&bars));
+
+    frame.render_widget(chart, area);
 }
 
 fn render_type_breakdown(frame: &mut Frame, area: Rect, stats: &DashboardStats) {
@@ -276,6 +743,45 @@ fn render_region_breakdown(frame: &mut Frame, area: Rect, stats: &DashboardStats
     frame.render_widget(table, area);
 }
 
+fn render_cost_by_tag(frame: &mut Frame, area: Rect, cost_by_tag: &[(String, Vec<(String, f64)>)]) {
+    let header_cells = ["Tag", "Value", "This Month"]
+        .iter()
+        .map(|h| ratatui::widgets::Cell::from(*h).style(Theme::table_header()));
+    let header = Row::new(header_cells).height(1).style(Theme::table_header());
+
+    let rows: Vec<Row> = cost_by_tag
+        .iter()
+        .flat_map(|(tag_key, values)| {
+            values.iter().map(move |(value, cost)| {
+                let cells = vec![
+                    ratatui::widgets::Cell::from(tag_key.as_str()),
+                    ratatui::widgets::Cell::from(value.as_str()),
+                    ratatui::widgets::Cell::from(format!("${:.2}", cost)),
+                ];
+                Row::new(cells).height(1)
+            })
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(12),
+        Constraint::Min(15),
+        Constraint::Length(12),
+    ];
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Cost by Tag")
+                .style(Theme::border()),
+        )
+        .column_spacing(1);
+
+    frame.render_widget(table, area);
+}
+
 fn render_top_resources(frame: &mut Frame, area: Rect, stats: &DashboardStats) {
     let header_cells = ["#", "Name", "Type", "Monthly Cost"]
         .iter()
@@ -2,17 +2,33 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::Style,
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Row, Table, Wrap},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Row, Sparkline, Table, Tabs, Wrap},
     Frame,
 };
 
-use crate::app::AppState;
-use crate::core::CloudResource;
+use crate::app::{AppState, DetailPane, DetailTab};
+use crate::core::{CloudResource, MetricSeries, ResourceHealth, ResourceType};
+use crate::providers::aws::resources::EC2Instance;
+use crate::providers::azure::resources::AzureAksCluster;
+use crate::providers::gcp::resources::GKECluster;
+use crate::ui::resource_list::health_style;
 use crate::ui::theme::Theme;
 
 pub async fn render_detail_view(frame: &mut Frame<'_>, area: Rect, state: &AppState) {
     if state.show_confirmation {
         render_with_confirmation(frame, area, state).await;
+    } else if state.is_prompting() {
+        render_detail_content(frame, area, state).await;
+        if state.tag_editor.is_some() {
+            render_tag_editor(frame, centered_rect(60, 60, area), state);
+        }
+        render_prompt(frame, centered_rect(60, 20, area), state);
+    } else if state.tag_editor.is_some() {
+        render_detail_content(frame, area, state).await;
+        render_tag_editor(frame, centered_rect(60, 60, area), state);
+    } else if state.show_modify_picker {
+        render_detail_content(frame, area, state).await;
+        render_modify_picker(frame, centered_rect(60, 50, area), state);
     } else {
         render_detail_content(frame, area, state).await;
     }
@@ -46,15 +62,143 @@ async fn render_detail_content(frame: &mut Frame<'_>, area: Rect, state: &AppSta
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
+            Constraint::Length(3),
             Constraint::Length(8),
             Constraint::Min(10),
             Constraint::Length(12),
         ])
         .split(area);
 
-    render_resource_header(frame, chunks[0], resource.as_ref());
-    render_resource_metadata(frame, chunks[1], resource.as_ref());
-    render_available_actions(frame, chunks[2], resource.as_ref(), state);
+    render_detail_tabs(frame, chunks[0], state);
+    render_resource_header(frame, chunks[1], resource.as_ref());
+    match state.detail_tab {
+        DetailTab::Overview => render_resource_metadata(frame, chunks[2], resource.as_ref(), state),
+        DetailTab::Metrics => render_metrics(frame, chunks[2], resource.as_ref(), state),
+        DetailTab::Logs => crate::ui::logs::render_logs(frame, chunks[2], resource.name(), state),
+        DetailTab::Raw => render_raw(frame, chunks[2], resource.as_ref(), state),
+    }
+    render_available_actions(frame, chunks[3], resource.as_ref(), state);
+}
+
+fn render_detail_tabs(frame: &mut Frame, area: Rect, state: &AppState) {
+    let detail_tabs = state.detail_tabs();
+    let titles: Vec<Line> = detail_tabs
+        .iter()
+        .map(|tab| Line::from(tab.as_str()))
+        .collect();
+    let selected = detail_tabs.iter().position(|tab| *tab == state.detail_tab).unwrap_or(0);
+
+    let tabs = Tabs::new(titles)
+        .block(Block::default().borders(Borders::ALL).style(Theme::border()))
+        .select(selected)
+        .style(Theme::tab_inactive())
+        .highlight_style(Theme::tab_active());
+
+    frame.render_widget(tabs, area);
+}
+
+fn render_metrics(frame: &mut Frame, area: Rect, resource: &dyn CloudResource, state: &AppState) {
+    let loaded = state.metrics_resource_id.as_deref() == Some(resource.id());
+    let series: Vec<&MetricSeries> = if loaded {
+        state.metrics.iter().filter(|s| !s.is_empty()).collect()
+    } else {
+        Vec::new()
+    };
+
+    if series.is_empty() {
+        let message = if loaded {
+            "No metrics available for this resource"
+        } else {
+            "Loading metrics..."
+        };
+        let paragraph = Paragraph::new(vec![Line::from(""), Line::from(message)])
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Metrics (last 3h)")
+                    .style(Theme::border()),
+            )
+            .style(Theme::help_text())
+            .alignment(ratatui::layout::Alignment::Center);
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let constraints: Vec<Constraint> = series
+        .iter()
+        .map(|_| Constraint::Ratio(1, series.len() as u32))
+        .collect();
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(area);
+
+    for (metric, row) in series.iter().zip(rows.iter()) {
+        render_sparkline(frame, *row, metric, state.metrics_cached);
+    }
+}
+
+fn render_sparkline(frame: &mut Frame, area: Rect, series: &MetricSeries, cached: bool) {
+    let max = series.max().unwrap_or(0.0);
+    let data = scale_for_sparkline(series, max);
+
+    let mut title = format!(
+        "{}  now {}  peak {}",
+        series.label,
+        series.latest().map(|v| series.format_value(v)).unwrap_or_else(|| "-".to_string()),
+        series.format_value(max),
+    );
+    if cached {
+        let sampled_at = series.datapoints.last().map(|point| point.timestamp.format("%Y-%m-%d %H:%M UTC"));
+        if let Some(sampled_at) = sampled_at {
+            title.push_str(&format!("  (cached, last sample {})", sampled_at));
+        }
+    }
+
+    let sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .style(Theme::border()),
+        )
+        .data(&data)
+        .max(SPARKLINE_RESOLUTION)
+        .style(Theme::spinner());
+
+    frame.render_widget(sparkline, area);
+}
+
+const SPARKLINE_RESOLUTION: u64 = 1000;
+
+/// Sparklines plot integers, so values are rescaled relative to the peak.
+fn scale_for_sparkline(series: &MetricSeries, max: f64) -> Vec<u64> {
+    series
+        .datapoints
+        .iter()
+        .map(|point| {
+            if max > 0.0 {
+                ((point.value / max) * SPARKLINE_RESOLUTION as f64) as u64
+            } else {
+                0
+            }
+        })
+        .collect()
+}
+
+fn render_raw(frame: &mut Frame, area: Rect, resource: &dyn CloudResource, state: &AppState) {
+    let text = serde_json::to_string_pretty(&resource.raw_json()).unwrap_or_default();
+    let lines: Vec<Line> = text.lines().skip(state.raw_scroll).map(|line| Line::from(line.to_string())).collect();
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Raw (↑↓ scroll)")
+            .style(Theme::border()),
+    );
+
+    frame.render_widget(paragraph, area);
 }
 
 fn render_resource_header(frame: &mut Frame, area: Rect, resource: &dyn CloudResource) {
@@ -90,17 +234,272 @@ fn render_resource_header(frame: &mut Frame, area: Rect, resource: &dyn CloudRes
     frame.render_widget(paragraph, area);
 }
 
-fn render_resource_metadata(frame: &mut Frame, area: Rect, resource: &dyn CloudResource) {
+fn render_resource_metadata(frame: &mut Frame, area: Rect, resource: &dyn CloudResource, state: &AppState) {
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(area);
+    let health = state.health_of(resource);
+    let basic_height = basic_info_height(resource, health.as_ref());
+
+    match resource.as_any().downcast_ref::<EC2Instance>() {
+        Some(instance) => {
+            let left = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(basic_height), Constraint::Min(0)])
+                .split(chunks[0]);
+            render_basic_info(frame, left[0], resource, state);
+            render_network_info(frame, left[1], instance);
+        }
+        None if resource.resource_type() == ResourceType::LoadBalancer => {
+            let left = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(basic_height), Constraint::Min(0)])
+                .split(chunks[0]);
+            render_basic_info(frame, left[0], resource, state);
+            render_target_health(frame, left[1], resource, state);
+        }
+        None => match resource.as_any().downcast_ref::<GKECluster>() {
+            Some(cluster) => {
+                let left = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(basic_height), Constraint::Min(0)])
+                    .split(chunks[0]);
+                render_basic_info(frame, left[0], resource, state);
+                render_cluster_info(frame, left[1], cluster);
+            }
+            None => match resource.as_any().downcast_ref::<AzureAksCluster>() {
+                Some(cluster) => {
+                    let left = Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints([Constraint::Length(basic_height), Constraint::Min(0)])
+                        .split(chunks[0]);
+                    render_basic_info(frame, left[0], resource, state);
+                    render_aks_cluster_info(frame, left[1], cluster);
+                }
+                None => render_basic_info(frame, chunks[0], resource, state),
+            },
+        },
+    }
+    render_tags_and_cost(frame, chunks[1], resource, state);
+}
+
+fn render_target_health(frame: &mut Frame, area: Rect, resource: &dyn CloudResource, state: &AppState) {
+    let loaded = state.target_health_resource_id.as_deref() == Some(resource.id());
+
+    let mut lines = Vec::new();
+    if !loaded {
+        lines.push(Line::from(Span::styled("Loading target health...", Theme::help_text())));
+    } else if state.target_health.is_empty() {
+        lines.push(Line::from(Span::styled("No target groups", Theme::help_text())));
+    }
+
+    if loaded {
+        for group in &state.target_health {
+            let listener = match (&group.protocol, group.port) {
+                (Some(protocol), Some(port)) => format!(" {}:{}", protocol, port),
+                _ => String::new(),
+            };
+            let summary_style = if group.unhealthy_count() > 0 {
+                Theme::error()
+            } else {
+                Theme::success()
+            };
+
+            lines.push(Line::from(vec![
+                Span::styled(format!("{}{}", group.name, listener), Theme::title()),
+                Span::styled(
+                    format!("  {}/{} healthy", group.healthy_count(), group.targets.len()),
+                    summary_style,
+                ),
+            ]));
+
+            for target in &group.targets {
+                let port = target.port.map(|p| format!(":{}", p)).unwrap_or_default();
+                let style = if target.is_healthy() {
+                    Theme::help_text()
+                } else {
+                    Theme::error()
+                };
+                let reason = target
+                    .reason
+                    .as_ref()
+                    .map(|r| format!(" - {}", r))
+                    .unwrap_or_default();
+
+                lines.push(Line::from(Span::styled(
+                    format!("  {}{}  {}{}", target.id, port, target.state, reason),
+                    style,
+                )));
+            }
+        }
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Target Health")
+                .style(Theme::border()),
+        )
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(paragraph, area);
+}
+
+fn render_cluster_info(frame: &mut Frame, area: Rect, cluster: &GKECluster) {
+    let mode = if cluster.is_autopilot() { "Autopilot" } else { "Standard" };
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Location:      ", Theme::help_text()),
+            Span::raw(format!("{} ({})", cluster.location(), mode)),
+        ]),
+        Line::from(vec![
+            Span::styled("Control plane: ", Theme::help_text()),
+            Span::raw(cluster.master_version().unwrap_or("-").to_string()),
+        ]),
+        Line::from(vec![
+            Span::styled("Nodes:         ", Theme::help_text()),
+            Span::raw(format!(
+                "{} in {} pool(s), version {}",
+                cluster.node_count(),
+                cluster.node_pool_count(),
+                cluster.node_version().unwrap_or("-")
+            )),
+        ]),
+    ];
+
+    if !cluster.node_pools().is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Node Pools", Theme::title())));
+        for pool in cluster.node_pools() {
+            lines.push(Line::from(format!("  {}", pool)));
+        }
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Cluster")
+                .style(Theme::border()),
+        )
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(paragraph, area);
+}
+
+fn render_aks_cluster_info(frame: &mut Frame, area: Rect, cluster: &AzureAksCluster) {
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Resource group: ", Theme::help_text()),
+            Span::raw(format!("{} ({} tier)", cluster.resource_group(), cluster.tier())),
+        ]),
+        Line::from(vec![
+            Span::styled("Kubernetes:     ", Theme::help_text()),
+            Span::raw(cluster.kubernetes_version().unwrap_or("-").to_string()),
+        ]),
+        Line::from(vec![
+            Span::styled("API server:     ", Theme::help_text()),
+            Span::raw(cluster.fqdn().unwrap_or("-").to_string()),
+        ]),
+        Line::from(vec![
+            Span::styled("Nodes:          ", Theme::help_text()),
+            Span::raw(format!("{} in {} pool(s)", cluster.node_count(), cluster.node_pool_count())),
+        ]),
+    ];
+
+    if !cluster.node_pools().is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Node Pools", Theme::title())));
+        for pool in cluster.node_pools() {
+            lines.push(Line::from(format!(
+                "  {} ({}): {} x {}",
+                pool.name,
+                pool.mode.as_deref().unwrap_or("-"),
+                pool.count.unwrap_or(0),
+                pool.vm_size.as_deref().unwrap_or("-")
+            )));
+        }
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Cluster")
+                .style(Theme::border()),
+        )
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(paragraph, area);
+}
 
-    render_basic_info(frame, chunks[0], resource);
-    render_tags_and_cost(frame, chunks[1], resource);
+fn render_network_info(frame: &mut Frame, area: Rect, instance: &EC2Instance) {
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("VPC:    ", Theme::help_text()),
+            Span::raw(instance.vpc_id().unwrap_or("-").to_string()),
+        ]),
+        Line::from(vec![
+            Span::styled("Subnet: ", Theme::help_text()),
+            Span::raw(instance.subnet_id().unwrap_or("-").to_string()),
+        ]),
+    ];
+
+    if !instance.network_interfaces().is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Interfaces", Theme::title())));
+        for eni in instance.network_interfaces() {
+            let mut ips = eni.private_ip.clone().unwrap_or_default();
+            if let Some(public_ip) = &eni.public_ip {
+                ips = format!("{} / {}", ips, public_ip);
+            }
+            lines.push(Line::from(format!("  {}  {}", eni.id, ips)));
+        }
+    }
+
+    if !instance.security_groups().is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Security Groups", Theme::title())));
+        for group in instance.security_groups() {
+            lines.push(Line::from(format!("  {} ({})", group.name, group.id)));
+            for rule in &group.ingress {
+                let style = if rule.is_open_to_world() {
+                    Theme::error()
+                } else {
+                    Theme::help_text()
+                };
+                let marker = if rule.is_open_to_world() { "  open to internet" } else { "" };
+                lines.push(Line::from(Span::styled(
+                    format!("    {} from {}{}", rule.port_range(), rule.sources.join(", "), marker),
+                    style,
+                )));
+            }
+        }
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Network")
+                .style(Theme::border()),
+        )
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(paragraph, area);
+}
+
+/// Height of the basic information table: the shared fields, the health
+/// row if there is one, the resource's own details, and the borders.
+fn basic_info_height(resource: &dyn CloudResource, health: Option<&ResourceHealth>) -> u16 {
+    6 + health.is_some() as u16 + resource.details().len() as u16
 }
 
-fn render_basic_info(frame: &mut Frame, area: Rect, resource: &dyn CloudResource) {
+fn render_basic_info(frame: &mut Frame, area: Rect, resource: &dyn CloudResource, state: &AppState) {
+    let health = state.health_of(resource);
+    let health = health.as_ref();
     let created = resource
         .created_at()
         .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
@@ -108,33 +507,43 @@ fn render_basic_info(frame: &mut Frame, area: Rect, resource: &dyn CloudResource
 
     let state_style = match resource.state() {
         crate::core::ResourceState::Running => Theme::success(),
-        crate::core::ResourceState::Stopped => Theme::warning(),
-        crate::core::ResourceState::Error => Theme::error(),
+        crate::core::ResourceState::Stopped | crate::core::ResourceState::Degraded => Theme::warning(),
+        crate::core::ResourceState::Error | crate::core::ResourceState::Deleting => Theme::error(),
         _ => Theme::help_text(),
     };
 
-    let rows = vec![
-        Row::new(vec!["ID", resource.id()]),
-        Row::new(vec!["State", resource.state().as_str()]).style(state_style),
-        Row::new(vec!["Region", resource.region()]),
-        Row::new(vec!["Created", &created]),
+    let mut rows = vec![
+        Row::new(vec!["ID".to_string(), resource.id().to_string()]),
+        Row::new(vec!["State".to_string(), resource.state().as_str().to_string()]).style(state_style),
+        Row::new(vec!["Region".to_string(), resource.region().to_string()]),
+        Row::new(vec!["Created".to_string(), created]),
     ];
+    if let Some(health) = health {
+        let value = match health.reason {
+            Some(ref reason) => format!("{} — {}", health.status.as_str(), reason),
+            None => health.status.as_str().to_string(),
+        };
+        rows.push(Row::new(vec!["Health".to_string(), value]).style(health_style(health.status)));
+    }
+    rows.extend(
+        resource
+            .details()
+            .into_iter()
+            .map(|(label, value)| Row::new(vec![label, value])),
+    );
 
-    let widths = [Constraint::Length(12), Constraint::Min(20)];
+    let widths = [Constraint::Length(18), Constraint::Min(20)];
+    let title = pane_title("Basic Information", state.metadata_scroll, rows.len());
+    let rows: Vec<Row> = rows.into_iter().skip(state.metadata_scroll).collect();
 
     let table = Table::new(rows, widths)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Basic Information")
-                .style(Theme::border()),
-        )
+        .block(pane_block(title, state.detail_pane == DetailPane::Metadata))
         .column_spacing(2);
 
     frame.render_widget(table, area);
 }
 
-fn render_tags_and_cost(frame: &mut Frame, area: Rect, resource: &dyn CloudResource) {
+fn render_tags_and_cost(frame: &mut Frame, area: Rect, resource: &dyn CloudResource, state: &AppState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
@@ -164,32 +573,53 @@ fn render_tags_and_cost(frame: &mut Frame, area: Rect, resource: &dyn CloudResou
 
     frame.render_widget(cost_widget, chunks[0]);
 
-    let tags = resource.tags();
+    let mut tags: Vec<(&String, &String)> = resource.tags().iter().collect();
+    tags.sort();
     let tag_items: Vec<ListItem> = if tags.is_empty() {
         vec![ListItem::new("No tags")]
     } else {
         tags.iter()
+            .skip(state.tags_scroll)
             .map(|(key, value)| ListItem::new(format!("{}: {}", key, value)))
             .collect()
     };
 
-    let tags_widget = List::new(tag_items).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title(format!("Tags ({})", tags.len()))
-            .style(Theme::border()),
-    );
+    let title = pane_title(&format!("Tags ({})", tags.len()), state.tags_scroll, tags.len());
+    let tags_widget = List::new(tag_items).block(pane_block(title, state.detail_pane == DetailPane::Tags));
 
     frame.render_widget(tags_widget, chunks[1]);
 }
 
+/// Adds the first row shown to a scrollable pane's title once it has been
+/// scrolled.
+fn pane_title(title: &str, scroll: usize, len: usize) -> String {
+    if scroll == 0 {
+        title.to_string()
+    } else {
+        format!("{} — from {} of {}", title, scroll + 1, len)
+    }
+}
+
+/// A bordered block whose border is highlighted while the pane has focus.
+fn pane_block(title: String, focused: bool) -> Block<'static> {
+    let block = Block::default().borders(Borders::ALL).title(title).style(Theme::border());
+    if focused {
+        block.border_style(Theme::title())
+    } else {
+        block
+    }
+}
+
 fn render_available_actions(
     frame: &mut Frame,
     area: Rect,
     resource: &dyn CloudResource,
     state: &AppState,
 ) {
-    let actions = resource.supported_actions();
+    let actions = match state.get_selected_resource_index() {
+        Some(resource_idx) => state.actions_for(resource_idx, resource),
+        None => resource.supported_actions(),
+    };
 
     if actions.is_empty() {
         let text = vec![Line::from("No actions available for this resource")];
@@ -237,36 +667,160 @@ fn render_available_actions(
         })
         .collect();
 
-    let help_text = vec![
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("↑↓", Theme::help_key()),
-            Span::raw(": Select  "),
-            Span::styled("Enter", Theme::help_key()),
-            Span::raw(": Execute  "),
-            Span::styled("ESC", Theme::help_key()),
-            Span::raw(": Back"),
-        ]),
+    let mut help_spans = vec![
+        Span::styled("↑↓", Theme::help_key()),
+        Span::raw(": Select  "),
+        Span::styled("Enter", Theme::help_key()),
+        Span::raw(": Execute  "),
     ];
+    help_spans.push(Span::styled("←→", Theme::help_key()));
+    help_spans.push(Span::raw(": Tabs  "));
+    if state.detail_tab == DetailTab::Overview {
+        help_spans.push(Span::styled("Tab", Theme::help_key()));
+        help_spans.push(Span::raw(": Focus  "));
+    }
+    help_spans.push(Span::styled("ESC", Theme::help_key()));
+    help_spans.push(Span::raw(": Back"));
+    let help_text = vec![Line::from(""), Line::from(help_spans)];
+
+    let focused = state.detail_tab == DetailTab::Overview && state.detail_pane == DetailPane::Actions;
+    let list = List::new(action_items).block(pane_block("Available Actions".to_string(), focused));
+
+    let help = Paragraph::new(help_text).style(Theme::help_text());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(3)])
+        .split(area);
+
+    frame.render_widget(list, chunks[0]);
+    frame.render_widget(help, chunks[1]);
+}
+
+fn render_modify_picker(frame: &mut Frame, area: Rect, state: &AppState) {
+    let current_cost = state
+        .get_selected_resource_index()
+        .and_then(|idx| state.resources.try_read().ok()?.get(idx).and_then(|r| r.cost_per_month()));
 
-    let list = List::new(action_items).block(
+    let items: Vec<ListItem> = state
+        .modify_options
+        .iter()
+        .enumerate()
+        .map(|(idx, option)| {
+            let selected = idx == state.selected_modify_option;
+            let prefix = if selected { "> " } else { "  " };
+            let cost = match (option.monthly_cost, option.cost_delta(current_cost)) {
+                (Some(cost), Some(delta)) => format!("  ${:.2}/mo ({:+.2})", cost, delta),
+                (Some(cost), None) => format!("  ${:.2}/mo", cost),
+                _ => String::new(),
+            };
+            let style = if selected {
+                Theme::selected_row()
+            } else {
+                Style::default()
+            };
+
+            ListItem::new(format!("{}{}{}", prefix, option.label, cost)).style(style)
+        })
+        .collect();
+
+    let list = List::new(items).block(
         Block::default()
             .borders(Borders::ALL)
-            .title("Available Actions")
+            .title("Modify (↑↓ select, Enter choose, ESC cancel)")
             .style(Theme::border()),
     );
 
-    let help = Paragraph::new(help_text).style(Theme::help_text());
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(list, area);
+}
+
+fn render_tag_editor(frame: &mut Frame, area: Rect, state: &AppState) {
+    let editor = match state.tag_editor {
+        Some(ref editor) => editor,
+        None => return,
+    };
+
+    let items: Vec<ListItem> = if editor.tags.is_empty() {
+        vec![ListItem::new("  No tags. Press 'a' to add one.").style(Theme::help_text())]
+    } else {
+        editor
+            .tags
+            .iter()
+            .enumerate()
+            .map(|(idx, (key, value))| {
+                let selected = idx == editor.selected;
+                let prefix = if selected { "> " } else { "  " };
+                let style = if selected {
+                    Theme::selected_row()
+                } else {
+                    Style::default()
+                };
+                ListItem::new(format!("{}{} = {}", prefix, key, value)).style(style)
+            })
+            .collect()
+    };
+
+    let pending = editor.changes();
+    let title = if pending.is_empty() {
+        "Edit Tags".to_string()
+    } else {
+        format!(
+            "Edit Tags ({} to set, {} to remove)",
+            pending.set.len(),
+            pending.remove.len()
+        )
+    };
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(5), Constraint::Length(3)])
+        .constraints([Constraint::Min(3), Constraint::Length(3)])
         .split(area);
 
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .style(Theme::border()),
+    );
+
+    let help = Paragraph::new(Line::from(vec![
+        Span::styled("a", Theme::help_key()),
+        Span::raw(": Add  "),
+        Span::styled("e", Theme::help_key()),
+        Span::raw(": Edit  "),
+        Span::styled("d", Theme::help_key()),
+        Span::raw(": Delete  "),
+        Span::styled("Enter", Theme::help_key()),
+        Span::raw(": Save  "),
+        Span::styled("ESC", Theme::help_key()),
+        Span::raw(": Cancel"),
+    ]))
+    .alignment(ratatui::layout::Alignment::Center)
+    .block(Block::default().borders(Borders::ALL));
+
+    frame.render_widget(ratatui::widgets::Clear, area);
     frame.render_widget(list, chunks[0]);
     frame.render_widget(help, chunks[1]);
 }
 
+pub(crate) fn render_prompt(frame: &mut Frame, area: Rect, state: &AppState) {
+    let input = Paragraph::new(Line::from(vec![
+        Span::raw(state.prompt_input.as_str()),
+        Span::styled("█", Theme::help_key()),
+    ]))
+    .wrap(Wrap { trim: false })
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(state.prompt_title.as_str())
+            .style(Theme::border()),
+    );
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(input, area);
+}
+
 async fn render_with_confirmation(frame: &mut Frame<'_>, area: Rect, state: &AppState) {
     render_detail_content(frame, area, state).await;
 
@@ -274,7 +828,7 @@ async fn render_with_confirmation(frame: &mut Frame<'_>, area: Rect, state: &App
     render_confirmation_dialog(frame, popup_area, state);
 }
 
-fn render_confirmation_dialog(frame: &mut Frame, area: Rect, state: &AppState) {
+pub(crate) fn render_confirmation_dialog(frame: &mut Frame, area: Rect, state: &AppState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -288,23 +842,89 @@ fn render_confirmation_dialog(frame: &mut Frame, area: Rect, state: &AppState) {
         .style(Theme::warning())
         .block(Block::default().borders(Borders::ALL).style(Theme::warning()));
 
-    let message = Paragraph::new(state.confirmation_message.as_str())
+    let mut text: Vec<Line> = state
+        .confirmation_message
+        .lines()
+        .map(|line| Line::from(line.to_string()))
+        .collect();
+
+    if let Some(ref request) = state.pending_request {
+        let params = request.describe_params();
+        if !params.is_empty() {
+            text.push(Line::from(""));
+            for param in params {
+                text.push(Line::from(Span::styled(param, Theme::title())));
+            }
+        }
+    }
+
+    if !state.deletion_blockers.is_empty() {
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled("Blocked by:", Theme::error())));
+        for blocker in &state.deletion_blockers {
+            text.push(Line::from(Span::styled(format!("• {}", blocker), Theme::error())));
+        }
+    }
+
+    if let Some(choice) = state.snapshot_choice {
+        let checkbox = if choice { "[x]" } else { "[ ]" };
+        let hint = if state.require_snapshot {
+            " (required)"
+        } else {
+            ""
+        };
+        text.push(Line::from(""));
+        text.push(Line::from(vec![
+            Span::styled(format!("{} Take a snapshot first", checkbox), Theme::warning()),
+            Span::raw(hint),
+        ]));
+    }
+
+    if state.needs_typed_confirmation() {
+        let style = if state.confirm_input_matches() {
+            Theme::success()
+        } else {
+            Theme::warning()
+        };
+        text.push(Line::from(""));
+        text.push(Line::from(vec![
+            Span::styled("Confirm: ", Theme::help_key()),
+            Span::styled(format!("{}█", state.confirm_input), style),
+        ]));
+    }
+
+    let message = Paragraph::new(text)
         .wrap(Wrap { trim: true })
         .style(Theme::help_text())
         .block(Block::default().borders(Borders::LEFT | Borders::RIGHT));
 
-    let buttons = Paragraph::new(vec![Line::from(vec![
-        Span::styled("Enter", Theme::help_key()),
-        Span::raw(": Confirm  "),
-        Span::styled("ESC", Theme::help_key()),
-        Span::raw(": Cancel"),
-    ])])
+    let blocked = !state.deletion_blockers.is_empty();
+    let mut button_spans = if state.can_confirm() {
+        vec![
+            Span::styled("Enter", Theme::help_key()),
+            Span::raw(": Confirm  "),
+            Span::styled("ESC", Theme::help_key()),
+            Span::raw(": Cancel"),
+        ]
+    } else if blocked {
+        vec![Span::styled("ESC", Theme::help_key()), Span::raw(": Close")]
+    } else {
+        vec![Span::styled("ESC", Theme::help_key()), Span::raw(": Cancel")]
+    };
+    if state.snapshot_choice.is_some() && !state.require_snapshot && !blocked {
+        // s would be typed into the name, so Tab toggles while one is asked for
+        let toggle_key = if state.needs_typed_confirmation() { "Tab" } else { "s" };
+        button_spans.push(Span::raw("  "));
+        button_spans.push(Span::styled(toggle_key, Theme::help_key()));
+        button_spans.push(Span::raw(": Toggle Snapshot"));
+    }
+
+    let buttons = Paragraph::new(vec![Line::from(button_spans)])
     .alignment(ratatui::layout::Alignment::Center)
     .block(Block::default().borders(Borders::ALL));
 
     frame.render_widget(
-        Block::default()
-            .style(Style::default().bg(ratatui::style::Color::Black)),
+        Block::default().style(Theme::dialog()),
         area,
     );
     frame.render_widget(title, chunks[0]);
@@ -351,7 +971,7 @@ fn render_no_selection(frame: &mut Frame, area: Rect) {
     frame.render_widget(paragraph, area);
 }
 
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+pub(crate) fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
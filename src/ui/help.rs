@@ -0,0 +1,47 @@
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::keymap;
+use crate::app::AppState;
+use crate::ui::detail::centered_rect;
+use crate::ui::theme::Theme;
+
+/// Draws every key binding from the keymap over nearly the whole screen.
+pub fn render_help(frame: &mut Frame, area: Rect, state: &AppState) {
+    let popup_area = centered_rect(90, 90, area);
+    let sections = keymap::sections(state.keymap);
+    let key_width = sections
+        .iter()
+        .flat_map(|section| section.bindings)
+        .map(|binding| binding.keys.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = Vec::new();
+    for section in sections {
+        lines.push(Line::from(Span::styled(section.title, Theme::title())));
+        for binding in section.bindings {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<width$}  ", binding.keys, width = key_width), Theme::help_key()),
+                Span::styled(binding.description, Theme::help_text()),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .scroll((state.help_scroll as u16, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Keys (↑↓ scroll, ? or ESC close)")
+                .style(Theme::border()),
+        );
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(paragraph, popup_area);
+}
@@ -0,0 +1,92 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    text::Line,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::core::{ActionOutcome, ActionRecord};
+use crate::ui::theme::Theme;
+
+pub async fn render_history_view(frame: &mut Frame<'_>, area: Rect, state: &AppState) {
+    let records = &state.action_history;
+    if records.is_empty() {
+        let message = if state.loading {
+            "Loading action history..."
+        } else {
+            "No actions have been run yet"
+        };
+        let paragraph = Paragraph::new(vec![Line::from(""), Line::from(message)])
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Action History")
+                    .style(Theme::border()),
+            )
+            .style(Theme::help_text())
+            .alignment(ratatui::layout::Alignment::Center);
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let header_cells = ["Time", "Action", "Account", "Resource", "Outcome", "Message"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Theme::table_header()));
+    let header = Row::new(header_cells).height(1).style(Theme::table_header());
+
+    let rows: Vec<Row> = records
+        .iter()
+        .map(|record| {
+            Row::new(vec![
+                Cell::from(record.recorded_at.format("%Y-%m-%d %H:%M:%S").to_string()),
+                Cell::from(record.action.clone()),
+                Cell::from(record.account.clone().unwrap_or_default()),
+                Cell::from(resource_text(record)),
+                Cell::from(record.outcome.as_str()).style(outcome_style(record.outcome)),
+                Cell::from(record.message.clone()),
+            ])
+            .height(1)
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(19),
+        Constraint::Length(16),
+        Constraint::Length(14),
+        Constraint::Length(28),
+        Constraint::Length(9),
+        Constraint::Min(30),
+    ];
+
+    let failed = records.iter().filter(|r| r.is_failure()).count();
+    let title = format!("Action History ({} actions, {} failed)", records.len(), failed);
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .style(Theme::border()),
+        )
+        .column_spacing(1);
+
+    frame.render_widget(table, area);
+}
+
+fn outcome_style(outcome: ActionOutcome) -> ratatui::style::Style {
+    match outcome {
+        ActionOutcome::Succeeded => Theme::success(),
+        ActionOutcome::Failed => Theme::error(),
+    }
+}
+
+fn resource_text(record: &ActionRecord) -> String {
+    match (&record.resource_name, &record.resource_id) {
+        (Some(name), Some(id)) if name != id => format!("{} ({})", name, id),
+        (_, Some(id)) => id.clone(),
+        (Some(name), None) => name.clone(),
+        (None, None) => String::new(),
+    }
+}
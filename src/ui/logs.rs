@@ -0,0 +1,74 @@
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::ui::theme::Theme;
+
+/// Draws the fetched log lines of the resource named `resource_name` in the
+/// detail view's Logs tab.
+pub fn render_logs(frame: &mut Frame, area: Rect, resource_name: &str, state: &AppState) {
+    let mode = if state.logs_follow {
+        "following"
+    } else {
+        "paused"
+    };
+    let title = format!("Logs: {} ({}, f to toggle)", resource_name, mode);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .style(Theme::border());
+
+    if state.logs.is_empty() {
+        let message = if state.loading {
+            "Loading logs..."
+        } else {
+            "No log events found for this resource"
+        };
+        let paragraph = Paragraph::new(vec![Line::from(""), Line::from(message)])
+            .block(block)
+            .style(Theme::help_text())
+            .alignment(ratatui::layout::Alignment::Center);
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let height = area.height.saturating_sub(2) as usize;
+    let (start, end) = visible_range(state.logs.len(), state.logs_scroll, height);
+
+    let lines: Vec<Line> = state.logs[start..end]
+        .iter()
+        .map(|entry| {
+            Line::from(vec![
+                Span::styled(entry.timestamp.format("%H:%M:%S ").to_string(), Theme::help_key()),
+                Span::raw(entry.message.trim_end().to_string()),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Returns the slice of log lines to display given the scroll offset from the end.
+fn visible_range(total: usize, scroll: usize, height: usize) -> (usize, usize) {
+    let end = total.saturating_sub(scroll);
+    let start = end.saturating_sub(height);
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visible_range() {
+        assert_eq!(visible_range(100, 0, 20), (80, 100));
+        assert_eq!(visible_range(100, 10, 20), (70, 90));
+        assert_eq!(visible_range(5, 0, 20), (0, 5));
+        assert_eq!(visible_range(5, 10, 20), (0, 0));
+    }
+}
@@ -1,9 +1,24 @@
+pub mod accounts;
+pub mod cache_stats;
+pub mod changes;
 pub mod components;
+pub mod compliance;
+pub mod contexts;
+pub mod create;
 pub mod dashboard;
 pub mod detail;
+pub mod help;
+pub mod history;
+pub mod logs;
+pub mod notifications;
+pub mod quotas;
+pub mod records;
+pub mod refresh_progress;
+pub mod regions;
 pub mod render;
 pub mod resource_list;
 pub mod tabs;
 pub mod theme;
+pub mod waste;
 
 pub use render::render;
\ No newline at end of file
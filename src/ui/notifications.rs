@@ -0,0 +1,55 @@
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::ui::detail::centered_rect;
+use crate::ui::theme::Theme;
+
+/// Lists the session's messages, newest first.
+pub fn render_notifications(frame: &mut Frame, area: Rect, state: &AppState) {
+    let popup_area = centered_rect(80, 70, area);
+
+    let lines: Vec<Line> = if state.notifications.is_empty() {
+        vec![Line::from(Span::styled("No messages yet", Theme::help_text()))]
+    } else {
+        state
+            .notifications
+            .iter()
+            .rev()
+            .skip(state.notifications_scroll)
+            .map(|notification| {
+                let (icon, style) = if notification.is_error {
+                    ("✗ ", Theme::error())
+                } else {
+                    ("✓ ", Theme::success())
+                };
+                let time = notification.at.format("%H:%M:%S");
+                Line::from(vec![
+                    Span::styled(format!("{}  ", time), Theme::help_text()),
+                    Span::styled(icon, style),
+                    Span::styled(notification.message.as_str(), style),
+                ])
+            })
+            .collect()
+    };
+
+    let errors = state.notifications.iter().filter(|n| n.is_error).count();
+    let title = format!(
+        "Notifications ({} messages, {} errors; ↑↓ scroll, m or ESC close)",
+        state.notifications.len(),
+        errors
+    );
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .style(Theme::border()),
+    );
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(paragraph, popup_area);
+}
@@ -0,0 +1,83 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    text::Line,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::core::Quota;
+use crate::ui::theme::Theme;
+
+pub async fn render_quotas_view(frame: &mut Frame<'_>, area: Rect, state: &AppState) {
+    let near_limit = state.quotas_near_limit();
+    let title = if near_limit > 0 {
+        format!("Service Quotas ({} near limit)", near_limit)
+    } else {
+        "Service Quotas".to_string()
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .style(Theme::border());
+
+    if state.quotas.is_empty() {
+        let message = if state.loading {
+            "Loading quotas..."
+        } else {
+            "No quota information available"
+        };
+        let paragraph = Paragraph::new(vec![Line::from(""), Line::from(message)])
+            .block(block)
+            .style(Theme::help_text())
+            .alignment(ratatui::layout::Alignment::Center);
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let header_cells = ["Provider", "Service", "Quota", "Usage", "Used"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Theme::table_header()));
+    let header = Row::new(header_cells).height(1).style(Theme::table_header());
+
+    let rows: Vec<Row> = state
+        .quotas
+        .iter()
+        .map(|(provider, quota)| {
+            Row::new(vec![
+                Cell::from(provider.clone()),
+                Cell::from(quota.service.clone()),
+                Cell::from(quota.name.clone()),
+                Cell::from(format!("{} / {}", quota.usage, quota.limit)),
+                Cell::from(format!("{:.0}%", quota.used_ratio() * 100.0)).style(quota_style(quota)),
+            ])
+            .height(1)
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Min(24),
+        Constraint::Length(16),
+        Constraint::Length(6),
+    ];
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .column_spacing(1);
+
+    frame.render_widget(table, area);
+}
+
+fn quota_style(quota: &Quota) -> ratatui::style::Style {
+    if quota.is_exhausted() {
+        Theme::error()
+    } else if quota.is_near_limit() {
+        Theme::warning()
+    } else {
+        Theme::success()
+    }
+}
@@ -0,0 +1,86 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    text::Line,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::ui::theme::Theme;
+
+pub async fn render_records_view(frame: &mut Frame<'_>, area: Rect, state: &AppState) {
+    let zone_name = match state.get_selected_resource_index() {
+        Some(idx) => match state.resources.try_read() {
+            Ok(resources) => resources.get(idx).map(|r| r.name().to_string()),
+            Err(_) => None,
+        },
+        None => None,
+    };
+
+    let page = format!(
+        "Page {}/{}, {} records",
+        state.records_page + 1,
+        state.records_page_count(),
+        state.dns_records.len()
+    );
+    let title = match zone_name {
+        Some(name) => format!("Records: {} ({})", name, page),
+        None => format!("Records ({})", page),
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .style(Theme::border());
+
+    if state.dns_records.is_empty() {
+        let message = if state.loading {
+            "Loading records..."
+        } else {
+            "No records found in this zone"
+        };
+        let paragraph = Paragraph::new(vec![Line::from(""), Line::from(message)])
+            .block(block)
+            .style(Theme::help_text())
+            .alignment(ratatui::layout::Alignment::Center);
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let header_cells = ["Name", "Type", "TTL", "Values"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Theme::table_header()));
+    let header = Row::new(header_cells).height(1).style(Theme::table_header());
+
+    let rows: Vec<Row> = state
+        .current_records_page()
+        .iter()
+        .map(|record| {
+            let ttl = match record.ttl {
+                Some(ttl) => ttl.to_string(),
+                None => "-".to_string(),
+            };
+            Row::new(vec![
+                Cell::from(record.name.clone()),
+                Cell::from(record.record_type.clone()),
+                Cell::from(ttl),
+                Cell::from(record.values_display()),
+            ])
+            .height(1)
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Percentage(35),
+        Constraint::Length(7),
+        Constraint::Length(7),
+        Constraint::Min(20),
+    ];
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .column_spacing(1);
+
+    frame.render_widget(table, area);
+}
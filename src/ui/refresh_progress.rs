@@ -0,0 +1,77 @@
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::{AppState, ListingStatus, RefreshProgress};
+use crate::ui::theme::Theme;
+
+/// Lines the progress panel takes, borders included.
+pub fn refresh_progress_height(state: &AppState) -> u16 {
+    state.refresh_progress.len() as u16 + 2
+}
+
+/// Shows how far each provider has got through the refresh, with a mark
+/// for every service it is listing, e.g. "EC2 ✓  RDS ✗  S3 ⠙".
+pub fn render_refresh_progress(frame: &mut Frame, area: Rect, state: &AppState) {
+    let frames = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+    let spinner_idx = (chrono::Utc::now().timestamp_millis() / 100) as usize % frames.len();
+    let spinner = frames[spinner_idx];
+
+    let lines: Vec<Line> = state
+        .refresh_progress
+        .iter()
+        .map(|progress| progress_line(progress, spinner))
+        .collect();
+
+    let finished = state
+        .refresh_progress
+        .iter()
+        .filter(|progress| progress.outcome.is_some())
+        .count();
+    let title = format!(
+        "Refreshing ({} of {} providers done)",
+        finished,
+        state.refresh_progress.len()
+    );
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .style(Theme::border()),
+    );
+
+    frame.render_widget(paragraph, area);
+}
+
+fn progress_line<'a>(progress: &'a RefreshProgress, spinner: &'a str) -> Line<'a> {
+    let mut spans = vec![Span::styled(format!("{}  ", progress.provider), Theme::title())];
+
+    if progress.listings.is_empty() {
+        // Nothing has started yet, usually while the provider authenticates
+        let (mark, style) = match progress.outcome {
+            Some(status) => status_mark(status, spinner),
+            None => ("…", Theme::help_text()),
+        };
+        spans.push(Span::styled(mark, style));
+    }
+
+    for &(service, status) in &progress.listings {
+        let (mark, style) = status_mark(status, spinner);
+        spans.push(Span::styled(format!("{} ", service), Theme::help_text()));
+        spans.push(Span::styled(mark, style));
+        spans.push(Span::raw("  "));
+    }
+
+    Line::from(spans)
+}
+
+fn status_mark(status: ListingStatus, spinner: &str) -> (&str, ratatui::style::Style) {
+    match status {
+        ListingStatus::Listing => (spinner, Theme::spinner()),
+        ListingStatus::Done => ("✓", Theme::success()),
+        ListingStatus::Failed => ("✗", Theme::error()),
+    }
+}
@@ -0,0 +1,44 @@
+use ratatui::{
+    layout::Rect,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::ui::detail::centered_rect;
+use crate::ui::theme::Theme;
+
+pub fn render_region_picker(frame: &mut Frame, area: Rect, state: &AppState) {
+    let picker = match state.region_picker {
+        Some(ref picker) => picker,
+        None => return,
+    };
+    let popup_area = centered_rect(40, 60, area);
+
+    let items: Vec<ListItem> = picker
+        .regions
+        .iter()
+        .enumerate()
+        .map(|(idx, region)| {
+            let prefix = if idx == picker.selected { "> " } else { "  " };
+            let label = if *region == picker.current {
+                format!("{}{} (current)", prefix, region)
+            } else {
+                format!("{}{}", prefix, region)
+            };
+            ListItem::new(label)
+        })
+        .collect();
+
+    // Long region lists scroll to keep the highlighted one in view
+    let mut list_state = ListState::default().with_selected(Some(picker.selected));
+    let list = List::new(items).highlight_style(Theme::selected_row()).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("{} region (↑↓ select, Enter switch, ESC cancel)", picker.provider_name))
+            .style(Theme::border()),
+    );
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_stateful_widget(list, popup_area, &mut list_state);
+}
@@ -4,7 +4,14 @@ use ratatui::{
 };
 
 use crate::app::AppState;
+use crate::ui::accounts::render_account_picker;
+use crate::ui::cache_stats::render_cache_stats;
 use crate::ui::components::render_status_bar;
+use crate::ui::contexts::render_context_picker;
+use crate::ui::create::render_create_wizard;
+use crate::ui::help::render_help;
+use crate::ui::notifications::render_notifications;
+use crate::ui::regions::render_region_picker;
 use crate::ui::tabs::{render_tab_content, render_tabs};
 
 pub async fn render(frame: &mut Frame<'_>, state: &AppState) {
@@ -19,5 +26,26 @@ pub async fn render(frame: &mut Frame<'_>, state: &AppState) {
 
     render_tabs(frame, chunks[0], state);
     render_tab_content(frame, chunks[1], state).await;
+    if state.create_wizard.is_some() {
+        render_create_wizard(frame, chunks[1], state);
+    }
+    if let Some(ref stats) = state.cache_stats {
+        render_cache_stats(frame, chunks[1], stats);
+    }
+    if state.show_context_picker {
+        render_context_picker(frame, chunks[1], state);
+    }
+    if state.region_picker.is_some() {
+        render_region_picker(frame, chunks[1], state);
+    }
+    if state.account_picker.is_some() {
+        render_account_picker(frame, chunks[1], state);
+    }
+    if state.show_notifications {
+        render_notifications(frame, chunks[1], state);
+    }
+    if state.show_help {
+        render_help(frame, chunks[1], state);
+    }
     render_status_bar(frame, chunks[2], state);
 }
\ No newline at end of file
@@ -1,16 +1,22 @@
+use std::collections::HashMap;
+
 use ratatui::{
-    layout::{Constraint, Layout, Rect},
+    layout::{Constraint, Layout, Margin, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    widgets::{
+        Block, Borders, Cell, List, ListItem, Paragraph, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table,
+    },
     Frame,
 };
 
-use crate::app::AppState;
+use crate::app::{AppState, ListColumn, ListRow};
+use crate::ui::detail::{centered_rect, render_confirmation_dialog};
+use crate::ui::refresh_progress::{refresh_progress_height, render_refresh_progress};
 use crate::ui::theme::{resource_icon, Theme};
 
 pub async fn render_resource_list(frame: &mut Frame<'_>, area: Rect, state: &AppState) {
-    let (filter_area, table_area) = if state.is_filtering() || !state.filter_text.is_empty() {
+    let (filter_area, table_area) = if state.is_filtering() || state.is_searching() || !state.filter_text.is_empty() {
         let chunks = Layout::default()
             .direction(ratatui::layout::Direction::Vertical)
             .constraints([Constraint::Length(3), Constraint::Min(0)])
@@ -25,10 +31,26 @@ pub async fn render_resource_list(frame: &mut Frame<'_>, area: Rect, state: &App
     }
 
     if state.loading {
-        render_loading(frame, table_area);
+        if state.is_refreshing() {
+            render_refresh_progress(frame, table_area, state);
+        } else {
+            render_loading(frame, table_area);
+        }
         return;
     }
 
+    // Resources that have arrived stay listed above the ones still loading
+    let table_area = if state.is_refreshing() {
+        let chunks = Layout::default()
+            .direction(ratatui::layout::Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(refresh_progress_height(state))])
+            .split(table_area);
+        render_refresh_progress(frame, chunks[1], state);
+        chunks[0]
+    } else {
+        table_area
+    };
+
     if let Some(ref error) = state.error_message {
         render_error(frame, table_area, error);
         return;
@@ -53,33 +75,93 @@ pub async fn render_resource_list(frame: &mut Frame<'_>, area: Rect, state: &App
         return;
     }
 
-    let header_cells = ["Type", "Name", "ID", "State", "Region", "Cost/Month"]
+    let columns = state.visible_columns();
+    let header_cells = columns
         .iter()
-        .map(|h| Cell::from(*h).style(Theme::table_header()));
+        .map(|column| Cell::from(column.as_str()).style(Theme::table_header()));
     let header = Row::new(header_cells).height(1).style(Theme::table_header());
 
-    let rows: Vec<Row> = state
-        .filtered_resources
+    let list_rows: Vec<ListRow> = if state.group_by.is_some() {
+        state.list_rows.clone()
+    } else {
+        state.filtered_resources.iter().map(|&idx| ListRow::Resource(idx)).collect()
+    };
+
+    // Borders and the header take three lines
+    let height = table_area.height.saturating_sub(3) as usize;
+    let (start, end) = visible_window(list_rows.len(), state.selected_index, height);
+
+    let rows: Vec<Row> = list_rows[start..end]
         .iter()
         .enumerate()
-        .filter_map(|(display_idx, &resource_idx)| {
+        .map(|(offset, list_row)| (start + offset, list_row))
+        .filter_map(|(display_idx, list_row)| {
+            let resource_idx = match list_row {
+                ListRow::Group { name, count, cost, collapsed } => {
+                    let row = group_row(&columns, name, *count, *cost, *collapsed);
+                    return Some(if display_idx == state.selected_index {
+                        row.style(Theme::selected_row())
+                    } else {
+                        row
+                    });
+                }
+                ListRow::Resource(resource_idx) => *resource_idx,
+            };
             resources.get(resource_idx).map(|resource| {
-                let cost = resource
-                    .cost_per_month()
-                    .map(|c| format!("${:.2}", c))
-                    .unwrap_or_else(|| "-".to_string());
-
-                let type_icon = resource_icon(resource.resource_type());
-                let type_display = format!("{} {}", type_icon, resource.resource_type().as_str());
-
-                let cells = vec![
-                    Cell::from(type_display),
-                    Cell::from(resource.name()),
-                    Cell::from(resource.id()),
-                    Cell::from(resource.state().as_str()).style(state_style(resource.state())),
-                    Cell::from(resource.region()),
-                    Cell::from(cost),
-                ];
+                let mark = match (state.marked_resources.is_empty(), state.is_marked(resource.id())) {
+                    (true, _) => "",
+                    (false, true) => "[x] ",
+                    (false, false) => "[ ] ",
+                };
+                let untagged = if state.missing_tags(resource.as_ref()).is_empty() {
+                    Span::raw("")
+                } else {
+                    Span::styled("⊘ ", Theme::error())
+                };
+                let name_cell = if resource.is_publicly_accessible() {
+                    Cell::from(Line::from(vec![
+                        Span::styled(format!("{}⚠ ", mark), Theme::warning()),
+                        untagged,
+                        Span::styled(resource.name(), Theme::warning()),
+                    ]))
+                } else {
+                    Cell::from(Line::from(vec![Span::raw(mark), untagged, Span::raw(resource.name())]))
+                };
+
+                let mut name_cell = Some(name_cell);
+                let cells: Vec<Cell> = columns
+                    .iter()
+                    .map(|column| match column {
+                        ListColumn::Type => Cell::from(format!(
+                            "{} {}",
+                            resource_icon(resource.resource_type()),
+                            resource.resource_type().as_str()
+                        )),
+                        ListColumn::Name => name_cell.take().unwrap_or_default(),
+                        ListColumn::Id => Cell::from(resource.id()),
+                        ListColumn::State => match state.operation_for(resource.id()) {
+                            Some(job) => {
+                                Cell::from(job.handle.progress_label(chrono::Utc::now())).style(Theme::spinner())
+                            }
+                            None => Cell::from(resource.state().as_str()).style(state_style(resource.state())),
+                        },
+                        ListColumn::Health => match state.health_of(resource.as_ref()) {
+                            Some(health) => Cell::from(format!("{} {}", health.status.badge(), health.status.as_str()))
+                                .style(health_style(health.status)),
+                            None => Cell::from("-"),
+                        },
+                        ListColumn::Region => Cell::from(resource.region()),
+                        ListColumn::Provider => Cell::from(resource.provider().as_str()),
+                        ListColumn::Account => Cell::from(state.account_for_resource(resource_idx).unwrap_or("-")),
+                        ListColumn::Tags => Cell::from(tag_summary(resource.tags())),
+                        ListColumn::Cost => Cell::from(
+                            resource
+                                .cost_per_month()
+                                .map(|c| format!("${:.2}", c))
+                                .unwrap_or_else(|| "-".to_string()),
+                        ),
+                    })
+                    .collect();
 
                 let mut row = Row::new(cells).height(1);
                 if display_idx == state.selected_index {
@@ -90,16 +172,9 @@ pub async fn render_resource_list(frame: &mut Frame<'_>, area: Rect, state: &App
         })
         .collect();
 
-    let widths = [
-        Constraint::Length(18),
-        Constraint::Min(20),
-        Constraint::Min(18),
-        Constraint::Length(10),
-        Constraint::Length(12),
-        Constraint::Length(12),
-    ];
+    let widths: Vec<Constraint> = columns.iter().map(|column| column_width(*column)).collect();
 
-    let title = if state.filtered_resources.len() != resources.len() {
+    let mut title = if state.filtered_resources.len() != resources.len() {
         format!(
             "Resources ({} of {} shown)",
             state.filtered_resources.len(),
@@ -108,6 +183,24 @@ pub async fn render_resource_list(frame: &mut Frame<'_>, area: Rect, state: &App
     } else {
         format!("Resources ({})", resources.len())
     };
+    if !state.marked_resources.is_empty() {
+        title.push_str(&format!(" — {} marked", state.marked_resources.len()));
+    }
+    if state.offline {
+        title.push_str(" — cached, offline");
+    } else if state.showing_cached {
+        title.push_str(" — cached");
+    }
+    if let Some(ref group_by) = state.group_by {
+        title.push_str(&format!(" — grouped by {}", group_by.label()));
+    }
+    if list_rows.len() > height {
+        title.push_str(&format!(
+            " — row {} of {}",
+            state.selected_index.min(list_rows.len() - 1) + 1,
+            list_rows.len()
+        ));
+    }
 
     let table = Table::new(rows, widths)
         .header(header)
@@ -120,16 +213,151 @@ pub async fn render_resource_list(frame: &mut Frame<'_>, area: Rect, state: &App
         .column_spacing(1);
 
     frame.render_widget(table, table_area);
+
+    if list_rows.len() > height {
+        let mut scrollbar_state = ScrollbarState::new(list_rows.len()).position(state.selected_index);
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight),
+            table_area.inner(&Margin {
+                vertical: 1,
+                horizontal: 0,
+            }),
+            &mut scrollbar_state,
+        );
+    }
+
+    if state.show_confirmation {
+        render_confirmation_dialog(frame, centered_rect(60, 60, table_area), state);
+    } else if state.show_bulk_picker {
+        render_bulk_picker(frame, centered_rect(40, 40, table_area), state);
+    } else if state.show_column_picker {
+        render_column_picker(frame, centered_rect(40, 60, table_area), state);
+    }
+}
+
+/// Returns the rows to show so the selected one is on screen: the page of
+/// `height` rows holding it.
+fn visible_window(total: usize, selected: usize, height: usize) -> (usize, usize) {
+    if height == 0 {
+        return (0, 0);
+    }
+    let start = (selected.min(total.saturating_sub(1)) / height) * height;
+    (start, (start + height).min(total))
+}
+
+/// A group's header line: its name and size in the first column, and its
+/// monthly cost in the cost column if shown.
+fn group_row<'a>(columns: &[ListColumn], name: &str, count: usize, cost: f64, collapsed: bool) -> Row<'a> {
+    let marker = if collapsed { "▸" } else { "▾" };
+    let cells: Vec<Cell> = columns
+        .iter()
+        .enumerate()
+        .map(|(idx, column)| match column {
+            ListColumn::Cost => Cell::from(format!("${:.2}", cost)),
+            _ if idx == 0 => Cell::from(format!("{} {} ({})", marker, name, count)),
+            _ => Cell::from(""),
+        })
+        .collect();
+    Row::new(cells).height(1).style(Theme::title())
+}
+
+fn column_width(column: ListColumn) -> Constraint {
+    match column {
+        ListColumn::Type => Constraint::Length(18),
+        ListColumn::Name => Constraint::Min(20),
+        ListColumn::Id => Constraint::Min(18),
+        ListColumn::State => Constraint::Length(16),
+        ListColumn::Health => Constraint::Length(10),
+        ListColumn::Region => Constraint::Length(12),
+        ListColumn::Provider => Constraint::Length(10),
+        ListColumn::Account => Constraint::Length(14),
+        ListColumn::Tags => Constraint::Min(24),
+        ListColumn::Cost => Constraint::Length(12),
+    }
+}
+
+/// Tags as `key=value` pairs sorted by key.
+fn tag_summary(tags: &HashMap<String, String>) -> String {
+    if tags.is_empty() {
+        return "-".to_string();
+    }
+    let mut pairs: Vec<String> = tags.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+    pairs.sort();
+    pairs.join(", ")
+}
+
+fn render_column_picker(frame: &mut Frame, area: Rect, state: &AppState) {
+    let items: Vec<ListItem> = state
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(idx, (column, shown))| {
+            let selected = idx == state.selected_column;
+            let prefix = if selected { "> " } else { "  " };
+            let check = if *shown { "[x]" } else { "[ ]" };
+            let item = ListItem::new(format!("{}{} {}", prefix, check, column.as_str()));
+            if selected {
+                item.style(Theme::selected_row())
+            } else {
+                item
+            }
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Columns (↑↓ select, Space show/hide, [ ] move, ESC close)")
+            .style(Theme::border()),
+    );
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(list, area);
+}
+
+fn render_bulk_picker(frame: &mut Frame, area: Rect, state: &AppState) {
+    let items: Vec<ListItem> = state
+        .bulk_actions
+        .iter()
+        .enumerate()
+        .map(|(idx, action)| {
+            let selected = idx == state.selected_bulk_action;
+            let prefix = if selected { "> " } else { "  " };
+            let style = match (selected, action.is_destructive()) {
+                (true, true) => Theme::error(),
+                (true, false) => Theme::selected_row(),
+                (false, true) => Theme::warning(),
+                (false, false) => Style::default(),
+            };
+            ListItem::new(format!("{}{}", prefix, action.as_str())).style(style)
+        })
+        .collect();
+
+    let title = format!(
+        "{} marked (↑↓ select, Enter choose, ESC cancel)",
+        state.marked_resources.len()
+    );
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .style(Theme::border()),
+    );
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(list, area);
 }
 
 fn render_filter_input(frame: &mut Frame, area: Rect, state: &AppState) {
-    let filter_text = if state.is_filtering() {
+    let filter_text = if state.is_searching() {
+        format!("Search: {}█", state.search_text)
+    } else if state.is_filtering() {
         format!("Filter: {}█", state.filter_text)
     } else {
         format!("Filter: {}", state.filter_text)
     };
 
-    let style = if state.is_filtering() {
+    let style = if state.is_filtering() || state.is_searching() {
         Theme::filter_active()
     } else {
         Theme::filter_inactive()
@@ -138,7 +366,7 @@ fn render_filter_input(frame: &mut Frame, area: Rect, state: &AppState) {
     let input = Paragraph::new(filter_text).style(style).block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(if state.is_filtering() {
+            .border_style(if state.is_filtering() || state.is_searching() {
                 Theme::filter_active()
             } else {
                 Theme::border()
@@ -270,15 +498,42 @@ fn render_message(frame: &mut Frame, area: Rect, message: &str) {
 
 fn state_style(state: crate::core::ResourceState) -> Style {
     use crate::core::ResourceState;
-    use ratatui::style::Color;
 
+    let palette = Theme::palette();
     match state {
-        ResourceState::Running => Style::default().fg(Color::Green),
-        ResourceState::Stopped => Style::default().fg(Color::Yellow),
-        ResourceState::Terminated => Style::default().fg(Color::Red),
-        ResourceState::Pending | ResourceState::Starting => Style::default().fg(Color::Cyan),
-        ResourceState::Stopping => Style::default().fg(Color::Yellow),
-        ResourceState::Error => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-        ResourceState::Unknown => Style::default().fg(Color::Gray),
+        ResourceState::Running => Style::default().fg(palette.success),
+        ResourceState::Degraded => Style::default().fg(palette.warning).add_modifier(Modifier::BOLD),
+        ResourceState::Maintenance => Style::default().fg(palette.accent),
+        ResourceState::Stopped => Style::default().fg(palette.warning),
+        ResourceState::Terminated => Style::default().fg(palette.error),
+        ResourceState::Pending | ResourceState::Starting => Style::default().fg(palette.accent),
+        ResourceState::Stopping => Style::default().fg(palette.warning),
+        ResourceState::Deleting => Style::default().fg(palette.error),
+        ResourceState::Error => Style::default().fg(palette.error).add_modifier(Modifier::BOLD),
+        ResourceState::Unknown => Style::default().fg(palette.muted),
     }
-}
\ No newline at end of file
+}
+
+pub(crate) fn health_style(status: crate::core::HealthStatus) -> Style {
+    use crate::core::HealthStatus;
+
+    match status {
+        HealthStatus::Healthy => Theme::success(),
+        HealthStatus::Warning => Theme::warning(),
+        HealthStatus::Impaired => Theme::error(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visible_window() {
+        assert_eq!(visible_window(100, 0, 20), (0, 20));
+        assert_eq!(visible_window(100, 25, 20), (20, 40));
+        assert_eq!(visible_window(45, 44, 20), (40, 45));
+        assert_eq!(visible_window(5, 3, 20), (0, 5));
+        assert_eq!(visible_window(5, 3, 0), (0, 0));
+    }
+}
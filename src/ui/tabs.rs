@@ -14,8 +14,13 @@ pub fn render_tabs(frame: &mut Frame, area: Rect, state: &AppState) {
         .map(|tab| Line::from(tab.as_str()))
         .collect();
 
+    let title = match state.identity_summary() {
+        Some(summary) => format!("Nimbus — {}", summary),
+        None => "Nimbus".to_string(),
+    };
+
     let tabs = Tabs::new(tab_titles)
-        .block(Block::default().borders(Borders::ALL).title("Nimbus"))
+        .block(Block::default().borders(Borders::ALL).title(title))
         .select(state.active_tab.index())
         .style(Theme::tab_inactive())
         .highlight_style(Theme::tab_active());
@@ -34,6 +39,24 @@ pub async fn render_tab_content(frame: &mut Frame<'_>, area: Rect, state: &AppSt
         ViewMode::ResourceDetail => {
             crate::ui::detail::render_detail_view(frame, area, state).await;
         }
+        ViewMode::Records => {
+            crate::ui::records::render_records_view(frame, area, state).await;
+        }
+        ViewMode::Quotas => {
+            crate::ui::quotas::render_quotas_view(frame, area, state).await;
+        }
+        ViewMode::Waste => {
+            crate::ui::waste::render_waste_view(frame, area, state).await;
+        }
+        ViewMode::Compliance => {
+            crate::ui::compliance::render_compliance_view(frame, area, state).await;
+        }
+        ViewMode::Changes => {
+            crate::ui::changes::render_changes_view(frame, area, state).await;
+        }
+        ViewMode::History => {
+            crate::ui::history::render_history_view(frame, area, state).await;
+        }
     }
 }
 
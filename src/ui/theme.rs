@@ -1,95 +1,229 @@
+use std::str::FromStr;
+use std::sync::OnceLock;
+
 use ratatui::style::{Color, Modifier, Style};
+use crate::config::ThemeConfig;
 use crate::core::ResourceType;
+use crate::error::{NimbusError, Result};
+
+static PALETTE: OnceLock<Palette> = OnceLock::new();
+
+/// The colors every style is built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    /// Titles, table headers, the active tab, and the spinner
+    pub accent: Color,
+    pub text: Color,
+    /// Borders and unknown states
+    pub muted: Color,
+    /// Inactive tabs and the selected row
+    pub surface: Color,
+    /// Dialogs and the filter bar
+    pub background: Color,
+    pub status_bar: Color,
+    /// Key hints in help and shortcut lists
+    pub key: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub error: Color,
+}
+
+impl Palette {
+    pub fn dark() -> Self {
+        Self {
+            accent: Color::Cyan,
+            text: Color::White,
+            muted: Color::Gray,
+            surface: Color::DarkGray,
+            background: Color::Black,
+            status_bar: Color::Blue,
+            key: Color::Yellow,
+            success: Color::Green,
+            warning: Color::Yellow,
+            error: Color::Red,
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            accent: Color::Blue,
+            text: Color::Black,
+            muted: Color::DarkGray,
+            surface: Color::Gray,
+            background: Color::White,
+            status_bar: Color::Blue,
+            key: Color::Magenta,
+            success: Color::Green,
+            warning: Color::Rgb(175, 95, 0),
+            error: Color::Red,
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Self {
+            accent: Color::LightYellow,
+            text: Color::White,
+            muted: Color::White,
+            surface: Color::Blue,
+            background: Color::Black,
+            status_bar: Color::Blue,
+            key: Color::LightYellow,
+            success: Color::LightGreen,
+            warning: Color::LightYellow,
+            error: Color::LightRed,
+        }
+    }
+
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" | "high_contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// Builds the palette for a `[theme]` section: the named preset, with
+    /// any colors it sets replacing the preset's.
+    pub fn from_config(config: &ThemeConfig) -> Result<Self> {
+        let mut palette = Self::preset(&config.preset).ok_or_else(|| {
+            NimbusError::ConfigError(format!(
+                "Unknown theme preset '{}' (expected dark, light, or high-contrast)",
+                config.preset
+            ))
+        })?;
+
+        let slots = [
+            ("accent", &config.accent, &mut palette.accent),
+            ("text", &config.text, &mut palette.text),
+            ("muted", &config.muted, &mut palette.muted),
+            ("surface", &config.surface, &mut palette.surface),
+            ("background", &config.background, &mut palette.background),
+            ("status_bar", &config.status_bar, &mut palette.status_bar),
+            ("key", &config.key, &mut palette.key),
+            ("success", &config.success, &mut palette.success),
+            ("warning", &config.warning, &mut palette.warning),
+            ("error", &config.error, &mut palette.error),
+        ];
+        for (slot, value, color) in slots {
+            if let Some(value) = value {
+                *color = Color::from_str(value).map_err(|_| {
+                    NimbusError::ConfigError(format!("Invalid theme color {} = '{}'", slot, value))
+                })?;
+            }
+        }
+
+        Ok(palette)
+    }
+}
 
 pub struct Theme;
 
 impl Theme {
+    /// Sets the palette used for the rest of the run. Only the first call
+    /// takes effect; until then the dark preset is used.
+    pub fn init(palette: Palette) {
+        let _ = PALETTE.set(palette);
+    }
+
+    pub fn palette() -> &'static Palette {
+        PALETTE.get_or_init(Palette::dark)
+    }
+
     pub fn tab_active() -> Style {
+        let palette = Self::palette();
         Style::default()
-            .fg(Color::Black)
-            .bg(Color::Cyan)
+            .fg(palette.background)
+            .bg(palette.accent)
             .add_modifier(Modifier::BOLD)
     }
 
     pub fn tab_inactive() -> Style {
-        Style::default().fg(Color::White).bg(Color::DarkGray)
+        let palette = Self::palette();
+        Style::default().fg(palette.text).bg(palette.surface)
     }
 
     pub fn status_bar() -> Style {
-        Style::default().fg(Color::White).bg(Color::Blue)
+        Style::default().fg(Color::White).bg(Self::palette().status_bar)
     }
 
     pub fn border() -> Style {
-        Style::default().fg(Color::Gray)
+        Style::default().fg(Self::palette().muted)
     }
 
     pub fn title() -> Style {
         Style::default()
-            .fg(Color::Cyan)
+            .fg(Self::palette().accent)
             .add_modifier(Modifier::BOLD)
     }
 
     pub fn help_key() -> Style {
         Style::default()
-            .fg(Color::Yellow)
+            .fg(Self::palette().key)
             .add_modifier(Modifier::BOLD)
     }
 
     pub fn help_text() -> Style {
-        Style::default().fg(Color::White)
+        Style::default().fg(Self::palette().text)
     }
 
     pub fn table_header() -> Style {
         Style::default()
-            .fg(Color::Cyan)
+            .fg(Self::palette().accent)
             .add_modifier(Modifier::BOLD)
     }
 
     pub fn selected_row() -> Style {
         Style::default()
-            .bg(Color::DarkGray)
+            .bg(Self::palette().surface)
             .add_modifier(Modifier::BOLD)
     }
 
     pub fn error() -> Style {
         Style::default()
-            .fg(Color::Red)
+            .fg(Self::palette().error)
             .add_modifier(Modifier::BOLD)
     }
 
     pub fn success() -> Style {
         Style::default()
-            .fg(Color::Green)
+            .fg(Self::palette().success)
             .add_modifier(Modifier::BOLD)
     }
 
     pub fn warning() -> Style {
         Style::default()
-            .fg(Color::Yellow)
+            .fg(Self::palette().warning)
             .add_modifier(Modifier::BOLD)
     }
 
     pub fn filter_active() -> Style {
+        let palette = Self::palette();
         Style::default()
-            .fg(Color::Yellow)
-            .bg(Color::Black)
+            .fg(palette.key)
+            .bg(palette.background)
             .add_modifier(Modifier::BOLD)
     }
 
     pub fn filter_inactive() -> Style {
-        Style::default().fg(Color::White).bg(Color::Black)
+        let palette = Self::palette();
+        Style::default().fg(palette.text).bg(palette.background)
     }
 
     pub fn spinner() -> Style {
         Style::default()
-            .fg(Color::Cyan)
+            .fg(Self::palette().accent)
             .add_modifier(Modifier::BOLD)
     }
 
     // CHANGES: Added cache_age style
     pub fn cache_age() -> Style {
         Style::default()
-            .fg(Color::Yellow)
+            .fg(Self::palette().warning)
+    }
+
+    pub fn dialog() -> Style {
+        Style::default().bg(Self::palette().background)
     }
 }
 
@@ -120,4 +254,22 @@ mod tests {
         assert_eq!(resource_icon(ResourceType::LoadBalancer), "[ELB]");
         assert_eq!(resource_icon(ResourceType::DNS), "[R53]");
     }
+
+    #[test]
+    fn test_palette_from_config() {
+        let mut config = ThemeConfig::default();
+        assert_eq!(Palette::from_config(&config).unwrap(), Palette::dark());
+
+        config.preset = "high-contrast".to_string();
+        config.accent = Some("#ff8800".to_string());
+        let palette = Palette::from_config(&config).unwrap();
+        assert_eq!(palette.accent, Color::Rgb(0xff, 0x88, 0x00));
+        assert_eq!(palette.error, Palette::high_contrast().error);
+
+        config.error = Some("not-a-color".to_string());
+        assert!(Palette::from_config(&config).is_err());
+
+        config.preset = "solarized".to_string();
+        assert!(Palette::from_config(&config).is_err());
+    }
 }
\ No newline at end of file
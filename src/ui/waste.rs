@@ -0,0 +1,106 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::app::AppState;
+use crate::core::{potential_savings, savings_by_kind};
+use crate::ui::theme::Theme;
+
+pub async fn render_waste_view(frame: &mut Frame<'_>, area: Rect, state: &AppState) {
+    if state.waste.is_empty() {
+        let message = if state.loading {
+            "Scanning for idle resources..."
+        } else {
+            "No idle resources found"
+        };
+        let paragraph = Paragraph::new(vec![Line::from(""), Line::from(message)])
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Waste")
+                    .style(Theme::border()),
+            )
+            .style(Theme::help_text())
+            .alignment(ratatui::layout::Alignment::Center);
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let findings = state.waste_findings();
+    let by_kind = savings_by_kind(&findings);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(by_kind.len() as u16 + 2), Constraint::Min(5)])
+        .split(area);
+
+    let summary: Vec<Line> = by_kind
+        .iter()
+        .map(|(kind, count, cost)| {
+            Line::from(vec![
+                Span::styled(format!("${:>9.2}", cost), Theme::warning()),
+                Span::raw(format!("  {} × {}", count, kind.as_str())),
+            ])
+        })
+        .collect();
+    let summary_title = format!(
+        "Waste (potential savings ${:.2}/month)",
+        potential_savings(&findings)
+    );
+    let paragraph = Paragraph::new(summary).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(summary_title)
+            .style(Theme::border()),
+    );
+    frame.render_widget(paragraph, chunks[0]);
+
+    let header_cells = ["Provider", "Finding", "Name", "Region", "Detail", "Monthly"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Theme::table_header()));
+    let header = Row::new(header_cells).height(1).style(Theme::table_header());
+
+    let rows: Vec<Row> = state
+        .waste
+        .iter()
+        .map(|(provider, finding)| {
+            let cost = match finding.monthly_cost {
+                Some(cost) => format!("${:.2}", cost),
+                None => "-".to_string(),
+            };
+            Row::new(vec![
+                Cell::from(provider.clone()),
+                Cell::from(finding.kind.as_str()),
+                Cell::from(finding.name.clone()),
+                Cell::from(finding.region.clone()),
+                Cell::from(finding.detail.clone().unwrap_or_default()),
+                Cell::from(cost).style(Theme::warning()),
+            ])
+            .height(1)
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(10),
+        Constraint::Length(30),
+        Constraint::Percentage(25),
+        Constraint::Length(14),
+        Constraint::Min(16),
+        Constraint::Length(10),
+    ];
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Idle Resources ({})", state.waste.len()))
+                .style(Theme::border()),
+        )
+        .column_spacing(1);
+
+    frame.render_widget(table, chunks[1]);
+}
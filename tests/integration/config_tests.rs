@@ -23,17 +23,19 @@ region = "us-west-2"
 [ui]
 default_tab = "aws"
 auto_refresh = false
+confirm_destructive_actions = true
 
 [cache]
 enabled = false
+max_age_hours = 24
     "#;
     
     fs::write(&config_path, config_content).unwrap();
     
     let config = NimbusConfig::from_file(&config_path)?;
     
-    assert!(config.providers.aws.is_some());
-    let aws_config = config.providers.aws.unwrap();
+    assert_eq!(config.providers.aws.len(), 1);
+    let aws_config = &config.providers.aws[0];
     assert_eq!(aws_config.profile, Some("test".to_string()));
     assert_eq!(aws_config.region, "us-west-2");
     assert!(!config.ui.auto_refresh);
@@ -49,7 +51,7 @@ fn test_config_validation() {
     let result = config.validate();
     assert!(result.is_err());
     
-    config.providers.aws = Some(nimbus::config::AwsConfig::default());
+    config.providers.aws = vec![nimbus::config::AwsConfig::default()];
     
     let result = config.validate();
     assert!(result.is_ok());
@@ -58,24 +60,63 @@ fn test_config_validation() {
 #[test]
 fn test_config_merge() {
     let mut config1 = NimbusConfig::default();
-    config1.providers.aws = Some(nimbus::config::AwsConfig {
+    config1.providers.aws = vec![nimbus::config::AwsConfig {
         profile: Some("default".to_string()),
         region: "us-east-1".to_string(),
-        access_key_id: None,
-        secret_access_key: None,
-    });
+        ..Default::default()
+    }];
     
     let mut config2 = NimbusConfig::default();
-    config2.providers.aws = Some(nimbus::config::AwsConfig {
+    config2.providers.aws = vec![nimbus::config::AwsConfig {
         profile: Some("production".to_string()),
         region: "us-west-2".to_string(),
-        access_key_id: None,
-        secret_access_key: None,
-    });
+        ..Default::default()
+    }];
     
     let merged = config1.merge(config2);
     
-    let aws_config = merged.providers.aws.unwrap();
+    let aws_config = &merged.providers.aws[0];
     assert_eq!(aws_config.profile, Some("production".to_string()));
     assert_eq!(aws_config.region, "us-west-2");
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_config_multiple_aws_accounts() -> Result<()> {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+
+    let config_content = r#"
+[[providers.aws]]
+label = "prod"
+profile = "production"
+region = "us-east-1"
+
+[[providers.aws]]
+label = "dev"
+profile = "development"
+region = "eu-west-1"
+    "#;
+
+    fs::write(&config_path, config_content).unwrap();
+
+    let config = NimbusConfig::from_file(&config_path)?;
+
+    assert_eq!(config.providers.aws.len(), 2);
+    assert_eq!(config.providers.aws[0].display_name(), "prod");
+    assert_eq!(config.providers.aws[1].region, "eu-west-1");
+    assert!(config.validate().is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_config_duplicate_aws_labels_rejected() {
+    let mut config = NimbusConfig::default();
+    let account = nimbus::config::AwsConfig {
+        label: Some("prod".to_string()),
+        ..Default::default()
+    };
+    config.providers.aws = vec![account.clone(), account];
+
+    assert!(config.validate().is_err());
+}
@@ -1,6 +1,6 @@
 use nimbus::core::{
-    Action, CloudProvider, CloudResource, CostBreakdown, CostPeriod, Provider, ResourceState,
-    ResourceType,
+    collect_stream, send_batch, Action, ActionRequest, CloudProvider, CloudResource, CostBreakdown, CostPeriod,
+    ListingEvent, OperationHandle, OperationStatus, Provider, ResourceState, ResourceType,
 };
 use nimbus::error::{NimbusError, Result};
 use async_trait::async_trait;
@@ -148,8 +148,8 @@ impl CloudProvider for MockProvider {
             .ok_or_else(|| NimbusError::ResourceNotFound(id.to_string()))
     }
 
-    async fn execute_action(&self, _resource_id: &str, _action: Action) -> Result<()> {
-        Ok(())
+    async fn execute_action(&self, resource_id: &str, resource_type: ResourceType, request: &ActionRequest) -> Result<OperationHandle> {
+        Ok(OperationHandle::new(resource_id, resource_type, request))
     }
 
     async fn get_total_cost(&self, _period: CostPeriod) -> Result<f64> {
@@ -253,6 +253,29 @@ async fn test_list_resources_by_type() {
     assert_eq!(storage.len(), 0);
 }
 
+#[tokio::test]
+async fn test_stream_all_resources() {
+    let provider = create_mock_provider();
+    let resources = collect_stream(&provider).await.unwrap();
+    assert_eq!(resources.len(), 2);
+
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let compute = provider.list_resources_by_type(ResourceType::Compute);
+    send_batch(&sender, ResourceType::Compute, "EC2", compute).await;
+    let throttled = async { Err(NimbusError::provider("AWS", "throttled")) };
+    send_batch(&sender, ResourceType::Database, "RDS", throttled).await;
+    drop(sender);
+
+    assert!(matches!(receiver.recv().await, Some(ListingEvent::Started("EC2"))));
+    match receiver.recv().await {
+        Some(ListingEvent::Listed("EC2", batch)) => assert_eq!(batch[0].id(), "i-1234"),
+        _ => panic!("Expected the EC2 batch"),
+    }
+    assert!(matches!(receiver.recv().await, Some(ListingEvent::Started("RDS"))));
+    assert!(matches!(receiver.recv().await, Some(ListingEvent::Failed("RDS", ResourceType::Database, _))));
+    assert!(receiver.recv().await.is_none());
+}
+
 #[tokio::test]
 async fn test_get_resource() {
     let provider = create_mock_provider();
@@ -272,6 +295,29 @@ async fn test_get_resource_not_found() {
     }
 }
 
+#[tokio::test]
+async fn test_poll_operation() {
+    let provider = create_mock_provider();
+
+    let stop = provider
+        .execute_action("i-1234", ResourceType::Compute, &Action::Stop.into())
+        .await
+        .unwrap();
+    assert_eq!(
+        provider.poll_operation(&stop).await.unwrap(),
+        OperationStatus::InProgress(ResourceState::Running)
+    );
+
+    let start = provider
+        .execute_action("i-1234", ResourceType::Compute, &Action::Start.into())
+        .await
+        .unwrap();
+    assert_eq!(provider.poll_operation(&start).await.unwrap(), OperationStatus::Completed);
+
+    let terminate = OperationHandle::new("i-gone", ResourceType::Compute, &Action::Terminate.into());
+    assert_eq!(provider.poll_operation(&terminate).await.unwrap(), OperationStatus::Completed);
+}
+
 #[tokio::test]
 async fn test_get_total_cost() {
     let provider = create_mock_provider();